@@ -0,0 +1,99 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Budget alerts delivered as chat messages to the admin.
+//!
+//! [`ChannelBudgetNotifier`] receives threshold crossings from the
+//! [`BudgetTracker`](blufio_cost::BudgetTracker) and sends each one to the
+//! configured admin chat. The tracker calls notifiers while holding its lock,
+//! so delivery happens on a spawned task.
+
+use std::sync::Arc;
+
+use blufio_core::ChannelAdapter;
+use blufio_core::types::OutboundMessage;
+use blufio_cost::{BudgetAlert, BudgetNotifier};
+use tracing::warn;
+
+/// Sends budget alerts to one chat over a channel adapter.
+pub struct ChannelBudgetNotifier {
+    channel: Arc<dyn ChannelAdapter + Send + Sync>,
+    channel_name: String,
+    chat_id: String,
+}
+
+impl ChannelBudgetNotifier {
+    /// Creates a notifier sending to `chat_id` on the channel named
+    /// `channel_name`.
+    pub fn new(
+        channel: Arc<dyn ChannelAdapter + Send + Sync>,
+        channel_name: String,
+        chat_id: String,
+    ) -> Self {
+        Self {
+            channel,
+            channel_name,
+            chat_id,
+        }
+    }
+
+    fn message(&self, alert: &BudgetAlert) -> OutboundMessage {
+        OutboundMessage {
+            session_id: None,
+            channel: self.channel_name.clone(),
+            content: format!("Budget alert: {alert}."),
+            reply_to: None,
+            parse_mode: None,
+            metadata: Some(serde_json::json!({ "chat_id": self.chat_id }).to_string()),
+            idempotency_key: None,
+        }
+    }
+}
+
+impl BudgetNotifier for ChannelBudgetNotifier {
+    fn notify(&self, alert: BudgetAlert) {
+        let out = self.message(&alert);
+        let channel = self.channel.clone();
+        tokio::spawn(async move {
+            if let Err(e) = channel.send(out).await {
+                warn!(error = %e, "failed to send budget alert");
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blufio_cost::BudgetPeriod;
+    use blufio_test_utils::MockChannel;
+
+    #[tokio::test]
+    async fn alert_is_sent_to_admin_chat() {
+        let channel = Arc::new(MockChannel::new());
+        let notifier = ChannelBudgetNotifier::new(channel.clone(), "telegram".into(), "42".into());
+
+        notifier.notify(BudgetAlert {
+            period: BudgetPeriod::Monthly,
+            threshold_pct: 80.0,
+            spent_usd: 40.0,
+            cap_usd: 50.0,
+        });
+        let mut sent = Vec::new();
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+            sent = channel.sent_messages().await;
+            if !sent.is_empty() {
+                break;
+            }
+        }
+
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].channel, "telegram");
+        assert_eq!(
+            sent[0].content,
+            "Budget alert: 80% of the monthly budget used ($40.00 of $50.00)."
+        );
+        assert!(sent[0].metadata.as_deref().unwrap().contains("\"42\""));
+    }
+}
@@ -15,7 +15,7 @@ use tokio::sync::{Mutex, mpsc};
 use tracing::{info, warn};
 
 use blufio_core::BlufioError;
-use blufio_core::error::{ChannelErrorKind, ErrorContext};
+use blufio_core::error::{ChannelErrorKind, ErrorCode, ErrorContext};
 use blufio_core::traits::adapter::PluginAdapter;
 use blufio_core::traits::channel::ChannelAdapter;
 use blufio_core::types::{
@@ -70,6 +70,38 @@ impl ChannelMultiplexer {
         self.pending_channels.push((name, channel));
     }
 
+    /// Find the connected channel an outbound message belongs to.
+    ///
+    /// Matches the message's channel field first, then the `source_channel`
+    /// metadata, and finally falls back to the only channel if exactly one
+    /// is connected.
+    fn route(&self, msg: &OutboundMessage) -> Option<&Arc<dyn ChannelAdapter + Send + Sync>> {
+        if let Some((_, channel)) = self
+            .connected_channels
+            .iter()
+            .find(|(name, _)| *name == msg.channel)
+        {
+            return Some(channel);
+        }
+
+        if let Some(ref meta_str) = msg.metadata
+            && let Ok(meta) = serde_json::from_str::<serde_json::Value>(meta_str)
+            && let Some(source) = meta.get("source_channel").and_then(|v| v.as_str())
+            && let Some((_, channel)) = self
+                .connected_channels
+                .iter()
+                .find(|(name, _)| name == source)
+        {
+            return Some(channel);
+        }
+
+        if self.connected_channels.len() == 1 {
+            return Some(&self.connected_channels[0].1);
+        }
+
+        None
+    }
+
     /// Number of channels registered (pending + connected).
     pub fn channel_count(&self) -> usize {
         self.pending_channels.len() + self.connected_channels.len()
@@ -256,7 +288,7 @@ impl ChannelAdapter for ChannelMultiplexer {
                             }
                         }
                         Err(e) => {
-                            if e.to_string().contains("closed") {
+                            if e.code() == ErrorCode::ChannelClosed {
                                 info!(
                                     channel = %channel_name,
                                     "channel closed, stopping receive task"
@@ -284,39 +316,41 @@ impl ChannelAdapter for ChannelMultiplexer {
     }
 
     async fn send(&self, msg: OutboundMessage) -> Result<MessageId, BlufioError> {
-        // Route to the correct channel based on the message's channel field
-        // or source_channel metadata.
-        let target_channel = &msg.channel;
-
-        // Try to find the target channel.
-        for (name, channel) in self.connected_channels.iter() {
-            if name == target_channel {
-                return channel.send(msg).await;
+        match self.route(&msg) {
+            Some(channel) => channel.send(msg).await,
+            None => {
+                warn!(
+                    target = %msg.channel,
+                    "no matching channel found for outbound message"
+                );
+                Ok(MessageId("unknown".to_string()))
             }
         }
+    }
 
-        // Fall back to source_channel from metadata.
-        if let Some(ref meta_str) = msg.metadata
-            && let Ok(meta) = serde_json::from_str::<serde_json::Value>(meta_str)
-            && let Some(source) = meta.get("source_channel").and_then(|v| v.as_str())
-        {
-            for (name, channel) in self.connected_channels.iter() {
-                if name == source {
-                    return channel.send(msg).await;
-                }
-            }
+    async fn send_partial(&self, msg: OutboundMessage) -> Result<(), BlufioError> {
+        match self.route(&msg) {
+            Some(channel) => channel.send_partial(msg).await,
+            None => Ok(()),
         }
+    }
 
-        // If only one channel, use it as default.
-        if self.connected_channels.len() == 1 {
-            return self.connected_channels[0].1.send(msg).await;
+    async fn send_voice(
+        &self,
+        msg: OutboundMessage,
+        audio: Vec<u8>,
+        content_type: &str,
+    ) -> Result<MessageId, BlufioError> {
+        match self.route(&msg) {
+            Some(channel) => channel.send_voice(msg, audio, content_type).await,
+            None => {
+                warn!(
+                    target = %msg.channel,
+                    "no matching channel found for outbound voice message"
+                );
+                Ok(MessageId("unknown".to_string()))
+            }
         }
-
-        warn!(
-            target = %target_channel,
-            "no matching channel found for outbound message"
-        );
-        Ok(MessageId("unknown".to_string()))
     }
 
     async fn receive(&self) -> Result<InboundMessage, BlufioError> {
@@ -49,6 +49,8 @@ pub struct DelegationRouter {
     budget_tracker: Arc<tokio::sync::Mutex<BudgetTracker>>,
     router: Arc<ModelRouter>,
     timeout: Duration,
+    /// Deadline for each specialist tool invocation (None = unlimited).
+    tool_timeout: Option<Duration>,
 }
 
 impl DelegationRouter {
@@ -93,9 +95,17 @@ impl DelegationRouter {
             budget_tracker,
             router,
             timeout: Duration::from_secs(timeout_secs),
+            tool_timeout: None,
         }
     }
 
+    /// Sets the per-tool deadline for specialists, normally the primary
+    /// agent's `skill.tool_timeout_secs`.
+    pub fn with_tool_timeout(mut self, tool_timeout: Option<Duration>) -> Self {
+        self.tool_timeout = tool_timeout;
+        self
+    }
+
     /// Delegate a task to a named specialist agent.
     ///
     /// Creates an Ed25519-signed request, spawns an ephemeral specialist
@@ -193,8 +203,14 @@ impl DelegationRouter {
             fallback_chain: Vec::new(),
             event_bus: None,
             injection_pipeline: None,
+            provider_limiter: None,
             boundary_manager: None,
             channel_interactive: true,
+            tool_timeout: self.tool_timeout,
+            max_parallel_tools: 1,
+            pii_redactor: None,
+            personas: Default::default(),
+            persona: None,
         });
 
         // 5. Build inbound message from the delegation request
@@ -487,6 +503,7 @@ mod tests {
         let storage_config = StorageConfig {
             database_path: db_path.to_string_lossy().to_string(),
             wal_mode: true,
+            ..Default::default()
         };
         let storage = blufio_storage::SqliteStorage::new(storage_config);
         storage.initialize().await.unwrap();
@@ -515,6 +532,7 @@ mod tests {
             daily_budget_usd: None,
             monthly_budget_usd: None,
             track_tokens: true,
+            ..Default::default()
         };
         Arc::new(tokio::sync::Mutex::new(BudgetTracker::new(&cost_config)))
     }
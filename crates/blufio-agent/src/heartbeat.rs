@@ -77,6 +77,7 @@ impl HeartbeatRunner {
             daily_budget_usd: None,
             monthly_budget_usd: Some(config.monthly_budget_usd),
             track_tokens: true,
+            ..Default::default()
         };
         let budget_tracker = BudgetTracker::new(&heartbeat_cost_config);
 
@@ -366,6 +367,7 @@ mod tests {
             daily_budget_usd: None,
             monthly_budget_usd: Some(10.0),
             track_tokens: true,
+            ..Default::default()
         };
         let mut tracker = BudgetTracker::new(&config);
 
@@ -0,0 +1,471 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Interceptor chain around provider calls.
+//!
+//! [`InterceptedProvider`] wraps a [`ProviderAdapter`] and runs each
+//! [`ProviderInterceptor`] around every `complete` and `stream` call.
+//! Interceptors see the request in the order they were added and the
+//! response (or each stream chunk) in reverse order, so the first one added
+//! is the outermost layer. Any interceptor may fail the call.
+//!
+//! Built-in interceptors: [`MetricsInterceptor`] (request and token counts),
+//! [`RedactionInterceptor`] (PII removed from outgoing text) and
+//! [`ModerationInterceptor`] (blocked prompts and replies fail the call).
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use async_trait::async_trait;
+use blufio_core::error::BlufioError;
+use blufio_core::types::{
+    AdapterType, ContentBlock, HealthStatus, ProviderRequest, ProviderResponse,
+    ProviderStreamChunk, TokenUsage,
+};
+use blufio_core::{ModerationAdapter, PluginAdapter, ProviderAdapter};
+use futures::{Stream, StreamExt};
+use tracing::debug;
+
+use crate::moderation::{ModerationStage, moderate};
+
+type ChunkStream = Pin<Box<dyn Stream<Item = Result<ProviderStreamChunk, BlufioError>> + Send>>;
+
+/// Cross-cutting behavior around provider calls.
+///
+/// Every hook defaults to a no-op, so an interceptor only implements the
+/// ones it needs.
+#[async_trait]
+pub trait ProviderInterceptor: Send + Sync {
+    /// Name used in logs.
+    fn name(&self) -> &str;
+
+    /// Runs before the request is sent; may modify it.
+    async fn on_request(&self, _request: &mut ProviderRequest) -> Result<(), BlufioError> {
+        Ok(())
+    }
+
+    /// Runs after a `complete` call succeeds; may modify the response.
+    async fn on_response(
+        &self,
+        _request: &ProviderRequest,
+        _response: &mut ProviderResponse,
+    ) -> Result<(), BlufioError> {
+        Ok(())
+    }
+
+    /// Runs on each chunk of a `stream` call; may modify the chunk.
+    fn on_stream_chunk(&self, _chunk: &mut ProviderStreamChunk) -> Result<(), BlufioError> {
+        Ok(())
+    }
+}
+
+/// A provider with an interceptor chain around its calls.
+pub struct InterceptedProvider {
+    inner: Arc<dyn ProviderAdapter + Send + Sync>,
+    interceptors: Vec<Arc<dyn ProviderInterceptor>>,
+}
+
+impl InterceptedProvider {
+    /// Wraps `inner` with an empty chain.
+    pub fn new(inner: Arc<dyn ProviderAdapter + Send + Sync>) -> Self {
+        Self {
+            inner,
+            interceptors: Vec::new(),
+        }
+    }
+
+    /// Adds `interceptor` inside the ones already added.
+    pub fn with_interceptor(mut self, interceptor: Arc<dyn ProviderInterceptor>) -> Self {
+        self.interceptors.push(interceptor);
+        self
+    }
+
+    /// Returns the number of interceptors in the chain.
+    pub fn len(&self) -> usize {
+        self.interceptors.len()
+    }
+
+    /// Returns true if the chain is empty.
+    pub fn is_empty(&self) -> bool {
+        self.interceptors.is_empty()
+    }
+
+    async fn intercept_request(&self, request: &mut ProviderRequest) -> Result<(), BlufioError> {
+        for interceptor in &self.interceptors {
+            interceptor.on_request(request).await?;
+            debug!(
+                interceptor = interceptor.name(),
+                "provider request intercepted"
+            );
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PluginAdapter for InterceptedProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn version(&self) -> semver::Version {
+        self.inner.version()
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        self.inner.adapter_type()
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus, BlufioError> {
+        self.inner.health_check().await
+    }
+
+    async fn shutdown(&self) -> Result<(), BlufioError> {
+        self.inner.shutdown().await
+    }
+}
+
+#[async_trait]
+impl ProviderAdapter for InterceptedProvider {
+    async fn complete(
+        &self,
+        mut request: ProviderRequest,
+    ) -> Result<ProviderResponse, BlufioError> {
+        self.intercept_request(&mut request).await?;
+        let mut response = self.inner.complete(request.clone()).await?;
+        for interceptor in self.interceptors.iter().rev() {
+            interceptor.on_response(&request, &mut response).await?;
+        }
+        Ok(response)
+    }
+
+    async fn stream(&self, mut request: ProviderRequest) -> Result<ChunkStream, BlufioError> {
+        self.intercept_request(&mut request).await?;
+        let stream = self.inner.stream(request).await?;
+        if self.interceptors.is_empty() {
+            return Ok(stream);
+        }
+        let interceptors = self.interceptors.clone();
+        Ok(Box::pin(stream.map(move |chunk| {
+            let mut chunk = chunk?;
+            for interceptor in interceptors.iter().rev() {
+                interceptor.on_stream_chunk(&mut chunk)?;
+            }
+            Ok(chunk)
+        })))
+    }
+}
+
+/// Counts provider requests and the tokens they used.
+#[derive(Default)]
+pub struct MetricsInterceptor {
+    requests: AtomicU64,
+    input_tokens: AtomicU64,
+    output_tokens: AtomicU64,
+}
+
+impl MetricsInterceptor {
+    /// Creates an interceptor with all counts at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests sent so far.
+    pub fn requests(&self) -> u64 {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    /// Input and output tokens reported by the provider so far.
+    pub fn tokens(&self) -> (u64, u64) {
+        (
+            self.input_tokens.load(Ordering::Relaxed),
+            self.output_tokens.load(Ordering::Relaxed),
+        )
+    }
+
+    fn record_usage(&self, usage: &TokenUsage) {
+        self.input_tokens
+            .fetch_add(u64::from(usage.input_tokens), Ordering::Relaxed);
+        self.output_tokens
+            .fetch_add(u64::from(usage.output_tokens), Ordering::Relaxed);
+    }
+}
+
+#[async_trait]
+impl ProviderInterceptor for MetricsInterceptor {
+    fn name(&self) -> &str {
+        "metrics"
+    }
+
+    async fn on_request(&self, _request: &mut ProviderRequest) -> Result<(), BlufioError> {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn on_response(
+        &self,
+        _request: &ProviderRequest,
+        response: &mut ProviderResponse,
+    ) -> Result<(), BlufioError> {
+        self.record_usage(&response.usage);
+        Ok(())
+    }
+
+    fn on_stream_chunk(&self, chunk: &mut ProviderStreamChunk) -> Result<(), BlufioError> {
+        if let Some(usage) = &chunk.usage {
+            self.record_usage(usage);
+        }
+        Ok(())
+    }
+}
+
+/// Removes PII from the text of outgoing requests, so it never reaches the
+/// provider.
+#[derive(Debug, Default)]
+pub struct RedactionInterceptor;
+
+#[async_trait]
+impl ProviderInterceptor for RedactionInterceptor {
+    fn name(&self) -> &str {
+        "redaction"
+    }
+
+    async fn on_request(&self, request: &mut ProviderRequest) -> Result<(), BlufioError> {
+        for block in request
+            .messages
+            .iter_mut()
+            .flat_map(|m| m.content.iter_mut())
+        {
+            if let ContentBlock::Text { text } = block {
+                *text = blufio_security::redact_pii(text);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Fails provider calls whose latest user text or whose reply the
+/// moderation backend blocks.
+///
+/// Only `complete` replies are checked; streamed replies are moderated by
+/// the agent loop once complete.
+pub struct ModerationInterceptor {
+    moderator: Arc<dyn ModerationAdapter + Send + Sync>,
+}
+
+impl ModerationInterceptor {
+    /// Creates an interceptor backed by `moderator`.
+    pub fn new(moderator: Arc<dyn ModerationAdapter + Send + Sync>) -> Self {
+        Self { moderator }
+    }
+}
+
+#[async_trait]
+impl ProviderInterceptor for ModerationInterceptor {
+    fn name(&self) -> &str {
+        "moderation"
+    }
+
+    async fn on_request(&self, request: &mut ProviderRequest) -> Result<(), BlufioError> {
+        let latest_user_text = request
+            .messages
+            .iter()
+            .rev()
+            .find(|m| m.role == "user")
+            .into_iter()
+            .flat_map(|m| m.content.iter())
+            .find_map(|block| match block {
+                ContentBlock::Text { text } => Some(text.as_str()),
+                _ => None,
+            });
+        if let Some(text) = latest_user_text
+            && let Some(reason) =
+                moderate(self.moderator.as_ref(), text, ModerationStage::Input).await
+        {
+            return Err(BlufioError::Security(format!(
+                "request blocked by moderation: {reason}"
+            )));
+        }
+        Ok(())
+    }
+
+    async fn on_response(
+        &self,
+        _request: &ProviderRequest,
+        response: &mut ProviderResponse,
+    ) -> Result<(), BlufioError> {
+        if let Some(reason) = moderate(
+            self.moderator.as_ref(),
+            &response.content,
+            ModerationStage::Output,
+        )
+        .await
+        {
+            return Err(BlufioError::Security(format!(
+                "response blocked by moderation: {reason}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blufio_core::types::ProviderMessage;
+    use blufio_test_utils::MockProvider;
+    use std::sync::Mutex;
+
+    /// Records each hook it runs in a shared log.
+    struct Recorder {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl ProviderInterceptor for Recorder {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn on_request(&self, _request: &mut ProviderRequest) -> Result<(), BlufioError> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{}:request", self.name));
+            Ok(())
+        }
+
+        async fn on_response(
+            &self,
+            _request: &ProviderRequest,
+            response: &mut ProviderResponse,
+        ) -> Result<(), BlufioError> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{}:response:{}", self.name, response.content));
+            Ok(())
+        }
+    }
+
+    /// Rewrites the model and system prompt of every request.
+    struct Rewriter;
+
+    #[async_trait]
+    impl ProviderInterceptor for Rewriter {
+        fn name(&self) -> &str {
+            "rewriter"
+        }
+
+        async fn on_request(&self, request: &mut ProviderRequest) -> Result<(), BlufioError> {
+            request.model = "rewritten-model".to_string();
+            request.system_prompt = Some("be brief".to_string());
+            Ok(())
+        }
+    }
+
+    fn request(text: &str) -> ProviderRequest {
+        ProviderRequest {
+            model: "test-model".to_string(),
+            system_prompt: None,
+            system_blocks: None,
+            messages: vec![ProviderMessage {
+                role: "user".to_string(),
+                content: vec![ContentBlock::Text {
+                    text: text.to_string(),
+                }],
+            }],
+            max_tokens: 100,
+            stream: false,
+            tools: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn interceptors_run_in_order_around_the_provider() {
+        let mock = Arc::new(MockProvider::with_responses(vec!["hi there".to_string()]));
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let provider = InterceptedProvider::new(mock.clone())
+            .with_interceptor(Arc::new(Recorder {
+                name: "outer",
+                log: log.clone(),
+            }))
+            .with_interceptor(Arc::new(Rewriter))
+            .with_interceptor(Arc::new(Recorder {
+                name: "inner",
+                log: log.clone(),
+            }));
+
+        let response = provider.complete(request("hello")).await.unwrap();
+        assert_eq!(response.content, "hi there");
+        assert_eq!(response.model, "rewritten-model");
+
+        let sent = mock.requests();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].model, "rewritten-model");
+        assert_eq!(sent[0].system_prompt.as_deref(), Some("be brief"));
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "outer:request",
+                "inner:request",
+                "inner:response:hi there",
+                "outer:response:hi there",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn metrics_and_redaction_apply_to_complete_and_stream() {
+        let mock = Arc::new(MockProvider::new());
+        let metrics = Arc::new(MetricsInterceptor::new());
+        let provider = InterceptedProvider::new(mock.clone())
+            .with_interceptor(metrics.clone())
+            .with_interceptor(Arc::new(RedactionInterceptor));
+
+        provider
+            .complete(request("mail jane.doe@example.com"))
+            .await
+            .unwrap();
+        let chunks: Vec<_> = provider
+            .stream(request("hello"))
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert!(chunks.iter().all(|c| c.is_ok()));
+
+        assert_eq!(metrics.requests(), 2);
+        assert!(metrics.tokens().0 >= 10);
+        let sent = mock.requests();
+        assert!(matches!(
+            &sent[0].messages[0].content[0],
+            ContentBlock::Text { text } if text == "mail [EMAIL]"
+        ));
+    }
+
+    #[tokio::test]
+    async fn failing_request_interceptor_skips_the_provider() {
+        struct Deny;
+
+        #[async_trait]
+        impl ProviderInterceptor for Deny {
+            fn name(&self) -> &str {
+                "deny"
+            }
+
+            async fn on_request(&self, _request: &mut ProviderRequest) -> Result<(), BlufioError> {
+                Err(BlufioError::Security("denied".to_string()))
+            }
+        }
+
+        let mock = Arc::new(MockProvider::new());
+        let provider = InterceptedProvider::new(mock.clone()).with_interceptor(Arc::new(Deny));
+        assert!(provider.complete(request("hello")).await.is_err());
+        assert!(provider.stream(request("hello")).await.is_err());
+        assert_eq!(mock.call_count(), 0);
+    }
+}
@@ -11,30 +11,46 @@
 //! - Enforces budget caps and records costs
 //! - Handles graceful shutdown
 
+pub mod budget_alert;
 pub mod channel_mux;
 pub mod context;
 pub mod delegation;
 pub mod heartbeat;
+pub mod interceptor;
+pub mod limiter;
+pub mod moderation;
+pub mod outbound;
+pub mod redaction;
+pub mod retention;
 #[cfg(unix)]
 pub mod sdnotify;
 pub mod session;
 pub mod shutdown;
+pub mod synthesis;
+pub mod tool_summary;
+pub mod transcription;
+pub mod typing;
 
 pub use delegation::{DelegationRouter, DelegationTool};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
 
 use blufio_config::model::BlufioConfig;
 use blufio_context::ContextEngine;
-use blufio_core::error::BlufioError;
+use blufio_core::error::{BlufioError, ErrorCode};
+use blufio_core::traits::adapter::PluginAdapter;
 use blufio_core::types::{
-    ContentBlock, InboundMessage, OutboundMessage, ProviderMessage, ProviderRequest,
-    ProviderStreamChunk, Session, StreamEventType, TokenUsage, ToolUseData,
+    ContentBlock, InboundMessage, MessageContent, OutboundMessage, ProviderMessage,
+    ProviderRequest, ProviderStreamChunk, Session, StreamEventType, TokenUsage, ToolLoopState,
+    ToolUseData, TtsRequest,
+};
+use blufio_core::{
+    ChannelAdapter, ModerationAdapter, ProviderAdapter, StorageAdapter, TranscriptionAdapter,
+    TtsAdapter,
 };
-use blufio_core::{ChannelAdapter, ProviderAdapter, StorageAdapter};
 use blufio_cost::{BudgetTracker, CostLedger};
 use blufio_memory::{MemoryExtractor, MemoryProvider};
 use blufio_router::ModelRouter;
@@ -46,14 +62,18 @@ pub use heartbeat::HeartbeatRunner;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
-use crate::session::{SessionActor, SessionActorConfig};
+use crate::limiter::ProviderLimiter;
+use crate::moderation::ModerationStage;
+use crate::redaction::{PiiRedactor, RedactionField};
+use crate::session::{SessionActor, SessionActorConfig, persona_from_metadata};
+use crate::typing::TypingRefresher;
 
 /// The main agent loop that coordinates message flow between channel, provider, and storage.
 ///
 /// Receives inbound messages from a channel adapter, routes them to per-session
 /// actors, streams LLM responses back, and manages session lifecycle.
 pub struct AgentLoop {
-    channel: Box<dyn ChannelAdapter + Send + Sync>,
+    channel: Arc<dyn ChannelAdapter + Send + Sync>,
     provider: Arc<dyn ProviderAdapter + Send + Sync>,
     storage: Arc<dyn StorageAdapter + Send + Sync>,
     context_engine: Arc<ContextEngine>,
@@ -86,6 +106,26 @@ pub struct AgentLoop {
     /// Shared injection defense pipeline for all sessions.
     injection_pipeline:
         Option<Arc<tokio::sync::Mutex<blufio_injection::pipeline::InjectionPipeline>>>,
+    /// Watchdog limit for a whole turn (None = disabled).
+    turn_timeout: Option<Duration>,
+    /// Global cap on concurrent provider requests (None = unlimited).
+    provider_limiter: Option<Arc<ProviderLimiter>>,
+    /// Interval between typing-indicator refreshes (None = send once).
+    typing_refresh: Option<Duration>,
+    /// Deadline for each tool invocation (None = unlimited).
+    tool_timeout: Option<Duration>,
+    /// Speech-to-text backend for inbound voice messages (None = disabled).
+    transcriber: Option<Arc<dyn TranscriptionAdapter + Send + Sync>>,
+    /// Text-to-speech backend for spoken replies to voice messages (None = disabled).
+    synthesizer: Option<Arc<dyn TtsAdapter + Send + Sync>>,
+    /// Content moderation for input and final replies (None = allow everything).
+    moderator: Option<Arc<dyn ModerationAdapter + Send + Sync>>,
+    /// PII redaction before persistence and memory extraction (None = store as-is).
+    pii_redactor: Option<Arc<PiiRedactor>>,
+    /// Retry queue for replies the channel failed to accept (None = drop them).
+    outbound: Option<Arc<outbound::OutboundQueue>>,
+    /// WASM skill runtimes to cancel on shutdown.
+    skill_cancel_handles: Vec<blufio_skill::SkillCancelHandle>,
 }
 
 impl AgentLoop {
@@ -111,6 +151,38 @@ impl AgentLoop {
             "agent loop initialized"
         );
 
+        let turn_timeout = (config.agent.turn_timeout_secs > 0)
+            .then(|| Duration::from_secs(config.agent.turn_timeout_secs));
+        let provider_limiter = (config.agent.max_concurrent_provider_requests > 0).then(|| {
+            Arc::new(ProviderLimiter::new(
+                config.agent.max_concurrent_provider_requests,
+            ))
+        });
+        let typing_refresh = (config.agent.typing_refresh_secs > 0)
+            .then(|| Duration::from_secs(config.agent.typing_refresh_secs));
+        let tool_timeout = (config.skill.tool_timeout_secs > 0)
+            .then(|| Duration::from_secs(config.skill.tool_timeout_secs));
+
+        let channel: Arc<dyn ChannelAdapter + Send + Sync> = Arc::from(channel);
+        if let Some(chat_id) = config.cost.alert_chat_id.clone() {
+            let channel_name = config
+                .cost
+                .alert_channel
+                .clone()
+                .unwrap_or_else(|| channel.name().to_string());
+            budget_tracker.lock().await.set_notifier(Arc::new(
+                budget_alert::ChannelBudgetNotifier::new(channel.clone(), channel_name, chat_id),
+            ));
+        }
+
+        let outbound = config.outbound.enabled.then(|| {
+            Arc::new(outbound::OutboundQueue::new(
+                storage.clone(),
+                channel.clone(),
+                &config.outbound,
+            ))
+        });
+
         Ok(Self {
             channel,
             provider,
@@ -132,6 +204,16 @@ impl AgentLoop {
             provider_registry: None,
             fallback_chain: Vec::new(),
             injection_pipeline: None,
+            turn_timeout,
+            provider_limiter,
+            typing_refresh,
+            tool_timeout,
+            transcriber: None,
+            synthesizer: None,
+            moderator: None,
+            pii_redactor: None,
+            outbound,
+            skill_cancel_handles: Vec::new(),
         })
     }
 
@@ -179,14 +261,81 @@ impl AgentLoop {
         self.injection_pipeline = Some(pipeline);
     }
 
+    /// Overrides the per-turn watchdog limit (None disables it).
+    pub fn set_turn_timeout(&mut self, timeout: Option<Duration>) {
+        self.turn_timeout = timeout;
+    }
+
+    /// Shares a provider concurrency limiter with other agent loops (None disables it).
+    ///
+    /// Replaces the per-loop limiter built from
+    /// `agent.max_concurrent_provider_requests`.
+    pub fn set_provider_limiter(&mut self, limiter: Option<Arc<ProviderLimiter>>) {
+        self.provider_limiter = limiter;
+    }
+
+    /// Overrides the typing-indicator refresh interval (None sends it only once).
+    pub fn set_typing_refresh(&mut self, interval: Option<Duration>) {
+        self.typing_refresh = interval;
+    }
+
+    /// Sets the speech-to-text backend that transcribes inbound voice messages.
+    pub fn set_transcriber(&mut self, transcriber: Arc<dyn TranscriptionAdapter + Send + Sync>) {
+        self.transcriber = Some(transcriber);
+    }
+
+    /// Sets the text-to-speech backend used to answer voice messages in kind.
+    pub fn set_synthesizer(&mut self, synthesizer: Arc<dyn TtsAdapter + Send + Sync>) {
+        self.synthesizer = Some(synthesizer);
+    }
+
+    /// Sets the moderation backend checking user input and final replies.
+    ///
+    /// With a moderator installed, replies are no longer streamed to the
+    /// channel as they arrive: the complete response is checked first.
+    pub fn set_moderator(&mut self, moderator: Arc<dyn ModerationAdapter + Send + Sync>) {
+        self.moderator = Some(moderator);
+    }
+
+    /// Sets the PII redactor applied before persistence and memory extraction.
+    ///
+    /// Only sessions created afterwards use it.
+    pub fn set_pii_redactor(&mut self, redactor: Arc<PiiRedactor>) {
+        self.pii_redactor = Some(redactor);
+    }
+
+    /// Registers a WASM skill runtime whose in-flight skills are cancelled
+    /// when the loop shuts down.
+    pub fn add_skill_cancel_handle(&mut self, handle: blufio_skill::SkillCancelHandle) {
+        self.skill_cancel_handles.push(handle);
+    }
+
     /// Runs the main agent loop until the cancellation token is triggered.
     ///
     /// The loop:
     /// 1. Waits for inbound messages from the channel
     /// 2. Routes each message to a session actor
     /// 3. Streams the LLM response back to the channel
-    /// 4. On cancellation, drains active sessions before exiting
+    /// 4. On cancellation, cancels running WASM skills and drains active sessions before exiting
     pub async fn run(&mut self, cancel: CancellationToken) -> Result<(), BlufioError> {
+        if let Err(e) = self.recover_tool_loops().await {
+            error!(error = %e, "failed to recover interrupted tool loops");
+        }
+
+        if let Some(queue) = self.outbound.clone() {
+            let cancel = cancel.clone();
+            tokio::spawn(async move { queue.run(cancel).await });
+        }
+
+        let retention = &self.config.retention;
+        if retention.enabled && retention.purge_interval_secs > 0 {
+            tokio::spawn(retention::run_purge_loop(
+                self.storage.clone(),
+                Duration::from_secs(retention.purge_interval_secs),
+                cancel.clone(),
+            ));
+        }
+
         info!("agent loop running");
 
         loop {
@@ -205,7 +354,7 @@ impl AgentLoop {
                             #[cfg(feature = "prometheus")]
                             blufio_prometheus::record_classified_error(&e);
                             // If the channel is closed, break out of the loop.
-                            if e.to_string().contains("closed") {
+                            if e.code() == ErrorCode::ChannelClosed {
                                 break;
                             }
                         }
@@ -218,17 +367,275 @@ impl AgentLoop {
             }
         }
 
+        // Interrupt running skills so sessions are not held up by them.
+        for handle in &self.skill_cancel_handles {
+            handle.cancel();
+        }
+
         // Drain active sessions.
         shutdown::drain_sessions(&self.sessions, Duration::from_secs(30)).await;
 
-        // Close storage.
-        self.storage.close().await?;
+        // Close storage, bounded so a hung checkpoint cannot block shutdown.
+        shutdown::close_storage_with_timeout(self.storage.close(), shutdown::STORAGE_CLOSE_TIMEOUT)
+            .await?;
 
         info!("agent loop stopped");
         Ok(())
     }
 
-    /// Handles a single inbound message: resolves session, calls LLM, sends response.
+    /// Sends a reply, handing it to the outbound retry queue if the send fails.
+    async fn send_or_queue(&self, out: OutboundMessage) {
+        let Some(queue) = &self.outbound else {
+            if let Err(e) = self.channel.send(out).await {
+                error!(error = %e, "failed to send response message");
+            }
+            return;
+        };
+        if let Err(e) = self.channel.send(out.clone()).await {
+            warn!(error = %e, "failed to send response message, queued for retry");
+            if let Err(e) = queue.enqueue(&out).await {
+                error!(error = %e, "failed to queue response message");
+            }
+        }
+    }
+
+    /// Repairs tool loops interrupted by a crash or restart.
+    ///
+    /// A turn that stopped between recording its tool calls and finishing
+    /// leaves a `tool_loop_state` record behind, and its session may hold
+    /// `tool_use` calls with no matching `tool_result`. Each such call gets a
+    /// synthetic error result so the next provider call sees a valid
+    /// conversation. Returns the number of synthetic results written.
+    ///
+    /// Called by [`run`](Self::run) before receiving messages.
+    pub async fn recover_tool_loops(&self) -> Result<usize, BlufioError> {
+        let mut repaired = 0;
+        for state in self.storage.list_tool_loop_states().await? {
+            repaired += self.repair_tool_loop(&state).await?;
+        }
+        Ok(repaired)
+    }
+
+    /// Closes the tool loop of a turn that ended early (an error or the
+    /// watchdog), so the session's next turn sees a valid conversation.
+    async fn close_interrupted_tool_loop(&self, channel: &str, sender_id: &str) {
+        let Some(session_id) = self
+            .sessions
+            .get(&session_key(channel, sender_id))
+            .map(|actor| actor.session_id().to_string())
+        else {
+            return;
+        };
+        let result = async {
+            for state in self.storage.list_tool_loop_states().await? {
+                if state.session_id == session_id {
+                    self.repair_tool_loop(&state).await?;
+                }
+            }
+            Ok::<_, BlufioError>(())
+        }
+        .await;
+        if let Err(e) = result {
+            error!(
+                session_id = session_id.as_str(),
+                error = %e,
+                "failed to close interrupted tool loop"
+            );
+        }
+    }
+
+    /// Writes a synthetic error result for each unanswered call in `state`,
+    /// then clears the record. Returns the number of results written.
+    async fn repair_tool_loop(&self, state: &ToolLoopState) -> Result<usize, BlufioError> {
+        let history = self.storage.get_messages(&state.session_id, None).await?;
+        let answered: HashSet<String> = history
+            .iter()
+            .filter_map(|m| stored_tool_result_id(&m.content))
+            .collect();
+
+        let missing: Vec<_> = state
+            .pending_tool_uses
+            .iter()
+            .filter(|tool_use| !answered.contains(&tool_use.id))
+            .map(|tool_use| {
+                tool_result_message(
+                    &state.session_id,
+                    &tool_use.id,
+                    INTERRUPTED_TOOL_RESULT,
+                    true,
+                )
+            })
+            .collect();
+        self.storage.insert_messages(&missing).await?;
+
+        self.storage
+            .clear_tool_loop_state(&state.session_id)
+            .await?;
+        warn!(
+            session_id = state.session_id.as_str(),
+            iteration = state.iteration,
+            "recovered interrupted tool loop"
+        );
+        Ok(missing.len())
+    }
+
+    /// Handles a single inbound message under the per-turn watchdog.
+    ///
+    /// The whole turn (every provider stream and tool execution in the tool
+    /// loop) is bounded by `turn_timeout`. This is separate from per-call
+    /// timeouts: many individually-fast steps can still add up to an unbounded
+    /// turn. When the limit is exceeded the turn future is dropped, which
+    /// cancels the in-flight provider stream and tool calls, and the user is
+    /// sent a timeout notice instead.
+    ///
+    /// Called by [`run`](Self::run) for each received message; exposed so test
+    /// harnesses can drive a single turn without the channel receive loop.
+    pub async fn handle_inbound(&mut self, inbound: InboundMessage) -> Result<(), BlufioError> {
+        // Nothing to answer: skip session resolution and the LLM call.
+        if is_blank_message(&inbound.content) {
+            debug!(
+                sender_id = inbound.sender_id.as_str(),
+                channel = inbound.channel.as_str(),
+                "ignoring empty inbound message"
+            );
+            if !self.config.agent.prompt_on_empty_message {
+                return Ok(());
+            }
+            let session_id = self
+                .sessions
+                .get(&session_key(&inbound.channel, &inbound.sender_id))
+                .map(|actor| actor.session_id().to_string());
+            let out = OutboundMessage {
+                session_id,
+                channel: inbound.channel,
+                content: EMPTY_MESSAGE_PROMPT.to_string(),
+                reply_to: None,
+                parse_mode: None,
+                metadata: inbound.metadata,
+                idempotency_key: None,
+            };
+            if let Err(e) = self.channel.send(out).await {
+                error!(error = %e, "failed to send empty message prompt");
+            }
+            return Ok(());
+        }
+
+        // `/reset` ends the conversation; the next message starts a new session.
+        if is_reset_command(&inbound.content) {
+            return self.reset_session(inbound).await;
+        }
+
+        let sender_id = inbound.sender_id.clone();
+        let channel_name = inbound.channel.clone();
+
+        let Some(limit) = self.turn_timeout else {
+            let result = self.process_inbound(inbound).await;
+            if result.is_err() {
+                self.close_interrupted_tool_loop(&channel_name, &sender_id)
+                    .await;
+            }
+            return result;
+        };
+
+        let metadata = inbound.metadata.clone();
+
+        match tokio::time::timeout(limit, self.process_inbound(inbound)).await {
+            Ok(result) => {
+                if result.is_err() {
+                    self.close_interrupted_tool_loop(&channel_name, &sender_id)
+                        .await;
+                }
+                result
+            }
+            Err(_) => {
+                warn!(
+                    sender_id = sender_id.as_str(),
+                    channel = channel_name.as_str(),
+                    timeout_secs = limit.as_secs_f64(),
+                    "turn exceeded watchdog limit, aborting"
+                );
+                self.close_interrupted_tool_loop(&channel_name, &sender_id)
+                    .await;
+                let session_id = self
+                    .sessions
+                    .get(&session_key(&channel_name, &sender_id))
+                    .map(|actor| actor.session_id().to_string());
+                let out = OutboundMessage {
+                    session_id,
+                    channel: channel_name,
+                    content: turn_timeout_message(limit),
+                    reply_to: None,
+                    parse_mode: None,
+                    metadata,
+                    idempotency_key: None,
+                };
+                if let Err(e) = self.channel.send(out).await {
+                    error!(error = %e, "failed to send turn timeout message");
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Closes the sender's active session and confirms the reset.
+    ///
+    /// The next message from the sender creates a fresh session, so earlier
+    /// messages no longer reach the context window. Long-term memories are
+    /// kept.
+    async fn reset_session(&mut self, inbound: InboundMessage) -> Result<(), BlufioError> {
+        let key = session_key(&inbound.channel, &inbound.sender_id);
+        let session_id = match self.sessions.remove(&key) {
+            Some(actor) => Some(actor.session_id().to_string()),
+            None => self
+                .storage
+                .list_sessions(Some("active"))
+                .await?
+                .into_iter()
+                .find(|s| {
+                    s.channel == inbound.channel
+                        && s.user_id.as_deref() == Some(inbound.sender_id.as_str())
+                })
+                .map(|s| s.id),
+        };
+        #[cfg(feature = "prometheus")]
+        blufio_prometheus::set_active_sessions(self.sessions.len() as f64);
+
+        if let Some(ref id) = session_id {
+            self.storage.update_session_state(id, "closed").await?;
+            info!(
+                session_id = id.as_str(),
+                sender_id = inbound.sender_id.as_str(),
+                channel = inbound.channel.as_str(),
+                "session reset by user"
+            );
+            if let Some(ref bus) = self.event_bus {
+                bus.publish(blufio_bus::events::BusEvent::Session(
+                    blufio_bus::events::SessionEvent::Closed {
+                        event_id: blufio_bus::events::new_event_id(),
+                        timestamp: blufio_bus::events::now_timestamp(),
+                        session_id: id.clone(),
+                    },
+                ))
+                .await;
+            }
+        }
+
+        let out = OutboundMessage {
+            session_id,
+            channel: inbound.channel,
+            content: RESET_CONFIRMATION.to_string(),
+            reply_to: None,
+            parse_mode: None,
+            metadata: inbound.metadata,
+            idempotency_key: None,
+        };
+        if let Err(e) = self.channel.send(out).await {
+            error!(error = %e, "failed to send reset confirmation");
+        }
+        Ok(())
+    }
+
+    /// Processes a single inbound message: resolves session, calls LLM, sends response.
     ///
     /// If a `BudgetExhausted` error is returned from the session actor, sends
     /// the budget message to the user instead of logging it as an error.
@@ -239,11 +646,51 @@ impl AgentLoop {
     /// After the LLM responds, if the response contains `tool_use` blocks,
     /// executes the tools, sends tool_result back, and re-calls the LLM
     /// in a loop (capped at [`MAX_TOOL_ITERATIONS`]).
-    async fn handle_inbound(&mut self, inbound: InboundMessage) -> Result<(), BlufioError> {
+    async fn process_inbound(&mut self, mut inbound: InboundMessage) -> Result<(), BlufioError> {
+        #[cfg(feature = "prometheus")]
+        let turn_start = std::time::Instant::now();
+
+        // Voice messages from any channel become text before reaching a session.
+        let inbound_was_voice = transcription::is_voice(&inbound);
+        if let Some(ref transcriber) = self.transcriber {
+            transcription::transcribe_inbound(transcriber.as_ref(), &mut inbound).await;
+        }
+
         let sender_id = inbound.sender_id.clone();
         let channel_name = inbound.channel.clone();
         let metadata = inbound.metadata.clone();
 
+        // Blocked input never reaches a session or the LLM.
+        if let Some(ref moderator) = self.moderator
+            && let Some(text) = moderation::inbound_text(&inbound.content)
+            && moderation::moderate(moderator.as_ref(), text, ModerationStage::Input)
+                .await
+                .is_some()
+        {
+            info!(
+                sender_id = sender_id.as_str(),
+                channel = channel_name.as_str(),
+                "inbound message blocked by moderation"
+            );
+            let session_id = self
+                .sessions
+                .get(&session_key(&channel_name, &sender_id))
+                .map(|actor| actor.session_id().to_string());
+            let out = OutboundMessage {
+                session_id,
+                channel: channel_name,
+                content: self.config.moderation.refusal_message.clone(),
+                reply_to: None,
+                parse_mode: None,
+                metadata,
+                idempotency_key: None,
+            };
+            if let Err(e) = self.channel.send(out).await {
+                error!(error = %e, "failed to send moderation refusal");
+            }
+            return Ok(());
+        }
+
         // Notify heartbeat runner of incoming message (for skip-when-unchanged detection).
         if let Some(ref runner) = self.heartbeat_runner {
             runner.notify_message_received().await;
@@ -266,31 +713,70 @@ impl AgentLoop {
         #[cfg(feature = "prometheus")]
         blufio_prometheus::record_message(&channel_name);
 
-        // Resolve or create session.
+        // Resolve or create session. Actors are keyed by channel + sender.
+        let session_key = session_key(&channel_name, &sender_id);
         let session_id = self
             .resolve_or_create_session(&sender_id, &channel_name)
             .await?;
 
+        // Claim the session for this turn; an overlapping turn for the same
+        // session waits here until this one finishes. The guard is held until
+        // the reply is persisted, or dropped with an early return.
+        let turn = session_actor(&mut self.sessions, &session_key)?
+            .begin_turn()
+            .await;
+
+        // `/persona` is answered by the session itself, without the LLM.
+        if let MessageContent::Text(ref text) = inbound.content
+            && let Some(reply) = session_actor(&mut self.sessions, &session_key)?
+                .handle_persona_command(text)
+                .await?
+        {
+            let content = match pending_heartbeat {
+                Some(hb) => format!("{hb}\n\n---\n\n{reply}"),
+                None => reply,
+            };
+            let out = OutboundMessage {
+                session_id: Some(session_id),
+                channel: channel_name,
+                content,
+                reply_to: None,
+                parse_mode: None,
+                metadata,
+                idempotency_key: None,
+            };
+            if let Err(e) = self.channel.send(out).await {
+                error!(error = %e, "failed to send persona reply");
+            }
+            return Ok(());
+        }
+
         // Extract chat_id from metadata for Telegram responses.
         let chat_id = extract_chat_id_from_metadata(&metadata).unwrap_or_default();
+        let reply_key = reply_idempotency_key(&channel_name, &chat_id, &inbound.id);
 
-        // Send typing indicator.
-        if !chat_id.is_empty()
-            && let Err(e) = self.channel.send_typing(&chat_id).await
-        {
-            debug!(error = %e, "failed to send typing indicator");
+        // Send typing indicator and keep it alive until the first reply goes out.
+        let mut typing: Option<TypingRefresher> = None;
+        if !chat_id.is_empty() && self.channel.capabilities().supports_typing {
+            if let Err(e) = self.channel.send_typing(&chat_id).await {
+                debug!(error = %e, "failed to send typing indicator");
+            }
+            typing = self.typing_refresh.map(|interval| {
+                TypingRefresher::spawn(self.channel.clone(), chat_id.clone(), interval)
+            });
         }
 
         // Get the session actor.
-        let actor = self.sessions.get_mut(&session_id).ok_or_else(|| {
-            BlufioError::Internal(format!("session actor not found for {session_id}"))
-        })?;
+        let actor = session_actor(&mut self.sessions, &session_key)?;
 
         // Capture start time for latency tracking.
         let _llm_start = std::time::Instant::now();
 
         // Handle message: persist user message, check budget, assemble context, get stream.
         let stream_result = actor.handle_message(inbound).await;
+        // Conversation buffer for tool follow-ups: the initial request's
+        // messages, extended with each iteration's tool calls and results.
+        let mut conversation = actor.take_last_request_messages();
 
         // Check for BudgetExhausted -- send user-facing message instead of error.
         let mut stream = match stream_result {
@@ -307,7 +793,9 @@ impl AgentLoop {
                     reply_to: None,
                     parse_mode: None,
                     metadata: metadata.clone(),
+                    idempotency_key: reply_key.clone(),
                 };
+                drop(typing);
                 if let Err(e) = self.channel.send(out).await {
                     error!(error = %e, "failed to send budget exhausted message");
                 }
@@ -316,34 +804,85 @@ impl AgentLoop {
             Err(e) => return Err(e),
         };
 
+        // Model label for the latency histograms, and the arrival time of the
+        // first streamed token.
+        #[cfg(feature = "prometheus")]
+        let latency_model = session_actor(&mut self.sessions, &session_key)?
+            .last_routing_decision()
+            .map(|d| d.actual_model.clone())
+            .unwrap_or_else(|| self.config.anthropic.default_model.clone());
+        #[cfg(feature = "prometheus")]
+        let first_token = Arc::new(std::sync::OnceLock::new());
+        #[cfg(feature = "prometheus")]
+        {
+            let first_token = first_token.clone();
+            stream = Box::pin(stream.inspect(move |chunk| {
+                if let Ok(c) = chunk
+                    && c.event_type == StreamEventType::ContentBlockDelta
+                {
+                    let _ = first_token.set(std::time::Instant::now());
+                }
+            }));
+        }
+
         // Consume the initial stream and enter the tool loop.
         let max_iterations = {
-            let actor = self.sessions.get(&session_id).ok_or_else(|| {
-                BlufioError::Internal(format!("session actor not found for {session_id}"))
-            })?;
+            let actor = session_actor(&mut self.sessions, &session_key)?;
             actor.max_tool_iterations()
         };
 
         let mut full_response = String::new();
         let mut usage: Option<TokenUsage> = None;
         let mut sent_message_id: Option<String> = None;
-        let supports_edit = self.channel.capabilities().supports_edit;
+        let mut tool_loop_recorded = false;
+        // Why the last provider response stopped, for the empty-reply notice.
+        let mut final_stop_reason: Option<String> = None;
+        // Output moderation needs the whole reply before anything is sent.
+        let moderate_output = self.moderator.is_some();
+        let supports_edit = self.channel.capabilities().supports_edit && !moderate_output;
+
+        let max_continuations = self.config.agent.max_continuations;
+        let mut continuations = 0;
+
+        // Display-only summaries of the tool calls, on channels that opt in.
+        let show_tool_calls = self.config.agent.show_tool_calls.contains(&channel_name);
+        let mut tool_summaries: Vec<String> = Vec::new();
 
         // Tool loop: consume stream, check for tool_use, execute, re-call LLM.
-        for iteration in 0..=max_iterations {
-            let (text, stream_usage, tool_uses, stop_reason) = consume_stream(&mut stream).await;
+        // Continuations of a reply cut off at max_tokens are not iterations.
+        let mut iteration = 0;
+        loop {
+            let delta_template = OutboundMessage {
+                session_id: Some(session_id.clone()),
+                channel: channel_name.clone(),
+                content: String::new(),
+                reply_to: None,
+                parse_mode: None,
+                metadata: metadata.clone(),
+                idempotency_key: None,
+            };
+            let (text, stream_usage, tool_uses, stop_reason) = if moderate_output {
+                consume_stream(&mut stream).await
+            } else {
+                consume_stream_forwarding(&mut stream, self.channel.as_ref(), &delta_template).await
+            };
 
             // Record end-to-end latency on first stream consumption.
             #[cfg(feature = "prometheus")]
-            if iteration == 0 {
+            if iteration == 0 && continuations == 0 {
                 let latency = _llm_start.elapsed().as_secs_f64();
-                blufio_prometheus::record_latency(latency);
+                blufio_prometheus::record_latency(&latency_model, "chat", latency);
+                if let Some(at) = first_token.get() {
+                    let ttft = at.duration_since(_llm_start).as_secs_f64();
+                    blufio_prometheus::record_ttft(&latency_model, "chat", ttft);
+                }
             }
 
             full_response.push_str(&text);
             if let Some(u) = stream_usage {
                 usage = Some(u);
             }
+            final_stop_reason.clone_from(&stop_reason);
 
             // Stream text to channel (edit-in-place or send).
             if !text.is_empty() && supports_edit {
@@ -356,7 +895,9 @@ impl AgentLoop {
                             reply_to: None,
                             parse_mode: None,
                             metadata: metadata.clone(),
+                            idempotency_key: reply_key.clone(),
                         };
+                        typing.take();
                         match self.channel.send(out).await {
                             Ok(mid) => sent_message_id = Some(mid.0),
                             Err(e) => warn!(error = %e, "failed to send initial message"),
@@ -374,6 +915,45 @@ impl AgentLoop {
                 }
             }
 
+            // Ask for the rest of a reply cut off at max_tokens. The parts
+            // accumulate in full_response and are persisted as one message.
+            if tool_uses.is_empty()
+                && !text.is_empty()
+                && stop_reason.as_deref() == Some("max_tokens")
+                && continuations < max_continuations
+            {
+                continuations += 1;
+                info!(
+                    session_id = %session_id,
+                    continuation = continuations,
+                    "response hit max_tokens, requesting continuation"
+                );
+                // Persisting the reply records only the last part's cost.
+                if let Some(u) = usage.take() {
+                    session_actor(&mut self.sessions, &session_key)?
+                        .record_response_cost(&u)
+                        .await?;
+                }
+                conversation.push(ProviderMessage {
+                    role: "assistant".to_string(),
+                    content: vec![ContentBlock::Text { text: text.clone() }],
+                });
+                conversation.push(ProviderMessage {
+                    role: "user".to_string(),
+                    content: vec![ContentBlock::Text {
+                        text: CONTINUE_PROMPT.to_string(),
+                    }],
+                });
+                blufio_context::guard::enforce_request_limits(
+                    &mut conversation,
+                    self.context_engine.request_limits(),
+                )?;
+                stream = self
+                    .stream_follow_up(&session_key, &session_id, conversation.clone(), stream)
+                    .await?;
+                continue;
+            }
+
             // Check if we have tool_use blocks to execute.
             let has_tool_use = !tool_uses.is_empty() || stop_reason.as_deref() == Some("tool_use");
 
@@ -400,60 +980,50 @@ impl AgentLoop {
                 "executing tool calls"
             );
 
-            let actor = self.sessions.get_mut(&session_id).ok_or_else(|| {
-                BlufioError::Internal(format!("session actor not found for {session_id}"))
-            })?;
+            let actor = session_actor(&mut self.sessions, &session_key)?;
 
             // Persist the assistant message with tool_use content (text + tool calls).
-            actor.persist_response(&text, usage.clone()).await?;
+            // Includes any continued parts that preceded the tool calls.
+            actor
+                .persist_response(&full_response, usage.clone())
+                .await?;
+
+            // Record the pending calls so a restart before their results are
+            // persisted can be repaired (see `recover_tool_loops`).
+            self.storage
+                .save_tool_loop_state(&session_id, iteration as u32, &tool_uses)
+                .await?;
+            tool_loop_recorded = true;
 
             let tool_results = actor.execute_tools(&tool_uses).await?;
-
-            // Build tool_result messages and persist them as user messages.
-            // Each tool_result is a separate content block in a single user message.
-            for (tool_use_id, output) in &tool_results {
-                let now = chrono::Utc::now().to_rfc3339();
-                let result_content = serde_json::json!({
-                    "type": "tool_result",
-                    "tool_use_id": tool_use_id,
-                    "content": output.content,
-                    "is_error": output.is_error,
-                });
-                let msg = blufio_core::types::Message {
-                    id: uuid::Uuid::new_v4().to_string(),
-                    session_id: session_id.clone(),
-                    role: "user".to_string(),
-                    content: result_content.to_string(),
-                    token_count: None,
-                    metadata: Some(serde_json::json!({"tool_result": true}).to_string()),
-                    created_at: now,
-                    classification: Default::default(),
-                };
-                self.storage.insert_message(&msg).await?;
+            if show_tool_calls {
+                for (tu, (_, output)) in tool_uses.iter().zip(&tool_results) {
+                    tool_summaries.push(tool_summary::summarize_call(tu, output));
+                }
             }
 
-            // Re-assemble context for the follow-up call by getting history from storage.
-            // The persisted messages now include the tool_use and tool_result messages.
-            let history = self.storage.get_messages(&session_id, Some(50)).await?;
-            let mut messages: Vec<ProviderMessage> = history
-                .iter()
-                .map(|m| ProviderMessage {
-                    role: m.role.clone(),
-                    content: vec![ContentBlock::Text {
-                        text: m.content.clone(),
-                    }],
-                })
-                .collect();
-
-            // Replace the last assistant + user tool_result messages with properly
-            // structured content blocks (the storage only has text representations).
-            // Pop the tool_result user messages and the assistant tool_use message.
-            let tool_result_count = tool_results.len();
-            for _ in 0..(tool_result_count + 1) {
-                messages.pop();
+            // Build tool_result messages and persist them as user messages,
+            // in one batch so a crash never leaves half the results stored.
+            let mut result_msgs = Vec::with_capacity(tool_results.len());
+            for (tool_use_id, output) in &tool_results {
+                let mut msg =
+                    tool_result_message(&session_id, tool_use_id, &output.content, output.is_error);
+                if let Some(ref redactor) = self.pii_redactor {
+                    // Redact the output itself, not the JSON envelope around it.
+                    let content = redactor
+                        .redact_for_storage(RedactionField::ToolResult, &msg.id, &output.content)
+                        .await;
+                    msg.content =
+                        tool_result_message(&session_id, tool_use_id, &content, output.is_error)
+                            .content;
+                }
+                result_msgs.push(msg);
             }
+            self.storage.insert_messages(&result_msgs).await?;
 
-            // Re-add the assistant message with structured tool_use content blocks.
+            // Extend the in-turn conversation with the structured tool calls
+            // and their results. Storage keeps only text representations, so
+            // it is written for durability but never read back within a turn.
             let mut assistant_blocks: Vec<ContentBlock> = Vec::new();
             if !text.is_empty() {
                 assistant_blocks.push(ContentBlock::Text { text: text.clone() });
@@ -465,12 +1035,12 @@ impl AgentLoop {
                     input: tu.input.clone(),
                 });
             }
-            messages.push(ProviderMessage {
+            conversation.push(ProviderMessage {
                 role: "assistant".to_string(),
                 content: assistant_blocks,
             });
 
-            // Re-add the user message with structured tool_result content blocks.
+            // All tool results of one iteration go in a single user message.
             let result_blocks: Vec<ContentBlock> = tool_results
                 .iter()
                 .map(|(tool_use_id, output)| ContentBlock::ToolResult {
@@ -479,66 +1049,38 @@ impl AgentLoop {
                     is_error: if output.is_error { Some(true) } else { None },
                 })
                 .collect();
-            messages.push(ProviderMessage {
+            conversation.push(ProviderMessage {
                 role: "user".to_string(),
                 content: result_blocks,
             });
-
-            // Build follow-up ProviderRequest.
-            let actor = self.sessions.get(&session_id).ok_or_else(|| {
-                BlufioError::Internal(format!("session actor not found for {session_id}"))
-            })?;
-
-            let tool_defs = {
-                let registry = actor.tool_registry().read().await;
-                if !registry.is_empty() {
-                    Some(registry.tool_definitions())
-                } else {
-                    None
-                }
-            };
-
-            // P3 fix: Use the model from the initial routing decision for this session,
-            // not the hardcoded default_model. This ensures tool follow-ups use the same
-            // model tier that was selected for the initial request (e.g., Opus for complex queries).
-            let (follow_up_model, follow_up_max_tokens) = match actor.last_routing_decision() {
-                Some(decision) => {
-                    debug!(
-                        session_id = %session_id,
-                        model = %decision.actual_model,
-                        "tool follow-up using routed model"
-                    );
-                    (decision.actual_model.clone(), decision.max_tokens)
-                }
-                None => {
-                    debug!(
-                        session_id = %session_id,
-                        model = %self.config.anthropic.default_model,
-                        "tool follow-up using default model (no routing decision)"
-                    );
-                    (
-                        self.config.anthropic.default_model.clone(),
-                        self.config.anthropic.max_tokens,
-                    )
-                }
-            };
-
-            let follow_up_request = ProviderRequest {
-                model: follow_up_model,
-                system_prompt: None,
-                system_blocks: None,
-                messages,
-                max_tokens: follow_up_max_tokens,
-                stream: true,
-                tools: tool_defs,
-            };
+            blufio_context::guard::enforce_request_limits(
+                &mut conversation,
+                self.context_engine.request_limits(),
+            )?;
 
             // Re-call the LLM with tool results.
-            stream = self.provider.stream(follow_up_request).await?;
+            stream = self
+                .stream_follow_up(&session_key, &session_id, conversation.clone(), stream)
+                .await?;
 
             // Reset for next iteration -- clear text accumulator but keep the
             // full_response for the final display.
             full_response.clear();
+            iteration += 1;
+        }
+
+        // A blocked reply is replaced by the refusal, both in the channel and
+        // in the persisted history.
+        if let Some(ref moderator) = self.moderator
+            && moderation::moderate(moderator.as_ref(), &full_response, ModerationStage::Output)
+                .await
+                .is_some()
+        {
+            info!(
+                session_id = session_id.as_str(),
+                "response blocked by moderation"
+            );
+            full_response = self.config.moderation.refusal_message.clone();
         }
 
         // Build the final display content, optionally prepending:
@@ -554,14 +1096,11 @@ impl AgentLoop {
 
         // Check for budget downgrade notification from the session actor.
         {
-            let actor = self.sessions.get(&session_id).ok_or_else(|| {
-                BlufioError::Internal(format!("session actor not found for {session_id}"))
-            })?;
+            let actor = session_actor(&mut self.sessions, &session_key)?;
             if let Some(decision) = actor.last_routing_decision()
                 && decision.downgraded
             {
-                let short_name =
-                    blufio_router::ModelRouter::short_model_name(&decision.actual_model);
+                let short_name = self.router.short_model_name(&decision.actual_model);
                 let note = format!(
                     "_(Using {} -- budget at {:.0}%)_\n\n",
                     short_name,
@@ -578,7 +1117,18 @@ impl AgentLoop {
             }
         }
 
-        display_response.push_str(&full_response);
+        if full_response.trim().is_empty() {
+            // Without this the user gets no reply at all.
+            warn!(
+                session_id = session_id.as_str(),
+                stop_reason = final_stop_reason.as_deref().unwrap_or("none"),
+                "provider returned an empty response"
+            );
+            display_response.push_str(empty_response_notice(final_stop_reason.as_deref()));
+        } else {
+            display_response.push_str(&full_response);
+        }
+        tool_summary::append_summary(&mut display_response, &tool_summaries);
 
         // If we haven't sent anything yet (non-edit channel or no delta arrived), send now.
         if sent_message_id.is_none() && !display_response.is_empty() {
@@ -589,10 +1139,10 @@ impl AgentLoop {
                 reply_to: None,
                 parse_mode: None,
                 metadata: metadata.clone(),
+                idempotency_key: reply_key.clone(),
             };
-            if let Err(e) = self.channel.send(out).await {
-                error!(error = %e, "failed to send response message");
-            }
+            typing.take();
+            self.send_or_queue(out).await;
         } else if sent_message_id.is_some() && !display_response.is_empty() {
             // Final edit to ensure the complete response is shown.
             if let Some(mid) = &sent_message_id
@@ -605,6 +1155,34 @@ impl AgentLoop {
             }
         }
 
+        #[cfg(feature = "prometheus")]
+        blufio_prometheus::record_turn_latency(
+            &latency_model,
+            "chat",
+            turn_start.elapsed().as_secs_f64(),
+        );
+
+        // Answer a voice message in kind with a spoken copy of the reply.
+        if self.synthesizer.is_some()
+            && synthesis::reply_in_kind(
+                inbound_was_voice,
+                self.channel.capabilities().supports_voice,
+                &full_response,
+                self.config.tts.max_chars,
+            )
+        {
+            let out = OutboundMessage {
+                session_id: Some(session_id.clone()),
+                channel: channel_name.clone(),
+                content: String::new(),
+                reply_to: None,
+                parse_mode: None,
+                metadata: metadata.clone(),
+                idempotency_key: None,
+            };
+            self.send_voice_reply(out, &full_response).await;
+        }
+
         // Publish ChannelEvent::MessageSent after final response delivery.
         if let Some(ref bus) = self.event_bus {
             bus.publish(blufio_bus::events::BusEvent::Channel(
@@ -619,12 +1197,14 @@ impl AgentLoop {
 
         // Persist final assistant response (also records cost).
         // Note: We persist the raw LLM response, not the display_response with prefixes.
-        let actor = self.sessions.get_mut(&session_id).ok_or_else(|| {
-            BlufioError::Internal(format!("session actor not found for {session_id}"))
-        })?;
+        let actor = session_actor(&mut self.sessions, &session_key)?;
         actor
             .persist_response(&full_response, usage.clone())
             .await?;
+        if tool_loop_recorded {
+            self.storage.clear_tool_loop_state(&session_id).await?;
+        }
+        drop(turn);
 
         if let Some(u) = &usage {
             info!(
@@ -643,6 +1223,110 @@ impl AgentLoop {
         Ok(())
     }
 
+    /// Streams a follow-up request within a turn (after tool calls, or to
+    /// continue a truncated reply), on the model routed for the turn.
+    ///
+    /// Takes the turn's previous stream so its provider permit is released
+    /// before a new one is requested; holding both would deadlock a turn
+    /// when the limit is 1.
+    async fn stream_follow_up(
+        &mut self,
+        session_key: &str,
+        session_id: &str,
+        messages: Vec<ProviderMessage>,
+        previous: Pin<Box<dyn Stream<Item = Result<ProviderStreamChunk, BlufioError>> + Send>>,
+    ) -> Result<
+        Pin<Box<dyn Stream<Item = Result<ProviderStreamChunk, BlufioError>> + Send>>,
+        BlufioError,
+    > {
+        let actor = session_actor(&mut self.sessions, session_key)?;
+
+        let tool_defs = {
+            let registry = actor.tool_registry().read().await;
+            if !registry.is_empty() {
+                Some(registry.tool_definitions())
+            } else {
+                None
+            }
+        };
+
+        // P3 fix: Use the model from the initial routing decision for this session,
+        // not the hardcoded default_model. This ensures follow-ups use the same
+        // model tier that was selected for the initial request (e.g., Opus for complex queries).
+        let (follow_up_model, follow_up_max_tokens) = match actor.last_routing_decision() {
+            Some(decision) => {
+                debug!(
+                    session_id = %session_id,
+                    model = %decision.actual_model,
+                    "follow-up using routed model"
+                );
+                (decision.actual_model.clone(), decision.max_tokens)
+            }
+            None => {
+                debug!(
+                    session_id = %session_id,
+                    model = %self.config.anthropic.default_model,
+                    "follow-up using default model (no routing decision)"
+                );
+                (
+                    self.config.anthropic.default_model.clone(),
+                    self.config.anthropic.max_tokens,
+                )
+            }
+        };
+
+        let follow_up_request = ProviderRequest {
+            model: follow_up_model,
+            system_prompt: None,
+            system_blocks: None,
+            messages,
+            max_tokens: follow_up_max_tokens,
+            stream: true,
+            tools: tool_defs,
+        };
+
+        // Waits for a provider slot when concurrency is limited.
+        drop(previous);
+        let permit = match &self.provider_limiter {
+            Some(l) => Some(l.acquire().await?),
+            None => None,
+        };
+        let follow_up = self.provider.stream(follow_up_request).await;
+        if let (Err(e), Some(l)) = (&follow_up, &self.provider_limiter) {
+            l.observe_error(e).await;
+        }
+        Ok(limiter::hold_permit(follow_up?, permit))
+    }
+
+    /// Synthesizes `text` and sends it as a voice message addressed like `out`.
+    ///
+    /// The text reply has already been delivered, so failures are only logged.
+    async fn send_voice_reply(&self, out: OutboundMessage, text: &str) {
+        let Some(ref synthesizer) = self.synthesizer else {
+            return;
+        };
+        let request = TtsRequest {
+            text: text.to_string(),
+            voice: self.config.tts.voice.clone().unwrap_or_default(),
+            output_format: self.config.tts.output_format.clone(),
+            speed: 1.0,
+        };
+        let audio = match synthesizer.synthesize(request).await {
+            Ok(audio) => audio,
+            Err(e) => {
+                warn!(error = %e, "failed to synthesize voice reply");
+                return;
+            }
+        };
+        if let Err(e) = self
+            .channel
+            .send_voice(out, audio.audio_data, &audio.content_type)
+            .await
+        {
+            warn!(error = %e, "failed to send voice reply");
+        }
+    }
+
     /// Resolves an existing session or creates a new one for the sender.
     ///
     /// Looks up by sender_id + channel in the in-memory map first, then
@@ -653,7 +1337,7 @@ impl AgentLoop {
         channel: &str,
     ) -> Result<String, BlufioError> {
         // Check in-memory sessions first.
-        let session_key = format!("{channel}:{sender_id}");
+        let session_key = session_key(channel, sender_id);
         if let Some(actor) = self.sessions.get(&session_key) {
             return Ok(actor.session_id().to_string());
         }
@@ -690,8 +1374,14 @@ impl AgentLoop {
                     fallback_chain: self.fallback_chain.clone(),
                     event_bus: self.event_bus.clone(),
                     injection_pipeline: self.injection_pipeline.clone(),
+                    provider_limiter: self.provider_limiter.clone(),
                     boundary_manager: None,
                     channel_interactive: self.channel.capabilities().supports_interactive,
+                    tool_timeout: self.tool_timeout,
+                    max_parallel_tools: self.config.skill.max_parallel_tools,
+                    pii_redactor: self.pii_redactor.clone(),
+                    personas: self.config.agent.personas.clone(),
+                    persona: persona_from_metadata(session.metadata.as_deref()),
                 });
                 let session_id = session.id.clone();
                 self.sessions.insert(session_key, actor);
@@ -748,8 +1438,14 @@ impl AgentLoop {
             fallback_chain: self.fallback_chain.clone(),
             event_bus: self.event_bus.clone(),
             injection_pipeline: None,
+            provider_limiter: self.provider_limiter.clone(),
             boundary_manager: None,
             channel_interactive: self.channel.capabilities().supports_interactive,
+            tool_timeout: self.tool_timeout,
+            max_parallel_tools: self.config.skill.max_parallel_tools,
+            pii_redactor: self.pii_redactor.clone(),
+            personas: self.config.agent.personas.clone(),
+            persona: None,
         });
         self.sessions.insert(session_key, actor);
         #[cfg(feature = "prometheus")]
@@ -759,18 +1455,149 @@ impl AgentLoop {
     }
 }
 
-/// Consumes a provider stream, collecting text, usage, tool_use blocks, and stop_reason.
+/// Result text for tool calls cut off by a restart.
+const INTERRUPTED_TOOL_RESULT: &str =
+    "Tool execution was interrupted by a restart and did not complete.";
+
+/// Builds the stored user message carrying one `tool_result`.
+fn tool_result_message(
+    session_id: &str,
+    tool_use_id: &str,
+    content: &str,
+    is_error: bool,
+) -> blufio_core::types::Message {
+    let result_content = serde_json::json!({
+        "type": "tool_result",
+        "tool_use_id": tool_use_id,
+        "content": content,
+        "is_error": is_error,
+    });
+    blufio_core::types::Message {
+        id: uuid::Uuid::new_v4().to_string(),
+        session_id: session_id.to_string(),
+        role: "user".to_string(),
+        content: result_content.to_string(),
+        token_count: None,
+        metadata: Some(serde_json::json!({"tool_result": true}).to_string()),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        classification: Default::default(),
+    }
+}
+
+/// Returns the `tool_use_id` of a stored `tool_result` message, if it is one.
+fn stored_tool_result_id(content: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(content).ok()?;
+    if value.get("type")?.as_str()? != "tool_result" {
+        return None;
+    }
+    value.get("tool_use_id")?.as_str().map(String::from)
+}
+
+/// Key of the in-memory session actor map: one actor per channel + sender.
+fn session_key(channel: &str, sender_id: &str) -> String {
+    format!("{channel}:{sender_id}")
+}
+
+/// Idempotency key for the reply to one inbound message.
+///
+/// Built from the platform message ID, scoped by channel and chat since
+/// platforms number messages per chat. A redelivered message, or a reply
+/// re-sent from the outbound queue, carries the same key, so the channel
+/// can drop the duplicate. Messages without an ID get no key.
+fn reply_idempotency_key(channel: &str, chat_id: &str, message_id: &str) -> Option<String> {
+    (!message_id.is_empty()).then(|| format!("reply:{channel}:{chat_id}:{message_id}"))
+}
+
+/// Looks up the session actor for `session_key`.
+///
+/// Takes the map rather than `&mut self` so other `AgentLoop` fields stay
+/// borrowable while the actor reference is live.
+fn session_actor<'a>(
+    sessions: &'a mut HashMap<String, SessionActor>,
+    session_key: &str,
+) -> Result<&'a mut SessionActor, BlufioError> {
+    sessions
+        .get_mut(session_key)
+        .ok_or_else(|| BlufioError::Internal(format!("session actor not found for {session_key}")))
+}
+
+/// Consumes a provider stream while forwarding each text delta to the channel
+/// via [`ChannelAdapter::send_partial`].
+///
+/// Deltas are sent from a concurrent future so a slow channel never stalls the
+/// provider stream; all of them are delivered before this returns, i.e.
+/// before the complete response is sent. `template` supplies the routing
+/// fields of each partial message.
+async fn consume_stream_forwarding(
+    stream: &mut Pin<Box<dyn Stream<Item = Result<ProviderStreamChunk, BlufioError>> + Send>>,
+    channel: &(dyn ChannelAdapter + Send + Sync),
+    template: &OutboundMessage,
+) -> (String, Option<TokenUsage>, Vec<ToolUseData>, Option<String>) {
+    let (delta_tx, mut delta_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+
+    let consume = async {
+        // Moved in so the forwarder sees the channel close once the stream ends.
+        let delta_tx = delta_tx;
+        consume_stream_with(stream, |chunk| {
+            if chunk.event_type == StreamEventType::ContentBlockDelta
+                && let Some(t) = &chunk.text
+                && !t.is_empty()
+            {
+                let _ = delta_tx.send(t.clone());
+            }
+        })
+        .await
+    };
+
+    let forward = async {
+        while let Some(delta) = delta_rx.recv().await {
+            let out = OutboundMessage {
+                content: delta,
+                ..template.clone()
+            };
+            if let Err(e) = channel.send_partial(out).await {
+                debug!(error = %e, "failed to send partial response");
+            }
+        }
+    };
+
+    let (result, ()) = tokio::join!(consume, forward);
+    result
+}
+
+/// Consumes a provider stream, collecting text, usage, tool_use blocks, and stop_reason.
 ///
 /// Returns `(text, usage, tool_uses, stop_reason)`.
 async fn consume_stream(
     stream: &mut Pin<Box<dyn Stream<Item = Result<ProviderStreamChunk, BlufioError>> + Send>>,
 ) -> (String, Option<TokenUsage>, Vec<ToolUseData>, Option<String>) {
+    consume_stream_with(stream, |_| {}).await
+}
+
+/// Consumes a provider stream, collecting text, usage, tool_use blocks, and stop_reason.
+///
+/// Calls `on_chunk` for every successfully received chunk before it is
+/// accumulated, which lets front-ends forward deltas as they arrive.
+/// The terminating `MessageStop`/`Error` chunk is passed to `on_chunk` too;
+/// `Err` items are not.
+///
+/// Returns `(text, usage, tool_uses, stop_reason)`.
+pub async fn consume_stream_with<F>(
+    stream: &mut Pin<Box<dyn Stream<Item = Result<ProviderStreamChunk, BlufioError>> + Send>>,
+    mut on_chunk: F,
+) -> (String, Option<TokenUsage>, Vec<ToolUseData>, Option<String>)
+where
+    F: FnMut(&ProviderStreamChunk),
+{
     let mut text = String::new();
     let mut usage: Option<TokenUsage> = None;
     let mut tool_uses: Vec<ToolUseData> = Vec::new();
     let mut stop_reason: Option<String> = None;
 
     while let Some(chunk_result) = stream.next().await {
+        if let Ok(chunk) = &chunk_result {
+            on_chunk(chunk);
+        }
         match chunk_result {
             Ok(chunk) => match chunk.event_type {
                 StreamEventType::ContentBlockDelta => {
@@ -812,18 +1639,1282 @@ async fn consume_stream(
     (text, usage, tool_uses, stop_reason)
 }
 
+/// User-facing prompt sent in reply to an empty or whitespace-only message.
+const EMPTY_MESSAGE_PROMPT: &str =
+    "It looks like your message was empty. What would you like to ask?";
+
+/// Confirmation sent after `/reset` closes the sender's session.
+const RESET_CONFIRMATION: &str =
+    "Started a new conversation. Earlier messages won't be used as context.";
+
+/// Follow-up user message asking the model to continue a reply that was
+/// cut off at max_tokens.
+const CONTINUE_PROMPT: &str = "Continue exactly where you left off, without repeating anything.";
+
+/// Notice sent in place of a final response with no text, keyed on the
+/// provider's stop reason.
+fn empty_response_notice(stop_reason: Option<&str>) -> &'static str {
+    match stop_reason {
+        Some("max_tokens") => {
+            "The response was cut off before any text was written \u{2014} try asking for less."
+        }
+        Some("tool_use") => {
+            "I ran out of tool calls before reaching an answer \u{2014} try a narrower request."
+        }
+        Some("refusal") => "The model declined to answer this request.",
+        _ => "The model returned an empty response \u{2014} please try again or rephrase.",
+    }
+}
+
+/// Returns true for a `/reset` command, with or without a Telegram-style
+/// `@botname` suffix.
+fn is_reset_command(content: &MessageContent) -> bool {
+    let MessageContent::Text(text) = content else {
+        return false;
+    };
+    let command = text.trim();
+    command == "/reset" || command.starts_with("/reset@")
+}
+
+/// Returns true for text that is empty once whitespace and any model
+/// override prefix (e.g. `/opus `) are stripped.
+///
+/// Non-text content (images, documents, voice) is never blank.
+fn is_blank_message(content: &MessageContent) -> bool {
+    match content {
+        MessageContent::Text(text) => {
+            let (_, rest) = blufio_router::parse_model_override(text);
+            rest.trim().is_empty()
+        }
+        _ => false,
+    }
+}
+
+/// User-facing notice sent when a turn is aborted by the watchdog.
+fn turn_timeout_message(limit: Duration) -> String {
+    format!(
+        "Sorry, this request took longer than {}s and was stopped. Please try again or simplify the request.",
+        limit.as_secs()
+    )
+}
+
 /// Extracts chat_id from an optional JSON metadata string.
+///
+/// Accepts a string (`"12345"`) or an integer (`12345`); other JSON types
+/// yield `None`.
 fn extract_chat_id_from_metadata(metadata: &Option<String>) -> Option<String> {
-    metadata.as_ref().and_then(|m| {
-        serde_json::from_str::<serde_json::Value>(m)
-            .ok()
-            .and_then(|v| v.get("chat_id").and_then(|c| c.as_str()).map(String::from))
-    })
+    let meta = serde_json::from_str::<serde_json::Value>(metadata.as_ref()?).ok()?;
+    match meta.get("chat_id")? {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) if n.is_i64() || n.is_u64() => Some(n.to_string()),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use blufio_test_utils::MockChannel;
+
+    /// A provider whose stream never produces its first chunk in time.
+    struct SlowMockProvider {
+        delay: Duration,
+    }
+
+    #[async_trait::async_trait]
+    impl blufio_core::traits::adapter::PluginAdapter for SlowMockProvider {
+        fn name(&self) -> &str {
+            "slow-mock"
+        }
+        fn version(&self) -> semver::Version {
+            semver::Version::new(0, 1, 0)
+        }
+        fn adapter_type(&self) -> blufio_core::types::AdapterType {
+            blufio_core::types::AdapterType::Provider
+        }
+        async fn health_check(&self) -> Result<blufio_core::types::HealthStatus, BlufioError> {
+            Ok(blufio_core::types::HealthStatus::Healthy)
+        }
+        async fn shutdown(&self) -> Result<(), BlufioError> {
+            Ok(())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ProviderAdapter for SlowMockProvider {
+        async fn complete(
+            &self,
+            _req: ProviderRequest,
+        ) -> Result<blufio_core::types::ProviderResponse, BlufioError> {
+            tokio::time::sleep(self.delay).await;
+            Err(BlufioError::Internal("slow mock never completes".into()))
+        }
+
+        async fn stream(
+            &self,
+            _req: ProviderRequest,
+        ) -> Result<
+            Pin<
+                Box<
+                    dyn futures_core::Stream<Item = Result<ProviderStreamChunk, BlufioError>>
+                        + Send,
+                >,
+            >,
+            BlufioError,
+        > {
+            let delay = self.delay;
+            Ok(Box::pin(futures::stream::once(async move {
+                tokio::time::sleep(delay).await;
+                Ok(ProviderStreamChunk {
+                    event_type: StreamEventType::MessageStop,
+                    text: None,
+                    usage: None,
+                    error: None,
+                    tool_use: None,
+                    stop_reason: None,
+                })
+            })))
+        }
+    }
+
+    /// Build an `AgentLoop` over a temp database with the given provider and channel.
+    async fn make_test_loop(
+        provider: Arc<dyn ProviderAdapter + Send + Sync>,
+        channel: MockChannel,
+    ) -> (AgentLoop, tempfile::TempDir) {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let storage = blufio_storage::SqliteStorage::new(blufio_config::model::StorageConfig {
+            database_path: db_path.to_string_lossy().to_string(),
+            wal_mode: true,
+            ..Default::default()
+        });
+        storage.initialize().await.unwrap();
+        let storage: Arc<dyn StorageAdapter + Send + Sync> = Arc::new(storage);
+
+        let cost_ledger = Arc::new(CostLedger::open(db_path.to_str().unwrap()).await.unwrap());
+        let config = BlufioConfig {
+            agent: blufio_config::model::AgentConfig {
+                system_prompt: Some("Test assistant.".to_string()),
+                ..Default::default()
+            },
+            routing: blufio_config::model::RoutingConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let budget_tracker = Arc::new(tokio::sync::Mutex::new(BudgetTracker::new(&config.cost)));
+        let token_cache = Arc::new(blufio_core::token_counter::TokenizerCache::new(
+            blufio_core::token_counter::TokenizerMode::Fast,
+        ));
+        let context_engine = Arc::new(
+            ContextEngine::new(&config.agent, &config.context, token_cache)
+                .await
+                .unwrap(),
+        );
+        let router = Arc::new(ModelRouter::new(config.routing.clone()));
+        let tool_registry = Arc::new(tokio::sync::RwLock::new(ToolRegistry::new()));
+
+        let agent_loop = AgentLoop::new(
+            Box::new(channel),
+            provider,
+            storage,
+            context_engine,
+            cost_ledger,
+            budget_tracker,
+            None,
+            None,
+            router,
+            None,
+            tool_registry,
+            config,
+        )
+        .await
+        .unwrap();
+
+        (agent_loop, temp_dir)
+    }
+
+    fn make_inbound(text: &str) -> InboundMessage {
+        InboundMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            session_id: None,
+            channel: "mock".to_string(),
+            sender_id: "test-user".to_string(),
+            content: blufio_core::types::MessageContent::Text(text.to_string()),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_messages_to_one_session_are_serialized() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_responses(vec![
+            "first reply".to_string(),
+            "second reply".to_string(),
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider.clone(), channel.clone()).await;
+
+        // Both messages are waiting before the loop starts.
+        channel.inject_message(make_inbound("first")).await;
+        channel.inject_message(make_inbound("second")).await;
+
+        let cancel = CancellationToken::new();
+        let (result, delivered) = tokio::join!(agent_loop.run(cancel.clone()), async {
+            let delivered = channel.wait_for_sent(2, Duration::from_secs(5)).await;
+            cancel.cancel();
+            delivered
+        });
+        result.unwrap();
+        assert!(delivered);
+
+        // The second turn starts only after the first reply is persisted, so
+        // its request carries the whole first exchange.
+        let requests = provider.requests();
+        assert_eq!(requests.len(), 2);
+        let texts: Vec<String> = requests[1]
+            .messages
+            .iter()
+            .flat_map(|m| &m.content)
+            .filter_map(|block| match block {
+                ContentBlock::Text { text } => Some(text.clone()),
+                _ => None,
+            })
+            .collect();
+        let position = |needle: &str| texts.iter().position(|t| t.contains(needle)).unwrap();
+        assert!(position("first") < position("first reply"));
+        assert!(position("first reply") < position("second"));
+
+        let sent: Vec<String> = channel
+            .sent_messages()
+            .await
+            .into_iter()
+            .map(|m| m.content)
+            .collect();
+        assert_eq!(sent, ["first reply", "second reply"]);
+    }
+
+    #[tokio::test]
+    async fn turn_watchdog_aborts_slow_turn() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(SlowMockProvider {
+            delay: Duration::from_secs(60),
+        });
+        let (mut agent_loop, _temp) = make_test_loop(provider, channel.clone()).await;
+        agent_loop.set_turn_timeout(Some(Duration::from_millis(200)));
+
+        let started = std::time::Instant::now();
+        agent_loop
+            .handle_inbound(make_inbound("hello"))
+            .await
+            .unwrap();
+        let elapsed = started.elapsed();
+
+        assert!(
+            elapsed < Duration::from_secs(5),
+            "turn should be aborted at the watchdog limit, took {elapsed:?}"
+        );
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].content.contains("took longer than"));
+        assert!(sent[0].session_id.is_some());
+    }
+
+    #[tokio::test]
+    async fn turn_within_watchdog_completes_normally() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_responses(vec![
+            "quick answer".to_string(),
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider, channel.clone()).await;
+        agent_loop.set_turn_timeout(Some(Duration::from_secs(30)));
+
+        agent_loop
+            .handle_inbound(make_inbound("hello"))
+            .await
+            .unwrap();
+
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].content, "quick answer");
+    }
+
+    #[tokio::test]
+    async fn mid_stream_provider_error_sends_and_persists_partial_response() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::error_after_chunks(vec![
+            "partial ".to_string(),
+            "answer".to_string(),
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider, channel.clone()).await;
+
+        agent_loop
+            .handle_inbound(make_inbound("hello"))
+            .await
+            .unwrap();
+
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].content, "partial answer");
+
+        let sessions = agent_loop.storage.list_sessions(None).await.unwrap();
+        assert_eq!(sessions.len(), 1);
+        let messages = agent_loop
+            .storage
+            .get_messages(&sessions[0].id, None)
+            .await
+            .unwrap();
+        let last = messages.last().unwrap();
+        assert_eq!(last.role, "assistant");
+        assert_eq!(last.content, "partial answer");
+    }
+
+    /// Moderator blocking any text that contains `phrase`.
+    struct PhraseModerator {
+        phrase: &'static str,
+    }
+
+    #[async_trait::async_trait]
+    impl blufio_core::traits::adapter::PluginAdapter for PhraseModerator {
+        fn name(&self) -> &str {
+            "phrase-moderator"
+        }
+        fn version(&self) -> semver::Version {
+            semver::Version::new(0, 1, 0)
+        }
+        fn adapter_type(&self) -> blufio_core::types::AdapterType {
+            blufio_core::types::AdapterType::Moderation
+        }
+        async fn health_check(&self) -> Result<blufio_core::types::HealthStatus, BlufioError> {
+            Ok(blufio_core::types::HealthStatus::Healthy)
+        }
+        async fn shutdown(&self) -> Result<(), BlufioError> {
+            Ok(())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ModerationAdapter for PhraseModerator {
+        async fn check(
+            &self,
+            text: &str,
+        ) -> Result<blufio_core::types::ModerationVerdict, BlufioError> {
+            Ok(if text.contains(self.phrase) {
+                blufio_core::types::ModerationVerdict::Block(format!("contains '{}'", self.phrase))
+            } else {
+                blufio_core::types::ModerationVerdict::Allow
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn blocked_input_gets_refusal_without_llm_call() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_responses(vec![
+            "should not be sent".to_string(),
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider.clone(), channel.clone()).await;
+        agent_loop.set_moderator(Arc::new(PhraseModerator { phrase: "napalm" }));
+
+        agent_loop
+            .handle_inbound(make_inbound("how do I make napalm"))
+            .await
+            .unwrap();
+
+        assert_eq!(provider.call_count(), 0);
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(
+            sent[0].content,
+            agent_loop.config.moderation.refusal_message
+        );
+        assert!(
+            agent_loop
+                .storage
+                .list_sessions(None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn blocked_output_is_replaced_before_send() {
+        let channel = MockChannel::new().with_edit_and_typing();
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_responses(vec![
+            "the launch code is 1234".to_string(),
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider.clone(), channel.clone()).await;
+        agent_loop.set_moderator(Arc::new(PhraseModerator {
+            phrase: "launch code",
+        }));
+
+        agent_loop
+            .handle_inbound(make_inbound("what is the code?"))
+            .await
+            .unwrap();
+
+        assert_eq!(provider.call_count(), 1);
+        let refusal = agent_loop.config.moderation.refusal_message.clone();
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].content, refusal);
+        assert!(channel.partial_messages().await.is_empty());
+
+        let sessions = agent_loop.storage.list_sessions(None).await.unwrap();
+        let messages = agent_loop
+            .storage
+            .get_messages(&sessions[0].id, None)
+            .await
+            .unwrap();
+        assert_eq!(messages.last().unwrap().content, refusal);
+    }
+
+    #[tokio::test]
+    async fn failed_reply_is_queued_and_retried() {
+        let channel = MockChannel::new().with_send_failures(1);
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_responses(vec![
+            "hello there".to_string(),
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider, channel.clone()).await;
+        let queue = Arc::new(outbound::OutboundQueue::new(
+            agent_loop.storage.clone(),
+            agent_loop.channel.clone(),
+            &blufio_config::model::OutboundConfig::default(),
+        ));
+        agent_loop.outbound = Some(queue.clone());
+
+        agent_loop.handle_inbound(make_inbound("hi")).await.unwrap();
+        assert!(channel.sent_messages().await.is_empty());
+
+        assert_eq!(
+            queue.deliver_next().await.unwrap(),
+            outbound::DeliveryOutcome::Delivered
+        );
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].content, "hello there");
+    }
+
+    /// Contents of the stored messages of the only session.
+    async fn stored_contents(agent_loop: &AgentLoop) -> Vec<String> {
+        let sessions = agent_loop.storage.list_sessions(None).await.unwrap();
+        agent_loop
+            .storage
+            .get_messages(&sessions[0].id, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.content)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn pii_is_redacted_in_storage_when_enabled() {
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_responses(vec![
+            "I'll write to jane.doe@example.com".to_string(),
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider, MockChannel::new()).await;
+        agent_loop.set_pii_redactor(Arc::new(redaction::PiiRedactor::new(
+            blufio_config::model::PiiRedactionConfig {
+                user_messages: true,
+                assistant_messages: true,
+                ..Default::default()
+            },
+        )));
+
+        agent_loop
+            .handle_inbound(make_inbound("my email is jane.doe@example.com"))
+            .await
+            .unwrap();
+
+        let contents = stored_contents(&agent_loop).await;
+        assert_eq!(contents.len(), 2);
+        for content in &contents {
+            assert!(!content.contains("jane.doe@example.com"), "{content}");
+            assert!(content.contains("[EMAIL]"), "{content}");
+        }
+    }
+
+    #[tokio::test]
+    async fn pii_is_preserved_in_storage_when_disabled() {
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_responses(vec![
+            "noted".to_string(),
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider, MockChannel::new()).await;
+
+        agent_loop
+            .handle_inbound(make_inbound("my email is jane.doe@example.com"))
+            .await
+            .unwrap();
+
+        let contents = stored_contents(&agent_loop).await;
+        assert_eq!(contents[0], "my email is jane.doe@example.com");
+    }
+
+    #[tokio::test]
+    async fn blank_messages_skip_the_llm() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::new());
+        let (mut agent_loop, _temp) = make_test_loop(provider.clone(), channel.clone()).await;
+
+        for text in ["", "   ", "\n\t ", "/opus   "] {
+            agent_loop.handle_inbound(make_inbound(text)).await.unwrap();
+        }
+
+        assert_eq!(provider.call_count(), 0);
+        assert!(
+            agent_loop
+                .storage
+                .list_sessions(None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent.len(), 4);
+        assert!(sent.iter().all(|m| m.content == EMPTY_MESSAGE_PROMPT));
+    }
+
+    #[tokio::test]
+    async fn reset_ends_session_and_next_message_starts_a_new_one() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::new());
+        let (mut agent_loop, _temp) = make_test_loop(provider.clone(), channel.clone()).await;
+
+        agent_loop
+            .handle_inbound(make_inbound("hello"))
+            .await
+            .unwrap();
+        let first = agent_loop.storage.list_sessions(None).await.unwrap();
+        assert_eq!(first.len(), 1);
+
+        agent_loop
+            .handle_inbound(make_inbound("/reset"))
+            .await
+            .unwrap();
+        assert_eq!(provider.call_count(), 1);
+        assert!(agent_loop.sessions.is_empty());
+        let closed = agent_loop.storage.get_session(&first[0].id).await.unwrap();
+        assert_eq!(closed.unwrap().state, "closed");
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent.last().unwrap().content, RESET_CONFIRMATION);
+
+        agent_loop
+            .handle_inbound(make_inbound("hi again"))
+            .await
+            .unwrap();
+        let active = agent_loop
+            .storage
+            .list_sessions(Some("active"))
+            .await
+            .unwrap();
+        assert_eq!(active.len(), 1);
+        assert_ne!(active[0].id, first[0].id);
+        // The new session starts without the earlier conversation.
+        let last = provider.requests().pop().unwrap();
+        assert!(
+            !last
+                .messages
+                .iter()
+                .flat_map(|m| &m.content)
+                .any(|b| matches!(b, ContentBlock::Text { text } if text.contains("hello")))
+        );
+    }
+
+    #[test]
+    fn reset_command_detection() {
+        let text = |t: &str| MessageContent::Text(t.to_string());
+        assert!(is_reset_command(&text("/reset")));
+        assert!(is_reset_command(&text("  /reset\n")));
+        assert!(is_reset_command(&text("/reset@blufio_bot")));
+        assert!(!is_reset_command(&text("/reset everything please")));
+        assert!(!is_reset_command(&text("please /reset")));
+        assert!(!is_reset_command(&text("/resetting")));
+    }
+
+    #[tokio::test]
+    async fn empty_max_tokens_response_sends_a_notice() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_script(vec![
+            blufio_test_utils::MockTurn::Empty {
+                stop_reason: "max_tokens".to_string(),
+            },
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider, channel.clone()).await;
+
+        agent_loop
+            .handle_inbound(make_inbound("write me a novel"))
+            .await
+            .unwrap();
+
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(
+            sent[0].content,
+            "The response was cut off before any text was written \u{2014} try asking for less."
+        );
+    }
+
+    #[tokio::test]
+    async fn max_tokens_stop_is_continued_and_joined() {
+        use blufio_test_utils::MockTurn;
+
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_script(vec![
+            MockTurn::Truncated("Once upon ".to_string()),
+            MockTurn::Truncated("a time, ".to_string()),
+            MockTurn::Text("the end.".to_string()),
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider.clone(), channel.clone()).await;
+        agent_loop.config.agent.max_continuations = 3;
+
+        agent_loop
+            .handle_inbound(make_inbound("tell me a story"))
+            .await
+            .unwrap();
+
+        assert_eq!(provider.call_count(), 3);
+        let last = provider.requests().pop().unwrap();
+        assert_eq!(
+            message_shapes(&last.messages[last.messages.len() - 4..]),
+            ["assistant:text", "user:text", "assistant:text", "user:text"]
+        );
+
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].content, "Once upon a time, the end.");
+        assert_eq!(
+            stored_contents(&agent_loop).await.last().unwrap(),
+            "Once upon a time, the end."
+        );
+
+        // Every part's cost is recorded.
+        let records = agent_loop.cost_ledger.records(None).await.unwrap();
+        assert_eq!(records.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn continuations_stop_at_the_configured_bound() {
+        use blufio_test_utils::MockTurn;
+
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_script(vec![
+            MockTurn::Truncated("one ".to_string()),
+            MockTurn::Truncated("two ".to_string()),
+            MockTurn::Truncated("three ".to_string()),
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider.clone(), channel.clone()).await;
+        agent_loop.config.agent.max_continuations = 1;
+
+        agent_loop
+            .handle_inbound(make_inbound("count forever"))
+            .await
+            .unwrap();
+
+        assert_eq!(provider.call_count(), 2);
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent[0].content, "one two ");
+        let records = agent_loop.cost_ledger.records(None).await.unwrap();
+        assert_eq!(records.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn max_tokens_stop_is_not_continued_by_default() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_script(vec![
+            blufio_test_utils::MockTurn::Truncated("cut".to_string()),
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider.clone(), channel.clone()).await;
+
+        agent_loop.handle_inbound(make_inbound("hi")).await.unwrap();
+
+        assert_eq!(provider.call_count(), 1);
+        assert_eq!(channel.sent_messages().await[0].content, "cut");
+    }
+
+    #[test]
+    fn empty_response_notice_depends_on_stop_reason() {
+        assert!(empty_response_notice(Some("max_tokens")).contains("cut off"));
+        assert!(empty_response_notice(Some("tool_use")).contains("tool calls"));
+        assert!(empty_response_notice(Some("end_turn")).contains("empty response"));
+        assert!(empty_response_notice(None).contains("empty response"));
+    }
+
+    #[tokio::test]
+    async fn blank_messages_can_be_dropped_silently() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::new());
+        let (mut agent_loop, _temp) = make_test_loop(provider.clone(), channel.clone()).await;
+        agent_loop.config.agent.prompt_on_empty_message = false;
+
+        agent_loop.handle_inbound(make_inbound("  ")).await.unwrap();
+
+        assert_eq!(provider.call_count(), 0);
+        assert!(channel.sent_messages().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn single_character_message_is_processed() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_responses(vec![
+            "Yes?".to_string(),
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider.clone(), channel.clone()).await;
+
+        agent_loop.handle_inbound(make_inbound("?")).await.unwrap();
+
+        assert_eq!(provider.call_count(), 1);
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].content, "Yes?");
+    }
+
+    fn make_inbound_in_chat(text: &str, chat_id: &str) -> InboundMessage {
+        InboundMessage {
+            metadata: Some(serde_json::json!({ "chat_id": chat_id }).to_string()),
+            ..make_inbound(text)
+        }
+    }
+
+    #[tokio::test]
+    async fn redelivered_message_reuses_reply_idempotency_key() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_responses(vec![
+            "reply".to_string(),
+            "reply again".to_string(),
+            "other reply".to_string(),
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider, channel.clone()).await;
+
+        let original = make_inbound_in_chat("hello", "42");
+        let other = make_inbound_in_chat("hello", "42");
+        agent_loop.handle_inbound(original.clone()).await.unwrap();
+        agent_loop.handle_inbound(original).await.unwrap();
+        agent_loop.handle_inbound(other).await.unwrap();
+
+        let keys: Vec<Option<String>> = channel
+            .sent_messages()
+            .await
+            .into_iter()
+            .map(|m| m.idempotency_key)
+            .collect();
+        assert_eq!(keys.len(), 3);
+        assert!(keys[0].is_some());
+        assert_eq!(keys[0], keys[1]);
+        assert_ne!(keys[0], keys[2]);
+    }
+
+    #[tokio::test]
+    async fn long_turn_refreshes_typing_indicator() {
+        let channel = MockChannel::new().with_edit_and_typing();
+        let provider = Arc::new(SlowMockProvider {
+            delay: Duration::from_millis(300),
+        });
+        let (mut agent_loop, _temp) = make_test_loop(provider, channel.clone()).await;
+        agent_loop.set_typing_refresh(Some(Duration::from_millis(50)));
+
+        agent_loop
+            .handle_inbound(make_inbound_in_chat("hello", "chat-42"))
+            .await
+            .unwrap();
+
+        // Initial indicator plus several refreshes during the 300ms stream.
+        let typing = channel.typing_events().await;
+        assert!(typing.len() >= 3, "only {} typing events", typing.len());
+        assert!(typing.iter().all(|c| c == "chat-42"));
+
+        // The refresher stops once the turn is over.
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(channel.typing_events().await.len(), typing.len());
+    }
+
+    #[tokio::test]
+    async fn typing_refresh_stops_at_first_reply() {
+        let channel = MockChannel::new().with_edit_and_typing();
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_responses(vec![
+            "Hi".to_string(),
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider, channel.clone()).await;
+        agent_loop.set_typing_refresh(Some(Duration::from_millis(20)));
+
+        agent_loop
+            .handle_inbound(make_inbound_in_chat("hello", "chat-42"))
+            .await
+            .unwrap();
+        assert_eq!(channel.sent_count().await, 1);
+
+        let after_reply = channel.typing_events().await.len();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(channel.typing_events().await.len(), after_reply);
+    }
+
+    #[tokio::test]
+    async fn typing_is_skipped_without_channel_support() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(SlowMockProvider {
+            delay: Duration::from_millis(100),
+        });
+        let (mut agent_loop, _temp) = make_test_loop(provider, channel.clone()).await;
+        agent_loop.set_typing_refresh(Some(Duration::from_millis(20)));
+
+        agent_loop
+            .handle_inbound(make_inbound_in_chat("hello", "chat-42"))
+            .await
+            .unwrap();
+
+        assert!(channel.typing_events().await.is_empty());
+    }
+
+    /// Seeds storage as if a turn crashed after persisting a `tool_use`
+    /// response and recording its pending calls, but before executing them.
+    async fn seed_interrupted_tool_loop(storage: &dyn StorageAdapter, tool_use_ids: &[&str]) {
+        storage
+            .create_session(&Session {
+                id: "sess-crash".to_string(),
+                channel: "mock".to_string(),
+                user_id: Some("test-user".to_string()),
+                state: "active".to_string(),
+                metadata: None,
+                created_at: "2026-01-01T00:00:00.000Z".to_string(),
+                updated_at: "2026-01-01T00:00:00.000Z".to_string(),
+                classification: Default::default(),
+            })
+            .await
+            .unwrap();
+        storage
+            .insert_message(&blufio_core::types::Message {
+                id: "msg-assistant".to_string(),
+                session_id: "sess-crash".to_string(),
+                role: "assistant".to_string(),
+                content: "Let me check.".to_string(),
+                token_count: None,
+                metadata: None,
+                created_at: "2026-01-01T00:00:01.000Z".to_string(),
+                classification: Default::default(),
+            })
+            .await
+            .unwrap();
+        let tool_uses: Vec<ToolUseData> = tool_use_ids
+            .iter()
+            .map(|id| ToolUseData {
+                id: id.to_string(),
+                name: "bash".to_string(),
+                input: serde_json::json!({"command": "ls"}),
+            })
+            .collect();
+        storage
+            .save_tool_loop_state("sess-crash", 0, &tool_uses)
+            .await
+            .unwrap();
+    }
+
+    fn make_voice_inbound() -> InboundMessage {
+        InboundMessage {
+            content: blufio_core::types::MessageContent::Voice {
+                data: vec![0; 16],
+                duration_secs: Some(3.0),
+            },
+            ..make_inbound("")
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn voice_message_gets_spoken_reply_in_kind() {
+        let channel = MockChannel::new().with_voice();
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_responses(vec![
+            "it is sunny".to_string(),
+            "still sunny".to_string(),
+        ]));
+        let (mut agent_loop, _temp) = make_test_loop(provider, channel.clone()).await;
+        // "Speaks" by echoing the text back as the audio bytes.
+        agent_loop.set_synthesizer(Arc::new(synthesis::CommandSynthesizer::new(
+            "cat".to_string(),
+            Vec::new(),
+            Duration::from_secs(10),
+        )));
+
+        agent_loop
+            .handle_inbound(make_voice_inbound())
+            .await
+            .unwrap();
+
+        // The text reply still goes out, followed by its spoken copy.
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent.last().unwrap().content, "it is sunny");
+        let voice = channel.voice_messages().await;
+        assert_eq!(voice.len(), 1);
+        assert_eq!(voice[0].1, b"it is sunny");
+
+        // A text message is answered with text only.
+        agent_loop
+            .handle_inbound(make_inbound("and tomorrow?"))
+            .await
+            .unwrap();
+        assert_eq!(channel.voice_messages().await.len(), 1);
+    }
+
+    /// Roles and block kinds of a request's messages, e.g. `"assistant:tool_use(tu-1)"`.
+    fn message_shapes(messages: &[ProviderMessage]) -> Vec<String> {
+        messages
+            .iter()
+            .map(|m| {
+                let blocks: Vec<String> = m
+                    .content
+                    .iter()
+                    .map(|block| match block {
+                        ContentBlock::Text { .. } => "text".to_string(),
+                        ContentBlock::Image { .. } => "image".to_string(),
+                        ContentBlock::ToolUse { id, .. } => format!("tool_use({id})"),
+                        ContentBlock::ToolResult { tool_use_id, .. } => {
+                            format!("tool_result({tool_use_id})")
+                        }
+                    })
+                    .collect();
+                format!("{}:{}", m.role, blocks.join(","))
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn multi_iteration_tool_loop_keeps_message_order() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::new());
+        provider
+            .add_tool_use("tu-1", "lookup", serde_json::json!({"q": "a"}))
+            .await;
+        provider
+            .add_tool_use("tu-2", "lookup", serde_json::json!({"q": "b"}))
+            .await;
+        provider.add_response("all done".to_string()).await;
+        let (mut agent_loop, _temp) = make_test_loop(provider.clone(), channel.clone()).await;
+
+        agent_loop
+            .handle_inbound(make_inbound("look things up"))
+            .await
+            .unwrap();
+
+        let requests = provider.requests();
+        assert_eq!(requests.len(), 3);
+        let initial = message_shapes(&requests[0].messages);
+        assert_eq!(initial.last().unwrap(), "user:text");
+
+        // Each follow-up is the previous request plus exactly one tool call
+        // and its result, in order.
+        let mut expected = initial.clone();
+        expected.extend(["assistant:tool_use(tu-1)", "user:tool_result(tu-1)"].map(String::from));
+        assert_eq!(message_shapes(&requests[1].messages), expected);
+        expected.extend(["assistant:tool_use(tu-2)", "user:tool_result(tu-2)"].map(String::from));
+        assert_eq!(message_shapes(&requests[2].messages), expected);
+
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent.last().unwrap().content, "all done");
+    }
+
+    struct LookupTool;
+
+    #[async_trait::async_trait]
+    impl blufio_skill::Tool for LookupTool {
+        fn name(&self) -> &str {
+            "lookup"
+        }
+
+        fn description(&self) -> &str {
+            "Looks things up"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({"type": "object"})
+        }
+
+        async fn invoke(
+            &self,
+            input: serde_json::Value,
+        ) -> Result<blufio_skill::ToolOutput, BlufioError> {
+            Ok(blufio_skill::ToolOutput {
+                content: format!("3 results for {}", input["q"].as_str().unwrap_or("")),
+                is_error: false,
+            })
+        }
+    }
+
+    async fn run_lookup_turn(show_tool_calls: Vec<String>) -> (Vec<OutboundMessage>, String) {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::new());
+        provider
+            .add_tool_use("tu-1", "lookup", serde_json::json!({"q": "rust"}))
+            .await;
+        provider.add_response("found it".to_string()).await;
+        let (mut agent_loop, _temp) = make_test_loop(provider.clone(), channel.clone()).await;
+        agent_loop.config.agent.show_tool_calls = show_tool_calls;
+        agent_loop
+            .tool_registry
+            .write()
+            .await
+            .register_builtin(Arc::new(LookupTool))
+            .unwrap();
+
+        agent_loop
+            .handle_inbound(make_inbound("search for rust"))
+            .await
+            .unwrap();
+
+        // The model gets the full result regardless of display.
+        let tool_result = provider.requests()[1]
+            .messages
+            .iter()
+            .flat_map(|m| &m.content)
+            .find_map(|block| match block {
+                ContentBlock::ToolResult { content, .. } => Some(content.clone()),
+                _ => None,
+            })
+            .unwrap();
+        (channel.sent_messages().await, tool_result)
+    }
+
+    #[tokio::test]
+    async fn tool_loop_completes_with_single_provider_permit() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::new());
+        provider
+            .add_tool_use("tu-1", "lookup", serde_json::json!({"q": "a"}))
+            .await;
+        provider
+            .add_tool_use("tu-2", "lookup", serde_json::json!({"q": "b"}))
+            .await;
+        provider.add_response("all done".to_string()).await;
+        let (mut agent_loop, _temp) = make_test_loop(provider.clone(), channel.clone()).await;
+        let limiter = Arc::new(ProviderLimiter::new(1));
+        agent_loop.set_provider_limiter(Some(limiter.clone()));
+        agent_loop
+            .tool_registry
+            .write()
+            .await
+            .register_builtin(Arc::new(LookupTool))
+            .unwrap();
+
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            agent_loop.handle_inbound(make_inbound("look things up")),
+        )
+        .await
+        .expect("tool loop deadlocked on the provider limiter")
+        .unwrap();
+
+        assert_eq!(provider.requests().len(), 3);
+        assert_eq!(limiter.available(), 1);
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent.last().unwrap().content, "all done");
+    }
+
+    #[tokio::test]
+    async fn tool_call_summary_is_appended_when_enabled() {
+        let (sent, tool_result) = run_lookup_turn(vec!["mock".to_string()]).await;
+        assert_eq!(
+            sent.last().unwrap().content,
+            "found it\n\n---\nRan: lookup `rust` → 3 results for rust"
+        );
+        assert_eq!(tool_result, "3 results for rust");
+    }
+
+    #[tokio::test]
+    async fn tool_call_summary_is_hidden_by_default() {
+        let (sent, tool_result) = run_lookup_turn(Vec::new()).await;
+        assert_eq!(sent.last().unwrap().content, "found it");
+        assert_eq!(tool_result, "3 results for rust");
+    }
+
+    #[tokio::test]
+    async fn tool_call_summary_is_per_channel() {
+        let (sent, _) = run_lookup_turn(vec!["telegram".to_string()]).await;
+        assert_eq!(sent.last().unwrap().content, "found it");
+    }
+
+    #[tokio::test]
+    async fn recovers_tool_loop_interrupted_before_execution() {
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_responses(vec![]));
+        let (agent_loop, _temp) = make_test_loop(provider, MockChannel::new()).await;
+        let storage = agent_loop.storage.clone();
+        seed_interrupted_tool_loop(storage.as_ref(), &["tu-1", "tu-2"]).await;
+
+        assert_eq!(agent_loop.recover_tool_loops().await.unwrap(), 2);
+
+        let messages = storage.get_messages("sess-crash", None).await.unwrap();
+        assert_eq!(messages.len(), 3);
+        let results: Vec<serde_json::Value> = messages[1..]
+            .iter()
+            .map(|m| serde_json::from_str(&m.content).unwrap())
+            .collect();
+        assert_eq!(results[0]["tool_use_id"], "tu-1");
+        assert_eq!(results[1]["tool_use_id"], "tu-2");
+        assert!(results.iter().all(|r| r["is_error"] == true));
+        assert!(messages[1..].iter().all(|m| m.role == "user"));
+
+        // The record is cleared, so a second startup changes nothing.
+        assert!(storage.list_tool_loop_states().await.unwrap().is_empty());
+        assert_eq!(agent_loop.recover_tool_loops().await.unwrap(), 0);
+    }
+
+    /// A tool that never finishes within a test's watchdog limit.
+    struct HangingTool;
+
+    #[async_trait::async_trait]
+    impl blufio_skill::Tool for HangingTool {
+        fn name(&self) -> &str {
+            "hang"
+        }
+
+        fn description(&self) -> &str {
+            "Never returns"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({"type": "object"})
+        }
+
+        async fn invoke(
+            &self,
+            _input: serde_json::Value,
+        ) -> Result<blufio_skill::ToolOutput, BlufioError> {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Err(BlufioError::Internal("hanging tool woke up".into()))
+        }
+    }
+
+    #[tokio::test]
+    async fn aborted_turn_closes_its_tool_loop() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::new());
+        provider
+            .add_tool_use("tu-1", "hang", serde_json::json!({}))
+            .await;
+        let (mut agent_loop, _temp) = make_test_loop(provider, channel.clone()).await;
+        agent_loop.set_turn_timeout(Some(Duration::from_millis(200)));
+        agent_loop
+            .tool_registry
+            .write()
+            .await
+            .register_builtin(Arc::new(HangingTool))
+            .unwrap();
+
+        agent_loop
+            .handle_inbound(make_inbound("wait for it"))
+            .await
+            .unwrap();
+
+        // The pending call is answered and the record cleared without a restart.
+        assert!(
+            agent_loop
+                .storage
+                .list_tool_loop_states()
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let last = stored_contents(&agent_loop).await.pop().unwrap();
+        let result: serde_json::Value = serde_json::from_str(&last).unwrap();
+        assert_eq!(result["tool_use_id"], "tu-1");
+        assert_eq!(result["is_error"], true);
+    }
+
+    #[tokio::test]
+    async fn recovery_skips_tool_calls_that_have_results() {
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_responses(vec![]));
+        let (agent_loop, _temp) = make_test_loop(provider, MockChannel::new()).await;
+        let storage = agent_loop.storage.clone();
+        seed_interrupted_tool_loop(storage.as_ref(), &["tu-1", "tu-2"]).await;
+        storage
+            .insert_message(&tool_result_message("sess-crash", "tu-1", "done", false))
+            .await
+            .unwrap();
+
+        assert_eq!(agent_loop.recover_tool_loops().await.unwrap(), 1);
+
+        let messages = storage.get_messages("sess-crash", None).await.unwrap();
+        let ids: Vec<String> = messages
+            .iter()
+            .filter_map(|m| stored_tool_result_id(&m.content))
+            .collect();
+        assert_eq!(ids, ["tu-1", "tu-2"]);
+    }
+
+    #[tokio::test]
+    async fn provider_error_before_stream_fails_turn() {
+        let channel = MockChannel::new();
+        let provider = Arc::new(blufio_test_utils::MockProvider::always_error());
+        let (mut agent_loop, _temp) = make_test_loop(provider.clone(), channel.clone()).await;
+
+        let result = agent_loop.handle_inbound(make_inbound("hello")).await;
+        assert!(matches!(
+            result,
+            Err(BlufioError::Provider {
+                kind: blufio_core::error::ProviderErrorKind::ServerError,
+                ..
+            })
+        ));
+        assert!(provider.call_count() >= 1);
+        assert!(channel.sent_messages().await.is_empty());
+    }
+
+    fn chunk(event_type: StreamEventType, text: Option<&str>) -> ProviderStreamChunk {
+        ProviderStreamChunk {
+            event_type,
+            text: text.map(str::to_string),
+            usage: None,
+            error: None,
+            tool_use: None,
+            stop_reason: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn consume_stream_with_reports_every_chunk_in_order() {
+        let items: Vec<Result<ProviderStreamChunk, BlufioError>> = vec![
+            Ok(chunk(StreamEventType::MessageStart, None)),
+            Ok(chunk(StreamEventType::ContentBlockDelta, Some("Hel"))),
+            Ok(chunk(StreamEventType::ContentBlockDelta, Some("lo"))),
+            Ok(chunk(StreamEventType::MessageDelta, None)),
+            Ok(chunk(StreamEventType::MessageStop, None)),
+            // Never reached: consumption stops at MessageStop.
+            Ok(chunk(StreamEventType::ContentBlockDelta, Some("!"))),
+        ];
+        let mut stream: Pin<
+            Box<dyn Stream<Item = Result<ProviderStreamChunk, BlufioError>> + Send>,
+        > = Box::pin(futures::stream::iter(items));
+
+        let mut seen = Vec::new();
+        let mut deltas = String::new();
+        let (text, _, _, _) = consume_stream_with(&mut stream, |c| {
+            seen.push(c.event_type.clone());
+            if let Some(t) = &c.text {
+                deltas.push_str(t);
+            }
+        })
+        .await;
+
+        assert_eq!(
+            seen,
+            vec![
+                StreamEventType::MessageStart,
+                StreamEventType::ContentBlockDelta,
+                StreamEventType::ContentBlockDelta,
+                StreamEventType::MessageDelta,
+                StreamEventType::MessageStop,
+            ]
+        );
+        assert_eq!(deltas, "Hello");
+        assert_eq!(text, "Hello");
+    }
+
+    #[test]
+    fn turn_timeout_defaults_from_config() {
+        let config = blufio_config::model::AgentConfig::default();
+        assert_eq!(config.turn_timeout_secs, 300);
+        assert!(turn_timeout_message(Duration::from_secs(300)).contains("300s"));
+    }
 
     #[test]
     fn extract_chat_id_from_valid_metadata() {
@@ -850,4 +2941,34 @@ mod tests {
         let meta = Some(r#"{"other":"value"}"#.to_string());
         assert_eq!(extract_chat_id_from_metadata(&meta), None);
     }
+
+    #[test]
+    fn extract_chat_id_from_numeric_metadata() {
+        let meta = Some(r#"{"chat_id":12345}"#.to_string());
+        assert_eq!(
+            extract_chat_id_from_metadata(&meta),
+            Some("12345".to_string())
+        );
+        let meta = Some(r#"{"chat_id":-100987}"#.to_string());
+        assert_eq!(
+            extract_chat_id_from_metadata(&meta),
+            Some("-100987".to_string())
+        );
+    }
+
+    #[test]
+    fn extract_chat_id_rejects_non_integer_values() {
+        for meta in [
+            r#"{"chat_id":12.5}"#,
+            r#"{"chat_id":true}"#,
+            r#"{"chat_id":null}"#,
+            r#"{"chat_id":{"id":1}}"#,
+        ] {
+            assert_eq!(
+                extract_chat_id_from_metadata(&Some(meta.to_string())),
+                None,
+                "{meta}"
+            );
+        }
+    }
 }
@@ -0,0 +1,175 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Global limit on concurrent LLM provider requests.
+//!
+//! Many sessions fanning out to the provider at once can exhaust account-wide
+//! rate limits and trigger cascading 429s. [`ProviderLimiter`] caps how many
+//! provider streams are in flight; excess turns queue for a permit instead.
+//! A permit is held until the returned stream is dropped, so the limit covers
+//! the whole response, not just the request that starts it.
+//!
+//! When the provider reports a rate limit with a `retry-after` hint, new
+//! requests are held back until that time passes.
+
+use std::pin::Pin;
+use std::sync::Arc;
+
+use futures::{Stream, StreamExt};
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+use blufio_core::error::{BlufioError, ProviderErrorKind};
+use blufio_core::types::ProviderStreamChunk;
+
+/// Boxed provider stream, as returned by `ProviderAdapter::stream`.
+type ProviderStream = Pin<Box<dyn Stream<Item = Result<ProviderStreamChunk, BlufioError>> + Send>>;
+
+/// Shared limiter for concurrent provider requests.
+///
+/// Cheap to share via `Arc`; every session actor and the agent loop's tool
+/// follow-up calls draw from the same pool.
+pub struct ProviderLimiter {
+    semaphore: Arc<Semaphore>,
+    max_concurrent: usize,
+    /// Earliest time new requests may start, from a provider `retry-after` hint.
+    paused_until: Mutex<Option<Instant>>,
+}
+
+impl ProviderLimiter {
+    /// Creates a limiter allowing `max_concurrent` provider requests at once.
+    ///
+    /// A limit of 0 is treated as 1.
+    pub fn new(max_concurrent: usize) -> Self {
+        let max_concurrent = max_concurrent.max(1);
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_concurrent)),
+            max_concurrent,
+            paused_until: Mutex::new(None),
+        }
+    }
+
+    /// Maximum number of concurrent provider requests.
+    pub fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+    }
+
+    /// Number of permits currently free.
+    pub fn available(&self) -> usize {
+        self.semaphore.available_permits()
+    }
+
+    /// Waits for a free permit, then for any active rate-limit pause to end.
+    pub async fn acquire(&self) -> Result<OwnedSemaphorePermit, BlufioError> {
+        let permit = self
+            .semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| BlufioError::Internal("provider limiter closed".to_string()))?;
+
+        let paused_until = *self.paused_until.lock().await;
+        if let Some(until) = paused_until
+            && until > Instant::now()
+        {
+            debug!(
+                wait_ms = (until - Instant::now()).as_millis() as u64,
+                "waiting for provider rate-limit pause to end"
+            );
+            tokio::time::sleep_until(until).await;
+        }
+        Ok(permit)
+    }
+
+    /// Records a provider error; a rate limit with a `retry-after` hint pauses
+    /// new requests until that time.
+    pub async fn observe_error(&self, err: &BlufioError) {
+        if let BlufioError::Provider {
+            kind: ProviderErrorKind::RateLimited,
+            context,
+            ..
+        } = err
+            && let Some(retry_after) = context.retry_after
+        {
+            let until = Instant::now() + retry_after;
+            let mut paused_until = self.paused_until.lock().await;
+            if paused_until.is_none_or(|current| current < until) {
+                warn!(
+                    retry_after_secs = retry_after.as_secs_f64(),
+                    "provider rate limited, pausing new requests"
+                );
+                *paused_until = Some(until);
+            }
+        }
+    }
+}
+
+/// Ties `permit` to `stream` so the permit is released when the stream is dropped.
+///
+/// Returns `stream` unchanged when there is no permit (limiter disabled).
+pub fn hold_permit(stream: ProviderStream, permit: Option<OwnedSemaphorePermit>) -> ProviderStream {
+    match permit {
+        Some(permit) => Box::pin(stream.map(move |chunk| {
+            let _permit = &permit;
+            chunk
+        })),
+        None => stream,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use blufio_core::error::ErrorContext;
+
+    #[tokio::test]
+    async fn permit_is_held_until_stream_is_dropped() {
+        let limiter = ProviderLimiter::new(1);
+        let permit = limiter.acquire().await.unwrap();
+        let stream = hold_permit(Box::pin(futures::stream::empty()), Some(permit));
+        assert_eq!(limiter.available(), 0);
+
+        drop(stream);
+        assert_eq!(limiter.available(), 1);
+    }
+
+    #[tokio::test]
+    async fn zero_limit_allows_one_request() {
+        let limiter = ProviderLimiter::new(0);
+        assert_eq!(limiter.max_concurrent(), 1);
+        assert_eq!(limiter.available(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retry_after_pauses_new_requests() {
+        let limiter = ProviderLimiter::new(2);
+        let err = BlufioError::Provider {
+            kind: ProviderErrorKind::RateLimited,
+            context: ErrorContext {
+                retry_after: Some(Duration::from_secs(3)),
+                ..Default::default()
+            },
+            source: None,
+        };
+        limiter.observe_error(&err).await;
+
+        let started = Instant::now();
+        let _permit = limiter.acquire().await.unwrap();
+        assert!(started.elapsed() >= Duration::from_secs(3));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn other_errors_do_not_pause() {
+        let limiter = ProviderLimiter::new(1);
+        limiter
+            .observe_error(&BlufioError::provider_timeout("test"))
+            .await;
+
+        let started = Instant::now();
+        let _permit = limiter.acquire().await.unwrap();
+        assert_eq!(started.elapsed(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,228 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Content moderation for user input and agent replies.
+//!
+//! The agent loop runs the installed [`ModerationAdapter`] over each inbound
+//! message before the LLM sees it and over the final response before it is
+//! sent. [`moderate`] turns a verdict into a decision: `Flag` is logged and
+//! passes, `Block` is logged and stops the content. A failing backend does
+//! not block the conversation.
+//!
+//! [`KeywordModerator`] is the built-in backend, blocking the phrases listed
+//! in `[moderation] blocked_terms`.
+
+use async_trait::async_trait;
+use blufio_config::model::ModerationConfig;
+use blufio_core::ModerationAdapter;
+use blufio_core::error::BlufioError;
+use blufio_core::traits::PluginAdapter;
+use blufio_core::types::{AdapterType, HealthStatus, MessageContent, ModerationVerdict};
+use tracing::warn;
+
+/// Blocks text containing any configured phrase, ignoring case.
+pub struct KeywordModerator {
+    /// Lowercased blocked phrases.
+    terms: Vec<String>,
+}
+
+impl KeywordModerator {
+    /// Creates a moderator blocking `terms`. Blank terms are ignored.
+    pub fn new(terms: &[String]) -> Self {
+        Self {
+            terms: terms
+                .iter()
+                .map(|t| t.trim().to_lowercase())
+                .filter(|t| !t.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Creates a moderator from config, or `None` when no terms are blocked.
+    pub fn from_config(config: &ModerationConfig) -> Option<Self> {
+        let moderator = Self::new(&config.blocked_terms);
+        (!moderator.terms.is_empty()).then_some(moderator)
+    }
+}
+
+#[async_trait]
+impl PluginAdapter for KeywordModerator {
+    fn name(&self) -> &str {
+        "keyword-moderator"
+    }
+    fn version(&self) -> semver::Version {
+        semver::Version::new(0, 1, 0)
+    }
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::Moderation
+    }
+    async fn health_check(&self) -> Result<HealthStatus, BlufioError> {
+        Ok(HealthStatus::Healthy)
+    }
+    async fn shutdown(&self) -> Result<(), BlufioError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ModerationAdapter for KeywordModerator {
+    async fn check(&self, text: &str) -> Result<ModerationVerdict, BlufioError> {
+        let text = text.to_lowercase();
+        Ok(
+            match self.terms.iter().find(|t| text.contains(t.as_str())) {
+                Some(term) => ModerationVerdict::Block(format!("blocked term \"{term}\"")),
+                None => ModerationVerdict::Allow,
+            },
+        )
+    }
+}
+
+/// Where in the turn a moderation check happens, for logging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModerationStage {
+    /// User input, before the LLM call.
+    Input,
+    /// Final agent response, before it is sent.
+    Output,
+}
+
+impl ModerationStage {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Input => "input",
+            Self::Output => "output",
+        }
+    }
+}
+
+/// Checks `text` and returns the block reason if it must not pass.
+///
+/// Flagged content passes with a warning. Moderation errors are logged and
+/// the content passes, so an unavailable backend does not take the agent down.
+pub async fn moderate(
+    moderator: &(dyn ModerationAdapter + Send + Sync),
+    text: &str,
+    stage: ModerationStage,
+) -> Option<String> {
+    match moderator.check(text).await {
+        Ok(ModerationVerdict::Allow) => None,
+        Ok(ModerationVerdict::Flag) => {
+            warn!(stage = stage.as_str(), "content flagged by moderation");
+            None
+        }
+        Ok(ModerationVerdict::Block(reason)) => {
+            warn!(
+                stage = stage.as_str(),
+                reason = reason.as_str(),
+                "content blocked by moderation"
+            );
+            Some(reason)
+        }
+        Err(e) => {
+            warn!(
+                stage = stage.as_str(),
+                error = %e,
+                "moderation check failed, allowing content"
+            );
+            None
+        }
+    }
+}
+
+/// The user-written text of an inbound message, if it has any.
+pub fn inbound_text(content: &MessageContent) -> Option<&str> {
+    match content {
+        MessageContent::Text(text) => Some(text),
+        MessageContent::Image { caption, .. } => caption.as_deref(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Moderator with a fixed verdict per keyword.
+    struct ScriptedModerator;
+
+    #[async_trait]
+    impl PluginAdapter for ScriptedModerator {
+        fn name(&self) -> &str {
+            "scripted-moderator"
+        }
+        fn version(&self) -> semver::Version {
+            semver::Version::new(0, 1, 0)
+        }
+        fn adapter_type(&self) -> AdapterType {
+            AdapterType::Moderation
+        }
+        async fn health_check(&self) -> Result<HealthStatus, BlufioError> {
+            Ok(HealthStatus::Healthy)
+        }
+        async fn shutdown(&self) -> Result<(), BlufioError> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl ModerationAdapter for ScriptedModerator {
+        async fn check(&self, text: &str) -> Result<ModerationVerdict, BlufioError> {
+            if text.contains("forbidden") {
+                Ok(ModerationVerdict::Block("forbidden word".to_string()))
+            } else if text.contains("dubious") {
+                Ok(ModerationVerdict::Flag)
+            } else if text.contains("crash") {
+                Err(BlufioError::Internal("moderation backend down".to_string()))
+            } else {
+                Ok(ModerationVerdict::Allow)
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn only_block_stops_content() {
+        let m = ScriptedModerator;
+        assert_eq!(
+            moderate(&m, "a forbidden thing", ModerationStage::Input).await,
+            Some("forbidden word".to_string())
+        );
+        assert_eq!(moderate(&m, "hello", ModerationStage::Input).await, None);
+        assert_eq!(moderate(&m, "dubious", ModerationStage::Output).await, None);
+        assert_eq!(moderate(&m, "crash", ModerationStage::Output).await, None);
+    }
+
+    #[tokio::test]
+    async fn keyword_moderator_blocks_configured_terms() {
+        let config = ModerationConfig {
+            blocked_terms: vec!["Wire Transfer".to_string(), "  ".to_string()],
+            ..Default::default()
+        };
+        let m = KeywordModerator::from_config(&config).unwrap();
+        assert_eq!(
+            m.check("please start a WIRE TRANSFER now").await.unwrap(),
+            ModerationVerdict::Block("blocked term \"wire transfer\"".to_string())
+        );
+        assert_eq!(m.check("hello").await.unwrap(), ModerationVerdict::Allow);
+
+        assert!(KeywordModerator::from_config(&ModerationConfig::default()).is_none());
+    }
+
+    #[test]
+    fn inbound_text_covers_text_and_captions() {
+        assert_eq!(
+            inbound_text(&MessageContent::Text("hi".to_string())),
+            Some("hi")
+        );
+        let image = MessageContent::Image {
+            data: vec![],
+            mime_type: "image/png".to_string(),
+            caption: Some("look".to_string()),
+        };
+        assert_eq!(inbound_text(&image), Some("look"));
+        let voice = MessageContent::Voice {
+            data: vec![],
+            duration_secs: None,
+        };
+        assert_eq!(inbound_text(&voice), None);
+    }
+}
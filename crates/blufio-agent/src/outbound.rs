@@ -0,0 +1,252 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Persistent retry queue for outbound channel sends.
+//!
+//! A reply the channel fails to accept (for example during a network blip)
+//! is stored in the `outbound` queue instead of being dropped. A background
+//! task re-sends the oldest entry with exponential backoff until the channel
+//! accepts it or the entry reaches its attempt limit and is left as `failed`.
+//! Entries are delivered in order: a retrying entry holds back newer ones.
+//!
+//! Entries interrupted mid-send by a crash are returned to `pending` by the
+//! startup queue recovery, like inbound entries.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use blufio_config::model::OutboundConfig;
+use blufio_core::types::OutboundMessage;
+use blufio_core::{BlufioError, ChannelAdapter, StorageAdapter};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+/// Queue name used for outbound entries.
+pub const OUTBOUND_QUEUE: &str = "outbound";
+
+/// Result of one delivery attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryOutcome {
+    /// The queue had no pending entries.
+    Empty,
+    /// The channel accepted the message.
+    Delivered,
+    /// The send failed and the entry will be retried. Holds the number of
+    /// failed attempts so far.
+    Retrying(u32),
+    /// The send failed on the last allowed attempt.
+    DeadLettered,
+}
+
+/// Stores failed sends and retries them over a channel adapter.
+pub struct OutboundQueue {
+    storage: Arc<dyn StorageAdapter + Send + Sync>,
+    channel: Arc<dyn ChannelAdapter + Send + Sync>,
+    base_backoff: Duration,
+    max_backoff: Duration,
+    poll_interval: Duration,
+}
+
+impl OutboundQueue {
+    /// Creates a queue with the backoff settings from `config`.
+    pub fn new(
+        storage: Arc<dyn StorageAdapter + Send + Sync>,
+        channel: Arc<dyn ChannelAdapter + Send + Sync>,
+        config: &OutboundConfig,
+    ) -> Self {
+        Self {
+            storage,
+            channel,
+            base_backoff: Duration::from_millis(config.base_backoff_ms),
+            max_backoff: Duration::from_millis(config.max_backoff_ms),
+            poll_interval: Duration::from_millis(config.poll_interval_ms),
+        }
+    }
+
+    /// Stores a message for later delivery. Returns the queue entry ID.
+    ///
+    /// A message without an idempotency key gets one, so a channel that
+    /// supports keys will not deliver it twice if an earlier send reached
+    /// the user despite reporting an error.
+    pub async fn enqueue(&self, msg: &OutboundMessage) -> Result<i64, BlufioError> {
+        let mut msg = msg.clone();
+        if msg.idempotency_key.is_none() {
+            msg.idempotency_key = Some(format!("outbound-{}", uuid::Uuid::new_v4()));
+        }
+        let payload = serde_json::to_string(&msg).map_err(|e| {
+            BlufioError::Internal(format!("failed to encode outbound message: {e}"))
+        })?;
+        self.storage.enqueue(OUTBOUND_QUEUE, &payload).await
+    }
+
+    /// Tries to deliver the oldest pending entry once.
+    pub async fn deliver_next(&self) -> Result<DeliveryOutcome, BlufioError> {
+        let Some(entry) = self.storage.dequeue(OUTBOUND_QUEUE).await? else {
+            return Ok(DeliveryOutcome::Empty);
+        };
+
+        let sent = match serde_json::from_str::<OutboundMessage>(&entry.payload) {
+            Ok(msg) => self.channel.send(msg).await.map(|_| ()),
+            Err(e) => Err(BlufioError::Internal(format!(
+                "invalid outbound queue payload: {e}"
+            ))),
+        };
+
+        match sent {
+            Ok(()) => {
+                self.storage.ack(entry.id).await?;
+                debug!(entry_id = entry.id, "queued outbound message delivered");
+                Ok(DeliveryOutcome::Delivered)
+            }
+            Err(e) => {
+                self.storage.fail(entry.id).await?;
+                let attempts = entry.attempts + 1;
+                if attempts >= entry.max_attempts {
+                    warn!(
+                        entry_id = entry.id,
+                        attempts,
+                        error = %e,
+                        "outbound message dead-lettered"
+                    );
+                    Ok(DeliveryOutcome::DeadLettered)
+                } else {
+                    debug!(entry_id = entry.id, attempts, error = %e, "outbound send failed");
+                    Ok(DeliveryOutcome::Retrying(attempts as u32))
+                }
+            }
+        }
+    }
+
+    /// Delivers queued messages until `cancel` is triggered.
+    pub async fn run(&self, cancel: CancellationToken) {
+        info!("outbound retry queue running");
+        loop {
+            let delay = match self.deliver_next().await {
+                Ok(DeliveryOutcome::Delivered | DeliveryOutcome::DeadLettered) => Duration::ZERO,
+                Ok(DeliveryOutcome::Retrying(attempts)) => self.backoff(attempts),
+                Ok(DeliveryOutcome::Empty) => self.poll_interval,
+                Err(e) => {
+                    warn!(error = %e, "outbound queue delivery error");
+                    self.poll_interval
+                }
+            };
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = tokio::time::sleep(delay) => {}
+            }
+        }
+    }
+
+    /// Delay before the retry that follows `attempts` failures.
+    fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.base_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blufio_test_utils::MockChannel;
+
+    async fn make_queue(
+        channel: MockChannel,
+    ) -> (
+        OutboundQueue,
+        Arc<dyn StorageAdapter + Send + Sync>,
+        tempfile::TempDir,
+    ) {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let storage_config = blufio_config::model::StorageConfig {
+            database_path: temp_dir
+                .path()
+                .join("test.db")
+                .to_string_lossy()
+                .to_string(),
+            wal_mode: true,
+            ..Default::default()
+        };
+        let storage = blufio_storage::SqliteStorage::new(storage_config);
+        storage.initialize().await.unwrap();
+        let storage: Arc<dyn StorageAdapter + Send + Sync> = Arc::new(storage);
+        let config = OutboundConfig {
+            enabled: true,
+            base_backoff_ms: 1,
+            max_backoff_ms: 5,
+            poll_interval_ms: 1,
+        };
+        let queue = OutboundQueue::new(storage.clone(), Arc::new(channel), &config);
+        (queue, storage, temp_dir)
+    }
+
+    fn message(content: &str) -> OutboundMessage {
+        OutboundMessage {
+            session_id: Some("s1".to_string()),
+            channel: "mock".to_string(),
+            content: content.to_string(),
+            reply_to: None,
+            parse_mode: None,
+            metadata: Some(r#"{"chat_id":"42"}"#.to_string()),
+            idempotency_key: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn transient_failures_are_retried_until_delivered() {
+        let channel = MockChannel::new().with_send_failures(2);
+        let (queue, _storage, _temp) = make_queue(channel.clone()).await;
+        queue.enqueue(&message("hello")).await.unwrap();
+
+        let cancel = CancellationToken::new();
+        let runner = {
+            let cancel = cancel.clone();
+            async move { queue.run(cancel).await }
+        };
+        let handle = tokio::spawn(runner);
+        assert!(channel.wait_for_sent(1, Duration::from_secs(5)).await);
+        cancel.cancel();
+        handle.await.unwrap();
+
+        let sent = channel.sent_messages().await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].content, "hello");
+        assert_eq!(sent[0].metadata.as_deref(), Some(r#"{"chat_id":"42"}"#));
+        assert!(sent[0].idempotency_key.is_some());
+    }
+
+    #[tokio::test]
+    async fn permanent_failure_is_dead_lettered() {
+        let channel = MockChannel::new().with_send_failures(usize::MAX);
+        let (queue, storage, _temp) = make_queue(channel.clone()).await;
+        queue.enqueue(&message("lost")).await.unwrap();
+
+        assert_eq!(
+            queue.deliver_next().await.unwrap(),
+            DeliveryOutcome::Retrying(1)
+        );
+        assert_eq!(
+            queue.deliver_next().await.unwrap(),
+            DeliveryOutcome::Retrying(2)
+        );
+        assert_eq!(
+            queue.deliver_next().await.unwrap(),
+            DeliveryOutcome::DeadLettered
+        );
+        assert_eq!(queue.deliver_next().await.unwrap(), DeliveryOutcome::Empty);
+        assert!(storage.dequeue(OUTBOUND_QUEUE).await.unwrap().is_none());
+        assert!(channel.sent_messages().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn backoff_doubles_up_to_the_cap() {
+        let (mut queue, _storage, _temp) = make_queue(MockChannel::new()).await;
+        queue.base_backoff = Duration::from_millis(100);
+        queue.max_backoff = Duration::from_millis(350);
+        assert_eq!(queue.backoff(1), Duration::from_millis(100));
+        assert_eq!(queue.backoff(2), Duration::from_millis(200));
+        assert_eq!(queue.backoff(3), Duration::from_millis(350));
+        assert_eq!(queue.backoff(40), Duration::from_millis(350));
+    }
+}
@@ -0,0 +1,191 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! PII redaction for persisted messages and memory extraction.
+//!
+//! [`PiiRedactor`] applies [`blufio_security::redact_pii`] to the kinds of
+//! content enabled in [`PiiRedactionConfig`]. When configured with a vault,
+//! the unredacted text of each redacted message is kept there, encrypted.
+
+use std::sync::Arc;
+
+use blufio_config::model::PiiRedactionConfig;
+use blufio_core::types::{ContentBlock, ProviderMessage};
+use blufio_vault::Vault;
+use tracing::{debug, warn};
+
+/// Kind of stored content, each with its own redaction switch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactionField {
+    /// Inbound user messages.
+    UserMessage,
+    /// Final assistant responses.
+    AssistantMessage,
+    /// Tool results fed back to the LLM.
+    ToolResult,
+}
+
+/// Redacts PII from content before it is persisted or mined for memories.
+pub struct PiiRedactor {
+    config: PiiRedactionConfig,
+    vault: Option<Arc<Vault>>,
+}
+
+impl PiiRedactor {
+    /// Creates a redactor for the given config.
+    pub fn new(config: PiiRedactionConfig) -> Self {
+        Self {
+            config,
+            vault: None,
+        }
+    }
+
+    /// Creates a redactor from config, or `None` when nothing is redacted.
+    pub fn from_config(config: &PiiRedactionConfig) -> Option<Self> {
+        config.any_enabled().then(|| Self::new(config.clone()))
+    }
+
+    /// Keeps unredacted originals in `vault` when `keep_encrypted_original` is set.
+    pub fn with_vault(mut self, vault: Arc<Vault>) -> Self {
+        self.vault = Some(vault);
+        self
+    }
+
+    fn field_enabled(&self, field: RedactionField) -> bool {
+        match field {
+            RedactionField::UserMessage => self.config.user_messages,
+            RedactionField::AssistantMessage => self.config.assistant_messages,
+            RedactionField::ToolResult => self.config.tool_results,
+        }
+    }
+
+    /// Returns the text to persist for message `message_id`.
+    ///
+    /// Text of a disabled field, or without PII, is returned unchanged. When
+    /// the original is kept, a vault failure is logged and the redacted text
+    /// is still returned, so PII never reaches storage unredacted.
+    pub async fn redact_for_storage(
+        &self,
+        field: RedactionField,
+        message_id: &str,
+        text: &str,
+    ) -> String {
+        if !self.field_enabled(field) {
+            return text.to_string();
+        }
+        let redacted = blufio_security::redact_pii(text);
+        if redacted == text {
+            return redacted;
+        }
+        debug!(message_id, field = ?field, "redacted PII before persistence");
+
+        if self.config.keep_encrypted_original {
+            match &self.vault {
+                Some(vault) => {
+                    if let Err(e) = vault.store_secret(&original_key(message_id), text).await {
+                        warn!(message_id, error = %e, "failed to keep encrypted original");
+                    }
+                }
+                None => warn!(
+                    message_id,
+                    "keep_encrypted_original is set but no vault is unlocked; original discarded"
+                ),
+            }
+        }
+        redacted
+    }
+
+    /// Redacts the text blocks of messages passed to memory extraction.
+    pub fn redact_for_memory(&self, messages: &mut [ProviderMessage]) {
+        if !self.config.memory_extraction {
+            return;
+        }
+        for block in messages.iter_mut().flat_map(|m| m.content.iter_mut()) {
+            if let ContentBlock::Text { text } = block {
+                *text = blufio_security::redact_pii(text);
+            }
+        }
+    }
+
+    /// Redacts extracted facts before they are stored as memories.
+    pub fn redact_memory_facts(&self, facts: &[String]) -> Vec<String> {
+        if !self.config.memory_extraction {
+            return facts.to_vec();
+        }
+        facts
+            .iter()
+            .map(|f| blufio_security::redact_pii(f))
+            .collect()
+    }
+}
+
+/// Vault entry name for the unredacted original of a message.
+pub fn original_key(message_id: &str) -> String {
+    format!("pii-original/{message_id}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redactor(config: PiiRedactionConfig) -> PiiRedactor {
+        PiiRedactor::new(config)
+    }
+
+    #[test]
+    fn from_config_requires_an_enabled_field() {
+        assert!(PiiRedactor::from_config(&PiiRedactionConfig::default()).is_none());
+        let config = PiiRedactionConfig {
+            tool_results: true,
+            ..Default::default()
+        };
+        assert!(PiiRedactor::from_config(&config).is_some());
+    }
+
+    #[tokio::test]
+    async fn redacts_only_enabled_fields() {
+        let r = redactor(PiiRedactionConfig {
+            user_messages: true,
+            ..Default::default()
+        });
+        let text = "mail me at jane.doe@example.com";
+
+        let user = r
+            .redact_for_storage(RedactionField::UserMessage, "m1", text)
+            .await;
+        assert!(!user.contains("jane.doe@example.com"));
+        assert!(user.contains("[EMAIL]"));
+
+        let assistant = r
+            .redact_for_storage(RedactionField::AssistantMessage, "m2", text)
+            .await;
+        assert_eq!(assistant, text);
+    }
+
+    #[test]
+    fn memory_input_is_redacted_when_enabled() {
+        let mut messages = vec![ProviderMessage {
+            role: "user".to_string(),
+            content: vec![ContentBlock::Text {
+                text: "I'm jane.doe@example.com".to_string(),
+            }],
+        }];
+        redactor(PiiRedactionConfig::default()).redact_for_memory(&mut messages);
+        assert!(
+            matches!(&messages[0].content[0], ContentBlock::Text { text } if text.contains('@'))
+        );
+
+        let r = redactor(PiiRedactionConfig {
+            memory_extraction: true,
+            ..Default::default()
+        });
+        r.redact_for_memory(&mut messages);
+        assert!(
+            matches!(&messages[0].content[0], ContentBlock::Text { text } if text.contains("[EMAIL]"))
+        );
+        assert_eq!(
+            r.redact_memory_facts(&["User email is jane.doe@example.com".to_string()]),
+            vec!["User email is [EMAIL]".to_string()]
+        );
+    }
+}
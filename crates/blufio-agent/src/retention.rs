@@ -0,0 +1,169 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Periodic retention purge of messages and sessions.
+//!
+//! While `[retention]` is enabled the agent loop calls
+//! [`StorageAdapter::purge_expired`] every `purge_interval_secs`. Expired rows
+//! are soft-deleted first and only removed once the grace period has passed,
+//! so an over-eager policy can be corrected before data is lost.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use blufio_core::error::StorageErrorKind;
+use blufio_core::types::PurgeReport;
+use blufio_core::{BlufioError, StorageAdapter};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+/// Runs one purge as of the current time.
+pub async fn purge_once(
+    storage: &(dyn StorageAdapter + Send + Sync),
+) -> Result<PurgeReport, BlufioError> {
+    let now = chrono::Utc::now()
+        .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+        .to_string();
+    storage.purge_expired(&now).await
+}
+
+/// Purges every `interval` until `cancel` fires.
+///
+/// The first purge runs immediately. Stops early if the backend does not
+/// support retention.
+pub async fn run_purge_loop(
+    storage: Arc<dyn StorageAdapter + Send + Sync>,
+    interval: Duration,
+    cancel: CancellationToken,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = cancel.cancelled() => break,
+        }
+        match purge_once(storage.as_ref()).await {
+            Ok(report) if report.is_empty() => debug!("retention purge: nothing expired"),
+            Ok(report) => info!(
+                messages_soft_deleted = report.messages_soft_deleted,
+                sessions_soft_deleted = report.sessions_soft_deleted,
+                messages_purged = report.messages_purged,
+                sessions_purged = report.sessions_purged,
+                "retention purge complete"
+            ),
+            Err(BlufioError::Storage {
+                kind: StorageErrorKind::Unsupported,
+                ..
+            }) => {
+                warn!(
+                    backend = storage.name(),
+                    "storage backend does not support retention; purge disabled"
+                );
+                break;
+            }
+            Err(e) => warn!(error = %e, "retention purge failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blufio_config::model::{RetentionConfig, RetentionPeriods, StorageConfig};
+    use blufio_core::classification::DataClassification;
+    use blufio_core::types::{Message, Session};
+    use blufio_storage::SqliteStorage;
+
+    async fn storage_with_old_and_fresh(
+        dir: &tempfile::TempDir,
+    ) -> Arc<dyn StorageAdapter + Send + Sync> {
+        let storage = SqliteStorage::new(StorageConfig {
+            database_path: dir.path().join("test.db").to_string_lossy().to_string(),
+            ..Default::default()
+        })
+        .with_retention(RetentionConfig {
+            enabled: true,
+            periods: RetentionPeriods {
+                messages: Some(30),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        storage.initialize().await.unwrap();
+
+        let now = chrono::Utc::now();
+        storage
+            .create_session(&Session {
+                id: "s1".to_string(),
+                channel: "cli".to_string(),
+                user_id: None,
+                state: "active".to_string(),
+                metadata: None,
+                created_at: now.to_rfc3339(),
+                updated_at: now.to_rfc3339(),
+                classification: DataClassification::default(),
+            })
+            .await
+            .unwrap();
+        for (id, created_at) in [
+            ("old", now - chrono::Duration::days(45)),
+            ("fresh", now - chrono::Duration::days(1)),
+        ] {
+            storage
+                .insert_message(&Message {
+                    id: id.to_string(),
+                    session_id: "s1".to_string(),
+                    role: "user".to_string(),
+                    content: id.to_string(),
+                    token_count: None,
+                    metadata: None,
+                    created_at: created_at.to_rfc3339(),
+                    classification: DataClassification::default(),
+                })
+                .await
+                .unwrap();
+        }
+        Arc::new(storage)
+    }
+
+    #[tokio::test]
+    async fn purge_once_soft_deletes_expired_messages() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage_with_old_and_fresh(&dir).await;
+
+        let report = purge_once(storage.as_ref()).await.unwrap();
+        assert_eq!(report.messages_soft_deleted, 1);
+
+        let remaining = storage.get_messages("s1", None).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, "fresh");
+    }
+
+    #[tokio::test]
+    async fn purge_loop_runs_until_cancelled() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage_with_old_and_fresh(&dir).await;
+        let cancel = CancellationToken::new();
+
+        let handle = tokio::spawn(run_purge_loop(
+            storage.clone(),
+            Duration::from_secs(3600),
+            cancel.clone(),
+        ));
+        // The first tick fires immediately.
+        for _ in 0..50 {
+            if storage.get_messages("s1", None).await.unwrap().len() == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(storage.get_messages("s1", None).await.unwrap().len(), 1);
+
+        cancel.cancel();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("purge loop should stop on cancel")
+            .unwrap();
+    }
+}
@@ -11,13 +11,16 @@
 //! - **Budget tracker**: Pre-call budget gate to enforce daily/monthly caps
 //! - **Cost ledger**: Post-call cost recording with full token breakdown
 
+use std::collections::HashMap;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
 
 use blufio_context::ContextEngine;
 use blufio_core::error::BlufioError;
-use blufio_core::types::{InboundMessage, Message, ProviderStreamChunk, TokenUsage, ToolUseData};
+use blufio_core::types::{
+    InboundMessage, Message, ProviderMessage, ProviderStreamChunk, TokenUsage, ToolUseData,
+};
 use blufio_core::{ProviderAdapter, StorageAdapter};
 use blufio_cost::BudgetTracker;
 use blufio_cost::CostLedger;
@@ -32,6 +35,8 @@ use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
 use crate::context;
+use crate::limiter::{self, ProviderLimiter};
+use crate::redaction::{PiiRedactor, RedactionField};
 
 /// Maximum number of tool call iterations before forcing a text response.
 pub const MAX_TOOL_ITERATIONS: usize = 10;
@@ -116,10 +121,22 @@ pub struct SessionActorConfig {
     /// Optional injection defense pipeline for L1/L4/L5 screening.
     pub injection_pipeline:
         Option<Arc<tokio::sync::Mutex<blufio_injection::pipeline::InjectionPipeline>>>,
+    /// Optional global cap on concurrent provider requests.
+    pub provider_limiter: Option<Arc<ProviderLimiter>>,
     /// Optional HMAC boundary manager for L3 content zone integrity (per-session).
     pub boundary_manager: Option<blufio_injection::boundary::BoundaryManager>,
     /// Whether the channel supports interactive confirmation (from adapter capabilities).
     pub channel_interactive: bool,
+    /// Deadline for each tool invocation (None = unlimited).
+    pub tool_timeout: Option<Duration>,
+    /// Most tool calls of one turn that run concurrently.
+    pub max_parallel_tools: usize,
+    /// PII redaction before persistence and memory extraction (None = store as-is).
+    pub pii_redactor: Option<Arc<PiiRedactor>>,
+    /// Named system prompts selectable with `/persona` (from `agent.personas`).
+    pub personas: HashMap<String, String>,
+    /// Persona already chosen for this session (see [`persona_from_metadata`]).
+    pub persona: Option<String>,
 }
 
 /// Manages the state and message processing for a single conversation session.
@@ -156,6 +173,9 @@ pub struct SessionActor {
     routing_enabled: bool,
     /// Last routing decision for cost recording in persist_response.
     last_routing_decision: Option<RoutingDecision>,
+    /// Messages of the last request sent by `handle_message`; the agent loop
+    /// extends them with tool calls and results for follow-up requests.
+    last_request_messages: Vec<ProviderMessage>,
     /// Timestamp of last message received -- for idle extraction detection.
     last_message_at: Option<chrono::DateTime<chrono::Utc>>,
     /// Idle timeout for triggering extraction (from config).
@@ -181,12 +201,26 @@ pub struct SessionActor {
     /// Optional injection defense pipeline for L1/L4/L5 screening.
     injection_pipeline:
         Option<Arc<tokio::sync::Mutex<blufio_injection::pipeline::InjectionPipeline>>>,
+    /// Optional global cap on concurrent provider requests.
+    provider_limiter: Option<Arc<ProviderLimiter>>,
     /// Optional HMAC boundary manager for L3 content zone integrity (per-session).
     boundary_manager: Option<blufio_injection::boundary::BoundaryManager>,
     /// Whether the last L1 scan flagged the input (for cross-layer escalation).
     flagged_input: bool,
     /// Whether the channel supports interactive confirmation (HITL prompts).
     channel_interactive: bool,
+    /// Deadline for each tool invocation (None = unlimited).
+    tool_timeout: Option<Duration>,
+    /// Most tool calls of one turn that run concurrently.
+    max_parallel_tools: usize,
+    /// PII redaction before persistence and memory extraction (None = store as-is).
+    pii_redactor: Option<Arc<PiiRedactor>>,
+    /// Named system prompts selectable with `/persona`.
+    personas: HashMap<String, String>,
+    /// Active persona; `None` uses the configured system prompt.
+    persona: Option<String>,
+    /// Serializes this session's turns; held for the whole turn by the driver.
+    turn_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl SessionActor {
@@ -208,6 +242,7 @@ impl SessionActor {
             default_max_tokens: config.default_max_tokens,
             routing_enabled: config.routing_enabled,
             last_routing_decision: None,
+            last_request_messages: Vec::new(),
             last_message_at: None,
             idle_timeout: Duration::from_secs(config.idle_timeout_secs),
             tool_registry: config.tool_registry,
@@ -220,9 +255,16 @@ impl SessionActor {
             fallback_chain: config.fallback_chain,
             event_bus: config.event_bus,
             injection_pipeline: config.injection_pipeline,
+            provider_limiter: config.provider_limiter,
             boundary_manager: config.boundary_manager,
             flagged_input: false,
             channel_interactive: config.channel_interactive,
+            tool_timeout: config.tool_timeout,
+            max_parallel_tools: config.max_parallel_tools,
+            pii_redactor: config.pii_redactor,
+            personas: config.personas,
+            persona: config.persona,
+            turn_lock: Arc::new(tokio::sync::Mutex::new(())),
         }
     }
 
@@ -241,6 +283,22 @@ impl SessionActor {
         &self.channel
     }
 
+    /// Returns the lock that serializes this session's turns.
+    ///
+    /// Shared by clones of the `Arc`, so any driver of this session can wait
+    /// on it. Different sessions have independent locks.
+    pub fn turn_lock(&self) -> Arc<tokio::sync::Mutex<()>> {
+        self.turn_lock.clone()
+    }
+
+    /// Waits until no other turn is in progress for this session, then claims it.
+    ///
+    /// The turn ends when the returned guard is dropped. Overlapping turns are
+    /// queued in arrival order (tokio's mutex is fair).
+    pub async fn begin_turn(&self) -> tokio::sync::OwnedMutexGuard<()> {
+        self.turn_lock.clone().lock_owned().await
+    }
+
     /// Returns the last routing decision (if routing is enabled).
     ///
     /// Used by the agent loop to detect budget downgrades and add
@@ -249,6 +307,65 @@ impl SessionActor {
         self.last_routing_decision.as_ref()
     }
 
+    /// Takes the messages of the request last sent by `handle_message`.
+    ///
+    /// The agent loop uses them as the start of its in-turn conversation
+    /// buffer, so tool follow-ups never re-read history from storage.
+    pub fn take_last_request_messages(&mut self) -> Vec<ProviderMessage> {
+        std::mem::take(&mut self.last_request_messages)
+    }
+
+    /// Handles a `/persona` command and returns the reply to send, or `None`
+    /// if `text` is not a persona command.
+    ///
+    /// `/persona <name>` switches this session's later turns to the named
+    /// system prompt and records the choice on the session row, so it
+    /// survives restarts. `/persona` alone lists the available personas.
+    pub async fn handle_persona_command(
+        &mut self,
+        text: &str,
+    ) -> Result<Option<String>, BlufioError> {
+        let Some(name) = parse_persona_command(text) else {
+            return Ok(None);
+        };
+
+        let mut names: Vec<&str> = self.personas.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        let available = if names.is_empty() {
+            "No personas are configured.".to_string()
+        } else {
+            format!("Available personas: {}.", names.join(", "))
+        };
+
+        if name.is_empty() {
+            let current = match self.persona {
+                Some(ref p) => format!("Current persona: {p}."),
+                None => "Using the default persona.".to_string(),
+            };
+            return Ok(Some(format!("{current} {available}")));
+        }
+        if !self.personas.contains_key(name) {
+            return Ok(Some(format!("Unknown persona '{name}'. {available}")));
+        }
+
+        let metadata = self
+            .storage
+            .get_session(&self.session_id)
+            .await?
+            .and_then(|s| s.metadata);
+        let metadata = metadata_with_persona(metadata.as_deref(), name);
+        self.storage
+            .update_session_metadata(&self.session_id, Some(&metadata))
+            .await?;
+        self.persona = Some(name.to_string());
+        info!(
+            session_id = %self.session_id,
+            persona = name,
+            "session persona switched"
+        );
+        Ok(Some(format!("Switched to persona '{name}'.")))
+    }
+
     /// Handles an inbound message: persists it, checks budget, assembles context,
     /// records compaction costs, and starts streaming.
     ///
@@ -356,12 +473,19 @@ impl SessionActor {
         }
 
         // Persist the inbound user message (with override prefix stripped).
+        let content = match &self.pii_redactor {
+            Some(r) => {
+                r.redact_for_storage(RedactionField::UserMessage, &msg_id, &text_content)
+                    .await
+            }
+            None => text_content.clone(),
+        };
         let now = chrono::Utc::now().to_rfc3339();
         let msg = Message {
             id: msg_id,
             session_id: self.session_id.clone(),
             role: "user".to_string(),
-            content: text_content.clone(),
+            content,
             token_count: None,
             metadata: inbound.metadata.clone(),
             created_at: now,
@@ -392,7 +516,6 @@ impl SessionActor {
             let recent_msgs = self.storage.get_messages(&self.session_id, Some(3)).await?;
             let recent_strings: Vec<String> =
                 recent_msgs.iter().map(|m| m.content.clone()).collect();
-            let recent_refs: Vec<&str> = recent_strings.iter().map(|s| s.as_str()).collect();
 
             // Get budget utilization for downgrade logic.
             let budget_util = {
@@ -401,7 +524,16 @@ impl SessionActor {
             };
 
             // Route using the raw text (which may have the /opus etc prefix).
-            let decision = self.router.route(&raw_text, &recent_refs, budget_util);
+            // Classification may run a synchronous embedding model, so it
+            // runs on the blocking pool rather than the executor.
+            let router = Arc::clone(&self.router);
+            let query = raw_text.clone();
+            let decision = tokio::task::spawn_blocking(move || {
+                let recent_refs: Vec<&str> = recent_strings.iter().map(|s| s.as_str()).collect();
+                router.route(&query, &recent_refs, budget_util)
+            })
+            .await
+            .map_err(|e| BlufioError::Internal(format!("routing spawn_blocking join: {e}")))?;
 
             if decision.downgraded {
                 info!(
@@ -447,6 +579,11 @@ impl SessionActor {
                 model: &model,
                 max_tokens,
                 boundary_manager: self.boundary_manager.as_ref(),
+                system_prompt: self
+                    .persona
+                    .as_ref()
+                    .and_then(|name| self.personas.get(name))
+                    .map(String::as_str),
             })
             .await;
 
@@ -464,6 +601,7 @@ impl SessionActor {
                 assembled.request.tools = Some(registry.tool_definitions());
             }
         }
+        self.last_request_messages = assembled.request.messages.clone();
 
         // Record compaction costs if compaction was triggered during assembly.
         // Compaction is a separate Haiku LLM call that must be recorded with
@@ -513,8 +651,12 @@ impl SessionActor {
         if !assembled.extracted_entities.is_empty()
             && let Some(ref extractor) = self.memory_extractor
         {
+            let entities = match &self.pii_redactor {
+                Some(r) => r.redact_memory_facts(&assembled.extracted_entities),
+                None => assembled.extracted_entities.clone(),
+            };
             match extractor
-                .persist_extracted_entities(&self.session_id, &assembled.extracted_entities)
+                .persist_extracted_entities(&self.session_id, &entities)
                 .await
             {
                 Ok(count) => {
@@ -571,6 +713,13 @@ impl SessionActor {
             }
         }
 
+        // Claim a provider slot when concurrency is limited; the permit is
+        // held by the returned stream until the response is fully consumed.
+        let permit = match &self.provider_limiter {
+            Some(l) => Some(l.acquire().await?),
+            None => None,
+        };
+
         // Check circuit breaker before provider call (if resilience enabled).
         // If primary breaker is open, try fallback providers from fallback_chain.
         if let Some(ref registry) = self.circuit_breaker_registry
@@ -638,7 +787,7 @@ impl SessionActor {
                                 }
                                 self.last_call_was_fallback = true;
                                 self.state = SessionState::Responding;
-                                return Ok(stream);
+                                return Ok(limiter::hold_permit(stream, permit));
                             }
                             Err(e) => {
                                 let trips = e.trips_circuit_breaker();
@@ -667,6 +816,9 @@ impl SessionActor {
                                     }
                                     self.publish_cb_transition(fallback_name, &transition).await;
                                 }
+                                if let Some(ref l) = self.provider_limiter {
+                                    l.observe_error(&e).await;
+                                }
                                 warn!(fallback = %fallback_name, error = %e, "fallback provider call failed");
                                 continue; // Try next fallback
                             }
@@ -732,6 +884,10 @@ impl SessionActor {
                 // OTel: Record error status on LLM span.
                 llm_span.record("otel.status_code", "ERROR");
 
+                if let Some(ref l) = self.provider_limiter {
+                    l.observe_error(e).await;
+                }
+
                 if let Some(ref registry) = self.circuit_breaker_registry {
                     // Only count as failure if error trips the circuit breaker.
                     let trips = e.trips_circuit_breaker();
@@ -768,7 +924,7 @@ impl SessionActor {
         // Transition: Processing -> Responding
         self.state = SessionState::Responding;
 
-        Ok(stream)
+        Ok(limiter::hold_permit(stream, permit))
     }
 
     /// Persists the full assistant response text and records message cost.
@@ -802,12 +958,19 @@ impl SessionActor {
             }
         }
 
+        let content = match &self.pii_redactor {
+            Some(r) => {
+                r.redact_for_storage(RedactionField::AssistantMessage, &msg_id, full_text)
+                    .await
+            }
+            None => full_text.to_string(),
+        };
         let now = chrono::Utc::now().to_rfc3339();
         let msg = Message {
             id: msg_id,
             session_id: self.session_id.clone(),
             role: "assistant".to_string(),
-            content: full_text.to_string(),
+            content,
             token_count: usage.as_ref().map(|u| i64::from(u.output_tokens)),
             metadata: None,
             created_at: now,
@@ -830,82 +993,94 @@ impl SessionActor {
         }
 
         // Record cost in ledger and budget tracker.
-        // Use routing decision to track intended vs actual model.
         if let Some(ref usage) = usage {
-            let (model_for_cost, intended_model) = match &self.last_routing_decision {
-                Some(d) => (d.actual_model.clone(), Some(d.intended_model.clone())),
-                None => (self.default_model.clone(), None),
-            };
+            self.record_response_cost(usage).await?;
+        }
 
-            let model_pricing = pricing::get_pricing(&model_for_cost);
-            let cost_usd = pricing::calculate_cost(usage, &model_pricing);
+        // Transition: Responding -> Idle
+        self.state = SessionState::Idle;
 
-            let mut record = CostRecord::new(
-                self.session_id.clone(),
-                model_for_cost.clone(),
-                FeatureType::Message,
-                usage,
-                cost_usd,
-            );
-            if let Some(intended) = intended_model {
-                record = record.with_intended_model(intended);
-            }
-            if self.last_call_was_fallback {
-                record = record.with_fallback(true);
-            }
+        Ok(())
+    }
 
-            self.cost_ledger.record(&record).await?;
+    /// Records the cost of one provider response in the ledger and budget.
+    ///
+    /// Uses the routing decision to track intended vs actual model.
+    /// [`persist_response`](Self::persist_response) calls this; callers that
+    /// make extra requests for the same reply (continuations) call it for
+    /// each of them.
+    pub async fn record_response_cost(&self, usage: &TokenUsage) -> Result<(), BlufioError> {
+        let (model_for_cost, intended_model) = match &self.last_routing_decision {
+            Some(d) => (d.actual_model.clone(), Some(d.intended_model.clone())),
+            None => (self.default_model.clone(), None),
+        };
 
-            {
-                let mut tracker = self.budget_tracker.lock().await;
-                tracker.record_cost(cost_usd);
+        let model_pricing = pricing::get_pricing(&model_for_cost);
+        let cost_usd = pricing::calculate_cost(usage, &model_pricing);
 
-                // Record Prometheus token and budget metrics.
-                #[cfg(feature = "prometheus")]
-                {
-                    blufio_prometheus::record_tokens(
-                        &model_for_cost,
-                        usage.input_tokens,
-                        usage.output_tokens,
-                    );
-                    let remaining = tracker.remaining_daily_budget();
-                    blufio_prometheus::set_budget_remaining(remaining);
-                }
-            }
+        let mut record = CostRecord::new(
+            self.session_id.clone(),
+            model_for_cost.clone(),
+            FeatureType::Message,
+            usage,
+            cost_usd,
+        );
+        if let Some(intended) = intended_model {
+            record = record.with_intended_model(intended);
+        }
+        if self.last_call_was_fallback {
+            record = record.with_fallback(true);
+        }
 
-            info!(
-                session_id = %self.session_id,
-                model = %model_for_cost,
-                intended_model = ?record.intended_model,
-                input_tokens = usage.input_tokens,
-                output_tokens = usage.output_tokens,
-                cache_read_tokens = usage.cache_read_tokens,
-                cost_usd = cost_usd,
-                "message cost recorded"
-            );
+        self.cost_ledger.record(&record).await?;
 
-            // Emit ProviderEvent for audit trail.
-            if let Some(ref bus) = self.event_bus {
-                bus.publish(blufio_bus::events::BusEvent::Provider(
-                    blufio_bus::events::ProviderEvent::Called {
-                        event_id: blufio_bus::events::new_event_id(),
-                        timestamp: blufio_bus::events::now_timestamp(),
-                        provider: self.provider_name.clone(),
-                        model: model_for_cost.clone(),
-                        input_tokens: usage.input_tokens,
-                        output_tokens: usage.output_tokens,
-                        cost_usd,
-                        latency_ms: 0, // latency not tracked at this level
-                        success: true,
-                        session_id: self.session_id.clone(),
-                    },
-                ))
-                .await;
+        {
+            let mut tracker = self.budget_tracker.lock().await;
+            tracker.record_cost(cost_usd);
+
+            // Record Prometheus token and budget metrics.
+            #[cfg(feature = "prometheus")]
+            {
+                blufio_prometheus::record_tokens(
+                    &model_for_cost,
+                    usage.input_tokens,
+                    usage.output_tokens,
+                );
+                blufio_prometheus::record_cache_usage(&model_for_cost, usage);
+                let remaining = tracker.remaining_daily_budget();
+                blufio_prometheus::set_budget_remaining(remaining);
             }
         }
 
-        // Transition: Responding -> Idle
-        self.state = SessionState::Idle;
+        info!(
+            session_id = %self.session_id,
+            model = %model_for_cost,
+            intended_model = ?record.intended_model,
+            input_tokens = usage.input_tokens,
+            output_tokens = usage.output_tokens,
+            cache_read_tokens = usage.cache_read_tokens,
+            cost_usd = cost_usd,
+            "message cost recorded"
+        );
+
+        // Emit ProviderEvent for audit trail.
+        if let Some(ref bus) = self.event_bus {
+            bus.publish(blufio_bus::events::BusEvent::Provider(
+                blufio_bus::events::ProviderEvent::Called {
+                    event_id: blufio_bus::events::new_event_id(),
+                    timestamp: blufio_bus::events::now_timestamp(),
+                    provider: self.provider_name.clone(),
+                    model: model_for_cost.clone(),
+                    input_tokens: usage.input_tokens,
+                    output_tokens: usage.output_tokens,
+                    cost_usd,
+                    latency_ms: 0, // latency not tracked at this level
+                    success: true,
+                    session_id: self.session_id.clone(),
+                },
+            ))
+            .await;
+        }
 
         Ok(())
     }
@@ -922,8 +1097,12 @@ impl SessionActor {
 
     /// Executes a batch of tool calls and returns the results.
     ///
-    /// For each [`ToolUseData`] block, looks up the tool in the registry,
-    /// invokes it, and collects the results as `(tool_use_id, content, is_error)`.
+    /// Each [`ToolUseData`] block is screened (L4/L5) in call order, then
+    /// invoked, and its output scanned (L1) before it is returned as
+    /// `(tool_use_id, output)`. Consecutive parallel-safe tools run
+    /// concurrently, at most `max_parallel_tools` at a time; a tool that is
+    /// not [parallel-safe](blufio_skill::Tool::is_parallel_safe) runs alone.
+    /// Results keep the order of `tool_uses`.
     ///
     /// Transitions state to [`SessionState::ToolExecuting`] during execution
     /// and back to [`SessionState::Processing`] when done.
@@ -931,223 +1110,294 @@ impl SessionActor {
         &mut self,
         tool_uses: &[ToolUseData],
     ) -> Result<Vec<(String, ToolOutput)>, BlufioError> {
-        self.state = SessionState::ToolExecuting;
+        use futures::StreamExt;
 
-        let mut results = Vec::with_capacity(tool_uses.len());
+        self.state = SessionState::ToolExecuting;
 
+        // Blocked calls get their output here; the rest are invoked below.
+        let mut corr_ids = Vec::with_capacity(tool_uses.len());
+        let mut outputs: Vec<Option<(ToolOutput, bool)>> = Vec::with_capacity(tool_uses.len());
         for tu in tool_uses {
             let corr_id = blufio_injection::pipeline::InjectionPipeline::new_correlation_id();
+            outputs.push(
+                self.screen_tool_use(tu, &corr_id)
+                    .await
+                    .map(|blocked| (blocked, false)),
+            );
+            corr_ids.push(corr_id);
+        }
 
-            // L4: Screen tool arguments before execution.
-            if let Some(ref pipeline) = self.injection_pipeline {
-                let mut pipeline_guard = pipeline.lock().await;
-                let screen_result =
-                    pipeline_guard.screen_output(&tu.name, &tu.input, &corr_id, self.flagged_input);
+        let parallel_safe: Vec<bool> = {
+            let registry = self.tool_registry.read().await;
+            tool_uses
+                .iter()
+                .map(|tu| registry.get(&tu.name).is_none_or(|t| t.is_parallel_safe()))
+                .collect()
+        };
+        let runnable: Vec<usize> = (0..tool_uses.len())
+            .filter(|&i| outputs[i].is_none())
+            .collect();
+        let mut next = 0;
+        while next < runnable.len() {
+            let mut end = next + 1;
+            if parallel_safe[runnable[next]] {
+                while end < runnable.len() && parallel_safe[runnable[end]] {
+                    end += 1;
+                }
+            }
+            let batch = &runnable[next..end];
+            let invoked: Vec<(ToolOutput, bool)> =
+                futures::stream::iter(batch.iter().map(|&i| self.invoke_tool(&tool_uses[i])))
+                    .buffered(self.max_parallel_tools.max(1))
+                    .collect()
+                    .await;
+            for (&i, result) in batch.iter().zip(invoked) {
+                outputs[i] = Some(result);
+            }
+            next = end;
+        }
+
+        let mut results = Vec::with_capacity(tool_uses.len());
+        for ((tu, corr_id), slot) in tool_uses.iter().zip(&corr_ids).zip(outputs) {
+            let Some((output, is_open_world)) = slot else {
+                continue;
+            };
+            let output = self
+                .scan_tool_output(tu, output, is_open_world, corr_id)
+                .await;
+            results.push((tu.id.clone(), output));
+        }
+
+        self.state = SessionState::Processing;
+        Ok(results)
+    }
+
+    /// Screens a tool call's arguments (L4) and checks HITL approval (L5).
+    ///
+    /// Returns the error result to send back when the call is blocked.
+    async fn screen_tool_use(&self, tu: &ToolUseData, corr_id: &str) -> Option<ToolOutput> {
+        // L4: Screen tool arguments before execution.
+        if let Some(ref pipeline) = self.injection_pipeline {
+            let mut pipeline_guard = pipeline.lock().await;
+            let screen_result =
+                pipeline_guard.screen_output(&tu.name, &tu.input, corr_id, self.flagged_input);
+
+            // Emit screening events.
+            if !screen_result.events.is_empty() {
+                pipeline_guard.emit_events(screen_result.events).await;
+            }
 
-                // Emit screening events.
-                if !screen_result.events.is_empty() {
-                    pipeline_guard.emit_events(screen_result.events).await;
+            match screen_result.action {
+                blufio_injection::output_screen::ScreeningAction::Block(reason) => {
+                    warn!(
+                        session_id = %self.session_id,
+                        tool = %tu.name,
+                        reason = %reason,
+                        "L4: tool execution blocked"
+                    );
+                    return Some(ToolOutput {
+                        content: format!("Tool {} was blocked.", tu.name),
+                        is_error: true,
+                    });
+                }
+                blufio_injection::output_screen::ScreeningAction::Redact(_redacted) => {
+                    // Continue with original args (credentials redacted in the screening
+                    // result but we still execute with original args -- the redaction
+                    // is for logging purposes). Tool execution proceeds.
+                    debug!(
+                        session_id = %self.session_id,
+                        tool = %tu.name,
+                        "L4: credentials detected and logged"
+                    );
                 }
+                _ => {} // Allow or DryRun -- proceed normally
+            }
 
-                match screen_result.action {
-                    blufio_injection::output_screen::ScreeningAction::Block(reason) => {
+            // L5: Check HITL for external tools.
+            let l4_escalated = pipeline_guard.l4_escalation_triggered();
+            let (decision, hitl_events) = pipeline_guard.check_hitl(
+                &tu.name,
+                &tu.input,
+                &self.session_id,
+                &self.channel,
+                self.channel_interactive,
+                corr_id,
+                l4_escalated,
+                self.flagged_input,
+            );
+
+            if !hitl_events.is_empty() {
+                pipeline_guard.emit_events(hitl_events).await;
+            }
+
+            match decision {
+                blufio_injection::hitl::HitlDecision::Denied(reason) => {
+                    warn!(
+                        session_id = %self.session_id,
+                        tool = %tu.name,
+                        reason = %reason,
+                        "L5: tool execution denied"
+                    );
+                    return Some(ToolOutput {
+                        content: format!("Tool {} was blocked. I'll answer without it.", tu.name),
+                        is_error: true,
+                    });
+                }
+                blufio_injection::hitl::HitlDecision::PendingConfirmation(_req) => {
+                    // For now, auto-deny pending confirmations (full HITL flow
+                    // requires channel adapter implementation).
+                    let (timeout_decision, timeout_event) =
+                        pipeline_guard.handle_hitl_timeout(&self.session_id, &tu.name, corr_id);
+                    pipeline_guard.emit_events(vec![timeout_event]).await;
+                    if matches!(
+                        timeout_decision,
+                        blufio_injection::hitl::HitlDecision::Denied(_)
+                    ) {
                         warn!(
                             session_id = %self.session_id,
                             tool = %tu.name,
-                            reason = %reason,
-                            "L4: tool execution blocked"
-                        );
-                        results.push((
-                            tu.id.clone(),
-                            ToolOutput {
-                                content: format!("Tool {} was blocked.", tu.name),
-                                is_error: true,
-                            },
-                        ));
-                        continue;
-                    }
-                    blufio_injection::output_screen::ScreeningAction::Redact(_redacted) => {
-                        // Continue with original args (credentials redacted in the screening
-                        // result but we still execute with original args -- the redaction
-                        // is for logging purposes). Tool execution proceeds.
-                        debug!(
-                            session_id = %self.session_id,
-                            tool = %tu.name,
-                            "L4: credentials detected and logged"
+                            "L5: tool execution denied (confirmation timeout)"
                         );
+                        return Some(ToolOutput {
+                            content: format!(
+                                "Tool {} was blocked. I'll answer without it.",
+                                tu.name
+                            ),
+                            is_error: true,
+                        });
                     }
-                    _ => {} // Allow or DryRun -- proceed normally
                 }
+                _ => {} // AutoApproved or DryRun -- proceed
+            }
 
-                // L5: Check HITL for external tools.
-                let l4_escalated = pipeline_guard.l4_escalation_triggered();
-                let (decision, hitl_events) = pipeline_guard.check_hitl(
-                    &tu.name,
-                    &tu.input,
-                    &self.session_id,
-                    &self.channel,
-                    self.channel_interactive,
-                    &corr_id,
-                    l4_escalated,
-                    self.flagged_input,
-                );
+            drop(pipeline_guard);
+        }
+        None
+    }
 
-                if !hitl_events.is_empty() {
-                    pipeline_guard.emit_events(hitl_events).await;
-                }
+    /// Invokes one tool under the tool timeout. Returns the output and
+    /// whether the tool is open-world.
+    async fn invoke_tool(&self, tu: &ToolUseData) -> (ToolOutput, bool) {
+        // OTel: Tool execution span with tool name attribute.
+        // Not entered directly -- used via Instrument on the async invoke.
+        let tool_span = tracing::info_span!(
+            "blufio.tool.execute",
+            "tool_name" = %tu.name,
+        );
 
-                match decision {
-                    blufio_injection::hitl::HitlDecision::Denied(reason) => {
+        let registry = self.tool_registry.read().await;
+        match registry.get(&tu.name) {
+            Some(tool) => {
+                let open_world = tool.is_open_world();
+                debug!(
+                    session_id = %self.session_id,
+                    tool = %tu.name,
+                    tool_use_id = %tu.id,
+                    "executing tool"
+                );
+                // Drop the read guard before the async invoke to avoid holding
+                // the lock across an await point.
+                drop(registry);
+                use tracing::Instrument;
+                let invoke = blufio_skill::tool::invoke_validated(tool.as_ref(), tu.input.clone())
+                    .instrument(tool_span);
+                let result = match self.tool_timeout {
+                    Some(limit) => match tokio::time::timeout(limit, invoke).await {
+                        Ok(result) => Some(result),
+                        Err(_) => None,
+                    },
+                    None => Some(invoke.await),
+                };
+                let out = match result {
+                    Some(Ok(output)) => output,
+                    None => {
+                        let secs = self.tool_timeout.unwrap_or_default().as_secs();
                         warn!(
                             session_id = %self.session_id,
                             tool = %tu.name,
-                            reason = %reason,
-                            "L5: tool execution denied"
+                            timeout_secs = secs,
+                            "tool invocation timed out"
                         );
-                        results.push((
-                            tu.id.clone(),
-                            ToolOutput {
-                                content: format!(
-                                    "Tool {} was blocked. I'll answer without it.",
-                                    tu.name
-                                ),
-                                is_error: true,
-                            },
-                        ));
-                        continue;
+                        ToolOutput {
+                            content: format!("tool '{}' timed out after {secs}s", tu.name),
+                            is_error: true,
+                        }
                     }
-                    blufio_injection::hitl::HitlDecision::PendingConfirmation(_req) => {
-                        // For now, auto-deny pending confirmations (full HITL flow
-                        // requires channel adapter implementation).
-                        let (timeout_decision, timeout_event) = pipeline_guard.handle_hitl_timeout(
-                            &self.session_id,
-                            &tu.name,
-                            &corr_id,
+                    Some(Err(e)) => {
+                        warn!(
+                            session_id = %self.session_id,
+                            tool = %tu.name,
+                            error = %e,
+                            "tool invocation failed"
                         );
-                        pipeline_guard.emit_events(vec![timeout_event]).await;
-                        if matches!(
-                            timeout_decision,
-                            blufio_injection::hitl::HitlDecision::Denied(_)
-                        ) {
-                            warn!(
-                                session_id = %self.session_id,
-                                tool = %tu.name,
-                                "L5: tool execution denied (confirmation timeout)"
-                            );
-                            results.push((
-                                tu.id.clone(),
-                                ToolOutput {
-                                    content: format!(
-                                        "Tool {} was blocked. I'll answer without it.",
-                                        tu.name
-                                    ),
-                                    is_error: true,
-                                },
-                            ));
-                            continue;
+                        ToolOutput {
+                            content: format!("Error: {e}"),
+                            is_error: true,
                         }
                     }
-                    _ => {} // AutoApproved or DryRun -- proceed
-                }
-
-                drop(pipeline_guard);
+                };
+                (out, open_world)
             }
+            None => {
+                drop(registry);
+                warn!(
+                    session_id = %self.session_id,
+                    tool = %tu.name,
+                    "tool not found in registry"
+                );
+                (
+                    ToolOutput {
+                        content: format!("Error: tool '{}' not found", tu.name),
+                        is_error: true,
+                    },
+                    false,
+                )
+            }
+        }
+    }
 
-            // OTel: Tool execution span with tool name attribute.
-            // Not entered directly -- used via Instrument on the async invoke.
-            let tool_span = tracing::info_span!(
-                "blufio.tool.execute",
-                "tool_name" = %tu.name,
-            );
-
-            let registry = self.tool_registry.read().await;
-            let (output, is_open_world) = match registry.get(&tu.name) {
-                Some(tool) => {
-                    let open_world = tool.is_open_world();
-                    debug!(
-                        session_id = %self.session_id,
-                        tool = %tu.name,
-                        tool_use_id = %tu.id,
-                        "executing tool"
-                    );
-                    // Drop the read guard before the async invoke to avoid holding
-                    // the lock across an await point.
-                    drop(registry);
-                    use tracing::Instrument;
-                    let out = match tool.invoke(tu.input.clone()).instrument(tool_span).await {
-                        Ok(output) => output,
-                        Err(e) => {
-                            warn!(
-                                session_id = %self.session_id,
-                                tool = %tu.name,
-                                error = %e,
-                                "tool invocation failed"
-                            );
-                            ToolOutput {
-                                content: format!("Error: {e}"),
-                                is_error: true,
-                            }
-                        }
-                    };
-                    (out, open_world)
-                }
-                None => {
-                    drop(registry);
+    /// Scans an open-world tool's output (L1) before it is fed back to the LLM.
+    async fn scan_tool_output(
+        &self,
+        tu: &ToolUseData,
+        output: ToolOutput,
+        is_open_world: bool,
+        corr_id: &str,
+    ) -> ToolOutput {
+        // L1 output scanning: scan tool output from open-world tools (MCP/WASM)
+        // before feeding results back to the LLM. Uses 0.98 blocking threshold.
+        if is_open_world && !output.is_error {
+            if let Some(ref pipeline) = self.injection_pipeline {
+                let pipeline_guard = pipeline.lock().await;
+                let scan = pipeline_guard.scan_input(&output.content, "tool_output", corr_id);
+                if scan.flagged && scan.score >= 0.98 {
                     warn!(
                         session_id = %self.session_id,
                         tool = %tu.name,
-                        "tool not found in registry"
+                        score = scan.score,
+                        "L1: tool output blocked by injection defense"
                     );
-                    (
-                        ToolOutput {
-                            content: format!("Error: tool '{}' not found", tu.name),
-                            is_error: true,
-                        },
-                        false,
-                    )
-                }
-            };
-
-            // L1 output scanning: scan tool output from open-world tools (MCP/WASM)
-            // before feeding results back to the LLM. Uses 0.98 blocking threshold.
-            let output = if is_open_world && !output.is_error {
-                if let Some(ref pipeline) = self.injection_pipeline {
-                    let pipeline_guard = pipeline.lock().await;
-                    let scan = pipeline_guard.scan_input(&output.content, "tool_output", &corr_id);
-                    if scan.flagged && scan.score >= 0.98 {
-                        warn!(
+                    ToolOutput {
+                        content: "[Tool output blocked by injection defense]".to_string(),
+                        is_error: true,
+                    }
+                } else {
+                    if scan.flagged {
+                        debug!(
                             session_id = %self.session_id,
                             tool = %tu.name,
                             score = scan.score,
-                            "L1: tool output blocked by injection defense"
+                            "L1: suspicious patterns in tool output (below blocking threshold)"
                         );
-                        ToolOutput {
-                            content: "[Tool output blocked by injection defense]".to_string(),
-                            is_error: true,
-                        }
-                    } else {
-                        if scan.flagged {
-                            debug!(
-                                session_id = %self.session_id,
-                                tool = %tu.name,
-                                score = scan.score,
-                                "L1: suspicious patterns in tool output (below blocking threshold)"
-                            );
-                        }
-                        output
                     }
-                } else {
                     output
                 }
             } else {
                 output
-            };
-
-            results.push((tu.id.clone(), output));
+            }
+        } else {
+            output
         }
-
-        self.state = SessionState::Processing;
-        Ok(results)
     }
 
     /// Checks if enough idle time has passed since the last message to trigger
@@ -1195,7 +1445,7 @@ impl SessionActor {
         }
 
         // Convert to ProviderMessages for the extractor.
-        let provider_messages: Vec<blufio_core::types::ProviderMessage> = messages
+        let mut provider_messages: Vec<blufio_core::types::ProviderMessage> = messages
             .iter()
             .map(|m| blufio_core::types::ProviderMessage {
                 role: m.role.clone(),
@@ -1204,6 +1454,9 @@ impl SessionActor {
                 }],
             })
             .collect();
+        if let Some(ref redactor) = self.pii_redactor {
+            redactor.redact_for_memory(&mut provider_messages);
+        }
 
         match extractor
             .extract_from_conversation(self.provider.as_ref(), &self.session_id, &provider_messages)
@@ -1295,6 +1548,34 @@ impl SessionActor {
     }
 }
 
+/// Returns the persona name of a `/persona` command (empty when none is
+/// given), or `None` for other text. Accepts a Telegram-style `@botname`
+/// suffix on the command.
+fn parse_persona_command(text: &str) -> Option<&str> {
+    let rest = text.trim().strip_prefix("/persona")?;
+    let rest = match rest.strip_prefix('@') {
+        Some(bot) => bot.split_once(char::is_whitespace).map_or("", |(_, r)| r),
+        None if rest.is_empty() || rest.starts_with(char::is_whitespace) => rest,
+        None => return None,
+    };
+    Some(rest.trim())
+}
+
+/// Reads the persona recorded in a session's metadata JSON.
+pub fn persona_from_metadata(metadata: Option<&str>) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(metadata?).ok()?;
+    value.get("persona")?.as_str().map(String::from)
+}
+
+/// Returns session metadata JSON with `persona` set, keeping other keys.
+fn metadata_with_persona(metadata: Option<&str>, persona: &str) -> String {
+    let mut map = metadata
+        .and_then(|m| serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(m).ok())
+        .unwrap_or_default();
+    map.insert("persona".to_string(), persona.into());
+    serde_json::Value::Object(map).to_string()
+}
+
 /// Maps a model name to an equivalent-tier model for a target provider.
 ///
 /// Preserves the quality tier (high/medium/low) when switching providers:
@@ -1431,6 +1712,7 @@ mod tests {
         let storage_config = blufio_config::model::StorageConfig {
             database_path: db_path.to_string_lossy().to_string(),
             wal_mode: true,
+            ..Default::default()
         };
         let storage = blufio_storage::SqliteStorage::new(storage_config);
         storage.initialize().await.unwrap();
@@ -1445,6 +1727,7 @@ mod tests {
             daily_budget_usd: None,
             monthly_budget_usd: None,
             track_tokens: true,
+            ..Default::default()
         };
         let budget_tracker = Arc::new(tokio::sync::Mutex::new(blufio_cost::BudgetTracker::new(
             &cost_config,
@@ -1508,13 +1791,292 @@ mod tests {
             fallback_chain: Vec::new(),
             event_bus,
             injection_pipeline: None,
+            provider_limiter: None,
             boundary_manager: None,
             channel_interactive: true,
+            tool_timeout: None,
+            max_parallel_tools: 4,
+            pii_redactor: None,
+            personas: Default::default(),
+            persona: None,
         });
 
         (actor, storage, temp_dir)
     }
 
+    #[tokio::test]
+    async fn turns_in_one_session_are_serialized() {
+        let provider: Arc<dyn blufio_core::ProviderAdapter + Send + Sync> =
+            Arc::new(FailingMockProvider);
+        let (actor, _storage, _temp) = make_test_actor(provider, None, None).await;
+        let events = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+
+        let first_guard = actor.begin_turn().await;
+
+        // Second turn must wait for the first to finish.
+        let lock = actor.turn_lock();
+        let events_clone = events.clone();
+        let second = tokio::spawn(async move {
+            let _guard = lock.lock_owned().await;
+            events_clone.lock().await.push("second start");
+        });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        events.lock().await.push("first end");
+        drop(first_guard);
+        second.await.unwrap();
+
+        assert_eq!(*events.lock().await, vec!["first end", "second start"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_tool_times_out_with_error_result() {
+        let provider: Arc<dyn blufio_core::ProviderAdapter + Send + Sync> =
+            Arc::new(FailingMockProvider);
+        let (mut actor, _storage, _temp) = make_test_actor(provider, None, None).await;
+        actor.tool_timeout = Some(Duration::from_secs(2));
+
+        let slow = blufio_test_utils::MockTool::new("slow").with_delay(Duration::from_secs(30));
+        let fast = blufio_test_utils::MockTool::new("fast");
+        fast.add_output("quick result").await;
+        {
+            let mut registry = actor.tool_registry().write().await;
+            registry.register(Arc::new(slow)).unwrap();
+            registry.register(Arc::new(fast)).unwrap();
+        }
+
+        let tool_use = |id: &str, name: &str| ToolUseData {
+            id: id.to_string(),
+            name: name.to_string(),
+            input: serde_json::json!({}),
+        };
+        let results = actor
+            .execute_tools(&[tool_use("tu-1", "slow"), tool_use("tu-2", "fast")])
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, "tu-1");
+        assert!(results[0].1.is_error);
+        assert_eq!(results[0].1.content, "tool 'slow' timed out after 2s");
+        // The next tool gets its own deadline and completes normally.
+        assert_eq!(results[1].0, "tu-2");
+        assert!(!results[1].1.is_error);
+        assert_eq!(results[1].1.content, "quick result");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn independent_tools_run_concurrently_in_call_order() {
+        let provider: Arc<dyn blufio_core::ProviderAdapter + Send + Sync> =
+            Arc::new(FailingMockProvider);
+        let (mut actor, _storage, _temp) = make_test_actor(provider, None, None).await;
+        {
+            let mut registry = actor.tool_registry().write().await;
+            for (name, secs) in [("lookup_a", 3), ("lookup_b", 1), ("lookup_c", 2)] {
+                let tool =
+                    blufio_test_utils::MockTool::new(name).with_delay(Duration::from_secs(secs));
+                tool.add_output(name).await;
+                registry.register(Arc::new(tool)).unwrap();
+            }
+        }
+
+        let tool_use = |id: &str, name: &str| ToolUseData {
+            id: id.to_string(),
+            name: name.to_string(),
+            input: serde_json::json!({}),
+        };
+        let calls = [
+            tool_use("tu-1", "lookup_a"),
+            tool_use("tu-2", "lookup_b"),
+            tool_use("tu-3", "lookup_c"),
+        ];
+        let started = tokio::time::Instant::now();
+        let results = actor.execute_tools(&calls).await.unwrap();
+
+        // The slowest tool sets the latency, not the sum of all three.
+        assert_eq!(started.elapsed(), Duration::from_secs(3));
+        let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["tu-1", "tu-2", "tu-3"]);
+        let contents: Vec<&str> = results.iter().map(|(_, o)| o.content.as_str()).collect();
+        assert_eq!(contents, vec!["lookup_a", "lookup_b", "lookup_c"]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn parallel_unsafe_tools_run_alone_and_cap_limits_concurrency() {
+        let provider: Arc<dyn blufio_core::ProviderAdapter + Send + Sync> =
+            Arc::new(FailingMockProvider);
+        let (mut actor, _storage, _temp) = make_test_actor(provider, None, None).await;
+        actor.max_parallel_tools = 2;
+        {
+            let mut registry = actor.tool_registry().write().await;
+            for name in ["read_a", "read_b", "read_c"] {
+                let tool =
+                    blufio_test_utils::MockTool::new(name).with_delay(Duration::from_secs(1));
+                registry.register(Arc::new(tool)).unwrap();
+            }
+            let write = blufio_test_utils::MockTool::new("write")
+                .with_delay(Duration::from_secs(1))
+                .with_parallel_safe(false);
+            registry.register(Arc::new(write)).unwrap();
+        }
+
+        let tool_use = |id: &str, name: &str| ToolUseData {
+            id: id.to_string(),
+            name: name.to_string(),
+            input: serde_json::json!({}),
+        };
+        let calls = [
+            tool_use("tu-1", "read_a"),
+            tool_use("tu-2", "write"),
+            tool_use("tu-3", "read_a"),
+            tool_use("tu-4", "read_b"),
+            tool_use("tu-5", "read_c"),
+        ];
+        let started = tokio::time::Instant::now();
+        let results = actor.execute_tools(&calls).await.unwrap();
+
+        // read_a | write | read_a + read_b, then read_c (cap of 2).
+        assert_eq!(started.elapsed(), Duration::from_secs(4));
+        let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["tu-1", "tu-2", "tu-3", "tu-4", "tu-5"]);
+    }
+
+    #[tokio::test]
+    async fn different_sessions_have_independent_turn_locks() {
+        let provider: Arc<dyn blufio_core::ProviderAdapter + Send + Sync> =
+            Arc::new(FailingMockProvider);
+        let (a, _storage_a, _temp_a) = make_test_actor(provider.clone(), None, None).await;
+        let (b, _storage_b, _temp_b) = make_test_actor(provider, None, None).await;
+
+        let _a_turn = a.begin_turn().await;
+        let b_turn = tokio::time::timeout(Duration::from_millis(100), b.begin_turn()).await;
+        assert!(b_turn.is_ok(), "session B must not wait on session A");
+    }
+
+    /// A provider whose streams stay open briefly and that records how many
+    /// were open at once.
+    #[derive(Default)]
+    struct ConcurrencyProbeProvider {
+        calls: std::sync::atomic::AtomicUsize,
+        in_flight: Arc<std::sync::atomic::AtomicUsize>,
+        max_in_flight: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    /// Decrements the in-flight count when the probe stream is dropped.
+    struct InFlightGuard(Arc<std::sync::atomic::AtomicUsize>);
+
+    impl Drop for InFlightGuard {
+        fn drop(&mut self) {
+            self.0.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl blufio_core::traits::adapter::PluginAdapter for ConcurrencyProbeProvider {
+        fn name(&self) -> &str {
+            "probe"
+        }
+        fn version(&self) -> semver::Version {
+            semver::Version::new(0, 1, 0)
+        }
+        fn adapter_type(&self) -> blufio_core::types::AdapterType {
+            blufio_core::types::AdapterType::Provider
+        }
+        async fn health_check(
+            &self,
+        ) -> Result<blufio_core::types::HealthStatus, blufio_core::error::BlufioError> {
+            Ok(blufio_core::types::HealthStatus::Healthy)
+        }
+        async fn shutdown(&self) -> Result<(), blufio_core::error::BlufioError> {
+            Ok(())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl blufio_core::ProviderAdapter for ConcurrencyProbeProvider {
+        async fn complete(
+            &self,
+            _req: blufio_core::types::ProviderRequest,
+        ) -> Result<blufio_core::types::ProviderResponse, blufio_core::error::BlufioError> {
+            Err(BlufioError::Internal("probe only streams".to_string()))
+        }
+
+        async fn stream(
+            &self,
+            _req: blufio_core::types::ProviderRequest,
+        ) -> Result<
+            Pin<
+                Box<
+                    dyn futures_core::Stream<
+                            Item = Result<ProviderStreamChunk, blufio_core::error::BlufioError>,
+                        > + Send,
+                >,
+            >,
+            blufio_core::error::BlufioError,
+        > {
+            use std::sync::atomic::Ordering;
+
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+            let guard = InFlightGuard(self.in_flight.clone());
+
+            let chunk = futures::stream::once(async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                let _guard = guard;
+                Ok(ProviderStreamChunk {
+                    event_type: blufio_core::types::StreamEventType::ContentBlockDelta,
+                    text: Some("ok".to_string()),
+                    usage: None,
+                    tool_use: None,
+                    stop_reason: None,
+                    error: None,
+                })
+            });
+            Ok(Box::pin(chunk))
+        }
+    }
+
+    /// Runs one turn on `actor` and drains the response stream.
+    async fn drain_turn(actor: &mut SessionActor) {
+        use futures::StreamExt;
+
+        let sid = actor.session_id().to_string();
+        let mut stream = actor.handle_message(make_inbound(&sid)).await.unwrap();
+        while stream.next().await.is_some() {}
+    }
+
+    #[tokio::test]
+    async fn provider_limiter_serializes_concurrent_turns() {
+        use std::sync::atomic::Ordering;
+
+        let probe = Arc::new(ConcurrencyProbeProvider::default());
+        let limiter = Arc::new(ProviderLimiter::new(1));
+        let (mut a, _storage_a, _temp_a) = make_test_actor(probe.clone(), None, None).await;
+        let (mut b, _storage_b, _temp_b) = make_test_actor(probe.clone(), None, None).await;
+        a.provider_limiter = Some(limiter.clone());
+        b.provider_limiter = Some(limiter.clone());
+
+        tokio::join!(drain_turn(&mut a), drain_turn(&mut b));
+
+        assert_eq!(probe.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(probe.max_in_flight.load(Ordering::SeqCst), 1);
+        assert_eq!(limiter.available(), 1);
+    }
+
+    #[tokio::test]
+    async fn unlimited_turns_overlap_provider_calls() {
+        use std::sync::atomic::Ordering;
+
+        let probe = Arc::new(ConcurrencyProbeProvider::default());
+        let (mut a, _storage_a, _temp_a) = make_test_actor(probe.clone(), None, None).await;
+        let (mut b, _storage_b, _temp_b) = make_test_actor(probe.clone(), None, None).await;
+
+        tokio::join!(drain_turn(&mut a), drain_turn(&mut b));
+
+        assert_eq!(probe.max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
     fn make_cb_registry(dep: &str) -> Arc<CircuitBreakerRegistry> {
         let mut configs = HashMap::new();
         configs.insert(dep.to_string(), CircuitBreakerConfig::default());
@@ -1669,4 +2231,98 @@ mod tests {
         let registry = Arc::new(RwLock::new(ToolRegistry::new()));
         assert_eq!(registry.blocking_read().len(), 0);
     }
+
+    fn system_text(request: &blufio_core::types::ProviderRequest) -> String {
+        request.system_blocks.as_ref().unwrap()[0]["text"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn persona_switch_changes_system_prompt() {
+        let provider = Arc::new(blufio_test_utils::MockProvider::with_responses(vec![
+            "hi".to_string(),
+            "hi".to_string(),
+        ]));
+        let (mut actor, storage, _temp) = make_test_actor(provider.clone(), None, None).await;
+        actor.personas = HashMap::from([
+            ("concise".to_string(), "Answer in one line.".to_string()),
+            ("detailed".to_string(), "Explain thoroughly.".to_string()),
+        ]);
+        let sid = actor.session_id().to_string();
+
+        actor.handle_message(make_inbound(&sid)).await.unwrap();
+        let reply = actor
+            .handle_persona_command("/persona concise")
+            .await
+            .unwrap();
+        assert_eq!(reply.as_deref(), Some("Switched to persona 'concise'."));
+        actor.handle_message(make_inbound(&sid)).await.unwrap();
+
+        let requests = provider.requests();
+        assert_eq!(system_text(&requests[0]), "Test assistant.");
+        assert_eq!(system_text(&requests[1]), "Answer in one line.");
+
+        // The choice is recorded on the session row for resumed actors.
+        let session = storage.get_session(&sid).await.unwrap().unwrap();
+        assert_eq!(
+            persona_from_metadata(session.metadata.as_deref()).as_deref(),
+            Some("concise")
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_persona_is_rejected_with_available_list() {
+        let provider: Arc<dyn blufio_core::ProviderAdapter + Send + Sync> =
+            Arc::new(FailingMockProvider);
+        let (mut actor, storage, _temp) = make_test_actor(provider, None, None).await;
+        actor.personas = HashMap::from([
+            ("detailed".to_string(), "Explain thoroughly.".to_string()),
+            ("concise".to_string(), "Answer in one line.".to_string()),
+        ]);
+
+        let reply = actor
+            .handle_persona_command("/persona pirate")
+            .await
+            .unwrap();
+        assert_eq!(
+            reply.as_deref(),
+            Some("Unknown persona 'pirate'. Available personas: concise, detailed.")
+        );
+        assert_eq!(actor.persona, None);
+        let session = storage
+            .get_session(actor.session_id())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(session.metadata, None);
+
+        assert_eq!(actor.handle_persona_command("hello").await.unwrap(), None);
+        assert_eq!(
+            actor.handle_persona_command("/personality").await.unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_persona_command_forms() {
+        assert_eq!(parse_persona_command("/persona concise"), Some("concise"));
+        assert_eq!(parse_persona_command("  /persona  "), Some(""));
+        assert_eq!(
+            parse_persona_command("/persona@blufio_bot concise"),
+            Some("concise")
+        );
+        assert_eq!(parse_persona_command("/personas"), None);
+        assert_eq!(parse_persona_command("persona concise"), None);
+    }
+
+    #[test]
+    fn metadata_with_persona_keeps_other_keys() {
+        let metadata = metadata_with_persona(Some(r#"{"forked_from":"s1"}"#), "concise");
+        let value: serde_json::Value = serde_json::from_str(&metadata).unwrap();
+        assert_eq!(value["forked_from"], "s1");
+        assert_eq!(value["persona"], "concise");
+        assert_eq!(metadata_with_persona(None, "x"), r#"{"persona":"x"}"#);
+    }
 }
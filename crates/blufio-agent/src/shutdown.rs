@@ -8,13 +8,18 @@
 //! are drained before the process exits.
 
 use std::collections::HashMap;
+use std::future::Future;
 use std::time::Duration;
 
+use blufio_core::error::BlufioError;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
 use crate::session::{SessionActor, SessionState};
 
+/// Upper bound on how long shutdown waits for storage to close (WAL checkpoint included).
+pub const STORAGE_CLOSE_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Installs signal handlers for SIGTERM and SIGINT.
 ///
 /// Returns a [`CancellationToken`] that is cancelled when either signal is received.
@@ -137,6 +142,27 @@ pub async fn drain_sessions(sessions: &HashMap<String, SessionActor>, timeout: D
     }
 }
 
+/// Runs a storage close future, giving up after `timeout`.
+///
+/// A hung WAL checkpoint must not block process exit indefinitely. On timeout
+/// a warning is logged and shutdown proceeds; SQLite recovers any remaining
+/// WAL content on the next open. Errors from the close itself are returned.
+pub async fn close_storage_with_timeout<F>(close: F, timeout: Duration) -> Result<(), BlufioError>
+where
+    F: Future<Output = Result<(), BlufioError>>,
+{
+    match tokio::time::timeout(timeout, close).await {
+        Ok(result) => result,
+        Err(_) => {
+            warn!(
+                timeout_secs = timeout.as_secs_f64(),
+                "storage close timed out, continuing shutdown"
+            );
+            Ok(())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,4 +182,41 @@ mod tests {
         // Should complete immediately with no sessions.
         drain_sessions(&sessions, Duration::from_millis(10)).await;
     }
+
+    #[tokio::test]
+    async fn close_storage_completes_within_timeout() {
+        use blufio_core::StorageAdapter;
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = blufio_storage::SqliteStorage::new(blufio_config::model::StorageConfig {
+            database_path: dir.path().join("close.db").to_string_lossy().into_owned(),
+            ..Default::default()
+        });
+        storage.initialize().await.unwrap();
+
+        close_storage_with_timeout(storage.close(), STORAGE_CLOSE_TIMEOUT)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn slow_storage_close_is_bounded() {
+        let slow_close = async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(())
+        };
+
+        let started = std::time::Instant::now();
+        close_storage_with_timeout(slow_close, Duration::from_millis(50))
+            .await
+            .unwrap();
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn storage_close_error_is_propagated() {
+        let failing_close = async { Err(BlufioError::Internal("checkpoint failed".into())) };
+        let result = close_storage_with_timeout(failing_close, STORAGE_CLOSE_TIMEOUT).await;
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,353 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Spoken replies to voice messages.
+//!
+//! When a user talks to the agent by voice, [`reply_in_kind`] decides whether
+//! the answer should also go back as audio, and the configured [`TtsAdapter`]
+//! produces it. [`CommandSynthesizer`] is the built-in backend: it runs a
+//! local program such as `piper` or `espeak-ng`.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use blufio_config::model::TtsConfig;
+use blufio_core::error::BlufioError;
+use blufio_core::traits::{PluginAdapter, TtsAdapter};
+use blufio_core::types::{AdapterType, HealthStatus, TtsRequest, TtsResponse};
+use tokio::io::AsyncWriteExt;
+use tracing::debug;
+
+/// Whether a reply should also be sent as a voice message.
+///
+/// Only voice messages get spoken replies, only on channels that can send
+/// voice, and only for non-empty replies of at most `max_chars` characters.
+pub fn reply_in_kind(
+    inbound_was_voice: bool,
+    channel_supports_voice: bool,
+    reply: &str,
+    max_chars: usize,
+) -> bool {
+    inbound_was_voice
+        && channel_supports_voice
+        && !reply.trim().is_empty()
+        && reply.chars().count() <= max_chars
+}
+
+/// MIME type for a synthesis output format.
+pub fn audio_content_type(format: &str) -> &'static str {
+    match format {
+        "ogg" | "opus" => "audio/ogg",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Speech synthesis backend that runs a local text-to-speech program.
+///
+/// The text is written to the program's stdin. Audio is read from the file
+/// whose path replaces `{output}` in the arguments, or from stdout when the
+/// arguments have no `{output}`.
+pub struct CommandSynthesizer {
+    program: String,
+    args: Vec<String>,
+    voice: Option<String>,
+    timeout: Duration,
+}
+
+impl CommandSynthesizer {
+    /// Creates a synthesizer running `program` with `args`.
+    pub fn new(program: String, args: Vec<String>, timeout: Duration) -> Self {
+        Self {
+            program,
+            args,
+            voice: None,
+            timeout,
+        }
+    }
+
+    /// Creates a synthesizer from config, or `None` when no command is set.
+    pub fn from_config(config: &TtsConfig) -> Option<Self> {
+        let program = config.command.clone()?;
+        Some(Self {
+            voice: config.voice.clone(),
+            ..Self::new(
+                program,
+                config.args.clone(),
+                Duration::from_secs(config.timeout_secs),
+            )
+        })
+    }
+
+    /// Arguments with the `{output}`, `{voice}` and `{format}` placeholders filled in.
+    fn render_args(&self, output: &str, voice: &str, format: &str) -> Vec<String> {
+        self.args
+            .iter()
+            .map(|arg| {
+                arg.replace("{output}", output)
+                    .replace("{voice}", voice)
+                    .replace("{format}", format)
+            })
+            .collect()
+    }
+
+    /// Runs the program with `text` on stdin and returns its stdout.
+    async fn run(&self, args: &[String], text: &str) -> Result<Vec<u8>, BlufioError> {
+        let mut child = tokio::process::Command::new(&self.program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                BlufioError::Internal(format!(
+                    "failed to run speech synthesis command `{}`: {e}",
+                    self.program
+                ))
+            })?;
+
+        let text = text.to_string();
+        let mut stdin = child.stdin.take();
+        let run = async move {
+            if let Some(stdin) = stdin.as_mut() {
+                stdin.write_all(text.as_bytes()).await?;
+                stdin.shutdown().await?;
+            }
+            drop(stdin);
+            child.wait_with_output().await
+        };
+        let output = tokio::time::timeout(self.timeout, run)
+            .await
+            .map_err(|_| {
+                BlufioError::Internal(format!(
+                    "speech synthesis command `{}` timed out after {}s",
+                    self.program,
+                    self.timeout.as_secs()
+                ))
+            })?
+            .map_err(|e| {
+                BlufioError::Internal(format!(
+                    "speech synthesis command `{}` failed: {e}",
+                    self.program
+                ))
+            })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(BlufioError::Internal(format!(
+                "speech synthesis command `{}` exited with {}: {}",
+                self.program,
+                output.status,
+                stderr.trim()
+            )));
+        }
+        Ok(output.stdout)
+    }
+}
+
+#[async_trait]
+impl PluginAdapter for CommandSynthesizer {
+    fn name(&self) -> &str {
+        "command-synthesizer"
+    }
+
+    fn version(&self) -> semver::Version {
+        semver::Version::new(0, 1, 0)
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::Tts
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus, BlufioError> {
+        Ok(HealthStatus::Healthy)
+    }
+
+    async fn shutdown(&self) -> Result<(), BlufioError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TtsAdapter for CommandSynthesizer {
+    async fn synthesize(&self, request: TtsRequest) -> Result<TtsResponse, BlufioError> {
+        let voice = if request.voice.is_empty() {
+            self.voice.clone().unwrap_or_default()
+        } else {
+            request.voice
+        };
+        let path = std::env::temp_dir().join(format!(
+            "blufio-tts-{}.{}",
+            uuid::Uuid::new_v4(),
+            request.output_format
+        ));
+        let args = self.render_args(&path.to_string_lossy(), &voice, &request.output_format);
+        let to_file = self.args.iter().any(|arg| arg.contains("{output}"));
+
+        let stdout = self.run(&args, &request.text).await;
+        let audio = if to_file {
+            let audio = match stdout {
+                Ok(_) => tokio::fs::read(&path).await.map_err(|e| {
+                    BlufioError::Internal(format!("failed to read synthesized audio: {e}"))
+                }),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = tokio::fs::remove_file(&path).await {
+                debug!(
+                    error = %e,
+                    path = %path.display(),
+                    "failed to remove synthesized audio file"
+                );
+            }
+            audio?
+        } else {
+            stdout?
+        };
+
+        if audio.is_empty() {
+            return Err(BlufioError::Internal(format!(
+                "speech synthesis command `{}` produced no audio",
+                self.program
+            )));
+        }
+        Ok(TtsResponse {
+            audio_data: audio,
+            content_type: audio_content_type(&request.output_format).to_string(),
+            duration_secs: None,
+        })
+    }
+
+    async fn list_voices(&self) -> Result<Vec<String>, BlufioError> {
+        Ok(self.voice.iter().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Synthesizer that "speaks" by returning the text bytes.
+    struct MockSynthesizer;
+
+    #[async_trait]
+    impl PluginAdapter for MockSynthesizer {
+        fn name(&self) -> &str {
+            "mock-synthesizer"
+        }
+        fn version(&self) -> semver::Version {
+            semver::Version::new(0, 1, 0)
+        }
+        fn adapter_type(&self) -> AdapterType {
+            AdapterType::Tts
+        }
+        async fn health_check(&self) -> Result<HealthStatus, BlufioError> {
+            Ok(HealthStatus::Healthy)
+        }
+        async fn shutdown(&self) -> Result<(), BlufioError> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl TtsAdapter for MockSynthesizer {
+        async fn synthesize(&self, request: TtsRequest) -> Result<TtsResponse, BlufioError> {
+            Ok(TtsResponse {
+                audio_data: request.text.into_bytes(),
+                content_type: audio_content_type(&request.output_format).to_string(),
+                duration_secs: None,
+            })
+        }
+
+        async fn list_voices(&self) -> Result<Vec<String>, BlufioError> {
+            Ok(vec!["mock".to_string()])
+        }
+    }
+
+    fn request(text: &str, format: &str) -> TtsRequest {
+        TtsRequest {
+            text: text.to_string(),
+            voice: String::new(),
+            output_format: format.to_string(),
+            speed: 1.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn trait_object_synthesizes_through_mock() {
+        let tts: Box<dyn TtsAdapter + Send + Sync> = Box::new(MockSynthesizer);
+        assert_eq!(tts.adapter_type(), AdapterType::Tts);
+        let response = tts.synthesize(request("hello", "ogg")).await.unwrap();
+        assert_eq!(response.audio_data, b"hello");
+        assert_eq!(response.content_type, "audio/ogg");
+        assert_eq!(tts.list_voices().await.unwrap(), vec!["mock"]);
+    }
+
+    #[test]
+    fn replies_in_kind_only_to_voice_on_voice_channels() {
+        assert!(reply_in_kind(true, true, "sure thing", 100));
+        assert!(!reply_in_kind(false, true, "sure thing", 100));
+        assert!(!reply_in_kind(true, false, "sure thing", 100));
+    }
+
+    #[test]
+    fn skips_empty_and_long_replies() {
+        assert!(!reply_in_kind(true, true, "  \n", 100));
+        assert!(reply_in_kind(true, true, &"é".repeat(100), 100));
+        assert!(!reply_in_kind(true, true, &"é".repeat(101), 100));
+    }
+
+    #[test]
+    fn from_config_requires_command() {
+        assert!(CommandSynthesizer::from_config(&TtsConfig::default()).is_none());
+
+        let config = TtsConfig {
+            command: Some("piper".to_string()),
+            args: vec![
+                "--model".into(),
+                "{voice}.onnx".into(),
+                "-f".into(),
+                "{output}".into(),
+            ],
+            voice: Some("amy".to_string()),
+            ..Default::default()
+        };
+        let synth = CommandSynthesizer::from_config(&config).unwrap();
+        assert_eq!(
+            synth.render_args("/tmp/out.wav", "amy", "wav"),
+            vec!["--model", "amy.onnx", "-f", "/tmp/out.wav"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_synthesizer_reads_stdout() {
+        let synth = CommandSynthesizer::new(
+            "sh".to_string(),
+            vec!["-c".to_string(), "printf 'audio:'; cat".to_string()],
+            Duration::from_secs(10),
+        );
+        let response = synth.synthesize(request("hi", "mp3")).await.unwrap();
+        assert_eq!(response.audio_data, b"audio:hi");
+        assert_eq!(response.content_type, "audio/mpeg");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_synthesizer_reads_output_file() {
+        let synth = CommandSynthesizer::new(
+            "sh".to_string(),
+            vec![
+                "-c".to_string(),
+                "cat > \"$0\"".to_string(),
+                "{output}".to_string(),
+            ],
+            Duration::from_secs(10),
+        );
+        let response = synth.synthesize(request("spoken", "wav")).await.unwrap();
+        assert_eq!(response.audio_data, b"spoken");
+        assert_eq!(response.content_type, "audio/wav");
+    }
+}
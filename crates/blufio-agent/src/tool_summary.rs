@@ -0,0 +1,133 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! One-line summaries of the tool calls made during a turn.
+//!
+//! Tool results normally reach only the model. On channels listed in
+//! `agent.show_tool_calls`, the displayed reply ends with a compact summary
+//! of each call, such as "Ran: bash `ls` → 12 files", so the user can see
+//! what the agent did. The model-facing results and the persisted history
+//! are not affected.
+
+use blufio_core::types::ToolUseData;
+use blufio_skill::ToolOutput;
+
+/// Longest argument shown for a call, in characters.
+const MAX_ARG_CHARS: usize = 40;
+
+/// Longest result excerpt shown for a call, in characters.
+const MAX_RESULT_CHARS: usize = 60;
+
+/// Summarizes one tool call and its output on a single line.
+///
+/// The argument is the first string field of the input; the result is the
+/// first non-empty line of the output. Both are shortened to fit.
+pub fn summarize_call(tool_use: &ToolUseData, output: &ToolOutput) -> String {
+    let mut line = format!("Ran: {}", tool_use.name);
+    if let Some(arg) = first_string_arg(&tool_use.input) {
+        line.push_str(&format!(
+            " `{}`",
+            truncate(&arg.replace('`', "'"), MAX_ARG_CHARS)
+        ));
+    }
+    let result = output
+        .content
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .map(|l| truncate(l, MAX_RESULT_CHARS));
+    match (output.is_error, result) {
+        (true, Some(r)) => line.push_str(&format!(" → failed: {r}")),
+        (true, None) => line.push_str(" → failed"),
+        (false, Some(r)) => line.push_str(&format!(" → {r}")),
+        (false, None) => line.push_str(" → no output"),
+    }
+    line
+}
+
+/// Appends the call summaries to a displayed reply, set apart by a rule.
+pub fn append_summary(display: &mut String, summaries: &[String]) {
+    if summaries.is_empty() {
+        return;
+    }
+    display.push_str("\n\n---\n");
+    display.push_str(&summaries.join("\n"));
+}
+
+fn first_string_arg(input: &serde_json::Value) -> Option<String> {
+    let arg = match input {
+        serde_json::Value::String(s) => s.as_str(),
+        serde_json::Value::Object(map) => map.values().find_map(|v| v.as_str())?,
+        _ => return None,
+    };
+    Some(arg.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn truncate(s: &str, max_chars: usize) -> String {
+    if s.chars().count() <= max_chars {
+        return s.to_string();
+    }
+    let mut out: String = s.chars().take(max_chars - 1).collect();
+    out.push('…');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, input: serde_json::Value) -> ToolUseData {
+        ToolUseData {
+            id: "tu-1".to_string(),
+            name: name.to_string(),
+            input,
+        }
+    }
+
+    fn output(content: &str, is_error: bool) -> ToolOutput {
+        ToolOutput {
+            content: content.to_string(),
+            is_error,
+        }
+    }
+
+    #[test]
+    fn summarizes_argument_and_first_result_line() {
+        let line = summarize_call(
+            &call("bash", serde_json::json!({"command": "ls"})),
+            &output("\n12 files\nmore", false),
+        );
+        assert_eq!(line, "Ran: bash `ls` → 12 files");
+    }
+
+    #[test]
+    fn marks_failures_and_empty_output() {
+        let failed = summarize_call(
+            &call("http", serde_json::json!({"url": "https://x"})),
+            &output("Error: timeout", true),
+        );
+        assert_eq!(failed, "Ran: http `https://x` → failed: Error: timeout");
+
+        let empty = summarize_call(&call("noop", serde_json::json!({})), &output("", false));
+        assert_eq!(empty, "Ran: noop → no output");
+    }
+
+    #[test]
+    fn long_values_are_shortened() {
+        let line = summarize_call(
+            &call("bash", serde_json::json!({"command": "x".repeat(100)})),
+            &output(&"y".repeat(100), false),
+        );
+        assert!(line.contains(&format!("`{}…`", "x".repeat(MAX_ARG_CHARS - 1))));
+        assert!(line.ends_with(&format!("{}…", "y".repeat(MAX_RESULT_CHARS - 1))));
+    }
+
+    #[test]
+    fn append_summary_skips_empty() {
+        let mut display = "done".to_string();
+        append_summary(&mut display, &[]);
+        assert_eq!(display, "done");
+        append_summary(&mut display, &["Ran: a → b".to_string()]);
+        assert_eq!(display, "done\n\n---\nRan: a → b");
+    }
+}
@@ -0,0 +1,366 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Speech-to-text for inbound voice messages.
+//!
+//! Channels deliver voice notes as [`MessageContent::Voice`] with the raw
+//! audio. [`transcribe_inbound`] turns them into text through the configured
+//! [`TranscriptionAdapter`] before the message reaches a session, so every
+//! channel shares one backend. [`CommandTranscriber`] is the built-in backend:
+//! it runs a local program such as whisper.cpp's `whisper-cli`.
+
+use std::path::Path;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use blufio_config::model::TranscriptionConfig;
+use blufio_core::error::BlufioError;
+use blufio_core::traits::{PluginAdapter, TranscriptionAdapter};
+use blufio_core::types::{
+    AdapterType, HealthStatus, InboundMessage, MessageContent, TranscriptionRequest,
+    TranscriptionResponse,
+};
+use tracing::{debug, warn};
+
+/// Transcription backend that runs a local speech-to-text program.
+///
+/// The audio is written to a temporary file whose path replaces `{input}` in
+/// the arguments; the program's trimmed stdout is the transcript.
+pub struct CommandTranscriber {
+    program: String,
+    args: Vec<String>,
+    language: Option<String>,
+    timeout: Duration,
+}
+
+impl CommandTranscriber {
+    /// Creates a transcriber running `program` with `args`.
+    pub fn new(program: String, args: Vec<String>, timeout: Duration) -> Self {
+        Self {
+            program,
+            args,
+            language: None,
+            timeout,
+        }
+    }
+
+    /// Creates a transcriber from config, or `None` when no command is set.
+    pub fn from_config(config: &TranscriptionConfig) -> Option<Self> {
+        let program = config.command.clone()?;
+        Some(Self {
+            language: config.language.clone(),
+            ..Self::new(
+                program,
+                config.args.clone(),
+                Duration::from_secs(config.timeout_secs),
+            )
+        })
+    }
+
+    /// Arguments with the `{input}` and `{language}` placeholders filled in.
+    fn render_args(&self, input: &str, language: Option<&str>) -> Vec<String> {
+        let language = language.unwrap_or("auto");
+        self.args
+            .iter()
+            .map(|arg| {
+                arg.replace("{input}", input)
+                    .replace("{language}", language)
+            })
+            .collect()
+    }
+
+    async fn run(&self, path: &Path, language: Option<&str>) -> Result<String, BlufioError> {
+        let args = self.render_args(&path.to_string_lossy(), language);
+        let output = tokio::process::Command::new(&self.program)
+            .args(&args)
+            .kill_on_drop(true)
+            .output();
+        let output = tokio::time::timeout(self.timeout, output)
+            .await
+            .map_err(|_| {
+                BlufioError::Internal(format!(
+                    "transcription command `{}` timed out after {}s",
+                    self.program,
+                    self.timeout.as_secs()
+                ))
+            })?
+            .map_err(|e| {
+                BlufioError::Internal(format!(
+                    "failed to run transcription command `{}`: {e}",
+                    self.program
+                ))
+            })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(BlufioError::Internal(format!(
+                "transcription command `{}` exited with {}: {}",
+                self.program,
+                output.status,
+                stderr.trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+#[async_trait]
+impl PluginAdapter for CommandTranscriber {
+    fn name(&self) -> &str {
+        "command-transcriber"
+    }
+
+    fn version(&self) -> semver::Version {
+        semver::Version::new(0, 1, 0)
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::Transcription
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus, BlufioError> {
+        Ok(HealthStatus::Healthy)
+    }
+
+    async fn shutdown(&self) -> Result<(), BlufioError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TranscriptionAdapter for CommandTranscriber {
+    async fn transcribe(
+        &self,
+        request: TranscriptionRequest,
+    ) -> Result<TranscriptionResponse, BlufioError> {
+        let path = std::env::temp_dir().join(format!(
+            "blufio-voice-{}.{}",
+            uuid::Uuid::new_v4(),
+            audio_extension(&request.content_type)
+        ));
+        tokio::fs::write(&path, &request.audio_data)
+            .await
+            .map_err(|e| BlufioError::Internal(format!("failed to write voice audio: {e}")))?;
+
+        let language = request.language.as_deref().or(self.language.as_deref());
+        let result = self.run(&path, language).await;
+        if let Err(e) = tokio::fs::remove_file(&path).await {
+            debug!(error = %e, path = %path.display(), "failed to remove voice audio file");
+        }
+
+        Ok(TranscriptionResponse {
+            text: result?,
+            language: language.map(str::to_string),
+            duration_secs: None,
+        })
+    }
+}
+
+/// Audio format assumed for [`MessageContent::Voice`] data.
+const VOICE_CONTENT_TYPE: &str = "audio/ogg";
+
+/// File extension for an audio MIME type, so backends can detect the format.
+fn audio_extension(content_type: &str) -> &'static str {
+    match content_type {
+        "audio/ogg" | "audio/opus" => "ogg",
+        "audio/mpeg" | "audio/mp3" => "mp3",
+        "audio/mp4" | "audio/m4a" | "audio/x-m4a" => "m4a",
+        "audio/wav" | "audio/x-wav" | "audio/wave" => "wav",
+        "audio/webm" => "webm",
+        _ => "bin",
+    }
+}
+
+/// Whether the user sent `inbound` as a voice message.
+///
+/// True for [`MessageContent::Voice`], and for text that a channel already
+/// transcribed and flagged with `"voice": true` in its metadata.
+pub fn is_voice(inbound: &InboundMessage) -> bool {
+    matches!(inbound.content, MessageContent::Voice { .. })
+        || inbound
+            .metadata
+            .as_deref()
+            .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+            .and_then(|meta| meta.get("voice")?.as_bool())
+            .unwrap_or(false)
+}
+
+/// Replaces a voice message's audio with its transcript.
+///
+/// Other content is left untouched. When transcription fails or yields no
+/// text, the voice content is kept and the agent sees the voice placeholder.
+pub async fn transcribe_inbound(
+    transcriber: &(dyn TranscriptionAdapter + Send + Sync),
+    inbound: &mut InboundMessage,
+) {
+    let MessageContent::Voice { data, .. } = &inbound.content else {
+        return;
+    };
+
+    // Voice notes carry no MIME type; chat apps record them as OGG/Opus.
+    let request = TranscriptionRequest {
+        audio_data: data.clone(),
+        content_type: VOICE_CONTENT_TYPE.to_string(),
+        language: None,
+    };
+    match transcriber.transcribe(request).await {
+        Ok(response) if !response.text.is_empty() => {
+            debug!(
+                channel = inbound.channel.as_str(),
+                chars = response.text.len(),
+                "voice message transcribed"
+            );
+            inbound.content = MessageContent::Text(response.text);
+        }
+        Ok(_) => warn!(
+            channel = inbound.channel.as_str(),
+            "voice message transcription was empty"
+        ),
+        Err(e) => warn!(
+            channel = inbound.channel.as_str(),
+            error = %e,
+            "voice message transcription failed"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blufio_test_utils::MockTranscriber;
+
+    fn inbound(content: MessageContent) -> InboundMessage {
+        InboundMessage {
+            id: "msg-1".to_string(),
+            session_id: None,
+            channel: "telegram".to_string(),
+            sender_id: "user-1".to_string(),
+            content,
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            metadata: None,
+        }
+    }
+
+    fn voice() -> MessageContent {
+        MessageContent::Voice {
+            data: vec![1, 2, 3],
+            duration_secs: Some(2.0),
+        }
+    }
+
+    #[tokio::test]
+    async fn voice_message_becomes_transcript() {
+        let transcriber = MockTranscriber::new(Ok("turn on the lights"));
+        let mut msg = inbound(voice());
+
+        transcribe_inbound(&transcriber, &mut msg).await;
+
+        assert!(matches!(&msg.content, MessageContent::Text(t) if t == "turn on the lights"));
+        let requests = transcriber.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].audio_data, vec![1, 2, 3]);
+        assert_eq!(requests[0].content_type, "audio/ogg");
+    }
+
+    #[tokio::test]
+    async fn failed_or_empty_transcription_keeps_voice() {
+        for result in [Err("backend down"), Ok("")] {
+            let transcriber = MockTranscriber::new(result);
+            let mut msg = inbound(voice());
+            transcribe_inbound(&transcriber, &mut msg).await;
+            assert!(matches!(msg.content, MessageContent::Voice { .. }));
+        }
+    }
+
+    #[tokio::test]
+    async fn non_voice_content_is_not_transcribed() {
+        let transcriber = MockTranscriber::new(Ok("unused"));
+        let mut msg = inbound(MessageContent::Text("hello".to_string()));
+
+        transcribe_inbound(&transcriber, &mut msg).await;
+
+        assert!(matches!(&msg.content, MessageContent::Text(t) if t == "hello"));
+        assert!(transcriber.requests().is_empty());
+    }
+
+    #[test]
+    fn channel_transcribed_voice_counts_as_voice() {
+        assert!(is_voice(&inbound(voice())));
+
+        let mut msg = inbound(MessageContent::Text("hello".to_string()));
+        assert!(!is_voice(&msg));
+        msg.metadata = Some(r#"{"chat_id":"1","voice":true}"#.to_string());
+        assert!(is_voice(&msg));
+        msg.metadata = Some(r#"{"chat_id":"1","voice":false}"#.to_string());
+        assert!(!is_voice(&msg));
+    }
+
+    #[test]
+    fn from_config_requires_command() {
+        assert!(CommandTranscriber::from_config(&TranscriptionConfig::default()).is_none());
+
+        let config = TranscriptionConfig {
+            command: Some("whisper-cli".to_string()),
+            args: vec![
+                "-l".into(),
+                "{language}".into(),
+                "-f".into(),
+                "{input}".into(),
+            ],
+            language: Some("de".to_string()),
+            ..Default::default()
+        };
+        let transcriber = CommandTranscriber::from_config(&config).unwrap();
+        assert_eq!(transcriber.adapter_type(), AdapterType::Transcription);
+        assert_eq!(
+            transcriber.render_args("/tmp/a.ogg", Some("de")),
+            vec!["-l", "de", "-f", "/tmp/a.ogg"]
+        );
+        assert_eq!(
+            transcriber.render_args("/tmp/a.ogg", None),
+            vec!["-l", "auto", "-f", "/tmp/a.ogg"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_transcriber_reads_program_stdout() {
+        let transcriber = CommandTranscriber::new(
+            "sh".to_string(),
+            vec![
+                "-c".to_string(),
+                "cat \"$0\"; echo".to_string(),
+                "{input}".to_string(),
+            ],
+            Duration::from_secs(10),
+        );
+        let response = transcriber
+            .transcribe(TranscriptionRequest {
+                audio_data: b"  hello from audio ".to_vec(),
+                content_type: "audio/ogg".to_string(),
+                language: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.text, "hello from audio");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_transcriber_reports_failure() {
+        let transcriber = CommandTranscriber::new(
+            "sh".to_string(),
+            vec!["-c".to_string(), "echo no model >&2; exit 3".to_string()],
+            Duration::from_secs(10),
+        );
+        let err = transcriber
+            .transcribe(TranscriptionRequest {
+                audio_data: vec![0],
+                content_type: "audio/wav".to_string(),
+                language: None,
+            })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no model"));
+    }
+}
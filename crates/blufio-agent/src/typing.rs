@@ -0,0 +1,56 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Typing-indicator refresh while a reply is being generated.
+//!
+//! Channels such as Telegram show a typing action for only a few seconds, so
+//! a single `send_typing` at the start of a turn disappears long before a slow
+//! reply arrives. [`TypingRefresher`] re-sends the action on a fixed interval
+//! from a background task until it is stopped or dropped.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use blufio_core::ChannelAdapter;
+use tokio_util::sync::{CancellationToken, DropGuard};
+use tracing::debug;
+
+/// Background task re-sending the typing action for one chat.
+///
+/// The task stops when the refresher is dropped.
+pub struct TypingRefresher {
+    _guard: DropGuard,
+}
+
+impl TypingRefresher {
+    /// Starts re-sending the typing action to `chat_id` every `interval`.
+    ///
+    /// The first refresh happens after one interval; callers send the initial
+    /// indicator themselves.
+    pub fn spawn(
+        channel: Arc<dyn ChannelAdapter + Send + Sync>,
+        chat_id: String,
+        interval: Duration,
+    ) -> Self {
+        let cancel = CancellationToken::new();
+        let token = cancel.clone();
+        tokio::spawn(async move {
+            let mut ticker =
+                tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = ticker.tick() => {
+                        if let Err(e) = channel.send_typing(&chat_id).await {
+                            debug!(error = %e, "failed to refresh typing indicator");
+                        }
+                    }
+                }
+            }
+        });
+        Self {
+            _guard: cancel.drop_guard(),
+        }
+    }
+}
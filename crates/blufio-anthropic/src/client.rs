@@ -10,10 +10,11 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
 
-use blufio_config::model::SecurityConfig;
+use blufio_config::model::{AnthropicConfig, SecurityConfig};
 use blufio_core::{BlufioError, ErrorContext, ProviderErrorKind};
 use blufio_security::SsrfSafeResolver;
 use futures::Stream;
+use rand::Rng;
 use reqwest::header::{HeaderMap, HeaderValue};
 use tracing::{debug, warn};
 
@@ -26,21 +27,80 @@ const PROVIDER_NAME: &str = "anthropic";
 /// Base URL for the Anthropic Messages API.
 const API_BASE_URL: &str = "https://api.anthropic.com/v1/messages";
 
+/// Retry policy for transient Anthropic API errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// Maximum retries after the first attempt (0 disables retry).
+    pub max_retries: u32,
+    /// Initial backoff delay in milliseconds, doubled per retry.
+    pub base_delay_ms: u64,
+    /// Upper bound on any single delay, including `retry-after`.
+    pub max_delay_ms: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self::from(&AnthropicConfig::default())
+    }
+}
+
+impl From<&AnthropicConfig> for RetryConfig {
+    fn from(config: &AnthropicConfig) -> Self {
+        Self {
+            max_retries: config.max_retries,
+            base_delay_ms: config.retry_base_delay_ms,
+            max_delay_ms: config.retry_max_delay_ms,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Delay before retry number `retry` (1-based).
+    ///
+    /// A server-provided `retry-after` wins; otherwise exponential backoff
+    /// `base * 2^(retry-1)` with up to 25% added jitter. Both are capped at
+    /// `max_delay_ms`.
+    fn delay_for(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        let max = Duration::from_millis(self.max_delay_ms);
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(max);
+        }
+        let exp = self
+            .base_delay_ms
+            .saturating_mul(1u64 << retry.saturating_sub(1).min(32));
+        let jitter = if exp >= 4 {
+            rand::thread_rng().gen_range(0..=exp / 4)
+        } else {
+            0
+        };
+        Duration::from_millis(exp.saturating_add(jitter)).min(max)
+    }
+}
+
+/// Whether an HTTP status is worth retrying: 429, 500, 503 and Anthropic's 529
+/// (`overloaded_error`). Everything else -- notably 400/401/403 -- fails fast.
+fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 429 | 500 | 503 | 529)
+}
+
 /// HTTP client for Anthropic API communication.
 ///
 /// Manages authentication headers, connection pooling, and retry logic
-/// for transient errors (429, 500, 503, 529).
+/// for transient errors (429, 500, 503, 529, connection failures).
 #[derive(Debug, Clone)]
 pub struct AnthropicClient {
     client: reqwest::Client,
     default_model: String,
-    max_retries: u32,
+    retry: RetryConfig,
     base_url: String,
 }
 
 impl AnthropicClient {
     /// Creates a new Anthropic API client.
     ///
+    /// Uses the default [`RetryConfig`]; override with
+    /// [`with_retry_config`](Self::with_retry_config).
+    ///
     /// # Arguments
     /// * `api_key` - Anthropic API key for authentication
     /// * `api_version` - API version string (e.g., "2023-06-01")
@@ -89,11 +149,17 @@ impl AnthropicClient {
         Ok(Self {
             client,
             default_model: model,
-            max_retries: 1,
+            retry: RetryConfig::default(),
             base_url: API_BASE_URL.to_string(),
         })
     }
 
+    /// Overrides the retry policy.
+    pub fn with_retry_config(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
     /// Returns the default model identifier.
     pub fn default_model(&self) -> &str {
         &self.default_model
@@ -116,34 +182,23 @@ impl AnthropicClient {
             .map(Duration::from_secs)
     }
 
-    /// Sends a streaming request and returns a stream of SSE events.
+    /// POSTs `req` and returns the first successful response, retrying
+    /// transient failures per the client's [`RetryConfig`].
     ///
-    /// On retryable errors (determined by `error.is_retryable()`), retries once
-    /// after a 1-second delay.
-    pub async fn stream_message(
+    /// Retries happen only before a success status is received, so a streaming
+    /// body that has started is never re-requested (no double-emitted content).
+    async fn send_with_retry(
         &self,
-        request: &MessageRequest,
-    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamEvent, BlufioError>> + Send>>, BlufioError>
-    {
-        let mut req = request.clone();
-        req.stream = true;
-
-        let mut last_error = None;
-
-        for attempt in 0..=self.max_retries {
-            if attempt > 0 {
-                warn!(attempt, "retrying streaming request after transient error");
-                tokio::time::sleep(Duration::from_secs(1)).await;
-            }
-
-            let response = self
-                .client
-                .post(&self.base_url)
-                .json(&req)
-                .send()
-                .await
-                .map_err(|e| {
-                    if e.is_timeout() {
+        req: &MessageRequest,
+    ) -> Result<reqwest::Response, BlufioError> {
+        let mut attempt: u32 = 0;
+
+        loop {
+            let response = match self.client.post(&self.base_url).json(req).send().await {
+                Ok(response) => response,
+                Err(e) => {
+                    let retryable = e.is_connect();
+                    let error = if e.is_timeout() {
                         BlufioError::provider_timeout(PROVIDER_NAME)
                     } else {
                         BlufioError::Provider {
@@ -154,66 +209,86 @@ impl AnthropicClient {
                             },
                             source: Some(Box::new(e)),
                         }
+                    };
+                    if retryable && attempt < self.retry.max_retries {
+                        attempt += 1;
+                        let delay = self.retry.delay_for(attempt, None);
+                        warn!(attempt, delay_ms = delay.as_millis() as u64, error = %error, "connection error, will retry");
+                        tokio::time::sleep(delay).await;
+                        continue;
                     }
-                })?;
+                    return Err(error);
+                }
+            };
 
             let status = response.status();
-            debug!(status = %status, attempt, "streaming response received");
+            debug!(status = %status, attempt, "response received");
 
             if status.is_success() {
-                return Ok(sse::parse_sse_stream(response));
+                return Ok(response);
             }
 
             let retry_after = Self::extract_retry_after(&response);
             let error = BlufioError::provider_from_http(status.as_u16(), PROVIDER_NAME, None);
             // Attach retry_after to context if present.
-            let error = if retry_after.is_some() {
-                match error {
+            let error = match error {
+                BlufioError::Provider {
+                    kind,
+                    mut context,
+                    source,
+                } if retry_after.is_some() => {
+                    context.retry_after = retry_after;
                     BlufioError::Provider {
                         kind,
-                        mut context,
+                        context,
                         source,
-                    } => {
-                        context.retry_after = retry_after;
-                        BlufioError::Provider {
-                            kind,
-                            context,
-                            source,
-                        }
                     }
-                    other => other,
                 }
-            } else {
-                error
+                other => other,
             };
 
-            if error.is_retryable() && attempt < self.max_retries {
-                let body = response.text().await.unwrap_or_default();
-                warn!(status = %status, body = %body, "transient error, will retry");
-                last_error = Some(error);
+            let body = response.text().await.unwrap_or_default();
+
+            if is_retryable_status(status.as_u16()) && attempt < self.retry.max_retries {
+                attempt += 1;
+                let delay = self.retry.delay_for(attempt, retry_after);
+                warn!(
+                    status = %status,
+                    attempt,
+                    delay_ms = delay.as_millis() as u64,
+                    body = %body,
+                    "transient error, will retry"
+                );
+                tokio::time::sleep(delay).await;
                 continue;
             }
 
-            // Non-retryable error or exhausted retries -- read body for diagnostics.
-            let body = response.text().await.unwrap_or_default();
+            // Non-retryable error or exhausted retries -- keep body for diagnostics.
             let _api_detail = serde_json::from_str::<ApiErrorResponse>(&body).ok();
             return Err(error);
         }
+    }
 
-        Err(last_error.unwrap_or_else(|| BlufioError::Provider {
-            kind: ProviderErrorKind::ServerError,
-            context: ErrorContext {
-                provider_name: Some(PROVIDER_NAME.into()),
-                ..Default::default()
-            },
-            source: None,
-        }))
+    /// Sends a streaming request and returns a stream of SSE events.
+    ///
+    /// Transient errors are retried with backoff before the stream starts;
+    /// once a success status is received no further retries are made.
+    pub async fn stream_message(
+        &self,
+        request: &MessageRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamEvent, BlufioError>> + Send>>, BlufioError>
+    {
+        let mut req = request.clone();
+        req.stream = true;
+
+        let response = self.send_with_retry(&req).await?;
+        Ok(sse::parse_sse_stream(response))
     }
 
     /// Sends a non-streaming request and returns the full response.
     ///
-    /// On retryable errors (determined by `error.is_retryable()`), retries once
-    /// after a 1-second delay.
+    /// Transient errors are retried with exponential backoff and jitter,
+    /// honoring `retry-after` when present.
     pub async fn complete_message(
         &self,
         request: &MessageRequest,
@@ -221,102 +296,23 @@ impl AnthropicClient {
         let mut req = request.clone();
         req.stream = false;
 
-        let mut last_error = None;
-
-        for attempt in 0..=self.max_retries {
-            if attempt > 0 {
-                warn!(attempt, "retrying completion request after transient error");
-                tokio::time::sleep(Duration::from_secs(1)).await;
-            }
-
-            let response = self
-                .client
-                .post(&self.base_url)
-                .json(&req)
-                .send()
-                .await
-                .map_err(|e| {
-                    if e.is_timeout() {
-                        BlufioError::provider_timeout(PROVIDER_NAME)
-                    } else {
-                        BlufioError::Provider {
-                            kind: ProviderErrorKind::ServerError,
-                            context: ErrorContext {
-                                provider_name: Some(PROVIDER_NAME.into()),
-                                ..Default::default()
-                            },
-                            source: Some(Box::new(e)),
-                        }
-                    }
-                })?;
-
-            let status = response.status();
-            debug!(status = %status, attempt, "completion response received");
-
-            if status.is_success() {
-                let body = response.text().await.map_err(|e| BlufioError::Provider {
-                    kind: ProviderErrorKind::ServerError,
-                    context: ErrorContext {
-                        provider_name: Some(PROVIDER_NAME.into()),
-                        ..Default::default()
-                    },
-                    source: Some(Box::new(e)),
-                })?;
-                let msg_response: MessageResponse =
-                    serde_json::from_str(&body).map_err(|e| BlufioError::Provider {
-                        kind: ProviderErrorKind::ServerError,
-                        context: ErrorContext {
-                            provider_name: Some(PROVIDER_NAME.into()),
-                            ..Default::default()
-                        },
-                        source: Some(Box::new(e)),
-                    })?;
-                return Ok(msg_response);
-            }
-
-            let retry_after = Self::extract_retry_after(&response);
-            let error = BlufioError::provider_from_http(status.as_u16(), PROVIDER_NAME, None);
-            let error = if retry_after.is_some() {
-                match error {
-                    BlufioError::Provider {
-                        kind,
-                        mut context,
-                        source,
-                    } => {
-                        context.retry_after = retry_after;
-                        BlufioError::Provider {
-                            kind,
-                            context,
-                            source,
-                        }
-                    }
-                    other => other,
-                }
-            } else {
-                error
-            };
-
-            if error.is_retryable() && attempt < self.max_retries {
-                let body = response.text().await.unwrap_or_default();
-                warn!(status = %status, body = %body, "transient error, will retry");
-                last_error = Some(error);
-                continue;
-            }
-
-            // Non-retryable error or exhausted retries.
-            let body = response.text().await.unwrap_or_default();
-            let _api_detail = serde_json::from_str::<ApiErrorResponse>(&body).ok();
-            return Err(error);
-        }
-
-        Err(last_error.unwrap_or_else(|| BlufioError::Provider {
+        let response = self.send_with_retry(&req).await?;
+        let body = response.text().await.map_err(|e| BlufioError::Provider {
+            kind: ProviderErrorKind::ServerError,
+            context: ErrorContext {
+                provider_name: Some(PROVIDER_NAME.into()),
+                ..Default::default()
+            },
+            source: Some(Box::new(e)),
+        })?;
+        serde_json::from_str(&body).map_err(|e| BlufioError::Provider {
             kind: ProviderErrorKind::ServerError,
             context: ErrorContext {
                 provider_name: Some(PROVIDER_NAME.into()),
                 ..Default::default()
             },
-            source: None,
-        }))
+            source: Some(Box::new(e)),
+        })
     }
 }
 
@@ -335,6 +331,11 @@ mod tests {
         )
         .unwrap()
         .with_base_url(base_url.to_string())
+        .with_retry_config(RetryConfig {
+            max_retries: 1,
+            base_delay_ms: 1,
+            max_delay_ms: 2_000,
+        })
     }
 
     fn test_request() -> MessageRequest {
@@ -427,6 +428,7 @@ mod tests {
         Mock::given(method("POST"))
             .and(path("/"))
             .respond_with(ResponseTemplate::new(400).set_body_json(&error_body))
+            .expect(1)
             .mount(&server)
             .await;
 
@@ -521,4 +523,154 @@ mod tests {
         let result = client.complete_message(&test_request()).await.unwrap();
         assert_eq!(result.id, "msg_529");
     }
+
+    fn success_body(id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "type": "message",
+            "role": "assistant",
+            "content": [{"type": "text", "text": "ok"}],
+            "model": "claude-sonnet-4-20250514",
+            "stop_reason": "end_turn",
+            "usage": {"input_tokens": 1, "output_tokens": 1}
+        })
+    }
+
+    #[tokio::test]
+    async fn complete_message_attempts_max_retries_plus_one() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(529).set_body_string("overloaded"))
+            .expect(4)
+            .mount(&server)
+            .await;
+
+        let client = test_client(&server.uri()).with_retry_config(RetryConfig {
+            max_retries: 3,
+            base_delay_ms: 1,
+            max_delay_ms: 10,
+        });
+        assert!(client.complete_message(&test_request()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn complete_message_never_retries_auth_errors() {
+        for status in [401u16, 403] {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/"))
+                .respond_with(ResponseTemplate::new(status))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let client = test_client(&server.uri()).with_retry_config(RetryConfig {
+                max_retries: 3,
+                base_delay_ms: 1,
+                max_delay_ms: 10,
+            });
+            assert!(client.complete_message(&test_request()).await.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn complete_message_honors_retry_after() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "1"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(success_body("msg_after")))
+            .mount(&server)
+            .await;
+
+        let client = test_client(&server.uri());
+        let started = std::time::Instant::now();
+        let result = client.complete_message(&test_request()).await.unwrap();
+
+        assert_eq!(result.id, "msg_after");
+        // Base delay is 1ms, so waiting >= 1s means retry-after was used.
+        assert!(started.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn stream_message_retries_before_stream_starts() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string("event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n"),
+            )
+            .mount(&server)
+            .await;
+
+        let client = test_client(&server.uri());
+        assert!(client.stream_message(&test_request()).await.is_ok());
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn retry_delay_grows_exponentially_and_is_capped() {
+        let retry = RetryConfig {
+            max_retries: 5,
+            base_delay_ms: 100,
+            max_delay_ms: 500,
+        };
+        let first = retry.delay_for(1, None);
+        assert!(first >= Duration::from_millis(100) && first <= Duration::from_millis(125));
+        let second = retry.delay_for(2, None);
+        assert!(second >= Duration::from_millis(200) && second <= Duration::from_millis(250));
+        assert_eq!(retry.delay_for(10, None), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn retry_after_overrides_backoff_but_is_capped() {
+        let retry = RetryConfig {
+            max_retries: 3,
+            base_delay_ms: 100,
+            max_delay_ms: 5_000,
+        };
+        assert_eq!(
+            retry.delay_for(1, Some(Duration::from_secs(2))),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            retry.delay_for(1, Some(Duration::from_secs(60))),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn retryable_statuses() {
+        for status in [429, 500, 503, 529] {
+            assert!(is_retryable_status(status), "{status} should retry");
+        }
+        for status in [400, 401, 403, 404, 502] {
+            assert!(!is_retryable_status(status), "{status} should not retry");
+        }
+    }
+
+    #[test]
+    fn retry_config_from_anthropic_config() {
+        let retry = RetryConfig::from(&AnthropicConfig::default());
+        assert_eq!(retry.max_retries, 3);
+        assert_eq!(retry.base_delay_ms, 1000);
+        assert_eq!(retry.max_delay_ms, 30_000);
+    }
 }
@@ -13,6 +13,7 @@ pub mod types;
 
 use std::collections::HashMap;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use async_trait::async_trait;
 use blufio_config::BlufioConfig;
@@ -25,7 +26,7 @@ use blufio_core::types::{
 use futures::stream::{Stream, StreamExt};
 use tracing::{debug, info};
 
-use crate::client::AnthropicClient;
+use crate::client::{AnthropicClient, RetryConfig};
 use crate::sse::StreamEvent;
 use crate::types::{
     ApiContent, ApiContentBlock, ApiMessage, CacheControlMarker, ImageSource, MessageRequest,
@@ -39,6 +40,9 @@ use crate::types::{
 pub struct AnthropicProvider {
     client: AnthropicClient,
     system_prompt: String,
+    /// Set once a malformed `system_blocks` has been reported, so a
+    /// persistently bad value logs one error instead of one per turn.
+    system_blocks_error_reported: AtomicBool,
 }
 
 impl AnthropicProvider {
@@ -67,7 +71,8 @@ impl AnthropicProvider {
             config.anthropic.api_version.clone(),
             config.anthropic.default_model.clone(),
             Some(&config.security),
-        )?;
+        )?
+        .with_retry_config(RetryConfig::from(&config.anthropic));
 
         info!(
             model = config.anthropic.default_model,
@@ -77,6 +82,7 @@ impl AnthropicProvider {
         Ok(Self {
             client,
             system_prompt,
+            system_blocks_error_reported: AtomicBool::new(false),
         })
     }
 
@@ -86,6 +92,7 @@ impl AnthropicProvider {
         Self {
             client,
             system_prompt,
+            system_blocks_error_reported: AtomicBool::new(false),
         }
     }
 
@@ -94,6 +101,11 @@ impl AnthropicProvider {
     /// When `system_blocks` is present, deserializes it as `Vec<SystemBlock>` and
     /// uses `SystemContent::Blocks`. Otherwise falls back to `SystemContent::Text`
     /// from `system_prompt` or the provider's default prompt.
+    ///
+    /// Callers are expected to validate `system_blocks` with
+    /// [`ProviderRequest::validate_system_blocks`]. If a malformed value still
+    /// gets here, the text fallback is used and the first failure is logged
+    /// as an error.
     fn to_message_request(&self, request: &ProviderRequest) -> MessageRequest {
         let messages: Vec<ApiMessage> = request
             .messages
@@ -109,7 +121,18 @@ impl AnthropicProvider {
             match serde_json::from_value::<Vec<SystemBlock>>(blocks_value.clone()) {
                 Ok(blocks) => Some(SystemContent::Blocks(blocks)),
                 Err(e) => {
-                    tracing::warn!(error = %e, "failed to parse system_blocks, falling back to text");
+                    if !self
+                        .system_blocks_error_reported
+                        .swap(true, Ordering::Relaxed)
+                    {
+                        tracing::error!(
+                            error = %e,
+                            "malformed system_blocks in provider request, using the text \
+                             system prompt instead; further occurrences are logged at debug"
+                        );
+                    } else {
+                        debug!(error = %e, "malformed system_blocks, using text system prompt");
+                    }
                     let text = request
                         .system_prompt
                         .clone()
@@ -357,6 +380,9 @@ fn map_stream_event_to_chunk_stateful(
 }
 
 /// Resolves the API key from config or environment.
+///
+/// Vault-stored keys arrive through `config_key`: the vault is applied to the
+/// config at startup, giving the precedence config -> vault -> env -> error.
 fn resolve_api_key(config_key: &Option<String>) -> Result<String, BlufioError> {
     if let Some(key) = config_key
         && !key.is_empty()
@@ -366,7 +392,7 @@ fn resolve_api_key(config_key: &Option<String>) -> Result<String, BlufioError> {
 
     std::env::var("ANTHROPIC_API_KEY").map_err(|_| {
         BlufioError::Config(
-            "Anthropic API key not found. Set anthropic.api_key in config or ANTHROPIC_API_KEY environment variable.".into(),
+            "Anthropic API key not found. Set anthropic.api_key in config, store it with `blufio config set-secret anthropic.api_key`, or set the ANTHROPIC_API_KEY environment variable.".into(),
         )
     })
 }
@@ -669,6 +695,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn malformed_system_blocks_fall_back_and_report_once() {
+        let client = AnthropicClient::new(
+            "test-key".into(),
+            "2023-06-01".into(),
+            "claude-sonnet-4-20250514".into(),
+            None,
+        )
+        .unwrap();
+
+        let provider = AnthropicProvider::with_client(client, "Default prompt.".into());
+
+        let request = ProviderRequest {
+            model: "claude-sonnet-4-20250514".into(),
+            system_prompt: Some("Fallback prompt.".into()),
+            system_blocks: Some(serde_json::json!({"text": "not an array"})),
+            messages: vec![],
+            max_tokens: 1024,
+            stream: false,
+            tools: None,
+        };
+        assert!(request.validate_system_blocks().is_err());
+
+        for _ in 0..2 {
+            let api_req = provider.to_message_request(&request);
+            match &api_req.system {
+                Some(SystemContent::Text(t)) => assert_eq!(t, "Fallback prompt."),
+                other => panic!("expected SystemContent::Text, got {:?}", other),
+            }
+        }
+        assert!(
+            provider
+                .system_blocks_error_reported
+                .load(Ordering::Relaxed)
+        );
+    }
+
     #[test]
     fn map_content_block_delta_text() {
         let mut tool_blocks = HashMap::new();
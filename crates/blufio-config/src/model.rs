@@ -188,6 +188,34 @@ pub struct BlufioConfig {
     /// GDPR data subject rights tooling settings.
     #[serde(default)]
     pub gdpr: GdprConfig,
+
+    /// Speech-to-text settings for inbound voice messages.
+    #[serde(default)]
+    pub transcription: TranscriptionConfig,
+
+    /// Text-to-speech settings for spoken replies.
+    #[serde(default)]
+    pub tts: TtsConfig,
+
+    /// Content-moderation settings for user input and agent replies.
+    #[serde(default)]
+    pub moderation: ModerationConfig,
+
+    /// PII redaction before messages are persisted or mined for memories.
+    #[serde(default)]
+    pub pii_redaction: PiiRedactionConfig,
+
+    /// Persistent retry queue for replies the channel failed to accept.
+    #[serde(default)]
+    pub outbound: OutboundConfig,
+
+    /// Offline mode for air-gapped deployments: never download models.
+    ///
+    /// The embedding model must then be pre-provisioned (see
+    /// `memory.model_dir`); without it the memory system is disabled.
+    /// Env var override: `BLUFIO_OFFLINE`.
+    #[serde(default)]
+    pub offline: bool,
 }
 
 /// Agent identity and behavior configuration.
@@ -214,6 +242,46 @@ pub struct AgentConfig {
     /// Takes precedence over `system_prompt` if both are set.
     #[serde(default)]
     pub system_prompt_file: Option<String>,
+
+    /// Maximum wall-clock duration of a single turn in seconds, covering every
+    /// provider call and tool execution in the tool loop. 0 disables the watchdog.
+    #[serde(default = "default_turn_timeout_secs")]
+    pub turn_timeout_secs: u64,
+
+    /// Maximum number of LLM provider requests in flight at once, across all
+    /// sessions. Excess turns queue for a slot. 0 means unlimited.
+    #[serde(default)]
+    pub max_concurrent_provider_requests: usize,
+
+    /// Seconds between typing-indicator refreshes while a reply is being
+    /// generated. Telegram drops the indicator after about 5 seconds, so the
+    /// default stays below that. 0 sends the indicator only once.
+    #[serde(default = "default_typing_refresh_secs")]
+    pub typing_refresh_secs: u64,
+
+    /// Follow-up requests made when a reply stops at `max_tokens`, each
+    /// asking the model to continue where it left off. The parts are joined
+    /// into one reply. 0 disables auto-continuation.
+    #[serde(default)]
+    pub max_continuations: u32,
+
+    /// Channels (e.g. "telegram") whose replies end with a one-line summary
+    /// of each tool call made during the turn. The model sees tool results
+    /// either way. Empty shows no summaries.
+    #[serde(default)]
+    pub show_tool_calls: Vec<String>,
+
+    /// Named system prompts a user can switch a session to with
+    /// `/persona <name>` (e.g. "concise", "detailed"). The choice lasts for
+    /// the rest of the session.
+    #[serde(default)]
+    pub personas: HashMap<String, String>,
+
+    /// Reply to an empty or whitespace-only message by asking the user what
+    /// they want. When false such messages are dropped silently. Either way
+    /// they never reach the LLM.
+    #[serde(default = "default_true")]
+    pub prompt_on_empty_message: bool,
 }
 
 impl Default for AgentConfig {
@@ -224,6 +292,13 @@ impl Default for AgentConfig {
             log_level: default_log_level(),
             system_prompt: None,
             system_prompt_file: None,
+            turn_timeout_secs: default_turn_timeout_secs(),
+            max_concurrent_provider_requests: 0,
+            typing_refresh_secs: default_typing_refresh_secs(),
+            max_continuations: 0,
+            show_tool_calls: Vec::new(),
+            personas: HashMap::new(),
+            prompt_on_empty_message: true,
         }
     }
 }
@@ -240,8 +315,16 @@ fn default_log_level() -> String {
     "info".to_string()
 }
 
+fn default_turn_timeout_secs() -> u64 {
+    300
+}
+
+fn default_typing_refresh_secs() -> u64 {
+    4
+}
+
 /// Telegram bot integration configuration.
-#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct TelegramConfig {
     /// Telegram Bot API token. `None` disables Telegram integration.
@@ -251,6 +334,60 @@ pub struct TelegramConfig {
     /// List of allowed Telegram user IDs or usernames.
     #[serde(default)]
     pub allowed_users: Vec<String>,
+
+    /// How long (in seconds) a delivered idempotency key is remembered.
+    /// A repeated send with the same key inside this window is not re-sent.
+    /// 0 disables deduplication.
+    #[serde(default = "default_telegram_idempotency_window_secs")]
+    pub idempotency_window_secs: u64,
+
+    /// Accept messages from group and supergroup chats, not just DMs.
+    #[serde(default)]
+    pub allow_groups: bool,
+
+    /// In groups, only respond when the bot is @-mentioned or replied to.
+    /// Has no effect on DMs or when `allow_groups` is false.
+    #[serde(default = "default_true")]
+    pub respond_on_mention_only: bool,
+
+    /// Minimum milliseconds between edits of a reply while it streams.
+    /// Deltas arriving in between are coalesced into the next edit, and the
+    /// complete reply is always written by a final edit. Keeps fast token
+    /// streams under Telegram's flood limits.
+    #[serde(default = "default_telegram_edit_throttle_ms")]
+    pub edit_throttle_ms: u64,
+
+    /// Largest photo, in bytes, passed to the model as an image.
+    /// Telegram keeps several resolutions of each photo; the largest one
+    /// under this limit is used, and photos with none that fit are rejected.
+    #[serde(default = "default_telegram_max_image_bytes")]
+    pub max_image_bytes: usize,
+}
+
+impl Default for TelegramConfig {
+    fn default() -> Self {
+        Self {
+            bot_token: None,
+            allowed_users: Vec::new(),
+            idempotency_window_secs: default_telegram_idempotency_window_secs(),
+            allow_groups: false,
+            respond_on_mention_only: true,
+            edit_throttle_ms: default_telegram_edit_throttle_ms(),
+            max_image_bytes: default_telegram_max_image_bytes(),
+        }
+    }
+}
+
+fn default_telegram_idempotency_window_secs() -> u64 {
+    300
+}
+
+fn default_telegram_edit_throttle_ms() -> u64 {
+    1500
+}
+
+fn default_telegram_max_image_bytes() -> usize {
+    5 * 1024 * 1024
 }
 
 /// Discord bot integration configuration.
@@ -575,6 +712,20 @@ pub struct AnthropicConfig {
     /// Anthropic API version string.
     #[serde(default = "default_api_version")]
     pub api_version: String,
+
+    /// Maximum retries for transient errors (429/500/503/529, connection failures).
+    /// 0 disables retry.
+    #[serde(default = "default_anthropic_max_retries")]
+    pub max_retries: u32,
+
+    /// Initial backoff delay in milliseconds, doubled on each retry (with jitter).
+    #[serde(default = "default_anthropic_retry_base_delay_ms")]
+    pub retry_base_delay_ms: u64,
+
+    /// Upper bound on a single backoff delay in milliseconds, including
+    /// server-provided `retry-after` values.
+    #[serde(default = "default_anthropic_retry_max_delay_ms")]
+    pub retry_max_delay_ms: u64,
 }
 
 impl Default for AnthropicConfig {
@@ -584,10 +735,25 @@ impl Default for AnthropicConfig {
             default_model: default_model(),
             max_tokens: default_max_tokens(),
             api_version: default_api_version(),
+            max_retries: default_anthropic_max_retries(),
+            retry_base_delay_ms: default_anthropic_retry_base_delay_ms(),
+            retry_max_delay_ms: default_anthropic_retry_max_delay_ms(),
         }
     }
 }
 
+fn default_anthropic_max_retries() -> u32 {
+    3
+}
+
+fn default_anthropic_retry_base_delay_ms() -> u64 {
+    1000
+}
+
+fn default_anthropic_retry_max_delay_ms() -> u64 {
+    30_000
+}
+
 fn default_model() -> String {
     "claude-sonnet-4-20250514".to_string()
 }
@@ -611,6 +777,39 @@ pub struct StorageConfig {
     /// Enable WAL (Write-Ahead Logging) mode for SQLite.
     #[serde(default = "default_wal_mode")]
     pub wal_mode: bool,
+
+    /// WAL size in pages at which SQLite automatically checkpoints
+    /// (`PRAGMA wal_autocheckpoint`). 0 disables automatic checkpoints.
+    /// Forced to 0 when Litestream replication is enabled.
+    #[serde(default = "default_wal_autocheckpoint_pages")]
+    pub wal_autocheckpoint_pages: u32,
+
+    /// Interval in seconds for the background `wal_checkpoint(TRUNCATE)` task
+    /// run by `blufio serve`. 0 disables the periodic checkpoint.
+    #[serde(default = "default_wal_checkpoint_interval_secs")]
+    pub wal_checkpoint_interval_secs: u64,
+
+    /// Interval in seconds for a scheduled `blufio maintain` (checkpoint and
+    /// VACUUM) inside `blufio serve`. VACUUM blocks writers while it rewrites
+    /// the file, so keep this long (e.g. weekly). 0 disables it.
+    #[serde(default)]
+    pub vacuum_interval_secs: u64,
+
+    /// Number of read-only connections used for session and message reads,
+    /// so they do not queue behind writes. Only used with `wal_mode`.
+    /// 0 sends every read through the writer connection.
+    #[serde(default = "default_read_pool_size")]
+    pub read_pool_size: usize,
+
+    /// Backend for sessions, messages and the queue: "sqlite" or "redis".
+    /// With "redis", several `blufio serve` replicas can share conversation
+    /// state; cost, memory and other data stay in SQLite.
+    #[serde(default = "default_storage_backend")]
+    pub backend: String,
+
+    /// Redis connection settings, used when `backend = "redis"`.
+    #[serde(default)]
+    pub redis: RedisStorageConfig,
 }
 
 impl Default for StorageConfig {
@@ -618,6 +817,12 @@ impl Default for StorageConfig {
         Self {
             database_path: default_database_path(),
             wal_mode: default_wal_mode(),
+            wal_autocheckpoint_pages: default_wal_autocheckpoint_pages(),
+            wal_checkpoint_interval_secs: default_wal_checkpoint_interval_secs(),
+            vacuum_interval_secs: 0,
+            read_pool_size: default_read_pool_size(),
+            backend: default_storage_backend(),
+            redis: RedisStorageConfig::default(),
         }
     }
 }
@@ -634,6 +839,82 @@ fn default_wal_mode() -> bool {
     true
 }
 
+fn default_wal_autocheckpoint_pages() -> u32 {
+    1000
+}
+
+fn default_wal_checkpoint_interval_secs() -> u64 {
+    300
+}
+
+fn default_read_pool_size() -> usize {
+    2
+}
+
+fn default_storage_backend() -> String {
+    "sqlite".to_string()
+}
+
+/// Redis storage backend configuration.
+///
+/// # Example TOML
+///
+/// ```toml
+/// [storage]
+/// backend = "redis"
+///
+/// [storage.redis]
+/// url = "redis://redis.internal:6379/0"
+/// pool_size = 8
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RedisStorageConfig {
+    /// Redis connection URL (`redis://` or `rediss://`).
+    #[serde(default = "default_redis_url")]
+    pub url: String,
+
+    /// Number of multiplexed connections shared round-robin.
+    #[serde(default = "default_redis_pool_size")]
+    pub pool_size: usize,
+
+    /// Prefix for every key, so several deployments can share a server.
+    #[serde(default = "default_redis_key_prefix")]
+    pub key_prefix: String,
+
+    /// Milliseconds a dequeued entry may stay unacknowledged before another
+    /// replica reclaims it.
+    #[serde(default = "default_redis_claim_idle_ms")]
+    pub claim_idle_ms: u64,
+}
+
+impl Default for RedisStorageConfig {
+    fn default() -> Self {
+        Self {
+            url: default_redis_url(),
+            pool_size: default_redis_pool_size(),
+            key_prefix: default_redis_key_prefix(),
+            claim_idle_ms: default_redis_claim_idle_ms(),
+        }
+    }
+}
+
+fn default_redis_url() -> String {
+    "redis://127.0.0.1:6379".to_string()
+}
+
+fn default_redis_pool_size() -> usize {
+    4
+}
+
+fn default_redis_key_prefix() -> String {
+    "blufio".to_string()
+}
+
+fn default_redis_claim_idle_ms() -> u64 {
+    300_000
+}
+
 /// Network and TLS security configuration.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -681,9 +962,29 @@ pub struct CostConfig {
     #[serde(default)]
     pub monthly_budget_usd: Option<f64>,
 
+    /// Maximum spend in any sliding 60-minute window, in USD. Stops a
+    /// runaway loop long before the daily cap would. `None` means no limit.
+    #[serde(default)]
+    pub hourly_budget_usd: Option<f64>,
+
     /// Whether to track token usage for cost estimation.
     #[serde(default = "default_track_tokens")]
     pub track_tokens: bool,
+
+    /// Percentages of the daily and monthly caps at which a budget alert
+    /// fires. Each fires at most once per budget period.
+    #[serde(default = "default_alert_thresholds_pct")]
+    pub alert_thresholds_pct: Vec<f64>,
+
+    /// Channel that budget alerts are sent on (e.g. "telegram").
+    /// Defaults to the agent's primary channel.
+    #[serde(default)]
+    pub alert_channel: Option<String>,
+
+    /// Chat ID of the admin who receives budget alerts. Alerts are only
+    /// sent when this is set.
+    #[serde(default)]
+    pub alert_chat_id: Option<String>,
 }
 
 impl Default for CostConfig {
@@ -691,7 +992,11 @@ impl Default for CostConfig {
         Self {
             daily_budget_usd: None,
             monthly_budget_usd: None,
+            hourly_budget_usd: None,
             track_tokens: default_track_tokens(),
+            alert_thresholds_pct: default_alert_thresholds_pct(),
+            alert_channel: None,
+            alert_chat_id: None,
         }
     }
 }
@@ -700,6 +1005,10 @@ fn default_track_tokens() -> bool {
     true
 }
 
+fn default_alert_thresholds_pct() -> Vec<f64> {
+    vec![50.0, 80.0, 95.0, 100.0]
+}
+
 /// Credential vault configuration.
 ///
 /// Controls Argon2id key derivation parameters used to protect the vault
@@ -829,6 +1138,17 @@ pub struct ContextConfig {
     /// Maximum number of archives to retain per user.
     #[serde(default = "default_max_archives")]
     pub max_archives: u32,
+
+    /// Hard cap on the number of messages in a single provider request.
+    /// Older messages beyond the cap are dropped. A safety net on top of
+    /// compaction, not a substitute for it.
+    #[serde(default = "default_max_request_messages")]
+    pub max_request_messages: usize,
+
+    /// Hard cap on the approximate size in bytes of all message content in a
+    /// single provider request. Older messages beyond the cap are dropped.
+    #[serde(default = "default_max_request_bytes")]
+    pub max_request_bytes: usize,
 }
 
 impl Default for ContextConfig {
@@ -854,6 +1174,8 @@ impl Default for ContextConfig {
             conditional_zone_budget: default_conditional_zone_budget(),
             archive_enabled: true,
             max_archives: default_max_archives(),
+            max_request_messages: default_max_request_messages(),
+            max_request_bytes: default_max_request_bytes(),
         }
     }
 }
@@ -950,6 +1272,14 @@ fn default_max_archives() -> u32 {
     10
 }
 
+fn default_max_request_messages() -> usize {
+    500
+}
+
+fn default_max_request_bytes() -> usize {
+    8 * 1024 * 1024
+}
+
 /// Memory system configuration.
 ///
 /// Controls long-term memory extraction, storage, retrieval, scoring,
@@ -970,6 +1300,11 @@ pub struct MemoryConfig {
     #[serde(default = "default_model_name")]
     pub model_name: String,
 
+    /// Directory holding a pre-provisioned `model.onnx` and `tokenizer.json`.
+    /// Defaults to `models/all-MiniLM-L6-v2` next to the database.
+    #[serde(default)]
+    pub model_dir: Option<String>,
+
     /// Model to use for memory extraction (Haiku for cost efficiency).
     #[serde(default = "default_extraction_model")]
     pub extraction_model: String,
@@ -982,6 +1317,29 @@ pub struct MemoryConfig {
     #[serde(default = "default_max_retrieval_results")]
     pub max_retrieval_results: usize,
 
+    // --- Fusion parameters ---
+    /// Reciprocal Rank Fusion constant `k` in `weight / (k + rank)`. Larger
+    /// values flatten the difference between top and lower ranks.
+    #[serde(default = "default_rrf_k")]
+    pub rrf_k: f64,
+
+    /// Weight of vector-similarity ranks in the fused score.
+    #[serde(default = "default_fusion_weight")]
+    pub vector_weight: f64,
+
+    /// Weight of BM25 keyword ranks in the fused score.
+    #[serde(default = "default_fusion_weight")]
+    pub bm25_weight: f64,
+
+    /// Maximum number of memories returned after fusion, scoring, and MMR.
+    #[serde(default = "default_retrieval_top_k")]
+    pub top_k: usize,
+
+    /// Minimum final score (fused score after boost and decay) a memory
+    /// needs to be returned. 0.0 disables the cutoff.
+    #[serde(default)]
+    pub min_score: f64,
+
     // --- Scoring parameters ---
     /// Exponential decay factor applied per day since memory creation.
     /// `max(decay_factor^days, decay_floor)`. File-sourced memories skip decay.
@@ -1022,6 +1380,19 @@ pub struct MemoryConfig {
     #[serde(default = "default_stale_threshold_days")]
     pub stale_threshold_days: u64,
 
+    // --- Access recency ---
+    /// Decay factor applied per day since a memory was last retrieved.
+    /// `max(factor^idle_days, decay_floor)`. Memories never retrieved and
+    /// file-sourced memories are not decayed. 1.0 (the default) disables
+    /// access-based decay.
+    #[serde(default = "default_access_decay_factor")]
+    pub access_decay_factor: f64,
+
+    /// Days without retrieval after which a memory is archived (status
+    /// `archived`, excluded from retrieval). 0 disables archival.
+    #[serde(default)]
+    pub archive_after_days: u64,
+
     // --- File watcher ---
     /// File watcher configuration for auto-indexing workspace files.
     #[serde(default)]
@@ -1033,6 +1404,80 @@ pub struct MemoryConfig {
     /// Requires restart to take effect (hot reload does not trigger vec0 population).
     #[serde(default)]
     pub vec0_enabled: bool,
+
+    // --- Context injection ---
+    /// Where retrieved memories go in the prompt: "system" appends the
+    /// labeled memory block to the system prompt, "conditional" places it
+    /// as a separate message before the conversation history.
+    #[serde(default = "default_memory_injection_placement")]
+    pub injection_placement: String,
+
+    // --- Deduplication ---
+    /// Cosine similarity at or above which a new memory is treated as a
+    /// near-duplicate of an existing one instead of being inserted.
+    #[serde(default = "default_dedup_threshold")]
+    pub dedup_threshold: f64,
+
+    /// What to do with a near-duplicate: "skip" drops it, "merge" refreshes
+    /// the existing memory's `updated_at` and records the new source on it.
+    #[serde(default = "default_dedup_strategy")]
+    pub dedup_strategy: String,
+
+    // --- Embedding backend ---
+    /// Remote embedding API used instead of the local ONNX model when its
+    /// `url` is set.
+    #[serde(default)]
+    pub remote_embedding: RemoteEmbeddingConfig,
+}
+
+/// Configuration for an OpenAI-compatible remote embedding API.
+///
+/// Lets small deployments skip the local model download and inference.
+/// The API must produce vectors of the same size as those already stored.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteEmbeddingConfig {
+    /// Base URL of the API, e.g. `https://api.openai.com/v1`. Requests go to
+    /// `{url}/embeddings`. Unset keeps the local ONNX embedder.
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Embedding model name sent with each request.
+    #[serde(default = "default_remote_embedding_model")]
+    pub model: String,
+
+    /// Bearer token for the API.
+    #[serde(default)]
+    pub api_key: Option<String>,
+
+    /// Output size requested from models that support shortening, e.g. 384
+    /// to stay compatible with memories embedded by the local model.
+    #[serde(default)]
+    pub dimensions: Option<usize>,
+
+    /// Request timeout in seconds.
+    #[serde(default = "default_remote_embedding_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for RemoteEmbeddingConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            model: default_remote_embedding_model(),
+            api_key: None,
+            dimensions: None,
+            timeout_secs: default_remote_embedding_timeout_secs(),
+        }
+    }
+}
+
+fn default_remote_embedding_model() -> String {
+    "text-embedding-3-small".to_string()
+}
+
+fn default_remote_embedding_timeout_secs() -> u64 {
+    30
 }
 
 /// Configuration for the file watcher subsystem.
@@ -1071,9 +1516,15 @@ impl Default for MemoryConfig {
             enabled: default_memory_enabled(),
             similarity_threshold: default_similarity_threshold(),
             model_name: default_model_name(),
+            model_dir: None,
             extraction_model: default_extraction_model(),
             idle_timeout_secs: default_idle_timeout_secs(),
             max_retrieval_results: default_max_retrieval_results(),
+            rrf_k: default_rrf_k(),
+            vector_weight: default_fusion_weight(),
+            bm25_weight: default_fusion_weight(),
+            top_k: default_retrieval_top_k(),
+            min_score: 0.0,
             decay_factor: default_decay_factor(),
             decay_floor: default_decay_floor(),
             mmr_lambda: default_mmr_lambda(),
@@ -1083,8 +1534,14 @@ impl Default for MemoryConfig {
             max_entries: default_max_entries(),
             eviction_sweep_interval_secs: default_eviction_sweep_interval_secs(),
             stale_threshold_days: default_stale_threshold_days(),
+            access_decay_factor: default_access_decay_factor(),
+            archive_after_days: 0,
             file_watcher: FileWatcherConfig::default(),
             vec0_enabled: true,
+            injection_placement: default_memory_injection_placement(),
+            dedup_threshold: default_dedup_threshold(),
+            dedup_strategy: default_dedup_strategy(),
+            remote_embedding: RemoteEmbeddingConfig::default(),
         }
     }
 }
@@ -1113,6 +1570,18 @@ fn default_max_retrieval_results() -> usize {
     50
 }
 
+fn default_rrf_k() -> f64 {
+    60.0
+}
+
+fn default_fusion_weight() -> f64 {
+    1.0
+}
+
+fn default_retrieval_top_k() -> usize {
+    50
+}
+
 fn default_decay_factor() -> f64 {
     0.95
 }
@@ -1149,6 +1618,22 @@ fn default_stale_threshold_days() -> u64 {
     180
 }
 
+fn default_access_decay_factor() -> f64 {
+    1.0
+}
+
+fn default_memory_injection_placement() -> String {
+    "conditional".to_string()
+}
+
+fn default_dedup_threshold() -> f64 {
+    0.9
+}
+
+fn default_dedup_strategy() -> String {
+    "skip".to_string()
+}
+
 fn default_max_file_size() -> usize {
     102_400 // 100 KB
 }
@@ -1193,6 +1678,29 @@ pub struct RoutingConfig {
     /// Max tokens for complex tier responses.
     #[serde(default = "default_complex_max_tokens")]
     pub complex_max_tokens: u32,
+
+    /// Display name for the simple tier, used in budget downgrade notes.
+    #[serde(default = "default_simple_name")]
+    pub simple_name: String,
+
+    /// Display name for the standard tier.
+    #[serde(default = "default_standard_name")]
+    pub standard_name: String,
+
+    /// Display name for the complex tier.
+    #[serde(default = "default_complex_name")]
+    pub complex_name: String,
+
+    /// Share of the exemplar-embedding classifier in the blended tier
+    /// (0.0-1.0). Only applies when an embedding model is available.
+    #[serde(default = "default_embedding_blend_weight")]
+    pub embedding_blend_weight: f32,
+
+    /// Let per-message overrides (`/opus ...`) bypass budget downgrades.
+    /// When false, an override picks the tier but high budget utilization
+    /// still downgrades it like a classified message.
+    #[serde(default = "default_true")]
+    pub allow_override_budget: bool,
 }
 
 impl Default for RoutingConfig {
@@ -1206,6 +1714,11 @@ impl Default for RoutingConfig {
             simple_max_tokens: default_simple_max_tokens(),
             standard_max_tokens: default_standard_max_tokens(),
             complex_max_tokens: default_complex_max_tokens(),
+            simple_name: default_simple_name(),
+            standard_name: default_standard_name(),
+            complex_name: default_complex_name(),
+            embedding_blend_weight: default_embedding_blend_weight(),
+            allow_override_budget: true,
         }
     }
 }
@@ -1238,6 +1751,22 @@ fn default_complex_max_tokens() -> u32 {
     8192
 }
 
+fn default_simple_name() -> String {
+    "Haiku".to_string()
+}
+
+fn default_standard_name() -> String {
+    "Sonnet".to_string()
+}
+
+fn default_complex_name() -> String {
+    "Opus".to_string()
+}
+
+fn default_embedding_blend_weight() -> f32 {
+    0.5
+}
+
 /// Smart heartbeat configuration.
 ///
 /// Controls proactive check-in behavior. Heartbeats run on Haiku
@@ -1330,6 +1859,53 @@ pub struct SkillConfig {
     /// Enable the skill system. When false, no skills are loaded or executed.
     #[serde(default = "default_skill_enabled")]
     pub enabled: bool,
+
+    /// Maximum seconds a single tool invocation may run before the agent gives
+    /// up on it and returns an error result to the LLM. 0 disables the limit.
+    #[serde(default = "default_tool_timeout_secs")]
+    pub tool_timeout_secs: u64,
+
+    /// Most tool calls from one LLM turn that run concurrently. Tools that
+    /// are not parallel-safe (bash, file) always run on their own.
+    #[serde(default = "default_max_parallel_tools")]
+    pub max_parallel_tools: usize,
+
+    /// Ask paired operator devices (the node system) to grant a capability a
+    /// WASM skill was denied; approved grants are saved to the skill's manifest.
+    #[serde(default)]
+    pub capability_prompts: bool,
+
+    /// Report bash commands instead of running them. Useful for trying out
+    /// prompts and tool flows without touching the host.
+    #[serde(default)]
+    pub bash_dry_run: bool,
+
+    /// Command filtering for the built-in bash tool.
+    #[serde(default)]
+    pub bash: BashToolConfig,
+
+    /// Limits for the built-in http tool.
+    #[serde(default)]
+    pub http: HttpToolConfig,
+
+    /// Path restriction and read cap for the built-in file tool.
+    #[serde(default)]
+    pub file: FileToolConfig,
+
+    /// The built-in grep tool (off by default).
+    #[serde(default)]
+    pub grep: GrepToolConfig,
+
+    /// Hex-encoded Ed25519 public keys of publishers whose skill signatures
+    /// are trusted. A valid signature from any other key installs the skill
+    /// as `untrusted`.
+    #[serde(default)]
+    pub trusted_publishers: Vec<String>,
+
+    /// Refuse to load skills that are not signed by a publisher currently
+    /// in `trusted_publishers`.
+    #[serde(default)]
+    pub require_verified: bool,
 }
 
 impl Default for SkillConfig {
@@ -1341,6 +1917,16 @@ impl Default for SkillConfig {
             default_epoch_timeout_secs: default_skill_epoch_timeout(),
             max_skills_in_prompt: default_max_skills_in_prompt(),
             enabled: default_skill_enabled(),
+            tool_timeout_secs: default_tool_timeout_secs(),
+            max_parallel_tools: default_max_parallel_tools(),
+            capability_prompts: false,
+            bash_dry_run: false,
+            bash: BashToolConfig::default(),
+            http: HttpToolConfig::default(),
+            file: FileToolConfig::default(),
+            grep: GrepToolConfig::default(),
+            trusted_publishers: Vec::new(),
+            require_verified: false,
         }
     }
 }
@@ -1373,6 +1959,130 @@ fn default_skill_enabled() -> bool {
     false
 }
 
+fn default_tool_timeout_secs() -> u64 {
+    60
+}
+
+fn default_max_parallel_tools() -> usize {
+    4
+}
+
+/// Command filtering for the built-in bash tool.
+///
+/// Whitespace is normalized before matching. Denied substrings are searched
+/// in the whole command line; when `allowed_prefixes` is non-empty, each
+/// command of a chain (`&&`, `||`, `;`, `|`, `&`, newlines) must start with
+/// one of them.
+///
+/// ```toml
+/// [skill.bash]
+/// allowed_prefixes = ["ls", "cat", "git status"]
+/// denied_substrings = ["rm -rf", "curl | sh"]
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BashToolConfig {
+    /// Command prefixes that may run. Empty allows any command.
+    #[serde(default)]
+    pub allowed_prefixes: Vec<String>,
+
+    /// Substrings that deny a command wherever they appear.
+    #[serde(default)]
+    pub denied_substrings: Vec<String>,
+}
+
+/// Limits for the built-in http tool.
+///
+/// ```toml
+/// [skill.http]
+/// max_response_bytes = 51200
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct HttpToolConfig {
+    /// Response body bytes returned to the LLM; longer bodies are cut and
+    /// flagged as truncated.
+    #[serde(default = "default_http_max_response_bytes")]
+    pub max_response_bytes: usize,
+}
+
+impl Default for HttpToolConfig {
+    fn default() -> Self {
+        Self {
+            max_response_bytes: default_http_max_response_bytes(),
+        }
+    }
+}
+
+fn default_http_max_response_bytes() -> usize {
+    50 * 1024
+}
+
+/// Path restriction and read cap for the built-in file tool.
+///
+/// ```toml
+/// [skill.file]
+/// allowed_root = "/home/me/workspace"
+/// max_bytes = 102400
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileToolConfig {
+    /// Directory that reads and writes must stay inside. Unset allows any path.
+    #[serde(default)]
+    pub allowed_root: Option<String>,
+
+    /// Maximum bytes of file content returned by one read.
+    #[serde(default = "default_file_max_bytes")]
+    pub max_bytes: usize,
+}
+
+impl Default for FileToolConfig {
+    fn default() -> Self {
+        Self {
+            allowed_root: None,
+            max_bytes: default_file_max_bytes(),
+        }
+    }
+}
+
+fn default_file_max_bytes() -> usize {
+    100 * 1024
+}
+
+/// The built-in grep tool. Searches are confined to `[skill.file]
+/// allowed_root` when that is set.
+///
+/// ```toml
+/// [skill.grep]
+/// enabled = true
+/// max_matches = 200
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GrepToolConfig {
+    /// Register the grep tool.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Matches returned before a search stops.
+    #[serde(default = "default_grep_max_matches")]
+    pub max_matches: usize,
+}
+
+impl Default for GrepToolConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_matches: default_grep_max_matches(),
+        }
+    }
+}
+
+fn default_grep_max_matches() -> usize {
+    200
+}
+
 /// Plugin system configuration.
 ///
 /// Controls which compiled-in adapters are enabled/disabled.
@@ -1419,6 +2129,52 @@ pub struct GatewayConfig {
     /// OpenAPI documentation settings.
     #[serde(default)]
     pub openapi: OpenApiConfig,
+    /// Require the same auth as the `/v1` API (bearer token, API key or
+    /// keypair signature) for `GET /metrics`. Off by default so Prometheus
+    /// can scrape without credentials.
+    #[serde(default)]
+    pub metrics_require_auth: bool,
+    /// CIDR ranges (e.g. "10.0.0.0/8", "::1/128") allowed to reach
+    /// `GET /metrics`; a bare address means that single host. Requests from
+    /// other peers get 403. Empty = any peer.
+    #[serde(default)]
+    pub metrics_allowlist: Vec<String>,
+    /// Sustained requests per minute allowed per authenticated identity
+    /// (scoped API key, or the master token/keypair as one identity),
+    /// enforced with an in-memory token bucket. 0 disables the limit.
+    #[serde(default)]
+    pub identity_requests_per_minute: u32,
+    /// Token bucket capacity: requests an identity may send back-to-back.
+    #[serde(default = "default_identity_burst")]
+    pub identity_burst: u32,
+    /// Maximum request body size in bytes. Larger bodies are rejected with
+    /// 413 before they reach a handler.
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+    /// How long (milliseconds) a request waits for room in the inbound
+    /// message queue when the agent loop is behind. On timeout the request
+    /// gets 503 instead of piling up in memory.
+    #[serde(default = "default_inbound_send_timeout_ms")]
+    pub inbound_send_timeout_ms: u64,
+    /// On shutdown, how long (seconds) in-flight requests may run before
+    /// they are failed and the server is stopped. New connections are
+    /// refused as soon as shutdown starts.
+    #[serde(default = "default_shutdown_drain_secs")]
+    pub shutdown_drain_secs: u64,
+    /// Cross-origin (CORS) policy for browser front-ends.
+    #[serde(default)]
+    pub cors: GatewayCorsConfig,
+    /// Seconds between WebSocket pings sent by the server, so proxies don't
+    /// drop quiet connections. 0 disables pings.
+    #[serde(default = "default_ws_ping_interval_secs")]
+    pub ws_ping_interval_secs: u64,
+    /// Seconds to wait for a pong before closing the WebSocket.
+    #[serde(default = "default_ws_pong_timeout_secs")]
+    pub ws_pong_timeout_secs: u64,
+    /// Close a WebSocket after this many seconds without a client message
+    /// (pongs don't count). 0 disables the idle timeout.
+    #[serde(default = "default_ws_idle_timeout_secs")]
+    pub ws_idle_timeout_secs: u64,
 }
 
 impl Default for GatewayConfig {
@@ -1432,6 +2188,17 @@ impl Default for GatewayConfig {
             default_rate_limit: default_rate_limit(),
             max_batch_size: default_max_batch_size(),
             openapi: OpenApiConfig::default(),
+            metrics_require_auth: false,
+            metrics_allowlist: Vec::new(),
+            identity_requests_per_minute: 0,
+            identity_burst: default_identity_burst(),
+            max_body_bytes: default_max_body_bytes(),
+            inbound_send_timeout_ms: default_inbound_send_timeout_ms(),
+            shutdown_drain_secs: default_shutdown_drain_secs(),
+            cors: GatewayCorsConfig::default(),
+            ws_ping_interval_secs: default_ws_ping_interval_secs(),
+            ws_pong_timeout_secs: default_ws_pong_timeout_secs(),
+            ws_idle_timeout_secs: default_ws_idle_timeout_secs(),
         }
     }
 }
@@ -1444,6 +2211,34 @@ fn default_max_batch_size() -> usize {
     100
 }
 
+fn default_identity_burst() -> u32 {
+    10
+}
+
+fn default_max_body_bytes() -> usize {
+    2 * 1024 * 1024
+}
+
+fn default_inbound_send_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_shutdown_drain_secs() -> u64 {
+    10
+}
+
+fn default_ws_ping_interval_secs() -> u64 {
+    30
+}
+
+fn default_ws_pong_timeout_secs() -> u64 {
+    10
+}
+
+fn default_ws_idle_timeout_secs() -> u64 {
+    900
+}
+
 fn default_gateway_enabled() -> bool {
     false
 }
@@ -1554,6 +2349,39 @@ pub struct OpenApiConfig {
     pub swagger_ui_enabled: bool,
 }
 
+/// Gateway CORS configuration.
+///
+/// Deny-all by default: no origin is allowed until listed. Preflight
+/// `OPTIONS` requests are answered without authentication.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct GatewayCorsConfig {
+    /// Origins allowed to call the API (e.g. "https://app.example.com").
+    /// "*" allows any origin.
+    pub allowed_origins: Vec<String>,
+    /// HTTP methods allowed in cross-origin requests. "*" allows any.
+    pub allowed_methods: Vec<String>,
+    /// Request headers allowed in cross-origin requests. "*" allows any.
+    /// The default covers bearer and keypair-signature auth.
+    pub allowed_headers: Vec<String>,
+}
+
+impl Default for GatewayCorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: Vec::new(),
+            allowed_methods: vec!["GET".to_string(), "POST".to_string(), "DELETE".to_string()],
+            allowed_headers: vec![
+                "authorization".to_string(),
+                "content-type".to_string(),
+                "accept".to_string(),
+                "x-signature".to_string(),
+                "x-timestamp".to_string(),
+            ],
+        }
+    }
+}
+
 /// Daemon and memory management configuration.
 ///
 /// Controls memory monitoring thresholds, health endpoint settings,
@@ -1574,6 +2402,16 @@ pub struct DaemonConfig {
     /// Port for the health endpoint. Defaults to the gateway port.
     #[serde(default = "default_health_port")]
     pub health_port: u16,
+
+    /// Minimum free disk space in MB at the database and model paths.
+    /// `blufio doctor --deep` warns when less than this is available.
+    #[serde(default = "default_disk_min_free_mb")]
+    pub disk_min_free_mb: u64,
+
+    /// Database size warning threshold in MB (main file plus WAL).
+    /// `blufio doctor --deep` warns when the database grows beyond this.
+    #[serde(default = "default_db_size_warn_mb")]
+    pub db_size_warn_mb: u64,
 }
 
 impl Default for DaemonConfig {
@@ -1582,6 +2420,8 @@ impl Default for DaemonConfig {
             memory_warn_mb: default_memory_warn_mb(),
             memory_limit_mb: default_memory_limit_mb(),
             health_port: default_health_port(),
+            disk_min_free_mb: default_disk_min_free_mb(),
+            db_size_warn_mb: default_db_size_warn_mb(),
         }
     }
 }
@@ -1594,6 +2434,14 @@ fn default_memory_limit_mb() -> u64 {
     200
 }
 
+fn default_disk_min_free_mb() -> u64 {
+    512
+}
+
+fn default_db_size_warn_mb() -> u64 {
+    1024
+}
+
 fn default_health_port() -> u16 {
     3000
 }
@@ -2719,6 +3567,7 @@ pub struct CronJobConfig {
 /// [retention]
 /// enabled = true
 /// grace_period_days = 7
+/// purge_interval_secs = 3600
 ///
 /// [retention.periods]
 /// messages = 90
@@ -2750,6 +3599,11 @@ pub struct RetentionConfig {
     /// Separate retention periods for Restricted-classified data.
     #[serde(default)]
     pub restricted: RetentionPeriods,
+
+    /// Seconds between message and session purges run by the agent loop.
+    /// 0 disables the in-process purge (cron jobs still run).
+    #[serde(default = "default_purge_interval_secs")]
+    pub purge_interval_secs: u64,
 }
 
 impl Default for RetentionConfig {
@@ -2759,6 +3613,7 @@ impl Default for RetentionConfig {
             grace_period_days: default_grace_period_days(),
             periods: RetentionPeriods::default(),
             restricted: RetentionPeriods::default(),
+            purge_interval_secs: default_purge_interval_secs(),
         }
     }
 }
@@ -2767,6 +3622,10 @@ fn default_grace_period_days() -> u64 {
     7
 }
 
+fn default_purge_interval_secs() -> u64 {
+    3600
+}
+
 /// Per-type retention periods in days.
 ///
 /// Each field specifies the number of days before records of that type
@@ -3003,6 +3862,288 @@ fn default_gdpr_format() -> String {
     "json".to_string()
 }
 
+// ---------------------------------------------------------------------------
+// Transcription configuration
+// ---------------------------------------------------------------------------
+
+/// Speech-to-text configuration for inbound voice messages.
+///
+/// Transcription is disabled unless `command` is set. The command runs once
+/// per voice message with `{input}` in `args` replaced by the path of a
+/// temporary audio file and `{language}` by the language hint (or `auto`);
+/// its trimmed stdout becomes the message text.
+///
+/// # Example TOML
+///
+/// ```toml
+/// [transcription]
+/// command = "whisper-cli"
+/// args = ["-m", "/opt/whisper/ggml-base.bin", "-l", "{language}", "-nt", "-np", "-f", "{input}"]
+/// language = "en"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TranscriptionConfig {
+    /// Transcription program to run. `None` disables transcription.
+    #[serde(default)]
+    pub command: Option<String>,
+
+    /// Arguments for `command`, with `{input}` and `{language}` placeholders.
+    #[serde(default = "default_transcription_args")]
+    pub args: Vec<String>,
+
+    /// Language hint (ISO 639-1 code). `None` lets the backend detect it.
+    #[serde(default)]
+    pub language: Option<String>,
+
+    /// Maximum seconds to wait for one transcription.
+    #[serde(default = "default_transcription_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for TranscriptionConfig {
+    fn default() -> Self {
+        Self {
+            command: None,
+            args: default_transcription_args(),
+            language: None,
+            timeout_secs: default_transcription_timeout_secs(),
+        }
+    }
+}
+
+fn default_transcription_args() -> Vec<String> {
+    vec!["{input}".to_string()]
+}
+
+fn default_transcription_timeout_secs() -> u64 {
+    60
+}
+
+// ---------------------------------------------------------------------------
+// Text-to-speech configuration
+// ---------------------------------------------------------------------------
+
+/// Text-to-speech configuration for spoken replies.
+///
+/// Disabled unless `command` is set. When a user sends a voice message on a
+/// channel that supports voice, the reply is also sent as audio. The command
+/// gets the reply text on stdin, with `{output}`, `{voice}` and `{format}` in
+/// `args` replaced; it writes audio to the `{output}` file or, if `args` has
+/// no `{output}`, to stdout.
+///
+/// # Example TOML
+///
+/// ```toml
+/// [tts]
+/// command = "piper"
+/// args = ["--model", "/opt/piper/{voice}.onnx", "--output_file", "{output}"]
+/// voice = "en_US-amy-medium"
+/// output_format = "wav"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TtsConfig {
+    /// Synthesis program to run. `None` disables spoken replies.
+    #[serde(default)]
+    pub command: Option<String>,
+
+    /// Arguments for `command`, with `{output}`, `{voice}` and `{format}` placeholders.
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Voice identifier passed to the backend.
+    #[serde(default)]
+    pub voice: Option<String>,
+
+    /// Audio format the command produces (`ogg`, `mp3` or `wav`).
+    #[serde(default = "default_tts_output_format")]
+    pub output_format: String,
+
+    /// Replies longer than this many characters are sent as text only.
+    #[serde(default = "default_tts_max_chars")]
+    pub max_chars: usize,
+
+    /// Maximum seconds to wait for one synthesis.
+    #[serde(default = "default_tts_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for TtsConfig {
+    fn default() -> Self {
+        Self {
+            command: None,
+            args: Vec::new(),
+            voice: None,
+            output_format: default_tts_output_format(),
+            max_chars: default_tts_max_chars(),
+            timeout_secs: default_tts_timeout_secs(),
+        }
+    }
+}
+
+fn default_tts_output_format() -> String {
+    "ogg".to_string()
+}
+
+fn default_tts_max_chars() -> usize {
+    1000
+}
+
+fn default_tts_timeout_secs() -> u64 {
+    60
+}
+
+/// Content-moderation configuration.
+///
+/// Moderation runs only when a moderation adapter is installed; without one,
+/// all content is allowed. Listing `blocked_terms` installs the built-in
+/// keyword moderator. Blocked input never reaches the LLM and a blocked
+/// reply is never sent; the user gets `refusal_message` instead.
+///
+/// # Example TOML
+///
+/// ```toml
+/// [moderation]
+/// blocked_terms = ["wire transfer"]
+/// refusal_message = "I can't help with that here."
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModerationConfig {
+    /// Phrases that block input or a reply containing them, matched
+    /// case-insensitively. Empty leaves the keyword moderator off.
+    #[serde(default)]
+    pub blocked_terms: Vec<String>,
+
+    /// Message sent in place of blocked input or output.
+    #[serde(default = "default_moderation_refusal_message")]
+    pub refusal_message: String,
+}
+
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            blocked_terms: Vec::new(),
+            refusal_message: default_moderation_refusal_message(),
+        }
+    }
+}
+
+fn default_moderation_refusal_message() -> String {
+    "Sorry, I can't help with that.".to_string()
+}
+
+/// PII redaction configuration.
+///
+/// Each flag enables redaction for one kind of stored content. Detected
+/// emails, phone numbers, SSNs and credit card numbers are replaced with
+/// placeholders such as `[EMAIL]`. With `keep_encrypted_original`, the
+/// unredacted text is stored in the vault under `pii-original/<message id>`
+/// (requires an unlocked vault). Context is assembled from storage, so the
+/// LLM sees redacted history as well. `provider_requests` also redacts the
+/// text of every request as it is sent to the LLM provider.
+///
+/// # Example TOML
+///
+/// ```toml
+/// [pii_redaction]
+/// user_messages = true
+/// assistant_messages = true
+/// memory_extraction = true
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PiiRedactionConfig {
+    /// Redact user messages before they are persisted.
+    #[serde(default)]
+    pub user_messages: bool,
+
+    /// Redact assistant responses before they are persisted.
+    #[serde(default)]
+    pub assistant_messages: bool,
+
+    /// Redact tool results before they are persisted.
+    #[serde(default)]
+    pub tool_results: bool,
+
+    /// Redact conversation text before memories are extracted from it.
+    #[serde(default)]
+    pub memory_extraction: bool,
+
+    /// Keep the unredacted text of persisted messages encrypted in the vault.
+    #[serde(default)]
+    pub keep_encrypted_original: bool,
+
+    /// Redact the text of LLM provider requests as they are sent.
+    #[serde(default)]
+    pub provider_requests: bool,
+}
+
+impl PiiRedactionConfig {
+    /// Whether any redaction of stored content is enabled.
+    pub fn any_enabled(&self) -> bool {
+        self.user_messages || self.assistant_messages || self.tool_results || self.memory_extraction
+    }
+}
+
+/// Outbound retry queue configuration.
+///
+/// When enabled, a reply that the channel fails to accept is stored in the
+/// `outbound` queue and re-sent in the background with exponential backoff.
+/// After the queue's attempt limit the entry is left as `failed` (dead-lettered).
+///
+/// # Example TOML
+///
+/// ```toml
+/// [outbound]
+/// enabled = true
+/// base_backoff_ms = 1000
+/// max_backoff_ms = 60000
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutboundConfig {
+    /// Queue failed sends for retry instead of dropping them.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Delay before the first retry, in milliseconds. Doubles per attempt.
+    #[serde(default = "default_outbound_base_backoff_ms")]
+    pub base_backoff_ms: u64,
+
+    /// Upper bound on the retry delay, in milliseconds.
+    #[serde(default = "default_outbound_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+
+    /// How often an idle queue is checked for new entries, in milliseconds.
+    #[serde(default = "default_outbound_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+}
+
+impl Default for OutboundConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base_backoff_ms: default_outbound_base_backoff_ms(),
+            max_backoff_ms: default_outbound_max_backoff_ms(),
+            poll_interval_ms: default_outbound_poll_interval_ms(),
+        }
+    }
+}
+
+fn default_outbound_base_backoff_ms() -> u64 {
+    1000
+}
+
+fn default_outbound_max_backoff_ms() -> u64 {
+    60_000
+}
+
+fn default_outbound_poll_interval_ms() -> u64 {
+    1000
+}
+
 #[cfg(test)]
 mod providers_config_tests {
     use super::*;
@@ -3394,6 +4535,27 @@ cors_origins = ["https://app.example.com", "https://other.example.com"]
         assert!(config.mcp.cors_origins.is_empty());
     }
 
+    #[test]
+    fn gateway_cors_defaults_deny_all_and_parses() {
+        let config: BlufioConfig = toml::from_str("").unwrap();
+        assert!(config.gateway.cors.allowed_origins.is_empty());
+        assert_eq!(
+            config.gateway.cors.allowed_methods,
+            ["GET", "POST", "DELETE"]
+        );
+
+        let toml_str = r#"
+[gateway.cors]
+allowed_origins = ["https://app.example.com"]
+"#;
+        let config: BlufioConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.gateway.cors.allowed_origins,
+            ["https://app.example.com"]
+        );
+        assert!(!config.gateway.cors.allowed_headers.is_empty());
+    }
+
     #[test]
     fn mcp_health_check_interval_defaults_to_60() {
         let config: BlufioConfig = toml::from_str("").unwrap();
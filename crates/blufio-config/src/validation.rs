@@ -49,6 +49,76 @@ pub fn validate_config(config: &BlufioConfig) -> Result<(), Vec<ConfigError>> {
         });
     }
 
+    // Validate storage backend
+    if !["sqlite", "redis"].contains(&config.storage.backend.as_str()) {
+        errors.push(ConfigError::Validation {
+            message: format!(
+                "storage.backend must be \"sqlite\" or \"redis\", got \"{}\"",
+                config.storage.backend
+            ),
+        });
+    }
+
+    if config.storage.backend == "redis" && config.storage.redis.pool_size == 0 {
+        errors.push(ConfigError::Validation {
+            message: "storage.redis.pool_size must be at least 1".to_string(),
+        });
+    }
+
+    // Validate gateway metrics allowlist entries are IPs or CIDR ranges
+    for entry in &config.gateway.metrics_allowlist {
+        if entry.parse::<blufio_core::net::Cidr>().is_err() {
+            errors.push(ConfigError::Validation {
+                message: format!(
+                    "gateway.metrics_allowlist entry `{entry}` is not an IP address or CIDR range"
+                ),
+            });
+        }
+    }
+
+    // Validate gateway CORS entries
+    let cors = &config.gateway.cors;
+    for origin in &cors.allowed_origins {
+        if !is_valid_origin(origin) {
+            errors.push(ConfigError::Validation {
+                message: format!(
+                    "gateway.cors.allowed_origins entry `{origin}` must be \"*\" or scheme://host[:port]"
+                ),
+            });
+        }
+    }
+    for (field, entries) in [
+        ("allowed_methods", &cors.allowed_methods),
+        ("allowed_headers", &cors.allowed_headers),
+    ] {
+        for entry in entries {
+            if !is_valid_http_token(entry) {
+                errors.push(ConfigError::Validation {
+                    message: format!("gateway.cors.{field} entry `{entry}` is not a valid token"),
+                });
+            }
+        }
+    }
+
+    if config.gateway.ws_ping_interval_secs > 0 && config.gateway.ws_pong_timeout_secs == 0 {
+        errors.push(ConfigError::Validation {
+            message: "gateway.ws_pong_timeout_secs must be at least 1 when pings are enabled"
+                .to_string(),
+        });
+    }
+
+    if config.telegram.max_image_bytes == 0 {
+        errors.push(ConfigError::Validation {
+            message: "telegram.max_image_bytes must be at least 1".to_string(),
+        });
+    }
+
+    if config.gateway.max_body_bytes == 0 {
+        errors.push(ConfigError::Validation {
+            message: "gateway.max_body_bytes must be at least 1".to_string(),
+        });
+    }
+
     // Validate budget values are non-negative if set
     if let Some(daily) = config.cost.daily_budget_usd
         && daily < 0.0
@@ -94,6 +164,85 @@ pub fn validate_config(config: &BlufioConfig) -> Result<(), Vec<ConfigError>> {
         });
     }
 
+    // Validate memory injection placement
+    if !["system", "conditional"].contains(&config.memory.injection_placement.as_str()) {
+        errors.push(ConfigError::Validation {
+            message: format!(
+                "memory.injection_placement must be \"system\" or \"conditional\", got \"{}\"",
+                config.memory.injection_placement
+            ),
+        });
+    }
+
+    // Validate hybrid retrieval fusion
+    let memory = &config.memory;
+    if memory.rrf_k <= 0.0 {
+        errors.push(ConfigError::Validation {
+            message: format!("memory.rrf_k must be positive, got {}", memory.rrf_k),
+        });
+    }
+    for (name, weight) in [
+        ("vector_weight", memory.vector_weight),
+        ("bm25_weight", memory.bm25_weight),
+    ] {
+        if weight < 0.0 {
+            errors.push(ConfigError::Validation {
+                message: format!("memory.{name} must be non-negative, got {weight}"),
+            });
+        }
+    }
+    if memory.vector_weight == 0.0 && memory.bm25_weight == 0.0 {
+        errors.push(ConfigError::Validation {
+            message: "memory.vector_weight and memory.bm25_weight cannot both be 0".to_string(),
+        });
+    }
+    if memory.top_k == 0 {
+        errors.push(ConfigError::Validation {
+            message: "memory.top_k must be at least 1".to_string(),
+        });
+    }
+    if memory.min_score < 0.0 {
+        errors.push(ConfigError::Validation {
+            message: format!(
+                "memory.min_score must be non-negative, got {}",
+                memory.min_score
+            ),
+        });
+    }
+
+    // Validate memory deduplication
+    let dedup_threshold = memory.dedup_threshold;
+    if dedup_threshold <= 0.0 || dedup_threshold > 1.0 {
+        errors.push(ConfigError::Validation {
+            message: format!("memory.dedup_threshold must be in (0.0, 1.0], got {dedup_threshold}"),
+        });
+    }
+    if !["skip", "merge"].contains(&config.memory.dedup_strategy.as_str()) {
+        errors.push(ConfigError::Validation {
+            message: format!(
+                "memory.dedup_strategy must be \"skip\" or \"merge\", got \"{}\"",
+                config.memory.dedup_strategy
+            ),
+        });
+    }
+
+    // Validate the remote embedding endpoint
+    let remote = &config.memory.remote_embedding;
+    if let Some(ref url) = remote.url
+        && !(url.starts_with("http://") || url.starts_with("https://"))
+    {
+        errors.push(ConfigError::Validation {
+            message: format!(
+                "memory.remote_embedding.url must start with http:// or https://, got \"{url}\""
+            ),
+        });
+    }
+    if remote.dimensions == Some(0) {
+        errors.push(ConfigError::Validation {
+            message: "memory.remote_embedding.dimensions must be at least 1".to_string(),
+        });
+    }
+
     // Validate MCP auth_token is set when MCP is enabled
     if config.mcp.enabled && config.mcp.auth_token.is_none() {
         errors.push(ConfigError::Validation {
@@ -190,6 +339,17 @@ pub fn validate_config(config: &BlufioConfig) -> Result<(), Vec<ConfigError>> {
         }
     }
 
+    // Validate trusted skill publishers are 32-byte hex public keys
+    for key in &config.skill.trusted_publishers {
+        if key.len() != 64 || !key.bytes().all(|b| b.is_ascii_hexdigit()) {
+            errors.push(ConfigError::Validation {
+                message: format!(
+                    "skill.trusted_publishers entry `{key}` must be a 64-character hex public key"
+                ),
+            });
+        }
+    }
+
     if errors.is_empty() {
         Ok(())
     } else {
@@ -197,6 +357,31 @@ pub fn validate_config(config: &BlufioConfig) -> Result<(), Vec<ConfigError>> {
     }
 }
 
+/// A browser origin: `*`, or `http(s)://host[:port]` with no path.
+fn is_valid_origin(s: &str) -> bool {
+    if s == "*" {
+        return true;
+    }
+    let Some(rest) = s
+        .strip_prefix("https://")
+        .or_else(|| s.strip_prefix("http://"))
+    else {
+        return false;
+    };
+    !rest.is_empty()
+        && rest
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b':' | b'[' | b']'))
+}
+
+/// `*`, or an HTTP token as used for method and header names.
+fn is_valid_http_token(s: &str) -> bool {
+    s == "*"
+        || (!s.is_empty()
+            && s.bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -227,6 +412,109 @@ mod tests {
             .any(|e| matches!(e, ConfigError::Validation { message } if message.contains("daily_budget_usd"))));
     }
 
+    #[test]
+    fn invalid_metrics_allowlist_fails_validation() {
+        let mut config = BlufioConfig::default();
+        config.gateway.metrics_allowlist = vec![
+            "10.0.0.0/8".to_string(),
+            "::1".to_string(),
+            "10.0.0.0/33".to_string(),
+            "prometheus.local".to_string(),
+        ];
+        let errors = validate_config(&config).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .all(|e| matches!(e, ConfigError::Validation { message } if message.contains("metrics_allowlist"))));
+    }
+
+    #[test]
+    fn invalid_cors_entries_fail_validation() {
+        let mut config = BlufioConfig::default();
+        config.gateway.cors.allowed_origins = vec![
+            "https://app.example.com".to_string(),
+            "http://localhost:5173".to_string(),
+            "*".to_string(),
+            "app.example.com".to_string(),
+            "https://app.example.com/".to_string(),
+        ];
+        config.gateway.cors.allowed_methods = vec!["GET".to_string(), "GET POST".to_string()];
+        let errors = validate_config(&config).unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().all(
+            |e| matches!(e, ConfigError::Validation { message } if message.contains("gateway.cors"))
+        ));
+    }
+
+    #[test]
+    fn invalid_remote_embedding_fails_validation() {
+        let mut config = BlufioConfig::default();
+        config.memory.remote_embedding.url = Some("api.openai.com/v1".to_string());
+        config.memory.remote_embedding.dimensions = Some(0);
+        let errors = validate_config(&config).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| matches!(
+            e,
+            ConfigError::Validation { message } if message.contains("memory.remote_embedding")
+        )));
+
+        config.memory.remote_embedding.url = Some("https://api.openai.com/v1".to_string());
+        config.memory.remote_embedding.dimensions = Some(384);
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn invalid_retrieval_fusion_fails_validation() {
+        let mut config = BlufioConfig::default();
+        config.memory.rrf_k = 0.0;
+        config.memory.vector_weight = -1.0;
+        config.memory.top_k = 0;
+        config.memory.min_score = -0.5;
+        let errors = validate_config(&config).unwrap_err();
+        assert_eq!(errors.len(), 4);
+
+        config.memory.rrf_k = 60.0;
+        config.memory.vector_weight = 0.0;
+        config.memory.bm25_weight = 0.0;
+        config.memory.top_k = 10;
+        config.memory.min_score = 0.0;
+        let errors = validate_config(&config).unwrap_err();
+        assert_eq!(errors.len(), 1);
+
+        config.memory.vector_weight = 0.3;
+        config.memory.bm25_weight = 1.5;
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn invalid_memory_dedup_fails_validation() {
+        let mut config = BlufioConfig::default();
+        config.memory.dedup_threshold = 1.5;
+        config.memory.dedup_strategy = "replace".to_string();
+        let errors = validate_config(&config).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| matches!(
+            e,
+            ConfigError::Validation { message } if message.contains("memory.dedup_")
+        )));
+
+        config.memory.dedup_threshold = 0.95;
+        config.memory.dedup_strategy = "merge".to_string();
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn invalid_trusted_publisher_fails_validation() {
+        let mut config = BlufioConfig::default();
+        config.skill.trusted_publishers = vec!["ab".repeat(32), "not-a-key".to_string()];
+        let errors = validate_config(&config).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            ConfigError::Validation { message } if message.contains("not-a-key")
+        ));
+    }
+
     #[test]
     fn valid_custom_config_passes() {
         let mut config = BlufioConfig::default();
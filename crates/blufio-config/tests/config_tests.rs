@@ -5,7 +5,7 @@
 
 use blufio_config::diagnostic::{ConfigError, suggest_key};
 use blufio_config::model::BlufioConfig;
-use blufio_config::{load_and_validate_str, load_config_from_str};
+use blufio_config::{load_and_validate_str, load_config_from_path, load_config_from_str};
 
 /// Valid TOML with all known fields deserializes successfully.
 #[test]
@@ -233,6 +233,54 @@ track_tokens = false
     assert!(!config.cost.track_tokens);
 }
 
+/// Top-level `offline` flag and a pre-provisioned `memory.model_dir` parse.
+#[test]
+fn offline_mode_and_model_dir_parse() {
+    let toml = r#"
+offline = true
+
+[memory]
+model_dir = "/opt/blufio/models/all-MiniLM-L6-v2"
+"#;
+
+    let config = load_config_from_str(toml).expect("offline config should parse");
+    assert!(config.offline);
+    assert_eq!(
+        config.memory.model_dir.as_deref(),
+        Some("/opt/blufio/models/all-MiniLM-L6-v2")
+    );
+    assert!(!BlufioConfig::default().offline);
+}
+
+/// BLUFIO_OFFLINE maps to the top-level `offline` key.
+#[test]
+fn env_var_overrides_offline() {
+    figment::Jail::expect_with(|jail| {
+        jail.set_env("BLUFIO_OFFLINE", "true");
+        let config = load_config_from_path(&jail.directory().join("blufio.toml"))?;
+        assert!(config.offline);
+        Ok(())
+    });
+}
+
+/// Skill trust store and strict mode parse, and default to empty/off.
+#[test]
+fn skill_trust_store_parses() {
+    let toml = r#"
+[skill]
+trusted_publishers = ["3b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da29"]
+require_verified = true
+"#;
+
+    let config = load_config_from_str(toml).expect("skill trust config should parse");
+    assert_eq!(config.skill.trusted_publishers.len(), 1);
+    assert!(config.skill.require_verified);
+
+    let defaults = BlufioConfig::default();
+    assert!(defaults.skill.trusted_publishers.is_empty());
+    assert!(!defaults.skill.require_verified);
+}
+
 /// Unexpected top-level section is rejected by deny_unknown_fields.
 #[test]
 fn deny_unknown_fields_at_top_level() {
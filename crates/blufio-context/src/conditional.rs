@@ -24,7 +24,8 @@ use crate::compaction::archive;
 ///
 /// The context engine calls all registered providers during assembly
 /// and includes their output between the static zone (system prompt)
-/// and the dynamic zone (conversation history).
+/// and the dynamic zone (conversation history). Messages with role
+/// `system` are appended to the system prompt instead.
 #[async_trait]
 pub trait ConditionalProvider: Send + Sync {
     /// Returns context messages to inject for the given session.
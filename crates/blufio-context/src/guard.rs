@@ -0,0 +1,241 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Hard limits on the message list of a single provider request.
+//!
+//! Compaction normally keeps history well within the context budget, but a
+//! session with a runaway history or a buggy caller can still build a request
+//! with thousands of messages, which the provider rejects or bills heavily.
+//! [`enforce_request_limits`] caps the message count and approximate byte
+//! size, dropping the oldest messages first.
+
+use blufio_config::model::ContextConfig;
+use blufio_core::error::BlufioError;
+use blufio_core::types::{ContentBlock, ProviderMessage};
+use tracing::warn;
+
+/// Message-count and size caps for a single provider request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimits {
+    /// Maximum number of messages.
+    pub max_messages: usize,
+    /// Maximum approximate size of all message content, in bytes.
+    pub max_bytes: usize,
+}
+
+impl RequestLimits {
+    /// Creates request limits from the given context configuration.
+    pub fn from_config(config: &ContextConfig) -> Self {
+        Self {
+            max_messages: config.max_request_messages,
+            max_bytes: config.max_request_bytes,
+        }
+    }
+}
+
+/// Approximate size of a message's content in bytes.
+///
+/// Counts text, base64 image data, tool inputs as JSON, and tool results.
+pub fn message_size(msg: &ProviderMessage) -> usize {
+    msg.content
+        .iter()
+        .map(|block| match block {
+            ContentBlock::Text { text } => text.len(),
+            ContentBlock::Image { data, .. } => data.len(),
+            ContentBlock::ToolUse { name, input, .. } => name.len() + input.to_string().len(),
+            ContentBlock::ToolResult { content, .. } => content.len(),
+        })
+        .sum()
+}
+
+/// Trims `messages` to fit `limits`, keeping the most recent messages.
+///
+/// After trimming, leading assistant messages and tool results (whose
+/// `tool_use` was dropped) are removed too, so the request still starts
+/// with a user turn. Returns the number of messages dropped.
+///
+/// Fails if nothing would be left, e.g. when the most recent message alone
+/// exceeds the size cap.
+pub fn enforce_request_limits(
+    messages: &mut Vec<ProviderMessage>,
+    limits: &RequestLimits,
+) -> Result<usize, BlufioError> {
+    let mut kept = 0;
+    let mut bytes = 0;
+    for msg in messages.iter().rev() {
+        let size = message_size(msg);
+        if kept == limits.max_messages || bytes + size > limits.max_bytes {
+            break;
+        }
+        kept += 1;
+        bytes += size;
+    }
+
+    let total = messages.len();
+    if kept == total {
+        return Ok(0);
+    }
+
+    let mut start = total - kept;
+    while start < total && !starts_turn(&messages[start]) {
+        start += 1;
+    }
+    if start == total {
+        return Err(BlufioError::Internal(format!(
+            "provider request exceeds limits ({} messages, max_request_messages = {}, \
+             max_request_bytes = {}) and cannot be trimmed to a valid conversation",
+            total, limits.max_messages, limits.max_bytes
+        )));
+    }
+
+    messages.drain(..start);
+    warn!(
+        dropped = start,
+        kept = messages.len(),
+        max_messages = limits.max_messages,
+        max_bytes = limits.max_bytes,
+        "provider request over limits, dropped oldest messages"
+    );
+    Ok(start)
+}
+
+/// Whether `msg` can open a conversation: a user message that is not a tool result.
+fn starts_turn(msg: &ProviderMessage) -> bool {
+    msg.role == "user"
+        && !msg
+            .content
+            .iter()
+            .any(|block| matches!(block, ContentBlock::ToolResult { .. }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(role: &str, text: &str) -> ProviderMessage {
+        ProviderMessage {
+            role: role.to_string(),
+            content: vec![ContentBlock::Text {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    /// Alternating user/assistant history of `n` messages, "m0".."m{n-1}".
+    fn history(n: usize) -> Vec<ProviderMessage> {
+        (0..n)
+            .map(|i| {
+                let role = if i % 2 == 0 { "user" } else { "assistant" };
+                text(role, &format!("m{i}"))
+            })
+            .collect()
+    }
+
+    fn texts(messages: &[ProviderMessage]) -> Vec<String> {
+        messages
+            .iter()
+            .map(|m| match &m.content[0] {
+                ContentBlock::Text { text } => text.clone(),
+                other => panic!("unexpected block {other:?}"),
+            })
+            .collect()
+    }
+
+    const UNLIMITED_BYTES: usize = usize::MAX;
+
+    #[test]
+    fn within_limits_is_untouched() {
+        let mut messages = history(5);
+        let limits = RequestLimits {
+            max_messages: 5,
+            max_bytes: UNLIMITED_BYTES,
+        };
+        assert_eq!(enforce_request_limits(&mut messages, &limits).unwrap(), 0);
+        assert_eq!(messages.len(), 5);
+    }
+
+    #[test]
+    fn oversized_list_is_trimmed_to_cap() {
+        let mut messages = history(2001);
+        let limits = RequestLimits {
+            max_messages: 501,
+            max_bytes: UNLIMITED_BYTES,
+        };
+        let dropped = enforce_request_limits(&mut messages, &limits).unwrap();
+        assert_eq!(dropped, 1500);
+        assert_eq!(messages.len(), 501);
+    }
+
+    #[test]
+    fn trim_keeps_most_recent_messages() {
+        let mut messages = history(10);
+        let limits = RequestLimits {
+            max_messages: 3,
+            max_bytes: UNLIMITED_BYTES,
+        };
+        enforce_request_limits(&mut messages, &limits).unwrap();
+        // The window m7..m9 starts with an assistant turn, which is dropped.
+        assert_eq!(texts(&messages), vec!["m8", "m9"]);
+    }
+
+    #[test]
+    fn size_cap_drops_oldest_messages() {
+        let mut messages = vec![
+            text("user", &"a".repeat(100)),
+            text("assistant", &"b".repeat(100)),
+            text("user", &"c".repeat(50)),
+            text("assistant", "ok"),
+            text("user", "latest"),
+        ];
+        let limits = RequestLimits {
+            max_messages: 100,
+            max_bytes: 120,
+        };
+        let dropped = enforce_request_limits(&mut messages, &limits).unwrap();
+        assert_eq!(dropped, 2);
+        assert_eq!(messages.len(), 3);
+        assert_eq!(texts(&messages)[2], "latest");
+    }
+
+    #[test]
+    fn orphaned_tool_result_is_dropped() {
+        let mut messages = history(4);
+        messages.push(ProviderMessage {
+            role: "assistant".to_string(),
+            content: vec![ContentBlock::ToolUse {
+                id: "tu1".to_string(),
+                name: "bash".to_string(),
+                input: serde_json::json!({"command": "ls"}),
+            }],
+        });
+        messages.push(ProviderMessage {
+            role: "user".to_string(),
+            content: vec![ContentBlock::ToolResult {
+                tool_use_id: "tu1".to_string(),
+                content: "file.txt".to_string(),
+                is_error: None,
+            }],
+        });
+        messages.push(text("assistant", "done"));
+        messages.push(text("user", "thanks"));
+
+        let limits = RequestLimits {
+            max_messages: 3,
+            max_bytes: UNLIMITED_BYTES,
+        };
+        enforce_request_limits(&mut messages, &limits).unwrap();
+        assert_eq!(texts(&messages), vec!["thanks"]);
+    }
+
+    #[test]
+    fn oversized_latest_message_is_an_error() {
+        let mut messages = vec![text("user", "hi"), text("user", &"x".repeat(1000))];
+        let limits = RequestLimits {
+            max_messages: 100,
+            max_bytes: 500,
+        };
+        let err = enforce_request_limits(&mut messages, &limits).unwrap_err();
+        assert!(err.to_string().contains("max_request_bytes = 500"));
+        assert_eq!(messages.len(), 2);
+    }
+}
@@ -16,6 +16,7 @@ pub mod budget;
 pub mod compaction;
 pub mod conditional;
 pub mod dynamic;
+pub mod guard;
 pub mod static_zone;
 
 use std::sync::Arc;
@@ -30,6 +31,7 @@ pub use budget::ZoneBudget;
 pub use compaction::{generate_compaction_summary, persist_compaction_summary};
 pub use conditional::ConditionalProvider;
 pub use dynamic::{DynamicResult, DynamicZone};
+pub use guard::RequestLimits;
 pub use static_zone::StaticZone;
 
 /// Parameters for [`ContextEngine::assemble_with_boundaries`].
@@ -48,6 +50,9 @@ pub struct AssemblyParams<'a> {
     pub max_tokens: u32,
     /// Optional boundary manager for L3 HMAC protection.
     pub boundary_manager: Option<&'a blufio_injection::boundary::BoundaryManager>,
+    /// System prompt replacing the configured one for this request, e.g. a
+    /// session's persona. `None` uses the static zone as configured.
+    pub system_prompt: Option<&'a str>,
 }
 
 /// Result of context assembly, containing the provider request and any
@@ -97,6 +102,8 @@ pub struct ContextEngine {
     token_cache: Arc<TokenizerCache>,
     /// Per-zone token budget configuration.
     zone_budget: ZoneBudget,
+    /// Hard caps on the assembled request's message list.
+    request_limits: RequestLimits,
 }
 
 impl ContextEngine {
@@ -120,6 +127,7 @@ impl ContextEngine {
             compaction_model: context_config.compaction_model.clone(),
             token_cache,
             zone_budget,
+            request_limits: RequestLimits::from_config(context_config),
         })
     }
 
@@ -142,6 +150,7 @@ impl ContextEngine {
             model,
             max_tokens,
             boundary_manager: None,
+            system_prompt: None,
         })
         .await
     }
@@ -177,13 +186,17 @@ impl ContextEngine {
             model,
             max_tokens,
             boundary_manager,
+            system_prompt,
         } = params;
 
         // --- Step 1: Static zone ---
-        let system_blocks = self.static_zone.system_blocks();
-        let actual_static = self.static_zone.token_count(&self.token_cache, model).await;
-        self.static_zone
-            .check_budget(actual_static, self.zone_budget.static_budget);
+        let static_zone = match system_prompt {
+            Some(prompt) => std::borrow::Cow::Owned(StaticZone::from_prompt(prompt)),
+            None => std::borrow::Cow::Borrowed(&self.static_zone),
+        };
+        let mut system_blocks = static_zone.system_blocks();
+        let actual_static = static_zone.token_count(&self.token_cache, model).await;
+        static_zone.check_budget(actual_static, self.zone_budget.static_budget);
         metrics::gauge!("blufio_context_zone_tokens", "zone" => "static").set(actual_static as f64);
 
         // --- Step 2: Conditional zone ---
@@ -209,6 +222,7 @@ impl ContextEngine {
             budget::count_messages_tokens(&conditional_messages, counter.as_ref()).await;
         metrics::gauge!("blufio_context_zone_tokens", "zone" => "conditional")
             .set(actual_conditional as f64);
+        let conditional_messages = move_system_messages(&mut system_blocks, conditional_messages);
 
         // --- Step 3: Dynamic zone ---
         let dynamic_budget = self
@@ -248,6 +262,9 @@ impl ContextEngine {
         let mut all_messages = conditional_messages;
         all_messages.extend(dynamic_result.messages);
 
+        // Safety net beyond compaction: cap message count and size.
+        guard::enforce_request_limits(&mut all_messages, &self.request_limits)?;
+
         // --- Step 4b: L3 HMAC boundary protection ---
         // Wrap system blocks and messages with HMAC boundaries, then validate
         // and strip before the LLM sees the content.
@@ -318,6 +335,9 @@ impl ContextEngine {
             stream: true,
             tools: None,
         };
+        // Reject a malformed static zone here rather than in the provider,
+        // which would otherwise degrade every turn to a plain-text prompt.
+        request.validate_system_blocks()?;
 
         // --- Step 6: Return AssembledContext ---
         let compaction_model = if !dynamic_result.compaction_usages.is_empty() {
@@ -345,6 +365,11 @@ impl ContextEngine {
     pub fn static_zone(&self) -> &StaticZone {
         &self.static_zone
     }
+
+    /// Returns the message-count and size caps applied to assembled requests.
+    pub fn request_limits(&self) -> &RequestLimits {
+        &self.request_limits
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -357,6 +382,25 @@ static BOUNDARY_STRIP_RE: std::sync::LazyLock<regex::Regex> = std::sync::LazyLoc
         .expect("boundary strip regex must compile")
 });
 
+/// Moves conditional messages with role `system` into the system blocks.
+///
+/// Their text is appended after the system prompt as uncached blocks, since
+/// it changes from turn to turn. Other messages are returned in order.
+fn move_system_messages(
+    system_blocks: &mut serde_json::Value,
+    messages: Vec<blufio_core::types::ProviderMessage>,
+) -> Vec<blufio_core::types::ProviderMessage> {
+    let (system, rest): (Vec<_>, Vec<_>) = messages.into_iter().partition(|m| m.role == "system");
+    if let serde_json::Value::Array(blocks) = system_blocks {
+        for block in system.into_iter().flat_map(|m| m.content) {
+            if let blufio_core::types::ContentBlock::Text { text } = block {
+                blocks.push(serde_json::json!({"type": "text", "text": text}));
+            }
+        }
+    }
+    rest
+}
+
 /// Wrap text blocks within system_blocks JSON with HMAC boundary tokens.
 fn wrap_system_blocks(
     system_blocks: serde_json::Value,
@@ -572,4 +616,30 @@ mod tests {
         assert_eq!(ctx.dropped_providers.len(), 1);
         assert_eq!(ctx.dropped_providers[0], "archive");
     }
+
+    fn text_message(role: &str, text: &str) -> blufio_core::types::ProviderMessage {
+        blufio_core::types::ProviderMessage {
+            role: role.into(),
+            content: vec![blufio_core::types::ContentBlock::Text { text: text.into() }],
+        }
+    }
+
+    #[test]
+    fn system_role_conditional_messages_join_system_blocks() {
+        let mut system_blocks = serde_json::json!([{"type": "text", "text": "sys"}]);
+        let messages = vec![
+            text_message("user", "archive summary"),
+            text_message("system", "Relevant memories:\n- likes tea"),
+        ];
+
+        let rest = move_system_messages(&mut system_blocks, messages);
+
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].role, "user");
+        let blocks = system_blocks.as_array().unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0]["text"], "sys");
+        assert_eq!(blocks[1]["text"], "Relevant memories:\n- likes tea");
+        assert!(blocks[1].get("cache_control").is_none());
+    }
 }
@@ -29,6 +29,13 @@ impl StaticZone {
         Ok(Self { system_prompt })
     }
 
+    /// Creates a static zone holding `system_prompt` as-is.
+    pub fn from_prompt(system_prompt: impl Into<String>) -> Self {
+        Self {
+            system_prompt: system_prompt.into(),
+        }
+    }
+
     /// Returns the system prompt as a JSON array of structured blocks
     /// with `cache_control: {"type": "ephemeral"}` on the last block.
     ///
@@ -169,6 +176,12 @@ mod tests {
         assert_eq!(arr[0]["cache_control"]["type"], "ephemeral");
     }
 
+    #[test]
+    fn from_prompt_uses_given_text() {
+        let zone = StaticZone::from_prompt("Be brief.");
+        assert_eq!(zone.system_blocks()[0]["text"], "Be brief.");
+    }
+
     #[tokio::test]
     async fn static_zone_token_count() {
         use blufio_core::token_counter::{TokenizerCache, TokenizerMode};
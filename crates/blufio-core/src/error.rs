@@ -7,7 +7,8 @@
 //! - [`BlufioError`] -- the primary error enum used across all Blufio crates
 //! - Sub-enums for each subsystem (Provider, Channel, Storage, Skill, Mcp, Migration)
 //! - [`ErrorContext`] -- optional metadata carried by structured error variants
-//! - Classification methods: `is_retryable()`, `severity()`, `category()`,
+//! - [`ErrorCode`] -- stable per-error codes for programmatic handling
+//! - Classification methods: `code()`, `is_retryable()`, `severity()`, `category()`,
 //!   `failure_mode()`, `trips_circuit_breaker()`, `suggested_backoff()`, `user_message()`
 //! - Constructor helpers for common error creation patterns
 //! - [`error_log!`] macro for structured logging with classification fields
@@ -142,6 +143,8 @@ pub enum StorageErrorKind {
     SchemaError,
     DiskFull,
     ConnectionFailed,
+    /// The backend does not implement the requested operation.
+    Unsupported,
 }
 
 /// Specific kind of MCP error.
@@ -184,6 +187,67 @@ pub enum AuditErrorKind {
     VerifyFailed,
 }
 
+// ---------------------------------------------------------------------------
+// ErrorCode
+// ---------------------------------------------------------------------------
+
+/// Stable code identifying a specific error, returned by [`BlufioError::code`].
+///
+/// Callers match on this instead of the error text, which is free to change.
+/// There is one code per simple variant and per kind of structured variant.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Display,
+)]
+#[non_exhaustive]
+pub enum ErrorCode {
+    ProviderRateLimited,
+    ProviderAuthFailed,
+    ProviderServerError,
+    ProviderTimeout,
+    ProviderModelNotFound,
+    ChannelDeliveryFailed,
+    /// The channel's connection was lost or its inbound stream ended.
+    ChannelClosed,
+    ChannelRateLimited,
+    ChannelMessageTooLarge,
+    ChannelUnsupportedContent,
+    StorageBusy,
+    StorageCorruption,
+    StorageSchemaError,
+    StorageDiskFull,
+    StorageConnectionFailed,
+    StorageUnsupported,
+    SkillExecutionFailed,
+    SkillCapabilityDenied,
+    SkillSandboxTimeout,
+    SkillCompilationFailed,
+    McpConnectionFailed,
+    McpToolExecutionFailed,
+    McpTimeout,
+    McpAuthFailed,
+    McpProtocolError,
+    MigrationSchemaFailed,
+    MigrationDataCorruption,
+    MigrationVersionMismatch,
+    AuditDbUnavailable,
+    AuditChainBroken,
+    AuditFlushFailed,
+    AuditVerifyFailed,
+    Config,
+    Vault,
+    Security,
+    Signature,
+    BudgetExhausted,
+    HealthCheckFailed,
+    Timeout,
+    Internal,
+    Update,
+    AdapterNotFound,
+    CircuitOpen,
+    Classification,
+    Gdpr,
+}
+
 // ---------------------------------------------------------------------------
 // ErrorContext
 // ---------------------------------------------------------------------------
@@ -282,7 +346,7 @@ pub enum BlufioError {
     #[error("signature error: {0}")]
     Signature(String),
 
-    /// Budget cap has been reached (daily or monthly).
+    /// Budget cap has been reached (hourly, daily or monthly).
     #[error("budget exhausted: {message}")]
     BudgetExhausted { message: String },
 
@@ -353,6 +417,7 @@ impl BlufioError {
                 StorageErrorKind::SchemaError => FailureMode::Internal,
                 StorageErrorKind::DiskFull => FailureMode::ResourceExhausted,
                 StorageErrorKind::ConnectionFailed => FailureMode::Network,
+                StorageErrorKind::Unsupported => FailureMode::Validation,
             },
             Self::Skill { kind, .. } => match kind {
                 SkillErrorKind::ExecutionFailed => FailureMode::Internal,
@@ -421,6 +486,10 @@ impl BlufioError {
                 kind: ChannelErrorKind::MessageTooLarge | ChannelErrorKind::UnsupportedContent,
                 ..
             } => Severity::Warning,
+            Self::Storage {
+                kind: StorageErrorKind::Unsupported,
+                ..
+            } => Severity::Warning,
             Self::Migration {
                 kind: MigrationErrorKind::VersionMismatch,
                 ..
@@ -465,6 +534,71 @@ impl BlufioError {
         }
     }
 
+    /// Returns the stable [`ErrorCode`] for this error.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::Provider { kind, .. } => match kind {
+                ProviderErrorKind::RateLimited => ErrorCode::ProviderRateLimited,
+                ProviderErrorKind::AuthFailed => ErrorCode::ProviderAuthFailed,
+                ProviderErrorKind::ServerError => ErrorCode::ProviderServerError,
+                ProviderErrorKind::Timeout => ErrorCode::ProviderTimeout,
+                ProviderErrorKind::ModelNotFound => ErrorCode::ProviderModelNotFound,
+            },
+            Self::Channel { kind, .. } => match kind {
+                ChannelErrorKind::DeliveryFailed => ErrorCode::ChannelDeliveryFailed,
+                ChannelErrorKind::ConnectionLost => ErrorCode::ChannelClosed,
+                ChannelErrorKind::RateLimited => ErrorCode::ChannelRateLimited,
+                ChannelErrorKind::MessageTooLarge => ErrorCode::ChannelMessageTooLarge,
+                ChannelErrorKind::UnsupportedContent => ErrorCode::ChannelUnsupportedContent,
+            },
+            Self::Storage { kind, .. } => match kind {
+                StorageErrorKind::Busy => ErrorCode::StorageBusy,
+                StorageErrorKind::Corruption => ErrorCode::StorageCorruption,
+                StorageErrorKind::SchemaError => ErrorCode::StorageSchemaError,
+                StorageErrorKind::DiskFull => ErrorCode::StorageDiskFull,
+                StorageErrorKind::ConnectionFailed => ErrorCode::StorageConnectionFailed,
+                StorageErrorKind::Unsupported => ErrorCode::StorageUnsupported,
+            },
+            Self::Skill { kind, .. } => match kind {
+                SkillErrorKind::ExecutionFailed => ErrorCode::SkillExecutionFailed,
+                SkillErrorKind::CapabilityDenied => ErrorCode::SkillCapabilityDenied,
+                SkillErrorKind::SandboxTimeout => ErrorCode::SkillSandboxTimeout,
+                SkillErrorKind::CompilationFailed => ErrorCode::SkillCompilationFailed,
+            },
+            Self::Mcp { kind, .. } => match kind {
+                McpErrorKind::ConnectionFailed => ErrorCode::McpConnectionFailed,
+                McpErrorKind::ToolExecutionFailed => ErrorCode::McpToolExecutionFailed,
+                McpErrorKind::Timeout => ErrorCode::McpTimeout,
+                McpErrorKind::AuthFailed => ErrorCode::McpAuthFailed,
+                McpErrorKind::ProtocolError => ErrorCode::McpProtocolError,
+            },
+            Self::Migration { kind, .. } => match kind {
+                MigrationErrorKind::SchemaFailed => ErrorCode::MigrationSchemaFailed,
+                MigrationErrorKind::DataCorruption => ErrorCode::MigrationDataCorruption,
+                MigrationErrorKind::VersionMismatch => ErrorCode::MigrationVersionMismatch,
+            },
+            Self::Audit { kind, .. } => match kind {
+                AuditErrorKind::DbUnavailable => ErrorCode::AuditDbUnavailable,
+                AuditErrorKind::ChainBroken => ErrorCode::AuditChainBroken,
+                AuditErrorKind::FlushFailed => ErrorCode::AuditFlushFailed,
+                AuditErrorKind::VerifyFailed => ErrorCode::AuditVerifyFailed,
+            },
+            Self::Config(_) => ErrorCode::Config,
+            Self::Vault(_) => ErrorCode::Vault,
+            Self::Security(_) => ErrorCode::Security,
+            Self::Signature(_) => ErrorCode::Signature,
+            Self::BudgetExhausted { .. } => ErrorCode::BudgetExhausted,
+            Self::HealthCheckFailed { .. } => ErrorCode::HealthCheckFailed,
+            Self::Timeout { .. } => ErrorCode::Timeout,
+            Self::Internal(_) => ErrorCode::Internal,
+            Self::Update(_) => ErrorCode::Update,
+            Self::AdapterNotFound { .. } => ErrorCode::AdapterNotFound,
+            Self::CircuitOpen { .. } => ErrorCode::CircuitOpen,
+            Self::Classification(_) => ErrorCode::Classification,
+            Self::Gdpr(_) => ErrorCode::Gdpr,
+        }
+    }
+
     /// Whether this error should trip a circuit breaker.
     ///
     /// True for server-side failure modes: Network, RateLimit, Timeout, Unavailable.
@@ -556,6 +690,9 @@ impl BlufioError {
                 StorageErrorKind::ConnectionFailed => {
                     Cow::Borrowed("Failed to connect to the database.")
                 }
+                StorageErrorKind::Unsupported => {
+                    Cow::Borrowed("This operation is not supported by the storage backend.")
+                }
             },
             Self::Skill { kind, .. } => match kind {
                 SkillErrorKind::ExecutionFailed => Cow::Borrowed("A skill failed to execute."),
@@ -838,6 +975,18 @@ impl BlufioError {
         }
     }
 
+    /// Create a storage error for an operation the backend does not implement.
+    pub fn storage_unsupported(operation: &str) -> Self {
+        Self::Storage {
+            kind: StorageErrorKind::Unsupported,
+            context: ErrorContext::default(),
+            source: Box::new(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!("{operation} is not supported by this storage backend"),
+            )),
+        }
+    }
+
     // --- MCP constructors ---
 
     /// Create an MCP connection failure.
@@ -1102,6 +1251,205 @@ macro_rules! error_log {
 mod tests {
     use super::*;
 
+    // -- ErrorCode tests --
+
+    #[test]
+    fn code_covers_every_variant() {
+        let storage = |kind| BlufioError::Storage {
+            kind,
+            context: ErrorContext::default(),
+            source: Box::new(std::io::Error::other("storage")),
+        };
+        let mcp = |kind| BlufioError::Mcp {
+            kind,
+            context: ErrorContext::default(),
+            source: None,
+        };
+        let cases = [
+            (
+                BlufioError::provider_rate_limited(None, "anthropic"),
+                ErrorCode::ProviderRateLimited,
+            ),
+            (
+                BlufioError::provider_auth_failed("anthropic"),
+                ErrorCode::ProviderAuthFailed,
+            ),
+            (
+                BlufioError::provider_server_error("anthropic", std::io::Error::other("500")),
+                ErrorCode::ProviderServerError,
+            ),
+            (
+                BlufioError::provider_timeout("anthropic"),
+                ErrorCode::ProviderTimeout,
+            ),
+            (
+                BlufioError::provider_model_not_found("gpt-9", "openai"),
+                ErrorCode::ProviderModelNotFound,
+            ),
+            (
+                BlufioError::channel_delivery_failed("telegram", std::io::Error::other("send")),
+                ErrorCode::ChannelDeliveryFailed,
+            ),
+            (
+                BlufioError::channel_connection_lost("telegram"),
+                ErrorCode::ChannelClosed,
+            ),
+            (
+                BlufioError::channel_rate_limited("telegram", None),
+                ErrorCode::ChannelRateLimited,
+            ),
+            (
+                BlufioError::channel_message_too_large("telegram"),
+                ErrorCode::ChannelMessageTooLarge,
+            ),
+            (
+                BlufioError::channel_unsupported_content("telegram"),
+                ErrorCode::ChannelUnsupportedContent,
+            ),
+            (storage(StorageErrorKind::Busy), ErrorCode::StorageBusy),
+            (
+                storage(StorageErrorKind::Corruption),
+                ErrorCode::StorageCorruption,
+            ),
+            (
+                storage(StorageErrorKind::SchemaError),
+                ErrorCode::StorageSchemaError,
+            ),
+            (
+                storage(StorageErrorKind::DiskFull),
+                ErrorCode::StorageDiskFull,
+            ),
+            (
+                storage(StorageErrorKind::ConnectionFailed),
+                ErrorCode::StorageConnectionFailed,
+            ),
+            (
+                BlufioError::storage_unsupported("vacuum"),
+                ErrorCode::StorageUnsupported,
+            ),
+            (
+                BlufioError::skill_execution_msg("boom"),
+                ErrorCode::SkillExecutionFailed,
+            ),
+            (
+                BlufioError::skill_capability_denied("network"),
+                ErrorCode::SkillCapabilityDenied,
+            ),
+            (
+                BlufioError::skill_sandbox_timeout("5s"),
+                ErrorCode::SkillSandboxTimeout,
+            ),
+            (
+                BlufioError::skill_compilation_msg("bad wasm"),
+                ErrorCode::SkillCompilationFailed,
+            ),
+            (
+                mcp(McpErrorKind::ConnectionFailed),
+                ErrorCode::McpConnectionFailed,
+            ),
+            (
+                mcp(McpErrorKind::ToolExecutionFailed),
+                ErrorCode::McpToolExecutionFailed,
+            ),
+            (mcp(McpErrorKind::Timeout), ErrorCode::McpTimeout),
+            (mcp(McpErrorKind::AuthFailed), ErrorCode::McpAuthFailed),
+            (
+                mcp(McpErrorKind::ProtocolError),
+                ErrorCode::McpProtocolError,
+            ),
+            (
+                BlufioError::migration_schema_failed("v9"),
+                ErrorCode::MigrationSchemaFailed,
+            ),
+            (
+                BlufioError::migration_data_corruption("row 3"),
+                ErrorCode::MigrationDataCorruption,
+            ),
+            (
+                BlufioError::migration_version_mismatch("v1"),
+                ErrorCode::MigrationVersionMismatch,
+            ),
+            (
+                BlufioError::audit_db_unavailable("locked"),
+                ErrorCode::AuditDbUnavailable,
+            ),
+            (
+                BlufioError::audit_chain_broken("entry 7"),
+                ErrorCode::AuditChainBroken,
+            ),
+            (
+                BlufioError::audit_flush_failed("disk"),
+                ErrorCode::AuditFlushFailed,
+            ),
+            (
+                BlufioError::audit_verify_failed("hash"),
+                ErrorCode::AuditVerifyFailed,
+            ),
+            (BlufioError::Config("bad".into()), ErrorCode::Config),
+            (BlufioError::Vault("locked".into()), ErrorCode::Vault),
+            (BlufioError::Security("denied".into()), ErrorCode::Security),
+            (BlufioError::Signature("bad".into()), ErrorCode::Signature),
+            (
+                BlufioError::BudgetExhausted {
+                    message: "daily".into(),
+                },
+                ErrorCode::BudgetExhausted,
+            ),
+            (
+                BlufioError::HealthCheckFailed {
+                    name: "db".into(),
+                    source: Box::new(std::io::Error::other("unreachable")),
+                },
+                ErrorCode::HealthCheckFailed,
+            ),
+            (
+                BlufioError::Timeout {
+                    duration: Duration::from_secs(1),
+                },
+                ErrorCode::Timeout,
+            ),
+            (BlufioError::Internal("bug".into()), ErrorCode::Internal),
+            (BlufioError::Update("offline".into()), ErrorCode::Update),
+            (
+                BlufioError::AdapterNotFound {
+                    adapter_type: "channel".into(),
+                    name: "fax".into(),
+                },
+                ErrorCode::AdapterNotFound,
+            ),
+            (
+                BlufioError::CircuitOpen {
+                    dependency: "anthropic".into(),
+                },
+                ErrorCode::CircuitOpen,
+            ),
+            (
+                BlufioError::Classification(
+                    crate::classification::ClassificationError::InvalidLevel("bad".into()),
+                ),
+                ErrorCode::Classification,
+            ),
+            (BlufioError::Gdpr("export".into()), ErrorCode::Gdpr),
+        ];
+        for (err, code) in cases {
+            assert_eq!(err.code(), code, "wrong code for {err}");
+        }
+    }
+
+    #[test]
+    fn channel_closed_code_does_not_depend_on_message() {
+        let err = BlufioError::Channel {
+            kind: ChannelErrorKind::ConnectionLost,
+            context: ErrorContext {
+                channel_name: Some("mux".into()),
+                ..ErrorContext::default()
+            },
+            source: None,
+        };
+        assert!(!err.to_string().contains("closed"));
+        assert_eq!(err.code(), ErrorCode::ChannelClosed);
+    }
+
     // -- FailureMode tests --
 
     #[test]
@@ -1287,6 +1635,15 @@ mod tests {
         assert_eq!(err.failure_mode(), FailureMode::Network);
     }
 
+    #[test]
+    fn storage_unsupported_classification() {
+        let err = BlufioError::storage_unsupported("search_messages");
+        assert!(!err.is_retryable());
+        assert_eq!(err.failure_mode(), FailureMode::Validation);
+        assert_eq!(err.category(), ErrorCategory::Storage);
+        assert!(err.to_string().contains("search_messages"));
+    }
+
     // -- Skill classification --
 
     #[test]
@@ -1810,6 +2167,7 @@ mod proptest_tests {
             Just(StorageErrorKind::SchemaError),
             Just(StorageErrorKind::DiskFull),
             Just(StorageErrorKind::ConnectionFailed),
+            Just(StorageErrorKind::Unsupported),
         ]
     }
 
@@ -11,6 +11,7 @@
 pub mod classification;
 pub mod error;
 pub mod format;
+pub mod net;
 pub mod streaming;
 pub mod token_counter;
 pub mod traits;
@@ -21,9 +22,9 @@ pub use classification::{Classifiable, ClassificationError, DataClassification};
 
 // Re-export key items at crate root for ergonomic imports.
 pub use error::{
-    BlufioError, ChannelErrorKind, ErrorCategory, ErrorContext, FailureMode, McpErrorKind,
-    MigrationErrorKind, ProviderErrorKind, Severity, SkillErrorKind, StorageErrorKind,
-    http_status_to_provider_error,
+    BlufioError, ChannelErrorKind, ErrorCategory, ErrorCode, ErrorContext, FailureMode,
+    McpErrorKind, MigrationErrorKind, ProviderErrorKind, Severity, SkillErrorKind,
+    StorageErrorKind, http_status_to_provider_error,
 };
 pub use format::{
     ColumnAlign, FormatPipeline, FormattedOutput, List, ListStyle, RichContent, Table,
@@ -31,10 +32,11 @@ pub use format::{
 pub use streaming::{StreamingBuffer, StreamingEditorOps, split_at_paragraph_boundary};
 pub use types::{
     AdapterType, ChannelCapabilities, ContentBlock, FormattingSupport, HealthStatus, ImageRequest,
-    ImageResponse, InboundMessage, Message, MessageContent, MessageId, OutboundMessage,
-    ProviderMessage, ProviderRequest, ProviderResponse, ProviderStreamChunk, QueueEntry, RateLimit,
-    Session, SessionId, StreamEventType, StreamingType, TokenUsage, ToolDefinition,
-    TranscriptionRequest, TranscriptionResponse, TtsRequest, TtsResponse,
+    ImageResponse, InboundMessage, Message, MessageContent, MessageId, ModerationVerdict,
+    OutboundMessage, ProviderMessage, ProviderRequest, ProviderResponse, ProviderStreamChunk,
+    PurgeReport, QueueEntry, QueueRecovery, RateLimit, Session, SessionId, StreamEventType,
+    StreamingType, TokenUsage, ToolDefinition, ToolLoopState, TranscriptionRequest,
+    TranscriptionResponse, TtsRequest, TtsResponse,
 };
 
 // Re-export token counting abstractions.
@@ -45,9 +47,9 @@ pub use token_counter::{
 
 // Re-export all adapter traits at crate root.
 pub use traits::{
-    AuthAdapter, ChannelAdapter, EmbeddingAdapter, ImageAdapter, ModelInfo, ObservabilityAdapter,
-    PluginAdapter, ProviderAdapter, ProviderRegistry, SkillRuntimeAdapter, StorageAdapter,
-    TranscriptionAdapter, TtsAdapter,
+    AuthAdapter, ChannelAdapter, EmbeddingAdapter, ImageAdapter, MessageCursor, ModelInfo,
+    ModerationAdapter, ObservabilityAdapter, PluginAdapter, ProviderAdapter, ProviderRegistry,
+    SkillRuntimeAdapter, StorageAdapter, TranscriptionAdapter, TtsAdapter,
 };
 
 #[cfg(test)]
@@ -117,7 +119,7 @@ mod tests {
     }
 
     #[test]
-    fn adapter_type_has_ten_variants() {
+    fn adapter_type_has_eleven_variants() {
         use std::str::FromStr;
 
         let variants = [
@@ -131,12 +133,13 @@ mod tests {
             AdapterType::Tts,
             AdapterType::Transcription,
             AdapterType::ImageGen,
+            AdapterType::Moderation,
         ];
 
         assert_eq!(
             variants.len(),
-            10,
-            "AdapterType must have exactly 10 variants"
+            11,
+            "AdapterType must have exactly 11 variants"
         );
 
         // Verify Display and FromStr round-trip for all variants.
@@ -181,7 +184,7 @@ mod tests {
 
     #[test]
     fn all_trait_modules_are_exported() {
-        // This test verifies that all 10 adapter trait modules compile
+        // This test verifies that all 11 adapter trait modules compile
         // and are accessible through the public API. If any module is
         // missing or has a compile error, this test won't compile.
         fn _assert_plugin_adapter<T: PluginAdapter>() {}
@@ -195,6 +198,7 @@ mod tests {
         fn _assert_tts_adapter<T: TtsAdapter>() {}
         fn _assert_transcription_adapter<T: TranscriptionAdapter>() {}
         fn _assert_image_adapter<T: ImageAdapter>() {}
+        fn _assert_moderation_adapter<T: ModerationAdapter>() {}
     }
 
     #[test]
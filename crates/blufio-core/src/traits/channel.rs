@@ -5,7 +5,7 @@
 
 use async_trait::async_trait;
 
-use crate::error::BlufioError;
+use crate::error::{BlufioError, ChannelErrorKind, ErrorContext};
 use crate::traits::adapter::PluginAdapter;
 use crate::types::{ChannelCapabilities, InboundMessage, MessageId, OutboundMessage};
 
@@ -40,10 +40,41 @@ pub trait ChannelAdapter: PluginAdapter {
         Ok(())
     }
 
+    /// Sends an incremental piece of a response that is still being generated.
+    ///
+    /// `msg.content` holds only the new delta text, not the accumulated
+    /// response; the complete response still arrives via [`send`](Self::send).
+    /// Default implementation is a no-op for channels that don't stream deltas.
+    async fn send_partial(&self, _msg: OutboundMessage) -> Result<(), BlufioError> {
+        Ok(())
+    }
+
     /// Sends a typing indicator to the channel.
     ///
     /// Default implementation is a no-op for channels that don't support typing indicators.
     async fn send_typing(&self, _chat_id: &str) -> Result<(), BlufioError> {
         Ok(())
     }
+
+    /// Sends an audio clip as a voice message, e.g. a spoken copy of a reply.
+    ///
+    /// `msg` addresses the message exactly as for [`send`](Self::send); its
+    /// `content` is not delivered. `content_type` is the audio MIME type.
+    /// Default implementation fails with `UnsupportedContent` for channels
+    /// that can't send voice.
+    async fn send_voice(
+        &self,
+        msg: OutboundMessage,
+        _audio: Vec<u8>,
+        _content_type: &str,
+    ) -> Result<MessageId, BlufioError> {
+        Err(BlufioError::Channel {
+            kind: ChannelErrorKind::UnsupportedContent,
+            context: ErrorContext {
+                channel_name: Some(msg.channel),
+                ..Default::default()
+            },
+            source: None,
+        })
+    }
 }
@@ -11,6 +11,7 @@ pub mod auth;
 pub mod channel;
 pub mod embedding;
 pub mod image;
+pub mod moderation;
 pub mod observability;
 pub mod provider;
 pub mod provider_registry;
@@ -25,10 +26,11 @@ pub use auth::AuthAdapter;
 pub use channel::ChannelAdapter;
 pub use embedding::EmbeddingAdapter;
 pub use image::ImageAdapter;
+pub use moderation::ModerationAdapter;
 pub use observability::ObservabilityAdapter;
 pub use provider::ProviderAdapter;
 pub use provider_registry::{ModelInfo, ProviderRegistry};
 pub use skill::SkillRuntimeAdapter;
-pub use storage::StorageAdapter;
+pub use storage::{MessageCursor, StorageAdapter};
 pub use transcription::TranscriptionAdapter;
 pub use tts::TtsAdapter;
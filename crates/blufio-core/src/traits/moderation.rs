@@ -0,0 +1,20 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Moderation adapter trait for filtering prohibited content.
+
+use async_trait::async_trait;
+
+use crate::error::BlufioError;
+use crate::traits::adapter::PluginAdapter;
+use crate::types::ModerationVerdict;
+
+/// Adapter for content-moderation backends (keyword lists, classifier APIs, etc.).
+///
+/// The agent checks user input before it reaches the LLM and the final
+/// response before it is sent.
+#[async_trait]
+pub trait ModerationAdapter: PluginAdapter {
+    /// Check a piece of text and return the verdict.
+    async fn check(&self, text: &str) -> Result<ModerationVerdict, BlufioError>;
+}
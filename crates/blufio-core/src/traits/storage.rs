@@ -7,7 +7,9 @@ use async_trait::async_trait;
 
 use crate::error::BlufioError;
 use crate::traits::adapter::PluginAdapter;
-use crate::types::{Message, QueueEntry, Session};
+use crate::types::{
+    Message, PurgeReport, QueueEntry, QueueRecovery, Session, ToolLoopState, ToolUseData,
+};
 
 /// Adapter for storage and persistence backends.
 ///
@@ -33,14 +35,48 @@ pub trait StorageAdapter: PluginAdapter {
     /// List sessions, optionally filtered by state.
     async fn list_sessions(&self, state: Option<&str>) -> Result<Vec<Session>, BlufioError>;
 
+    /// Create a session together with its initial messages, atomically: the
+    /// session and all messages are written or nothing is.
+    ///
+    /// Used when a new session starts from existing history, such as a fork.
+    /// The default implementation writes one by one and is only atomic if the
+    /// backend overrides it.
+    async fn create_session_with_messages(
+        &self,
+        session: &Session,
+        messages: &[Message],
+    ) -> Result<(), BlufioError> {
+        self.create_session(session).await?;
+        self.insert_messages(messages).await
+    }
+
     /// Update a session's state.
     async fn update_session_state(&self, id: &str, state: &str) -> Result<(), BlufioError>;
 
+    /// Replace a session's metadata JSON.
+    async fn update_session_metadata(
+        &self,
+        id: &str,
+        metadata: Option<&str>,
+    ) -> Result<(), BlufioError>;
+
     // --- Message operations ---
 
     /// Insert a new message into a session.
     async fn insert_message(&self, message: &Message) -> Result<(), BlufioError>;
 
+    /// Insert several messages atomically: all are written or none are.
+    ///
+    /// Used for batches that must stay consistent, such as the tool_result
+    /// messages answering one assistant turn. The default implementation
+    /// inserts one by one and is only atomic if the backend overrides it.
+    async fn insert_messages(&self, messages: &[Message]) -> Result<(), BlufioError> {
+        for message in messages {
+            self.insert_message(message).await?;
+        }
+        Ok(())
+    }
+
     /// Get messages for a session in chronological order, with optional limit.
     async fn get_messages(
         &self,
@@ -57,6 +93,55 @@ pub trait StorageAdapter: PluginAdapter {
         message_ids: &[String],
     ) -> Result<usize, BlufioError>;
 
+    /// Get up to `limit` messages of a session that follow `after_id`, in
+    /// the order of [`get_messages`](Self::get_messages).
+    ///
+    /// Starts at the first message when `after_id` is `None`; an unknown
+    /// `after_id` yields no messages. Used by [`MessageCursor`] to walk long
+    /// histories page by page. The default implementation loads the whole
+    /// session and slices it; backends should override it with a keyset query.
+    async fn get_messages_page(
+        &self,
+        session_id: &str,
+        after_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Message>, BlufioError> {
+        let messages = self.get_messages(session_id, None).await?;
+        let start = match after_id {
+            Some(id) => match messages.iter().position(|m| m.id == id) {
+                Some(pos) => pos + 1,
+                None => return Ok(Vec::new()),
+            },
+            None => 0,
+        };
+        Ok(messages.into_iter().skip(start).take(limit).collect())
+    }
+
+    /// Full-text search over message content, best matches first.
+    ///
+    /// `query` uses the backend's search syntax (FTS5 for SQLite). Searches
+    /// every session when `session_id` is `None`. Default implementation
+    /// fails with `Unsupported` for backends without a text index.
+    async fn search_messages(
+        &self,
+        _session_id: Option<&str>,
+        _query: &str,
+        _limit: usize,
+    ) -> Result<Vec<Message>, BlufioError> {
+        Err(BlufioError::storage_unsupported("search_messages"))
+    }
+
+    /// Apply the retention policy as of `now` (an ISO 8601 timestamp).
+    ///
+    /// Messages and sessions older than their retention period are
+    /// soft-deleted by setting `deleted_at`; soft-deleted rows older than the
+    /// grace period are permanently removed. Until then a soft delete can be
+    /// undone by clearing `deleted_at`. Default implementation fails with
+    /// `Unsupported` for backends without retention support.
+    async fn purge_expired(&self, _now: &str) -> Result<PurgeReport, BlufioError> {
+        Err(BlufioError::storage_unsupported("purge_expired"))
+    }
+
     // --- Queue operations ---
 
     /// Enqueue a new item. Returns the auto-generated queue entry ID.
@@ -71,6 +156,31 @@ pub trait StorageAdapter: PluginAdapter {
     /// Mark a queue entry as failed (increments attempts, may retry or mark permanently failed).
     async fn fail(&self, id: i64) -> Result<(), BlufioError>;
 
+    /// Reset queue entries left mid-processing by a crash.
+    ///
+    /// Called once at `blufio serve` startup, before anything dequeues.
+    /// Default implementation recovers nothing, for backends whose consumers
+    /// reclaim abandoned entries themselves.
+    async fn recover_in_flight_queue(&self) -> Result<QueueRecovery, BlufioError> {
+        Ok(QueueRecovery::default())
+    }
+
+    // --- Tool-loop state operations ---
+
+    /// Record the tool iteration about to execute for a session, replacing any earlier record.
+    async fn save_tool_loop_state(
+        &self,
+        session_id: &str,
+        iteration: u32,
+        pending_tool_uses: &[ToolUseData],
+    ) -> Result<(), BlufioError>;
+
+    /// Remove the tool-loop record for a session, if any.
+    async fn clear_tool_loop_state(&self, session_id: &str) -> Result<(), BlufioError>;
+
+    /// List tool-loop records left behind by interrupted turns.
+    async fn list_tool_loop_states(&self) -> Result<Vec<ToolLoopState>, BlufioError>;
+
     // --- Classification operations ---
 
     /// Get classification level for an entity.
@@ -118,3 +228,62 @@ pub trait StorageAdapter: PluginAdapter {
         dry_run: bool,
     ) -> Result<(usize, usize, usize, Vec<String>), BlufioError>;
 }
+
+/// Streams a session's messages in pages instead of loading them all.
+///
+/// ```ignore
+/// let mut cursor = MessageCursor::new(storage, "session-id", 500);
+/// while let Some(message) = cursor.next().await? {
+///     // ...
+/// }
+/// ```
+pub struct MessageCursor<'a> {
+    storage: &'a dyn StorageAdapter,
+    session_id: String,
+    page_size: usize,
+    page: std::vec::IntoIter<Message>,
+    last_id: Option<String>,
+    exhausted: bool,
+}
+
+impl<'a> MessageCursor<'a> {
+    /// Creates a cursor over `session_id` fetching `page_size` messages at a time.
+    pub fn new(storage: &'a dyn StorageAdapter, session_id: &str, page_size: usize) -> Self {
+        Self {
+            storage,
+            session_id: session_id.to_string(),
+            page_size: page_size.max(1),
+            page: Vec::new().into_iter(),
+            last_id: None,
+            exhausted: false,
+        }
+    }
+
+    /// Returns the next message, fetching another page when needed.
+    pub async fn next(&mut self) -> Result<Option<Message>, BlufioError> {
+        if let Some(message) = self.page.next() {
+            self.last_id = Some(message.id.clone());
+            return Ok(Some(message));
+        }
+        if self.exhausted {
+            return Ok(None);
+        }
+
+        let page = self
+            .storage
+            .get_messages_page(&self.session_id, self.last_id.as_deref(), self.page_size)
+            .await?;
+        self.exhausted = page.len() < self.page_size;
+        self.page = page.into_iter();
+        match self.page.next() {
+            Some(message) => {
+                self.last_id = Some(message.id.clone());
+                Ok(Some(message))
+            }
+            None => {
+                self.exhausted = true;
+                Ok(None)
+            }
+        }
+    }
+}
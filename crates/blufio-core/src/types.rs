@@ -40,6 +40,7 @@ pub enum AdapterType {
     Tts,
     Transcription,
     ImageGen,
+    Moderation,
 }
 
 // --- Channel types ---
@@ -88,7 +89,7 @@ pub struct InboundMessage {
 }
 
 /// An outbound message to be sent via a channel adapter.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OutboundMessage {
     /// Session this message belongs to.
     pub session_id: Option<String>,
@@ -102,6 +103,11 @@ pub struct OutboundMessage {
     pub parse_mode: Option<String>,
     /// Optional JSON metadata blob.
     pub metadata: Option<String>,
+    /// Optional key identifying this delivery across retries.
+    ///
+    /// Channels that support it skip re-sending a message whose key was
+    /// delivered recently and return the original message ID instead.
+    pub idempotency_key: Option<String>,
 }
 
 /// How a channel supports streaming message updates.
@@ -262,6 +268,69 @@ pub struct ProviderRequest {
     pub tools: Option<Vec<ToolDefinition>>,
 }
 
+impl ProviderRequest {
+    /// Checks that `system_blocks`, if set, is well-formed.
+    ///
+    /// Code that sets `system_blocks` should call this so a malformed value is
+    /// rejected with a clear error, rather than silently dropped by the
+    /// provider when the request is built. See [`validate_system_blocks`].
+    pub fn validate_system_blocks(&self) -> Result<(), crate::error::BlufioError> {
+        match &self.system_blocks {
+            Some(blocks) => validate_system_blocks(blocks),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Validates a structured system prompt value.
+///
+/// The value must be an array of objects, each with string `type` and `text`
+/// fields and an optional `cache_control` object with a string `type`.
+pub fn validate_system_blocks(blocks: &serde_json::Value) -> Result<(), crate::error::BlufioError> {
+    let invalid = |reason: String| {
+        crate::error::BlufioError::Config(format!("invalid system_blocks: {reason}"))
+    };
+
+    let blocks = blocks
+        .as_array()
+        .ok_or_else(|| invalid(format!("expected an array, got {}", json_kind(blocks))))?;
+
+    for (i, block) in blocks.iter().enumerate() {
+        let obj = block
+            .as_object()
+            .ok_or_else(|| invalid(format!("block {i} is {}, not an object", json_kind(block))))?;
+        for field in ["type", "text"] {
+            if !obj.get(field).is_some_and(serde_json::Value::is_string) {
+                return Err(invalid(format!(
+                    "block {i} is missing string field `{field}`"
+                )));
+            }
+        }
+        if let Some(cache_control) = obj.get("cache_control")
+            && !cache_control
+                .get("type")
+                .is_some_and(serde_json::Value::is_string)
+        {
+            return Err(invalid(format!(
+                "block {i} has `cache_control` without a string `type`"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Short name of a JSON value's kind, for error messages.
+fn json_kind(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "a boolean",
+        serde_json::Value::Number(_) => "a number",
+        serde_json::Value::String(_) => "a string",
+        serde_json::Value::Array(_) => "an array",
+        serde_json::Value::Object(_) => "an object",
+    }
+}
+
 /// Token usage statistics from a provider response.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TokenUsage {
@@ -417,12 +486,43 @@ pub struct SkillManifest {
     /// WASM binary filename (relative to skill directory).
     #[serde(default = "default_wasm_entry")]
     pub wasm_entry: String,
+    /// Skill type, naming the runtime that executes it (e.g. "wasm").
+    #[serde(default = "default_skill_runtime")]
+    pub runtime: String,
+    /// JSON Schema for the skill's input, advertised as the tool's
+    /// parameters. Defaults to any JSON object when unset.
+    #[serde(default)]
+    pub input_schema: Option<serde_json::Value>,
+    /// JSON Schema the skill's output must match. When set, the output is
+    /// parsed as JSON and validated before it is returned.
+    #[serde(default)]
+    pub output_schema: Option<serde_json::Value>,
+    /// Publisher signature over the WASM bytes, if the skill is signed.
+    #[serde(default)]
+    pub signature: Option<SkillSignature>,
+    /// Link WASI preview1 so core modules built for `wasm32-wasip1` run
+    /// unchanged. Filesystem access is limited to the declared capability paths.
+    #[serde(default)]
+    pub wasi: bool,
+}
+
+/// An Ed25519 signature over a skill's WASM bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillSignature {
+    /// Hex-encoded Ed25519 public key of the publisher.
+    pub publisher_id: String,
+    /// Hex-encoded Ed25519 signature.
+    pub signature: String,
 }
 
 fn default_wasm_entry() -> String {
     "skill.wasm".to_string()
 }
 
+fn default_skill_runtime() -> String {
+    "wasm".to_string()
+}
+
 /// Capabilities a skill declares it needs.
 ///
 /// Each capability must be explicitly declared in the skill manifest.
@@ -626,6 +726,54 @@ pub struct QueueEntry {
     pub locked_until: Option<String>,
 }
 
+/// In-flight tool-loop state for a session, persisted for crash recovery.
+///
+/// Written before a tool iteration executes and cleared when the turn
+/// completes. A record still present at startup means the process stopped
+/// mid-loop and the session may have `tool_use` calls without results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolLoopState {
+    /// Session the tool loop belongs to.
+    pub session_id: String,
+    /// Zero-based tool-loop iteration that was executing.
+    pub iteration: u32,
+    /// Tool calls requested by the model in that iteration.
+    pub pending_tool_uses: Vec<ToolUseData>,
+    /// ISO 8601 creation timestamp.
+    pub created_at: String,
+    /// ISO 8601 last-update timestamp.
+    pub updated_at: String,
+}
+
+/// Row counts from one [`purge_expired`](crate::StorageAdapter::purge_expired) run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PurgeReport {
+    /// Messages past their retention period, newly marked `deleted_at`.
+    pub messages_soft_deleted: u64,
+    /// Sessions past their retention period, newly marked `deleted_at`.
+    pub sessions_soft_deleted: u64,
+    /// Soft-deleted messages past the grace period, permanently removed.
+    pub messages_purged: u64,
+    /// Soft-deleted sessions past the grace period, permanently removed.
+    pub sessions_purged: u64,
+}
+
+impl PurgeReport {
+    /// Whether the run changed nothing.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Outcome of one [`recover_in_flight_queue`](crate::StorageAdapter::recover_in_flight_queue) run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueRecovery {
+    /// Entries reset to pending for another attempt.
+    pub requeued: usize,
+    /// Entries that had used their last attempt and are now failed.
+    pub dead_lettered: usize,
+}
+
 // --- TTS types ---
 
 /// A request to a text-to-speech provider.
@@ -676,6 +824,19 @@ pub struct TranscriptionResponse {
     pub duration_secs: Option<f32>,
 }
 
+// --- Moderation types ---
+
+/// Outcome of a content-moderation check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModerationVerdict {
+    /// Content may pass unchanged.
+    Allow,
+    /// Content must not pass; carries the reason for the log.
+    Block(String),
+    /// Content may pass but should be logged for review.
+    Flag,
+}
+
 // --- Image generation types ---
 
 /// A request to an image generation provider.
@@ -699,3 +860,68 @@ pub struct ImageResponse {
     /// MIME type of the images (e.g., "image/png").
     pub content_type: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request_with_blocks(blocks: serde_json::Value) -> ProviderRequest {
+        ProviderRequest {
+            model: "test-model".into(),
+            system_prompt: None,
+            system_blocks: Some(blocks),
+            messages: vec![],
+            max_tokens: 1024,
+            stream: false,
+            tools: None,
+        }
+    }
+
+    #[test]
+    fn well_formed_system_blocks_pass() {
+        let request = request_with_blocks(json!([
+            {"type": "text", "text": "You are helpful.", "cache_control": {"type": "ephemeral"}},
+            {"type": "text", "text": "Be concise."}
+        ]));
+        assert!(request.validate_system_blocks().is_ok());
+    }
+
+    #[test]
+    fn missing_system_blocks_pass() {
+        let request = ProviderRequest {
+            system_blocks: None,
+            ..request_with_blocks(json!([]))
+        };
+        assert!(request.validate_system_blocks().is_ok());
+    }
+
+    #[test]
+    fn malformed_system_blocks_are_rejected_with_reason() {
+        let cases = [
+            (json!("You are helpful."), "expected an array, got a string"),
+            (json!([42]), "block 0 is a number, not an object"),
+            (
+                json!([{"type": "text"}]),
+                "block 0 is missing string field `text`",
+            ),
+            (
+                json!([{"type": "text", "text": "ok"}, {"text": "no type"}]),
+                "block 1 is missing string field `type`",
+            ),
+            (
+                json!([{"type": "text", "text": "ok", "cache_control": "ephemeral"}]),
+                "block 0 has `cache_control` without a string `type`",
+            ),
+        ];
+        for (blocks, reason) in cases {
+            let err = request_with_blocks(blocks)
+                .validate_system_blocks()
+                .unwrap_err();
+            assert!(
+                matches!(&err, crate::error::BlufioError::Config(msg) if msg.contains(reason)),
+                "expected {reason:?}, got {err}"
+            );
+        }
+    }
+}
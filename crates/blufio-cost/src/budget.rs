@@ -1,22 +1,110 @@
 // SPDX-FileCopyrightText: 2026 Blufio Contributors
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-//! Budget tracking with daily and monthly caps.
+//! Budget tracking with hourly, daily and monthly caps.
 //!
 //! The budget tracker keeps in-memory running totals and enforces spending
-//! caps configured via `CostConfig`. It emits a `tracing::warn` at 80% of
-//! any cap and returns `BlufioError::BudgetExhausted` when a cap is reached.
+//! caps configured via `CostConfig`. The hourly cap applies to a sliding
+//! 60-minute window over recorded costs rather than to calendar hours. It
+//! emits a `tracing::warn` at 80% of any cap and returns
+//! `BlufioError::BudgetExhausted` when a cap is reached.
 //!
 //! On restart, `from_ledger()` re-hydrates totals from the persistent cost
 //! ledger so budget enforcement survives process restarts.
+//!
+//! Crossing a configured percentage of a cap (`alert_thresholds_pct`) sends a
+//! [`BudgetAlert`] to the registered [`BudgetNotifier`], once per threshold
+//! per budget period.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
 
 use blufio_config::model::CostConfig;
 use blufio_core::BlufioError;
-use chrono::{Datelike, Utc};
+use chrono::{DateTime, Datelike, TimeDelta, Utc};
 use tracing::warn;
 
 use crate::ledger::CostLedger;
 
+/// Length of the sliding window the hourly cap applies to.
+const HOURLY_WINDOW: TimeDelta = TimeDelta::hours(1);
+
+/// Which budget cap an alert concerns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum BudgetPeriod {
+    /// The daily cap, reset at midnight UTC.
+    Daily,
+    /// The monthly cap, reset on the first of the month.
+    Monthly,
+}
+
+/// Spend crossed a configured percentage of a budget cap.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetAlert {
+    /// Cap that was crossed.
+    pub period: BudgetPeriod,
+    /// Threshold crossed, as a percentage of the cap.
+    pub threshold_pct: f64,
+    /// Spend in the period so far.
+    pub spent_usd: f64,
+    /// The cap itself.
+    pub cap_usd: f64,
+}
+
+impl std::fmt::Display for BudgetAlert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}% of the {} budget used (${:.2} of ${:.2})",
+            self.threshold_pct, self.period, self.spent_usd, self.cap_usd
+        )
+    }
+}
+
+/// Receives budget threshold alerts, e.g. to forward them to an admin.
+///
+/// Called while the tracker is locked, so implementations should hand the
+/// alert off (spawn a task, send on a channel) rather than block.
+pub trait BudgetNotifier: Send + Sync {
+    /// Handles one threshold crossing.
+    fn notify(&self, alert: BudgetAlert);
+}
+
+/// Alert thresholds of one cap and how many of them have fired this period.
+#[derive(Debug, Clone, Default)]
+struct ThresholdState {
+    /// Fractions of the cap, ascending.
+    thresholds: Vec<f64>,
+    /// Number of leading thresholds already fired this period.
+    fired: usize,
+}
+
+impl ThresholdState {
+    fn new(thresholds_pct: &[f64]) -> Self {
+        let mut thresholds: Vec<f64> = thresholds_pct
+            .iter()
+            .filter(|p| **p > 0.0)
+            .map(|p| p / 100.0)
+            .collect();
+        thresholds.sort_by(f64::total_cmp);
+        thresholds.dedup();
+        Self {
+            thresholds,
+            fired: 0,
+        }
+    }
+
+    /// Marks thresholds at or below `utilization` as fired and returns them.
+    fn cross(&mut self, utilization: f64) -> &[f64] {
+        let start = self.fired;
+        while self.fired < self.thresholds.len() && utilization >= self.thresholds[self.fired] {
+            self.fired += 1;
+        }
+        &self.thresholds[start..self.fired]
+    }
+}
+
 /// In-memory budget tracker with daily and monthly spending caps.
 pub struct BudgetTracker {
     /// Running total of today's spend.
@@ -27,10 +115,21 @@ pub struct BudgetTracker {
     daily_cap: Option<f64>,
     /// Monthly spending cap (None = unlimited).
     monthly_cap: Option<f64>,
+    /// Sliding-hour spending cap (None = unlimited).
+    hourly_cap: Option<f64>,
+    /// Costs recorded within the last hour, oldest first. Only kept when an
+    /// hourly cap is set.
+    hourly_spend: VecDeque<(DateTime<Utc>, f64)>,
     /// Day-of-year for daily reset detection.
     current_day: u32,
     /// Month number for monthly reset detection.
     current_month: u32,
+    /// Daily alert thresholds fired this day.
+    daily_alerts: ThresholdState,
+    /// Monthly alert thresholds fired this month.
+    monthly_alerts: ThresholdState,
+    /// Receiver of threshold alerts (None = alerts are only logged).
+    notifier: Option<Arc<dyn BudgetNotifier>>,
 }
 
 impl BudgetTracker {
@@ -42,8 +141,13 @@ impl BudgetTracker {
             monthly_total_usd: 0.0,
             daily_cap: config.daily_budget_usd,
             monthly_cap: config.monthly_budget_usd,
+            hourly_cap: config.hourly_budget_usd,
+            hourly_spend: VecDeque::new(),
             current_day: now.ordinal(),
             current_month: now.month(),
+            daily_alerts: ThresholdState::new(&config.alert_thresholds_pct),
+            monthly_alerts: ThresholdState::new(&config.alert_thresholds_pct),
+            notifier: None,
         }
     }
 
@@ -62,24 +166,71 @@ impl BudgetTracker {
         let daily_total = ledger.daily_total(&today).await?;
         let monthly_total = ledger.monthly_total(&year_month).await?;
 
-        Ok(Self {
+        let mut tracker = Self {
             daily_total_usd: daily_total,
             monthly_total_usd: monthly_total,
-            daily_cap: config.daily_budget_usd,
-            monthly_cap: config.monthly_budget_usd,
-            current_day: now.ordinal(),
-            current_month: now.month(),
-        })
+            ..Self::new(config)
+        };
+        // Thresholds crossed before the restart were already alerted.
+        if let Some(cap) = tracker.daily_cap.filter(|c| *c > 0.0) {
+            tracker.daily_alerts.cross(daily_total / cap);
+        }
+        if let Some(cap) = tracker.monthly_cap.filter(|c| *c > 0.0) {
+            tracker.monthly_alerts.cross(monthly_total / cap);
+        }
+        if tracker.hourly_cap.is_some() {
+            let since = (now - HOURLY_WINDOW)
+                .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+                .to_string();
+            for (created_at, cost) in ledger.costs_since(&since).await? {
+                if let Ok(at) = DateTime::parse_from_rfc3339(&created_at) {
+                    tracker
+                        .hourly_spend
+                        .push_back((at.with_timezone(&Utc), cost));
+                }
+            }
+        }
+        Ok(tracker)
+    }
+
+    /// Registers the receiver of budget threshold alerts.
+    pub fn set_notifier(&mut self, notifier: Arc<dyn BudgetNotifier>) {
+        self.notifier = Some(notifier);
     }
 
     /// Check whether the budget allows another API call.
     ///
-    /// Emits `tracing::warn` at 80% of daily or monthly cap.
+    /// Emits `tracing::warn` at 80% of any cap.
     /// Returns `BlufioError::BudgetExhausted` when a cap is exceeded.
     pub fn check_budget(&mut self) -> Result<(), BlufioError> {
+        self.check_budget_at(Utc::now())
+    }
+
+    fn check_budget_at(&mut self, now: DateTime<Utc>) -> Result<(), BlufioError> {
         self.maybe_reset_daily();
         self.maybe_reset_monthly();
 
+        if let Some(hourly_cap) = self.hourly_cap {
+            self.prune_hourly(now);
+            let hourly_total = self.hourly_spend.iter().map(|(_, c)| c).sum::<f64>();
+            if hourly_total >= hourly_cap {
+                return Err(BlufioError::BudgetExhausted {
+                    message: format!(
+                        "Hourly budget of ${:.2} reached. Resumes in {} min.",
+                        hourly_cap,
+                        self.minutes_until_under_hourly_cap(hourly_cap, hourly_total, now)
+                    ),
+                });
+            }
+            if hourly_total >= hourly_cap * 0.8 {
+                warn!(
+                    hourly_total = hourly_total,
+                    hourly_cap = hourly_cap,
+                    "approaching hourly budget cap (80%+)"
+                );
+            }
+        }
+
         if let Some(daily_cap) = self.daily_cap {
             if self.daily_total_usd >= daily_cap {
                 return Err(BlufioError::BudgetExhausted {
@@ -120,9 +271,82 @@ impl BudgetTracker {
     }
 
     /// Record a cost, incrementing daily and monthly totals.
+    ///
+    /// Sends an alert for each threshold the new totals cross.
     pub fn record_cost(&mut self, cost_usd: f64) {
+        self.record_cost_at(cost_usd, Utc::now());
+    }
+
+    fn record_cost_at(&mut self, cost_usd: f64, at: DateTime<Utc>) {
         self.daily_total_usd += cost_usd;
         self.monthly_total_usd += cost_usd;
+        if self.hourly_cap.is_some() {
+            self.prune_hourly(at);
+            self.hourly_spend.push_back((at, cost_usd));
+        }
+
+        if let Some(cap) = self.daily_cap.filter(|c| *c > 0.0) {
+            for &threshold in self.daily_alerts.cross(self.daily_total_usd / cap) {
+                Self::alert(
+                    self.notifier.as_deref(),
+                    BudgetAlert {
+                        period: BudgetPeriod::Daily,
+                        threshold_pct: threshold * 100.0,
+                        spent_usd: self.daily_total_usd,
+                        cap_usd: cap,
+                    },
+                );
+            }
+        }
+        if let Some(cap) = self.monthly_cap.filter(|c| *c > 0.0) {
+            for &threshold in self.monthly_alerts.cross(self.monthly_total_usd / cap) {
+                Self::alert(
+                    self.notifier.as_deref(),
+                    BudgetAlert {
+                        period: BudgetPeriod::Monthly,
+                        threshold_pct: threshold * 100.0,
+                        spent_usd: self.monthly_total_usd,
+                        cap_usd: cap,
+                    },
+                );
+            }
+        }
+    }
+
+    fn alert(notifier: Option<&dyn BudgetNotifier>, alert: BudgetAlert) {
+        warn!(
+            period = %alert.period,
+            threshold_pct = alert.threshold_pct,
+            spent_usd = alert.spent_usd,
+            cap_usd = alert.cap_usd,
+            "budget alert threshold crossed"
+        );
+        if let Some(notifier) = notifier {
+            notifier.notify(alert);
+        }
+    }
+
+    /// Drop costs that have left the sliding hour ending at `now`.
+    fn prune_hourly(&mut self, now: DateTime<Utc>) {
+        while let Some(&(at, _)) = self.hourly_spend.front() {
+            if at > now - HOURLY_WINDOW {
+                break;
+            }
+            self.hourly_spend.pop_front();
+        }
+    }
+
+    /// Whole minutes until enough of the window expires to drop below `cap`.
+    fn minutes_until_under_hourly_cap(&self, cap: f64, total: f64, now: DateTime<Utc>) -> i64 {
+        let mut remaining = total;
+        for &(at, cost) in &self.hourly_spend {
+            remaining -= cost;
+            if remaining < cap {
+                let wait = (at + HOURLY_WINDOW - now).num_seconds().max(0);
+                return (wait + 59) / 60;
+            }
+        }
+        0
     }
 
     /// Reset daily total if the day has changed.
@@ -130,6 +354,7 @@ impl BudgetTracker {
         let today = Utc::now().ordinal();
         if today != self.current_day {
             self.daily_total_usd = 0.0;
+            self.daily_alerts.fired = 0;
             self.current_day = today;
         }
     }
@@ -139,6 +364,7 @@ impl BudgetTracker {
         let month = Utc::now().month();
         if month != self.current_month {
             self.monthly_total_usd = 0.0;
+            self.monthly_alerts.fired = 0;
             self.current_month = month;
         }
     }
@@ -153,6 +379,17 @@ impl BudgetTracker {
         self.monthly_total_usd
     }
 
+    /// Spend in the last 60 minutes (for testing/reporting). Always 0.0 when
+    /// no hourly cap is configured.
+    pub fn hourly_total(&self) -> f64 {
+        let start = Utc::now() - HOURLY_WINDOW;
+        self.hourly_spend
+            .iter()
+            .filter(|(at, _)| *at > start)
+            .map(|(_, c)| c)
+            .sum()
+    }
+
     /// Returns the remaining daily budget in USD.
     ///
     /// If no daily cap is configured, returns `f64::INFINITY`.
@@ -193,6 +430,7 @@ mod tests {
             daily_budget_usd: daily,
             monthly_budget_usd: monthly,
             track_tokens: true,
+            ..Default::default()
         }
     }
 
@@ -360,4 +598,210 @@ mod tests {
             tracker.monthly_total()
         );
     }
+
+    #[derive(Default)]
+    struct RecordingNotifier(std::sync::Mutex<Vec<BudgetAlert>>);
+
+    impl BudgetNotifier for RecordingNotifier {
+        fn notify(&self, alert: BudgetAlert) {
+            self.0.lock().unwrap().push(alert);
+        }
+    }
+
+    impl RecordingNotifier {
+        fn fired(&self) -> Vec<(BudgetPeriod, f64)> {
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|a| (a.period, a.threshold_pct))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn alerts_fire_once_per_threshold() {
+        let config = config_with_caps(Some(10.0), None);
+        let notifier = Arc::new(RecordingNotifier::default());
+        let mut tracker = BudgetTracker::new(&config);
+        tracker.set_notifier(notifier.clone());
+
+        tracker.record_cost(4.0);
+        assert!(notifier.fired().is_empty());
+
+        tracker.record_cost(1.5); // 55%
+        tracker.record_cost(0.5); // 60%, no new threshold
+        assert_eq!(notifier.fired(), [(BudgetPeriod::Daily, 50.0)]);
+
+        tracker.record_cost(2.5); // 85%
+        tracker.record_cost(1.5); // 100%
+        tracker.record_cost(1.0); // 110%
+        assert_eq!(
+            notifier.fired(),
+            [
+                (BudgetPeriod::Daily, 50.0),
+                (BudgetPeriod::Daily, 80.0),
+                (BudgetPeriod::Daily, 95.0),
+                (BudgetPeriod::Daily, 100.0),
+            ]
+        );
+        let last = notifier.0.lock().unwrap().last().cloned().unwrap();
+        assert!((last.spent_usd - 10.0).abs() < 1e-10);
+        assert_eq!(last.cap_usd, 10.0);
+    }
+
+    #[test]
+    fn alerts_track_daily_and_monthly_separately() {
+        let mut config = config_with_caps(Some(10.0), Some(20.0));
+        config.alert_thresholds_pct = vec![90.0, 50.0];
+        let notifier = Arc::new(RecordingNotifier::default());
+        let mut tracker = BudgetTracker::new(&config);
+        tracker.set_notifier(notifier.clone());
+
+        tracker.record_cost(9.5);
+        assert_eq!(
+            notifier.fired(),
+            [(BudgetPeriod::Daily, 50.0), (BudgetPeriod::Daily, 90.0)]
+        );
+
+        tracker.record_cost(1.0);
+        assert_eq!(
+            notifier.fired().last(),
+            Some(&(BudgetPeriod::Monthly, 50.0))
+        );
+        assert_eq!(notifier.fired().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn from_ledger_does_not_refire_crossed_thresholds() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = CostLedger::open(&dir.path().join("test.db").to_string_lossy())
+            .await
+            .unwrap();
+        let today = Utc::now().format("%Y-%m-%d").to_string();
+        ledger
+            .record(&crate::ledger::CostRecord {
+                id: "r1".to_string(),
+                session_id: "s1".to_string(),
+                model: "claude-sonnet-4-20250514".to_string(),
+                feature_type: crate::ledger::FeatureType::Message,
+                input_tokens: 100,
+                output_tokens: 50,
+                cache_read_tokens: 0,
+                cache_creation_tokens: 0,
+                cost_usd: 6.0,
+                created_at: format!("{today}T12:00:00.000Z"),
+                intended_model: None,
+                server_name: None,
+                fallback: false,
+            })
+            .await
+            .unwrap();
+
+        let config = config_with_caps(Some(10.0), None);
+        let notifier = Arc::new(RecordingNotifier::default());
+        let mut tracker = BudgetTracker::from_ledger(&config, &ledger).await.unwrap();
+        tracker.set_notifier(notifier.clone());
+
+        tracker.record_cost(0.5);
+        assert!(notifier.fired().is_empty());
+        tracker.record_cost(2.0);
+        assert_eq!(notifier.fired(), [(BudgetPeriod::Daily, 80.0)]);
+    }
+
+    fn hourly_config(cap: f64) -> CostConfig {
+        CostConfig {
+            hourly_budget_usd: Some(cap),
+            ..config_with_caps(None, None)
+        }
+    }
+
+    #[test]
+    fn hourly_cap_trips_within_the_hour() {
+        let mut tracker = BudgetTracker::new(&hourly_config(1.0));
+        let t0 = Utc::now();
+        tracker.record_cost_at(0.6, t0);
+        assert!(tracker.check_budget_at(t0 + TimeDelta::minutes(5)).is_ok());
+
+        tracker.record_cost_at(0.5, t0 + TimeDelta::minutes(10));
+        let err = tracker
+            .check_budget_at(t0 + TimeDelta::minutes(20))
+            .unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("Hourly budget"), "got: {msg}");
+        // The first cost leaves the window at t0 + 60 min.
+        assert!(msg.contains("40 min"), "got: {msg}");
+    }
+
+    #[test]
+    fn hourly_cap_recovers_as_window_slides() {
+        let mut tracker = BudgetTracker::new(&hourly_config(1.0));
+        let t0 = Utc::now();
+        tracker.record_cost_at(0.6, t0);
+        tracker.record_cost_at(0.5, t0 + TimeDelta::minutes(30));
+        assert!(
+            tracker
+                .check_budget_at(t0 + TimeDelta::minutes(59))
+                .is_err()
+        );
+
+        // Not a calendar hour: only the first cost has aged out.
+        assert!(tracker.check_budget_at(t0 + TimeDelta::minutes(61)).is_ok());
+        assert_eq!(tracker.hourly_spend.len(), 1);
+
+        tracker.record_cost_at(0.5, t0 + TimeDelta::minutes(62));
+        assert!(
+            tracker
+                .check_budget_at(t0 + TimeDelta::minutes(63))
+                .is_err()
+        );
+        assert!(tracker.check_budget_at(t0 + TimeDelta::minutes(91)).is_ok());
+    }
+
+    #[test]
+    fn no_hourly_cap_keeps_no_window() {
+        let mut tracker = BudgetTracker::new(&config_with_caps(Some(100.0), None));
+        tracker.record_cost(5.0);
+        assert!(tracker.hourly_spend.is_empty());
+        assert_eq!(tracker.hourly_total(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn from_ledger_restores_hourly_window() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = CostLedger::open(&dir.path().join("test.db").to_string_lossy())
+            .await
+            .unwrap();
+        let now = Utc::now();
+        for (id, age_min, cost) in [("old", 90, 5.0), ("recent", 30, 2.0)] {
+            ledger
+                .record(&crate::ledger::CostRecord {
+                    id: id.to_string(),
+                    session_id: "s1".to_string(),
+                    model: "claude-sonnet-4-20250514".to_string(),
+                    feature_type: crate::ledger::FeatureType::Message,
+                    input_tokens: 100,
+                    output_tokens: 50,
+                    cache_read_tokens: 0,
+                    cache_creation_tokens: 0,
+                    cost_usd: cost,
+                    created_at: (now - TimeDelta::minutes(age_min))
+                        .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+                        .to_string(),
+                    intended_model: None,
+                    server_name: None,
+                    fallback: false,
+                })
+                .await
+                .unwrap();
+        }
+
+        let mut tracker = BudgetTracker::from_ledger(&hourly_config(3.0), &ledger)
+            .await
+            .unwrap();
+        assert!((tracker.hourly_total() - 2.0).abs() < 1e-10);
+        assert!(tracker.check_budget().is_ok());
+        tracker.record_cost(1.5);
+        assert!(tracker.check_budget().is_err());
+    }
 }
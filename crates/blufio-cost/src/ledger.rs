@@ -118,6 +118,40 @@ impl CostRecord {
     }
 }
 
+/// Aggregated spend for one model and feature type in a [`CostReport`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostReportLine {
+    /// Model identifier.
+    pub model: String,
+    /// Feature that triggered the calls.
+    pub feature_type: FeatureType,
+    /// Number of recorded calls.
+    pub calls: u64,
+    /// Total input tokens.
+    pub input_tokens: u64,
+    /// Total output tokens.
+    pub output_tokens: u64,
+    /// Total cache-read tokens.
+    pub cache_read_tokens: u64,
+    /// Total cache-creation tokens.
+    pub cache_creation_tokens: u64,
+    /// Total cost in USD.
+    pub cost_usd: f64,
+}
+
+/// Spend over an inclusive date range, grouped by model and feature type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostReport {
+    /// First day covered (e.g. "2026-03-01").
+    pub from: String,
+    /// Last day covered, inclusive.
+    pub to: String,
+    /// One line per (model, feature type), highest cost first.
+    pub lines: Vec<CostReportLine>,
+    /// Sum of `cost_usd` over all lines.
+    pub total_cost_usd: f64,
+}
+
 /// Convert a tokio-rusqlite error into BlufioError::Storage.
 fn map_tr_err(e: tokio_rusqlite::Error<rusqlite::Error>) -> BlufioError {
     BlufioError::storage_connection_failed(e)
@@ -146,6 +180,15 @@ impl CostLedger {
         Ok(Self::new(conn))
     }
 
+    /// Open a read-only cost ledger for reporting.
+    ///
+    /// Uses a read-only connection so reports never contend with a running
+    /// agent's writes. [`record`](Self::record) fails on this handle.
+    pub async fn open_read_only(path: &str) -> Result<Self, BlufioError> {
+        let conn = blufio_storage::open_read_only_connection(path).await?;
+        Ok(Self::new(conn))
+    }
+
     /// Record a cost entry in the ledger.
     pub async fn record(&self, record: &CostRecord) -> Result<(), BlufioError> {
         let id = record.id.clone();
@@ -236,6 +279,28 @@ impl CostLedger {
             .map_err(map_tr_err)
     }
 
+    /// `(created_at, cost_usd)` of every record at or after `since` (an RFC
+    /// 3339 timestamp in the ledger's format), oldest first.
+    pub async fn costs_since(&self, since: &str) -> Result<Vec<(String, f64)>, BlufioError> {
+        let since = since.to_string();
+        self.conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT created_at, cost_usd FROM cost_ledger \
+                     WHERE created_at >= ?1 AND deleted_at IS NULL \
+                     ORDER BY created_at",
+                )?;
+                let rows = stmt
+                    .query_map(rusqlite::params![since], |row| {
+                        Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(rows)
+            })
+            .await
+            .map_err(map_tr_err)
+    }
+
     /// Per-server cost totals for MCP cost attribution (CLNT-12).
     ///
     /// Returns `(server_name, total_cost_usd)` pairs, ordered by cost descending.
@@ -261,6 +326,60 @@ impl CostLedger {
             .map_err(map_tr_err)
     }
 
+    /// Spend between `from` and `to` (ISO 8601 dates, both inclusive), grouped
+    /// by model and feature type.
+    pub async fn report(&self, from: &str, to: &str) -> Result<CostReport, BlufioError> {
+        let (from_date, to_date) = (from.to_string(), to.to_string());
+        let lines = self
+            .conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT model, feature_type, COUNT(*), SUM(input_tokens), \
+                     SUM(output_tokens), SUM(cache_read_tokens), SUM(cache_creation_tokens), \
+                     SUM(cost_usd) \
+                     FROM cost_ledger \
+                     WHERE created_at >= ?1 AND created_at < date(?2, '+1 day') \
+                     AND deleted_at IS NULL \
+                     GROUP BY model, feature_type \
+                     ORDER BY SUM(cost_usd) DESC, model, feature_type",
+                )?;
+                let rows = stmt
+                    .query_map(rusqlite::params![from_date, to_date], |row| {
+                        let feature_type: String = row.get(1)?;
+                        let feature_type = feature_type.parse::<FeatureType>().map_err(|e| {
+                            rusqlite::Error::FromSqlConversionFailure(
+                                1,
+                                rusqlite::types::Type::Text,
+                                Box::new(e),
+                            )
+                        })?;
+                        // Counts and sums are non-negative INTEGERs.
+                        let count = |i: usize| row.get::<_, i64>(i).map(|n| n as u64);
+                        Ok(CostReportLine {
+                            model: row.get(0)?,
+                            feature_type,
+                            calls: count(2)?,
+                            input_tokens: count(3)?,
+                            output_tokens: count(4)?,
+                            cache_read_tokens: count(5)?,
+                            cache_creation_tokens: count(6)?,
+                            cost_usd: row.get(7)?,
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(rows)
+            })
+            .await
+            .map_err(map_tr_err)?;
+
+        Ok(CostReport {
+            from: from.to_string(),
+            to: to.to_string(),
+            total_cost_usd: lines.iter().map(|l| l.cost_usd).sum(),
+            lines,
+        })
+    }
+
     /// Sum of costs for a given session.
     pub async fn session_total(&self, session_id: &str) -> Result<f64, BlufioError> {
         let session_id = session_id.to_string();
@@ -277,6 +396,55 @@ impl CostLedger {
             .await
             .map_err(map_tr_err)
     }
+
+    /// Non-deleted cost records in insertion-time order, optionally scoped to a session.
+    ///
+    /// Intended for reporting and test assertions; `fallback` is not persisted
+    /// and is always `false` on returned records.
+    pub async fn records(&self, session_id: Option<&str>) -> Result<Vec<CostRecord>, BlufioError> {
+        let session_id = session_id.map(str::to_string);
+        self.conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, session_id, model, feature_type, input_tokens, output_tokens, \
+                     cache_read_tokens, cache_creation_tokens, cost_usd, created_at, \
+                     intended_model, server_name \
+                     FROM cost_ledger \
+                     WHERE deleted_at IS NULL AND (?1 IS NULL OR session_id = ?1) \
+                     ORDER BY created_at, rowid",
+                )?;
+                let rows = stmt
+                    .query_map(rusqlite::params![session_id], |row| {
+                        let feature_type: String = row.get(3)?;
+                        let feature_type = feature_type.parse::<FeatureType>().map_err(|e| {
+                            rusqlite::Error::FromSqlConversionFailure(
+                                3,
+                                rusqlite::types::Type::Text,
+                                Box::new(e),
+                            )
+                        })?;
+                        Ok(CostRecord {
+                            id: row.get(0)?,
+                            session_id: row.get(1)?,
+                            model: row.get(2)?,
+                            feature_type,
+                            input_tokens: row.get(4)?,
+                            output_tokens: row.get(5)?,
+                            cache_read_tokens: row.get(6)?,
+                            cache_creation_tokens: row.get(7)?,
+                            cost_usd: row.get(8)?,
+                            created_at: row.get(9)?,
+                            intended_model: row.get(10)?,
+                            server_name: row.get(11)?,
+                            fallback: false,
+                        })
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(rows)
+            })
+            .await
+            .map_err(map_tr_err)
+    }
 }
 
 #[cfg(test)]
@@ -423,6 +591,34 @@ mod tests {
         assert!((total_b - 2.0).abs() < 1e-10);
     }
 
+    #[tokio::test]
+    async fn records_returns_rows_scoped_by_session() {
+        let conn = test_db().await;
+        let ledger = CostLedger::new(conn);
+
+        ledger
+            .record(&sample_record("sess-a", 1.0, "2026-03-01T10:00:00.000Z"))
+            .await
+            .unwrap();
+        let mut compaction = sample_record("sess-a", 0.5, "2026-03-01T10:00:01.000Z");
+        compaction.feature_type = FeatureType::Compaction;
+        ledger.record(&compaction).await.unwrap();
+        ledger
+            .record(&sample_record("sess-b", 2.0, "2026-03-01T10:00:02.000Z"))
+            .await
+            .unwrap();
+
+        let all = ledger.records(None).await.unwrap();
+        assert_eq!(all.len(), 3);
+
+        let sess_a = ledger.records(Some("sess-a")).await.unwrap();
+        assert_eq!(sess_a.len(), 2);
+        assert_eq!(sess_a[0].feature_type, FeatureType::Message);
+        assert_eq!(sess_a[1].feature_type, FeatureType::Compaction);
+        assert_eq!(sess_a[0].input_tokens, 1000);
+        assert_eq!(sess_a[0].output_tokens, 500);
+    }
+
     #[test]
     fn feature_type_display_and_parse() {
         use std::str::FromStr;
@@ -600,4 +796,57 @@ mod tests {
 
         assert_eq!(intended.as_deref(), Some("claude-opus-4-20250514"));
     }
+
+    #[tokio::test]
+    async fn report_groups_by_model_and_feature_within_range() {
+        let ledger = CostLedger::new(test_db().await);
+        let haiku = |mut r: CostRecord| {
+            r.model = "claude-haiku-4-5-20250901".to_string();
+            r
+        };
+        let compaction = |mut r: CostRecord| {
+            r.feature_type = FeatureType::Compaction;
+            r.cache_read_tokens = 200;
+            r
+        };
+        for record in [
+            sample_record("s1", 1.0, "2026-03-01T09:00:00.000Z"),
+            sample_record("s2", 2.0, "2026-03-02T23:59:59.999Z"),
+            compaction(haiku(sample_record("s1", 0.25, "2026-03-01T10:00:00.000Z"))),
+            compaction(haiku(sample_record("s2", 0.25, "2026-03-02T10:00:00.000Z"))),
+            haiku(sample_record("s1", 0.5, "2026-03-02T11:00:00.000Z")),
+            // Outside the range.
+            sample_record("s3", 9.0, "2026-02-28T23:00:00.000Z"),
+            sample_record("s3", 9.0, "2026-03-03T00:00:00.000Z"),
+        ] {
+            ledger.record(&record).await.unwrap();
+        }
+
+        let report = ledger.report("2026-03-01", "2026-03-02").await.unwrap();
+        assert_eq!(report.from, "2026-03-01");
+        assert_eq!(report.to, "2026-03-02");
+        assert!((report.total_cost_usd - 4.0).abs() < 1e-9);
+
+        let groups: Vec<_> = report
+            .lines
+            .iter()
+            .map(|l| (l.model.as_str(), l.feature_type.clone(), l.calls))
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                ("claude-sonnet-4-20250514", FeatureType::Message, 2),
+                ("claude-haiku-4-5-20250901", FeatureType::Compaction, 2),
+                ("claude-haiku-4-5-20250901", FeatureType::Message, 1),
+            ]
+        );
+
+        let sonnet = &report.lines[0];
+        assert_eq!(sonnet.input_tokens, 2000);
+        assert_eq!(sonnet.output_tokens, 1000);
+        assert!((sonnet.cost_usd - 3.0).abs() < 1e-9);
+        let compaction = &report.lines[1];
+        assert_eq!(compaction.cache_read_tokens, 400);
+        assert!((compaction.cost_usd - 0.5).abs() < 1e-9);
+    }
 }
@@ -13,5 +13,5 @@ pub mod budget;
 pub mod ledger;
 pub mod pricing;
 
-pub use budget::BudgetTracker;
-pub use ledger::{CostLedger, CostRecord, FeatureType};
+pub use budget::{BudgetAlert, BudgetNotifier, BudgetPeriod, BudgetTracker};
+pub use ledger::{CostLedger, CostRecord, CostReport, CostReportLine, FeatureType};
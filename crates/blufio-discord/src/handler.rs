@@ -24,11 +24,12 @@ pub fn should_respond(msg: &Message, bot_id: UserId) -> bool {
 ///
 /// Empty allowed_users means everyone is allowed.
 pub fn is_authorized(msg: &Message, allowed_users: &[String]) -> bool {
-    if allowed_users.is_empty() {
-        return true;
-    }
-    let sender_id = msg.author.id.to_string();
-    allowed_users.contains(&sender_id)
+    is_user_allowed(&msg.author.id.to_string(), allowed_users)
+}
+
+/// Returns true if `user_id` is in `allowed_users` (or the list is empty).
+pub fn is_user_allowed(user_id: &str, allowed_users: &[String]) -> bool {
+    allowed_users.is_empty() || allowed_users.iter().any(|u| u == user_id)
 }
 
 /// Strips the bot @mention from message content.
@@ -48,6 +49,7 @@ pub fn to_inbound_message(msg: &Message, content: String) -> InboundMessage {
         "channel_id": msg.channel_id.to_string(),
         "guild_id": msg.guild_id.map(|g| g.to_string()),
         "chat_id": msg.channel_id.to_string(),
+        "user_id": msg.author.id.to_string(),
     });
 
     InboundMessage {
@@ -88,18 +90,16 @@ mod tests {
     }
 
     #[test]
-    fn is_authorized_empty_allows_all() {
-        // Cannot construct a real Message in tests without full serenity fixtures,
-        // so we test the logic via the string comparison.
-        let allowed: Vec<String> = vec![];
-        // Empty list = everyone allowed
-        assert!(allowed.is_empty());
+    fn is_user_allowed_empty_allows_all() {
+        assert!(is_user_allowed("111", &[]));
     }
 
     #[test]
-    fn is_authorized_checks_id() {
+    fn is_user_allowed_checks_id() {
         let allowed = ["111".to_string(), "222".to_string()];
-        assert!(allowed.contains(&"111".to_string()));
-        assert!(!allowed.contains(&"333".to_string()));
+        assert!(is_user_allowed("111", &allowed));
+        assert!(is_user_allowed("222", &allowed));
+        assert!(!is_user_allowed("333", &allowed));
+        assert!(!is_user_allowed("11", &allowed));
     }
 }
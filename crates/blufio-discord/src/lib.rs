@@ -459,6 +459,7 @@ mod tests {
             reply_to: None,
             parse_mode: None,
             metadata: Some(r#"{"chat_id":"123456789012345678"}"#.into()),
+            idempotency_key: None,
         };
         let id = extract_channel_id(&msg).unwrap();
         assert_eq!(id.get(), 123456789012345678);
@@ -473,6 +474,7 @@ mod tests {
             reply_to: None,
             parse_mode: None,
             metadata: None,
+            idempotency_key: None,
         };
         let id = extract_channel_id(&msg).unwrap();
         assert_eq!(id.get(), 123456789012345678);
@@ -487,6 +489,7 @@ mod tests {
             reply_to: None,
             parse_mode: None,
             metadata: None,
+            idempotency_key: None,
         };
         assert!(extract_channel_id(&msg).is_err());
     }
@@ -0,0 +1,82 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! CORS policy for the gateway's HTTP routes.
+//!
+//! The default allows no origin, so browsers refuse cross-origin calls until
+//! an operator lists the front-ends that may call the API. Preflight
+//! `OPTIONS` requests are answered by the CORS layer itself, before routing,
+//! so they never reach the auth middleware.
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use blufio_config::model::GatewayCorsConfig;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+
+/// Cross-origin access settings (mirrors `GatewayCorsConfig` from
+/// `blufio-config`, which also supplies the defaults).
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    /// Origins allowed to call the API, e.g. `https://app.example.com`.
+    /// `"*"` allows any origin. Empty = deny all.
+    pub allowed_origins: Vec<String>,
+    /// Methods allowed in cross-origin requests. `"*"` allows any.
+    pub allowed_methods: Vec<String>,
+    /// Request headers allowed in cross-origin requests. `"*"` allows any.
+    pub allowed_headers: Vec<String>,
+}
+
+impl From<&GatewayCorsConfig> for CorsConfig {
+    fn from(config: &GatewayCorsConfig) -> Self {
+        Self {
+            allowed_origins: config.allowed_origins.clone(),
+            allowed_methods: config.allowed_methods.clone(),
+            allowed_headers: config.allowed_headers.clone(),
+        }
+    }
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self::from(&GatewayCorsConfig::default())
+    }
+}
+
+/// Builds the gateway CORS layer from `config`.
+///
+/// Entries that are not valid header values, methods or header names are
+/// skipped with a warning (config validation reports them at load time).
+pub fn build_gateway_cors(config: &CorsConfig) -> CorsLayer {
+    let origin = if config.allowed_origins.iter().any(|o| o == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(parse_all::<HeaderValue>(&config.allowed_origins, "origin"))
+    };
+    let methods = if config.allowed_methods.iter().any(|m| m == "*") {
+        AllowMethods::any()
+    } else {
+        AllowMethods::list(parse_all::<Method>(&config.allowed_methods, "method"))
+    };
+    let headers = if config.allowed_headers.iter().any(|h| h == "*") {
+        AllowHeaders::any()
+    } else {
+        AllowHeaders::list(parse_all::<HeaderName>(&config.allowed_headers, "header"))
+    };
+
+    CorsLayer::new()
+        .allow_origin(origin)
+        .allow_methods(methods)
+        .allow_headers(headers)
+}
+
+fn parse_all<T: std::str::FromStr>(entries: &[String], kind: &str) -> Vec<T> {
+    entries
+        .iter()
+        .filter_map(|entry| match entry.parse() {
+            Ok(value) => Some(value),
+            Err(_) => {
+                tracing::warn!(entry = %entry, "ignoring invalid CORS {kind}");
+                None
+            }
+        })
+        .collect()
+}
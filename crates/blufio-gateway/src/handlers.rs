@@ -122,6 +122,58 @@ pub struct PublicHealthResponse {
     pub uptime_secs: u64,
 }
 
+/// Why an inbound message could not be queued for the agent loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnqueueError {
+    /// The agent loop has dropped its receiver.
+    Closed,
+    /// The queue stayed full for the whole send timeout.
+    Full,
+}
+
+impl EnqueueError {
+    /// Error text shared by HTTP, SSE and WebSocket responses.
+    pub fn message(self) -> &'static str {
+        match self {
+            Self::Closed => "agent loop not accepting messages",
+            Self::Full => "inbound channel full",
+        }
+    }
+}
+
+impl IntoResponse for EnqueueError {
+    fn into_response(self) -> Response {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse {
+                error: self.message().to_string(),
+            }),
+        )
+            .into_response()
+    }
+}
+
+/// Queue a message for the agent loop, applying backpressure.
+///
+/// Waits up to `state.inbound_send_timeout` for room in the bounded inbound
+/// queue, so a burst slows clients down instead of piling up in memory.
+pub async fn enqueue_inbound(
+    state: &GatewayState,
+    inbound: InboundMessage,
+) -> Result<(), EnqueueError> {
+    match tokio::time::timeout(state.inbound_send_timeout, state.inbound_tx.send(inbound)).await {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(_)) => Err(EnqueueError::Closed),
+        Err(_) => {
+            tracing::warn!(
+                timeout_ms = state.inbound_send_timeout.as_millis() as u64,
+                "inbound queue full; rejecting message"
+            );
+            Err(EnqueueError::Full)
+        }
+    }
+}
+
 /// POST /v1/messages
 ///
 /// Accepts a message, routes it through the agent loop, and returns the response.
@@ -135,7 +187,8 @@ pub struct PublicHealthResponse {
         (status = 200, description = "Message processed", body = MessageResponse),
         (status = 400, description = "Invalid request", body = ErrorResponse),
         (status = 401, description = "Unauthorized"),
-        (status = 503, description = "Service unavailable", body = ErrorResponse),
+        (status = 413, description = "Request body too large"),
+        (status = 503, description = "Agent loop unavailable or inbound queue full", body = ErrorResponse),
         (status = 504, description = "Gateway timeout", body = ErrorResponse),
     ),
     security(("bearer_auth" = []))
@@ -152,7 +205,7 @@ pub async fn post_messages(
         .unwrap_or("");
 
     if accept.contains("text/event-stream") {
-        return sse::stream_messages(state, body).await.into_response();
+        return sse::stream_messages(state, body).await;
     }
 
     let request_id = uuid::Uuid::new_v4().to_string();
@@ -178,34 +231,10 @@ pub async fn post_messages(
     let (tx, rx) = oneshot::channel::<String>();
     state.response_map.insert(request_id.clone(), tx);
 
-    // Send to inbound channel (with timeout).
-    match tokio::time::timeout(
-        std::time::Duration::from_secs(5),
-        state.inbound_tx.send(inbound),
-    )
-    .await
-    {
-        Ok(Ok(())) => {}
-        Ok(Err(_)) => {
-            state.response_map.remove(&request_id);
-            return (
-                StatusCode::SERVICE_UNAVAILABLE,
-                Json(ErrorResponse {
-                    error: "agent loop not accepting messages".to_string(),
-                }),
-            )
-                .into_response();
-        }
-        Err(_) => {
-            state.response_map.remove(&request_id);
-            return (
-                StatusCode::SERVICE_UNAVAILABLE,
-                Json(ErrorResponse {
-                    error: "inbound channel full".to_string(),
-                }),
-            )
-                .into_response();
-        }
+    // Send to inbound channel, waiting for capacity up to the send timeout.
+    if let Err(e) = enqueue_inbound(&state, inbound).await {
+        state.response_map.remove(&request_id);
+        return e.into_response();
     }
 
     // Wait for response (with timeout for LLM processing).
@@ -13,7 +13,9 @@ pub mod audit;
 pub mod auth;
 pub mod batch;
 pub mod classify;
+pub mod cors;
 pub mod handlers;
+pub mod metrics_access;
 pub mod openai_compat;
 pub mod openapi;
 pub mod rate_limit;
@@ -27,6 +29,7 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use dashmap::DashMap;
 use tokio::sync::{Mutex, mpsc};
+use tokio_util::sync::CancellationToken;
 
 use blufio_core::BlufioError;
 use blufio_core::ProviderRegistry;
@@ -64,6 +67,22 @@ pub struct GatewayChannelConfig {
     pub prometheus_render: Option<Arc<dyn Fn() -> String + Send + Sync>>,
     /// Maximum concurrent MCP connections (INTG-05). Default: 10.
     pub mcp_max_connections: usize,
+    /// Require API auth for GET /metrics.
+    pub metrics_require_auth: bool,
+    /// Peer networks allowed to reach GET /metrics. Empty = any peer.
+    pub metrics_allowlist: Vec<crate::metrics_access::Cidr>,
+    /// Per-identity token bucket for authenticated API routes (None = off).
+    pub identity_rate_limit: Option<crate::rate_limit::TokenBucketConfig>,
+    /// Maximum request body size in bytes; larger bodies get 413.
+    pub max_body_bytes: usize,
+    /// How long a request waits for room in the inbound queue before 503.
+    pub inbound_send_timeout: std::time::Duration,
+    /// How long shutdown waits for in-flight requests before failing them.
+    pub drain_timeout: std::time::Duration,
+    /// Cross-origin policy for browser clients (default: deny all).
+    pub cors: crate::cors::CorsConfig,
+    /// WebSocket keepalive pings and idle timeout.
+    pub ws_keepalive: ws::WsKeepalive,
 }
 
 impl std::fmt::Debug for GatewayChannelConfig {
@@ -81,6 +100,14 @@ impl std::fmt::Debug for GatewayChannelConfig {
                 "prometheus_render",
                 &self.prometheus_render.as_ref().map(|_| "<fn>"),
             )
+            .field("metrics_require_auth", &self.metrics_require_auth)
+            .field("metrics_allowlist", &self.metrics_allowlist)
+            .field("identity_rate_limit", &self.identity_rate_limit)
+            .field("max_body_bytes", &self.max_body_bytes)
+            .field("inbound_send_timeout", &self.inbound_send_timeout)
+            .field("drain_timeout", &self.drain_timeout)
+            .field("cors", &self.cors)
+            .field("ws_keepalive", &self.ws_keepalive)
             .finish()
     }
 }
@@ -98,6 +125,8 @@ pub struct GatewayChannel {
     response_map: Arc<DashMap<String, tokio::sync::oneshot::Sender<String>>>,
     ws_senders: Arc<DashMap<String, mpsc::Sender<String>>>,
     server_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
+    /// Cancelled by `shutdown()` to stop accepting and drain the server.
+    shutdown_token: CancellationToken,
     /// Optional MCP HTTP router to mount at /mcp on the gateway.
     /// Set via [`set_mcp_router`] before calling `connect()`.
     mcp_router: Mutex<Option<axum::Router>>,
@@ -146,6 +175,7 @@ impl GatewayChannel {
             response_map: Arc::new(DashMap::new()),
             ws_senders: Arc::new(DashMap::new()),
             server_handle: Mutex::new(None),
+            shutdown_token: CancellationToken::new(),
             mcp_router: Mutex::new(None),
             storage: Mutex::new(None),
             providers: Mutex::new(None),
@@ -297,9 +327,37 @@ impl PluginAdapter for GatewayChannel {
     }
 
     async fn shutdown(&self) -> Result<(), BlufioError> {
+        // Stop accepting connections; in-flight requests keep running.
+        self.shutdown_token.cancel();
+
         let mut handle = self.server_handle.lock().await;
-        if let Some(h) = handle.take() {
-            h.abort();
+        let mut drained = true;
+        if let Some(h) = handle.as_mut() {
+            drained = tokio::time::timeout(self.config.drain_timeout, &mut *h)
+                .await
+                .is_ok();
+        }
+
+        // Anything still waiting for the agent gets an error response now.
+        let pending = server::fail_pending_responses(&self.response_map, &self.ws_senders);
+        if pending > 0 {
+            tracing::warn!(
+                pending,
+                "gateway drain deadline passed; failing pending requests"
+            );
+        }
+
+        if let Some(mut h) = handle.take()
+            && !drained
+        {
+            // Give the failed handlers a moment to write their responses.
+            if tokio::time::timeout(std::time::Duration::from_secs(1), &mut h)
+                .await
+                .is_err()
+            {
+                tracing::warn!("gateway server did not stop in time; aborting");
+                h.abort();
+            }
         }
         Ok(())
     }
@@ -332,6 +390,12 @@ impl ChannelAdapter for GatewayChannel {
             port: self.config.port,
             bearer_token: self.config.bearer_token.clone(),
             swagger_ui_enabled: false,
+            metrics_access: crate::metrics_access::MetricsAccess {
+                require_auth: self.config.metrics_require_auth,
+                allowlist: self.config.metrics_allowlist.clone(),
+            },
+            max_body_bytes: self.config.max_body_bytes,
+            cors: self.config.cors.clone(),
         };
 
         // Take optional adapters (if set).
@@ -368,12 +432,19 @@ impl ChannelAdapter for GatewayChannel {
             event_bus,
             degradation_manager,
             circuit_breaker_registry,
+            identity_limiter: self
+                .config
+                .identity_rate_limit
+                .map(|limit| Arc::new(crate::rate_limit::IdentityRateLimiter::new(limit))),
+            inbound_send_timeout: self.config.inbound_send_timeout,
+            ws_keepalive: self.config.ws_keepalive,
         };
 
         // Take the MCP router (if set) to pass to the server.
         let mcp_router = self.mcp_router.lock().await.take();
         let mcp_max_connections = self.config.mcp_max_connections;
         let extra_public_routes = self.extra_public_routes.lock().await.take();
+        let shutdown = self.shutdown_token.clone();
 
         let handle = tokio::spawn(async move {
             if let Err(e) = server::start_server(
@@ -382,6 +453,7 @@ impl ChannelAdapter for GatewayChannel {
                 mcp_router,
                 mcp_max_connections,
                 extra_public_routes,
+                shutdown,
             )
             .await
             {
@@ -455,6 +527,28 @@ impl ChannelAdapter for GatewayChannel {
             .ok_or_else(|| BlufioError::channel_connection_lost("gateway"))
     }
 
+    async fn send_partial(&self, msg: OutboundMessage) -> Result<(), BlufioError> {
+        // Only WebSocket clients receive deltas; HTTP callers get the
+        // complete response from `send`.
+        let meta: serde_json::Value = msg
+            .metadata
+            .as_deref()
+            .and_then(|m| serde_json::from_str(m).ok())
+            .unwrap_or(serde_json::Value::Null);
+
+        if let Some(ws_id) = meta.get("ws_id").and_then(|v| v.as_str())
+            && let Some(sender) = self.ws_senders.get(ws_id)
+        {
+            let ws_msg = serde_json::json!({
+                "type": ws::message_types::DELTA,
+                "content": msg.content,
+                "session_id": msg.session_id,
+            });
+            let _ = sender.send(ws_msg.to_string()).await;
+        }
+        Ok(())
+    }
+
     async fn send_typing(&self, chat_id: &str) -> Result<(), BlufioError> {
         // For WebSocket connections, send a typing indicator.
         if let Some(sender) = self.ws_senders.get(chat_id) {
@@ -480,6 +574,14 @@ mod tests {
             keypair_public_key: None,
             prometheus_render: None,
             mcp_max_connections: 10,
+            metrics_require_auth: false,
+            metrics_allowlist: Vec::new(),
+            identity_rate_limit: None,
+            max_body_bytes: 2 * 1024 * 1024,
+            inbound_send_timeout: std::time::Duration::from_secs(5),
+            ws_keepalive: ws::WsKeepalive::default(),
+            drain_timeout: std::time::Duration::from_secs(10),
+            cors: crate::cors::CorsConfig::default(),
         }
     }
 
@@ -512,4 +614,276 @@ mod tests {
             _ => panic!("expected Unhealthy before connect"),
         }
     }
+
+    #[tokio::test]
+    async fn shutdown_fails_requests_still_pending() {
+        let channel = GatewayChannel::new(test_config());
+        let (tx, rx) = tokio::sync::oneshot::channel::<String>();
+        channel.response_map.insert("req-1".to_string(), tx);
+
+        channel.shutdown().await.unwrap();
+
+        assert!(channel.response_map.is_empty());
+        assert!(rx.await.is_err(), "pending responder should be dropped");
+        assert!(channel.shutdown_token.is_cancelled());
+    }
+
+    /// Gateway state sharing `channel`'s queues, without auth.
+    fn channel_state(channel: &GatewayChannel) -> GatewayState {
+        GatewayState {
+            response_map: Arc::clone(&channel.response_map),
+            ws_senders: Arc::clone(&channel.ws_senders),
+            ..GatewayState::for_test(channel.inbound_tx.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn ws_client_receives_deltas_then_completion() {
+        use futures::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        let channel = GatewayChannel::new(test_config());
+        let app = axum::Router::new()
+            .route("/ws", axum::routing::get(ws::ws_handler))
+            .with_state(channel_state(&channel));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let (mut client, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws"))
+            .await
+            .unwrap();
+        client
+            .send(Message::Text(r#"{"content": "hello"}"#.into()))
+            .await
+            .unwrap();
+
+        let inbound = channel.receive().await.unwrap();
+        let reply = |content: &str| OutboundMessage {
+            session_id: Some("sess-1".to_string()),
+            channel: inbound.channel.clone(),
+            content: content.to_string(),
+            reply_to: None,
+            parse_mode: None,
+            metadata: inbound.metadata.clone(),
+            idempotency_key: None,
+        };
+        for delta in ["Hel", "lo ", "there"] {
+            channel.send_partial(reply(delta)).await.unwrap();
+        }
+        channel.send(reply("Hello there")).await.unwrap();
+
+        let mut frames = Vec::new();
+        while frames.len() < 4 {
+            let msg = client.next().await.unwrap().unwrap();
+            if let Message::Text(text) = msg {
+                frames.push(serde_json::from_str::<serde_json::Value>(&text).unwrap());
+            }
+        }
+
+        // Deltas use the documented frame shape exactly.
+        let expected: Vec<serde_json::Value> = ["Hel", "lo ", "there"]
+            .iter()
+            .map(|d| serde_json::json!({"type": "delta", "content": d, "session_id": "sess-1"}))
+            .collect();
+        assert_eq!(frames[..3], expected[..]);
+        assert_eq!(frames[3]["type"], ws::message_types::MESSAGE_COMPLETE);
+        assert_eq!(frames[3]["content"], "Hello there");
+    }
+
+    /// Serve `/ws` for `channel` with the given keepalive settings.
+    async fn serve_ws(channel: &GatewayChannel, keepalive: ws::WsKeepalive) -> String {
+        let state = GatewayState {
+            ws_keepalive: keepalive,
+            ..channel_state(channel)
+        };
+        let app = axum::Router::new()
+            .route("/ws", axum::routing::get(ws::ws_handler))
+            .with_state(state);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("ws://{addr}/ws")
+    }
+
+    /// Poll until `channel` has `count` registered WebSocket senders.
+    async fn wait_for_ws_senders(channel: &GatewayChannel, count: usize) -> bool {
+        for _ in 0..200 {
+            if channel.ws_senders.len() == count {
+                return true;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn ws_unresponsive_client_is_disconnected() {
+        let channel = GatewayChannel::new(test_config());
+        let url = serve_ws(
+            &channel,
+            ws::WsKeepalive {
+                ping_interval: Some(std::time::Duration::from_millis(50)),
+                pong_timeout: std::time::Duration::from_millis(100),
+                idle_timeout: None,
+            },
+        )
+        .await;
+
+        // The client never polls its stream, so it never answers pings.
+        let (_client, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+        assert!(wait_for_ws_senders(&channel, 1).await);
+        assert!(
+            wait_for_ws_senders(&channel, 0).await,
+            "connection without pongs should be closed and unregistered"
+        );
+    }
+
+    #[tokio::test]
+    async fn ws_responsive_client_stays_connected_until_idle() {
+        use futures::StreamExt;
+
+        let channel = GatewayChannel::new(test_config());
+        let url = serve_ws(
+            &channel,
+            ws::WsKeepalive {
+                ping_interval: Some(std::time::Duration::from_millis(50)),
+                pong_timeout: std::time::Duration::from_millis(100),
+                idle_timeout: Some(std::time::Duration::from_millis(600)),
+            },
+        )
+        .await;
+
+        // Reading drives tungstenite's automatic pong replies.
+        let (client, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+        let (_write, mut read) = client.split();
+        let reader = tokio::spawn(async move { while let Some(Ok(_)) = read.next().await {} });
+        assert!(wait_for_ws_senders(&channel, 1).await);
+
+        // Several ping rounds pass without a disconnect.
+        tokio::time::sleep(std::time::Duration::from_millis(350)).await;
+        assert_eq!(channel.ws_senders.len(), 1);
+
+        // With no client messages, the idle timeout closes the connection.
+        assert!(wait_for_ws_senders(&channel, 0).await);
+        tokio::time::timeout(std::time::Duration::from_secs(2), reader)
+            .await
+            .expect("client should see the close")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn sse_chat_stream_forwards_deltas_then_done() {
+        let channel = GatewayChannel::new(test_config());
+        let app = axum::Router::new()
+            .route(
+                "/v1/chat/stream",
+                axum::routing::post(sse::post_chat_stream),
+            )
+            .with_state(channel_state(&channel));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let client = tokio::spawn(async move {
+            let response = reqwest::Client::new()
+                .post(format!("http://{addr}/v1/chat/stream"))
+                .json(&serde_json::json!({"content": "hello"}))
+                .send()
+                .await
+                .unwrap();
+            let content_type = response.headers()["content-type"].clone();
+            (content_type, response.text().await.unwrap())
+        });
+
+        let inbound = channel.receive().await.unwrap();
+        let reply = |content: &str| OutboundMessage {
+            session_id: Some("sess-1".to_string()),
+            channel: inbound.channel.clone(),
+            content: content.to_string(),
+            reply_to: None,
+            parse_mode: None,
+            metadata: inbound.metadata.clone(),
+            idempotency_key: None,
+        };
+        for delta in ["Hel", "lo"] {
+            channel.send_partial(reply(delta)).await.unwrap();
+        }
+        channel.send(reply("Hello")).await.unwrap();
+
+        let (content_type, body) = client.await.unwrap();
+        assert!(
+            content_type
+                .to_str()
+                .unwrap()
+                .starts_with("text/event-stream")
+        );
+
+        // Parse `id:`/`event:`/`data:` frames, skipping keep-alive comments.
+        let mut events = Vec::new();
+        for block in body.split("\n\n").filter(|b| !b.trim().is_empty()) {
+            let (mut id, mut name, mut data) = (None, None, None);
+            for line in block.lines() {
+                if let Some(v) = line.strip_prefix("id:") {
+                    id = Some(v.trim().to_string());
+                } else if let Some(v) = line.strip_prefix("event:") {
+                    name = Some(v.trim().to_string());
+                } else if let Some(v) = line.strip_prefix("data:") {
+                    data = Some(serde_json::from_str::<serde_json::Value>(v.trim()).unwrap());
+                }
+            }
+            if let Some(name) = name {
+                events.push((id.unwrap(), name, data.unwrap()));
+            }
+        }
+
+        let names: Vec<&str> = events.iter().map(|(_, n, _)| n.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "message_start",
+                "content_block_delta",
+                "content_block_delta",
+                "message_stop",
+                "done"
+            ]
+        );
+        assert_eq!(events[1].2["delta"]["text"], "Hel");
+        assert_eq!(events[2].2["delta"]["text"], "lo");
+        assert_eq!(events[3].2["content"], "Hello");
+        assert_eq!(events[3].2["session_id"], "sess-1");
+
+        // IDs are unique and ordered within the stream.
+        let request_id = events[0].2["message"]["id"].as_str().unwrap().to_string();
+        for (seq, (id, _, _)) in events.iter().enumerate() {
+            assert_eq!(id, &format!("{request_id}:{}", seq + 1));
+        }
+        // The stream's sender is unregistered once it ends.
+        assert!(channel.ws_senders.is_empty());
+    }
+
+    #[tokio::test]
+    async fn send_partial_without_ws_client_is_noop() {
+        let channel = GatewayChannel::new(test_config());
+        let (tx, _rx) = tokio::sync::oneshot::channel();
+        channel.response_map.insert("req-1".to_string(), tx);
+        let msg = OutboundMessage {
+            session_id: None,
+            channel: "gateway".to_string(),
+            content: "partial".to_string(),
+            reply_to: None,
+            parse_mode: None,
+            metadata: Some(r#"{"request_id": "req-1"}"#.to_string()),
+            idempotency_key: None,
+        };
+        channel.send_partial(msg).await.unwrap();
+        // The HTTP response slot is left for the complete response.
+        assert!(channel.response_map.contains_key("req-1"));
+    }
 }
@@ -0,0 +1,214 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Access control for the `/metrics` endpoint.
+//!
+//! `/metrics` is public by default so Prometheus can scrape without
+//! credentials. Two optional guards tighten that:
+//! 1. An IP allowlist of CIDR ranges, checked against the peer address (403).
+//! 2. The same auth as the `/v1` API, via [`auth_middleware`] (401).
+//!
+//! The allowlist runs first, so peers outside it are refused before any
+//! credential is looked at.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    extract::{ConnectInfo, Request, State},
+    http::StatusCode,
+    middleware::{self as axum_middleware, Next},
+    response::Response,
+    routing::get,
+};
+
+use crate::auth::auth_middleware;
+use crate::handlers;
+use crate::server::GatewayState;
+
+pub use blufio_core::net::Cidr;
+
+/// How `/metrics` is protected. The default leaves it public.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsAccess {
+    /// Require `/v1` API auth.
+    pub require_auth: bool,
+    /// Allowed peer networks. Empty = any peer.
+    pub allowlist: Vec<Cidr>,
+}
+
+/// Middleware that rejects peers outside `allowlist` with 403.
+///
+/// The peer address comes from [`ConnectInfo`], so the server must be run
+/// with `into_make_service_with_connect_info::<SocketAddr>()`. Requests
+/// without it are rejected (fail-closed).
+pub async fn ip_allowlist_middleware(
+    State(allowlist): State<Arc<Vec<Cidr>>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    match peer {
+        Some(ip) if allowlist.iter().any(|net| net.contains(ip)) => Ok(next.run(request).await),
+        Some(ip) => {
+            tracing::debug!(peer = %ip, "metrics request rejected: peer not in allowlist");
+            Err(StatusCode::FORBIDDEN)
+        }
+        None => {
+            tracing::warn!("metrics request rejected: peer address unavailable");
+            Err(StatusCode::FORBIDDEN)
+        }
+    }
+}
+
+/// The `/metrics` route with the guards `access` asks for.
+pub(crate) fn metrics_routes(state: GatewayState, access: &MetricsAccess) -> Router {
+    let mut routes = Router::new().route("/metrics", get(handlers::get_public_metrics));
+    // Layers run bottom-up: auth is added first so the allowlist wraps it.
+    if access.require_auth {
+        routes = routes.route_layer(axum_middleware::from_fn_with_state(
+            state.auth.clone(),
+            auth_middleware,
+        ));
+    }
+    if !access.allowlist.is_empty() {
+        routes = routes.route_layer(axum_middleware::from_fn_with_state(
+            Arc::new(access.allowlist.clone()),
+            ip_allowlist_middleware,
+        ));
+    }
+    routes.with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::AuthConfig;
+    use crate::server::HealthState;
+    use axum::body::Body;
+    use tokio::sync::mpsc;
+    use tower::ServiceExt;
+
+    fn cidr(s: &str) -> Cidr {
+        s.parse().unwrap()
+    }
+
+    fn test_state() -> GatewayState {
+        let (tx, _rx) = mpsc::channel(1);
+        GatewayState {
+            auth: AuthConfig {
+                bearer_token: Some("secret".to_string()),
+                keypair_public_key: None,
+                key_store: None,
+            },
+            health: HealthState {
+                start_time: std::time::Instant::now(),
+                prometheus_render: Some(Arc::new(|| "blufio_up 1\n".to_string())),
+            },
+            ..GatewayState::for_test(tx)
+        }
+    }
+
+    async fn get_metrics(app: Router, peer: &str, token: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().uri("/metrics");
+        if let Some(token) = token {
+            request = request.header("authorization", format!("Bearer {token}"));
+        }
+        let mut request = request.body(Body::empty()).unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(peer.parse::<SocketAddr>().unwrap()));
+        app.oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn metrics_public_by_default() {
+        let app = metrics_routes(test_state(), &MetricsAccess::default());
+        assert_eq!(
+            get_metrics(app, "203.0.113.7:9000", None).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn metrics_auth_requires_credentials() {
+        let access = MetricsAccess {
+            require_auth: true,
+            allowlist: vec![],
+        };
+        let app = metrics_routes(test_state(), &access);
+        assert_eq!(
+            get_metrics(app.clone(), "127.0.0.1:9000", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            get_metrics(app.clone(), "127.0.0.1:9000", Some("wrong")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            get_metrics(app, "127.0.0.1:9000", Some("secret")).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn metrics_allowlist_rejects_other_peers() {
+        let access = MetricsAccess {
+            require_auth: false,
+            allowlist: vec![cidr("10.0.0.0/8"), cidr("::1")],
+        };
+        let app = metrics_routes(test_state(), &access);
+        assert_eq!(
+            get_metrics(app.clone(), "10.4.5.6:9000", None).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            get_metrics(app.clone(), "[::1]:9000", None).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            get_metrics(app, "192.168.1.10:9000", None).await,
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[tokio::test]
+    async fn allowlist_is_checked_before_auth() {
+        let access = MetricsAccess {
+            require_auth: true,
+            allowlist: vec![cidr("10.0.0.0/8")],
+        };
+        let app = metrics_routes(test_state(), &access);
+        assert_eq!(
+            get_metrics(app.clone(), "192.168.1.10:9000", Some("secret")).await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            get_metrics(app.clone(), "10.0.0.1:9000", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            get_metrics(app, "10.0.0.1:9000", Some("secret")).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn allowlist_without_peer_address_fails_closed() {
+        let access = MetricsAccess {
+            require_auth: false,
+            allowlist: vec![cidr("0.0.0.0/0")],
+        };
+        let app = metrics_routes(test_state(), &access);
+        let request = Request::builder()
+            .uri("/metrics")
+            .body(Body::empty())
+            .unwrap();
+        let status = app.oneshot(request).await.unwrap().status();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+}
@@ -14,6 +14,7 @@ use utoipa::OpenApi;
     paths(
         // Core handlers
         crate::handlers::post_messages,
+        crate::sse::post_chat_stream,
         crate::handlers::get_health,
         crate::handlers::get_sessions,
         crate::handlers::get_public_health,
@@ -1,10 +1,16 @@
 // SPDX-FileCopyrightText: 2026 Blufio Contributors
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-//! Sliding window rate limiter middleware for scoped API keys.
+//! Rate limiting middleware for authenticated gateway requests.
 //!
-//! Enforces per-key request limits using atomic SQLite counters.
-//! Master bearer tokens bypass rate limiting entirely.
+//! Two independent limits apply:
+//! - An in-memory token bucket per auth identity ([`IdentityRateLimiter`]),
+//!   applied to every identity including the master token, so one client
+//!   cannot flood the agent's inbound channel and starve the others.
+//! - A per-key sliding window for scoped API keys, using atomic SQLite
+//!   counters and the key's own `rate_limit`. Master tokens bypass it.
+
+use std::time::{Duration, Instant};
 
 use axum::{
     extract::{Request, State},
@@ -13,9 +19,110 @@ use axum::{
     response::Response,
 };
 
+use dashmap::DashMap;
+
 use crate::api_keys::AuthContext;
 use crate::server::GatewayState;
 
+/// Token bucket settings for [`IdentityRateLimiter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenBucketConfig {
+    /// Sustained rate: tokens refilled per minute.
+    pub requests_per_minute: u32,
+    /// Bucket capacity: requests allowed back-to-back after an idle period.
+    pub burst: u32,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// In-memory token bucket rate limiter keyed by auth identity.
+///
+/// Each identity gets its own bucket, so throttling one never affects
+/// another. Buckets are created on first use, full.
+#[derive(Debug)]
+pub struct IdentityRateLimiter {
+    config: TokenBucketConfig,
+    buckets: DashMap<String, Bucket>,
+}
+
+impl IdentityRateLimiter {
+    /// Create a limiter. A `burst` of 0 is treated as 1.
+    pub fn new(config: TokenBucketConfig) -> Self {
+        Self {
+            config,
+            buckets: DashMap::new(),
+        }
+    }
+
+    /// Take one token for `identity`.
+    ///
+    /// Returns `Err(retry_after)` when the bucket is empty, with the time
+    /// until a token is available.
+    pub fn check(&self, identity: &str) -> Result<(), Duration> {
+        self.check_at(identity, Instant::now())
+    }
+
+    fn check_at(&self, identity: &str, now: Instant) -> Result<(), Duration> {
+        let capacity = f64::from(self.config.burst.max(1));
+        let per_sec = f64::from(self.config.requests_per_minute) / 60.0;
+        let mut bucket = self
+            .buckets
+            .entry(identity.to_string())
+            .or_insert_with(|| Bucket {
+                tokens: capacity,
+                refilled_at: now,
+            });
+
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * per_sec).min(capacity);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else if per_sec > 0.0 {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_sec))
+        } else {
+            Err(Duration::from_secs(60))
+        }
+    }
+}
+
+/// Identity used for per-identity limits: the key ID for scoped keys, or
+/// `master` for the bearer token and keypair signatures.
+fn identity_of(ctx: &AuthContext) -> &str {
+    ctx.key_id().unwrap_or("master")
+}
+
+/// 429 response in the OpenAI error format with a `Retry-After` header.
+fn too_many_requests(retry_after_secs: u64) -> Response {
+    let mut response = Response::builder()
+        .status(StatusCode::TOO_MANY_REQUESTS)
+        .body(axum::body::Body::from(
+            serde_json::json!({
+                "error": {
+                    "message": "Rate limit exceeded",
+                    "type": "rate_limit_error",
+                    "code": "rate_limit_exceeded"
+                }
+            })
+            .to_string(),
+        ))
+        .expect("valid response builder");
+    let headers = response.headers_mut();
+    headers.insert(
+        "Retry-After",
+        HeaderValue::from_str(&retry_after_secs.to_string())
+            .expect("valid header: numeric retry_after"),
+    );
+    headers.insert("Content-Type", HeaderValue::from_static("application/json"));
+    response
+}
+
 /// Returns the start of the current minute as an ISO 8601 string.
 ///
 /// Used as the rate limit window key. Truncates to minute boundary.
@@ -31,12 +138,14 @@ fn seconds_until_next_minute() -> u64 {
     (60 - secs) as u64
 }
 
-/// Rate limiting middleware that enforces per-key request counts.
+/// Rate limiting middleware that enforces per-identity and per-key limits.
 ///
 /// Must run AFTER `auth_middleware` (which inserts `AuthContext` into extensions).
 ///
 /// Behavior:
-/// - `AuthContext::Master`: No rate limiting, no rate limit headers.
+/// - Any identity: when `GatewayState::identity_limiter` is set, takes a token
+///   from the identity's bucket and returns 429 with `Retry-After` if empty.
+/// - `AuthContext::Master`: No per-key limit, no rate limit headers.
 /// - `AuthContext::Scoped`: Enforces sliding window counter per key.
 /// - Missing `AuthContext`: Passes through (auth middleware handles rejection).
 pub async fn rate_limit_middleware(
@@ -56,9 +165,20 @@ pub async fn rate_limit_middleware(
         }
     };
 
+    if let Some(limiter) = state.identity_limiter.as_ref()
+        && let Err(retry_after) = limiter.check(identity_of(&auth_ctx))
+    {
+        tracing::debug!(
+            identity = identity_of(&auth_ctx),
+            "identity rate limit exceeded"
+        );
+        // Round up so clients never retry before a token is available.
+        return Ok(too_many_requests(retry_after.as_secs_f64().ceil() as u64));
+    }
+
     match auth_ctx {
         AuthContext::Master => {
-            // Master token: no rate limiting.
+            // Master token: no per-key limit.
             Ok(next.run(request).await)
         }
         AuthContext::Scoped {
@@ -92,26 +212,8 @@ pub async fn rate_limit_middleware(
                 // Re-insert AuthContext since we consumed it.
                 request.extensions_mut().insert(auth_ctx);
 
-                let mut response = Response::builder()
-                    .status(StatusCode::TOO_MANY_REQUESTS)
-                    .body(axum::body::Body::from(
-                        serde_json::json!({
-                            "error": {
-                                "message": "Rate limit exceeded",
-                                "type": "rate_limit_error",
-                                "code": "rate_limit_exceeded"
-                            }
-                        })
-                        .to_string(),
-                    ))
-                    .expect("valid response builder");
-
+                let mut response = too_many_requests(retry_after);
                 let headers = response.headers_mut();
-                headers.insert(
-                    "Retry-After",
-                    HeaderValue::from_str(&retry_after.to_string())
-                        .expect("valid header: numeric retry_after"),
-                );
                 headers.insert(
                     "X-RateLimit-Limit",
                     HeaderValue::from_str(&rate_limit.to_string())
@@ -123,7 +225,6 @@ pub async fn rate_limit_middleware(
                     HeaderValue::from_str(&retry_after.to_string())
                         .expect("valid header: numeric retry_after"),
                 );
-                headers.insert("Content-Type", HeaderValue::from_static("application/json"));
 
                 return Ok(response);
             }
@@ -176,4 +277,127 @@ mod tests {
         let secs = seconds_until_next_minute();
         assert!(secs > 0 && secs <= 60);
     }
+
+    fn limiter(requests_per_minute: u32, burst: u32) -> IdentityRateLimiter {
+        IdentityRateLimiter::new(TokenBucketConfig {
+            requests_per_minute,
+            burst,
+        })
+    }
+
+    #[test]
+    fn bucket_allows_burst_then_refills() {
+        let limiter = limiter(60, 3);
+        let start = Instant::now();
+        for _ in 0..3 {
+            assert!(limiter.check_at("key-a", start).is_ok());
+        }
+        let retry = limiter.check_at("key-a", start).unwrap_err();
+        assert!(retry <= Duration::from_secs(1) && retry > Duration::ZERO);
+
+        // 60/min refills one token per second.
+        assert!(
+            limiter
+                .check_at("key-a", start + Duration::from_secs(1))
+                .is_ok()
+        );
+        assert!(
+            limiter
+                .check_at("key-a", start + Duration::from_secs(1))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn refill_is_capped_at_burst() {
+        let limiter = limiter(60, 2);
+        let start = Instant::now();
+        assert!(limiter.check_at("key-a", start).is_ok());
+        let later = start + Duration::from_secs(3600);
+        assert!(limiter.check_at("key-a", later).is_ok());
+        assert!(limiter.check_at("key-a", later).is_ok());
+        assert!(limiter.check_at("key-a", later).is_err());
+    }
+
+    #[test]
+    fn identities_have_separate_buckets() {
+        let limiter = limiter(1, 1);
+        let now = Instant::now();
+        assert!(limiter.check_at("flooder", now).is_ok());
+        for _ in 0..100 {
+            assert!(limiter.check_at("flooder", now).is_err());
+        }
+        assert!(limiter.check_at("quiet", now).is_ok());
+        assert!(limiter.check_at("master", now).is_ok());
+    }
+
+    /// Router with the limiter, where `x-test-key` picks the auth identity
+    /// (standing in for `auth_middleware`).
+    fn limited_router(limiter: IdentityRateLimiter) -> axum::Router {
+        use axum::{Router, middleware::from_fn, middleware::from_fn_with_state, routing::get};
+        use std::sync::Arc;
+
+        let (tx, _rx) = tokio::sync::mpsc::channel(1);
+        let state = GatewayState {
+            identity_limiter: Some(Arc::new(limiter)),
+            ..GatewayState::for_test(tx)
+        };
+
+        Router::new()
+            .route("/v1/test", get(|| async { "ok" }))
+            .route_layer(from_fn_with_state(state, rate_limit_middleware))
+            .route_layer(from_fn(|mut request: Request, next: Next| async move {
+                let ctx = match request.headers().get("x-test-key") {
+                    Some(key) => AuthContext::Scoped {
+                        key_id: key.to_str().unwrap().to_string(),
+                        scopes: vec![],
+                        rate_limit: 1000,
+                    },
+                    None => AuthContext::master(),
+                };
+                request.extensions_mut().insert(ctx);
+                next.run(request).await
+            }))
+    }
+
+    async fn send(app: &axum::Router, key: Option<&str>) -> Response {
+        use tower::ServiceExt;
+        let mut request = Request::builder().uri("/v1/test");
+        if let Some(key) = key {
+            request = request.header("x-test-key", key);
+        }
+        app.clone()
+            .oneshot(request.body(axum::body::Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn flood_from_one_identity_does_not_throttle_another() {
+        let app = limited_router(limiter(60, 5));
+
+        let mut throttled = 0;
+        for _ in 0..20 {
+            let response = send(&app, Some("flooder")).await;
+            if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                let retry_after = response.headers()["Retry-After"].to_str().unwrap();
+                assert!(retry_after.parse::<u64>().unwrap() >= 1);
+                throttled += 1;
+            }
+        }
+        assert_eq!(throttled, 15);
+
+        assert_eq!(send(&app, Some("quiet")).await.status(), StatusCode::OK);
+        assert_eq!(send(&app, None).await.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn master_identity_is_limited_too() {
+        let app = limited_router(limiter(60, 1));
+        assert_eq!(send(&app, None).await.status(), StatusCode::OK);
+        assert_eq!(
+            send(&app, None).await.status(),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+    }
 }
@@ -8,7 +8,9 @@
 use std::sync::Arc;
 
 use axum::{
-    Router, middleware as axum_middleware,
+    Router,
+    extract::DefaultBodyLimit,
+    middleware as axum_middleware,
     routing::{delete, get, post},
 };
 use blufio_core::BlufioError;
@@ -18,15 +20,18 @@ use blufio_core::types::InboundMessage;
 use blufio_skill::ToolRegistry;
 use dashmap::DashMap;
 use tokio::sync::{RwLock, mpsc, oneshot};
-use tower_http::cors::CorsLayer;
+use tokio_util::sync::CancellationToken;
 
 use crate::api_keys;
 use crate::auth::{AuthConfig, auth_middleware};
 use crate::batch;
 use crate::classify;
+use crate::cors::{CorsConfig, build_gateway_cors};
 use crate::handlers;
+use crate::metrics_access::{MetricsAccess, metrics_routes};
 use crate::openai_compat;
 use crate::rate_limit::rate_limit_middleware;
+use crate::sse;
 use crate::webhooks;
 use crate::ws;
 
@@ -72,6 +77,48 @@ pub struct GatewayState {
     pub degradation_manager: Option<Arc<blufio_resilience::DegradationManager>>,
     /// Circuit breaker registry for per-dependency state visibility (CB-04).
     pub circuit_breaker_registry: Option<Arc<blufio_resilience::CircuitBreakerRegistry>>,
+    /// Per-identity token buckets; `None` disables the identity limit.
+    pub identity_limiter: Option<Arc<crate::rate_limit::IdentityRateLimiter>>,
+    /// How long handlers wait for room in the inbound queue before giving
+    /// up with 503.
+    pub inbound_send_timeout: std::time::Duration,
+    /// WebSocket ping interval, pong deadline and idle timeout.
+    pub ws_keepalive: ws::WsKeepalive,
+}
+
+#[cfg(test)]
+impl GatewayState {
+    /// State for tests: no auth, no optional adapters, default timeouts.
+    /// Override fields with struct update syntax.
+    pub(crate) fn for_test(inbound_tx: mpsc::Sender<InboundMessage>) -> Self {
+        Self {
+            inbound_tx,
+            response_map: Arc::new(DashMap::new()),
+            ws_senders: Arc::new(DashMap::new()),
+            auth: AuthConfig {
+                bearer_token: None,
+                keypair_public_key: None,
+                key_store: None,
+            },
+            health: HealthState {
+                start_time: std::time::Instant::now(),
+                prometheus_render: None,
+            },
+            storage: None,
+            providers: None,
+            tools: None,
+            api_tools_allowlist: Vec::new(),
+            max_batch_size: 100,
+            webhook_store: None,
+            batch_store: None,
+            event_bus: None,
+            degradation_manager: None,
+            circuit_breaker_registry: None,
+            identity_limiter: None,
+            inbound_send_timeout: std::time::Duration::from_secs(5),
+            ws_keepalive: ws::WsKeepalive::default(),
+        }
+    }
 }
 
 /// Gateway server configuration (mirrors GatewayConfig from blufio-config).
@@ -85,12 +132,19 @@ pub struct ServerConfig {
     pub bearer_token: Option<String>,
     /// Whether to enable Swagger UI at /docs (requires `swagger-ui` feature).
     pub swagger_ui_enabled: bool,
+    /// Optional auth and IP allowlist for GET /metrics.
+    pub metrics_access: MetricsAccess,
+    /// Maximum request body size in bytes; larger bodies get 413.
+    pub max_body_bytes: usize,
+    /// Cross-origin policy for non-MCP routes.
+    pub cors: CorsConfig,
 }
 
 /// Start the gateway HTTP/WebSocket server.
 ///
 /// Binds to the configured host:port and serves routes:
 /// - POST /v1/messages (with auth)
+/// - POST /v1/chat/stream (with auth, Server-Sent Events)
 /// - GET /v1/sessions (with auth)
 /// - GET /v1/health (with auth)
 /// - POST /v1/api-keys, GET /v1/api-keys, DELETE /v1/api-keys/:id (API-11 through API-14)
@@ -99,28 +153,102 @@ pub struct ServerConfig {
 ///
 /// When an MCP router is provided, it is nested at `/mcp` with its own
 /// restricted CORS and auth layers (applied internally by the MCP router).
-/// The permissive CORS layer only applies to non-MCP routes.
+/// The configured gateway CORS policy applies to all other routes.
+///
+/// Runs until `shutdown` is cancelled, then drains in-flight requests (see
+/// [`serve`]).
 pub async fn start_server(
     config: &ServerConfig,
     state: GatewayState,
     mcp_router: Option<Router>,
     mcp_max_connections: usize,
     extra_public_routes: Option<Router>,
+    shutdown: CancellationToken,
+) -> Result<(), BlufioError> {
+    let app = build_router(
+        config,
+        state,
+        mcp_router,
+        mcp_max_connections,
+        extra_public_routes,
+    );
+
+    let addr = format!("{}:{}", config.host, config.port);
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .map_err(|e| BlufioError::channel_delivery_failed("gateway", e))?;
+
+    tracing::info!("Gateway server listening on {addr}");
+
+    serve(listener, app, shutdown).await
+}
+
+/// Serve `app` on `listener` until `shutdown` is cancelled.
+///
+/// On cancellation the listener is closed, so new connections are refused,
+/// and the call returns once every in-flight request has completed. The
+/// caller bounds that wait (see `GatewayChannel::shutdown`).
+pub async fn serve(
+    listener: tokio::net::TcpListener,
+    app: Router,
+    shutdown: CancellationToken,
 ) -> Result<(), BlufioError> {
+    // Peer addresses are needed by the /metrics IP allowlist.
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown.cancelled_owned())
+    .await
+    .map_err(|e| BlufioError::channel_delivery_failed("gateway", e))?;
+
+    tracing::info!("gateway server stopped");
+    Ok(())
+}
+
+/// Fail every request still waiting for an agent response.
+///
+/// Dropping the responders wakes the waiting handlers, which answer with an
+/// error instead of hanging until their own timeout. WebSocket senders are
+/// dropped too, which ends their forwarding tasks. Returns the number of
+/// HTTP requests failed.
+pub fn fail_pending_responses(
+    response_map: &DashMap<String, oneshot::Sender<String>>,
+    ws_senders: &DashMap<String, mpsc::Sender<String>>,
+) -> usize {
+    let pending = response_map.len();
+    response_map.clear();
+    ws_senders.clear();
+    pending
+}
+
+/// Build the gateway router with all routes and middleware, without binding.
+///
+/// See [`start_server`] for the route list.
+pub fn build_router(
+    config: &ServerConfig,
+    state: GatewayState,
+    mcp_router: Option<Router>,
+    mcp_max_connections: usize,
+    extra_public_routes: Option<Router>,
+) -> Router {
     let auth_state = state.auth.clone();
 
-    // Unauthenticated public routes (health + metrics + OpenAPI spec for systemd and Prometheus).
+    // Unauthenticated public routes (health + OpenAPI spec for systemd).
     let public_routes = Router::new()
         .route("/health", get(handlers::get_public_health))
-        .route("/metrics", get(handlers::get_public_metrics))
         .route("/openapi.json", get(get_openapi_json))
         .with_state(state.clone());
 
+    // Prometheus metrics: public unless auth or an IP allowlist is configured.
+    let metrics = metrics_routes(state.clone(), &config.metrics_access);
+
     // Routes requiring authentication.
     // Layer order matters: axum applies layers bottom-up, so rate_limit runs
     // AFTER auth (auth inserts AuthContext, rate_limit reads it).
     let api_routes = Router::new()
         .route("/v1/messages", post(handlers::post_messages))
+        .route("/v1/chat/stream", post(sse::post_chat_stream))
         .route("/v1/sessions", get(handlers::get_sessions))
         .route("/v1/health", get(handlers::get_health))
         // OpenAI-compatible API endpoints (API-01 through API-10).
@@ -187,6 +315,7 @@ pub async fn start_server(
 
     let mut app = Router::new()
         .merge(public_routes)
+        .merge(metrics)
         .merge(api_routes)
         .merge(ws_routes);
 
@@ -197,7 +326,7 @@ pub async fn start_server(
 
     // Mount MCP Streamable HTTP routes at /mcp (if enabled).
     // The MCP router includes its own restricted CORS and auth layers,
-    // so it must be nested BEFORE the permissive CORS layer.
+    // so it must be nested BEFORE the gateway CORS layer.
     // Connection limit (INTG-05) enforces max concurrent MCP connections.
     if let Some(mcp) = mcp_router {
         let limited_mcp = mcp.layer(tower::limit::ConcurrencyLimitLayer::new(
@@ -227,22 +356,13 @@ pub async fn start_server(
     // When otel is not compiled, this is a no-op passthrough.
     let app = app.layer(axum_middleware::from_fn(trace_id_header_middleware));
 
-    // Permissive CORS for non-MCP routes.
-    // NOTE: The MCP router already has its own restricted CORS layer applied internally.
-    let app = app.layer(CorsLayer::permissive());
+    // Request body cap, enforced by the body extractors (413 when exceeded).
+    let app = app.layer(DefaultBodyLimit::max(config.max_body_bytes));
 
-    let addr = format!("{}:{}", config.host, config.port);
-    let listener = tokio::net::TcpListener::bind(&addr)
-        .await
-        .map_err(|e| BlufioError::channel_delivery_failed("gateway", e))?;
-
-    tracing::info!("Gateway server listening on {addr}");
-
-    axum::serve(listener, app)
-        .await
-        .map_err(|e| BlufioError::channel_delivery_failed("gateway", e))?;
-
-    Ok(())
+    // Configured CORS (deny-all by default). Outermost, so preflight OPTIONS
+    // requests are answered here and never reach the auth middleware.
+    // NOTE: The MCP router already has its own restricted CORS layer applied internally.
+    app.layer(build_gateway_cors(&config.cors))
 }
 
 /// GET /openapi.json -- Serve the OpenAPI 3.1 specification.
@@ -300,34 +420,306 @@ async fn trace_id_header_middleware(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
 
-    #[test]
-    fn gateway_state_is_clone() {
-        let (tx, _rx) = mpsc::channel(1);
-        let state = GatewayState {
-            inbound_tx: tx,
-            response_map: Arc::new(DashMap::new()),
-            ws_senders: Arc::new(DashMap::new()),
+    fn test_state(
+        inbound_tx: mpsc::Sender<InboundMessage>,
+        inbound_send_timeout: std::time::Duration,
+    ) -> GatewayState {
+        GatewayState {
             auth: AuthConfig {
-                bearer_token: None,
+                bearer_token: Some("secret".to_string()),
                 keypair_public_key: None,
                 key_store: None,
             },
-            health: HealthState {
-                start_time: std::time::Instant::now(),
-                prometheus_render: None,
+            inbound_send_timeout,
+            ..GatewayState::for_test(inbound_tx)
+        }
+    }
+
+    fn test_server_config(max_body_bytes: usize) -> ServerConfig {
+        ServerConfig {
+            host: "127.0.0.1".to_string(),
+            port: 0,
+            bearer_token: Some("secret".to_string()),
+            swagger_ui_enabled: false,
+            metrics_access: MetricsAccess::default(),
+            max_body_bytes,
+            cors: CorsConfig::default(),
+        }
+    }
+
+    fn post_message(body: String) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/v1/messages")
+            .header("authorization", "Bearer secret")
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn oversized_body_is_rejected_with_413() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let state = test_state(tx, std::time::Duration::from_secs(5));
+        let app = build_router(&test_server_config(1024), state, None, 10, None);
+
+        let content = "x".repeat(4096);
+        let response = app
+            .oneshot(post_message(
+                serde_json::json!({ "content": content }).to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(
+            rx.try_recv().is_err(),
+            "oversized message must not be queued"
+        );
+    }
+
+    #[tokio::test]
+    async fn full_inbound_queue_returns_503() {
+        let (tx, mut rx) = mpsc::channel(1);
+        // Occupy the only slot so the next send has to wait.
+        tx.send(InboundMessage {
+            id: "queued".to_string(),
+            session_id: None,
+            channel: "api".to_string(),
+            sender_id: "api-user".to_string(),
+            content: blufio_core::types::MessageContent::Text("first".to_string()),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            metadata: None,
+        })
+        .await
+        .unwrap();
+        let state = test_state(tx, std::time::Duration::from_millis(50));
+        let response_map = Arc::clone(&state.response_map);
+        let app = build_router(&test_server_config(2 * 1024 * 1024), state, None, 10, None);
+
+        let response = app
+            .oneshot(post_message(r#"{"content": "second"}"#.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "inbound channel full");
+
+        // The rejected request leaves nothing behind.
+        assert!(response_map.is_empty());
+        assert_eq!(rx.recv().await.unwrap().id, "queued");
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn graceful_shutdown_finishes_in_flight_and_refuses_new() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let state = test_state(tx, std::time::Duration::from_secs(5));
+        let response_map = Arc::clone(&state.response_map);
+        let app = build_router(&test_server_config(2 * 1024 * 1024), state, None, 10, None);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let shutdown = CancellationToken::new();
+        let server = tokio::spawn(serve(listener, app, shutdown.clone()));
+
+        // Start a request and wait until it is parked on the agent loop.
+        let in_flight = tokio::spawn(async move {
+            reqwest::Client::new()
+                .post(format!("http://{addr}/v1/messages"))
+                .bearer_auth("secret")
+                .json(&serde_json::json!({"content": "hello"}))
+                .send()
+                .await
+        });
+        let inbound = rx.recv().await.unwrap();
+
+        shutdown.cancel();
+
+        // The listener closes, so new connections are refused.
+        let mut refused = false;
+        for _ in 0..100 {
+            if tokio::net::TcpStream::connect(addr).await.is_err() {
+                refused = true;
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(refused, "new connections should be refused during shutdown");
+
+        // The in-flight request still gets its response.
+        let (_, responder) = response_map.remove(&inbound.id).unwrap();
+        responder.send("done".to_string()).unwrap();
+        let response = in_flight.await.unwrap().unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["content"], "done");
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), server)
+            .await
+            .expect("server should stop once drained")
+            .unwrap()
+            .unwrap();
+    }
+
+    fn cors_config(origins: &[&str]) -> ServerConfig {
+        ServerConfig {
+            cors: CorsConfig {
+                allowed_origins: origins.iter().map(|o| o.to_string()).collect(),
+                ..CorsConfig::default()
             },
-            storage: None,
-            providers: None,
-            tools: None,
-            api_tools_allowlist: vec![],
-            max_batch_size: 100,
-            webhook_store: None,
-            batch_store: None,
-            event_bus: None,
-            degradation_manager: None,
-            circuit_breaker_registry: None,
+            ..test_server_config(2 * 1024 * 1024)
+        }
+    }
+
+    fn cors_app(config: &ServerConfig) -> Router {
+        let (tx, _rx) = mpsc::channel(1);
+        build_router(
+            config,
+            test_state(tx, std::time::Duration::from_secs(5)),
+            None,
+            10,
+            None,
+        )
+    }
+
+    fn preflight(origin: &str) -> Request<Body> {
+        Request::builder()
+            .method("OPTIONS")
+            .uri("/v1/sessions")
+            .header("origin", origin)
+            .header("access-control-request-method", "GET")
+            .header("access-control-request-headers", "authorization")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn cors_preflight_bypasses_auth_for_allowed_origin() {
+        let app = cors_app(&cors_config(&["https://app.example.com"]));
+        let response = app
+            .oneshot(preflight("https://app.example.com"))
+            .await
+            .unwrap();
+        // No bearer token, yet the preflight is answered by the CORS layer.
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(
+            headers["access-control-allow-origin"],
+            "https://app.example.com"
+        );
+        assert!(
+            headers["access-control-allow-methods"]
+                .to_str()
+                .unwrap()
+                .contains("GET")
+        );
+        // Keypair-signature auth headers are allowed by default.
+        let allowed_headers = headers["access-control-allow-headers"].to_str().unwrap();
+        assert!(allowed_headers.contains("x-signature"));
+        assert!(allowed_headers.contains("x-timestamp"));
+    }
+
+    #[tokio::test]
+    async fn cors_denies_unlisted_origin() {
+        let app = cors_app(&cors_config(&["https://app.example.com"]));
+        let response = app
+            .clone()
+            .oneshot(preflight("https://evil.example.com"))
+            .await
+            .unwrap();
+        assert!(
+            !response
+                .headers()
+                .contains_key("access-control-allow-origin")
+        );
+
+        let request = Request::builder()
+            .uri("/health")
+            .header("origin", "https://evil.example.com")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert!(
+            !response
+                .headers()
+                .contains_key("access-control-allow-origin")
+        );
+    }
+
+    #[tokio::test]
+    async fn cors_default_denies_all_origins() {
+        let app = cors_app(&test_server_config(2 * 1024 * 1024));
+        let response = app
+            .oneshot(preflight("https://app.example.com"))
+            .await
+            .unwrap();
+        assert!(
+            !response
+                .headers()
+                .contains_key("access-control-allow-origin")
+        );
+    }
+
+    #[tokio::test]
+    async fn cors_allowed_origin_still_requires_auth() {
+        let app = cors_app(&cors_config(&["https://app.example.com"]));
+        let request = |token: Option<&str>| {
+            let mut builder = Request::builder()
+                .uri("/v1/health")
+                .header("origin", "https://app.example.com");
+            if let Some(token) = token {
+                builder = builder.header("authorization", format!("Bearer {token}"));
+            }
+            builder.body(Body::empty()).unwrap()
         };
+
+        let response = app.clone().oneshot(request(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app.oneshot(request(Some("secret"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()["access-control-allow-origin"],
+            "https://app.example.com"
+        );
+    }
+
+    #[tokio::test]
+    async fn cors_wildcard_origin_allows_any() {
+        let app = cors_app(&cors_config(&["*"]));
+        let response = app
+            .oneshot(preflight("https://anything.test"))
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["access-control-allow-origin"], "*");
+    }
+
+    #[test]
+    fn fail_pending_responses_drops_responders() {
+        let response_map = DashMap::new();
+        let ws_senders = DashMap::new();
+        let (tx, mut rx) = oneshot::channel::<String>();
+        response_map.insert("req-1".to_string(), tx);
+        let (ws_tx, _ws_rx) = mpsc::channel::<String>(1);
+        ws_senders.insert("ws-1".to_string(), ws_tx);
+
+        assert_eq!(fail_pending_responses(&response_map, &ws_senders), 1);
+        assert!(response_map.is_empty());
+        assert!(ws_senders.is_empty());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn gateway_state_is_clone() {
+        let (tx, _rx) = mpsc::channel(1);
+        let state = GatewayState::for_test(tx);
         let _cloned = state.clone();
     }
 
@@ -338,6 +730,9 @@ mod tests {
             port: 3000,
             bearer_token: None,
             swagger_ui_enabled: false,
+            metrics_access: MetricsAccess::default(),
+            max_body_bytes: 2 * 1024 * 1024,
+            cors: CorsConfig::default(),
         };
         let debug = format!("{config:?}");
         assert!(debug.contains("127.0.0.1"));
@@ -1,41 +1,259 @@
 // SPDX-FileCopyrightText: 2026 Blufio Contributors
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-//! Server-Sent Events (SSE) streaming for POST /v1/messages.
+//! Server-Sent Events (SSE) streaming.
 //!
-//! When clients send Accept: text/event-stream, the gateway returns an SSE
-//! stream with partial content deltas as the agent generates them.
+//! Two entry points:
 //!
-//! SSE event format:
-//! ```text
-//! event: text_delta
-//! data: {"text": "partial content here"}
+//! - `POST /v1/chat/stream` streams the reply as the provider generates it,
+//!   using Anthropic-style event names:
 //!
-//! event: message_stop
-//! data: {"content": "full content", "session_id": "..."}
-//! ```
+//!   ```text
+//!   id: <request_id>:1
+//!   event: message_start
+//!   data: {"type": "message_start", "message": {"id": "...", "session_id": "..."}}
 //!
-//! Note: True streaming requires integration with the agent loop's streaming
-//! response pipeline (Plan 03). For now, this returns the complete response
-//! as a single text_delta + message_stop pair.
+//!   id: <request_id>:2
+//!   event: content_block_delta
+//!   data: {"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Hel"}}
+//!
+//!   id: <request_id>:3
+//!   event: message_stop
+//!   data: {"type": "message_stop", "content": "Hello", "session_id": "..."}
+//!
+//!   id: <request_id>:4
+//!   event: done
+//!   data: {"type": "done"}
+//!   ```
+//!
+//!   Deltas arrive through the same `send_partial` path as WebSocket clients:
+//!   the stream registers a sender in `ws_senders` and tags the inbound
+//!   message with its `ws_id`. Event IDs are `<request_id>:<seq>`, unique
+//!   across streams, so a reconnecting client's `Last-Event-ID` never
+//!   matches another stream. A stream cannot be resumed; reconnecting
+//!   clients must send the message again.
+//!
+//! - `POST /v1/messages` with `Accept: text/event-stream` returns the complete
+//!   response as a single `text_delta` + `message_stop` pair.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
 
-use axum::response::sse::{Event, Sse};
-use futures::stream::{self, Stream};
-use tokio::sync::oneshot;
+use axum::Json;
+use axum::extract::State;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use dashmap::DashMap;
+use futures::{StreamExt, stream};
+use tokio::sync::{mpsc, oneshot};
 
 use blufio_core::types::{InboundMessage, MessageContent};
 
-use crate::handlers::MessageRequest;
+use crate::handlers::{MessageRequest, enqueue_inbound};
 use crate::server::GatewayState;
+use crate::ws::message_types;
+
+/// How long a chat stream waits for the next frame from the agent.
+const STREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// SSE event names emitted by `POST /v1/chat/stream`.
+pub mod event_types {
+    /// First event; carries the request ID.
+    pub const MESSAGE_START: &str = "message_start";
+    /// Partial text.
+    pub const CONTENT_BLOCK_DELTA: &str = "content_block_delta";
+    /// Complete response text.
+    pub const MESSAGE_STOP: &str = "message_stop";
+    /// The request failed; followed by `done`.
+    pub const ERROR: &str = "error";
+    /// Always the last event.
+    pub const DONE: &str = "done";
+}
+
+/// POST /v1/chat/stream
+///
+/// Sends a message to the agent and streams the reply as Server-Sent Events:
+/// message_start, content_block_delta per partial, message_stop, then done.
+#[utoipa::path(
+    post,
+    path = "/v1/chat/stream",
+    tag = "Messages",
+    request_body = MessageRequest,
+    responses(
+        (status = 200, description = "text/event-stream of message_start, content_block_delta, message_stop and done events"),
+        (status = 401, description = "Unauthorized"),
+        (status = 413, description = "Request body too large"),
+        (status = 503, description = "Agent loop unavailable or inbound queue full", body = crate::handlers::ErrorResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn post_chat_stream(
+    State(state): State<GatewayState>,
+    Json(body): Json<MessageRequest>,
+) -> Response {
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let stream_id = format!("sse-{request_id}");
+
+    // Register before queueing so no partial can arrive unrouted.
+    let (tx, rx) = mpsc::channel::<String>(64);
+    state.ws_senders.insert(stream_id.clone(), tx);
+    let registration = Registration {
+        senders: Arc::clone(&state.ws_senders),
+        stream_id: stream_id.clone(),
+    };
+
+    let inbound = InboundMessage {
+        id: request_id.clone(),
+        session_id: body.session_id.clone(),
+        channel: "api".to_string(),
+        sender_id: body.sender_id.unwrap_or_else(|| "api-user".to_string()),
+        content: MessageContent::Text(body.content),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        metadata: Some(
+            serde_json::json!({
+                "request_id": request_id,
+                "channel": "api",
+                "ws_id": stream_id,
+            })
+            .to_string(),
+        ),
+    };
+
+    if let Err(e) = enqueue_inbound(&state, inbound).await {
+        return e.into_response();
+    }
+
+    let chat = ChatStream {
+        rx,
+        _registration: registration,
+        request_id,
+        session_id: body.session_id,
+        seq: 0,
+        started: false,
+        finished: false,
+    };
+    let events = stream::unfold(chat, |mut chat| async move {
+        let events = chat.next_events().await?;
+        Some((events, chat))
+    })
+    .flat_map(|events| stream::iter(events.into_iter().map(Ok::<_, Infallible>)));
+
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Removes a stream's sender from `ws_senders` when the stream is dropped,
+/// including when the client disconnects mid-stream.
+struct Registration {
+    senders: Arc<DashMap<String, mpsc::Sender<String>>>,
+    stream_id: String,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.senders.remove(&self.stream_id);
+    }
+}
+
+/// Translates agent frames (WebSocket JSON) into SSE events.
+struct ChatStream {
+    rx: mpsc::Receiver<String>,
+    _registration: Registration,
+    request_id: String,
+    session_id: Option<String>,
+    seq: u64,
+    started: bool,
+    finished: bool,
+}
+
+impl ChatStream {
+    /// Events for the next step, or `None` once `done` has been sent.
+    async fn next_events(&mut self) -> Option<Vec<Event>> {
+        if self.finished {
+            return None;
+        }
+        if !self.started {
+            self.started = true;
+            let start = serde_json::json!({
+                "type": event_types::MESSAGE_START,
+                "message": {
+                    "id": self.request_id,
+                    "session_id": self.session_id,
+                },
+            });
+            return Some(vec![self.event(event_types::MESSAGE_START, start)]);
+        }
+        match tokio::time::timeout(STREAM_IDLE_TIMEOUT, self.rx.recv()).await {
+            Ok(Some(frame)) => Some(self.translate(&frame)),
+            Ok(None) => Some(self.fail("response channel closed")),
+            Err(_) => Some(self.fail("response timeout (120s)")),
+        }
+    }
+
+    /// Map one agent frame to SSE events. Frames without an SSE equivalent
+    /// (e.g. typing) produce none.
+    fn translate(&mut self, frame: &str) -> Vec<Event> {
+        let frame: serde_json::Value =
+            serde_json::from_str(frame).unwrap_or(serde_json::Value::Null);
+        match frame.get("type").and_then(|t| t.as_str()) {
+            Some(message_types::DELTA) => {
+                let delta = serde_json::json!({
+                    "type": event_types::CONTENT_BLOCK_DELTA,
+                    "index": 0,
+                    "delta": {"type": "text_delta", "text": frame["content"]},
+                });
+                vec![self.event(event_types::CONTENT_BLOCK_DELTA, delta)]
+            }
+            Some(message_types::MESSAGE_COMPLETE) => {
+                self.finished = true;
+                let session_id = match frame.get("session_id") {
+                    Some(id) if !id.is_null() => id.clone(),
+                    _ => serde_json::json!(self.session_id),
+                };
+                let stop = serde_json::json!({
+                    "type": event_types::MESSAGE_STOP,
+                    "content": frame["content"],
+                    "session_id": session_id,
+                });
+                vec![self.event(event_types::MESSAGE_STOP, stop), self.done()]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn fail(&mut self, message: &str) -> Vec<Event> {
+        self.finished = true;
+        let error = serde_json::json!({
+            "type": event_types::ERROR,
+            "error": {"type": "api_error", "message": message},
+        });
+        vec![self.event(event_types::ERROR, error), self.done()]
+    }
+
+    fn done(&mut self) -> Event {
+        self.event(
+            event_types::DONE,
+            serde_json::json!({"type": event_types::DONE}),
+        )
+    }
+
+    fn event(&mut self, name: &str, data: serde_json::Value) -> Event {
+        self.seq += 1;
+        Event::default()
+            .id(format!("{}:{}", self.request_id, self.seq))
+            .event(name)
+            .data(data.to_string())
+    }
+}
 
 /// Stream a response as Server-Sent Events.
 ///
 /// Creates an inbound message, waits for the agent's response, and returns
-/// it as SSE events (text_delta + message_stop).
-pub async fn stream_messages(
-    state: GatewayState,
-    body: MessageRequest,
-) -> Sse<impl Stream<Item = Result<Event, std::convert::Infallible>>> {
+/// it as SSE events (text_delta + message_stop). Returns 503 before the
+/// stream starts if the message cannot be queued.
+pub async fn stream_messages(state: GatewayState, body: MessageRequest) -> Response {
     let request_id = uuid::Uuid::new_v4().to_string();
     let now = chrono::Utc::now().to_rfc3339();
 
@@ -60,19 +278,17 @@ pub async fn stream_messages(
     let (tx, rx) = oneshot::channel::<String>();
     state.response_map.insert(request_id.clone(), tx);
 
-    // Send to inbound channel.
-    let send_result = state.inbound_tx.send(inbound).await;
+    // Send to inbound channel, waiting for capacity up to the send timeout.
+    if let Err(e) = enqueue_inbound(&state, inbound).await {
+        state.response_map.remove(&request_id);
+        return e.into_response();
+    }
 
     // Build the SSE stream.
     let session_id = body.session_id;
 
-    let events: Vec<Result<Event, std::convert::Infallible>> = if send_result.is_err() {
-        // Channel closed, return error event.
-        vec![Ok(Event::default()
-            .event("error")
-            .data(r#"{"error": "agent loop not accepting messages"}"#))]
-    } else {
-        // Wait for response.
+    // Wait for response.
+    let events: Vec<Result<Event, std::convert::Infallible>> =
         match tokio::time::timeout(std::time::Duration::from_secs(120), rx).await {
             Ok(Ok(content)) => {
                 // Return the complete response as text_delta + message_stop.
@@ -101,19 +317,74 @@ pub async fn stream_messages(
                     .event("error")
                     .data(r#"{"error": "response timeout (120s)"}"#))]
             }
-        }
-    };
+        };
 
-    Sse::new(stream::iter(events))
+    Sse::new(stream::iter(events)).into_response()
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    fn chat_stream() -> (ChatStream, Arc<DashMap<String, mpsc::Sender<String>>>) {
+        let senders = Arc::new(DashMap::new());
+        let (tx, rx) = mpsc::channel(4);
+        senders.insert("sse-req-1".to_string(), tx);
+        let chat = ChatStream {
+            rx,
+            _registration: Registration {
+                senders: Arc::clone(&senders),
+                stream_id: "sse-req-1".to_string(),
+            },
+            request_id: "req-1".to_string(),
+            session_id: None,
+            seq: 0,
+            started: true,
+            finished: false,
+        };
+        (chat, senders)
+    }
+
     #[test]
     fn sse_event_types_defined() {
         // Verify the SSE event type strings match the documented format.
         assert_eq!("text_delta", "text_delta");
         assert_eq!("message_stop", "message_stop");
         assert_eq!("error", "error");
+        assert_eq!(event_types::CONTENT_BLOCK_DELTA, "content_block_delta");
+        assert_eq!(event_types::DONE, "done");
+    }
+
+    #[test]
+    fn translate_maps_frames_and_finishes_on_completion() {
+        let (mut chat, _senders) = chat_stream();
+        assert_eq!(chat.translate(r#"{"type": "typing"}"#).len(), 0);
+        assert_eq!(
+            chat.translate(r#"{"type": "delta", "content": "Hi"}"#)
+                .len(),
+            1
+        );
+        assert!(!chat.finished);
+        let events = chat.translate(r#"{"type": "message_complete", "content": "Hi"}"#);
+        assert_eq!(events.len(), 2);
+        assert!(chat.finished);
+        assert_eq!(chat.seq, 3);
+    }
+
+    #[tokio::test]
+    async fn dropping_stream_unregisters_sender() {
+        let (chat, senders) = chat_stream();
+        assert!(senders.contains_key("sse-req-1"));
+        drop(chat);
+        assert!(senders.is_empty());
+    }
+
+    #[tokio::test]
+    async fn closed_channel_ends_with_error_then_done() {
+        let (mut chat, senders) = chat_stream();
+        senders.clear();
+        let events = chat.next_events().await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(chat.next_events().await.is_none());
     }
 }
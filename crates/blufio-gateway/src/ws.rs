@@ -11,9 +11,13 @@
 //! Server -> Client (JSON):
 //! ```json
 //! {"type": "typing"}
-//! {"type": "text_delta", "text": "partial..."}
+//! {"type": "delta", "content": "partial...", "session_id": "..."}
 //! {"type": "message_complete", "content": "full response", "session_id": "..."}
+//! {"type": "error", "error": "inbound channel full"}
 //! ```
+//!
+//! The server pings idle connections and closes them when pongs stop
+//! arriving or the client stays silent too long (see [`WsKeepalive`]).
 
 use axum::{
     extract::{
@@ -24,10 +28,13 @@ use axum::{
 };
 use futures::{SinkExt, StreamExt};
 use serde::Deserialize;
+use std::time::Duration;
 use tokio::sync::mpsc;
+use tokio::time::Instant;
 
 use blufio_core::types::{InboundMessage, MessageContent};
 
+use crate::handlers::{EnqueueError, enqueue_inbound};
 use crate::server::GatewayState;
 
 /// WebSocket message from client.
@@ -45,84 +52,182 @@ pub async fn ws_handler(ws: WebSocketUpgrade, State(state): State<GatewayState>)
     ws.on_upgrade(|socket| handle_socket(socket, state))
 }
 
+/// Keepalive and idle limits for WebSocket connections.
+///
+/// Proxies drop quiet connections without telling either side, so the server
+/// pings on an interval and closes sockets whose pong doesn't arrive in time.
+/// Separately, a connection with no client messages for `idle_timeout` is
+/// closed. Pongs keep the connection alive but don't count as activity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WsKeepalive {
+    /// Interval between server pings. `None` disables pings.
+    pub ping_interval: Option<Duration>,
+    /// How long to wait for a pong before closing the connection.
+    pub pong_timeout: Duration,
+    /// Close after this long without a client message. `None` disables.
+    pub idle_timeout: Option<Duration>,
+}
+
+impl Default for WsKeepalive {
+    fn default() -> Self {
+        Self {
+            ping_interval: Some(Duration::from_secs(30)),
+            pong_timeout: Duration::from_secs(10),
+            idle_timeout: Some(Duration::from_secs(900)),
+        }
+    }
+}
+
 /// Handle an individual WebSocket connection.
 ///
-/// Spawns two tasks:
-/// 1. Sender task: forwards responses from the agent to the WebSocket client
-/// 2. Receiver loop: reads messages from client and forwards to agent loop
+/// A single loop reads client messages (forwarding them to the agent loop),
+/// writes agent responses, sends keepalive pings, and enforces the pong and
+/// idle deadlines. Every exit path unregisters the connection from
+/// `ws_senders`.
 async fn handle_socket(socket: WebSocket, state: GatewayState) {
     let (mut ws_sender, mut ws_receiver) = socket.split();
     let ws_id = uuid::Uuid::new_v4().to_string();
+    let keepalive = state.ws_keepalive;
 
     // Create mpsc channel for sending responses back to this WebSocket.
     let (tx, mut rx) = mpsc::channel::<String>(64);
     state.ws_senders.insert(ws_id.clone(), tx);
 
-    // Spawn task to forward responses to WebSocket.
-    let sender_task = tokio::spawn(async move {
-        while let Some(msg) = rx.recv().await {
-            if ws_sender.send(Message::Text(msg.into())).await.is_err() {
-                break;
-            }
-        }
-    });
-
-    // Read messages from WebSocket client.
-    while let Some(Ok(msg)) = ws_receiver.next().await {
-        match msg {
-            Message::Text(text) => {
-                let text_str: &str = &text;
-                let incoming: WsIncoming = match serde_json::from_str(text_str) {
-                    Ok(v) => v,
-                    Err(e) => {
-                        tracing::warn!("invalid WebSocket message: {e}");
-                        continue;
-                    }
-                };
+    // The first ping goes out one interval after connect.
+    let ping_period = keepalive.ping_interval.unwrap_or(Duration::MAX);
+    let mut ping = tokio::time::interval_at(
+        Instant::now()
+            .checked_add(ping_period)
+            .unwrap_or_else(far_future),
+        ping_period,
+    );
+    let mut pong_deadline: Option<Instant> = None;
+    let mut last_activity = Instant::now();
 
-                let request_id = uuid::Uuid::new_v4().to_string();
-                let now = chrono::Utc::now().to_rfc3339();
-
-                let inbound = InboundMessage {
-                    id: request_id.clone(),
-                    session_id: incoming.session_id.clone(),
-                    channel: "ws".to_string(),
-                    sender_id: ws_id.clone(),
-                    content: MessageContent::Text(incoming.content),
-                    timestamp: now,
-                    metadata: Some(
-                        serde_json::json!({
-                            "request_id": request_id,
-                            "channel": "ws",
-                            "ws_id": ws_id
-                        })
-                        .to_string(),
-                    ),
-                };
+    loop {
+        let idle_deadline = keepalive
+            .idle_timeout
+            .and_then(|timeout| last_activity.checked_add(timeout));
 
-                if state.inbound_tx.send(inbound).await.is_err() {
-                    tracing::error!("failed to send WebSocket message to agent loop");
+        tokio::select! {
+            incoming = ws_receiver.next() => {
+                let msg = match incoming {
+                    Some(Ok(msg)) => msg,
+                    _ => break,
+                };
+                match msg {
+                    Message::Text(text) => {
+                        last_activity = Instant::now();
+                        match forward_text(&state, &ws_id, &text).await {
+                            Ok(()) => {}
+                            Err(EnqueueError::Full) => {
+                                // Tell the client and keep the socket open; it may retry.
+                                let error = serde_json::json!({
+                                    "type": message_types::ERROR,
+                                    "error": EnqueueError::Full.message(),
+                                });
+                                let frame = Message::Text(error.to_string().into());
+                                if ws_sender.send(frame).await.is_err() {
+                                    break;
+                                }
+                            }
+                            Err(EnqueueError::Closed) => {
+                                tracing::error!("failed to send WebSocket message to agent loop");
+                                break;
+                            }
+                        }
+                    }
+                    Message::Pong(_) => pong_deadline = None,
+                    Message::Close(_) => break,
+                    _ => {} // Ignore binary; client pings are answered by the tungstenite layer
+                }
+            }
+            outgoing = rx.recv() => {
+                // `None` means the sender was unregistered (e.g. gateway shutdown).
+                let Some(msg) = outgoing else { break };
+                if ws_sender.send(Message::Text(msg.into())).await.is_err() {
                     break;
                 }
             }
-            Message::Close(_) => break,
-            _ => {} // Ignore binary, ping (handled by tungstenite layer)
+            _ = ping.tick(), if keepalive.ping_interval.is_some() => {
+                if pong_deadline.is_none() {
+                    pong_deadline = Some(Instant::now() + keepalive.pong_timeout);
+                }
+                if ws_sender.send(Message::Ping(Default::default())).await.is_err() {
+                    break;
+                }
+            }
+            _ = sleep_until_opt(pong_deadline), if pong_deadline.is_some() => {
+                tracing::debug!(ws_id = %ws_id, "WebSocket pong timeout; closing");
+                break;
+            }
+            _ = sleep_until_opt(idle_deadline), if idle_deadline.is_some() => {
+                tracing::debug!(ws_id = %ws_id, "WebSocket idle timeout; closing");
+                let _ = ws_sender.send(Message::Close(None)).await;
+                break;
+            }
         }
     }
 
     // Cleanup.
     state.ws_senders.remove(&ws_id);
-    sender_task.abort();
+}
+
+/// Forward one client text frame to the agent loop.
+///
+/// Malformed frames are logged and dropped.
+async fn forward_text(state: &GatewayState, ws_id: &str, text: &str) -> Result<(), EnqueueError> {
+    let incoming: WsIncoming = match serde_json::from_str(text) {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::warn!("invalid WebSocket message: {e}");
+            return Ok(());
+        }
+    };
+
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let inbound = InboundMessage {
+        id: request_id.clone(),
+        session_id: incoming.session_id.clone(),
+        channel: "ws".to_string(),
+        sender_id: ws_id.to_string(),
+        content: MessageContent::Text(incoming.content),
+        timestamp: now,
+        metadata: Some(
+            serde_json::json!({
+                "request_id": request_id,
+                "channel": "ws",
+                "ws_id": ws_id
+            })
+            .to_string(),
+        ),
+    };
+
+    enqueue_inbound(state, inbound).await
+}
+
+/// A deadline that never fires in practice, for disabled timers.
+fn far_future() -> Instant {
+    Instant::now() + Duration::from_secs(86_400 * 365)
+}
+
+/// Sleep until `deadline`; callers guard the branch with `is_some()`.
+async fn sleep_until_opt(deadline: Option<Instant>) {
+    tokio::time::sleep_until(deadline.unwrap_or_else(far_future)).await;
 }
 
 /// WebSocket message type constants for server -> client messages.
 pub mod message_types {
     /// Typing indicator.
     pub const TYPING: &str = "typing";
-    /// Partial text content.
-    pub const TEXT_DELTA: &str = "text_delta";
+    /// Partial text content, streamed before the complete message.
+    pub const DELTA: &str = "delta";
     /// Complete message.
     pub const MESSAGE_COMPLETE: &str = "message_complete";
+    /// Request could not be processed.
+    pub const ERROR: &str = "error";
 }
 
 #[cfg(test)]
@@ -148,7 +253,8 @@ mod tests {
     #[test]
     fn message_type_constants() {
         assert_eq!(message_types::TYPING, "typing");
-        assert_eq!(message_types::TEXT_DELTA, "text_delta");
+        assert_eq!(message_types::DELTA, "delta");
         assert_eq!(message_types::MESSAGE_COMPLETE, "message_complete");
+        assert_eq!(message_types::ERROR, "error");
     }
 }
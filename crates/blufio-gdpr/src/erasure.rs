@@ -112,6 +112,20 @@ pub async fn execute_erasure(
             )? as u64
         };
 
+        // b3. Delete merged source references to the erased sessions that remain on
+        // memories from other sessions. Only a missing table is tolerated; any
+        // other failure aborts the erasure.
+        if !session_ids.is_empty() {
+            match tx.execute(
+                &format!("DELETE FROM memory_sources WHERE session_id IN ({placeholders})"),
+                rusqlite::params_from_iter(session_ids.iter()),
+            ) {
+                Ok(_) => {}
+                Err(e) if is_missing_table(&e) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
         // c. DELETE compaction archives (LIKE-based JSON matching)
         let mut archives_deleted: u64 = 0;
         for sid in &session_ids {
@@ -205,6 +219,11 @@ pub async fn cleanup_memory_index(
     .map_err(|e| GdprError::ErasureFailed(format!("FTS5 cleanup failed: {e}")))
 }
 
+/// Whether `e` is SQLite's "no such table" error.
+fn is_missing_table(e: &rusqlite::Error) -> bool {
+    matches!(e, rusqlite::Error::SqliteFailure(_, Some(msg)) if msg.starts_with("no such table"))
+}
+
 /// A lightweight session record used during erasure operations.
 ///
 /// Contains only the fields needed for erasure logic (identifying sessions,
@@ -513,4 +532,18 @@ mod tests {
         let result = cleanup_memory_index(&conn, &["sess-1".to_string()]).await;
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn only_missing_tables_are_tolerated() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        let missing = conn.execute("DELETE FROM memory_sources", []).unwrap_err();
+        assert!(is_missing_table(&missing));
+
+        conn.execute_batch("CREATE TABLE memory_sources (session_id TEXT)")
+            .unwrap();
+        let other = conn
+            .execute("DELETE FROM memory_sources WHERE no_such_column = 1", [])
+            .unwrap_err();
+        assert!(!is_missing_table(&other));
+    }
 }
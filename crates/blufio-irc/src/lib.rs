@@ -156,17 +156,19 @@ impl ChannelAdapter for IrcChannel {
             .await
             .map_err(|e| BlufioError::channel_delivery_failed("irc", e))?;
 
-        // SASL auth: request capability before identify.
+        // SASL auth: request capability before registering. Registration must
+        // not end capability negotiation; the stream sends CAP END afterwards.
         if self.config.auth_method.as_deref() == Some("sasl") && self.config.password.is_some() {
             sasl::request_sasl_cap(&client).await?;
             debug!("SASL CAP REQ sent, flow continues in message stream");
+            sasl::register(&client, &nickname).await?;
+        } else {
+            // Identify (complete connection registration).
+            client
+                .identify()
+                .map_err(|e| BlufioError::channel_delivery_failed("irc", e))?;
         }
 
-        // Identify (complete connection registration).
-        client
-            .identify()
-            .map_err(|e| BlufioError::channel_delivery_failed("irc", e))?;
-
         // Get the message stream BEFORE wrapping in Arc (requires &mut self).
         let stream = client
             .stream()
@@ -228,6 +230,17 @@ impl ChannelAdapter for IrcChannel {
                                     }
                                     continue;
                                 }
+                                Command::CAP(_, sub, _, _)
+                                    if format!("{sub:?}").contains("NAK") =>
+                                {
+                                    // Registration waits on CAP END, so end
+                                    // negotiation and continue unauthenticated.
+                                    warn!("IRC server rejected the SASL capability");
+                                    if let Err(e) = sasl::finish_cap(&client).await {
+                                        warn!(error = %e, "failed to send CAP END after CAP NAK");
+                                    }
+                                    continue;
+                                }
                                 Command::Raw(cmd, _) if cmd == "AUTHENTICATE" => {
                                     // Server sent AUTHENTICATE +, send credentials.
                                     if let Some(ref pw) = password {
@@ -28,6 +28,25 @@ pub async fn request_sasl_cap(client: &irc::client::Client) -> Result<(), Blufio
         .map_err(|e| BlufioError::channel_delivery_failed("irc", e))
 }
 
+/// Register the connection without ending capability negotiation.
+///
+/// `Client::identify` sends `CAP END` ahead of `NICK`/`USER`, which closes
+/// negotiation before the server can ACK `sasl`. With SASL, `CAP END` is sent
+/// by [`finish_cap`] once authentication succeeds or fails instead.
+pub async fn register(client: &irc::client::Client, nickname: &str) -> Result<(), BlufioError> {
+    debug!("registering without CAP END for SASL");
+    client
+        .send(Command::NICK(nickname.to_string()))
+        .and_then(|()| {
+            client.send(Command::USER(
+                nickname.to_string(),
+                "0".to_string(),
+                nickname.to_string(),
+            ))
+        })
+        .map_err(|e| BlufioError::channel_delivery_failed("irc", e))
+}
+
 /// Encode SASL PLAIN credentials.
 ///
 /// The SASL PLAIN format is: `\0{authcid}\0{password}` encoded as base64.
@@ -471,18 +471,16 @@ impl ServerHandler for BlufioMcpHandler {
 /// Returns `Ok(())` if valid, or `Err(message)` with a human-readable
 /// description of the first validation error.
 fn validate_input(schema: &serde_json::Value, input: &serde_json::Value) -> Result<(), String> {
-    let validator =
-        jsonschema::validator_for(schema).map_err(|e| format!("invalid schema: {e}"))?;
-    let mut errors = validator.iter_errors(input);
-    if let Some(first_error) = errors.next() {
-        let remaining = errors.count();
-        if remaining > 0 {
-            Err(format!("{} (and {} more errors)", first_error, remaining))
-        } else {
-            Err(first_error.to_string())
-        }
-    } else {
-        Ok(())
+    let violations = blufio_skill::tool::schema_violations(schema, input)
+        .map_err(|e| format!("invalid schema: {e}"))?;
+    match violations.split_first() {
+        None => Ok(()),
+        Some((first, [])) => Err(first.message.clone()),
+        Some((first, rest)) => Err(format!(
+            "{} (and {} more errors)",
+            first.message,
+            rest.len()
+        )),
     }
 }
 
@@ -1013,6 +1011,13 @@ mod tests {
         ) -> Result<(), blufio_core::BlufioError> {
             Ok(())
         }
+        async fn update_session_metadata(
+            &self,
+            _id: &str,
+            _metadata: Option<&str>,
+        ) -> Result<(), blufio_core::BlufioError> {
+            Ok(())
+        }
         async fn insert_message(
             &self,
             _message: &blufio_core::types::Message,
@@ -1052,6 +1057,25 @@ mod tests {
         async fn fail(&self, _id: i64) -> Result<(), blufio_core::BlufioError> {
             Ok(())
         }
+        async fn save_tool_loop_state(
+            &self,
+            _session_id: &str,
+            _iteration: u32,
+            _pending_tool_uses: &[blufio_core::types::ToolUseData],
+        ) -> Result<(), blufio_core::BlufioError> {
+            Ok(())
+        }
+        async fn clear_tool_loop_state(
+            &self,
+            _session_id: &str,
+        ) -> Result<(), blufio_core::BlufioError> {
+            Ok(())
+        }
+        async fn list_tool_loop_states(
+            &self,
+        ) -> Result<Vec<blufio_core::types::ToolLoopState>, blufio_core::BlufioError> {
+            Ok(vec![])
+        }
         async fn get_entity_classification(
             &self,
             _entity_type: &str,
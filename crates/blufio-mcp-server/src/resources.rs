@@ -455,7 +455,9 @@ mod tests {
 
     use async_trait::async_trait;
     use blufio_core::error::BlufioError;
-    use blufio_core::types::{HealthStatus, Message, QueueEntry, Session};
+    use blufio_core::types::{
+        HealthStatus, Message, QueueEntry, Session, ToolLoopState, ToolUseData,
+    };
 
     struct MockStorage {
         sessions: Vec<Session>,
@@ -552,6 +554,13 @@ mod tests {
         async fn update_session_state(&self, _id: &str, _state: &str) -> Result<(), BlufioError> {
             Ok(())
         }
+        async fn update_session_metadata(
+            &self,
+            _id: &str,
+            _metadata: Option<&str>,
+        ) -> Result<(), BlufioError> {
+            Ok(())
+        }
         async fn insert_message(&self, _message: &Message) -> Result<(), BlufioError> {
             Ok(())
         }
@@ -586,6 +595,20 @@ mod tests {
         async fn fail(&self, _id: i64) -> Result<(), BlufioError> {
             Ok(())
         }
+        async fn save_tool_loop_state(
+            &self,
+            _session_id: &str,
+            _iteration: u32,
+            _pending_tool_uses: &[ToolUseData],
+        ) -> Result<(), BlufioError> {
+            Ok(())
+        }
+        async fn clear_tool_loop_state(&self, _session_id: &str) -> Result<(), BlufioError> {
+            Ok(())
+        }
+        async fn list_tool_loop_states(&self) -> Result<Vec<ToolLoopState>, BlufioError> {
+            Ok(vec![])
+        }
         async fn get_entity_classification(
             &self,
             _entity_type: &str,
@@ -3,8 +3,8 @@
 
 //! Combined background task for memory eviction and validation.
 //!
-//! Runs eviction sweeps on a configurable interval (default: 5 minutes)
-//! and validation (duplicate/stale/conflict detection) daily.
+//! Runs eviction and archival sweeps on a configurable interval (default:
+//! 5 minutes) and validation (duplicate/stale/conflict detection) daily.
 
 use std::sync::Arc;
 
@@ -20,7 +20,7 @@ use crate::validation;
 
 /// Spawn a combined background task that runs eviction and validation on separate timers.
 ///
-/// - Eviction: runs every `config.eviction_sweep_interval_secs` (default 300s = 5min).
+/// - Eviction and archival: run every `config.eviction_sweep_interval_secs` (default 300s = 5min).
 /// - Validation: runs every 86400 seconds (daily).
 ///
 /// Both timers skip their first immediate tick. The task respects the provided
@@ -50,6 +50,9 @@ pub async fn spawn_background_task(
                 if let Err(e) = eviction::run_eviction_sweep(&store, &config, &event_bus).await {
                     warn!(error = %e, "Eviction sweep failed");
                 }
+                if let Err(e) = eviction::run_archival_sweep(&store, &config).await {
+                    warn!(error = %e, "Archival sweep failed");
+                }
             }
             _ = validation_interval.tick() => {
                 if let Err(e) = validation::run_validation(&store, &config, &event_bus).await {
@@ -85,7 +88,9 @@ mod tests {
                     classification TEXT NOT NULL DEFAULT 'internal',
                     created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
                     updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
-                    deleted_at TEXT
+                    deleted_at TEXT,
+                    last_accessed_at TEXT,
+                    access_count INTEGER NOT NULL DEFAULT 0
                 );
 
                 CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
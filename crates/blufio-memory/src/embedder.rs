@@ -4,9 +4,11 @@
 //! ONNX embedding adapter for local inference using all-MiniLM-L6-v2.
 //!
 //! Produces 384-dimensional embeddings on CPU with zero external API calls.
+//! The memory system accepts any [`EmbeddingAdapter`] in its place;
+//! [`verify_dimensions`] guards the store against vectors of the wrong size.
 
 use std::path::Path;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use ndarray::Array2;
@@ -19,6 +21,8 @@ use blufio_core::traits::EmbeddingAdapter;
 use blufio_core::traits::adapter::PluginAdapter;
 use blufio_core::types::{AdapterType, EmbeddingInput, EmbeddingOutput, HealthStatus};
 
+use crate::store::MemoryStore;
+
 /// Embedding dimensions for all-MiniLM-L6-v2.
 pub const EMBEDDING_DIM: usize = 384;
 
@@ -232,9 +236,243 @@ impl EmbeddingAdapter for OnnxEmbedder {
     }
 }
 
+/// Error for embeddings whose size doesn't match the stored vectors.
+///
+/// Mixing dimensions would make every similarity score meaningless, so it is
+/// reported as a configuration problem rather than silently stored.
+pub fn dimension_mismatch(expected: usize, actual: usize) -> BlufioError {
+    BlufioError::Config(format!(
+        "embedding dimension mismatch: memory store expects {expected}-dim vectors \
+         but the embedder produced {actual}; use an embedding model with {expected} \
+         dimensions or start from an empty memory store"
+    ))
+}
+
+/// Embedding adapter that rejects vectors of an unexpected size.
+///
+/// Wraps the configured embedder so a model swapped behind a remote API
+/// can't write incompatible vectors into the store.
+pub struct DimensionCheckedEmbedder {
+    inner: Arc<dyn EmbeddingAdapter + Send + Sync>,
+    dimensions: usize,
+}
+
+impl DimensionCheckedEmbedder {
+    /// Wraps `inner`, requiring every vector to have `dimensions` entries.
+    pub fn new(inner: Arc<dyn EmbeddingAdapter + Send + Sync>, dimensions: usize) -> Self {
+        Self { inner, dimensions }
+    }
+
+    /// The vector size every embedding must have.
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+}
+
+#[async_trait]
+impl PluginAdapter for DimensionCheckedEmbedder {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn version(&self) -> semver::Version {
+        self.inner.version()
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::Embedding
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus, BlufioError> {
+        self.inner.health_check().await
+    }
+
+    async fn shutdown(&self) -> Result<(), BlufioError> {
+        self.inner.shutdown().await
+    }
+}
+
+#[async_trait]
+impl EmbeddingAdapter for DimensionCheckedEmbedder {
+    async fn embed(&self, input: EmbeddingInput) -> Result<EmbeddingOutput, BlufioError> {
+        let output = self.inner.embed(input).await?;
+        if let Some(bad) = output
+            .embeddings
+            .iter()
+            .find(|v| v.len() != self.dimensions)
+        {
+            return Err(dimension_mismatch(self.dimensions, bad.len()));
+        }
+        Ok(output)
+    }
+}
+
+/// Checks that `embedder` produces vectors matching those already stored.
+///
+/// Embeds a probe text to learn the embedder's dimensionality and compares
+/// it with the vectors in `store` (and the fixed-size vec0 index when
+/// enabled). Returns the embedder wrapped in a [`DimensionCheckedEmbedder`]
+/// so later outputs are held to the same size.
+pub async fn verify_dimensions(
+    embedder: Arc<dyn EmbeddingAdapter + Send + Sync>,
+    store: &MemoryStore,
+) -> Result<Arc<dyn EmbeddingAdapter + Send + Sync>, BlufioError> {
+    let probe = embedder
+        .embed(EmbeddingInput {
+            texts: vec!["dimension probe".to_string()],
+        })
+        .await?;
+    let dimensions = probe
+        .embeddings
+        .first()
+        .map(Vec::len)
+        .ok_or_else(|| BlufioError::Internal("Embedding returned no results".to_string()))?;
+
+    if let Some(stored) = store.embedding_dimensions().await?
+        && stored != dimensions
+    {
+        return Err(dimension_mismatch(stored, dimensions));
+    }
+    // The vec0 virtual table is declared with a fixed vector size.
+    if store.vec0_enabled() && dimensions != EMBEDDING_DIM {
+        return Err(dimension_mismatch(EMBEDDING_DIM, dimensions));
+    }
+
+    Ok(Arc::new(DimensionCheckedEmbedder::new(
+        embedder, dimensions,
+    )))
+}
+
+/// Embedding adapter returning one fixed vector, standing in for a remote API.
+#[cfg(test)]
+pub(crate) struct FixedEmbedder(pub(crate) Vec<f32>);
+
+#[cfg(test)]
+#[async_trait]
+impl PluginAdapter for FixedEmbedder {
+    fn name(&self) -> &str {
+        "fixed-embedder"
+    }
+    fn version(&self) -> semver::Version {
+        semver::Version::new(0, 1, 0)
+    }
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::Embedding
+    }
+    async fn health_check(&self) -> Result<HealthStatus, BlufioError> {
+        Ok(HealthStatus::Healthy)
+    }
+    async fn shutdown(&self) -> Result<(), BlufioError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[async_trait]
+impl EmbeddingAdapter for FixedEmbedder {
+    async fn embed(&self, input: EmbeddingInput) -> Result<EmbeddingOutput, BlufioError> {
+        Ok(EmbeddingOutput {
+            embeddings: vec![self.0.clone(); input.texts.len()],
+            dimensions: self.0.len(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{Memory, MemorySource, MemoryStatus};
+    use blufio_core::classification::DataClassification;
+
+    fn mock(dimensions: usize) -> Arc<dyn EmbeddingAdapter + Send + Sync> {
+        Arc::new(FixedEmbedder(vec![0.5; dimensions]))
+    }
+
+    async fn store_with_memory(dimensions: Option<usize>) -> MemoryStore {
+        let conn = tokio_rusqlite::Connection::open_in_memory().await.unwrap();
+        conn.call(|conn| -> Result<(), rusqlite::Error> {
+            conn.execute_batch(
+                "CREATE TABLE memories (
+                    id TEXT PRIMARY KEY NOT NULL,
+                    content TEXT NOT NULL,
+                    embedding BLOB NOT NULL,
+                    source TEXT NOT NULL,
+                    confidence REAL NOT NULL DEFAULT 0.5,
+                    status TEXT NOT NULL DEFAULT 'active',
+                    superseded_by TEXT,
+                    session_id TEXT,
+                    classification TEXT NOT NULL DEFAULT 'internal',
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL,
+                    deleted_at TEXT,
+                    last_accessed_at TEXT,
+                    access_count INTEGER NOT NULL DEFAULT 0
+                );",
+            )?;
+            Ok(())
+        })
+        .await
+        .unwrap();
+        let store = MemoryStore::new(conn);
+        if let Some(dimensions) = dimensions {
+            store
+                .save(&Memory {
+                    id: "mem-1".to_string(),
+                    content: "user likes tea".to_string(),
+                    embedding: vec![0.1; dimensions],
+                    source: MemorySource::Explicit,
+                    confidence: 0.9,
+                    status: MemoryStatus::Active,
+                    superseded_by: None,
+                    session_id: None,
+                    classification: DataClassification::default(),
+                    created_at: "2026-03-01T00:00:00.000Z".to_string(),
+                    updated_at: "2026-03-01T00:00:00.000Z".to_string(),
+                })
+                .await
+                .unwrap();
+        }
+        store
+    }
+
+    #[tokio::test]
+    async fn verify_dimensions_accepts_matching_or_empty_store() {
+        let empty = store_with_memory(None).await;
+        let checked = verify_dimensions(mock(1536), &empty).await.unwrap();
+        let out = checked
+            .embed(EmbeddingInput {
+                texts: vec!["a".to_string(), "b".to_string()],
+            })
+            .await
+            .unwrap();
+        assert_eq!(out.embeddings.len(), 2);
+
+        let populated = store_with_memory(Some(384)).await;
+        assert_eq!(populated.embedding_dimensions().await.unwrap(), Some(384));
+        assert!(verify_dimensions(mock(384), &populated).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn verify_dimensions_rejects_mismatch_with_stored_vectors() {
+        let store = store_with_memory(Some(384)).await;
+        let err = verify_dimensions(mock(768), &store).await.err().unwrap();
+        assert!(matches!(err, BlufioError::Config(_)));
+        assert!(err.to_string().contains("expects 384-dim"));
+        assert!(err.to_string().contains("produced 768"));
+    }
+
+    #[tokio::test]
+    async fn checked_embedder_rejects_resized_output() {
+        let checked = DimensionCheckedEmbedder::new(mock(8), 4);
+        assert_eq!(checked.dimensions(), 4);
+        let err = checked
+            .embed(EmbeddingInput {
+                texts: vec!["a".to_string()],
+            })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("dimension mismatch"));
+    }
 
     #[test]
     fn l2_normalize_unit_vector() {
@@ -5,7 +5,8 @@
 //!
 //! When the active memory count exceeds `max_entries`, eviction removes
 //! the lowest-scored entries (by composite eviction score) down to 90%
-//! of the configured maximum.
+//! of the configured maximum. The archival sweep separately moves memories
+//! that have not been retrieved for `archive_after_days` to `archived`.
 
 use std::sync::Arc;
 
@@ -78,6 +79,34 @@ pub async fn run_eviction_sweep(
     Ok(())
 }
 
+/// Run an archival sweep: archive active memories not retrieved within
+/// `archive_after_days` (or created before then, if never retrieved).
+///
+/// Does nothing when `archive_after_days` is 0. Returns the archived count.
+pub async fn run_archival_sweep(
+    store: &MemoryStore,
+    config: &MemoryConfig,
+) -> Result<usize, BlufioError> {
+    if config.archive_after_days == 0 {
+        return Ok(0);
+    }
+
+    let cutoff = (chrono::Utc::now() - chrono::Duration::days(config.archive_after_days as i64))
+        .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+        .to_string();
+    let archived = store.archive_idle(&cutoff).await?;
+
+    if !archived.is_empty() {
+        info!(
+            archived = archived.len(),
+            archive_after_days = config.archive_after_days,
+            "Archival sweep complete"
+        );
+    }
+
+    Ok(archived.len())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,7 +131,9 @@ mod tests {
                     classification TEXT NOT NULL DEFAULT 'internal',
                     created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
                     updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
-                    deleted_at TEXT
+                    deleted_at TEXT,
+                    last_accessed_at TEXT,
+                    access_count INTEGER NOT NULL DEFAULT 0
                 );
 
                 CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
@@ -295,4 +326,50 @@ mod tests {
         let count = store.count_active().await.unwrap();
         assert_eq!(count, 9);
     }
+
+    #[tokio::test]
+    async fn archival_sweep_archives_idle_memories() {
+        let conn = setup_test_db().await;
+        let store = MemoryStore::new(conn);
+
+        let stale = make_memory("mem-stale", MemorySource::Extracted, 0.6, 120);
+        let accessed = make_memory("mem-accessed", MemorySource::Extracted, 0.6, 120);
+        let fresh = make_memory("mem-fresh", MemorySource::Extracted, 0.6, 5);
+        let file = make_memory("mem-file", MemorySource::FileWatcher, 0.8, 120);
+        for mem in [&stale, &accessed, &fresh, &file] {
+            store.save(mem).await.unwrap();
+        }
+        store
+            .record_access(&["mem-accessed".to_string()])
+            .await
+            .unwrap();
+
+        let config = MemoryConfig {
+            archive_after_days: 90,
+            ..test_config(100)
+        };
+        let archived = run_archival_sweep(&store, &config).await.unwrap();
+        assert_eq!(archived, 1);
+
+        let stale = store.get_by_id("mem-stale").await.unwrap().unwrap();
+        assert_eq!(stale.status, MemoryStatus::Archived);
+        for id in ["mem-accessed", "mem-fresh", "mem-file"] {
+            let mem = store.get_by_id(id).await.unwrap().unwrap();
+            assert_eq!(mem.status, MemoryStatus::Active, "{id} should stay active");
+        }
+        assert_eq!(store.count_active().await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn archival_sweep_disabled_by_default() {
+        let conn = setup_test_db().await;
+        let store = MemoryStore::new(conn);
+
+        let mem = make_memory("mem-old", MemorySource::Extracted, 0.6, 3650);
+        store.save(&mem).await.unwrap();
+
+        let archived = run_archival_sweep(&store, &test_config(100)).await.unwrap();
+        assert_eq!(archived, 0);
+        assert_eq!(store.count_active().await.unwrap(), 1);
+    }
 }
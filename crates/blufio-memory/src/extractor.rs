@@ -16,13 +16,14 @@ use blufio_core::types::{ContentBlock, EmbeddingInput, ProviderMessage, Provider
 use tracing::{debug, warn};
 use uuid::Uuid;
 
-use crate::embedder::OnnxEmbedder;
 use crate::store::MemoryStore;
 use crate::types::{
-    ExtractedFact, ExtractionResult, Memory, MemorySource, MemoryStatus, cosine_similarity,
+    DedupOutcome, DedupStrategy, ExtractedFact, ExtractionResult, Memory, MemorySource,
+    MemoryStatus, find_most_similar,
 };
 
-/// Similarity threshold above which a new fact is considered a duplicate.
+/// Default similarity threshold at or above which a new fact is considered
+/// a duplicate (`memory.dedup_threshold`).
 const DEDUP_THRESHOLD: f32 = 0.9;
 
 /// Similarity threshold for contradiction detection.
@@ -51,24 +52,36 @@ Output JSON array only, no explanation:"#;
 /// Extracts and stores long-term memories from conversations.
 pub struct MemoryExtractor {
     store: Arc<MemoryStore>,
-    embedder: Arc<OnnxEmbedder>,
+    embedder: Arc<dyn EmbeddingAdapter + Send + Sync>,
     extraction_model: String,
+    dedup_threshold: f32,
+    dedup_strategy: DedupStrategy,
 }
 
 impl MemoryExtractor {
     /// Creates a new memory extractor.
     pub fn new(
         store: Arc<MemoryStore>,
-        embedder: Arc<OnnxEmbedder>,
+        embedder: Arc<dyn EmbeddingAdapter + Send + Sync>,
         extraction_model: String,
     ) -> Self {
         Self {
             store,
             embedder,
             extraction_model,
+            dedup_threshold: DEDUP_THRESHOLD,
+            dedup_strategy: DedupStrategy::Skip,
         }
     }
 
+    /// Sets the near-duplicate threshold and strategy
+    /// (`memory.dedup_threshold`, `memory.dedup_strategy`).
+    pub fn with_dedup(mut self, threshold: f64, strategy: &str) -> Self {
+        self.dedup_threshold = threshold as f32;
+        self.dedup_strategy = DedupStrategy::from_str_value(strategy);
+        self
+    }
+
     /// Returns the extraction model name (for cost tracking).
     pub fn extraction_model(&self) -> &str {
         &self.extraction_model
@@ -91,6 +104,7 @@ impl MemoryExtractor {
         }
 
         let mut saved = 0usize;
+        let mut active_embeddings = self.store.get_active_embeddings().await?;
         for entity in entities {
             // Generate embedding.
             let embed_result = self
@@ -134,10 +148,26 @@ impl MemoryExtractor {
                 updated_at: now,
             };
 
-            match self.store.save(&memory).await {
-                Ok(()) => {
+            match self
+                .store
+                .save_deduplicated(
+                    &memory,
+                    &mut active_embeddings,
+                    self.dedup_threshold,
+                    self.dedup_strategy,
+                )
+                .await
+            {
+                Ok(DedupOutcome::Inserted) => {
                     saved += 1;
                 }
+                Ok(outcome) => {
+                    debug!(
+                        entity = entity.as_str(),
+                        ?outcome,
+                        "extracted entity is a duplicate"
+                    );
+                }
                 Err(e) => {
                     warn!(entity = entity.as_str(), error = %e, "failed to save extracted entity (non-fatal)");
                 }
@@ -194,11 +224,11 @@ impl MemoryExtractor {
 
         // Process each fact
         let mut new_memories = Vec::new();
-        let active_embeddings = self.store.get_active_embeddings().await?;
+        let mut active_embeddings = self.store.get_active_embeddings().await?;
 
         for fact in facts {
             match self
-                .process_fact(&fact, session_id, &active_embeddings)
+                .process_fact(&fact, session_id, &mut active_embeddings)
                 .await
             {
                 Ok(Some(memory)) => {
@@ -245,7 +275,7 @@ impl MemoryExtractor {
         // Check for duplicates
         let active_embeddings = self.store.get_active_embeddings().await?;
         if let Some((dup_id, sim)) = find_most_similar(&embedding, &active_embeddings)
-            && sim > DEDUP_THRESHOLD
+            && sim >= self.dedup_threshold
         {
             debug!("Explicit memory is duplicate of {dup_id} (similarity {sim:.3}), superseding");
             // Supersede existing since user is explicitly updating
@@ -281,7 +311,7 @@ impl MemoryExtractor {
         &self,
         fact: &ExtractedFact,
         session_id: &str,
-        active_embeddings: &[(String, Vec<f32>)],
+        active_embeddings: &mut Vec<(String, Vec<f32>)>,
     ) -> Result<Option<Memory>, BlufioError> {
         // Generate embedding
         let output = self
@@ -295,41 +325,40 @@ impl MemoryExtractor {
                 BlufioError::Internal("Embedding returned no results".to_string())
             })?;
 
-        // Check for duplicates and contradictions
-        if let Some((existing_id, sim)) = find_most_similar(&embedding, active_embeddings) {
-            if sim > DEDUP_THRESHOLD {
-                // Near-duplicate, skip
-                return Ok(None);
-            } else if sim > CONTRADICTION_THRESHOLD {
-                // Potentially contradicting -- newer wins, supersede old
-                debug!(
-                    "Possible contradiction with {existing_id} (similarity {sim:.3}), superseding"
-                );
-                let new_id = Uuid::new_v4().to_string();
-                self.store.supersede(&existing_id, &new_id).await?;
-
-                let now = chrono::Utc::now()
-                    .format("%Y-%m-%dT%H:%M:%S%.3fZ")
-                    .to_string();
-                let memory = Memory {
-                    id: new_id,
-                    content: fact.content.clone(),
-                    embedding,
-                    source: MemorySource::Extracted,
-                    confidence: 0.6,
-                    status: MemoryStatus::Active,
-                    superseded_by: None,
-                    session_id: Some(session_id.to_string()),
-                    classification: DataClassification::default(),
-                    created_at: now.clone(),
-                    updated_at: now,
-                };
-                self.store.save(&memory).await?;
-                return Ok(Some(memory));
-            }
+        // Check for contradictions; near-duplicates are handled by the store
+        if let Some((existing_id, sim)) = find_most_similar(&embedding, active_embeddings)
+            && sim < self.dedup_threshold
+            && sim > CONTRADICTION_THRESHOLD
+        {
+            // Potentially contradicting -- newer wins, supersede old
+            debug!("Possible contradiction with {existing_id} (similarity {sim:.3}), superseding");
+            let new_id = Uuid::new_v4().to_string();
+            self.store.supersede(&existing_id, &new_id).await?;
+
+            let now = chrono::Utc::now()
+                .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+                .to_string();
+            let memory = Memory {
+                id: new_id,
+                content: fact.content.clone(),
+                embedding,
+                source: MemorySource::Extracted,
+                confidence: 0.6,
+                status: MemoryStatus::Active,
+                superseded_by: None,
+                session_id: Some(session_id.to_string()),
+                classification: DataClassification::default(),
+                created_at: now.clone(),
+                updated_at: now,
+            };
+            self.store.save(&memory).await?;
+            // Keep the batch's view of active memories in step.
+            active_embeddings.retain(|(id, _)| *id != existing_id);
+            active_embeddings.push((memory.id.clone(), memory.embedding.clone()));
+            return Ok(Some(memory));
         }
 
-        // New unique fact
+        // New fact, unless the store finds a near-duplicate
         let now = chrono::Utc::now()
             .format("%Y-%m-%dT%H:%M:%S%.3fZ")
             .to_string();
@@ -346,8 +375,19 @@ impl MemoryExtractor {
             created_at: now.clone(),
             updated_at: now,
         };
-        self.store.save(&memory).await?;
-        Ok(Some(memory))
+        match self
+            .store
+            .save_deduplicated(
+                &memory,
+                active_embeddings,
+                self.dedup_threshold,
+                self.dedup_strategy,
+            )
+            .await?
+        {
+            DedupOutcome::Inserted => Ok(Some(memory)),
+            _ => Ok(None),
+        }
     }
 }
 
@@ -419,20 +459,6 @@ fn strip_remember_prefix(text: &str) -> &str {
     text.trim()
 }
 
-/// Find the most similar embedding in the active set.
-///
-/// Returns (id, similarity) for the closest match, or None if empty.
-fn find_most_similar(
-    query: &[f32],
-    active_embeddings: &[(String, Vec<f32>)],
-) -> Option<(String, f32)> {
-    active_embeddings
-        .iter()
-        .filter(|(_, emb)| emb.len() == query.len())
-        .map(|(id, emb)| (id.clone(), cosine_similarity(query, emb)))
-        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -11,6 +11,7 @@
 //! ## Architecture
 //!
 //! - **OnnxEmbedder**: Local ONNX model for 384-dim embedding inference
+//! - **RemoteEmbedder**: OpenAI-compatible embedding API, an alternative to ONNX
 //! - **MemoryStore**: SQLite persistence with BLOB vectors and FTS5
 //! - **ModelManager**: First-run model download from HuggingFace
 //! - **HybridRetriever**: Vector + BM25 + RRF fusion search
@@ -24,6 +25,7 @@ pub mod eviction;
 pub mod extractor;
 pub mod model_manager;
 pub mod provider;
+pub mod remote;
 pub mod retriever;
 pub mod store;
 pub mod types;
@@ -33,8 +35,9 @@ pub mod watcher;
 
 pub use embedder::OnnxEmbedder;
 pub use extractor::MemoryExtractor;
-pub use model_manager::ModelManager;
+pub use model_manager::{DownloadProgress, ModelFile, ModelManager, ProgressCallback};
 pub use provider::MemoryProvider;
+pub use remote::RemoteEmbedder;
 pub use retriever::HybridRetriever;
 pub use store::MemoryStore;
 pub use types::*;
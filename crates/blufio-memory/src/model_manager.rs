@@ -5,21 +5,88 @@
 //!
 //! Downloads all-MiniLM-L6-v2 INT8 quantized model from HuggingFace
 //! on first run and caches it in the data directory.
+//!
+//! Each file is streamed to a `.part` path next to its destination while
+//! its SHA-256 is computed, verified, and only then renamed into place, so
+//! an interrupted or corrupted download never looks like a usable model.
+//!
+//! In offline mode no network I/O is attempted: the model must already be
+//! present in the model directory, otherwise `ensure_model` fails with an
+//! error naming the missing files.
 
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use blufio_core::error::BlufioError;
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
 use tokio::sync::OnceCell;
-use tracing::info;
+use tracing::{info, warn};
 
 /// URLs for model files on HuggingFace.
 const MODEL_URL: &str = "https://huggingface.co/onnx-community/all-MiniLM-L6-v2-ONNX/resolve/main/onnx/model_quantized.onnx";
 const TOKENIZER_URL: &str =
     "https://huggingface.co/sentence-transformers/all-MiniLM-L6-v2/resolve/main/tokenizer.json";
 
+/// Attempts per file before giving up on a download that fails verification.
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 3;
+
+/// Header carrying the SHA-256 of Git LFS files on HuggingFace.
+const LINKED_ETAG_HEADER: &str = "x-linked-etag";
+
+/// A file the model manager downloads into the model directory.
+#[derive(Debug, Clone)]
+pub struct ModelFile {
+    /// File name inside the model directory.
+    pub filename: String,
+    /// Download URL.
+    pub url: String,
+    /// Known-good lowercase hex SHA-256. The SHA-256 the server advertises
+    /// for LFS files (`X-Linked-Etag`) is only a cross-check: it must agree
+    /// with this value, and is used on its own only when this is `None`. The
+    /// header is read from the first response, before any redirect to a CDN
+    /// that does not send it.
+    pub sha256: Option<String>,
+}
+
+impl ModelFile {
+    /// Creates a file spec without a pinned checksum.
+    pub fn new(filename: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            filename: filename.into(),
+            url: url.into(),
+            sha256: None,
+        }
+    }
+
+    /// Pins the expected SHA-256 (hex, case-insensitive).
+    pub fn with_sha256(mut self, sha256: impl Into<String>) -> Self {
+        self.sha256 = Some(sha256.into().to_ascii_lowercase());
+        self
+    }
+}
+
+/// Progress of a single file download, passed to the progress hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// File being downloaded.
+    pub filename: String,
+    /// Bytes received so far.
+    pub downloaded: u64,
+    /// Total size, when the server sent `Content-Length`.
+    pub total: Option<u64>,
+}
+
+/// Callback invoked as download chunks arrive.
+pub type ProgressCallback = Arc<dyn Fn(&DownloadProgress) + Send + Sync>;
+
 /// Manages ONNX model download and path resolution.
 pub struct ModelManager {
     data_dir: PathBuf,
+    model_dir: Option<PathBuf>,
+    files: Vec<ModelFile>,
+    progress: Option<ProgressCallback>,
+    offline: bool,
     /// Ensures model is downloaded only once even with concurrent callers.
     _init_guard: OnceCell<()>,
 }
@@ -29,13 +96,51 @@ impl ModelManager {
     pub fn new(data_dir: PathBuf) -> Self {
         Self {
             data_dir,
+            model_dir: None,
+            files: vec![
+                ModelFile::new("model.onnx", MODEL_URL),
+                ModelFile::new("tokenizer.json", TOKENIZER_URL),
+            ],
+            progress: None,
+            offline: false,
             _init_guard: OnceCell::new(),
         }
     }
 
+    /// Replaces the files to download (e.g. mirrors or pinned checksums).
+    ///
+    /// The set should still include `model.onnx` and `tokenizer.json`.
+    pub fn with_files(mut self, files: Vec<ModelFile>) -> Self {
+        self.files = files;
+        self
+    }
+
+    /// Sets a hook called with byte counts as each file downloads.
+    pub fn with_progress(mut self, progress: ProgressCallback) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Uses a pre-provisioned model directory instead of the default
+    /// `<data_dir>/models/all-MiniLM-L6-v2`.
+    pub fn with_model_dir(mut self, model_dir: PathBuf) -> Self {
+        self.model_dir = Some(model_dir);
+        self
+    }
+
+    /// Enables offline mode: `ensure_model` never downloads and fails if the
+    /// model is not already provisioned.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
     /// Returns the directory where model files are stored.
     pub fn model_dir(&self) -> PathBuf {
-        self.data_dir.join("models").join("all-MiniLM-L6-v2")
+        match &self.model_dir {
+            Some(dir) => dir.clone(),
+            None => self.data_dir.join("models").join("all-MiniLM-L6-v2"),
+        }
     }
 
     /// Returns the path to the ONNX model file.
@@ -56,12 +161,18 @@ impl ModelManager {
     /// Ensures the model is downloaded and available.
     ///
     /// Downloads from HuggingFace on first run; subsequent calls are no-ops.
-    /// Uses `OnceCell` to prevent concurrent download races.
+    /// Uses `OnceCell` to prevent concurrent download races. A file failing
+    /// size or checksum verification is downloaded again, up to
+    /// `MAX_DOWNLOAD_ATTEMPTS` times.
     pub async fn ensure_model(&self) -> Result<PathBuf, BlufioError> {
         if self.is_model_available() {
             return Ok(self.model_path());
         }
 
+        if self.offline {
+            return Err(self.offline_missing_error());
+        }
+
         info!("Embedding model not found, downloading from HuggingFace...");
 
         let model_dir = self.model_dir();
@@ -69,23 +180,30 @@ impl ModelManager {
             .await
             .map_err(|e| BlufioError::Internal(format!("Failed to create model directory: {e}")))?;
 
-        let files = [("model.onnx", MODEL_URL), ("tokenizer.json", TOKENIZER_URL)];
-
-        for (filename, url) in &files {
-            let dest = model_dir.join(filename);
+        for file in &self.files {
+            let dest = model_dir.join(&file.filename);
             if dest.exists() {
                 continue;
             }
 
-            info!("Downloading {filename}...");
-            match download_file(url, &dest).await {
-                Ok(size) => {
-                    info!("Downloaded {filename} ({size} bytes)");
-                }
-                Err(e) => {
-                    // Clean up partial download
-                    let _ = tokio::fs::remove_file(&dest).await;
-                    return Err(e);
+            info!("Downloading {}...", file.filename);
+            let mut attempt = 1;
+            loop {
+                match download_file(file, &dest, self.progress.as_ref()).await {
+                    Ok(size) => {
+                        info!("Downloaded {} ({size} bytes)", file.filename);
+                        break;
+                    }
+                    Err(e) if attempt < MAX_DOWNLOAD_ATTEMPTS => {
+                        warn!(
+                            file = file.filename.as_str(),
+                            attempt,
+                            error = %e,
+                            "model download failed, retrying"
+                        );
+                        attempt += 1;
+                    }
+                    Err(e) => return Err(e),
                 }
             }
         }
@@ -93,13 +211,62 @@ impl ModelManager {
         info!("Embedding model ready at: {}", model_dir.display());
         Ok(self.model_path())
     }
+
+    /// Error for a model that is missing while downloads are disabled.
+    fn offline_missing_error(&self) -> BlufioError {
+        let model_dir = self.model_dir();
+        let missing: Vec<&str> = self
+            .files
+            .iter()
+            .map(|f| f.filename.as_str())
+            .filter(|name| !model_dir.join(name).exists())
+            .collect();
+        BlufioError::Internal(format!(
+            "Offline mode is enabled and the embedding model is not provisioned: \
+             missing {} in {}. Copy the model files there (or point memory.model_dir \
+             at them), or unset offline / BLUFIO_OFFLINE to allow downloading.",
+            missing.join(", "),
+            model_dir.display()
+        ))
+    }
 }
 
-/// Download a file from a URL to a local path.
-async fn download_file(url: &str, dest: &Path) -> Result<usize, BlufioError> {
-    let response = reqwest::get(url)
-        .await
-        .map_err(|e| BlufioError::Internal(format!("Failed to download {url}: {e}")))?;
+/// Download a file to `dest` via a `.part` temp file, verifying its size and
+/// SHA-256 before atomically renaming it into place.
+///
+/// The temp file is removed on any failure. Returns the file size.
+async fn download_file(
+    file: &ModelFile,
+    dest: &Path,
+    progress: Option<&ProgressCallback>,
+) -> Result<u64, BlufioError> {
+    let part = part_path(dest);
+    let result = download_to(file, &part, progress).await;
+    let size = match result {
+        Ok(size) => size,
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&part).await;
+            return Err(e);
+        }
+    };
+
+    tokio::fs::rename(&part, dest).await.map_err(|e| {
+        BlufioError::Internal(format!(
+            "Failed to move download to {}: {e}",
+            dest.display()
+        ))
+    })?;
+    Ok(size)
+}
+
+/// Stream `file.url` into `part`, hashing as it goes, then verify.
+async fn download_to(
+    file: &ModelFile,
+    part: &Path,
+    progress: Option<&ProgressCallback>,
+) -> Result<u64, BlufioError> {
+    let url = &file.url;
+    let (mut response, advertised) = fetch(url).await?;
 
     if !response.status().is_success() {
         return Err(BlufioError::Internal(format!(
@@ -108,21 +275,152 @@ async fn download_file(url: &str, dest: &Path) -> Result<usize, BlufioError> {
         )));
     }
 
-    let bytes = response.bytes().await.map_err(|e| {
+    let total = response.content_length();
+    let expected = match (&file.sha256, advertised) {
+        (Some(pinned), Some(advertised)) if *pinned != advertised => {
+            return Err(BlufioError::Internal(format!(
+                "Checksum mismatch for {}: pinned sha256 {pinned}, server advertises {advertised}",
+                file.filename
+            )));
+        }
+        (Some(pinned), _) => Some(pinned.clone()),
+        (None, advertised) => advertised,
+    };
+    if expected.is_none() {
+        warn!(
+            file = file.filename.as_str(),
+            "no known checksum for model file, verifying size only"
+        );
+    }
+
+    let mut out = tokio::fs::File::create(part)
+        .await
+        .map_err(|e| BlufioError::Internal(format!("Failed to create {}: {e}", part.display())))?;
+    let mut hasher = Sha256::new();
+    let mut downloaded: u64 = 0;
+    let mut logged_tenth = 0;
+
+    while let Some(chunk) = response.chunk().await.map_err(|e| {
         BlufioError::Internal(format!("Failed to read response body from {url}: {e}"))
-    })?;
+    })? {
+        hasher.update(&chunk);
+        out.write_all(&chunk).await.map_err(|e| {
+            BlufioError::Internal(format!("Failed to write {}: {e}", part.display()))
+        })?;
+        downloaded += chunk.len() as u64;
+
+        if let Some(cb) = progress {
+            cb(&DownloadProgress {
+                filename: file.filename.clone(),
+                downloaded,
+                total,
+            });
+        }
+        if let Some(total) = total.filter(|t| *t > 0) {
+            let tenth = downloaded * 10 / total;
+            if tenth > logged_tenth {
+                logged_tenth = tenth;
+                info!("Downloading {}: {}%", file.filename, tenth * 10);
+            }
+        }
+    }
 
-    let size = bytes.len();
-    tokio::fs::write(dest, &bytes)
+    out.sync_all()
         .await
-        .map_err(|e| BlufioError::Internal(format!("Failed to write {}: {e}", dest.display())))?;
+        .map_err(|e| BlufioError::Internal(format!("Failed to flush {}: {e}", part.display())))?;
 
-    Ok(size)
+    if let Some(total) = total
+        && downloaded != total
+    {
+        return Err(BlufioError::Internal(format!(
+            "Incomplete download of {}: got {downloaded} of {total} bytes",
+            file.filename
+        )));
+    }
+
+    if let Some(expected) = expected {
+        let actual = format!("{:x}", hasher.finalize());
+        if actual != expected {
+            return Err(BlufioError::Internal(format!(
+                "Checksum mismatch for {}: expected sha256 {expected}, got {actual}",
+                file.filename
+            )));
+        }
+    }
+
+    Ok(downloaded)
+}
+
+/// GET `url`, returning the response and the SHA-256 the server advertised.
+///
+/// HuggingFace answers LFS downloads with a redirect to its CDN, and only
+/// that first response carries `X-Linked-Etag`. It is therefore requested
+/// without following redirects; a redirect is then followed normally.
+async fn fetch(url: &str) -> Result<(reqwest::Response, Option<String>), BlufioError> {
+    let download_err =
+        |e: reqwest::Error| BlufioError::Internal(format!("Failed to download {url}: {e}"));
+    let first = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .map_err(download_err)?
+        .get(url)
+        .send()
+        .await
+        .map_err(download_err)?;
+    let advertised = advertised_sha256(first.headers());
+    if !first.status().is_redirection() {
+        return Ok((first, advertised));
+    }
+
+    let target = first
+        .headers()
+        .get(reqwest::header::LOCATION)
+        .and_then(|location| location.to_str().ok())
+        .and_then(|location| first.url().join(location).ok())
+        .ok_or_else(|| {
+            BlufioError::Internal(format!(
+                "Download redirect without a valid Location header: {url}"
+            ))
+        })?;
+    let response = reqwest::get(target).await.map_err(download_err)?;
+    Ok((response, advertised))
+}
+
+/// The `.part` temp path a download is written to before being renamed.
+fn part_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+/// SHA-256 advertised by HuggingFace for LFS files via `X-Linked-Etag`.
+///
+/// Non-LFS files carry a git blob hash there instead, which is ignored.
+fn advertised_sha256(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    let etag = headers.get(LINKED_ETAG_HEADER)?.to_str().ok()?;
+    let etag = etag.trim_start_matches("W/").trim_matches('"');
+    (etag.len() == 64 && etag.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| etag.to_ascii_lowercase())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Mutex;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn sha256_hex(bytes: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(bytes))
+    }
+
+    async fn serve(server: &MockServer, route: &str, body: &'static [u8]) {
+        Mock::given(method("GET"))
+            .and(path(route))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(body))
+            .mount(server)
+            .await;
+    }
 
     #[test]
     fn model_path_under_data_dir() {
@@ -156,4 +454,203 @@ mod tests {
         let mgr = ModelManager::new(PathBuf::from("/nonexistent/path"));
         assert!(!mgr.is_model_available());
     }
+
+    #[test]
+    fn model_dir_override() {
+        let mgr = ModelManager::new(PathBuf::from("/data"))
+            .with_model_dir(PathBuf::from("/opt/models/minilm"));
+        assert_eq!(
+            mgr.model_path(),
+            PathBuf::from("/opt/models/minilm/model.onnx")
+        );
+    }
+
+    #[test]
+    fn advertised_sha256_accepts_only_sha256_etags() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        let sha = "A".repeat(64);
+        headers.insert(LINKED_ETAG_HEADER, format!("\"{sha}\"").parse().unwrap());
+        assert_eq!(advertised_sha256(&headers), Some("a".repeat(64)));
+
+        // Git blob SHA-1 of a non-LFS file
+        headers.insert(
+            LINKED_ETAG_HEADER,
+            format!("\"{}\"", "b".repeat(40)).parse().unwrap(),
+        );
+        assert_eq!(advertised_sha256(&headers), None);
+    }
+
+    #[tokio::test]
+    async fn ensure_model_verifies_and_reports_progress() {
+        let server = MockServer::start().await;
+        serve(&server, "/model.onnx", b"onnx-bytes").await;
+        serve(&server, "/tokenizer.json", b"{\"tokenizer\":true}").await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let mgr = ModelManager::new(dir.path().to_path_buf())
+            .with_files(vec![
+                ModelFile::new("model.onnx", format!("{}/model.onnx", server.uri()))
+                    .with_sha256(sha256_hex(b"onnx-bytes")),
+                ModelFile::new("tokenizer.json", format!("{}/tokenizer.json", server.uri())),
+            ])
+            .with_progress(Arc::new(move |p| sink.lock().unwrap().push(p.clone())));
+
+        let model = mgr.ensure_model().await.unwrap();
+        assert_eq!(std::fs::read(&model).unwrap(), b"onnx-bytes");
+        assert!(mgr.is_model_available());
+        assert!(!part_path(&model).exists());
+
+        let events = events.lock().unwrap();
+        let last_model = events.iter().rfind(|p| p.filename == "model.onnx").unwrap();
+        assert_eq!(last_model.downloaded, 10);
+        assert_eq!(last_model.total, Some(10));
+    }
+
+    #[tokio::test]
+    async fn checksum_mismatch_is_rejected_after_retries() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/model.onnx"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"corrupted".as_slice()))
+            .expect(u64::from(MAX_DOWNLOAD_ATTEMPTS))
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mgr = ModelManager::new(dir.path().to_path_buf()).with_files(vec![
+            ModelFile::new("model.onnx", format!("{}/model.onnx", server.uri()))
+                .with_sha256(sha256_hex(b"the real model")),
+        ]);
+
+        let err = mgr.ensure_model().await.unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"), "{err}");
+        assert!(!mgr.model_path().exists());
+        assert!(!part_path(&mgr.model_path()).exists());
+    }
+
+    #[tokio::test]
+    async fn advertised_checksum_is_verified() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/model.onnx"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(LINKED_ETAG_HEADER, format!("\"{}\"", sha256_hex(b"other")))
+                    .set_body_bytes(b"onnx-bytes".as_slice()),
+            )
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mgr = ModelManager::new(dir.path().to_path_buf()).with_files(vec![ModelFile::new(
+            "model.onnx",
+            format!("{}/model.onnx", server.uri()),
+        )]);
+
+        let err = mgr.ensure_model().await.unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"), "{err}");
+        assert!(!mgr.model_path().exists());
+    }
+
+    #[tokio::test]
+    async fn checksum_is_read_before_following_redirect() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/resolve/model.onnx"))
+            .respond_with(
+                ResponseTemplate::new(302)
+                    .insert_header(LINKED_ETAG_HEADER, format!("\"{}\"", sha256_hex(b"other")))
+                    .insert_header("location", "/cdn/model.onnx"),
+            )
+            .mount(&server)
+            .await;
+        serve(&server, "/cdn/model.onnx", b"onnx-bytes").await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mgr = ModelManager::new(dir.path().to_path_buf()).with_files(vec![ModelFile::new(
+            "model.onnx",
+            format!("{}/resolve/model.onnx", server.uri()),
+        )]);
+
+        // The CDN response has no checksum; the redirect's is still enforced.
+        let err = mgr.ensure_model().await.unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"), "{err}");
+        assert!(!mgr.model_path().exists());
+    }
+
+    #[tokio::test]
+    async fn advertised_checksum_must_match_pinned_one() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/model.onnx"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(LINKED_ETAG_HEADER, format!("\"{}\"", sha256_hex(b"other")))
+                    .set_body_bytes(b"onnx-bytes".as_slice()),
+            )
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mgr = ModelManager::new(dir.path().to_path_buf()).with_files(vec![
+            ModelFile::new("model.onnx", format!("{}/model.onnx", server.uri()))
+                .with_sha256(sha256_hex(b"onnx-bytes")),
+        ]);
+
+        // The body matches the pin, but upstream no longer serves that file.
+        let err = mgr.ensure_model().await.unwrap_err();
+        assert!(err.to_string().contains("server advertises"), "{err}");
+        assert!(!mgr.model_path().exists());
+    }
+
+    #[tokio::test]
+    async fn offline_missing_model_fails_without_network() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"onnx-bytes".as_slice()))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mgr = ModelManager::new(dir.path().to_path_buf())
+            .with_files(vec![
+                ModelFile::new("model.onnx", format!("{}/model.onnx", server.uri())),
+                ModelFile::new("tokenizer.json", format!("{}/tokenizer.json", server.uri())),
+            ])
+            .with_offline(true);
+
+        let err = mgr.ensure_model().await.unwrap_err().to_string();
+        assert!(err.contains("Offline mode"), "{err}");
+        assert!(err.contains("model.onnx, tokenizer.json"), "{err}");
+        assert!(!mgr.model_dir().exists());
+        // MockServer verifies `.expect(0)` on drop.
+    }
+
+    #[tokio::test]
+    async fn offline_uses_provisioned_model() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("model.onnx"), b"onnx-bytes").unwrap();
+        std::fs::write(dir.path().join("tokenizer.json"), b"{}").unwrap();
+
+        let mgr = ModelManager::new(PathBuf::from("/nonexistent"))
+            .with_model_dir(dir.path().to_path_buf())
+            .with_files(vec![ModelFile::new(
+                "model.onnx",
+                format!("{}/model.onnx", server.uri()),
+            )])
+            .with_offline(true);
+
+        let model = mgr.ensure_model().await.unwrap();
+        assert_eq!(model, dir.path().join("model.onnx"));
+    }
 }
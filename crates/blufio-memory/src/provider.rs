@@ -4,7 +4,8 @@
 //! ConditionalProvider implementation for memory-based context injection.
 //!
 //! MemoryProvider implements the ConditionalProvider trait from blufio-context,
-//! injecting relevant memories as structured blocks in the prompt.
+//! injecting relevant memories as a labeled block in the prompt, either in the
+//! system prompt or as a message before the conversation history.
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -16,6 +17,21 @@ use blufio_core::types::{ContentBlock, ProviderMessage};
 use tokio::sync::RwLock;
 
 use crate::retriever::HybridRetriever;
+use crate::types::ScoredMemory;
+
+/// Label opening the memory block, so the model does not mistake recalled
+/// facts for something the user just said.
+pub const MEMORY_BLOCK_LABEL: &str =
+    "Relevant memories (recalled from earlier conversations, not part of the current message):";
+
+/// Formats retrieved memories as a labeled bullet list.
+pub fn format_memory_block(memories: &[ScoredMemory]) -> String {
+    let mut text = format!("{MEMORY_BLOCK_LABEL}\n");
+    for scored in memories {
+        text.push_str(&format!("- {}\n", scored.memory.content));
+    }
+    text
+}
 
 /// ConditionalProvider that injects relevant long-term memories into context.
 ///
@@ -30,6 +46,9 @@ pub struct MemoryProvider {
     retriever: Arc<HybridRetriever>,
     /// Per-session current query, set by SessionActor before context assembly.
     current_queries: Arc<RwLock<HashMap<String, String>>>,
+    /// Role of the injected block: "system" joins the system prompt,
+    /// "user" places it before the conversation history.
+    role: &'static str,
 }
 
 impl MemoryProvider {
@@ -38,9 +57,19 @@ impl MemoryProvider {
         Self {
             retriever,
             current_queries: Arc::new(RwLock::new(HashMap::new())),
+            role: "user",
         }
     }
 
+    /// Sets where memories are injected (`memory.injection_placement`).
+    ///
+    /// "system" appends the block to the system prompt; anything else
+    /// places it as a message before the conversation history.
+    pub fn with_placement(mut self, placement: &str) -> Self {
+        self.role = placement_role(placement);
+        self
+    }
+
     /// Called by SessionActor before context assembly to set the current query.
     ///
     /// The query is the user's latest message text, used to retrieve
@@ -72,8 +101,8 @@ impl MemoryProvider {
 impl ConditionalProvider for MemoryProvider {
     /// Retrieves relevant memories and formats them as context.
     ///
-    /// Returns a single ProviderMessage with role "user" containing
-    /// a "## Relevant Memories" header and bullet-pointed facts.
+    /// Returns a single ProviderMessage containing the labeled memory block,
+    /// with role "system" or "user" depending on the configured placement.
     /// Returns empty Vec when no memories exceed the threshold.
     async fn provide_context(&self, session_id: &str) -> Result<Vec<ProviderMessage>, BlufioError> {
         let query = self.get_current_query(session_id).await;
@@ -86,31 +115,36 @@ impl ConditionalProvider for MemoryProvider {
             return Ok(vec![]);
         }
 
-        // Format as structured memory block
-        let mut memory_text = String::from("## Relevant Memories\n");
-        for scored in &memories {
-            memory_text.push_str(&format!("- {}\n", scored.memory.content));
-        }
+        Ok(vec![memory_message(self.role, &memories)])
+    }
+}
 
-        Ok(vec![ProviderMessage {
-            role: "user".to_string(),
-            content: vec![ContentBlock::Text { text: memory_text }],
-        }])
+/// Message role for a `memory.injection_placement` value.
+fn placement_role(placement: &str) -> &'static str {
+    if placement == "system" {
+        "system"
+    } else {
+        "user"
+    }
+}
+
+/// Builds the context message carrying the memory block.
+fn memory_message(role: &str, memories: &[ScoredMemory]) -> ProviderMessage {
+    ProviderMessage {
+        role: role.to_string(),
+        content: vec![ContentBlock::Text {
+            text: format_memory_block(memories),
+        }],
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{Memory, MemorySource, MemoryStatus, ScoredMemory};
+    use crate::types::{Memory, MemorySource, MemoryStatus};
 
-    /// Format memories the same way the provider does (for test verification).
     fn format_memories(memories: &[ScoredMemory]) -> String {
-        let mut text = String::from("## Relevant Memories\n");
-        for scored in memories {
-            text.push_str(&format!("- {}\n", scored.memory.content));
-        }
-        text
+        format_memory_block(memories)
     }
 
     fn make_scored_memory(content: &str, score: f32) -> ScoredMemory {
@@ -136,7 +170,7 @@ mod tests {
     fn format_memories_header() {
         let memories = vec![make_scored_memory("User has a dog named Max", 0.8)];
         let formatted = format_memories(&memories);
-        assert!(formatted.starts_with("## Relevant Memories\n"));
+        assert!(formatted.starts_with(&format!("{MEMORY_BLOCK_LABEL}\n")));
     }
 
     #[test]
@@ -154,7 +188,23 @@ mod tests {
     fn format_memories_empty() {
         let memories: Vec<ScoredMemory> = vec![];
         let formatted = format_memories(&memories);
-        assert_eq!(formatted, "## Relevant Memories\n");
+        assert_eq!(formatted, format!("{MEMORY_BLOCK_LABEL}\n"));
+    }
+
+    #[test]
+    fn memory_message_uses_configured_placement() {
+        let memories = vec![make_scored_memory("User's cat is called Miso", 0.9)];
+
+        let system = memory_message(placement_role("system"), &memories);
+        assert_eq!(system.role, "system");
+        let conditional = memory_message(placement_role("conditional"), &memories);
+        assert_eq!(conditional.role, "user");
+
+        let ContentBlock::Text { text } = &system.content[0] else {
+            panic!("expected text block");
+        };
+        assert!(text.starts_with(MEMORY_BLOCK_LABEL));
+        assert!(text.contains("- User's cat is called Miso\n"));
     }
 
     #[tokio::test]
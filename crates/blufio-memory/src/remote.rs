@@ -0,0 +1,250 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Remote embedding adapter for OpenAI-compatible `/embeddings` APIs.
+//!
+//! An alternative to [`OnnxEmbedder`](crate::OnnxEmbedder) for deployments
+//! that would rather not download a model and run local inference.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use blufio_config::model::RemoteEmbeddingConfig;
+use blufio_core::error::BlufioError;
+use blufio_core::traits::EmbeddingAdapter;
+use blufio_core::traits::adapter::PluginAdapter;
+use blufio_core::types::{AdapterType, EmbeddingInput, EmbeddingOutput, HealthStatus};
+
+/// Embedding adapter calling an OpenAI-compatible embeddings endpoint.
+pub struct RemoteEmbedder {
+    client: reqwest::Client,
+    endpoint: String,
+    model: String,
+    api_key: Option<String>,
+    dimensions: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct EmbeddingsRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dimensions: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+impl RemoteEmbedder {
+    /// Creates a remote embedder from config, or `None` when no URL is set.
+    pub fn from_config(config: &RemoteEmbeddingConfig) -> Result<Option<Self>, BlufioError> {
+        let Some(url) = config.url.as_deref() else {
+            return Ok(None);
+        };
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| {
+                BlufioError::Internal(format!("Failed to build embedding HTTP client: {e}"))
+            })?;
+
+        Ok(Some(Self {
+            client,
+            endpoint: format!("{}/embeddings", url.trim_end_matches('/')),
+            model: config.model.clone(),
+            api_key: config.api_key.clone(),
+            dimensions: config.dimensions,
+        }))
+    }
+
+    /// The URL embedding requests are sent to.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+}
+
+#[async_trait]
+impl PluginAdapter for RemoteEmbedder {
+    fn name(&self) -> &str {
+        "remote-embedder"
+    }
+
+    fn version(&self) -> semver::Version {
+        semver::Version::new(0, 1, 0)
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::Embedding
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus, BlufioError> {
+        match self
+            .embed(EmbeddingInput {
+                texts: vec!["health check".to_string()],
+            })
+            .await
+        {
+            Ok(_) => Ok(HealthStatus::Healthy),
+            Err(e) => Ok(HealthStatus::Unhealthy(format!(
+                "Embedding API unreachable: {e}"
+            ))),
+        }
+    }
+
+    async fn shutdown(&self) -> Result<(), BlufioError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EmbeddingAdapter for RemoteEmbedder {
+    async fn embed(&self, input: EmbeddingInput) -> Result<EmbeddingOutput, BlufioError> {
+        if input.texts.is_empty() {
+            return Ok(EmbeddingOutput {
+                embeddings: Vec::new(),
+                dimensions: self.dimensions.unwrap_or(0),
+            });
+        }
+
+        let mut request = self.client.post(&self.endpoint).json(&EmbeddingsRequest {
+            model: &self.model,
+            input: &input.texts,
+            dimensions: self.dimensions,
+        });
+        if let Some(ref key) = self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            BlufioError::Internal(format!(
+                "Embedding request to {} failed: {e}",
+                self.endpoint
+            ))
+        })?;
+        if !response.status().is_success() {
+            return Err(BlufioError::Internal(format!(
+                "Embedding request failed with status {}: {}",
+                response.status(),
+                self.endpoint
+            )));
+        }
+        let mut body: EmbeddingsResponse = response.json().await.map_err(|e| {
+            BlufioError::Internal(format!(
+                "Invalid embedding response from {}: {e}",
+                self.endpoint
+            ))
+        })?;
+
+        if body.data.len() != input.texts.len() {
+            return Err(BlufioError::Internal(format!(
+                "Embedding API returned {} vectors for {} inputs",
+                body.data.len(),
+                input.texts.len()
+            )));
+        }
+        // Entries carry their input position; don't rely on response order.
+        body.data.sort_by_key(|d| d.index);
+        let embeddings: Vec<Vec<f32>> = body.data.into_iter().map(|d| d.embedding).collect();
+        let dimensions = embeddings.first().map(Vec::len).unwrap_or(0);
+
+        Ok(EmbeddingOutput {
+            embeddings,
+            dimensions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn config(url: &str) -> RemoteEmbeddingConfig {
+        RemoteEmbeddingConfig {
+            url: Some(url.to_string()),
+            api_key: Some("sk-test".to_string()),
+            dimensions: Some(3),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn from_config_requires_url() {
+        assert!(
+            RemoteEmbedder::from_config(&RemoteEmbeddingConfig::default())
+                .unwrap()
+                .is_none()
+        );
+        let embedder = RemoteEmbedder::from_config(&config("https://api.example.com/v1/"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(embedder.endpoint(), "https://api.example.com/v1/embeddings");
+    }
+
+    #[tokio::test]
+    async fn embed_posts_inputs_and_orders_by_index() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/embeddings"))
+            .and(header("authorization", "Bearer sk-test"))
+            .and(body_partial_json(serde_json::json!({
+                "model": "text-embedding-3-small",
+                "input": ["first", "second"],
+                "dimensions": 3
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [
+                    {"index": 1, "embedding": [0.0, 1.0, 0.0]},
+                    {"index": 0, "embedding": [1.0, 0.0, 0.0]}
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let embedder = RemoteEmbedder::from_config(&config(&format!("{}/v1", server.uri())))
+            .unwrap()
+            .unwrap();
+        let output = embedder
+            .embed(EmbeddingInput {
+                texts: vec!["first".to_string(), "second".to_string()],
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(output.dimensions, 3);
+        assert_eq!(output.embeddings[0], vec![1.0, 0.0, 0.0]);
+        assert_eq!(output.embeddings[1], vec![0.0, 1.0, 0.0]);
+    }
+
+    #[tokio::test]
+    async fn embed_reports_http_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let embedder = RemoteEmbedder::from_config(&config(&server.uri()))
+            .unwrap()
+            .unwrap();
+        let err = embedder
+            .embed(EmbeddingInput {
+                texts: vec!["x".to_string()],
+            })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("401"));
+    }
+}
@@ -4,8 +4,10 @@
 //! Hybrid retriever combining vector similarity and BM25 via RRF fusion.
 //!
 //! The retriever embeds the query, runs both vector search and FTS5 BM25,
-//! fuses results using Reciprocal Rank Fusion (k=60), applies source-based
-//! importance boost and temporal decay, then reranks with MMR for diversity.
+//! fuses results using weighted Reciprocal Rank Fusion (k=60 and equal
+//! weights by default, see `memory.rrf_k`), applies source-based
+//! importance boost, temporal decay, and access-recency decay, then reranks
+//! with MMR for diversity.
 //!
 //! When vec0 is enabled, the scoring pipeline uses auxiliary column data
 //! (content, source, confidence, created_at) from vec0 search results,
@@ -24,12 +26,11 @@ use blufio_core::error::BlufioError;
 use blufio_core::traits::EmbeddingAdapter;
 use blufio_core::types::EmbeddingInput;
 
-use crate::embedder::OnnxEmbedder;
 use crate::store::MemoryStore;
 use crate::types::{Memory, MemorySource, ScoredMemory, cosine_similarity};
 use crate::vec0;
 
-/// RRF constant per research literature.
+/// Default RRF constant per research literature (`memory.rrf_k`).
 const RRF_K: f32 = 60.0;
 
 /// Compute temporal decay factor for a memory based on its age.
@@ -70,6 +71,31 @@ fn importance_boost_for_source(source: &MemorySource, config: &MemoryConfig) ->
     }
 }
 
+/// Compute access-recency decay from when a memory was last retrieved.
+///
+/// `last_accessed` is `None` for never-retrieved memories (see
+/// [`MemoryStore::get_last_accessed`]); like unparseable timestamps, that
+/// means no decay. File-sourced memories skip it like temporal decay.
+/// Formula: `max(access_decay_factor^idle_days, decay_floor)`.
+fn access_decay(
+    last_accessed: Option<&str>,
+    source: &MemorySource,
+    now: chrono::DateTime<Utc>,
+    config: &MemoryConfig,
+) -> f32 {
+    if *source == MemorySource::FileWatcher {
+        return 1.0;
+    }
+    let Some(accessed) = last_accessed.and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+    else {
+        return 1.0;
+    };
+    let idle_days = (now - accessed.with_timezone(&Utc)).num_days().max(0) as f32;
+    (config.access_decay_factor as f32)
+        .powf(idle_days)
+        .max(config.decay_floor as f32)
+}
+
 /// Scoring data carried from vec0 search results through the pipeline.
 /// Avoids re-fetching content, source, confidence, created_at from the memories table.
 struct Vec0ScoringData {
@@ -157,6 +183,13 @@ async fn score_from_vec0_data(
         .map(|m| (m.id.as_str(), m))
         .collect();
 
+    let fused_ids: Vec<String> = fused.iter().map(|(id, _)| id.clone()).collect();
+    let last_accessed: HashMap<String, String> = store
+        .get_last_accessed(&fused_ids)
+        .await?
+        .into_iter()
+        .collect();
+
     // Build scored memories
     let now = Utc::now();
     let mut scored: Vec<ScoredMemory> = Vec::new();
@@ -169,7 +202,13 @@ async fn score_from_vec0_data(
             let source = parse_memory_source(&v.source);
             let importance = importance_boost_for_source(&source, config);
             let decay = temporal_decay_from_str(&v.created_at, &source, now, config);
-            let final_score = rrf_score * importance * decay;
+            let recency = access_decay(
+                last_accessed.get(id).map(String::as_str),
+                &source,
+                now,
+                config,
+            );
+            let final_score = rrf_score * importance * decay * recency;
 
             scored.push(ScoredMemory {
                 memory: Memory {
@@ -191,7 +230,13 @@ async fn score_from_vec0_data(
             // BM25-only results: use full Memory from fallback
             let importance = importance_boost_for_source(&m.source, config);
             let decay = temporal_decay(m, now, config);
-            let final_score = rrf_score * importance * decay;
+            let recency = access_decay(
+                last_accessed.get(id).map(String::as_str),
+                &m.source,
+                now,
+                config,
+            );
+            let final_score = rrf_score * importance * decay * recency;
             scored.push(ScoredMemory {
                 memory: (*m).clone(),
                 score: final_score,
@@ -199,6 +244,9 @@ async fn score_from_vec0_data(
         }
     }
 
+    // Drop results below the configured cutoff
+    scored.retain(|s| s.score >= config.min_score as f32);
+
     // Sort by combined score descending
     scored.sort_by(|a, b| {
         b.score
@@ -220,17 +268,14 @@ async fn score_from_vec0_data(
     }
 
     // MMR diversity reranking
-    Ok(mmr_rerank(
-        &scored,
-        config.mmr_lambda,
-        config.max_retrieval_results,
-    ))
+    Ok(mmr_rerank(&scored, config.mmr_lambda, config.top_k))
 }
 
 /// Score memories using full Memory structs from the database (original path).
 ///
 /// Fetches complete Memory records including embeddings, applies importance
-/// boost and temporal decay, sorts, and applies MMR reranking.
+/// boost and temporal decay, drops results below `min_score`, sorts, and
+/// applies MMR reranking down to `top_k`.
 async fn score_from_memory_structs(
     store: &MemoryStore,
     config: &MemoryConfig,
@@ -238,6 +283,11 @@ async fn score_from_memory_structs(
 ) -> Result<Vec<ScoredMemory>, BlufioError> {
     let top_ids: Vec<String> = fused.iter().map(|(id, _)| id.clone()).collect();
     let memories = store.get_memories_by_ids(&top_ids).await?;
+    let last_accessed: HashMap<String, String> = store
+        .get_last_accessed(&top_ids)
+        .await?
+        .into_iter()
+        .collect();
 
     let score_map: HashMap<&str, f32> = fused
         .iter()
@@ -251,7 +301,13 @@ async fn score_from_memory_structs(
             let rrf_score = score_map.get(memory.id.as_str()).copied().unwrap_or(0.0);
             let importance = importance_boost_for_source(&memory.source, config);
             let decay = temporal_decay(&memory, now, config);
-            let final_score = rrf_score * importance * decay;
+            let recency = access_decay(
+                last_accessed.get(&memory.id).map(String::as_str),
+                &memory.source,
+                now,
+                config,
+            );
+            let final_score = rrf_score * importance * decay * recency;
             ScoredMemory {
                 memory,
                 score: final_score,
@@ -259,17 +315,15 @@ async fn score_from_memory_structs(
         })
         .collect();
 
+    scored.retain(|s| s.score >= config.min_score as f32);
+
     scored.sort_by(|a, b| {
         b.score
             .partial_cmp(&a.score)
             .unwrap_or(std::cmp::Ordering::Equal)
     });
 
-    Ok(mmr_rerank(
-        &scored,
-        config.mmr_lambda,
-        config.max_retrieval_results,
-    ))
+    Ok(mmr_rerank(&scored, config.mmr_lambda, config.top_k))
 }
 
 /// Hybrid retriever combining vector similarity search and BM25 keyword search.
@@ -286,7 +340,7 @@ const FALLBACK_LOG_INTERVAL_SECS: u64 = 60;
 
 pub struct HybridRetriever {
     store: Arc<MemoryStore>,
-    embedder: Arc<OnnxEmbedder>,
+    embedder: Arc<dyn EmbeddingAdapter + Send + Sync>,
     config: MemoryConfig,
     /// Whether to use vec0 KNN search (from config toggle).
     vec0_enabled: bool,
@@ -294,11 +348,21 @@ pub struct HybridRetriever {
     fallback_count: Arc<AtomicU64>,
     /// Timestamp (epoch secs) of last fallback log for suppression.
     last_fallback_log: Arc<AtomicU64>,
+    /// Whether retrieval records access on the returned memories.
+    record_access: bool,
 }
 
 impl HybridRetriever {
     /// Creates a new hybrid retriever.
-    pub fn new(store: Arc<MemoryStore>, embedder: Arc<OnnxEmbedder>, config: MemoryConfig) -> Self {
+    ///
+    /// `embedder` can be any [`EmbeddingAdapter`], such as the local
+    /// [`OnnxEmbedder`](crate::OnnxEmbedder) or a
+    /// [`RemoteEmbedder`](crate::RemoteEmbedder).
+    pub fn new(
+        store: Arc<MemoryStore>,
+        embedder: Arc<dyn EmbeddingAdapter + Send + Sync>,
+        config: MemoryConfig,
+    ) -> Self {
         let vec0_enabled = config.vec0_enabled;
         Self {
             store,
@@ -307,18 +371,28 @@ impl HybridRetriever {
             vec0_enabled,
             fallback_count: Arc::new(AtomicU64::new(0)),
             last_fallback_log: Arc::new(AtomicU64::new(0)),
+            record_access: true,
         }
     }
 
+    /// Makes retrieval read-only: returned memories are not recorded as
+    /// accessed, so inspecting memories does not change their decay.
+    pub fn read_only(mut self) -> Self {
+        self.record_access = false;
+        self
+    }
+
     /// Retrieve relevant memories for a query using hybrid search.
     ///
     /// Pipeline:
     /// 1. Embed the query text
     /// 2. Run vector similarity search (vec0 KNN with auxiliary data when enabled)
     /// 3. Run BM25 keyword search via FTS5
-    /// 4. Fuse results with RRF (k=60)
-    /// 5. Score, sort, and MMR rerank (vec0 uses auxiliary data; fallback fetches full Memory structs)
-    /// 6. Return `Vec<ScoredMemory>`
+    /// 4. Fuse results with weighted RRF (`rrf_k`, `vector_weight`, `bm25_weight`)
+    /// 5. Score, apply `min_score`, sort, and MMR rerank to `top_k`
+    ///    (vec0 uses auxiliary data; fallback fetches full Memory structs)
+    /// 6. Record access on the returned memories (unless [`read_only`](Self::read_only))
+    /// 7. Return `Vec<ScoredMemory>`
     pub async fn retrieve(&self, query: &str) -> Result<Vec<ScoredMemory>, BlufioError> {
         // OTel: Memory retrieval span with result count, top score, and backend type.
         // Created as a handle (not entered) because entered spans are !Send.
@@ -383,8 +457,14 @@ impl HybridRetriever {
             .search_bm25(query, self.config.max_retrieval_results)
             .await?;
 
-        // Step 4: RRF fusion
-        let fused = reciprocal_rank_fusion(&vector_results, &bm25_results);
+        // Step 4: Weighted RRF fusion
+        let fused = weighted_rank_fusion(
+            &vector_results,
+            &bm25_results,
+            self.config.rrf_k as f32,
+            self.config.vector_weight as f32,
+            self.config.bm25_weight as f32,
+        );
 
         if fused.is_empty() {
             return Ok(vec![]);
@@ -400,6 +480,14 @@ impl HybridRetriever {
             score_from_memory_structs(&self.store, &self.config, &fused).await?
         };
 
+        // Step 9: Bump access recency of the returned memories (non-fatal)
+        if self.record_access {
+            let returned_ids: Vec<String> = result.iter().map(|s| s.memory.id.clone()).collect();
+            if let Err(e) = self.store.record_access(&returned_ids).await {
+                warn!(error = %e, "failed to record memory access");
+            }
+        }
+
         // OTel: Record retrieval result attributes on span.
         _memory_span.record("blufio.memory.results_count", result.len() as u64);
         if let Some(top) = result.first() {
@@ -515,17 +603,33 @@ impl HybridRetriever {
 pub fn reciprocal_rank_fusion(
     vector_results: &[(String, f32)],
     bm25_results: &[(String, f64)],
+) -> Vec<(String, f32)> {
+    weighted_rank_fusion(vector_results, bm25_results, RRF_K, 1.0, 1.0)
+}
+
+/// Weighted Reciprocal Rank Fusion with a configurable `k`.
+///
+/// RRF score for document d = `vector_weight / (k + vector_rank)` +
+/// `bm25_weight / (k + bm25_rank)`, each term present only if d is in that
+/// list. A weight of 0 drops that list's contribution (its documents still
+/// appear, with score 0, so they can be filtered by `min_score`).
+pub fn weighted_rank_fusion(
+    vector_results: &[(String, f32)],
+    bm25_results: &[(String, f64)],
+    k: f32,
+    vector_weight: f32,
+    bm25_weight: f32,
 ) -> Vec<(String, f32)> {
     let mut scores: HashMap<String, f32> = HashMap::new();
 
     // RRF from vector results (already sorted by similarity descending)
     for (rank, (id, _)) in vector_results.iter().enumerate() {
-        *scores.entry(id.clone()).or_insert(0.0) += 1.0 / (RRF_K + rank as f32 + 1.0);
+        *scores.entry(id.clone()).or_insert(0.0) += vector_weight / (k + rank as f32 + 1.0);
     }
 
     // RRF from BM25 results (already sorted by bm25 score ascending = most relevant first)
     for (rank, (id, _)) in bm25_results.iter().enumerate() {
-        *scores.entry(id.clone()).or_insert(0.0) += 1.0 / (RRF_K + rank as f32 + 1.0);
+        *scores.entry(id.clone()).or_insert(0.0) += bm25_weight / (k + rank as f32 + 1.0);
     }
 
     // Sort by fused score descending
@@ -624,6 +728,7 @@ fn mmr_rerank(scored: &[ScoredMemory], lambda: f64, k: usize) -> Vec<ScoredMemor
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::embedder::FixedEmbedder;
 
     #[test]
     fn rrf_fusion_overlapping_lists() {
@@ -695,6 +800,36 @@ mod tests {
         assert_eq!(fused[0].0, "x");
     }
 
+    #[test]
+    fn weighted_fusion_weights_reorder_results() {
+        // "v" tops the vector list, "b" tops the BM25 list
+        let vector = vec![("v".to_string(), 0.9f32), ("b".to_string(), 0.5f32)];
+        let bm25 = vec![("b".to_string(), -5.0f64), ("v".to_string(), -1.0f64)];
+
+        let fused = weighted_rank_fusion(&vector, &bm25, 60.0, 2.0, 1.0);
+        assert_eq!(fused[0].0, "v");
+
+        let fused = weighted_rank_fusion(&vector, &bm25, 60.0, 1.0, 2.0);
+        assert_eq!(fused[0].0, "b");
+
+        // Equal weights with default k match the unweighted fusion
+        let weighted = weighted_rank_fusion(&vector, &bm25, RRF_K, 1.0, 1.0);
+        let plain = reciprocal_rank_fusion(&vector, &bm25);
+        for ((id_w, score_w), (id_p, score_p)) in weighted.iter().zip(&plain) {
+            assert_eq!(id_w, id_p);
+            assert!((score_w - score_p).abs() < f32::EPSILON);
+        }
+    }
+
+    #[test]
+    fn weighted_fusion_k_controls_rank_spread() {
+        let vector = vec![("a".to_string(), 0.9f32), ("b".to_string(), 0.8f32)];
+        let small_k = weighted_rank_fusion(&vector, &[], 1.0, 1.0, 1.0);
+        let large_k = weighted_rank_fusion(&vector, &[], 100.0, 1.0, 1.0);
+        let ratio = |fused: &[(String, f32)]| fused[0].1 / fused[1].1;
+        assert!(ratio(&small_k) > ratio(&large_k));
+    }
+
     #[test]
     fn rrf_preserves_correct_ordering() {
         // d1 in both at rank 0, d2 in vector at rank 1, d3 in bm25 at rank 1, d4 in both at rank 2
@@ -1060,7 +1195,9 @@ mod tests {
                     classification TEXT NOT NULL DEFAULT 'internal',
                     created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
                     updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
-                    deleted_at TEXT
+                    deleted_at TEXT,
+                    last_accessed_at TEXT,
+                    access_count INTEGER NOT NULL DEFAULT 0
                 );
 
                 CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
@@ -1184,7 +1321,9 @@ mod tests {
                     classification TEXT NOT NULL DEFAULT 'internal',
                     created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
                     updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
-                    deleted_at TEXT
+                    deleted_at TEXT,
+                    last_accessed_at TEXT,
+                    access_count INTEGER NOT NULL DEFAULT 0
                 );
 
                 CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
@@ -1595,4 +1734,215 @@ mod tests {
         assert!(vec0_pairs[0].1 > 0.0);
         assert!(in_mem_pairs[0].1 > 0.0);
     }
+
+    #[tokio::test]
+    async fn retrieve_with_any_embedding_adapter() {
+        let conn = setup_retriever_test_db().await;
+        let store = Arc::new(MemoryStore::with_vec0(conn, None, false));
+        store
+            .save(&make_test_memory_full("mem-adapter", "Coffee preference"))
+            .await
+            .unwrap();
+
+        let embedder: Arc<dyn EmbeddingAdapter + Send + Sync> =
+            Arc::new(FixedEmbedder(vec![0.1; 384]));
+        let config = MemoryConfig {
+            vec0_enabled: false,
+            ..default_config()
+        };
+        let retriever = HybridRetriever::new(store, embedder, config);
+
+        let results = retriever.retrieve("coffee").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].memory.id, "mem-adapter");
+    }
+
+    #[tokio::test]
+    async fn retrieve_rejects_mismatched_embedder_dimensions() {
+        let conn = setup_retriever_test_db().await;
+        let store = Arc::new(MemoryStore::with_vec0(conn, None, false));
+        store
+            .save(&make_test_memory_full("mem-dim", "Coffee preference"))
+            .await
+            .unwrap();
+
+        let remote: Arc<dyn EmbeddingAdapter + Send + Sync> =
+            Arc::new(FixedEmbedder(vec![0.1; 1536]));
+        let err = crate::embedder::verify_dimensions(remote, &store)
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("dimension mismatch"));
+    }
+
+    #[test]
+    fn access_decay_penalizes_idle_memories() {
+        let config = MemoryConfig {
+            access_decay_factor: 0.99,
+            ..default_config()
+        };
+        let now = Utc::now();
+        let recent = (now - chrono::Duration::days(1)).to_rfc3339();
+        let idle = (now - chrono::Duration::days(60)).to_rfc3339();
+
+        let recent_decay = access_decay(Some(&recent), &MemorySource::Extracted, now, &config);
+        let idle_decay = access_decay(Some(&idle), &MemorySource::Extracted, now, &config);
+        assert!(recent_decay > idle_decay);
+        assert!(idle_decay >= config.decay_floor as f32);
+
+        // File-sourced and unknown-access memories are not decayed
+        assert_eq!(
+            access_decay(Some(&idle), &MemorySource::FileWatcher, now, &config),
+            1.0
+        );
+        assert_eq!(
+            access_decay(None, &MemorySource::Extracted, now, &config),
+            1.0
+        );
+
+        // The default factor disables access decay
+        assert_eq!(
+            access_decay(
+                Some(&idle),
+                &MemorySource::Extracted,
+                now,
+                &default_config()
+            ),
+            1.0
+        );
+    }
+
+    #[tokio::test]
+    async fn idle_memory_ranks_below_recent_and_never_accessed() {
+        let conn = setup_retriever_test_db().await;
+        let store = Arc::new(MemoryStore::with_vec0(conn.clone(), None, false));
+        for id in ["mem-idle", "mem-recent", "mem-new"] {
+            store
+                .save(&make_test_memory_full(id, "Coffee preference"))
+                .await
+                .unwrap();
+        }
+        store
+            .record_access(&["mem-idle".to_string(), "mem-recent".to_string()])
+            .await
+            .unwrap();
+        conn.call(|conn| -> Result<(), rusqlite::Error> {
+            conn.execute(
+                "UPDATE memories SET last_accessed_at = '2020-01-01T00:00:00.000Z' \
+                 WHERE id = 'mem-idle'",
+                [],
+            )?;
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        let embedder: Arc<dyn EmbeddingAdapter + Send + Sync> =
+            Arc::new(FixedEmbedder(vec![0.1; 384]));
+        let config = MemoryConfig {
+            vec0_enabled: false,
+            access_decay_factor: 0.99,
+            ..default_config()
+        };
+        let retriever = HybridRetriever::new(store.clone(), embedder, config);
+
+        // Only the memory left idle since its last retrieval is decayed.
+        let results = retriever.retrieve("coffee").await.unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[2].memory.id, "mem-idle");
+        assert!(results[1].score > results[2].score);
+
+        // Retrieval itself counts as access
+        assert_eq!(store.access_count("mem-recent").await.unwrap(), Some(2));
+        assert_eq!(store.access_count("mem-new").await.unwrap(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn read_only_retrieval_does_not_record_access() {
+        let conn = setup_retriever_test_db().await;
+        let store = Arc::new(MemoryStore::with_vec0(conn, None, false));
+        store
+            .save(&make_test_memory_full("mem-inspected", "Coffee preference"))
+            .await
+            .unwrap();
+        let before = store.access_count("mem-inspected").await.unwrap();
+
+        let embedder: Arc<dyn EmbeddingAdapter + Send + Sync> =
+            Arc::new(FixedEmbedder(vec![0.1; 384]));
+        let config = MemoryConfig {
+            vec0_enabled: false,
+            ..default_config()
+        };
+        let retriever = HybridRetriever::new(store.clone(), embedder, config).read_only();
+
+        let results = retriever.retrieve("coffee").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(store.access_count("mem-inspected").await.unwrap(), before);
+    }
+
+    /// Store a vector-only match and a keyword match for the query "coffee".
+    async fn setup_fusion_retriever(config: MemoryConfig) -> HybridRetriever {
+        let conn = setup_retriever_test_db().await;
+        let store = Arc::new(MemoryStore::with_vec0(conn, None, false));
+
+        let mut query = vec![0.0; 384];
+        query[0] = 1.0;
+
+        // Closest embedding, but no keyword match
+        let mut semantic = make_test_memory_full("mem-semantic", "Prefers dark roast");
+        semantic.embedding = query.clone();
+        store.save(&semantic).await.unwrap();
+
+        // Weaker embedding match, but matches the keyword
+        let mut keyword = make_test_memory_full("mem-keyword", "Drinks coffee daily");
+        keyword.embedding = vec![0.0; 384];
+        keyword.embedding[0] = std::f32::consts::FRAC_1_SQRT_2;
+        keyword.embedding[1] = std::f32::consts::FRAC_1_SQRT_2;
+        store.save(&keyword).await.unwrap();
+
+        let embedder: Arc<dyn EmbeddingAdapter + Send + Sync> = Arc::new(FixedEmbedder(query));
+        HybridRetriever::new(
+            store,
+            embedder,
+            MemoryConfig {
+                vec0_enabled: false,
+                ..config
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn fusion_weights_reorder_retrieved_memories() {
+        // Equal weights: the keyword memory appears in both lists and wins
+        let retriever = setup_fusion_retriever(default_config()).await;
+        let results = retriever.retrieve("coffee").await.unwrap();
+        assert_eq!(results[0].memory.id, "mem-keyword");
+
+        // Ignoring BM25 leaves pure vector order
+        let retriever = setup_fusion_retriever(MemoryConfig {
+            bm25_weight: 0.0,
+            ..default_config()
+        })
+        .await;
+        let results = retriever.retrieve("coffee").await.unwrap();
+        assert_eq!(results[0].memory.id, "mem-semantic");
+    }
+
+    #[tokio::test]
+    async fn top_k_and_min_score_limit_results() {
+        let retriever = setup_fusion_retriever(MemoryConfig {
+            top_k: 1,
+            ..default_config()
+        })
+        .await;
+        let results = retriever.retrieve("coffee").await.unwrap();
+        assert_eq!(results.len(), 1);
+
+        let retriever = setup_fusion_retriever(MemoryConfig {
+            min_score: 1.0,
+            ..default_config()
+        })
+        .await;
+        assert!(retriever.retrieve("coffee").await.unwrap().is_empty());
+    }
 }
@@ -13,7 +13,10 @@ use metrics::gauge;
 use tokio_rusqlite::Connection;
 use tracing::info;
 
-use crate::types::{Memory, MemorySource, MemoryStatus, blob_to_vec, vec_to_blob};
+use crate::types::{
+    DedupOutcome, DedupStrategy, Memory, MemorySource, MemoryStatus, blob_to_vec,
+    find_most_similar, vec_to_blob,
+};
 use crate::vec0;
 
 /// Helper to convert tokio_rusqlite errors into BlufioError::Storage.
@@ -215,6 +218,33 @@ impl MemoryStore {
             .map_err(storage_err)
     }
 
+    /// List memories newest first, optionally only those with `status`,
+    /// excluding Restricted and retention-deleted rows.
+    ///
+    /// Unlike [`MemoryStore::get_active`], superseded, forgotten, and archived
+    /// memories are included unless filtered out, so users can inspect them.
+    pub async fn list(
+        &self,
+        status: Option<MemoryStatus>,
+        limit: usize,
+    ) -> Result<Vec<Memory>, BlufioError> {
+        let status = status.map(|s| s.as_str().to_string());
+        self.conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, content, embedding, source, confidence, status, superseded_by, session_id, classification, created_at, updated_at FROM memories WHERE (?1 IS NULL OR status = ?1) AND classification != 'restricted' AND deleted_at IS NULL ORDER BY created_at DESC LIMIT ?2",
+                )?;
+                let memories = stmt
+                    .query_map(rusqlite::params![status, limit as i64], |row| {
+                        Ok(row_to_memory(row))
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(memories)
+            })
+            .await
+            .map_err(storage_err)
+    }
+
     /// Get all active memory embeddings (lightweight -- no content), excluding Restricted.
     ///
     /// Returns (id, embedding) pairs for vector search.
@@ -342,6 +372,141 @@ impl MemoryStore {
         Ok(())
     }
 
+    /// Save a memory unless it nearly duplicates an active one.
+    ///
+    /// The memory's embedding is compared against `active`, the active
+    /// embeddings from [`MemoryStore::get_active_embeddings`] loaded once per
+    /// batch; if the best cosine similarity is at or above `threshold`, nothing
+    /// is inserted and `strategy` decides whether the existing memory is left
+    /// alone or has the new memory merged into it (see
+    /// [`MemoryStore::merge_into`]). An inserted memory is added to `active`,
+    /// so the rest of the batch is checked against it too.
+    pub async fn save_deduplicated(
+        &self,
+        memory: &Memory,
+        active: &mut Vec<(String, Vec<f32>)>,
+        threshold: f32,
+        strategy: DedupStrategy,
+    ) -> Result<DedupOutcome, BlufioError> {
+        if let Some((existing_id, similarity)) = find_most_similar(&memory.embedding, active)
+            && similarity >= threshold
+        {
+            return match strategy {
+                DedupStrategy::Skip => Ok(DedupOutcome::Skipped {
+                    existing_id,
+                    similarity,
+                }),
+                DedupStrategy::Merge => {
+                    self.merge_into(&existing_id, memory).await?;
+                    Ok(DedupOutcome::Merged {
+                        existing_id,
+                        similarity,
+                    })
+                }
+            };
+        }
+
+        self.save(memory).await?;
+        active.push((memory.id.clone(), memory.embedding.clone()));
+        Ok(DedupOutcome::Inserted)
+    }
+
+    /// Merge a duplicate memory into an existing one.
+    ///
+    /// Bumps the existing memory's `updated_at` and records the duplicate's
+    /// source and session in `memory_sources`. The duplicate itself is not stored.
+    pub async fn merge_into(
+        &self,
+        existing_id: &str,
+        duplicate: &Memory,
+    ) -> Result<(), BlufioError> {
+        let mem_id = existing_id.to_string();
+        let existing_id = existing_id.to_string();
+        let source = duplicate.source.as_str().to_string();
+        let session_id = duplicate.session_id.clone();
+        self.conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                tx.execute(
+                    "UPDATE memories SET updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?1",
+                    rusqlite::params![existing_id],
+                )?;
+                tx.execute(
+                    "INSERT OR IGNORE INTO memory_sources (memory_id, source, session_id) VALUES (?1, ?2, ?3)",
+                    rusqlite::params![existing_id, source, session_id],
+                )?;
+                tx.commit()?;
+                Ok(())
+            })
+            .await
+            .map_err(storage_err)?;
+
+        if let Some(ref bus) = self.event_bus {
+            bus.publish(BusEvent::Memory(MemoryEvent::Updated {
+                event_id: new_event_id(),
+                timestamp: now_timestamp(),
+                memory_id: mem_id,
+            }))
+            .await;
+        }
+
+        Ok(())
+    }
+
+    /// All (source, session_id) references of a memory: its own origin first,
+    /// followed by those of duplicates merged into it, oldest first.
+    pub async fn get_sources(
+        &self,
+        id: &str,
+    ) -> Result<Vec<(MemorySource, Option<String>)>, BlufioError> {
+        let id = id.to_string();
+        self.conn
+            .call(move |conn| {
+                let origin = conn
+                    .query_row(
+                        "SELECT source, session_id FROM memories WHERE id = ?1",
+                        rusqlite::params![id],
+                        |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)),
+                    )
+                    .optional()?;
+                let mut stmt = conn.prepare(
+                    "SELECT source, session_id FROM memory_sources WHERE memory_id = ?1 ORDER BY rowid",
+                )?;
+                let merged = stmt
+                    .query_map(rusqlite::params![id], |row| Ok((row.get(0)?, row.get(1)?)))?
+                    .collect::<Result<Vec<(String, Option<String>)>, _>>()?;
+                let results = origin
+                    .into_iter()
+                    .chain(merged)
+                    .map(|(source, session_id)| (MemorySource::from_str_value(&source), session_id))
+                    .collect();
+                Ok(results)
+            })
+            .await
+            .map_err(storage_err)
+    }
+
+    /// Dimensionality of the stored embedding vectors, or `None` when the
+    /// store holds no memories yet.
+    ///
+    /// Taken from the most recently saved memory; every stored vector must
+    /// share it for similarity search to be meaningful.
+    pub async fn embedding_dimensions(&self) -> Result<Option<usize>, BlufioError> {
+        self.conn
+            .call(move |conn| {
+                let bytes: Option<i64> = conn
+                    .query_row(
+                        "SELECT length(embedding) FROM memories WHERE deleted_at IS NULL ORDER BY created_at DESC LIMIT 1",
+                        [],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                Ok(bytes.map(|b| b as usize / std::mem::size_of::<f32>()))
+            })
+            .await
+            .map_err(storage_err)
+    }
+
     /// Count all active non-restricted memories.
     pub async fn count_active(&self) -> Result<usize, BlufioError> {
         self.conn
@@ -541,6 +706,137 @@ impl MemoryStore {
             .map_err(storage_err)
     }
 
+    /// Record that memories were retrieved: sets `last_accessed_at` to now and
+    /// increments `access_count`.
+    pub async fn record_access(&self, ids: &[String]) -> Result<(), BlufioError> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let ids = ids.to_vec();
+        self.conn
+            .call(move |conn| {
+                let placeholders: Vec<String> = (1..=ids.len()).map(|i| format!("?{i}")).collect();
+                let sql = format!(
+                    "UPDATE memories SET last_accessed_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), \
+                     access_count = access_count + 1 WHERE id IN ({})",
+                    placeholders.join(", ")
+                );
+                let params: Vec<&dyn rusqlite::types::ToSql> = ids
+                    .iter()
+                    .map(|id| id as &dyn rusqlite::types::ToSql)
+                    .collect();
+                conn.execute(&sql, params.as_slice())?;
+                Ok(())
+            })
+            .await
+            .map_err(storage_err)
+    }
+
+    /// Get when each memory was last retrieved.
+    ///
+    /// Returns (id, timestamp) pairs for access-recency decay; memories that
+    /// were never retrieved are left out.
+    pub async fn get_last_accessed(
+        &self,
+        ids: &[String],
+    ) -> Result<Vec<(String, String)>, BlufioError> {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let ids = ids.to_vec();
+        self.conn
+            .call(move |conn| {
+                let placeholders: Vec<String> = (1..=ids.len()).map(|i| format!("?{i}")).collect();
+                let sql = format!(
+                    "SELECT id, last_accessed_at FROM memories \
+                     WHERE id IN ({}) AND last_accessed_at IS NOT NULL",
+                    placeholders.join(", ")
+                );
+                let mut stmt = conn.prepare(&sql)?;
+                let params: Vec<&dyn rusqlite::types::ToSql> = ids
+                    .iter()
+                    .map(|id| id as &dyn rusqlite::types::ToSql)
+                    .collect();
+                let results = stmt
+                    .query_map(params.as_slice(), |row| Ok((row.get(0)?, row.get(1)?)))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(results)
+            })
+            .await
+            .map_err(storage_err)
+    }
+
+    /// Get the number of times a memory has been retrieved.
+    pub async fn access_count(&self, id: &str) -> Result<Option<u64>, BlufioError> {
+        let id = id.to_string();
+        self.conn
+            .call(move |conn| {
+                let count: Option<i64> = conn
+                    .query_row(
+                        "SELECT access_count FROM memories WHERE id = ?1",
+                        rusqlite::params![id],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                Ok(count.map(|c| c as u64))
+            })
+            .await
+            .map_err(storage_err)
+    }
+
+    /// Archive active memories not retrieved (or, if never retrieved, not
+    /// created) since `cutoff`, an ISO 8601 timestamp.
+    ///
+    /// File-sourced memories are kept, since the watcher owns their lifecycle.
+    /// When vec0 is enabled the status change is mirrored there so KNN search
+    /// excludes archived memories. Returns the archived IDs.
+    pub async fn archive_idle(&self, cutoff: &str) -> Result<Vec<String>, BlufioError> {
+        let cutoff = cutoff.to_string();
+        let vec0_enabled = self.vec0_enabled;
+        let archived: Vec<(String, i64)> = self
+            .conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                let rows: Vec<(String, i64)> = {
+                    let mut stmt = tx.prepare(
+                        "SELECT id, rowid FROM memories WHERE status = 'active' \
+                         AND source != 'file_watcher' AND deleted_at IS NULL \
+                         AND julianday(COALESCE(last_accessed_at, created_at)) < julianday(?1)",
+                    )?;
+                    stmt.query_map(rusqlite::params![cutoff], |row| {
+                        Ok((row.get(0)?, row.get(1)?))
+                    })?
+                    .collect::<Result<Vec<_>, _>>()?
+                };
+                for (id, rowid) in &rows {
+                    tx.execute(
+                        "UPDATE memories SET status = 'archived', updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE id = ?1",
+                        rusqlite::params![id],
+                    )?;
+                    if vec0_enabled {
+                        let _ = vec0::vec0_update_status(&tx, *rowid, "archived");
+                    }
+                }
+                tx.commit()?;
+                Ok(rows)
+            })
+            .await
+            .map_err(storage_err)?;
+
+        if let Some(ref bus) = self.event_bus {
+            for (id, _) in &archived {
+                bus.publish(BusEvent::Memory(MemoryEvent::Updated {
+                    event_id: new_event_id(),
+                    timestamp: now_timestamp(),
+                    memory_id: id.clone(),
+                }))
+                .await;
+            }
+        }
+
+        Ok(archived.into_iter().map(|(id, _)| id).collect())
+    }
+
     /// Populate the vec0 virtual table from existing memories.
     ///
     /// Copies all active, non-restricted embeddings to `memories_vec0` in
@@ -683,7 +979,9 @@ mod tests {
                     classification TEXT NOT NULL DEFAULT 'internal',
                     created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
                     updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
-                    deleted_at TEXT
+                    deleted_at TEXT,
+                    last_accessed_at TEXT,
+                    access_count INTEGER NOT NULL DEFAULT 0
                 );
 
                 CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
@@ -708,7 +1006,17 @@ mod tests {
                 END;
 
                 CREATE INDEX IF NOT EXISTS idx_memories_status ON memories(status);
-                CREATE INDEX IF NOT EXISTS idx_memories_created ON memories(created_at);",
+                CREATE INDEX IF NOT EXISTS idx_memories_created ON memories(created_at);
+
+                CREATE TABLE IF NOT EXISTS memory_sources (
+                    memory_id TEXT NOT NULL,
+                    source TEXT NOT NULL,
+                    session_id TEXT,
+                    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+                );
+
+                CREATE UNIQUE INDEX IF NOT EXISTS idx_memory_sources_unique
+                    ON memory_sources(memory_id, source, COALESCE(session_id, ''));",
             )?;
             Ok(())
         })
@@ -1441,4 +1749,188 @@ mod tests {
             .unwrap();
         assert!(results.is_empty(), "restricted memories should be excluded");
     }
+
+    #[tokio::test]
+    async fn list_includes_inactive_and_filters_by_status() {
+        let conn = setup_test_db().await;
+        let store = MemoryStore::new(conn);
+
+        let mut older = make_test_memory("mem-old", "Old fact");
+        older.status = MemoryStatus::Superseded;
+        store.save(&older).await.unwrap();
+        let mut newer = make_test_memory("mem-new", "New fact");
+        newer.created_at = "2026-03-02T00:00:00.000Z".to_string();
+        store.save(&newer).await.unwrap();
+        let mut secret = make_test_memory("mem-secret", "Secret fact");
+        secret.classification = DataClassification::Restricted;
+        store.save(&secret).await.unwrap();
+
+        let all = store.list(None, 10).await.unwrap();
+        let ids: Vec<&str> = all.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["mem-new", "mem-old"]);
+
+        let superseded = store
+            .list(Some(MemoryStatus::Superseded), 10)
+            .await
+            .unwrap();
+        assert_eq!(superseded.len(), 1);
+        assert_eq!(superseded[0].id, "mem-old");
+
+        assert_eq!(store.list(None, 1).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn record_access_tracks_recency_and_count() {
+        let conn = setup_test_db().await;
+        let store = MemoryStore::new(conn);
+        store
+            .save(&make_test_memory("mem-1", "Accessed"))
+            .await
+            .unwrap();
+        store
+            .save(&make_test_memory("mem-2", "Never accessed"))
+            .await
+            .unwrap();
+
+        let ids = vec!["mem-1".to_string()];
+        store.record_access(&ids).await.unwrap();
+        store.record_access(&ids).await.unwrap();
+        assert_eq!(store.access_count("mem-1").await.unwrap(), Some(2));
+        assert_eq!(store.access_count("mem-2").await.unwrap(), Some(0));
+        assert_eq!(store.access_count("missing").await.unwrap(), None);
+
+        let last: std::collections::HashMap<String, String> = store
+            .get_last_accessed(&["mem-1".to_string(), "mem-2".to_string()])
+            .await
+            .unwrap()
+            .into_iter()
+            .collect();
+        // Never-accessed memories have no access time
+        assert_eq!(last.len(), 1);
+        assert!(last.contains_key("mem-1"));
+    }
+
+    /// Unit vector mostly along the first axis, tilted toward the second by `tilt`.
+    fn unit_embedding(axis: usize, tilt: f32) -> Vec<f32> {
+        let mut v = vec![0.0; 384];
+        v[axis] = 1.0;
+        v[axis + 1] = tilt;
+        let norm = (1.0 + tilt * tilt).sqrt();
+        v.iter().map(|x| x / norm).collect()
+    }
+
+    #[tokio::test]
+    async fn save_deduplicated_skips_near_duplicate() {
+        let conn = setup_test_db().await;
+        let store = MemoryStore::new(conn);
+
+        // One load for the batch; the first save is seen by the second.
+        let mut active = store.get_active_embeddings().await.unwrap();
+        let mut original = make_test_memory("mem-1", "The user's dog is named Max");
+        original.embedding = unit_embedding(0, 0.0);
+        let outcome = store
+            .save_deduplicated(&original, &mut active, 0.9, DedupStrategy::Skip)
+            .await
+            .unwrap();
+        assert_eq!(outcome, DedupOutcome::Inserted);
+
+        let mut duplicate = make_test_memory("mem-2", "User has a dog called Max");
+        duplicate.embedding = unit_embedding(0, 0.1);
+        let outcome = store
+            .save_deduplicated(&duplicate, &mut active, 0.9, DedupStrategy::Skip)
+            .await
+            .unwrap();
+        assert!(matches!(
+            outcome,
+            DedupOutcome::Skipped { ref existing_id, similarity }
+                if existing_id == "mem-1" && similarity > 0.99
+        ));
+        assert_eq!(store.count_active().await.unwrap(), 1);
+        assert!(store.get_by_id("mem-2").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn save_deduplicated_merges_near_duplicate() {
+        let conn = setup_test_db().await;
+        let store = MemoryStore::new(conn);
+
+        let mut original = make_test_memory("mem-1", "The user's dog is named Max");
+        original.embedding = unit_embedding(0, 0.0);
+        store.save(&original).await.unwrap();
+
+        let mut duplicate = make_test_memory("mem-2", "User has a dog called Max");
+        duplicate.embedding = unit_embedding(0, 0.1);
+        duplicate.source = MemorySource::Extracted;
+        duplicate.session_id = Some("later-session".to_string());
+        let mut active = store.get_active_embeddings().await.unwrap();
+        let outcome = store
+            .save_deduplicated(&duplicate, &mut active, 0.9, DedupStrategy::Merge)
+            .await
+            .unwrap();
+        assert!(matches!(
+            outcome,
+            DedupOutcome::Merged { ref existing_id, .. } if existing_id == "mem-1"
+        ));
+
+        assert_eq!(store.count_active().await.unwrap(), 1);
+        let merged = store.get_by_id("mem-1").await.unwrap().unwrap();
+        assert_eq!(merged.content, "The user's dog is named Max");
+        assert!(merged.updated_at > original.updated_at);
+
+        let sources = store.get_sources("mem-1").await.unwrap();
+        assert_eq!(
+            sources,
+            vec![
+                (MemorySource::Explicit, Some("test-session".to_string())),
+                (MemorySource::Extracted, Some("later-session".to_string())),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn merging_a_sessionless_source_twice_records_it_once() {
+        let conn = setup_test_db().await;
+        let store = MemoryStore::new(conn);
+        store
+            .save(&make_test_memory("mem-1", "The user's dog is named Max"))
+            .await
+            .unwrap();
+
+        let mut duplicate = make_test_memory("mem-2", "User has a dog called Max");
+        duplicate.source = MemorySource::Extracted;
+        duplicate.session_id = None;
+        store.merge_into("mem-1", &duplicate).await.unwrap();
+        store.merge_into("mem-1", &duplicate).await.unwrap();
+
+        let sources = store.get_sources("mem-1").await.unwrap();
+        assert_eq!(
+            sources,
+            vec![
+                (MemorySource::Explicit, Some("test-session".to_string())),
+                (MemorySource::Extracted, None),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn save_deduplicated_inserts_distinct_memory() {
+        let conn = setup_test_db().await;
+        let store = MemoryStore::new(conn);
+
+        let mut first = make_test_memory("mem-1", "The user's dog is named Max");
+        first.embedding = unit_embedding(0, 0.0);
+        store.save(&first).await.unwrap();
+
+        let mut second = make_test_memory("mem-2", "The user lives in Lisbon");
+        second.embedding = unit_embedding(10, 0.0);
+        let mut active = store.get_active_embeddings().await.unwrap();
+        let outcome = store
+            .save_deduplicated(&second, &mut active, 0.9, DedupStrategy::Merge)
+            .await
+            .unwrap();
+        assert_eq!(outcome, DedupOutcome::Inserted);
+        assert_eq!(active.len(), 2);
+        assert_eq!(store.count_active().await.unwrap(), 2);
+        assert_eq!(store.get_sources("mem-2").await.unwrap().len(), 1);
+    }
 }
@@ -86,6 +86,8 @@ pub enum MemoryStatus {
     Superseded,
     /// User explicitly asked to forget this.
     Forgotten,
+    /// Not retrieved for longer than `memory.archive_after_days`.
+    Archived,
 }
 
 impl MemoryStatus {
@@ -95,6 +97,7 @@ impl MemoryStatus {
             MemoryStatus::Active => "active",
             MemoryStatus::Superseded => "superseded",
             MemoryStatus::Forgotten => "forgotten",
+            MemoryStatus::Archived => "archived",
         }
     }
 
@@ -103,11 +106,53 @@ impl MemoryStatus {
         match s {
             "superseded" => MemoryStatus::Superseded,
             "forgotten" => MemoryStatus::Forgotten,
+            "archived" => MemoryStatus::Archived,
             _ => MemoryStatus::Active,
         }
     }
 }
 
+/// How the store handles a new memory that nearly duplicates an existing one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DedupStrategy {
+    /// Drop the new memory and keep the existing one untouched.
+    Skip,
+    /// Keep the existing memory, refresh its `updated_at`, and record the
+    /// new memory's source and session on it.
+    Merge,
+}
+
+impl DedupStrategy {
+    /// Parse from config string (`memory.dedup_strategy`). Unknown values skip.
+    pub fn from_str_value(s: &str) -> Self {
+        match s {
+            "merge" => DedupStrategy::Merge,
+            _ => DedupStrategy::Skip,
+        }
+    }
+}
+
+/// Outcome of a deduplicated save.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DedupOutcome {
+    /// No near-duplicate existed; the memory was inserted.
+    Inserted,
+    /// A near-duplicate existed and the new memory was dropped.
+    Skipped {
+        /// ID of the existing memory.
+        existing_id: String,
+        /// Cosine similarity to the existing memory.
+        similarity: f32,
+    },
+    /// A near-duplicate existed and the new memory was merged into it.
+    Merged {
+        /// ID of the existing memory.
+        existing_id: String,
+        /// Cosine similarity to the existing memory.
+        similarity: f32,
+    },
+}
+
 /// A memory with a retrieval score from hybrid search.
 #[derive(Debug, Clone)]
 pub struct ScoredMemory {
@@ -162,6 +207,21 @@ pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
 }
 
+/// Find the most similar embedding in a set of (id, embedding) pairs.
+///
+/// Embeddings of a different length than `query` are ignored.
+/// Returns (id, similarity) for the closest match, or None if empty.
+pub fn find_most_similar(
+    query: &[f32],
+    embeddings: &[(String, Vec<f32>)],
+) -> Option<(String, f32)> {
+    embeddings
+        .iter()
+        .filter(|(_, emb)| emb.len() == query.len())
+        .map(|(id, emb)| (id.clone(), cosine_similarity(query, emb)))
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -267,6 +327,7 @@ mod tests {
         assert_eq!(MemoryStatus::Active.as_str(), "active");
         assert_eq!(MemoryStatus::Superseded.as_str(), "superseded");
         assert_eq!(MemoryStatus::Forgotten.as_str(), "forgotten");
+        assert_eq!(MemoryStatus::Archived.as_str(), "archived");
         assert_eq!(MemoryStatus::from_str_value("active"), MemoryStatus::Active);
         assert_eq!(
             MemoryStatus::from_str_value("superseded"),
@@ -276,6 +337,10 @@ mod tests {
             MemoryStatus::from_str_value("forgotten"),
             MemoryStatus::Forgotten
         );
+        assert_eq!(
+            MemoryStatus::from_str_value("archived"),
+            MemoryStatus::Archived
+        );
     }
 
     #[test]
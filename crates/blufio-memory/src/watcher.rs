@@ -14,13 +14,13 @@ use std::time::Duration;
 use blufio_config::model::FileWatcherConfig;
 use blufio_core::classification::DataClassification;
 use blufio_core::error::BlufioError;
+use blufio_core::traits::EmbeddingAdapter;
 use blufio_core::types::EmbeddingInput;
 use sha2::{Digest, Sha256};
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 use tracing::{info, warn};
 
-use crate::embedder::OnnxEmbedder;
 use crate::store::MemoryStore;
 use crate::types::{Memory, MemorySource, MemoryStatus};
 
@@ -62,7 +62,7 @@ async fn process_file_change(
     path: &Path,
     config: &FileWatcherConfig,
     store: &MemoryStore,
-    embedder: &OnnxEmbedder,
+    embedder: &(dyn EmbeddingAdapter + Send + Sync),
 ) -> Result<(), BlufioError> {
     if !should_index(path, config) {
         return Ok(());
@@ -106,7 +106,6 @@ async fn process_file_change(
     };
 
     // Generate embedding
-    use blufio_core::traits::EmbeddingAdapter;
     let embed_output = embedder
         .embed(EmbeddingInput {
             texts: vec![content.clone()],
@@ -169,7 +168,7 @@ async fn delete_memory_row(store: &MemoryStore, id: &str) -> Result<(), BlufioEr
 pub async fn initial_scan(
     config: &FileWatcherConfig,
     store: &MemoryStore,
-    embedder: &OnnxEmbedder,
+    embedder: &(dyn EmbeddingAdapter + Send + Sync),
 ) -> Result<usize, BlufioError> {
     let mut count = 0;
 
@@ -190,7 +189,7 @@ async fn walk_and_index(
     dir: &Path,
     config: &FileWatcherConfig,
     store: &MemoryStore,
-    embedder: &OnnxEmbedder,
+    embedder: &(dyn EmbeddingAdapter + Send + Sync),
 ) -> Result<usize, BlufioError> {
     let mut count = 0;
 
@@ -230,7 +229,7 @@ async fn walk_and_index(
 pub fn start_file_watcher(
     config: &FileWatcherConfig,
     store: Arc<MemoryStore>,
-    embedder: Arc<OnnxEmbedder>,
+    embedder: Arc<dyn EmbeddingAdapter + Send + Sync>,
     cancel: CancellationToken,
 ) -> Result<(), BlufioError> {
     if config.paths.is_empty() {
@@ -280,7 +279,7 @@ pub fn start_file_watcher(
             tokio::select! {
                 Some(paths) = rx.recv() => {
                     for path in paths {
-                        if let Err(e) = process_file_change(&path, &config, &store, &embedder).await {
+                        if let Err(e) = process_file_change(&path, &config, &store, embedder.as_ref()).await {
                             warn!(path = %path.display(), error = %e, "file watcher: failed to process change");
                         }
                     }
@@ -245,27 +245,26 @@ impl ProviderAdapter for OpenAIProvider {
         // Key: tool call index -> (id, name, accumulated_args)
         let mut tool_calls: HashMap<usize, (String, String, String)> = HashMap::new();
         let mut is_first = true;
-
-        let mapped = chunk_stream.filter_map(move |result| {
-            let chunks = match result {
-                Ok(sse_chunk) => {
-                    map_sse_chunk_to_provider_chunks(sse_chunk, &mut tool_calls, &mut is_first)
-                }
-                Err(e) => vec![Err(e)],
-            };
-            async move {
-                // filter_map expects Option<Item>, but we may produce multiple items.
-                // We return the first item if any; the rest we handle via flattening below.
-                // Actually, let's return Some for non-empty, None for empty.
-                if chunks.is_empty() {
-                    None
-                } else {
-                    // For simplicity, return the first chunk. Multi-chunk case is handled
-                    // by the finish_reason logic emitting tool_use chunks inline.
-                    Some(futures::stream::iter(chunks))
-                }
-            }
-        });
+        // Stop reason held back until the trailing usage chunk arrives.
+        let mut pending_stop: Option<String> = None;
+
+        // A trailing `None` marks end of stream so a held-back stop can be flushed.
+        let mapped = chunk_stream
+            .map(Some)
+            .chain(futures::stream::once(async { None }))
+            .map(move |item| {
+                let chunks = match item {
+                    Some(Ok(sse_chunk)) => map_sse_chunk_to_provider_chunks(
+                        sse_chunk,
+                        &mut tool_calls,
+                        &mut is_first,
+                        &mut pending_stop,
+                    ),
+                    Some(Err(e)) => vec![Err(e)],
+                    None => flush_pending_stop(&mut pending_stop),
+                };
+                futures::stream::iter(chunks)
+            });
 
         // Flatten the stream of streams into a single stream.
         let flattened = mapped.flatten();
@@ -281,10 +280,17 @@ impl ProviderAdapter for OpenAIProvider {
 /// - Tool call deltas -> accumulate id/name/args in `tool_calls`
 /// - finish_reason -> emit accumulated tool_use chunks, then stop
 /// - Usage -> MessageDelta with token usage
+///
+/// With `stream_options.include_usage`, OpenAI sends usage in a separate final
+/// chunk with empty `choices` *after* the finish_reason chunk. In that case the
+/// stop is recorded in `pending_stop` and MessageDelta/MessageStop are emitted
+/// once the usage chunk arrives, so consumers that stop at MessageStop still
+/// see token usage.
 fn map_sse_chunk_to_provider_chunks(
     sse_chunk: crate::types::SseChunk,
     tool_calls: &mut HashMap<usize, (String, String, String)>,
     is_first: &mut bool,
+    pending_stop: &mut Option<String>,
 ) -> Vec<Result<ProviderStreamChunk, BlufioError>> {
     let mut chunks = Vec::new();
 
@@ -292,12 +298,7 @@ fn map_sse_chunk_to_provider_chunks(
     if *is_first {
         *is_first = false;
         // Include usage from the initial chunk if present.
-        let usage = sse_chunk.usage.as_ref().map(|u| TokenUsage {
-            input_tokens: u.prompt_tokens,
-            output_tokens: u.completion_tokens,
-            cache_read_tokens: 0,
-            cache_creation_tokens: 0,
-        });
+        let usage = sse_chunk.usage.as_ref().map(map_usage);
         chunks.push(Ok(ProviderStreamChunk {
             event_type: StreamEventType::MessageStart,
             text: None,
@@ -377,38 +378,71 @@ fn map_sse_chunk_to_provider_chunks(
                 }
             }
 
-            // Emit MessageDelta with stop_reason and usage.
-            let usage = sse_chunk.usage.as_ref().map(|u| TokenUsage {
-                input_tokens: u.prompt_tokens,
-                output_tokens: u.completion_tokens,
-                cache_read_tokens: 0,
-                cache_creation_tokens: 0,
-            });
-
-            chunks.push(Ok(ProviderStreamChunk {
-                event_type: StreamEventType::MessageDelta,
-                text: None,
-                usage,
-                error: None,
-                tool_use: None,
-                stop_reason: Some(stop_reason.to_string()),
-            }));
-
-            // Emit MessageStop.
-            chunks.push(Ok(ProviderStreamChunk {
-                event_type: StreamEventType::MessageStop,
-                text: None,
-                usage: None,
-                error: None,
-                tool_use: None,
-                stop_reason: Some(stop_reason.to_string()),
-            }));
+            // Emit MessageDelta + MessageStop now if usage is inline,
+            // otherwise wait for the trailing usage chunk.
+            match sse_chunk.usage.as_ref() {
+                Some(u) => chunks.extend(stop_chunks(stop_reason, Some(map_usage(u)))),
+                None => *pending_stop = Some(stop_reason.to_string()),
+            }
         }
     }
 
+    // Trailing usage-only chunk completes a held-back stop.
+    if sse_chunk.choices.is_empty()
+        && let Some(u) = sse_chunk.usage.as_ref()
+        && let Some(stop_reason) = pending_stop.take()
+    {
+        chunks.extend(stop_chunks(&stop_reason, Some(map_usage(u))));
+    }
+
     chunks
 }
 
+/// Emits a held-back stop at end of stream when no usage chunk arrived.
+fn flush_pending_stop(
+    pending_stop: &mut Option<String>,
+) -> Vec<Result<ProviderStreamChunk, BlufioError>> {
+    match pending_stop.take() {
+        Some(stop_reason) => stop_chunks(&stop_reason, None),
+        None => Vec::new(),
+    }
+}
+
+/// Builds the MessageDelta (stop_reason + usage) and MessageStop pair.
+fn stop_chunks(
+    stop_reason: &str,
+    usage: Option<TokenUsage>,
+) -> Vec<Result<ProviderStreamChunk, BlufioError>> {
+    vec![
+        Ok(ProviderStreamChunk {
+            event_type: StreamEventType::MessageDelta,
+            text: None,
+            usage,
+            error: None,
+            tool_use: None,
+            stop_reason: Some(stop_reason.to_string()),
+        }),
+        Ok(ProviderStreamChunk {
+            event_type: StreamEventType::MessageStop,
+            text: None,
+            usage: None,
+            error: None,
+            tool_use: None,
+            stop_reason: Some(stop_reason.to_string()),
+        }),
+    ]
+}
+
+/// Maps OpenAI token usage to provider-agnostic [`TokenUsage`].
+fn map_usage(usage: &crate::types::OpenAIUsage) -> TokenUsage {
+    TokenUsage {
+        input_tokens: usage.prompt_tokens,
+        output_tokens: usage.completion_tokens,
+        cache_read_tokens: 0,
+        cache_creation_tokens: 0,
+    }
+}
+
 /// Maps OpenAI `finish_reason` to provider-agnostic `stop_reason`.
 fn map_finish_reason(reason: &str) -> &str {
     match reason {
@@ -839,8 +873,14 @@ mod tests {
         };
 
         let mut tool_calls = HashMap::new();
+        let mut pending_stop = None;
         let mut is_first = true;
-        let chunks = map_sse_chunk_to_provider_chunks(sse_chunk, &mut tool_calls, &mut is_first);
+        let chunks = map_sse_chunk_to_provider_chunks(
+            sse_chunk,
+            &mut tool_calls,
+            &mut is_first,
+            &mut pending_stop,
+        );
 
         // Should have MessageStart + text delta = 2 chunks
         assert_eq!(chunks.len(), 2);
@@ -855,6 +895,7 @@ mod tests {
     #[test]
     fn map_sse_tool_call_accumulation() {
         let mut tool_calls: HashMap<usize, (String, String, String)> = HashMap::new();
+        let mut pending_stop = None;
         let mut is_first = false;
 
         // First delta: id + name + partial args
@@ -881,7 +922,12 @@ mod tests {
             usage: None,
         };
 
-        let results = map_sse_chunk_to_provider_chunks(chunk1, &mut tool_calls, &mut is_first);
+        let results = map_sse_chunk_to_provider_chunks(
+            chunk1,
+            &mut tool_calls,
+            &mut is_first,
+            &mut pending_stop,
+        );
         assert!(results.is_empty()); // No emit yet, just accumulation.
         assert_eq!(tool_calls[&0].0, "call_abc");
         assert_eq!(tool_calls[&0].1, "bash");
@@ -911,7 +957,12 @@ mod tests {
             usage: None,
         };
 
-        let results = map_sse_chunk_to_provider_chunks(chunk2, &mut tool_calls, &mut is_first);
+        let results = map_sse_chunk_to_provider_chunks(
+            chunk2,
+            &mut tool_calls,
+            &mut is_first,
+            &mut pending_stop,
+        );
         assert!(results.is_empty());
         assert_eq!(tool_calls[&0].2, "{\"command\":\"echo hello\"}");
 
@@ -931,7 +982,12 @@ mod tests {
             }),
         };
 
-        let results = map_sse_chunk_to_provider_chunks(chunk3, &mut tool_calls, &mut is_first);
+        let results = map_sse_chunk_to_provider_chunks(
+            chunk3,
+            &mut tool_calls,
+            &mut is_first,
+            &mut pending_stop,
+        );
         // Should emit: ContentBlockStop (tool_use), MessageDelta, MessageStop = 3
         assert_eq!(results.len(), 3);
 
@@ -956,6 +1012,7 @@ mod tests {
     #[test]
     fn map_sse_stop_finish_reason() {
         let mut tool_calls = HashMap::new();
+        let mut pending_stop = None;
         let mut is_first = false;
 
         let chunk = crate::types::SseChunk {
@@ -973,7 +1030,12 @@ mod tests {
             }),
         };
 
-        let results = map_sse_chunk_to_provider_chunks(chunk, &mut tool_calls, &mut is_first);
+        let results = map_sse_chunk_to_provider_chunks(
+            chunk,
+            &mut tool_calls,
+            &mut is_first,
+            &mut pending_stop,
+        );
         // Should emit: MessageDelta + MessageStop = 2
         assert_eq!(results.len(), 2);
 
@@ -986,6 +1048,104 @@ mod tests {
         assert_eq!(stop.stop_reason.as_deref(), Some("end_turn"));
     }
 
+    /// Runs raw SSE `data:` payloads through the stateful mapper, flushing at end of stream.
+    fn map_sse_payloads(payloads: &[&str]) -> Vec<ProviderStreamChunk> {
+        let mut tool_calls = HashMap::new();
+        let mut is_first = true;
+        let mut pending_stop = None;
+        let mut out = Vec::new();
+        for payload in payloads {
+            let chunk: crate::types::SseChunk = serde_json::from_str(payload).unwrap();
+            out.extend(map_sse_chunk_to_provider_chunks(
+                chunk,
+                &mut tool_calls,
+                &mut is_first,
+                &mut pending_stop,
+            ));
+        }
+        out.extend(flush_pending_stop(&mut pending_stop));
+        out.into_iter().map(Result::unwrap).collect()
+    }
+
+    #[test]
+    fn sse_round_trip_text_with_trailing_usage() {
+        let chunks = map_sse_payloads(&[
+            r#"{"id":"c1","choices":[{"index":0,"delta":{"role":"assistant","content":""},"finish_reason":null}],"usage":null}"#,
+            r#"{"id":"c1","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}],"usage":null}"#,
+            r#"{"id":"c1","choices":[{"index":0,"delta":{"content":" world"},"finish_reason":null}],"usage":null}"#,
+            r#"{"id":"c1","choices":[{"index":0,"delta":{},"finish_reason":"stop"}],"usage":null}"#,
+            r#"{"id":"c1","choices":[],"usage":{"prompt_tokens":12,"completion_tokens":5,"total_tokens":17}}"#,
+        ]);
+
+        assert_eq!(chunks[0].event_type, StreamEventType::MessageStart);
+        let text: String = chunks.iter().filter_map(|c| c.text.clone()).collect();
+        assert_eq!(text, "Hello world");
+
+        let delta = &chunks[chunks.len() - 2];
+        assert_eq!(delta.event_type, StreamEventType::MessageDelta);
+        assert_eq!(delta.stop_reason.as_deref(), Some("end_turn"));
+        let usage = delta.usage.as_ref().unwrap();
+        assert_eq!(usage.input_tokens, 12);
+        assert_eq!(usage.output_tokens, 5);
+
+        let stop = chunks.last().unwrap();
+        assert_eq!(stop.event_type, StreamEventType::MessageStop);
+        assert_eq!(
+            chunks
+                .iter()
+                .filter(|c| c.event_type == StreamEventType::MessageStop)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn sse_round_trip_multi_delta_tool_calls() {
+        let chunks = map_sse_payloads(&[
+            r#"{"choices":[{"index":0,"delta":{"role":"assistant","tool_calls":[{"index":0,"id":"call_1","type":"function","function":{"name":"bash","arguments":""}}]},"finish_reason":null}]}"#,
+            r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"comm"}}]},"finish_reason":null}]}"#,
+            r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":1,"id":"call_2","type":"function","function":{"name":"http","arguments":"{\"url\":\"https://example.com\"}"}}]},"finish_reason":null}]}"#,
+            r#"{"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"and\":\"ls\"}"}}]},"finish_reason":null}]}"#,
+            r#"{"choices":[{"index":0,"delta":{},"finish_reason":"tool_calls"}]}"#,
+            r#"{"choices":[],"usage":{"prompt_tokens":40,"completion_tokens":22,"total_tokens":62}}"#,
+        ]);
+
+        let tool_uses: Vec<&ToolUseData> =
+            chunks.iter().filter_map(|c| c.tool_use.as_ref()).collect();
+        assert_eq!(tool_uses.len(), 2);
+        assert_eq!(tool_uses[0].id, "call_1");
+        assert_eq!(tool_uses[0].name, "bash");
+        assert_eq!(tool_uses[0].input["command"], "ls");
+        assert_eq!(tool_uses[1].id, "call_2");
+        assert_eq!(tool_uses[1].name, "http");
+        assert_eq!(tool_uses[1].input["url"], "https://example.com");
+
+        let delta = &chunks[chunks.len() - 2];
+        assert_eq!(delta.stop_reason.as_deref(), Some("tool_use"));
+        assert_eq!(delta.usage.as_ref().unwrap().output_tokens, 22);
+        assert_eq!(
+            chunks.last().unwrap().event_type,
+            StreamEventType::MessageStop
+        );
+    }
+
+    #[test]
+    fn sse_stop_without_usage_is_flushed_at_end_of_stream() {
+        let chunks = map_sse_payloads(&[
+            r#"{"choices":[{"index":0,"delta":{"content":"hi"},"finish_reason":null}]}"#,
+            r#"{"choices":[{"index":0,"delta":{},"finish_reason":"length"}]}"#,
+        ]);
+
+        let delta = &chunks[chunks.len() - 2];
+        assert_eq!(delta.event_type, StreamEventType::MessageDelta);
+        assert_eq!(delta.stop_reason.as_deref(), Some("max_tokens"));
+        assert!(delta.usage.is_none());
+        assert_eq!(
+            chunks.last().unwrap().event_type,
+            StreamEventType::MessageStop
+        );
+    }
+
     #[test]
     fn token_usage_maps_correctly() {
         let openai_usage = crate::types::OpenAIUsage {
@@ -3,7 +3,7 @@
 
 //! Built-in adapter catalog.
 //!
-//! Returns hardcoded `PluginManifest` entries for the 6 default adapters
+//! Returns hardcoded `PluginManifest` entries for the 7 default adapters
 //! compiled into the Blufio binary. No network calls are made.
 
 use blufio_core::types::AdapterType;
@@ -12,13 +12,14 @@ use crate::manifest::PluginManifest;
 
 /// Returns manifests for all built-in adapters.
 ///
-/// The catalog contains 6 default adapters:
+/// The catalog contains 7 default adapters:
 /// - telegram (Channel)
 /// - anthropic (Provider)
 /// - sqlite (Storage)
 /// - onnx-embedder (Embedding)
 /// - prometheus (Observability)
 /// - keypair-auth (Auth)
+/// - wasm (SkillRuntime)
 pub fn builtin_catalog() -> Vec<PluginManifest> {
     vec![
         PluginManifest {
@@ -100,6 +101,20 @@ pub fn builtin_catalog() -> Vec<PluginManifest> {
             min_blufio_version: Some("0.1.0".to_string()),
             config_keys: vec![],
         },
+        PluginManifest {
+            name: "wasm".to_string(),
+            version: "0.1.0".to_string(),
+            description: "Sandboxed WebAssembly skill runtime".to_string(),
+            adapter_type: AdapterType::SkillRuntime,
+            author: Some("Blufio Contributors".to_string()),
+            capabilities: vec![
+                "fuel_metering".to_string(),
+                "capability_gating".to_string(),
+                "signature_verification".to_string(),
+            ],
+            min_blufio_version: Some("0.1.0".to_string()),
+            config_keys: vec!["skill.enabled".to_string()],
+        },
     ]
 }
 
@@ -126,9 +141,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn builtin_catalog_returns_six_entries() {
+    fn builtin_catalog_returns_seven_entries() {
         let catalog = builtin_catalog();
-        assert_eq!(catalog.len(), 6);
+        assert_eq!(catalog.len(), 7);
     }
 
     #[test]
@@ -143,6 +158,7 @@ mod tests {
         assert!(types.contains(&AdapterType::Embedding));
         assert!(types.contains(&AdapterType::Observability));
         assert!(types.contains(&AdapterType::Auth));
+        assert!(types.contains(&AdapterType::SkillRuntime));
     }
 
     #[test]
@@ -169,7 +185,7 @@ mod tests {
     #[test]
     fn search_catalog_empty_returns_all() {
         let results = search_catalog("");
-        assert_eq!(results.len(), 6);
+        assert_eq!(results.len(), 7);
     }
 
     #[test]
@@ -11,7 +11,7 @@
 pub mod recording;
 
 use async_trait::async_trait;
-use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
 
 use blufio_core::BlufioError;
 use blufio_core::traits::adapter::PluginAdapter;
@@ -19,6 +19,7 @@ use blufio_core::traits::observability::ObservabilityAdapter;
 use blufio_core::types::{AdapterType, HealthStatus, MetricEvent};
 
 pub use recording::{
+    record_cache_usage,
     record_classification_blocked,
     record_classified_error,
     record_error,
@@ -29,6 +30,8 @@ pub use recording::{
     record_mcp_tool_response_size,
     record_message,
     record_tokens,
+    record_ttft,
+    record_turn_latency,
     // Memory validation metrics (MEME-06)
     record_validation_conflicts,
     record_validation_duplicates,
@@ -58,7 +61,7 @@ impl PrometheusAdapter {
     /// Installs the Prometheus recorder globally. Only one recorder can be
     /// installed per process. Returns an error if a recorder is already installed.
     pub fn new() -> Result<Self, BlufioError> {
-        let handle = PrometheusBuilder::new().install_recorder().map_err(|e| {
+        let handle = builder()?.install_recorder().map_err(|e| {
             BlufioError::Internal(format!("failed to install Prometheus recorder: {e}"))
         })?;
 
@@ -80,6 +83,23 @@ impl PrometheusAdapter {
     }
 }
 
+/// Prometheus builder with Blufio's histogram buckets applied.
+///
+/// Latency histograms use [`recording::LLM_LATENCY_BUCKETS`]; others are
+/// rendered as summaries.
+pub fn builder() -> Result<PrometheusBuilder, BlufioError> {
+    let mut builder = PrometheusBuilder::new();
+    for name in recording::LLM_LATENCY_HISTOGRAMS {
+        builder = builder
+            .set_buckets_for_metric(
+                Matcher::Full(name.to_string()),
+                recording::LLM_LATENCY_BUCKETS,
+            )
+            .map_err(|e| BlufioError::Internal(format!("invalid histogram buckets: {e}")))?;
+    }
+    Ok(builder)
+}
+
 #[async_trait]
 impl PluginAdapter for PrometheusAdapter {
     fn name(&self) -> &str {
@@ -149,6 +169,68 @@ impl ObservabilityAdapter for PrometheusAdapter {
 mod tests {
     use super::*;
 
+    #[test]
+    fn cache_usage_metrics_render() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        metrics::with_local_recorder(&recorder, || {
+            recording::register_metrics();
+            record_cache_usage(
+                "claude-sonnet",
+                &blufio_core::types::TokenUsage {
+                    input_tokens: 100,
+                    output_tokens: 20,
+                    cache_read_tokens: 300,
+                    cache_creation_tokens: 0,
+                },
+            );
+            // No cache activity: nothing recorded for this model.
+            record_cache_usage(
+                "claude-haiku",
+                &blufio_core::types::TokenUsage {
+                    input_tokens: 50,
+                    output_tokens: 5,
+                    ..Default::default()
+                },
+            );
+        });
+
+        let output = handle.render();
+        assert!(
+            output.contains(r#"blufio_cache_tokens_total{model="claude-sonnet",type="read"} 300"#),
+            "got: {output}"
+        );
+        assert!(
+            output
+                .contains(r#"blufio_cache_tokens_total{model="claude-sonnet",type="creation"} 0"#)
+        );
+        assert!(output.contains(r#"blufio_cache_hit_ratio{model="claude-sonnet"} 0.75"#));
+        assert!(!output.contains("claude-haiku"));
+    }
+
+    #[test]
+    fn latency_histograms_render_with_buckets() {
+        let recorder = builder().unwrap().build_recorder();
+        let handle = recorder.handle();
+        metrics::with_local_recorder(&recorder, || {
+            recording::register_metrics();
+            record_ttft("claude-sonnet", "chat", 0.8);
+            record_turn_latency("claude-sonnet", "chat", 4.2);
+        });
+
+        let output = handle.render();
+        for name in ["blufio_ttft_seconds", "blufio_turn_latency_seconds"] {
+            assert!(
+                output.contains(&format!("# TYPE {name} histogram")),
+                "got: {output}"
+            );
+            assert!(output.contains(&format!("{name}_bucket{{")));
+            assert!(output.contains(&format!(
+                r#"{name}_count{{model="claude-sonnet",feature="chat"}} 1"#
+            )));
+        }
+    }
+
     #[test]
     fn prometheus_adapter_name() {
         // We can't call new() in tests because the recorder can only be installed once.
@@ -6,14 +6,34 @@
 //! Uses the metrics-rs facade so any recorder (Prometheus, statsd, etc.)
 //! can collect these metrics.
 
+use blufio_core::types::TokenUsage;
 use metrics::{describe_counter, describe_gauge, describe_histogram};
 
+/// Histogram buckets for LLM response times, in seconds (100ms to 120s).
+pub const LLM_LATENCY_BUCKETS: &[f64] =
+    &[0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0];
+
+/// Histograms recorded with [`LLM_LATENCY_BUCKETS`].
+pub const LLM_LATENCY_HISTOGRAMS: &[&str] = &[
+    "blufio_response_latency_seconds",
+    "blufio_ttft_seconds",
+    "blufio_turn_latency_seconds",
+];
+
 /// Register all Blufio metric descriptions.
 ///
 /// Called once at startup after the recorder is installed.
 pub fn register_metrics() {
     describe_counter!("blufio_messages_total", "Total messages processed");
     describe_counter!("blufio_tokens_total", "Total tokens consumed");
+    describe_counter!(
+        "blufio_cache_tokens_total",
+        "Prompt cache tokens by model and type (read, creation)"
+    );
+    describe_gauge!(
+        "blufio_cache_hit_ratio",
+        "Share of the last request's prompt tokens read from cache, by model"
+    );
     describe_counter!("blufio_errors_total", "Total errors by type");
     describe_gauge!("blufio_active_sessions", "Currently active sessions");
     describe_gauge!(
@@ -32,7 +52,15 @@ pub fn register_metrics() {
     );
     describe_histogram!(
         "blufio_response_latency_seconds",
-        "LLM response latency in seconds"
+        "LLM response latency in seconds, by model and feature"
+    );
+    describe_histogram!(
+        "blufio_ttft_seconds",
+        "Time to first token in seconds, by model and feature"
+    );
+    describe_histogram!(
+        "blufio_turn_latency_seconds",
+        "End-to-end turn latency in seconds, by model and feature"
     );
 
     // MCP metrics (INTG-04)
@@ -73,6 +101,28 @@ pub fn record_tokens(model: &str, input: u32, output: u32) {
         .increment(output as u64);
 }
 
+/// Record prompt cache usage for a response.
+///
+/// Adds the cache read and creation tokens to `blufio_cache_tokens_total`
+/// and sets `blufio_cache_hit_ratio` to the share of the prompt read from
+/// cache: `read / (input + read + creation)`. Usage without cache activity
+/// is ignored.
+pub fn record_cache_usage(model: &str, usage: &TokenUsage) {
+    let read = u64::from(usage.cache_read_tokens);
+    let creation = u64::from(usage.cache_creation_tokens);
+    if read == 0 && creation == 0 {
+        return;
+    }
+    metrics::counter!("blufio_cache_tokens_total", "model" => model.to_string(), "type" => "read")
+        .increment(read);
+    metrics::counter!("blufio_cache_tokens_total", "model" => model.to_string(), "type" => "creation")
+        .increment(creation);
+
+    let prompt = u64::from(usage.input_tokens) + read + creation;
+    metrics::gauge!("blufio_cache_hit_ratio", "model" => model.to_string())
+        .set(read as f64 / prompt as f64);
+}
+
 /// Set the number of active sessions.
 pub fn set_active_sessions(count: f64) {
     metrics::gauge!("blufio_active_sessions").set(count);
@@ -83,9 +133,34 @@ pub fn set_budget_remaining(usd: f64) {
     metrics::gauge!("blufio_budget_remaining_usd").set(usd);
 }
 
-/// Record response latency.
-pub fn record_latency(seconds: f64) {
-    metrics::histogram!("blufio_response_latency_seconds").record(seconds);
+/// Record response latency for a model and feature (e.g. "chat").
+pub fn record_latency(model: &str, feature: &str, seconds: f64) {
+    metrics::histogram!(
+        "blufio_response_latency_seconds",
+        "model" => model.to_string(),
+        "feature" => feature.to_string()
+    )
+    .record(seconds);
+}
+
+/// Record the time from sending a request to its first streamed token.
+pub fn record_ttft(model: &str, feature: &str, seconds: f64) {
+    metrics::histogram!(
+        "blufio_ttft_seconds",
+        "model" => model.to_string(),
+        "feature" => feature.to_string()
+    )
+    .record(seconds);
+}
+
+/// Record the end-to-end latency of a turn, tool calls included.
+pub fn record_turn_latency(model: &str, feature: &str, seconds: f64) {
+    metrics::histogram!(
+        "blufio_turn_latency_seconds",
+        "model" => model.to_string(),
+        "feature" => feature.to_string()
+    )
+    .record(seconds);
 }
 
 /// Set jemalloc allocated heap bytes.
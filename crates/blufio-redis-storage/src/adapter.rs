@@ -0,0 +1,280 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Redis implementation of the StorageAdapter trait.
+
+use async_trait::async_trait;
+use tokio::sync::OnceCell;
+use tracing::debug;
+
+use blufio_config::model::RedisStorageConfig;
+use blufio_core::types::{Message, QueueEntry, Session, ToolLoopState, ToolUseData};
+use blufio_core::{AdapterType, BlufioError, HealthStatus, PluginAdapter, StorageAdapter};
+
+use crate::database::{RedisDb, map_redis_err};
+use crate::queries;
+
+/// Redis-backed storage adapter.
+///
+/// Wraps a [`RedisDb`] connection pool and delegates all operations to the
+/// query modules. Connections are opened by [`StorageAdapter::initialize`].
+pub struct RedisStorage {
+    config: RedisStorageConfig,
+    db: OnceCell<RedisDb>,
+}
+
+impl RedisStorage {
+    /// Create a new RedisStorage with the given configuration.
+    ///
+    /// No connection is opened until [`initialize`](StorageAdapter::initialize) is called.
+    pub fn new(config: RedisStorageConfig) -> Self {
+        Self {
+            config,
+            db: OnceCell::new(),
+        }
+    }
+
+    /// Returns the connection pool, or an error if not initialized.
+    fn db(&self) -> Result<&RedisDb, BlufioError> {
+        self.db.get().ok_or_else(|| {
+            BlufioError::storage_connection_failed(std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "storage not initialized -- call initialize() first",
+            ))
+        })
+    }
+}
+
+#[async_trait]
+impl PluginAdapter for RedisStorage {
+    fn name(&self) -> &str {
+        "redis"
+    }
+
+    fn version(&self) -> semver::Version {
+        semver::Version::new(0, 1, 0)
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::Storage
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus, BlufioError> {
+        let mut conn = self.db()?.conn();
+        let _: String = redis::cmd("PING")
+            .query_async(&mut conn)
+            .await
+            .map_err(map_redis_err)?;
+        Ok(HealthStatus::Healthy)
+    }
+
+    async fn shutdown(&self) -> Result<(), BlufioError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StorageAdapter for RedisStorage {
+    async fn initialize(&self) -> Result<(), BlufioError> {
+        let db = RedisDb::connect(&self.config).await?;
+        self.db.set(db).map_err(|_| {
+            BlufioError::storage_connection_failed(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                "storage already initialized",
+            ))
+        })?;
+        debug!(
+            pool_size = self.config.pool_size,
+            key_prefix = %self.config.key_prefix,
+            "Redis storage initialized"
+        );
+        Ok(())
+    }
+
+    async fn close(&self) -> Result<(), BlufioError> {
+        // Writes are acknowledged by Redis before returning; nothing to flush.
+        debug!("Redis storage closed");
+        Ok(())
+    }
+
+    // --- Session operations ---
+
+    async fn create_session(&self, session: &Session) -> Result<(), BlufioError> {
+        queries::sessions::create_session(self.db()?, session).await
+    }
+
+    async fn get_session(&self, id: &str) -> Result<Option<Session>, BlufioError> {
+        queries::sessions::get_session(self.db()?, id).await
+    }
+
+    async fn list_sessions(&self, state: Option<&str>) -> Result<Vec<Session>, BlufioError> {
+        queries::sessions::list_sessions(self.db()?, state).await
+    }
+
+    async fn create_session_with_messages(
+        &self,
+        session: &Session,
+        messages: &[Message],
+    ) -> Result<(), BlufioError> {
+        queries::sessions::create_session_with_messages(self.db()?, session, messages).await
+    }
+
+    async fn update_session_state(&self, id: &str, state: &str) -> Result<(), BlufioError> {
+        queries::sessions::update_session_state(self.db()?, id, state).await
+    }
+
+    async fn update_session_metadata(
+        &self,
+        id: &str,
+        metadata: Option<&str>,
+    ) -> Result<(), BlufioError> {
+        queries::sessions::update_session_metadata(self.db()?, id, metadata).await
+    }
+
+    // --- Message operations ---
+
+    async fn insert_message(&self, message: &Message) -> Result<(), BlufioError> {
+        queries::messages::insert_message(self.db()?, message).await
+    }
+
+    async fn insert_messages(&self, messages: &[Message]) -> Result<(), BlufioError> {
+        queries::messages::insert_messages(self.db()?, messages).await
+    }
+
+    async fn get_messages(
+        &self,
+        session_id: &str,
+        limit: Option<i64>,
+    ) -> Result<Vec<Message>, BlufioError> {
+        queries::messages::get_messages_for_session(self.db()?, session_id, limit).await
+    }
+
+    async fn delete_messages_by_ids(
+        &self,
+        session_id: &str,
+        message_ids: &[String],
+    ) -> Result<usize, BlufioError> {
+        queries::messages::delete_messages_by_ids(self.db()?, session_id, message_ids).await
+    }
+
+    // --- Queue operations ---
+
+    async fn enqueue(&self, queue_name: &str, payload: &str) -> Result<i64, BlufioError> {
+        queries::queue::enqueue(self.db()?, queue_name, payload).await
+    }
+
+    async fn dequeue(&self, queue_name: &str) -> Result<Option<QueueEntry>, BlufioError> {
+        queries::queue::dequeue(self.db()?, queue_name).await
+    }
+
+    async fn ack(&self, id: i64) -> Result<(), BlufioError> {
+        queries::queue::ack(self.db()?, id).await
+    }
+
+    async fn fail(&self, id: i64) -> Result<(), BlufioError> {
+        queries::queue::fail(self.db()?, id).await
+    }
+
+    // --- Tool-loop state operations ---
+
+    async fn save_tool_loop_state(
+        &self,
+        session_id: &str,
+        iteration: u32,
+        pending_tool_uses: &[ToolUseData],
+    ) -> Result<(), BlufioError> {
+        queries::tool_loop::save_tool_loop_state(
+            self.db()?,
+            session_id,
+            iteration,
+            pending_tool_uses,
+        )
+        .await
+    }
+
+    async fn clear_tool_loop_state(&self, session_id: &str) -> Result<(), BlufioError> {
+        queries::tool_loop::clear_tool_loop_state(self.db()?, session_id).await
+    }
+
+    async fn list_tool_loop_states(&self) -> Result<Vec<ToolLoopState>, BlufioError> {
+        queries::tool_loop::list_tool_loop_states(self.db()?).await
+    }
+
+    // --- Classification operations ---
+
+    async fn get_entity_classification(
+        &self,
+        entity_type: &str,
+        entity_id: &str,
+    ) -> Result<Option<String>, BlufioError> {
+        queries::classification::get_entity_classification(self.db()?, entity_type, entity_id).await
+    }
+
+    async fn set_entity_classification(
+        &self,
+        entity_type: &str,
+        entity_id: &str,
+        level: &str,
+    ) -> Result<bool, BlufioError> {
+        queries::classification::set_entity_classification(
+            self.db()?,
+            entity_type,
+            entity_id,
+            level,
+        )
+        .await
+    }
+
+    async fn list_entities_by_classification(
+        &self,
+        entity_type: &str,
+        level: Option<&str>,
+    ) -> Result<Vec<(String, String)>, BlufioError> {
+        queries::classification::list_entities_by_classification(self.db()?, entity_type, level)
+            .await
+    }
+
+    async fn bulk_update_classification(
+        &self,
+        entity_type: &str,
+        new_level: &str,
+        current_level: Option<&str>,
+        session_id: Option<&str>,
+        from_date: Option<&str>,
+        to_date: Option<&str>,
+        pattern: Option<&str>,
+        dry_run: bool,
+    ) -> Result<(usize, usize, usize, Vec<String>), BlufioError> {
+        queries::classification::bulk_update_classification(
+            self.db()?,
+            entity_type,
+            new_level,
+            current_level,
+            session_id,
+            from_date,
+            to_date,
+            pattern,
+            dry_run,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redis_storage_implements_plugin_adapter() {
+        let storage = RedisStorage::new(RedisStorageConfig::default());
+        assert_eq!(storage.name(), "redis");
+        assert_eq!(storage.version(), semver::Version::new(0, 1, 0));
+        assert_eq!(storage.adapter_type(), AdapterType::Storage);
+    }
+
+    #[tokio::test]
+    async fn operations_before_initialize_fail() {
+        let storage = RedisStorage::new(RedisStorageConfig::default());
+        assert!(storage.get_session("s1").await.is_err());
+    }
+}
@@ -0,0 +1,156 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Redis connection pool and key layout.
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use blufio_config::model::RedisStorageConfig;
+use blufio_core::BlufioError;
+use redis::aio::ConnectionManager;
+
+/// Consumer group used by every replica for queue streams.
+pub const QUEUE_GROUP: &str = "blufio";
+
+/// A small pool of multiplexed Redis connections plus the key prefix.
+///
+/// Each [`ConnectionManager`] pipelines concurrent requests over one socket
+/// and reconnects on its own; the pool hands them out round-robin so busy
+/// replicas are not limited to a single socket.
+pub struct RedisDb {
+    conns: Vec<ConnectionManager>,
+    next: AtomicUsize,
+    prefix: String,
+    consumer: String,
+    claim_idle: Duration,
+    groups: Mutex<HashSet<String>>,
+}
+
+impl RedisDb {
+    /// Open `config.pool_size` connections to `config.url`.
+    pub async fn connect(config: &RedisStorageConfig) -> Result<Self, BlufioError> {
+        let client = redis::Client::open(config.url.as_str()).map_err(map_redis_err)?;
+        let size = config.pool_size.max(1);
+        let mut conns = Vec::with_capacity(size);
+        for _ in 0..size {
+            conns.push(
+                ConnectionManager::new(client.clone())
+                    .await
+                    .map_err(map_redis_err)?,
+            );
+        }
+        Ok(Self {
+            conns,
+            next: AtomicUsize::new(0),
+            prefix: config.key_prefix.clone(),
+            consumer: format!("blufio-{}", uuid::Uuid::new_v4()),
+            claim_idle: Duration::from_millis(config.claim_idle_ms),
+            groups: Mutex::new(HashSet::new()),
+        })
+    }
+
+    /// Next connection from the pool.
+    pub fn conn(&self) -> ConnectionManager {
+        let i = self.next.fetch_add(1, Ordering::Relaxed) % self.conns.len();
+        self.conns[i].clone()
+    }
+
+    /// Build a key under the configured prefix: `<prefix>:<part>:<part>...`.
+    pub fn key(&self, parts: &[&str]) -> String {
+        build_key(&self.prefix, parts)
+    }
+
+    /// Consumer name of this process within [`QUEUE_GROUP`].
+    pub fn consumer(&self) -> &str {
+        &self.consumer
+    }
+
+    /// How long a delivered queue entry may stay unacknowledged before
+    /// another consumer reclaims it.
+    pub fn claim_idle(&self) -> Duration {
+        self.claim_idle
+    }
+
+    /// Create the consumer group for a queue stream once per process.
+    pub(crate) async fn ensure_group(&self, stream_key: &str) -> Result<(), BlufioError> {
+        if self
+            .groups
+            .lock()
+            .map_err(|_| BlufioError::Internal("redis group cache poisoned".to_string()))?
+            .contains(stream_key)
+        {
+            return Ok(());
+        }
+
+        let mut conn = self.conn();
+        let created: redis::RedisResult<()> = redis::cmd("XGROUP")
+            .arg("CREATE")
+            .arg(stream_key)
+            .arg(QUEUE_GROUP)
+            .arg("0")
+            .arg("MKSTREAM")
+            .query_async(&mut conn)
+            .await;
+        match created {
+            Ok(()) => {}
+            Err(e) if e.code() == Some("BUSYGROUP") => {}
+            Err(e) => return Err(map_redis_err(e)),
+        }
+
+        if let Ok(mut groups) = self.groups.lock() {
+            groups.insert(stream_key.to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Join a prefix and key parts with `:`.
+pub(crate) fn build_key(prefix: &str, parts: &[&str]) -> String {
+    let mut key = prefix.to_string();
+    for part in parts {
+        key.push(':');
+        key.push_str(part);
+    }
+    key
+}
+
+/// Map a Redis error to a storage error.
+pub(crate) fn map_redis_err(e: redis::RedisError) -> BlufioError {
+    BlufioError::storage_connection_failed(e)
+}
+
+/// Map a JSON encoding error for a stored value to a storage error.
+pub(crate) fn map_json_err(e: serde_json::Error) -> BlufioError {
+    BlufioError::storage_corruption(e)
+}
+
+/// Current time in the ISO 8601 format the SQLite backend writes.
+pub(crate) fn now_timestamp() -> String {
+    format_timestamp(chrono::Utc::now())
+}
+
+pub(crate) fn format_timestamp(at: chrono::DateTime<chrono::Utc>) -> String {
+    at.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_prefixed_and_colon_separated() {
+        assert_eq!(build_key("blufio", &["session", "s1"]), "blufio:session:s1");
+        assert_eq!(build_key("staging", &["sessions"]), "staging:sessions");
+    }
+
+    #[test]
+    fn timestamps_match_sqlite_format() {
+        let at = chrono::DateTime::parse_from_rfc3339("2026-03-04T05:06:07.089Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(format_timestamp(at), "2026-03-04T05:06:07.089Z");
+    }
+}
@@ -0,0 +1,32 @@
+#![cfg_attr(not(test), deny(clippy::unwrap_used))]
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Redis persistence layer for horizontally scaled Blufio deployments.
+//!
+//! SQLite allows a single writer, which limits a deployment to one process.
+//! [`RedisStorage`] implements the [`StorageAdapter`](blufio_core::StorageAdapter)
+//! trait on Redis so several gateway replicas can share sessions, messages
+//! and the crash-safe queue. Selected with `storage.backend = "redis"`.
+//!
+//! Key layout, under the configured prefix (default `blufio`):
+//!
+//! | Key | Type | Contents |
+//! |-----|------|----------|
+//! | `session:<id>` | hash | session fields |
+//! | `sessions`, `sessions:<state>` | set | session IDs, all and by state |
+//! | `messages:<session_id>` | list | message JSON in insertion order |
+//! | `message:<id>` | string | session ID of a message |
+//! | `queue:<name>` | stream | entry IDs, read via the `blufio` consumer group |
+//! | `queue:entry:<id>` | hash | queue entry fields |
+//! | `queue:seq` | string | last queue entry ID |
+//! | `tool_loop:<session_id>` | string | tool-loop state JSON |
+//! | `tool_loops` | set | sessions with tool-loop state |
+
+pub mod adapter;
+pub mod database;
+pub mod models;
+pub mod queries;
+
+pub use adapter::RedisStorage;
+pub use database::RedisDb;
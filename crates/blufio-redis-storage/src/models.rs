@@ -0,0 +1,9 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Domain model types for storage entities.
+//!
+//! The canonical types are defined in `blufio-core::types`; this module
+//! re-exports them for use within the Redis storage crate.
+
+pub use blufio_core::types::{Message, QueueEntry, Session, ToolLoopState};
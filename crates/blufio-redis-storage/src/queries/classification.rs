@@ -0,0 +1,289 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Data classification operations on sessions and messages.
+//!
+//! Memories are not stored in Redis, so "memory" entities are always
+//! reported as missing.
+
+use std::collections::HashMap;
+
+use blufio_core::BlufioError;
+use blufio_core::classification::DataClassification;
+use redis::AsyncCommands;
+
+use crate::database::{RedisDb, map_json_err, map_redis_err};
+use crate::models::Message;
+use crate::queries::messages::find_message;
+
+/// Entity types with a classification, as in the SQLite backend.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Entity {
+    Memory,
+    Message,
+    Session,
+}
+
+fn entity_for(entity_type: &str) -> Result<Entity, BlufioError> {
+    match entity_type {
+        "memory" => Ok(Entity::Memory),
+        "message" => Ok(Entity::Message),
+        "session" => Ok(Entity::Session),
+        _ => Err(BlufioError::Internal(format!(
+            "unknown entity type: {entity_type}"
+        ))),
+    }
+}
+
+/// Get classification level for an entity.
+pub async fn get_entity_classification(
+    db: &RedisDb,
+    entity_type: &str,
+    entity_id: &str,
+) -> Result<Option<String>, BlufioError> {
+    match entity_for(entity_type)? {
+        Entity::Memory => Ok(None),
+        Entity::Session => {
+            let mut conn = db.conn();
+            conn.hget(db.key(&["session", entity_id]), "classification")
+                .await
+                .map_err(map_redis_err)
+        }
+        Entity::Message => Ok(find_message(db, entity_id)
+            .await?
+            .map(|(_, _, msg)| msg.classification.as_str().to_string())),
+    }
+}
+
+/// Set classification level on an entity.
+///
+/// Returns `true` if the entity was found and updated, `false` otherwise.
+pub async fn set_entity_classification(
+    db: &RedisDb,
+    entity_type: &str,
+    entity_id: &str,
+    level: &str,
+) -> Result<bool, BlufioError> {
+    let level = parse_level(level)?;
+    match entity_for(entity_type)? {
+        Entity::Memory => Ok(false),
+        Entity::Session => set_session_level(db, entity_id, level).await,
+        Entity::Message => {
+            let Some((list, index, mut msg)) = find_message(db, entity_id).await? else {
+                return Ok(false);
+            };
+            msg.classification = level;
+            let json = serde_json::to_string(&msg).map_err(map_json_err)?;
+            let mut conn = db.conn();
+            let _: () = conn.lset(&list, index, json).await.map_err(map_redis_err)?;
+            Ok(true)
+        }
+    }
+}
+
+/// List entities with their classification levels, optionally filtered by level.
+///
+/// Sorted by ID when filtered, otherwise by level then ID.
+pub async fn list_entities_by_classification(
+    db: &RedisDb,
+    entity_type: &str,
+    level: Option<&str>,
+) -> Result<Vec<(String, String)>, BlufioError> {
+    let mut results: Vec<(String, String)> = match entity_for(entity_type)? {
+        Entity::Memory => Vec::new(),
+        Entity::Session => session_levels(db)
+            .await?
+            .into_iter()
+            .map(|(id, level, _)| (id, level))
+            .collect(),
+        Entity::Message => all_messages(db, None)
+            .await?
+            .into_iter()
+            .map(|msg| (msg.id, msg.classification.as_str().to_string()))
+            .collect(),
+    };
+
+    match level {
+        Some(level) => {
+            results.retain(|(_, l)| l == level);
+            results.sort();
+        }
+        None => results.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0))),
+    }
+    Ok(results)
+}
+
+/// Bulk update classification levels with the same filters as SQLite.
+///
+/// `session_id` and `pattern` (a SQL `LIKE` pattern on content) apply to
+/// messages only. Returns `(total, succeeded, failed, errors)`.
+#[allow(clippy::too_many_arguments)]
+pub async fn bulk_update_classification(
+    db: &RedisDb,
+    entity_type: &str,
+    new_level: &str,
+    current_level: Option<&str>,
+    session_id: Option<&str>,
+    from_date: Option<&str>,
+    to_date: Option<&str>,
+    pattern: Option<&str>,
+    dry_run: bool,
+) -> Result<(usize, usize, usize, Vec<String>), BlufioError> {
+    let level = parse_level(new_level)?;
+    let in_range = |created_at: &str| {
+        from_date.is_none_or(|from| created_at >= from) && to_date.is_none_or(|to| created_at <= to)
+    };
+    let matches_level = |l: &str| current_level.is_none_or(|c| l == c);
+
+    let ids: Vec<String> = match entity_for(entity_type)? {
+        Entity::Memory => Vec::new(),
+        Entity::Session => session_levels(db)
+            .await?
+            .into_iter()
+            .filter(|(_, l, created_at)| matches_level(l) && in_range(created_at))
+            .map(|(id, _, _)| id)
+            .collect(),
+        Entity::Message => all_messages(db, session_id)
+            .await?
+            .into_iter()
+            .filter(|msg| {
+                matches_level(msg.classification.as_str())
+                    && in_range(&msg.created_at)
+                    && pattern.is_none_or(|p| like_match(p, &msg.content))
+            })
+            .map(|msg| msg.id)
+            .collect(),
+    };
+
+    if dry_run {
+        return Ok((ids.len(), 0, 0, Vec::new()));
+    }
+
+    let mut succeeded = 0;
+    let mut errors = Vec::new();
+    for id in &ids {
+        match set_entity_classification(db, entity_type, id, level.as_str()).await {
+            Ok(true) => succeeded += 1,
+            Ok(false) => errors.push(format!("{id}: not found")),
+            Err(e) => errors.push(format!("{id}: {e}")),
+        }
+    }
+    Ok((ids.len(), succeeded, errors.len(), errors))
+}
+
+fn parse_level(level: &str) -> Result<DataClassification, BlufioError> {
+    DataClassification::from_str_value(level)
+        .ok_or_else(|| BlufioError::Internal(format!("unknown classification level: {level}")))
+}
+
+async fn set_session_level(
+    db: &RedisDb,
+    id: &str,
+    level: DataClassification,
+) -> Result<bool, BlufioError> {
+    let key = db.key(&["session", id]);
+    let mut conn = db.conn();
+    let exists: bool = conn.exists(&key).await.map_err(map_redis_err)?;
+    if !exists {
+        return Ok(false);
+    }
+    let _: () = conn
+        .hset(&key, "classification", level.as_str())
+        .await
+        .map_err(map_redis_err)?;
+    Ok(true)
+}
+
+/// `(id, classification, created_at)` for every session.
+async fn session_levels(db: &RedisDb) -> Result<Vec<(String, String, String)>, BlufioError> {
+    let mut conn = db.conn();
+    let ids: Vec<String> = conn
+        .smembers(db.key(&["sessions"]))
+        .await
+        .map_err(map_redis_err)?;
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut pipe = redis::pipe();
+    for id in &ids {
+        pipe.hgetall(db.key(&["session", id]));
+    }
+    let rows: Vec<HashMap<String, String>> =
+        pipe.query_async(&mut conn).await.map_err(map_redis_err)?;
+    Ok(ids
+        .into_iter()
+        .zip(rows)
+        .filter(|(_, fields)| !fields.is_empty())
+        .map(|(id, mut fields)| {
+            let level = fields
+                .remove("classification")
+                .unwrap_or_else(|| DataClassification::default().as_str().to_string());
+            let created_at = fields.remove("created_at").unwrap_or_default();
+            (id, level, created_at)
+        })
+        .collect())
+}
+
+/// Messages of one session, or of every session.
+async fn all_messages(db: &RedisDb, session_id: Option<&str>) -> Result<Vec<Message>, BlufioError> {
+    let session_ids = match session_id {
+        Some(id) => vec![id.to_string()],
+        None => {
+            let mut conn = db.conn();
+            conn.smembers(db.key(&["sessions"]))
+                .await
+                .map_err(map_redis_err)?
+        }
+    };
+    let mut messages = Vec::new();
+    for id in &session_ids {
+        messages.extend(crate::queries::messages::get_messages_for_session(db, id, None).await?);
+    }
+    Ok(messages)
+}
+
+/// SQL `LIKE` matching: `%` matches any run, `_` any one character, and
+/// ASCII letters compare case-insensitively, as in SQLite.
+pub(crate) fn like_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while ti < t.len() {
+        if pi < p.len() && p[pi] == '%' {
+            backtrack = Some((pi, ti));
+            pi += 1;
+        } else if pi < p.len() && (p[pi] == '_' || p[pi].eq_ignore_ascii_case(&t[ti])) {
+            pi += 1;
+            ti += 1;
+        } else if let Some((bp, bt)) = backtrack {
+            pi = bp + 1;
+            ti = bt + 1;
+            backtrack = Some((bp, bt + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '%')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn like_match_follows_sqlite_semantics() {
+        assert!(like_match("%secret%", "my Secret plan"));
+        assert!(like_match("a_c", "abc"));
+        assert!(!like_match("a_c", "abbc"));
+        assert!(like_match("%", ""));
+        assert!(!like_match("abc", "abcd"));
+        assert!(like_match("%b%d", "abcd"));
+    }
+
+    #[test]
+    fn unknown_entity_type_is_rejected() {
+        assert!(entity_for("widget").is_err());
+        assert!(entity_for("message").is_ok());
+    }
+}
@@ -0,0 +1,131 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Message operations.
+//!
+//! Messages are JSON values in a per-session list at
+//! `<prefix>:messages:<session_id>`, appended in insertion order.
+//! `<prefix>:message:<id>` maps a message ID to its session for lookups by ID.
+
+use blufio_core::BlufioError;
+use redis::AsyncCommands;
+
+use crate::database::{RedisDb, map_json_err, map_redis_err};
+use crate::models::Message;
+
+/// Append a message to its session.
+pub async fn insert_message(db: &RedisDb, msg: &Message) -> Result<(), BlufioError> {
+    let json = serde_json::to_string(msg).map_err(map_json_err)?;
+    let mut conn = db.conn();
+    let _: () = redis::pipe()
+        .atomic()
+        .rpush(db.key(&["messages", &msg.session_id]), json)
+        .ignore()
+        .set(db.key(&["message", &msg.id]), &msg.session_id)
+        .ignore()
+        .query_async(&mut conn)
+        .await
+        .map_err(map_redis_err)?;
+    Ok(())
+}
+
+/// Insert several messages in one MULTI/EXEC block.
+pub async fn insert_messages(db: &RedisDb, msgs: &[Message]) -> Result<(), BlufioError> {
+    if msgs.is_empty() {
+        return Ok(());
+    }
+    let mut pipe = redis::pipe();
+    pipe.atomic();
+    for msg in msgs {
+        let json = serde_json::to_string(msg).map_err(map_json_err)?;
+        pipe.rpush(db.key(&["messages", &msg.session_id]), json)
+            .ignore()
+            .set(db.key(&["message", &msg.id]), &msg.session_id)
+            .ignore();
+    }
+    let mut conn = db.conn();
+    let _: () = pipe.query_async(&mut conn).await.map_err(map_redis_err)?;
+    Ok(())
+}
+
+/// Get messages for a session in chronological order, with optional limit.
+///
+/// Like the SQLite backend, a limit returns the oldest `limit` messages.
+pub async fn get_messages_for_session(
+    db: &RedisDb,
+    session_id: &str,
+    limit: Option<i64>,
+) -> Result<Vec<Message>, BlufioError> {
+    let stop = match limit {
+        Some(0) => return Ok(Vec::new()),
+        Some(n) => (n - 1) as isize,
+        None => -1,
+    };
+    let mut conn = db.conn();
+    let raw: Vec<String> = conn
+        .lrange(db.key(&["messages", session_id]), 0, stop)
+        .await
+        .map_err(map_redis_err)?;
+    raw.iter()
+        .map(|json| serde_json::from_str(json).map_err(map_json_err))
+        .collect()
+}
+
+/// Delete specific messages by their IDs within a session.
+///
+/// Returns the number of messages actually deleted.
+pub async fn delete_messages_by_ids(
+    db: &RedisDb,
+    session_id: &str,
+    message_ids: &[String],
+) -> Result<usize, BlufioError> {
+    if message_ids.is_empty() {
+        return Ok(0);
+    }
+    let list = db.key(&["messages", session_id]);
+    let mut conn = db.conn();
+    let raw: Vec<String> = conn.lrange(&list, 0, -1).await.map_err(map_redis_err)?;
+
+    let mut pipe = redis::pipe();
+    pipe.atomic();
+    let mut targets = 0;
+    for json in &raw {
+        let msg: Message = serde_json::from_str(json).map_err(map_json_err)?;
+        if message_ids.contains(&msg.id) {
+            pipe.lrem(&list, 1, json);
+            pipe.del(db.key(&["message", &msg.id])).ignore();
+            targets += 1;
+        }
+    }
+    if targets == 0 {
+        return Ok(0);
+    }
+
+    let removed: Vec<usize> = pipe.query_async(&mut conn).await.map_err(map_redis_err)?;
+    Ok(removed.iter().sum())
+}
+
+/// Find a message by ID. Returns its list key, index and decoded value.
+pub(crate) async fn find_message(
+    db: &RedisDb,
+    id: &str,
+) -> Result<Option<(String, isize, Message)>, BlufioError> {
+    let mut conn = db.conn();
+    let session_id: Option<String> = conn
+        .get(db.key(&["message", id]))
+        .await
+        .map_err(map_redis_err)?;
+    let Some(session_id) = session_id else {
+        return Ok(None);
+    };
+
+    let list = db.key(&["messages", &session_id]);
+    let raw: Vec<String> = conn.lrange(&list, 0, -1).await.map_err(map_redis_err)?;
+    for (index, json) in raw.iter().enumerate() {
+        let msg: Message = serde_json::from_str(json).map_err(map_json_err)?;
+        if msg.id == id {
+            return Ok(Some((list, index as isize, msg)));
+        }
+    }
+    Ok(None)
+}
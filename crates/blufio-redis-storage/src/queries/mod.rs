@@ -0,0 +1,10 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Query modules for operations on storage entities.
+
+pub mod classification;
+pub mod messages;
+pub mod queue;
+pub mod sessions;
+pub mod tool_loop;
@@ -0,0 +1,346 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Queue operations on Redis streams for at-least-once delivery.
+//!
+//! Each queue is a stream at `<prefix>:queue:<name>` read through the
+//! [`QUEUE_GROUP`] consumer group. Stream entries carry only the numeric
+//! entry ID; the entry itself is a hash at `<prefix>:queue:entry:<id>` with
+//! the same fields as the SQLite `queue` table. IDs come from the
+//! `<prefix>:queue:seq` counter.
+//!
+//! A dequeued entry stays pending in the group until it is acknowledged or
+//! failed. If its consumer dies, another replica reclaims it with
+//! `XAUTOCLAIM` once it has been idle for `claim_idle_ms`, which replaces the
+//! SQLite backend's `locked_until` and startup recovery. Like that recovery,
+//! a reclaim counts as an attempt, and an entry out of attempts is
+//! dead-lettered instead of redelivered.
+//!
+//! A failed entry that still has attempts left is re-added at the end of
+//! the stream, so unlike SQLite it no longer holds back newer entries.
+
+use std::collections::HashMap;
+
+use blufio_core::BlufioError;
+use redis::AsyncCommands;
+use redis::streams::{StreamAutoClaimReply, StreamId, StreamReadOptions, StreamReadReply};
+
+use crate::database::{QUEUE_GROUP, RedisDb, format_timestamp, map_redis_err, now_timestamp};
+use crate::models::QueueEntry;
+
+/// Attempts allowed per entry, matching the SQLite column default.
+pub const DEFAULT_MAX_ATTEMPTS: i32 = 3;
+
+/// Enqueue a new item. Returns the auto-generated queue entry ID.
+pub async fn enqueue(db: &RedisDb, queue_name: &str, payload: &str) -> Result<i64, BlufioError> {
+    let stream = db.key(&["queue", queue_name]);
+    db.ensure_group(&stream).await?;
+
+    let mut conn = db.conn();
+    let id: i64 = conn
+        .incr(db.key(&["queue", "seq"]), 1)
+        .await
+        .map_err(map_redis_err)?;
+    let now = now_timestamp();
+    let fields = [
+        ("queue_name", queue_name.to_string()),
+        ("payload", payload.to_string()),
+        ("status", "pending".to_string()),
+        ("attempts", "0".to_string()),
+        ("max_attempts", DEFAULT_MAX_ATTEMPTS.to_string()),
+        ("created_at", now.clone()),
+        ("updated_at", now),
+    ];
+    let _: () = redis::pipe()
+        .atomic()
+        .hset_multiple(entry_key(db, id), &fields)
+        .ignore()
+        .xadd(&stream, "*", &[("id", id)])
+        .ignore()
+        .query_async(&mut conn)
+        .await
+        .map_err(map_redis_err)?;
+    Ok(id)
+}
+
+/// Dequeue the next entry from the named queue.
+///
+/// Entries abandoned by a crashed consumer are reclaimed first; otherwise
+/// the next new entry is delivered. A reclaimed entry uses up an attempt and
+/// is marked "failed" instead of delivered once it has none left. The
+/// delivered entry is marked "processing" with `locked_until` set to when it
+/// becomes reclaimable, and records its stream ID for [`ack`] and [`fail`].
+/// Returns `None` if the queue has nothing to deliver.
+pub async fn dequeue(db: &RedisDb, queue_name: &str) -> Result<Option<QueueEntry>, BlufioError> {
+    let stream = db.key(&["queue", queue_name]);
+    db.ensure_group(&stream).await?;
+    let mut conn = db.conn();
+
+    loop {
+        let Some((delivered, reclaimed)) = next_stream_entry(db, &mut conn, &stream).await? else {
+            return Ok(None);
+        };
+        let Some(id) = delivered.get::<i64>("id") else {
+            // Not written by `enqueue`; drop it so it is not redelivered.
+            discard_stream_entry(&mut conn, &stream, &delivered.id).await?;
+            continue;
+        };
+
+        let key = entry_key(db, id);
+        let mut fields: HashMap<String, String> =
+            conn.hgetall(&key).await.map_err(map_redis_err)?;
+        let status = fields.get("status").map(String::as_str);
+        if !matches!(status, Some("pending" | "processing")) {
+            // Entry hash gone or already settled.
+            discard_stream_entry(&mut conn, &stream, &delivered.id).await?;
+            continue;
+        }
+
+        if reclaimed {
+            // The consumer it was delivered to never settled it.
+            let attempts: i32 = conn
+                .hincr(&key, "attempts", 1)
+                .await
+                .map_err(map_redis_err)?;
+            fields.insert("attempts".to_string(), attempts.to_string());
+            if attempts >= parse_i32(&fields, "max_attempts", DEFAULT_MAX_ATTEMPTS) {
+                dead_letter(&mut conn, &key, &stream, &delivered.id).await?;
+                continue;
+            }
+        }
+
+        let now = chrono::Utc::now();
+        let locked_until = match chrono::TimeDelta::from_std(db.claim_idle()) {
+            Ok(idle) => format_timestamp(now + idle),
+            Err(_) => format_timestamp(now),
+        };
+        let updates = [
+            ("status", "processing".to_string()),
+            ("locked_until", locked_until),
+            ("updated_at", format_timestamp(now)),
+            ("stream_id", delivered.id.clone()),
+        ];
+        let _: () = conn
+            .hset_multiple(&key, &updates)
+            .await
+            .map_err(map_redis_err)?;
+
+        fields.extend(updates.into_iter().map(|(k, v)| (k.to_string(), v)));
+        return Ok(entry_from_fields(id, &fields));
+    }
+}
+
+/// Acknowledge successful processing of a queue entry.
+///
+/// Marks the entry as "completed" and removes it from the stream.
+pub async fn ack(db: &RedisDb, id: i64) -> Result<(), BlufioError> {
+    let key = entry_key(db, id);
+    let mut conn = db.conn();
+    let (queue_name, stream_id): (Option<String>, Option<String>) = conn
+        .hget(&key, &["queue_name", "stream_id"])
+        .await
+        .map_err(map_redis_err)?;
+    let Some(queue_name) = queue_name else {
+        return Ok(());
+    };
+
+    let mut pipe = redis::pipe();
+    pipe.atomic()
+        .hset(&key, "status", "completed")
+        .ignore()
+        .hset(&key, "updated_at", now_timestamp())
+        .ignore();
+    if let Some(stream_id) = stream_id {
+        let stream = db.key(&["queue", &queue_name]);
+        pipe.xack(&stream, QUEUE_GROUP, &[&stream_id])
+            .ignore()
+            .xdel(&stream, &[&stream_id])
+            .ignore();
+    }
+    let _: () = pipe.query_async(&mut conn).await.map_err(map_redis_err)?;
+    Ok(())
+}
+
+/// Mark a queue entry as failed.
+///
+/// Increments attempts. If attempts >= max_attempts, sets status to "failed"
+/// and leaves the entry out of the stream (dead-lettered). Otherwise resets
+/// it to "pending" and re-adds it to the stream for retry.
+pub async fn fail(db: &RedisDb, id: i64) -> Result<(), BlufioError> {
+    let key = entry_key(db, id);
+    let mut conn = db.conn();
+    let fields: HashMap<String, String> = conn.hgetall(&key).await.map_err(map_redis_err)?;
+    let Some(queue_name) = fields.get("queue_name") else {
+        return Ok(());
+    };
+    let attempts = parse_i32(&fields, "attempts", 0) + 1;
+    let max_attempts = parse_i32(&fields, "max_attempts", DEFAULT_MAX_ATTEMPTS);
+    let status = if attempts >= max_attempts {
+        "failed"
+    } else {
+        "pending"
+    };
+    let stream = db.key(&["queue", queue_name]);
+
+    let mut pipe = redis::pipe();
+    pipe.atomic()
+        .hset_multiple(
+            &key,
+            &[
+                ("status", status.to_string()),
+                ("attempts", attempts.to_string()),
+                ("updated_at", now_timestamp()),
+            ],
+        )
+        .ignore()
+        .hdel(&key, &["locked_until", "stream_id"])
+        .ignore();
+    if let Some(stream_id) = fields.get("stream_id") {
+        pipe.xack(&stream, QUEUE_GROUP, &[stream_id])
+            .ignore()
+            .xdel(&stream, &[stream_id])
+            .ignore();
+    }
+    if status == "pending" {
+        pipe.xadd(&stream, "*", &[("id", id)]).ignore();
+    }
+    let _: () = pipe.query_async(&mut conn).await.map_err(map_redis_err)?;
+    Ok(())
+}
+
+/// Reclaim an abandoned stream entry, or read the next new one. The flag is
+/// true for a reclaimed entry.
+async fn next_stream_entry(
+    db: &RedisDb,
+    conn: &mut redis::aio::ConnectionManager,
+    stream: &str,
+) -> Result<Option<(StreamId, bool)>, BlufioError> {
+    let reclaimed: StreamAutoClaimReply = redis::cmd("XAUTOCLAIM")
+        .arg(stream)
+        .arg(QUEUE_GROUP)
+        .arg(db.consumer())
+        .arg(db.claim_idle().as_millis() as u64)
+        .arg("0-0")
+        .arg("COUNT")
+        .arg(1)
+        .query_async(conn)
+        .await
+        .map_err(map_redis_err)?;
+    if let Some(entry) = reclaimed.claimed.into_iter().next() {
+        return Ok(Some((entry, true)));
+    }
+
+    let options = StreamReadOptions::default()
+        .group(QUEUE_GROUP, db.consumer())
+        .count(1);
+    let reply: Option<StreamReadReply> = conn
+        .xread_options(&[stream], &[">"], &options)
+        .await
+        .map_err(map_redis_err)?;
+    Ok(reply
+        .and_then(|r| r.keys.into_iter().next())
+        .and_then(|k| k.ids.into_iter().next())
+        .map(|entry| (entry, false)))
+}
+
+/// Mark a reclaimed entry "failed" and remove it from the stream.
+async fn dead_letter(
+    conn: &mut redis::aio::ConnectionManager,
+    key: &str,
+    stream: &str,
+    stream_id: &str,
+) -> Result<(), BlufioError> {
+    let _: () = redis::pipe()
+        .atomic()
+        .hset_multiple(
+            key,
+            &[
+                ("status", "failed".to_string()),
+                ("updated_at", now_timestamp()),
+            ],
+        )
+        .ignore()
+        .hdel(key, &["locked_until", "stream_id"])
+        .ignore()
+        .xack(stream, QUEUE_GROUP, &[stream_id])
+        .ignore()
+        .xdel(stream, &[stream_id])
+        .ignore()
+        .query_async(conn)
+        .await
+        .map_err(map_redis_err)?;
+    Ok(())
+}
+
+async fn discard_stream_entry(
+    conn: &mut redis::aio::ConnectionManager,
+    stream: &str,
+    stream_id: &str,
+) -> Result<(), BlufioError> {
+    let _: () = redis::pipe()
+        .xack(stream, QUEUE_GROUP, &[stream_id])
+        .ignore()
+        .xdel(stream, &[stream_id])
+        .ignore()
+        .query_async(conn)
+        .await
+        .map_err(map_redis_err)?;
+    Ok(())
+}
+
+fn entry_key(db: &RedisDb, id: i64) -> String {
+    db.key(&["queue", "entry", &id.to_string()])
+}
+
+fn parse_i32(fields: &HashMap<String, String>, name: &str, default: i32) -> i32 {
+    fields
+        .get(name)
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Rebuild a queue entry from its hash.
+pub(crate) fn entry_from_fields(id: i64, fields: &HashMap<String, String>) -> Option<QueueEntry> {
+    let get = |name: &str| fields.get(name).cloned();
+    Some(QueueEntry {
+        id,
+        queue_name: get("queue_name")?,
+        payload: get("payload").unwrap_or_default(),
+        status: get("status").unwrap_or_default(),
+        attempts: parse_i32(fields, "attempts", 0),
+        max_attempts: parse_i32(fields, "max_attempts", DEFAULT_MAX_ATTEMPTS),
+        created_at: get("created_at").unwrap_or_default(),
+        updated_at: get("updated_at").unwrap_or_default(),
+        locked_until: get("locked_until"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_hash_decodes_with_defaults() {
+        let fields: HashMap<String, String> = [
+            ("queue_name", "inbound"),
+            ("payload", "{}"),
+            ("status", "pending"),
+            ("attempts", "2"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let entry = entry_from_fields(7, &fields).unwrap();
+        assert_eq!(entry.id, 7);
+        assert_eq!(entry.queue_name, "inbound");
+        assert_eq!(entry.attempts, 2);
+        assert_eq!(entry.max_attempts, DEFAULT_MAX_ATTEMPTS);
+        assert!(entry.locked_until.is_none());
+    }
+
+    #[test]
+    fn missing_entry_hash_decodes_to_none() {
+        assert!(entry_from_fields(1, &HashMap::new()).is_none());
+    }
+}
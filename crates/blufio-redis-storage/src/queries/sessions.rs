@@ -0,0 +1,234 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Session operations.
+//!
+//! Each session is a hash at `<prefix>:session:<id>`. The set
+//! `<prefix>:sessions` holds every session ID and `<prefix>:sessions:<state>`
+//! the IDs in each state, so `list_sessions(Some("active"))` reads one set.
+
+use std::collections::HashMap;
+
+use blufio_core::BlufioError;
+use blufio_core::classification::DataClassification;
+use redis::AsyncCommands;
+
+use crate::database::{RedisDb, map_json_err, map_redis_err, now_timestamp};
+use crate::models::{Message, Session};
+
+/// Moves a session between state sets and updates its hash atomically.
+///
+/// KEYS[1] = session hash, KEYS[2] = state set prefix (`<prefix>:sessions:`).
+/// ARGV[1] = new state, ARGV[2] = timestamp, ARGV[3] = session ID.
+const UPDATE_STATE_SCRIPT: &str = r"
+if redis.call('EXISTS', KEYS[1]) == 0 then return 0 end
+local old = redis.call('HGET', KEYS[1], 'state')
+redis.call('HSET', KEYS[1], 'state', ARGV[1], 'updated_at', ARGV[2])
+if old then redis.call('SREM', KEYS[2] .. old, ARGV[3]) end
+redis.call('SADD', KEYS[2] .. ARGV[1], ARGV[3])
+return 1
+";
+
+/// Create a new session.
+pub async fn create_session(db: &RedisDb, session: &Session) -> Result<(), BlufioError> {
+    let mut conn = db.conn();
+    let _: () = redis::pipe()
+        .atomic()
+        .hset_multiple(db.key(&["session", &session.id]), &session_fields(session))
+        .ignore()
+        .sadd(db.key(&["sessions"]), &session.id)
+        .ignore()
+        .sadd(db.key(&["sessions", &session.state]), &session.id)
+        .ignore()
+        .query_async(&mut conn)
+        .await
+        .map_err(map_redis_err)?;
+    Ok(())
+}
+
+/// Create a session and append its initial messages in one MULTI/EXEC block.
+pub async fn create_session_with_messages(
+    db: &RedisDb,
+    session: &Session,
+    msgs: &[Message],
+) -> Result<(), BlufioError> {
+    let mut pipe = redis::pipe();
+    pipe.atomic()
+        .hset_multiple(db.key(&["session", &session.id]), &session_fields(session))
+        .ignore()
+        .sadd(db.key(&["sessions"]), &session.id)
+        .ignore()
+        .sadd(db.key(&["sessions", &session.state]), &session.id)
+        .ignore();
+    for msg in msgs {
+        let json = serde_json::to_string(msg).map_err(map_json_err)?;
+        pipe.rpush(db.key(&["messages", &msg.session_id]), json)
+            .ignore()
+            .set(db.key(&["message", &msg.id]), &msg.session_id)
+            .ignore();
+    }
+    let mut conn = db.conn();
+    let _: () = pipe.query_async(&mut conn).await.map_err(map_redis_err)?;
+    Ok(())
+}
+
+/// Get a session by ID.
+pub async fn get_session(db: &RedisDb, id: &str) -> Result<Option<Session>, BlufioError> {
+    let mut conn = db.conn();
+    let fields: HashMap<String, String> = conn
+        .hgetall(db.key(&["session", id]))
+        .await
+        .map_err(map_redis_err)?;
+    Ok(session_from_fields(&fields))
+}
+
+/// List sessions, optionally filtered by state, newest first.
+pub async fn list_sessions(db: &RedisDb, state: Option<&str>) -> Result<Vec<Session>, BlufioError> {
+    let set = match state {
+        Some(state) => db.key(&["sessions", state]),
+        None => db.key(&["sessions"]),
+    };
+    let mut conn = db.conn();
+    let ids: Vec<String> = conn.smembers(set).await.map_err(map_redis_err)?;
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut pipe = redis::pipe();
+    for id in &ids {
+        pipe.hgetall(db.key(&["session", id]));
+    }
+    let rows: Vec<HashMap<String, String>> =
+        pipe.query_async(&mut conn).await.map_err(map_redis_err)?;
+
+    let mut sessions: Vec<Session> = rows.iter().filter_map(session_from_fields).collect();
+    sessions.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(sessions)
+}
+
+/// Update a session's state. Unknown sessions are ignored.
+pub async fn update_session_state(db: &RedisDb, id: &str, state: &str) -> Result<(), BlufioError> {
+    let mut conn = db.conn();
+    let _: i64 = redis::Script::new(UPDATE_STATE_SCRIPT)
+        .key(db.key(&["session", id]))
+        .key(format!("{}:", db.key(&["sessions"])))
+        .arg(state)
+        .arg(now_timestamp())
+        .arg(id)
+        .invoke_async(&mut conn)
+        .await
+        .map_err(map_redis_err)?;
+    Ok(())
+}
+
+/// Replace a session's metadata JSON. Unknown sessions are ignored.
+pub async fn update_session_metadata(
+    db: &RedisDb,
+    id: &str,
+    metadata: Option<&str>,
+) -> Result<(), BlufioError> {
+    let key = db.key(&["session", id]);
+    let mut conn = db.conn();
+    let exists: bool = conn.exists(&key).await.map_err(map_redis_err)?;
+    if !exists {
+        return Ok(());
+    }
+
+    let mut pipe = redis::pipe();
+    pipe.atomic();
+    match metadata {
+        Some(metadata) => pipe.hset(&key, "metadata", metadata).ignore(),
+        None => pipe.hdel(&key, "metadata").ignore(),
+    };
+    pipe.hset(&key, "updated_at", now_timestamp()).ignore();
+    let _: () = pipe.query_async(&mut conn).await.map_err(map_redis_err)?;
+    Ok(())
+}
+
+/// Hash fields for a session. Absent optional values are not stored.
+pub(crate) fn session_fields(session: &Session) -> Vec<(&'static str, String)> {
+    let mut fields = vec![
+        ("id", session.id.clone()),
+        ("channel", session.channel.clone()),
+        ("state", session.state.clone()),
+        ("created_at", session.created_at.clone()),
+        ("updated_at", session.updated_at.clone()),
+        (
+            "classification",
+            session.classification.as_str().to_string(),
+        ),
+    ];
+    if let Some(user_id) = &session.user_id {
+        fields.push(("user_id", user_id.clone()));
+    }
+    if let Some(metadata) = &session.metadata {
+        fields.push(("metadata", metadata.clone()));
+    }
+    fields
+}
+
+/// Rebuild a session from its hash. Returns `None` for a missing hash.
+pub(crate) fn session_from_fields(fields: &HashMap<String, String>) -> Option<Session> {
+    let get = |name: &str| fields.get(name).cloned();
+    Some(Session {
+        id: get("id")?,
+        channel: get("channel").unwrap_or_default(),
+        user_id: get("user_id"),
+        state: get("state").unwrap_or_default(),
+        metadata: get("metadata"),
+        created_at: get("created_at").unwrap_or_default(),
+        updated_at: get("updated_at").unwrap_or_default(),
+        classification: fields
+            .get("classification")
+            .and_then(|c| DataClassification::from_str_value(c))
+            .unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_session(user_id: Option<&str>) -> Session {
+        Session {
+            id: "s1".to_string(),
+            channel: "telegram".to_string(),
+            user_id: user_id.map(str::to_string),
+            state: "active".to_string(),
+            metadata: Some(r#"{"chat_id":"42"}"#.to_string()),
+            created_at: "2026-01-01T00:00:00.000Z".to_string(),
+            updated_at: "2026-01-01T00:00:01.000Z".to_string(),
+            classification: DataClassification::Confidential,
+        }
+    }
+
+    fn to_map(fields: Vec<(&'static str, String)>) -> HashMap<String, String> {
+        fields
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect()
+    }
+
+    #[test]
+    fn session_hash_roundtrips() {
+        let session = make_session(Some("u1"));
+        let back = session_from_fields(&to_map(session_fields(&session))).unwrap();
+        assert_eq!(back.id, "s1");
+        assert_eq!(back.user_id.as_deref(), Some("u1"));
+        assert_eq!(back.metadata, session.metadata);
+        assert_eq!(back.updated_at, session.updated_at);
+        assert_eq!(back.classification, DataClassification::Confidential);
+    }
+
+    #[test]
+    fn absent_optionals_are_not_stored() {
+        let fields = to_map(session_fields(&make_session(None)));
+        assert!(!fields.contains_key("user_id"));
+        assert!(session_from_fields(&fields).unwrap().user_id.is_none());
+    }
+
+    #[test]
+    fn empty_hash_is_no_session() {
+        assert!(session_from_fields(&HashMap::new()).is_none());
+    }
+}
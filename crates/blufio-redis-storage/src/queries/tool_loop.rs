@@ -0,0 +1,96 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Tool-loop state operations for crash recovery of in-flight tool calls.
+//!
+//! Each record is a JSON [`ToolLoopState`] at `<prefix>:tool_loop:<session_id>`;
+//! the set `<prefix>:tool_loops` lists the sessions that have one.
+
+use blufio_core::BlufioError;
+use blufio_core::types::ToolUseData;
+use redis::AsyncCommands;
+
+use crate::database::{RedisDb, map_json_err, map_redis_err, now_timestamp};
+use crate::models::ToolLoopState;
+
+/// Record the tool iteration about to execute for a session.
+///
+/// Replaces any earlier record for the same session, keeping its creation time.
+pub async fn save_tool_loop_state(
+    db: &RedisDb,
+    session_id: &str,
+    iteration: u32,
+    pending_tool_uses: &[ToolUseData],
+) -> Result<(), BlufioError> {
+    let key = db.key(&["tool_loop", session_id]);
+    let mut conn = db.conn();
+    let existing: Option<String> = conn.get(&key).await.map_err(map_redis_err)?;
+    let now = now_timestamp();
+    let created_at = existing
+        .and_then(|json| serde_json::from_str::<ToolLoopState>(&json).ok())
+        .map(|state| state.created_at)
+        .unwrap_or_else(|| now.clone());
+
+    let state = ToolLoopState {
+        session_id: session_id.to_string(),
+        iteration,
+        pending_tool_uses: pending_tool_uses.to_vec(),
+        created_at,
+        updated_at: now,
+    };
+    let json = serde_json::to_string(&state).map_err(map_json_err)?;
+    let _: () = redis::pipe()
+        .atomic()
+        .set(&key, json)
+        .ignore()
+        .sadd(db.key(&["tool_loops"]), session_id)
+        .ignore()
+        .query_async(&mut conn)
+        .await
+        .map_err(map_redis_err)?;
+    Ok(())
+}
+
+/// Remove the tool-loop record for a session, if any.
+pub async fn clear_tool_loop_state(db: &RedisDb, session_id: &str) -> Result<(), BlufioError> {
+    let mut conn = db.conn();
+    let _: () = redis::pipe()
+        .atomic()
+        .del(db.key(&["tool_loop", session_id]))
+        .ignore()
+        .srem(db.key(&["tool_loops"]), session_id)
+        .ignore()
+        .query_async(&mut conn)
+        .await
+        .map_err(map_redis_err)?;
+    Ok(())
+}
+
+/// List every tool-loop record, oldest first.
+pub async fn list_tool_loop_states(db: &RedisDb) -> Result<Vec<ToolLoopState>, BlufioError> {
+    let mut conn = db.conn();
+    let session_ids: Vec<String> = conn
+        .smembers(db.key(&["tool_loops"]))
+        .await
+        .map_err(map_redis_err)?;
+    if session_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let keys: Vec<String> = session_ids
+        .iter()
+        .map(|id| db.key(&["tool_loop", id]))
+        .collect();
+    let raw: Vec<Option<String>> = redis::cmd("MGET")
+        .arg(&keys)
+        .query_async(&mut conn)
+        .await
+        .map_err(map_redis_err)?;
+    let mut states = raw
+        .into_iter()
+        .flatten()
+        .map(|json| serde_json::from_str::<ToolLoopState>(&json).map_err(map_json_err))
+        .collect::<Result<Vec<_>, _>>()?;
+    states.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    Ok(states)
+}
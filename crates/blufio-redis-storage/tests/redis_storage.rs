@@ -0,0 +1,273 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Integration tests against a live Redis server.
+//!
+//! Enabled with the `redis-tests` feature. Start a throwaway server and point
+//! the tests at it:
+//!
+//! ```sh
+//! docker run --rm -d -p 6379:6379 redis:7
+//! BLUFIO_TEST_REDIS_URL=redis://127.0.0.1:6379 \
+//!     cargo test -p blufio-redis-storage --features redis-tests
+//! ```
+//!
+//! Every test uses its own key prefix, so they can share one server.
+
+#![cfg(feature = "redis-tests")]
+
+use blufio_config::model::RedisStorageConfig;
+use blufio_core::StorageAdapter;
+use blufio_core::classification::DataClassification;
+use blufio_core::types::{Message, Session, ToolUseData};
+use blufio_redis_storage::RedisStorage;
+
+async fn storage_with(prefix: &str, claim_idle_ms: u64) -> RedisStorage {
+    let storage = RedisStorage::new(RedisStorageConfig {
+        url: std::env::var("BLUFIO_TEST_REDIS_URL")
+            .unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string()),
+        pool_size: 2,
+        key_prefix: prefix.to_string(),
+        claim_idle_ms,
+    });
+    storage.initialize().await.unwrap();
+    storage
+}
+
+async fn storage() -> RedisStorage {
+    storage_with(&unique_prefix(), 300_000).await
+}
+
+fn unique_prefix() -> String {
+    format!("blufio-test-{}", uuid::Uuid::new_v4())
+}
+
+fn make_session(id: &str, state: &str, created_at: &str) -> Session {
+    Session {
+        id: id.to_string(),
+        channel: "cli".to_string(),
+        user_id: Some("user-1".to_string()),
+        state: state.to_string(),
+        metadata: None,
+        created_at: created_at.to_string(),
+        updated_at: created_at.to_string(),
+        classification: DataClassification::default(),
+    }
+}
+
+fn make_msg(id: &str, session_id: &str, content: &str) -> Message {
+    Message {
+        id: id.to_string(),
+        session_id: session_id.to_string(),
+        role: "user".to_string(),
+        content: content.to_string(),
+        token_count: None,
+        metadata: None,
+        created_at: "2026-01-01T00:00:01.000Z".to_string(),
+        classification: DataClassification::default(),
+    }
+}
+
+#[tokio::test]
+async fn sessions_roundtrip_and_move_between_state_sets() {
+    let storage = storage().await;
+    storage
+        .create_session(&make_session("s1", "active", "2026-01-01T00:00:00.000Z"))
+        .await
+        .unwrap();
+    storage
+        .create_session(&make_session("s2", "active", "2026-01-02T00:00:00.000Z"))
+        .await
+        .unwrap();
+
+    let s1 = storage.get_session("s1").await.unwrap().unwrap();
+    assert_eq!(s1.user_id.as_deref(), Some("user-1"));
+    assert!(storage.get_session("missing").await.unwrap().is_none());
+
+    let active = storage.list_sessions(Some("active")).await.unwrap();
+    let ids: Vec<&str> = active.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, ["s2", "s1"]);
+
+    storage.update_session_state("s1", "closed").await.unwrap();
+    storage
+        .update_session_metadata("s1", Some(r#"{"persona":"pirate"}"#))
+        .await
+        .unwrap();
+    let active = storage.list_sessions(Some("active")).await.unwrap();
+    assert_eq!(active.len(), 1);
+    let closed = storage.list_sessions(Some("closed")).await.unwrap();
+    assert_eq!(closed[0].id, "s1");
+    assert_eq!(
+        closed[0].metadata.as_deref(),
+        Some(r#"{"persona":"pirate"}"#)
+    );
+    assert_eq!(storage.list_sessions(None).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn messages_keep_order_and_can_be_deleted() {
+    let storage = storage().await;
+    for (id, content) in [("m1", "one"), ("m2", "two"), ("m3", "three")] {
+        storage
+            .insert_message(&make_msg(id, "s1", content))
+            .await
+            .unwrap();
+    }
+
+    let all = storage.get_messages("s1", None).await.unwrap();
+    let contents: Vec<&str> = all.iter().map(|m| m.content.as_str()).collect();
+    assert_eq!(contents, ["one", "two", "three"]);
+    assert_eq!(storage.get_messages("s1", Some(2)).await.unwrap().len(), 2);
+
+    let deleted = storage
+        .delete_messages_by_ids("s1", &["m2".to_string(), "nope".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(deleted, 1);
+    let ids: Vec<String> = storage
+        .get_messages("s1", None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| m.id)
+        .collect();
+    assert_eq!(ids, ["m1", "m3"]);
+}
+
+#[tokio::test]
+async fn queue_delivers_acks_and_dead_letters() {
+    let storage = storage().await;
+    let first = storage.enqueue("inbound", r#"{"n":1}"#).await.unwrap();
+    let second = storage.enqueue("inbound", r#"{"n":2}"#).await.unwrap();
+    assert!(second > first);
+
+    let entry = storage.dequeue("inbound").await.unwrap().unwrap();
+    assert_eq!(entry.id, first);
+    assert_eq!(entry.status, "processing");
+    assert!(entry.locked_until.is_some());
+    storage.ack(entry.id).await.unwrap();
+
+    // Fails until the attempt limit, then stays out of the queue.
+    for attempt in 1..=entry.max_attempts {
+        let entry = storage.dequeue("inbound").await.unwrap().unwrap();
+        assert_eq!(entry.id, second);
+        assert_eq!(entry.attempts, attempt - 1);
+        storage.fail(entry.id).await.unwrap();
+    }
+    assert!(storage.dequeue("inbound").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn abandoned_entries_are_reclaimed_by_another_replica() {
+    let prefix = unique_prefix();
+    let crashed = storage_with(&prefix, 0).await;
+    let survivor = storage_with(&prefix, 0).await;
+
+    let id = crashed.enqueue("inbound", "{}").await.unwrap();
+    let taken = crashed.dequeue("inbound").await.unwrap().unwrap();
+    assert_eq!(taken.id, id);
+    drop(crashed);
+
+    let reclaimed = survivor.dequeue("inbound").await.unwrap().unwrap();
+    assert_eq!(reclaimed.id, id);
+    assert_eq!(reclaimed.attempts, 1);
+    survivor.ack(reclaimed.id).await.unwrap();
+    assert!(survivor.dequeue("inbound").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn reclaims_use_up_attempts_then_dead_letter() {
+    let storage = storage_with(&unique_prefix(), 0).await;
+    let id = storage.enqueue("inbound", "{}").await.unwrap();
+
+    // With no idle time, every later dequeue reclaims the unsettled entry.
+    let first = storage.dequeue("inbound").await.unwrap().unwrap();
+    for attempt in 1..first.max_attempts {
+        let entry = storage.dequeue("inbound").await.unwrap().unwrap();
+        assert_eq!(entry.id, id);
+        assert_eq!(entry.attempts, attempt);
+    }
+    assert!(storage.dequeue("inbound").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn tool_loop_state_roundtrips() {
+    let storage = storage().await;
+    let call = ToolUseData {
+        id: "tu-1".to_string(),
+        name: "bash".to_string(),
+        input: serde_json::json!({"command": "ls"}),
+    };
+    storage
+        .save_tool_loop_state("s1", 0, std::slice::from_ref(&call))
+        .await
+        .unwrap();
+    storage
+        .save_tool_loop_state("s1", 1, &[call])
+        .await
+        .unwrap();
+
+    let states = storage.list_tool_loop_states().await.unwrap();
+    assert_eq!(states.len(), 1);
+    assert_eq!(states[0].iteration, 1);
+    assert_eq!(states[0].pending_tool_uses[0].name, "bash");
+
+    storage.clear_tool_loop_state("s1").await.unwrap();
+    assert!(storage.list_tool_loop_states().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn message_classification_is_updated_in_place() {
+    let storage = storage().await;
+    storage
+        .create_session(&make_session("s1", "active", "2026-01-01T00:00:00.000Z"))
+        .await
+        .unwrap();
+    storage
+        .insert_message(&make_msg("m1", "s1", "my secret plan"))
+        .await
+        .unwrap();
+    storage
+        .insert_message(&make_msg("m2", "s1", "hello"))
+        .await
+        .unwrap();
+
+    let (total, ..) = storage
+        .bulk_update_classification(
+            "message",
+            "restricted",
+            None,
+            Some("s1"),
+            None,
+            None,
+            Some("%secret%"),
+            true,
+        )
+        .await
+        .unwrap();
+    assert_eq!(total, 1);
+
+    assert!(
+        storage
+            .set_entity_classification("message", "m1", "restricted")
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        storage
+            .get_entity_classification("message", "m1")
+            .await
+            .unwrap()
+            .as_deref(),
+        Some("restricted")
+    );
+    let restricted = storage
+        .list_entities_by_classification("message", Some("restricted"))
+        .await
+        .unwrap();
+    assert_eq!(restricted, [("m1".to_string(), "restricted".to_string())]);
+    assert_eq!(
+        storage.get_messages("s1", None).await.unwrap()[0].content,
+        "my secret plan"
+    );
+}
@@ -5,8 +5,14 @@
 //!
 //! Classifies user messages into Simple/Standard/Complex tiers using
 //! zero-cost heuristic rules. No LLM pre-call, no network, no latency.
+//!
+//! With a [`QueryEmbedder`] installed, the query embedding is also compared
+//! against centroids of labeled exemplar queries per tier, and the result
+//! blended with the heuristic. Without one, classification is heuristic only.
+
+use std::sync::Arc;
 
-/// Query complexity tiers mapped to Claude model families.
+/// Query complexity tiers, each mapped to a model by `RoutingConfig`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ComplexityTier {
     /// Haiku: greetings, time queries, single-fact lookups, yes/no.
@@ -27,6 +33,40 @@ impl std::fmt::Display for ComplexityTier {
     }
 }
 
+impl ComplexityTier {
+    const ALL: [ComplexityTier; 3] = [Self::Simple, Self::Standard, Self::Complex];
+
+    /// Position on a -1 (simple) to 1 (complex) scale, for blending.
+    fn ordinal(self) -> f32 {
+        match self {
+            Self::Simple => -1.0,
+            Self::Standard => 0.0,
+            Self::Complex => 1.0,
+        }
+    }
+
+    fn from_ordinal(value: f32) -> Self {
+        if value <= -0.5 {
+            Self::Simple
+        } else if value >= 0.5 {
+            Self::Complex
+        } else {
+            Self::Standard
+        }
+    }
+}
+
+/// How a classification was reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClassificationMethod {
+    /// Heuristic rules only.
+    Heuristic,
+    /// Exemplar embeddings only (blend weight 1.0).
+    Embedding,
+    /// Heuristic blended with exemplar embeddings.
+    Blended,
+}
+
 /// Result of classifying a query's complexity.
 #[derive(Debug, Clone)]
 pub struct ClassificationResult {
@@ -36,6 +76,102 @@ pub struct ClassificationResult {
     pub confidence: f32,
     /// Human-readable reason for the classification.
     pub reason: &'static str,
+    /// How the tier was chosen.
+    pub method: ClassificationMethod,
+}
+
+/// Embeds query text for the exemplar-based classifier path.
+///
+/// Returns `None` when the text cannot be embedded; the classifier then
+/// falls back to heuristics. Implemented for closures, so an embedding
+/// model can be plugged in as `move |text| embedder.embed_text(text).ok()`.
+pub trait QueryEmbedder: Send + Sync {
+    /// Returns the embedding of `text`.
+    fn embed(&self, text: &str) -> Option<Vec<f32>>;
+}
+
+impl<F> QueryEmbedder for F
+where
+    F: Fn(&str) -> Option<Vec<f32>> + Send + Sync,
+{
+    fn embed(&self, text: &str) -> Option<Vec<f32>> {
+        self(text)
+    }
+}
+
+/// Labeled exemplar queries used when no others are given.
+pub const DEFAULT_EXEMPLARS: &[(ComplexityTier, &str)] = &[
+    (ComplexityTier::Simple, "hi there"),
+    (ComplexityTier::Simple, "what time is it?"),
+    (ComplexityTier::Simple, "thanks, that's all"),
+    (ComplexityTier::Simple, "what's the capital of France?"),
+    (
+        ComplexityTier::Standard,
+        "what's a good recipe for dinner tonight?",
+    ),
+    (ComplexityTier::Standard, "summarize this article for me"),
+    (ComplexityTier::Standard, "how do I set up a cron job?"),
+    (
+        ComplexityTier::Standard,
+        "write a short email declining the meeting",
+    ),
+    (
+        ComplexityTier::Complex,
+        "prove that there are infinitely many primes",
+    ),
+    (
+        ComplexityTier::Complex,
+        "why does my async Rust code deadlock?",
+    ),
+    (
+        ComplexityTier::Complex,
+        "design a sharded database schema for this workload",
+    ),
+    (
+        ComplexityTier::Complex,
+        "what are the implications of this contract clause?",
+    ),
+];
+
+/// Softmax temperature over cosine similarities to the tier centroids.
+const SIMILARITY_TEMPERATURE: f32 = 0.05;
+
+/// Exemplar-based classifier path: an embedder and one centroid per tier.
+struct EmbeddingPath {
+    embedder: Arc<dyn QueryEmbedder>,
+    centroids: Vec<(ComplexityTier, Vec<f32>)>,
+    blend_weight: f32,
+}
+
+impl EmbeddingPath {
+    /// Probability of each tier in [`ComplexityTier::ALL`] order, or `None`
+    /// if the query cannot be embedded.
+    fn tier_probabilities(&self, message: &str) -> Option<[f32; 3]> {
+        let query = self.embedder.embed(message)?;
+        let mut logits = [f32::NEG_INFINITY; 3];
+        for (tier, centroid) in &self.centroids {
+            let i = ComplexityTier::ALL.iter().position(|t| t == tier)?;
+            logits[i] = cosine_similarity(&query, centroid)? / SIMILARITY_TEMPERATURE;
+        }
+        let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let exp = logits.map(|l| (l - max).exp());
+        let sum: f32 = exp.iter().sum();
+        Some(exp.map(|e| e / sum))
+    }
+}
+
+/// Cosine similarity of two equal-length vectors.
+fn cosine_similarity(a: &[f32], b: &[f32]) -> Option<f32> {
+    if a.len() != b.len() || a.is_empty() {
+        return None;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return None;
+    }
+    Some(dot / (norm_a * norm_b))
 }
 
 /// Simple greeting/farewell patterns (exact match, case-insensitive).
@@ -109,34 +245,109 @@ pub struct QueryClassifier {
     /// Confidence threshold below which uncertain Simple classifications
     /// are upgraded to Standard (default UP rule).
     confidence_threshold: f32,
+    /// Optional exemplar-based path blended with the heuristic.
+    embedding: Option<EmbeddingPath>,
 }
 
 impl QueryClassifier {
     /// Create a new classifier with default confidence threshold.
     pub fn new() -> Self {
-        Self {
-            confidence_threshold: 0.4,
-        }
+        Self::with_threshold(0.4)
     }
 
     /// Create a new classifier with a custom confidence threshold.
     pub fn with_threshold(confidence_threshold: f32) -> Self {
         Self {
             confidence_threshold,
+            embedding: None,
+        }
+    }
+
+    /// Blends exemplar embeddings into classification.
+    ///
+    /// Each tier's centroid is the mean embedding of its `exemplars`.
+    /// `blend_weight` (0.0-1.0) is the share of the embedding path in the
+    /// result. If an exemplar cannot be embedded, or a tier has none, the
+    /// classifier stays heuristic-only.
+    pub fn with_embedder(
+        mut self,
+        embedder: Arc<dyn QueryEmbedder>,
+        exemplars: &[(ComplexityTier, &str)],
+        blend_weight: f32,
+    ) -> Self {
+        let mut centroids = Vec::with_capacity(ComplexityTier::ALL.len());
+        for tier in ComplexityTier::ALL {
+            let embeddings: Option<Vec<Vec<f32>>> = exemplars
+                .iter()
+                .filter(|(t, _)| *t == tier)
+                .map(|(_, text)| embedder.embed(text))
+                .collect();
+            let Some(centroid) = embeddings.as_deref().and_then(mean) else {
+                tracing::warn!(%tier, "no usable exemplars, using heuristic classification");
+                return self;
+            };
+            centroids.push((tier, centroid));
         }
+        self.embedding = Some(EmbeddingPath {
+            embedder,
+            centroids,
+            blend_weight: blend_weight.clamp(0.0, 1.0),
+        });
+        self
     }
 
-    /// Classify a message's complexity using heuristic signals.
+    /// Classify a message's complexity.
     ///
     /// Considers the current message text and recent conversation context
-    /// (last 2-3 messages) to track conversation momentum.
+    /// (last 2-3 messages) to track conversation momentum. With an embedder
+    /// installed, the heuristic tier is blended with the exemplar match.
     pub fn classify(&self, message: &str, recent_context: &[&str]) -> ClassificationResult {
+        let heuristic = self.classify_heuristic(message, recent_context);
+        if message.trim().is_empty() {
+            return heuristic;
+        }
+        let Some(path) = &self.embedding else {
+            return heuristic;
+        };
+        let Some(probabilities) = path.tier_probabilities(message.trim()) else {
+            return heuristic;
+        };
+
+        let w = path.blend_weight;
+        let embedded: f32 = ComplexityTier::ALL
+            .iter()
+            .zip(probabilities)
+            .map(|(tier, p)| tier.ordinal() * p)
+            .sum();
+        let blended = (1.0 - w) * heuristic.tier.ordinal() + w * embedded;
+        let tier = ComplexityTier::from_ordinal(blended);
+        let best = probabilities.iter().copied().fold(0.0, f32::max);
+        let method = if w >= 1.0 {
+            ClassificationMethod::Embedding
+        } else {
+            ClassificationMethod::Blended
+        };
+        ClassificationResult {
+            tier,
+            confidence: (1.0 - w) * heuristic.confidence + w * best,
+            reason: if tier == heuristic.tier {
+                heuristic.reason
+            } else {
+                "reclassified by exemplar similarity"
+            },
+            method,
+        }
+    }
+
+    /// Classify a message's complexity using heuristic signals only.
+    fn classify_heuristic(&self, message: &str, recent_context: &[&str]) -> ClassificationResult {
         let trimmed = message.trim();
         if trimmed.is_empty() {
             return ClassificationResult {
                 tier: ComplexityTier::Simple,
                 confidence: 1.0,
                 reason: "empty message",
+                method: ClassificationMethod::Heuristic,
             };
         }
 
@@ -185,6 +396,7 @@ impl QueryClassifier {
                 tier: ComplexityTier::Standard,
                 confidence,
                 reason: "low confidence, defaulting up",
+                method: ClassificationMethod::Heuristic,
             };
         }
 
@@ -192,6 +404,7 @@ impl QueryClassifier {
             tier,
             confidence,
             reason,
+            method: ClassificationMethod::Heuristic,
         }
     }
 
@@ -259,6 +472,22 @@ impl Default for QueryClassifier {
     }
 }
 
+/// Element-wise mean of equal-length vectors.
+fn mean(vectors: &[Vec<f32>]) -> Option<Vec<f32>> {
+    let first = vectors.first()?;
+    if vectors.iter().any(|v| v.len() != first.len()) {
+        return None;
+    }
+    let mut sum = vec![0.0; first.len()];
+    for v in vectors {
+        for (s, x) in sum.iter_mut().zip(v) {
+            *s += x;
+        }
+    }
+    let n = vectors.len() as f32;
+    Some(sum.into_iter().map(|s| s / n).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -356,6 +585,84 @@ mod tests {
         assert_eq!(ComplexityTier::Complex.to_string(), "complex");
     }
 
+    /// Toy embedder: one dimension per keyword family, so exemplars and
+    /// queries sharing a family embed close together.
+    fn keyword_embedder() -> Arc<dyn QueryEmbedder> {
+        Arc::new(|text: &str| {
+            let lower = text.to_lowercase();
+            let has = |words: &[&str]| -> f32 {
+                if words.iter().any(|w| lower.contains(w)) {
+                    1.0
+                } else {
+                    0.0
+                }
+            };
+            Some(vec![
+                has(&["hello", "thanks"]) + 0.01,
+                has(&["recipe", "weather"]) + 0.01,
+                has(&["prove", "theorem", "deadlock"]) + 0.01,
+            ])
+        })
+    }
+
+    const EXEMPLARS: &[(ComplexityTier, &str)] = &[
+        (ComplexityTier::Simple, "hello"),
+        (ComplexityTier::Simple, "thanks"),
+        (ComplexityTier::Standard, "a recipe"),
+        (ComplexityTier::Standard, "the weather"),
+        (ComplexityTier::Complex, "prove this theorem"),
+        (ComplexityTier::Complex, "fix the deadlock"),
+    ];
+
+    #[test]
+    fn heuristic_only_without_embedder() {
+        let result = QueryClassifier::new().classify("prove it", &[]);
+        assert_eq!(result.method, ClassificationMethod::Heuristic);
+    }
+
+    #[test]
+    fn exemplars_reclassify_short_hard_question() {
+        // No complex keywords, so the heuristic alone says standard.
+        let heuristic = QueryClassifier::new().classify("can you prove fermat's theorem?", &[]);
+        assert_eq!(heuristic.tier, ComplexityTier::Standard);
+
+        let c = QueryClassifier::new().with_embedder(keyword_embedder(), EXEMPLARS, 0.6);
+        let result = c.classify("can you prove fermat's theorem?", &[]);
+        assert_eq!(result.tier, ComplexityTier::Complex);
+        assert_eq!(result.method, ClassificationMethod::Blended);
+        assert_eq!(result.reason, "reclassified by exemplar similarity");
+
+        let c = QueryClassifier::new().with_embedder(keyword_embedder(), EXEMPLARS, 1.0);
+        let result = c.classify("hello!", &[]);
+        assert_eq!(result.tier, ComplexityTier::Simple);
+        assert_eq!(result.method, ClassificationMethod::Embedding);
+    }
+
+    #[test]
+    fn zero_blend_weight_keeps_heuristic_tier() {
+        let c = QueryClassifier::new().with_embedder(keyword_embedder(), EXEMPLARS, 0.0);
+        let heuristic = QueryClassifier::new().classify("prove fermat's theorem?", &[]);
+        assert_eq!(
+            c.classify("prove fermat's theorem?", &[]).tier,
+            heuristic.tier
+        );
+    }
+
+    #[test]
+    fn failing_embedder_falls_back_to_heuristics() {
+        let failing: Arc<dyn QueryEmbedder> = Arc::new(|_: &str| None::<Vec<f32>>);
+        let c = QueryClassifier::new().with_embedder(failing, EXEMPLARS, 0.7);
+        let result = c.classify("prove fermat's theorem?", &[]);
+        assert_eq!(result.method, ClassificationMethod::Heuristic);
+
+        // Embeddable exemplars, but a query the embedder rejects.
+        let picky: Arc<dyn QueryEmbedder> =
+            Arc::new(|text: &str| (!text.contains('?')).then(|| vec![1.0f32, 0.0]));
+        let c = QueryClassifier::new().with_embedder(picky, EXEMPLARS, 0.7);
+        let result = c.classify("prove fermat's theorem?", &[]);
+        assert_eq!(result.method, ClassificationMethod::Heuristic);
+    }
+
     #[test]
     fn high_confidence_on_strong_signals() {
         let c = QueryClassifier::new();
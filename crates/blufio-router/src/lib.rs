@@ -5,15 +5,19 @@
 //! Query complexity classification and model routing for the Blufio agent.
 //!
 //! This crate provides:
-//! - [`QueryClassifier`]: Heuristic complexity classification (zero-cost, zero-latency)
+//! - [`QueryClassifier`]: Heuristic complexity classification (zero-cost, zero-latency),
+//!   optionally blended with exemplar embeddings via a [`QueryEmbedder`]
 //! - [`ModelRouter`]: Budget-aware model selection with per-message overrides
 //!
 //! The router intercepts user messages before LLM calls, selecting the
-//! appropriate Claude model tier (Haiku/Sonnet/Opus) based on query
+//! configured model tier (Haiku/Sonnet/Opus by default) based on query
 //! complexity, budget utilization, and optional per-message overrides.
 
 pub mod classifier;
 pub mod router;
 
-pub use classifier::{ClassificationResult, ComplexityTier, QueryClassifier};
+pub use classifier::{
+    ClassificationMethod, ClassificationResult, ComplexityTier, DEFAULT_EXEMPLARS, QueryClassifier,
+    QueryEmbedder,
+};
 pub use router::{ModelRouter, RoutingDecision, parse_model_override};
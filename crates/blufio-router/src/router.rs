@@ -4,11 +4,16 @@
 //! Model routing with budget-aware downgrades and per-message overrides.
 //!
 //! Orchestrates model selection: per-message override > global force > classify > budget downgrade.
+//!
+//! Tiers map to models through [`RoutingConfig`] (`simple_model`,
+//! `standard_model`, `complex_model`), so the router works for any provider.
+
+use std::sync::Arc;
 
 use blufio_config::model::RoutingConfig;
 use tracing::info;
 
-use crate::classifier::{ComplexityTier, QueryClassifier};
+use crate::classifier::{ComplexityTier, DEFAULT_EXEMPLARS, QueryClassifier, QueryEmbedder};
 
 /// Routing decision with both intended and actual model for cost tracking.
 #[derive(Debug, Clone)]
@@ -21,6 +26,8 @@ pub struct RoutingDecision {
     pub max_tokens: u32,
     /// Whether budget forced a downgrade from intended model.
     pub downgraded: bool,
+    /// Whether an explicit per-message override bypassed budget downgrades.
+    pub force: bool,
     /// Classified complexity tier.
     pub tier: ComplexityTier,
     /// Human-readable reason for routing decision.
@@ -42,12 +49,23 @@ impl ModelRouter {
         }
     }
 
+    /// Blend exemplar embeddings into classification, weighted by
+    /// `embedding_blend_weight` from the routing config.
+    pub fn with_embedder(mut self, embedder: Arc<dyn QueryEmbedder>) -> Self {
+        self.classifier = self.classifier.with_embedder(
+            embedder,
+            DEFAULT_EXEMPLARS,
+            self.config.embedding_blend_weight,
+        );
+        self
+    }
+
     /// Route a message to the appropriate model.
     ///
     /// Priority order:
-    /// 1. Per-message override (/opus, /haiku, /sonnet)
+    /// 1. Per-message tier override (see [`parse_model_override`])
     /// 2. Global force_model config
-    /// 3. Heuristic classification + budget-aware downgrade
+    /// 3. Classification (heuristic, optionally embedding-blended) + budget-aware downgrade
     ///
     /// `budget_utilization` is a fraction (0.0-1.0+) of the higher of daily/monthly budget.
     pub fn route(
@@ -56,18 +74,22 @@ impl ModelRouter {
         recent_context: &[&str],
         budget_utilization: f64,
     ) -> RoutingDecision {
-        // 1. Check per-message override
-        let (override_model, _clean_text) = parse_model_override(message);
-        if let Some(model) = override_model {
-            let tier = self.tier_for_model(&model);
+        // 1. Check per-message override. It bypasses the budget unless the
+        //    operator forbids that, in which case it is downgraded below.
+        let (override_tier, _clean_text) = parse_model_override(message);
+        if let Some(tier) = override_tier
+            && self.config.allow_override_budget
+        {
+            let model = self.model_for_tier(tier);
             let max_tokens = self.max_tokens_for_tier(tier);
             return RoutingDecision {
                 intended_model: model.clone(),
                 actual_model: model,
                 max_tokens,
                 downgraded: false,
+                force: true,
                 tier,
-                reason: "per-message override".to_string(),
+                reason: "explicit override".to_string(),
             };
         }
 
@@ -80,32 +102,38 @@ impl ModelRouter {
                 actual_model: forced.clone(),
                 max_tokens,
                 downgraded: false,
+                force: false,
                 tier,
                 reason: "global force_model config".to_string(),
             };
         }
 
-        // 3. Classify complexity
-        let classification = self.classifier.classify(message, recent_context);
+        // 3. Classify complexity, unless an override already chose the tier
+        let (tier, tier_reason) = match override_tier {
+            Some(tier) => (tier, "per-message override"),
+            None => {
+                let classification = self.classifier.classify(message, recent_context);
+                (classification.tier, classification.reason)
+            }
+        };
 
         // Map tier to model
-        let intended = self.model_for_tier(classification.tier);
+        let intended = self.model_for_tier(tier);
 
         // 4. Apply budget downgrade
-        let (actual, downgraded) =
-            self.apply_budget_downgrade(classification.tier, &intended, budget_utilization);
+        let (actual, downgraded) = self.apply_budget_downgrade(tier, &intended, budget_utilization);
 
         let max_tokens = self.max_tokens_for_model(&actual);
 
         let reason = if downgraded {
             format!(
                 "{} (downgraded from {} due to budget at {:.0}%)",
-                classification.reason,
-                Self::short_model_name(&intended),
+                tier_reason,
+                self.short_model_name(&intended),
                 budget_utilization * 100.0
             )
         } else {
-            classification.reason.to_string()
+            tier_reason.to_string()
         };
 
         if downgraded {
@@ -122,7 +150,8 @@ impl ModelRouter {
             actual_model: actual,
             max_tokens,
             downgraded,
-            tier: classification.tier,
+            force: false,
+            tier,
             reason,
         }
     }
@@ -135,7 +164,18 @@ impl ModelRouter {
         }
     }
 
+    /// Returns the tier `model` is configured for. Models outside the
+    /// configured tiers (e.g. a `force_model`) are placed by name.
     fn tier_for_model(&self, model: &str) -> ComplexityTier {
+        if model == self.config.simple_model {
+            return ComplexityTier::Simple;
+        }
+        if model == self.config.complex_model {
+            return ComplexityTier::Complex;
+        }
+        if model == self.config.standard_model {
+            return ComplexityTier::Standard;
+        }
         let lower = model.to_lowercase();
         if lower.contains("haiku") {
             ComplexityTier::Simple
@@ -183,37 +223,38 @@ impl ModelRouter {
         }
     }
 
-    /// Get a short display name for a model string.
-    pub fn short_model_name(model: &str) -> &str {
-        let lower = model.to_lowercase();
-        if lower.contains("opus") {
-            "Opus"
-        } else if lower.contains("haiku") {
-            "Haiku"
-        } else {
-            "Sonnet"
+    /// Get the configured display name of the tier `model` belongs to.
+    pub fn short_model_name(&self, model: &str) -> &str {
+        match self.tier_for_model(model) {
+            ComplexityTier::Simple => &self.config.simple_name,
+            ComplexityTier::Standard => &self.config.standard_name,
+            ComplexityTier::Complex => &self.config.complex_name,
         }
     }
 }
 
-/// Parse a per-message model override prefix from user input.
+/// Parse a per-message tier override prefix from user input.
 ///
-/// Supports `/opus `, `/haiku `, `/sonnet ` prefixes (with trailing space).
-/// Returns `(Some(model_string), rest_of_message)` if an override is found,
+/// Supports `/simple `, `/standard `, `/complex ` prefixes (with trailing
+/// space) and their Claude aliases `/haiku `, `/sonnet `, `/opus `.
+/// Returns `(Some(tier), rest_of_message)` if an override is found,
 /// or `(None, original_message)` if no override.
 ///
 /// The override prefix is stripped from the returned message text.
-pub fn parse_model_override(text: &str) -> (Option<String>, &str) {
+pub fn parse_model_override(text: &str) -> (Option<ComplexityTier>, &str) {
+    const PREFIXES: [(&str, ComplexityTier); 6] = [
+        ("/simple ", ComplexityTier::Simple),
+        ("/haiku ", ComplexityTier::Simple),
+        ("/standard ", ComplexityTier::Standard),
+        ("/sonnet ", ComplexityTier::Standard),
+        ("/complex ", ComplexityTier::Complex),
+        ("/opus ", ComplexityTier::Complex),
+    ];
     let trimmed = text.trim_start();
-    if let Some(rest) = trimmed.strip_prefix("/opus ") {
-        (Some("claude-opus-4-20250514".to_string()), rest)
-    } else if let Some(rest) = trimmed.strip_prefix("/haiku ") {
-        (Some("claude-haiku-4-5-20250901".to_string()), rest)
-    } else if let Some(rest) = trimmed.strip_prefix("/sonnet ") {
-        (Some("claude-sonnet-4-20250514".to_string()), rest)
-    } else {
-        (None, text)
-    }
+    PREFIXES
+        .iter()
+        .find_map(|(prefix, tier)| trimmed.strip_prefix(prefix).map(|rest| (Some(*tier), rest)))
+        .unwrap_or((None, text))
 }
 
 #[cfg(test)]
@@ -226,25 +267,37 @@ mod tests {
 
     #[test]
     fn parse_override_opus() {
-        let (model, rest) = parse_model_override("/opus analyze this code");
-        assert_eq!(model.unwrap(), "claude-opus-4-20250514");
+        let (tier, rest) = parse_model_override("/opus analyze this code");
+        assert_eq!(tier, Some(ComplexityTier::Complex));
         assert_eq!(rest, "analyze this code");
     }
 
     #[test]
     fn parse_override_haiku() {
-        let (model, rest) = parse_model_override("/haiku what time");
-        assert_eq!(model.unwrap(), "claude-haiku-4-5-20250901");
+        let (tier, rest) = parse_model_override("/haiku what time");
+        assert_eq!(tier, Some(ComplexityTier::Simple));
         assert_eq!(rest, "what time");
     }
 
     #[test]
     fn parse_override_sonnet() {
-        let (model, rest) = parse_model_override("/sonnet help me");
-        assert_eq!(model.unwrap(), "claude-sonnet-4-20250514");
+        let (tier, rest) = parse_model_override("/sonnet help me");
+        assert_eq!(tier, Some(ComplexityTier::Standard));
         assert_eq!(rest, "help me");
     }
 
+    #[test]
+    fn parse_override_tier_names() {
+        assert_eq!(
+            parse_model_override("/complex plan it"),
+            (Some(ComplexityTier::Complex), "plan it")
+        );
+        assert_eq!(
+            parse_model_override("/simple hi"),
+            (Some(ComplexityTier::Simple), "hi")
+        );
+    }
+
     #[test]
     fn parse_override_none() {
         let (model, rest) = parse_model_override("normal message");
@@ -298,6 +351,33 @@ mod tests {
         let decision = router.route("/opus analyze this", &[], 0.96);
         assert!(decision.actual_model.contains("opus"));
         assert!(!decision.downgraded);
+        assert!(decision.force);
+        assert_eq!(decision.reason, "explicit override");
+    }
+
+    #[test]
+    fn route_override_downgraded_when_budget_override_forbidden() {
+        let router = ModelRouter::new(RoutingConfig {
+            allow_override_budget: false,
+            ..test_config()
+        });
+
+        let decision = router.route("/opus analyze this", &[], 0.85);
+        assert_eq!(decision.intended_model, test_config().complex_model);
+        assert_eq!(decision.actual_model, test_config().standard_model);
+        assert!(decision.downgraded);
+        assert!(!decision.force);
+        assert!(
+            decision
+                .reason
+                .starts_with("per-message override (downgraded")
+        );
+
+        // Below the downgrade threshold the override still picks the tier.
+        let decision = router.route("/opus analyze this", &[], 0.1);
+        assert_eq!(decision.actual_model, test_config().complex_model);
+        assert!(!decision.downgraded);
+        assert_eq!(decision.reason, "per-message override");
     }
 
     #[test]
@@ -321,17 +401,55 @@ mod tests {
 
     #[test]
     fn short_model_name_extraction() {
+        let router = ModelRouter::new(test_config());
+        assert_eq!(router.short_model_name("claude-opus-4-20250514"), "Opus");
         assert_eq!(
-            ModelRouter::short_model_name("claude-opus-4-20250514"),
-            "Opus"
-        );
-        assert_eq!(
-            ModelRouter::short_model_name("claude-haiku-4-5-20250901"),
+            router.short_model_name("claude-haiku-4-5-20250901"),
             "Haiku"
         );
         assert_eq!(
-            ModelRouter::short_model_name("claude-sonnet-4-20250514"),
+            router.short_model_name("claude-sonnet-4-20250514"),
             "Sonnet"
         );
     }
+
+    fn openai_config() -> RoutingConfig {
+        RoutingConfig {
+            simple_model: "gpt-4o-mini".to_string(),
+            standard_model: "gpt-4o".to_string(),
+            complex_model: "o3".to_string(),
+            simple_name: "Mini".to_string(),
+            standard_name: "4o".to_string(),
+            complex_name: "o3".to_string(),
+            ..test_config()
+        }
+    }
+
+    #[test]
+    fn custom_tiers_route_complex_query_to_configured_model() {
+        let router = ModelRouter::new(openai_config());
+
+        let decision = router.route(
+            "analyze this code and refactor it for better performance",
+            &[],
+            0.0,
+        );
+        assert_eq!(decision.tier, ComplexityTier::Complex);
+        assert_eq!(decision.actual_model, "o3");
+        assert_eq!(decision.max_tokens, 8192);
+
+        // Budget downgrade stays within the configured tiers.
+        let decision = router.route(
+            "analyze this code and refactor it for better performance",
+            &[],
+            0.85,
+        );
+        assert_eq!(decision.actual_model, "gpt-4o");
+        assert_eq!(router.short_model_name(&decision.actual_model), "4o");
+        assert!(decision.reason.contains("downgraded from o3"));
+
+        let decision = router.route("/opus hi", &[], 0.0);
+        assert_eq!(decision.actual_model, "o3");
+        assert_eq!(router.short_model_name("gpt-4o-mini"), "Mini");
+    }
 }
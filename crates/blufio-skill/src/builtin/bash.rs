@@ -4,15 +4,204 @@
 //! Built-in bash command execution tool.
 //!
 //! Executes shell commands via `bash -c` and returns stdout/stderr.
-//! No restrictions on bash access -- this is a personal agent on a single-user VPS.
+//! Unrestricted by default -- this is a personal agent on a single-user VPS.
+//! A [`BashPolicy`] can deny substrings and limit commands to allowed
+//! prefixes. In dry-run mode the command is echoed back instead of being run.
 
 use async_trait::async_trait;
+use blufio_config::model::BashToolConfig;
 use blufio_core::BlufioError;
 
 use crate::tool::{Tool, ToolOutput};
 
+/// Reason given when a command line contains command substitution.
+const SUBSTITUTION_DENIED: &str = "command substitution is not allowed by the command allowlist";
+
+/// Which commands [`BashTool`] may run.
+///
+/// Commands are compared with whitespace runs collapsed to single spaces.
+/// Denied substrings are searched in the whole command line. When
+/// `allowed_prefixes` is non-empty, every segment of a chained command
+/// (split on unquoted `&&`, `||`, `;`, `|`, `&` and newlines) must start with
+/// one of them. Command substitution (`$(...)`, backticks), process
+/// substitution, here-documents, here-strings and file redirections are
+/// rejected because their effects cannot be checked; only descriptor
+/// duplication such as `2>&1` is allowed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BashPolicy {
+    /// Command prefixes that may run; empty allows any command.
+    pub allowed_prefixes: Vec<String>,
+    /// Substrings that deny a command wherever they appear.
+    pub denied_substrings: Vec<String>,
+}
+
+impl BashPolicy {
+    /// Checks `command`, returning why it is denied.
+    pub fn check(&self, command: &str) -> Result<(), String> {
+        let normalized = normalize_whitespace(command);
+
+        for denied in &self.denied_substrings {
+            let denied = normalize_whitespace(denied);
+            if !denied.is_empty() && normalized.contains(&denied) {
+                return Err(format!("command contains denied pattern '{denied}'"));
+            }
+        }
+
+        if self.allowed_prefixes.is_empty() {
+            return Ok(());
+        }
+        let segments = split_segments(command)?;
+        let allowed: Vec<String> = self
+            .allowed_prefixes
+            .iter()
+            .map(|p| normalize_whitespace(p))
+            .filter(|p| !p.is_empty())
+            .collect();
+        for segment in segments {
+            let permitted = allowed
+                .iter()
+                .any(|p| segment == *p || segment.starts_with(&format!("{p} ")));
+            if !permitted {
+                return Err(format!(
+                    "'{segment}' does not match an allowed command prefix"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&BashToolConfig> for BashPolicy {
+    fn from(config: &BashToolConfig) -> Self {
+        Self {
+            allowed_prefixes: config.allowed_prefixes.clone(),
+            denied_substrings: config.denied_substrings.clone(),
+        }
+    }
+}
+
+/// Collapses runs of whitespace to single spaces and trims the ends.
+fn normalize_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Splits a command line into its chained commands, normalized and non-empty.
+///
+/// A small shell lexer: quotes and backslash escapes are honoured, so
+/// separators and metacharacters inside quotes are plain text. Constructs the
+/// allowlist cannot check are rejected, command substitution even inside
+/// double quotes, where bash still expands it.
+fn split_segments(command: &str) -> Result<Vec<String>, String> {
+    let chars: Vec<char> = command.chars().collect();
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match quote {
+            Some('\'') => {
+                if c == '\'' {
+                    quote = None;
+                }
+                current.push(c);
+            }
+            Some(_) => match c {
+                '\\' => {
+                    current.push(c);
+                    if let Some(n) = next {
+                        current.push(n);
+                        i += 1;
+                    }
+                }
+                '"' => {
+                    quote = None;
+                    current.push(c);
+                }
+                '`' => return Err(SUBSTITUTION_DENIED.into()),
+                '$' if next == Some('(') => return Err(SUBSTITUTION_DENIED.into()),
+                _ => current.push(c),
+            },
+            None => match c {
+                '\\' => {
+                    current.push(c);
+                    if let Some(n) = next {
+                        current.push(n);
+                        i += 1;
+                    }
+                }
+                '\'' | '"' => {
+                    quote = Some(c);
+                    current.push(c);
+                }
+                '`' => return Err(SUBSTITUTION_DENIED.into()),
+                '$' if next == Some('(') => return Err(SUBSTITUTION_DENIED.into()),
+                '>' | '<' => {
+                    // `2>&1` duplicates a descriptor and opens no file.
+                    if next == Some('&') && chars.get(i + 2).is_some_and(char::is_ascii_digit) {
+                        current.extend(&chars[i..i + 3]);
+                        i += 3;
+                        continue;
+                    }
+                    let construct = match (c, next, chars.get(i + 2)) {
+                        (_, Some('('), _) => "process substitution",
+                        ('<', Some('<'), Some('<')) => "a here-string",
+                        ('<', Some('<'), _) => "a here-document",
+                        _ => "redirection",
+                    };
+                    return Err(format!(
+                        "{construct} is not allowed by the command allowlist"
+                    ));
+                }
+                ';' | '\n' | '|' | '&' => {
+                    segments.push(std::mem::take(&mut current));
+                    // `&&` and `||` are one separator.
+                    if (c == '&' || c == '|') && next == Some(c) {
+                        i += 1;
+                    }
+                }
+                _ => current.push(c),
+            },
+        }
+        i += 1;
+    }
+    if quote.is_some() {
+        return Err("command has an unterminated quote".into());
+    }
+    segments.push(current);
+    Ok(segments
+        .iter()
+        .map(|s| normalize_whitespace(s))
+        .filter(|s| !s.is_empty())
+        .collect())
+}
+
 /// Executes bash commands and returns stdout/stderr.
-pub struct BashTool;
+#[derive(Debug, Clone, Default)]
+pub struct BashTool {
+    dry_run: bool,
+    policy: BashPolicy,
+}
+
+impl BashTool {
+    /// Creates a bash tool that executes commands.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether commands are reported instead of executed.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Restricts which commands may run.
+    pub fn with_policy(mut self, policy: BashPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
 
 #[async_trait]
 impl Tool for BashTool {
@@ -42,6 +231,20 @@ impl Tool for BashTool {
             BlufioError::skill_execution_msg("missing required 'command' parameter")
         })?;
 
+        if let Err(reason) = self.policy.check(command) {
+            return Ok(ToolOutput {
+                content: format!("Command denied by bash policy: {reason}"),
+                is_error: true,
+            });
+        }
+
+        if self.dry_run {
+            return Ok(ToolOutput {
+                content: format!("[dry-run] would execute: {command}"),
+                is_error: false,
+            });
+        }
+
         let output = tokio::process::Command::new("bash")
             .arg("-c")
             .arg(command)
@@ -64,6 +267,11 @@ impl Tool for BashTool {
 
         Ok(ToolOutput { content, is_error })
     }
+
+    fn is_parallel_safe(&self) -> bool {
+        // Commands share the host's filesystem and processes.
+        false
+    }
 }
 
 #[cfg(test)]
@@ -73,7 +281,7 @@ mod tests {
     #[tokio::test]
     #[cfg(unix)]
     async fn bash_tool_echo_hello() {
-        let tool = BashTool;
+        let tool = BashTool::new();
         let input = serde_json::json!({"command": "echo hello"});
         let output = tool.invoke(input).await.unwrap();
         assert_eq!(output.content.trim(), "hello");
@@ -83,7 +291,7 @@ mod tests {
     #[tokio::test]
     #[cfg(unix)]
     async fn bash_tool_exit_nonzero_returns_error() {
-        let tool = BashTool;
+        let tool = BashTool::new();
         let input = serde_json::json!({"command": "exit 1"});
         let output = tool.invoke(input).await.unwrap();
         assert!(output.is_error);
@@ -92,15 +300,180 @@ mod tests {
 
     #[tokio::test]
     async fn bash_tool_missing_command_returns_error() {
-        let tool = BashTool;
+        let tool = BashTool::new();
         let input = serde_json::json!({});
         let result = tool.invoke(input).await;
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn bash_tool_dry_run_skips_execution() {
+        let marker = std::env::temp_dir().join(format!("blufio-bash-dry-{}", std::process::id()));
+        let _ = std::fs::remove_file(&marker);
+        let command = format!("touch {}", marker.display());
+
+        let dry = BashTool::new().with_dry_run(true);
+        let output = dry
+            .invoke(serde_json::json!({"command": command}))
+            .await
+            .unwrap();
+        assert_eq!(
+            output.content,
+            format!("[dry-run] would execute: {command}")
+        );
+        assert!(!output.is_error);
+        assert!(!marker.exists());
+
+        let output = BashTool::new()
+            .invoke(serde_json::json!({"command": command}))
+            .await
+            .unwrap();
+        assert!(!output.is_error);
+        assert!(marker.exists());
+        std::fs::remove_file(&marker).unwrap();
+    }
+
+    fn policy(allowed: &[&str], denied: &[&str]) -> BashPolicy {
+        BashPolicy {
+            allowed_prefixes: allowed.iter().map(|s| s.to_string()).collect(),
+            denied_substrings: denied.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn policy_parses_from_config() {
+        let config: BashToolConfig = toml::from_str(
+            r#"
+            allowed_prefixes = ["ls", "git status"]
+            denied_substrings = ["rm -rf"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            BashPolicy::from(&config),
+            policy(&["ls", "git status"], &["rm -rf"])
+        );
+    }
+
+    #[test]
+    fn empty_policy_allows_everything() {
+        assert!(BashPolicy::default().check("rm -rf /tmp/x").is_ok());
+    }
+
+    #[test]
+    fn allowlist_matches_whole_word_prefixes() {
+        let p = policy(&["ls", "git status"], &[]);
+        assert!(p.check("ls").is_ok());
+        assert!(p.check("ls   -la").is_ok());
+        assert!(p.check("git  status --short").is_ok());
+        assert!(p.check("lsblk").is_err());
+        assert!(p.check("git push").is_err());
+        assert!(p.check("ls $(rm -rf ~)").is_err());
+    }
+
+    #[test]
+    fn denied_substrings_match_after_whitespace_normalization() {
+        let p = policy(&[], &["rm -rf", "curl | sh"]);
+        assert!(p.check("rm  -rf /").is_err());
+        assert!(p.check("curl |\tsh").is_err());
+        assert!(p.check("rm -r build").is_ok());
+    }
+
+    #[test]
+    fn chained_commands_check_every_segment() {
+        let p = policy(&["ls", "echo"], &[]);
+        assert!(p.check("ls && echo done; echo ok").is_ok());
+        assert!(p.check("ls && rm file").is_err());
+        assert!(p.check("echo hi; wget evil").is_err());
+        assert!(p.check("ls | sh").is_err());
+        assert!(p.check("echo a ||\nls").is_ok());
+        assert!(p.check("echo a\nrm file").is_err());
+        assert!(p.check("ls & rm file").is_err());
+        assert!(p.check("ls missing 2>&1").is_ok());
+    }
+
+    #[test]
+    fn separators_inside_quotes_do_not_split() {
+        let p = policy(&["echo"], &[]);
+        assert!(p.check("echo 'a; rm file'").is_ok());
+        assert!(p.check("echo \"a && rm file\" | echo").is_ok());
+        assert!(p.check("echo 'unterminated").is_err());
+    }
+
+    #[test]
+    fn command_substitution_is_rejected_outside_single_quotes() {
+        let p = policy(&["echo"], &[]);
+        assert!(p.check("echo `rm file`").is_err());
+        assert!(p.check("echo \"$(rm file)\"").is_err());
+        assert!(p.check("echo \"`rm file`\"").is_err());
+        assert!(p.check("echo '$(rm file)'").is_ok());
+    }
+
+    #[test]
+    fn process_substitution_is_rejected() {
+        let p = policy(&["cat", "tee"], &[]);
+        let err = p.check("cat <(rm file)").unwrap_err();
+        assert!(err.contains("process substitution"));
+        assert!(p.check("echo hi | tee >(sh)").is_err());
+    }
+
+    #[test]
+    fn file_redirection_is_rejected() {
+        let p = policy(&["echo", "cat"], &[]);
+        assert!(
+            p.check("echo pwned > ~/.bashrc")
+                .unwrap_err()
+                .contains("redirection")
+        );
+        assert!(p.check("echo pwned >> ~/.bashrc").is_err());
+        assert!(p.check("cat < /etc/shadow").is_err());
+        assert!(p.check("echo pwned &> out.txt").is_err());
+        assert!(p.check("echo 'a > b'").is_ok());
+        assert!(p.check("echo a\\>b").is_ok());
+    }
+
+    #[test]
+    fn here_strings_and_documents_are_rejected() {
+        let p = policy(&["cat"], &[]);
+        assert!(
+            p.check("cat <<< 'text'")
+                .unwrap_err()
+                .contains("here-string")
+        );
+        assert!(
+            p.check("cat <<EOF\nhi\nEOF")
+                .unwrap_err()
+                .contains("here-document")
+        );
+    }
+
+    #[tokio::test]
+    async fn denied_command_returns_error_without_running() {
+        let tool = BashTool::new().with_policy(policy(&[], &["rm -rf"]));
+        let output = tool
+            .invoke(serde_json::json!({"command": "rm -rf /nonexistent"}))
+            .await
+            .unwrap();
+        assert!(output.is_error);
+        assert!(output.content.contains("denied pattern 'rm -rf'"));
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn allowed_command_still_runs() {
+        let tool = BashTool::new().with_policy(policy(&["echo"], &["rm -rf"]));
+        let output = tool
+            .invoke(serde_json::json!({"command": "echo hello && echo world"}))
+            .await
+            .unwrap();
+        assert!(!output.is_error);
+        assert_eq!(output.content, "hello\nworld\n");
+    }
+
     #[test]
     fn bash_tool_parameters_schema_has_required_command() {
-        let tool = BashTool;
+        let tool = BashTool::new();
         let schema = tool.parameters_schema();
         let required = schema["required"].as_array().unwrap();
         assert!(required.iter().any(|v| v == "command"));
@@ -4,19 +4,225 @@
 //! Built-in file I/O tool.
 //!
 //! Reads and writes files on the filesystem. Full filesystem access is
-//! permitted -- consistent with bash access for the personal agent.
-//! Read contents are truncated to 100KB to prevent excessive token usage.
+//! permitted by default -- consistent with bash access for the personal
+//! agent -- unless an allowed root is configured.
+//!
+//! Reads are line-paginated (`offset`/`limit`, negative offsets count from the
+//! end of the file) and capped at `max_bytes` (100KB by default) to prevent
+//! excessive token usage. Writes either replace the file or append to it.
+
+use std::path::{Component, Path, PathBuf};
 
 use async_trait::async_trait;
 use blufio_core::BlufioError;
+use tokio::io::AsyncWriteExt;
 
 use crate::tool::{Tool, ToolOutput};
 
-/// Maximum file read size in bytes (100KB).
-const MAX_READ_SIZE: usize = 100 * 1024;
+/// Default maximum bytes of file content returned by a read (100KB).
+const DEFAULT_MAX_BYTES: usize = 100 * 1024;
 
 /// Reads and writes files on the filesystem.
-pub struct FileTool;
+#[derive(Debug, Clone)]
+pub struct FileTool {
+    /// Directory all paths must resolve into. `None` allows any path.
+    allowed_root: Option<PathBuf>,
+    /// Upper bound on the content bytes returned by one read.
+    max_bytes: usize,
+}
+
+impl Default for FileTool {
+    fn default() -> Self {
+        Self {
+            allowed_root: None,
+            max_bytes: DEFAULT_MAX_BYTES,
+        }
+    }
+}
+
+impl FileTool {
+    /// Creates a file tool with unrestricted paths and the default read cap.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts reads and writes to paths inside `root`.
+    pub fn with_allowed_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.allowed_root = Some(root.into());
+        self
+    }
+
+    /// Sets the maximum bytes of content returned by one read.
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Validates `path` against the allowed root.
+    ///
+    /// Like the sandbox's capability paths, the check is a prefix match, but
+    /// on resolved paths, so neither `..` nor symlinks can escape the root.
+    fn check_path(&self, path: &str) -> Result<(), String> {
+        match &self.allowed_root {
+            Some(root) => check_within_root(root, path),
+            None => Ok(()),
+        }
+    }
+
+    async fn read(&self, path: &str, input: &serde_json::Value) -> Result<ToolOutput, BlufioError> {
+        let contents = tokio::fs::read_to_string(path)
+            .await
+            .map_err(BlufioError::skill_execution_failed)?;
+        let offset = input["offset"].as_i64().unwrap_or(1);
+        let limit = input["limit"].as_u64().map(|l| l as usize);
+        let page = read_page(&contents, offset, limit, self.max_bytes);
+        Ok(ToolOutput {
+            content: serde_json::json!({
+                "content": page.content,
+                "start_line": page.start_line,
+                "end_line": page.end_line,
+                "eof": page.eof,
+            })
+            .to_string(),
+            is_error: false,
+        })
+    }
+
+    async fn write(
+        &self,
+        path: &str,
+        input: &serde_json::Value,
+    ) -> Result<ToolOutput, BlufioError> {
+        let content = input["content"].as_str().ok_or_else(|| {
+            BlufioError::skill_execution_msg(
+                "missing required 'content' parameter for write action",
+            )
+        })?;
+        let append = input["append"].as_bool().unwrap_or(false);
+
+        if append {
+            let mut file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .await
+                .map_err(BlufioError::skill_execution_failed)?;
+            file.write_all(content.as_bytes())
+                .await
+                .map_err(BlufioError::skill_execution_failed)?;
+            file.flush()
+                .await
+                .map_err(BlufioError::skill_execution_failed)?;
+        } else {
+            tokio::fs::write(path, content)
+                .await
+                .map_err(BlufioError::skill_execution_failed)?;
+        }
+
+        let verb = if append { "appended" } else { "wrote" };
+        Ok(ToolOutput {
+            content: format!("Successfully {verb} {} bytes to '{path}'", content.len()),
+            is_error: false,
+        })
+    }
+}
+
+/// One page of a line-paginated read.
+#[derive(Debug, PartialEq)]
+struct Page {
+    content: String,
+    /// 1-based number of the first line returned.
+    start_line: usize,
+    /// 1-based number of the last line returned; `start_line - 1` when empty.
+    end_line: usize,
+    /// Whether the page reaches the end of the file.
+    eof: bool,
+}
+
+/// Selects lines from `contents`.
+///
+/// `offset` is the 1-based first line (0 is treated as 1); negative values
+/// count back from the end (`-10` returns the last 10 lines). At most `limit` lines and
+/// `max_bytes` bytes are returned; a single line longer than the cap is cut
+/// at a character boundary.
+fn read_page(contents: &str, offset: i64, limit: Option<usize>, max_bytes: usize) -> Page {
+    let lines: Vec<&str> = contents.split_inclusive('\n').collect();
+    let total = lines.len();
+    let start = if offset >= 0 {
+        (offset.max(1) as usize - 1).min(total)
+    } else {
+        total.saturating_sub(offset.unsigned_abs() as usize)
+    };
+    let wanted = limit.unwrap_or(total).min(total - start);
+
+    let mut content = String::new();
+    let mut taken = 0;
+    for line in &lines[start..start + wanted] {
+        if content.len() + line.len() > max_bytes {
+            if taken == 0 {
+                let mut end = max_bytes.min(line.len());
+                while !line.is_char_boundary(end) {
+                    end -= 1;
+                }
+                content.push_str(&line[..end]);
+                taken = 1;
+            }
+            break;
+        }
+        content.push_str(line);
+        taken += 1;
+    }
+
+    let partial_line = taken == 1 && content.len() < lines.get(start).map_or(0, |l| l.len());
+    Page {
+        content,
+        start_line: start + 1,
+        end_line: start + taken,
+        eof: start + taken == total && !partial_line,
+    }
+}
+
+/// Rejects `path` unless it resolves to `root` or a path inside it.
+pub(crate) fn check_within_root(root: &Path, path: &str) -> Result<(), String> {
+    let root = resolve(root);
+    if resolve(Path::new(path)).starts_with(&root) {
+        Ok(())
+    } else {
+        Err(format!(
+            "path '{path}' is not within the allowed root '{}'",
+            root.display()
+        ))
+    }
+}
+
+/// Resolves `path` the way the OS would: the longest existing ancestor is
+/// canonicalized (following symlinks and `..`), and the remaining components,
+/// which do not exist yet, are appended with `.` and `..` folded.
+fn resolve(path: &Path) -> PathBuf {
+    let components: Vec<Component> = path.components().collect();
+    for split in (0..=components.len()).rev() {
+        let existing: PathBuf = components[..split].iter().collect();
+        let base = if split == 0 {
+            std::env::current_dir()
+        } else {
+            std::fs::canonicalize(&existing)
+        };
+        let Ok(mut resolved) = base else {
+            continue;
+        };
+        for component in &components[split..] {
+            match component {
+                Component::ParentDir => {
+                    resolved.pop();
+                }
+                Component::CurDir => {}
+                other => resolved.push(other),
+            }
+        }
+        return resolved;
+    }
+    path.to_path_buf()
+}
 
 #[async_trait]
 impl Tool for FileTool {
@@ -25,7 +231,9 @@ impl Tool for FileTool {
     }
 
     fn description(&self) -> &str {
-        "Read or write files on the filesystem"
+        "Read or write files on the filesystem. Reads return JSON: {\"content\", \
+         \"start_line\", \"end_line\", \"eof\"}; page through large files with \
+         offset/limit (a negative offset reads the last lines)."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -44,6 +252,19 @@ impl Tool for FileTool {
                 "content": {
                     "type": "string",
                     "description": "Content to write (required for write action)"
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Read: 1-based first line (default 1). Negative values count from the end, e.g. -20 for the last 20 lines"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Read: maximum number of lines to return (default: all, subject to the size cap)"
+                },
+                "append": {
+                    "type": "boolean",
+                    "description": "Write: append to the file instead of replacing it (default false)"
                 }
             },
             "required": ["action", "path"]
@@ -59,51 +280,27 @@ impl Tool for FileTool {
             .as_str()
             .ok_or_else(|| BlufioError::skill_execution_msg("missing required 'path' parameter"))?;
 
+        if let Err(reason) = self.check_path(path) {
+            return Ok(ToolOutput {
+                content: reason,
+                is_error: true,
+            });
+        }
+
         match action {
-            "read" => {
-                let contents = tokio::fs::read_to_string(path)
-                    .await
-                    .map_err(BlufioError::skill_execution_failed)?;
-
-                // Truncate if too large.
-                let output = if contents.len() > MAX_READ_SIZE {
-                    format!(
-                        "{}...\n\n[File truncated from {} to {} bytes]",
-                        &contents[..MAX_READ_SIZE],
-                        contents.len(),
-                        MAX_READ_SIZE
-                    )
-                } else {
-                    contents
-                };
-
-                Ok(ToolOutput {
-                    content: output,
-                    is_error: false,
-                })
-            }
-            "write" => {
-                let content = input["content"].as_str().ok_or_else(|| {
-                    BlufioError::skill_execution_msg(
-                        "missing required 'content' parameter for write action",
-                    )
-                })?;
-
-                tokio::fs::write(path, content)
-                    .await
-                    .map_err(BlufioError::skill_execution_failed)?;
-
-                Ok(ToolOutput {
-                    content: format!("Successfully wrote {} bytes to '{path}'", content.len()),
-                    is_error: false,
-                })
-            }
+            "read" => self.read(path, &input).await,
+            "write" => self.write(path, &input).await,
             other => Ok(ToolOutput {
                 content: format!("Unknown action '{other}'. Supported actions: 'read', 'write'."),
                 is_error: true,
             }),
         }
     }
+
+    fn is_parallel_safe(&self) -> bool {
+        // Writes to the same file must not interleave.
+        false
+    }
 }
 
 #[cfg(test)]
@@ -112,7 +309,7 @@ mod tests {
 
     #[tokio::test]
     async fn file_tool_read_nonexistent_returns_error() {
-        let tool = FileTool;
+        let tool = FileTool::new();
         let input = serde_json::json!({
             "action": "read",
             "path": "/tmp/blufio-test-nonexistent-file-xyz-12345"
@@ -127,7 +324,7 @@ mod tests {
         let file_path = dir.path().join("test.txt");
         let path_str = file_path.to_str().unwrap();
 
-        let tool = FileTool;
+        let tool = FileTool::new();
 
         // Write.
         let write_input = serde_json::json!({
@@ -146,12 +343,215 @@ mod tests {
         });
         let read_output = tool.invoke(read_input).await.unwrap();
         assert!(!read_output.is_error);
-        assert_eq!(read_output.content, "hello from blufio");
+        let page: serde_json::Value = serde_json::from_str(&read_output.content).unwrap();
+        assert_eq!(page["content"], "hello from blufio");
+        assert_eq!(page["start_line"], 1);
+        assert_eq!(page["end_line"], 1);
+        assert_eq!(page["eof"], true);
+    }
+
+    fn numbered_lines(n: usize) -> String {
+        (1..=n).map(|i| format!("line {i}\n")).collect()
+    }
+
+    #[test]
+    fn read_page_offset_and_limit() {
+        let text = numbered_lines(10);
+        let page = read_page(&text, 3, Some(2), DEFAULT_MAX_BYTES);
+        assert_eq!(page.content, "line 3\nline 4\n");
+        assert_eq!((page.start_line, page.end_line, page.eof), (3, 4, false));
+
+        let rest = read_page(&text, 9, Some(5), DEFAULT_MAX_BYTES);
+        assert_eq!(rest.content, "line 9\nline 10\n");
+        assert_eq!((rest.start_line, rest.end_line, rest.eof), (9, 10, true));
+
+        let past_end = read_page(&text, 20, None, DEFAULT_MAX_BYTES);
+        assert_eq!(past_end.content, "");
+        assert!(past_end.eof);
+    }
+
+    #[test]
+    fn read_page_tail_with_negative_offset() {
+        let text = numbered_lines(100);
+        let page = read_page(&text, -3, None, DEFAULT_MAX_BYTES);
+        assert_eq!(page.content, "line 98\nline 99\nline 100\n");
+        assert_eq!((page.start_line, page.end_line, page.eof), (98, 100, true));
+
+        let whole = read_page("a\nb\n", -10, None, DEFAULT_MAX_BYTES);
+        assert_eq!((whole.start_line, whole.end_line), (1, 2));
+    }
+
+    #[test]
+    fn read_page_stops_at_byte_cap() {
+        let text = numbered_lines(10);
+        // Each "line N\n" below 10 is 7 bytes: 20 bytes fit two whole lines.
+        let page = read_page(&text, 1, None, 20);
+        assert_eq!(page.content, "line 1\nline 2\n");
+        assert_eq!((page.end_line, page.eof), (2, false));
+
+        // A single line over the cap is cut rather than skipped.
+        let long = read_page("ééééé\n", 1, None, 5);
+        assert_eq!(long.content, "éé");
+        assert_eq!((long.end_line, long.eof), (1, false));
+    }
+
+    #[tokio::test]
+    async fn file_tool_tail_read_through_invoke() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("app.log");
+        std::fs::write(&file_path, numbered_lines(50)).unwrap();
+
+        let output = FileTool::new()
+            .invoke(serde_json::json!({
+                "action": "read",
+                "path": file_path.to_str().unwrap(),
+                "offset": -2
+            }))
+            .await
+            .unwrap();
+        let page: serde_json::Value = serde_json::from_str(&output.content).unwrap();
+        assert_eq!(page["content"], "line 49\nline 50\n");
+        assert_eq!(page["start_line"], 49);
+        assert_eq!(page["end_line"], 50);
+        assert_eq!(page["eof"], true);
+    }
+
+    #[tokio::test]
+    async fn file_tool_append_adds_to_existing_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("notes.txt");
+        let path_str = file_path.to_str().unwrap();
+        let tool = FileTool::new();
+
+        for (content, append) in [("first\n", false), ("second\n", true), ("third\n", true)] {
+            let output = tool
+                .invoke(serde_json::json!({
+                    "action": "write",
+                    "path": path_str,
+                    "content": content,
+                    "append": append
+                }))
+                .await
+                .unwrap();
+            assert!(!output.is_error);
+        }
+        assert_eq!(
+            std::fs::read_to_string(&file_path).unwrap(),
+            "first\nsecond\nthird\n"
+        );
+
+        // A plain write still replaces the file.
+        tool.invoke(serde_json::json!({"action": "write", "path": path_str, "content": "new"}))
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "new");
+    }
+
+    #[tokio::test]
+    async fn file_tool_append_creates_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("new.txt");
+        let output = FileTool::new()
+            .invoke(serde_json::json!({
+                "action": "write",
+                "path": file_path.to_str().unwrap(),
+                "content": "hi",
+                "append": true
+            }))
+            .await
+            .unwrap();
+        assert!(output.content.contains("appended 2 bytes"));
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "hi");
+    }
+
+    #[tokio::test]
+    async fn file_tool_rejects_paths_outside_allowed_root() {
+        let root = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret.txt"), "secret").unwrap();
+        let tool = FileTool::new().with_allowed_root(root.path());
+
+        let traversal = format!(
+            "{}/../{}/secret.txt",
+            root.path().display(),
+            outside.path().file_name().unwrap().to_str().unwrap()
+        );
+        for path in [
+            outside
+                .path()
+                .join("secret.txt")
+                .to_str()
+                .unwrap()
+                .to_string(),
+            traversal,
+        ] {
+            let output = tool
+                .invoke(serde_json::json!({"action": "read", "path": path}))
+                .await
+                .unwrap();
+            assert!(output.is_error, "{path} should be rejected");
+            assert!(output.content.contains("not within the allowed root"));
+        }
+
+        let write = tool
+            .invoke(serde_json::json!({
+                "action": "write",
+                "path": outside.path().join("new.txt").to_str().unwrap(),
+                "content": "x"
+            }))
+            .await
+            .unwrap();
+        assert!(write.is_error);
+        assert!(!outside.path().join("new.txt").exists());
+
+        std::fs::create_dir(root.path().join("sub")).unwrap();
+        let inside = root.path().join("sub").join("..").join("ok.txt");
+        let output = tool
+            .invoke(serde_json::json!({
+                "action": "write",
+                "path": inside.to_str().unwrap(),
+                "content": "fine"
+            }))
+            .await
+            .unwrap();
+        assert!(!output.is_error, "got: {}", output.content);
+        assert!(root.path().join("ok.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn file_tool_rejects_symlink_escape() {
+        let root = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret.txt"), "secret").unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.path().join("link")).unwrap();
+
+        let output = FileTool::new()
+            .with_allowed_root(root.path())
+            .invoke(serde_json::json!({
+                "action": "read",
+                "path": root.path().join("link/secret.txt").to_str().unwrap()
+            }))
+            .await
+            .unwrap();
+        assert!(output.is_error);
+
+        // `link/..` is the parent of the link target, not the root itself.
+        let output = FileTool::new()
+            .with_allowed_root(root.path())
+            .invoke(serde_json::json!({
+                "action": "write",
+                "path": root.path().join("link/../escaped.txt").to_str().unwrap(),
+                "content": "x"
+            }))
+            .await
+            .unwrap();
+        assert!(output.is_error);
     }
 
     #[tokio::test]
     async fn file_tool_unknown_action_returns_error() {
-        let tool = FileTool;
+        let tool = FileTool::new();
         let input = serde_json::json!({
             "action": "delete",
             "path": "/tmp/test"
@@ -163,7 +563,7 @@ mod tests {
 
     #[test]
     fn file_tool_parameters_schema_has_required_fields() {
-        let tool = FileTool;
+        let tool = FileTool::new();
         let schema = tool.parameters_schema();
         let required = schema["required"].as_array().unwrap();
         assert!(required.iter().any(|v| v == "action"));
@@ -0,0 +1,429 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Built-in text search tool.
+//!
+//! Searches files under a directory for a regex and returns `file:line:text`
+//! entries, for deployments where the bash tool is disabled. Binary files
+//! (containing a NUL byte in their first 8KB) are skipped, symlinks are not
+//! followed, and results stop at a configurable number of matches.
+
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use blufio_core::BlufioError;
+use regex::Regex;
+
+use crate::builtin::file::check_within_root;
+use crate::tool::{Tool, ToolOutput};
+
+/// Default maximum number of matches returned.
+const DEFAULT_MAX_MATCHES: usize = 200;
+
+/// Bytes inspected when deciding whether a file is binary.
+const BINARY_SNIFF_LEN: usize = 8 * 1024;
+
+/// Longest matched line returned, in bytes; longer lines are cut.
+const MAX_LINE_LEN: usize = 500;
+
+/// Searches files for lines matching a regex.
+#[derive(Debug, Clone)]
+pub struct GrepTool {
+    /// Directory searches must stay inside. `None` allows any path.
+    allowed_root: Option<PathBuf>,
+    /// Matches returned before the search stops.
+    max_matches: usize,
+}
+
+impl Default for GrepTool {
+    fn default() -> Self {
+        Self {
+            allowed_root: None,
+            max_matches: DEFAULT_MAX_MATCHES,
+        }
+    }
+}
+
+impl GrepTool {
+    /// Creates a grep tool with unrestricted paths and the default match cap.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts searches to paths inside `root`.
+    pub fn with_allowed_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.allowed_root = Some(root.into());
+        self
+    }
+
+    /// Sets the maximum number of matches returned.
+    pub fn with_max_matches(mut self, max_matches: usize) -> Self {
+        self.max_matches = max_matches;
+        self
+    }
+}
+
+/// Result of a search: `file:line:text` entries and whether the cap was hit.
+#[derive(Debug, Default)]
+struct Matches {
+    entries: Vec<String>,
+    capped: bool,
+}
+
+/// Searches `path` (a file or a directory, recursively) for `pattern`.
+///
+/// Entries are visited in name order so results are stable. Unreadable
+/// entries are skipped.
+fn search(path: &Path, pattern: &Regex, glob: Option<&Glob>, max: usize) -> Matches {
+    let mut matches = Matches::default();
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(meta) = std::fs::symlink_metadata(&current) else {
+            continue;
+        };
+        if meta.is_dir() {
+            let Ok(dir) = std::fs::read_dir(&current) else {
+                continue;
+            };
+            let mut children: Vec<PathBuf> = dir.flatten().map(|e| e.path()).collect();
+            children.sort();
+            // Reverse so the stack pops them in name order.
+            pending.extend(children.into_iter().rev());
+            continue;
+        }
+        if !meta.is_file() {
+            continue;
+        }
+        if let Some(glob) = glob
+            && !glob.matches(path, &current)
+        {
+            continue;
+        }
+        let Ok(bytes) = std::fs::read(&current) else {
+            continue;
+        };
+        if bytes[..bytes.len().min(BINARY_SNIFF_LEN)].contains(&0) {
+            continue;
+        }
+        let text = String::from_utf8_lossy(&bytes);
+        for (number, line) in text.lines().enumerate() {
+            if !pattern.is_match(line) {
+                continue;
+            }
+            if matches.entries.len() == max {
+                matches.capped = true;
+                return matches;
+            }
+            matches.entries.push(format!(
+                "{}:{}:{}",
+                current.display(),
+                number + 1,
+                truncate_line(line)
+            ));
+        }
+    }
+    matches
+}
+
+/// A compiled file glob (`*`, `**`, `?`).
+#[derive(Debug)]
+struct Glob {
+    regex: Regex,
+    /// Patterns with a `/` match the path relative to the search root,
+    /// others match the file name.
+    match_path: bool,
+}
+
+impl Glob {
+    fn new(glob: &str) -> Result<Self, regex::Error> {
+        let mut pattern = String::from("^");
+        let mut chars = glob.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    // `**/` also matches zero directories.
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        pattern.push_str("(?:.*/)?");
+                    } else {
+                        pattern.push_str(".*");
+                    }
+                }
+                '*' => pattern.push_str("[^/]*"),
+                '?' => pattern.push_str("[^/]"),
+                other => pattern.push_str(&regex::escape(&other.to_string())),
+            }
+        }
+        pattern.push('$');
+        Ok(Self {
+            regex: Regex::new(&pattern)?,
+            match_path: glob.contains('/'),
+        })
+    }
+
+    fn matches(&self, root: &Path, file: &Path) -> bool {
+        if self.match_path {
+            let relative = file.strip_prefix(root).unwrap_or(file);
+            self.regex.is_match(&relative.to_string_lossy())
+        } else {
+            file.file_name()
+                .is_some_and(|name| self.regex.is_match(&name.to_string_lossy()))
+        }
+    }
+}
+
+fn truncate_line(line: &str) -> &str {
+    if line.len() <= MAX_LINE_LEN {
+        return line;
+    }
+    let mut end = MAX_LINE_LEN;
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    &line[..end]
+}
+
+#[async_trait]
+impl Tool for GrepTool {
+    fn name(&self) -> &str {
+        "grep"
+    }
+
+    fn description(&self) -> &str {
+        "Search files under a directory for lines matching a regex. Returns one \
+         `file:line:text` entry per match; binary files are skipped."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "pattern": {
+                    "type": "string",
+                    "description": "Regular expression to search for (Rust regex syntax)"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "File or directory to search; directories are searched recursively"
+                },
+                "glob": {
+                    "type": "string",
+                    "description": "Only search files matching this glob, e.g. \"*.rs\" or \"src/**/*.toml\""
+                }
+            },
+            "required": ["pattern", "path"]
+        })
+    }
+
+    async fn invoke(&self, input: serde_json::Value) -> Result<ToolOutput, BlufioError> {
+        let pattern = input["pattern"].as_str().ok_or_else(|| {
+            BlufioError::skill_execution_msg("missing required 'pattern' parameter")
+        })?;
+        let path = input["path"]
+            .as_str()
+            .ok_or_else(|| BlufioError::skill_execution_msg("missing required 'path' parameter"))?;
+
+        if let Some(root) = &self.allowed_root
+            && let Err(reason) = check_within_root(root, path)
+        {
+            return Ok(ToolOutput {
+                content: reason,
+                is_error: true,
+            });
+        }
+
+        let pattern = match Regex::new(pattern) {
+            Ok(re) => re,
+            Err(e) => {
+                return Ok(ToolOutput {
+                    content: format!("Invalid pattern: {e}"),
+                    is_error: true,
+                });
+            }
+        };
+        let glob = match input["glob"].as_str().map(Glob::new).transpose() {
+            Ok(glob) => glob,
+            Err(e) => {
+                return Ok(ToolOutput {
+                    content: format!("Invalid glob: {e}"),
+                    is_error: true,
+                });
+            }
+        };
+
+        let root = PathBuf::from(path);
+        let max = self.max_matches;
+        let matches =
+            tokio::task::spawn_blocking(move || search(&root, &pattern, glob.as_ref(), max))
+                .await
+                .map_err(BlufioError::skill_execution_failed)?;
+
+        let content = if matches.entries.is_empty() {
+            "No matches found".to_string()
+        } else if matches.capped {
+            format!(
+                "{}\n[results truncated at {max} matches]",
+                matches.entries.join("\n")
+            )
+        } else {
+            matches.entries.join("\n")
+        };
+
+        Ok(ToolOutput {
+            content,
+            is_error: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds:
+    /// ```text
+    /// root/a.txt          "hello world\nbye\n"
+    /// root/src/lib.rs     "fn hello() {}\n"
+    /// root/src/blob.bin   "hello\0binary"
+    /// ```
+    fn tree() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "hello world\nbye\n").unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "fn hello() {}\n").unwrap();
+        std::fs::write(dir.path().join("src/blob.bin"), b"hello\0binary").unwrap();
+        dir
+    }
+
+    async fn grep(tool: &GrepTool, input: serde_json::Value) -> ToolOutput {
+        tool.invoke(input).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn finds_matches_recursively_and_skips_binary() {
+        let dir = tree();
+        let root = dir.path().display().to_string();
+        let output = grep(
+            &GrepTool::new(),
+            serde_json::json!({"pattern": "hel+o", "path": root}),
+        )
+        .await;
+        assert!(!output.is_error);
+        assert_eq!(
+            output.content,
+            format!("{root}/a.txt:1:hello world\n{root}/src/lib.rs:1:fn hello() {{}}")
+        );
+    }
+
+    #[tokio::test]
+    async fn glob_filters_files() {
+        let dir = tree();
+        let root = dir.path().display().to_string();
+        for glob in ["*.rs", "src/**/*.rs", "**/lib.rs"] {
+            let output = grep(
+                &GrepTool::new(),
+                serde_json::json!({"pattern": "hello", "path": root, "glob": glob}),
+            )
+            .await;
+            assert_eq!(
+                output.content,
+                format!("{root}/src/lib.rs:1:fn hello() {{}}"),
+                "glob {glob}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn results_are_capped() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("many.txt"), "x\n".repeat(10)).unwrap();
+        let output = grep(
+            &GrepTool::new().with_max_matches(3),
+            serde_json::json!({"pattern": "x", "path": dir.path().to_str().unwrap()}),
+        )
+        .await;
+        let lines: Vec<&str> = output.content.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[2].ends_with("many.txt:3:x"));
+        assert_eq!(lines[3], "[results truncated at 3 matches]");
+    }
+
+    #[tokio::test]
+    async fn no_match_and_invalid_pattern() {
+        let dir = tree();
+        let path = dir.path().to_str().unwrap();
+        let none = grep(
+            &GrepTool::new(),
+            serde_json::json!({"pattern": "absent", "path": path}),
+        )
+        .await;
+        assert_eq!(none.content, "No matches found");
+
+        let invalid = grep(
+            &GrepTool::new(),
+            serde_json::json!({"pattern": "(", "path": path}),
+        )
+        .await;
+        assert!(invalid.is_error);
+        assert!(invalid.content.starts_with("Invalid pattern"));
+    }
+
+    #[tokio::test]
+    async fn rejects_paths_outside_allowed_root() {
+        let dir = tree();
+        let tool = GrepTool::new().with_allowed_root(dir.path().join("src"));
+
+        let outside = grep(
+            &tool,
+            serde_json::json!({"pattern": "hello", "path": dir.path().to_str().unwrap()}),
+        )
+        .await;
+        assert!(outside.is_error);
+        assert!(outside.content.contains("not within the allowed root"));
+
+        let traversal = format!("{}/src/..", dir.path().display());
+        let escaped = grep(
+            &tool,
+            serde_json::json!({"pattern": "hello", "path": traversal}),
+        )
+        .await;
+        assert!(escaped.is_error);
+
+        let inside = grep(
+            &tool,
+            serde_json::json!({"pattern": "hello", "path": dir.path().join("src").to_str().unwrap()}),
+        )
+        .await;
+        assert!(!inside.is_error);
+        assert!(inside.content.ends_with("lib.rs:1:fn hello() {}"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn symlinks_are_not_followed() {
+        let root = tempfile::tempdir().unwrap();
+        let outside = tree();
+        std::os::unix::fs::symlink(outside.path(), root.path().join("link")).unwrap();
+        let output = grep(
+            &GrepTool::new().with_allowed_root(root.path()),
+            serde_json::json!({"pattern": "hello", "path": root.path().to_str().unwrap()}),
+        )
+        .await;
+        assert_eq!(output.content, "No matches found");
+    }
+
+    #[test]
+    fn glob_translation() {
+        let root = Path::new("/r");
+        let glob = Glob::new("*.rs").unwrap();
+        assert!(glob.matches(root, Path::new("/r/src/lib.rs")));
+        assert!(!glob.matches(root, Path::new("/r/lib.rsx")));
+        let glob = Glob::new("src/**/*.rs").unwrap();
+        assert!(glob.matches(root, Path::new("/r/src/lib.rs")));
+        assert!(glob.matches(root, Path::new("/r/src/a/b/lib.rs")));
+        assert!(!glob.matches(root, Path::new("/r/tests/lib.rs")));
+        let glob = Glob::new("file?.txt").unwrap();
+        assert!(glob.matches(root, Path::new("/r/file1.txt")));
+    }
+}
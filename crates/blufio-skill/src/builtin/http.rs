@@ -4,19 +4,24 @@
 //! Built-in HTTP request tool.
 //!
 //! Makes HTTP requests using reqwest with SSRF prevention from blufio-security.
-//! Response bodies are truncated to 50KB to prevent excessive token usage.
+//! The response is returned as JSON with the status, selected headers and the
+//! body, truncated to a byte cap (50KB by default) to limit token usage.
 
 use async_trait::async_trait;
 use blufio_core::BlufioError;
 
 use crate::tool::{Tool, ToolOutput};
 
-/// Maximum response body size in bytes (50KB).
-const MAX_RESPONSE_SIZE: usize = 50 * 1024;
+/// Default maximum response body size in bytes (50KB).
+const DEFAULT_MAX_RESPONSE_BYTES: usize = 50 * 1024;
+
+/// Response headers passed through to the LLM.
+const EXPOSED_HEADERS: &[&str] = &["content-type", "etag", "retry-after"];
 
 /// Makes HTTP requests and returns the response.
 pub struct HttpTool {
     client: reqwest::Client,
+    max_response_bytes: usize,
 }
 
 impl HttpTool {
@@ -24,7 +29,70 @@ impl HttpTool {
     pub fn new() -> Self {
         Self {
             client: reqwest::Client::new(),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+        }
+    }
+
+    /// Sets the response body byte cap.
+    pub fn with_max_response_bytes(mut self, max_response_bytes: usize) -> Self {
+        self.max_response_bytes = max_response_bytes;
+        self
+    }
+
+    /// Reads the response into the JSON tool output.
+    ///
+    /// At most `max_response_bytes` of the body are read; the cut falls on a
+    /// UTF-8 character boundary.
+    async fn render_response(
+        &self,
+        mut response: reqwest::Response,
+    ) -> Result<ToolOutput, BlufioError> {
+        let status = response.status();
+
+        let mut headers = serde_json::Map::new();
+        for name in EXPOSED_HEADERS {
+            if let Some(value) = response.headers().get(*name)
+                && let Ok(value) = value.to_str()
+            {
+                headers.insert(name.to_string(), value.into());
+            }
+        }
+
+        let mut body = Vec::new();
+        let mut truncated = false;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(BlufioError::skill_execution_failed)?
+        {
+            body.extend_from_slice(&chunk);
+            if body.len() > self.max_response_bytes {
+                truncated = true;
+                break;
+            }
         }
+        let body = if truncated {
+            let mut text = String::from_utf8_lossy(&body).into_owned();
+            let mut end = self.max_response_bytes.min(text.len());
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            text.truncate(end);
+            text
+        } else {
+            String::from_utf8_lossy(&body).into_owned()
+        };
+
+        let content = serde_json::json!({
+            "status": status.as_u16(),
+            "headers": headers,
+            "body": body,
+            "truncated": truncated,
+        })
+        .to_string();
+        let is_error = status.is_client_error() || status.is_server_error();
+
+        Ok(ToolOutput { content, is_error })
     }
 }
 
@@ -41,7 +109,9 @@ impl Tool for HttpTool {
     }
 
     fn description(&self) -> &str {
-        "Make an HTTP request and return the response"
+        "Make an HTTP request. Returns JSON: {\"status\": <code>, \"headers\": \
+         {content-type, etag, retry-after when present}, \"body\": <text>, \
+         \"truncated\": <true if the body was cut at the size limit>}"
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -123,28 +193,7 @@ impl Tool for HttpTool {
             .await
             .map_err(BlufioError::skill_execution_failed)?;
 
-        let status = response.status();
-        let body = response
-            .text()
-            .await
-            .map_err(BlufioError::skill_execution_failed)?;
-
-        // Truncate response body if too large.
-        let truncated = if body.len() > MAX_RESPONSE_SIZE {
-            format!(
-                "{}...\n\n[Response truncated from {} to {} bytes]",
-                &body[..MAX_RESPONSE_SIZE],
-                body.len(),
-                MAX_RESPONSE_SIZE
-            )
-        } else {
-            body
-        };
-
-        let content = format!("HTTP {status}\n\n{truncated}");
-        let is_error = status.is_client_error() || status.is_server_error();
-
-        Ok(ToolOutput { content, is_error })
+        self.render_response(response).await
     }
 }
 
@@ -185,6 +234,72 @@ mod tests {
         assert!(output.content.contains("not allowed"));
     }
 
+    /// Fetches `path` from `server` and renders it like `invoke` does.
+    ///
+    /// The mock server listens on a loopback address, which `invoke` rejects
+    /// through SSRF validation, so tests go straight to the response handling.
+    async fn fetch(tool: &HttpTool, server: &wiremock::MockServer, path: &str) -> ToolOutput {
+        let response = tool
+            .client
+            .get(format!("{}{path}", server.uri()))
+            .send()
+            .await
+            .unwrap();
+        tool.render_response(response).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn response_includes_status_and_whitelisted_headers() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/item"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("content-type", "application/json")
+                    .insert_header("etag", "\"v1\"")
+                    .insert_header("retry-after", "30")
+                    .insert_header("x-secret", "internal")
+                    .set_body_string("{\"error\":\"slow down\"}"),
+            )
+            .mount(&server)
+            .await;
+
+        let output = fetch(&HttpTool::new(), &server, "/item").await;
+        assert!(output.is_error);
+        let json: serde_json::Value = serde_json::from_str(&output.content).unwrap();
+        assert_eq!(json["status"], 429);
+        assert_eq!(json["headers"]["content-type"], "application/json");
+        assert_eq!(json["headers"]["etag"], "\"v1\"");
+        assert_eq!(json["headers"]["retry-after"], "30");
+        assert!(json["headers"].get("x-secret").is_none());
+        assert_eq!(json["body"], "{\"error\":\"slow down\"}");
+        assert_eq!(json["truncated"], false);
+    }
+
+    #[tokio::test]
+    async fn body_over_cap_is_truncated_and_flagged() {
+        use wiremock::matchers::path;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(path("/big"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("é".repeat(100)))
+            .mount(&server)
+            .await;
+
+        let tool = HttpTool::new().with_max_response_bytes(15);
+        let output = fetch(&tool, &server, "/big").await;
+        assert!(!output.is_error);
+        let json: serde_json::Value = serde_json::from_str(&output.content).unwrap();
+        assert_eq!(json["status"], 200);
+        assert_eq!(json["truncated"], true);
+        // 15 bytes cut back to the last whole two-byte character.
+        assert_eq!(json["body"], "é".repeat(7));
+    }
+
     #[tokio::test]
     async fn http_tool_ssrf_blocks_private_ip() {
         let tool = HttpTool::new();
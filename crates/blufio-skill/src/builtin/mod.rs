@@ -7,29 +7,75 @@
 
 pub mod bash;
 pub mod file;
+pub mod grep;
 pub mod http;
 
-pub use bash::BashTool;
+pub use bash::{BashPolicy, BashTool};
 pub use file::FileTool;
+pub use grep::GrepTool;
 pub use http::HttpTool;
 
 use crate::ToolRegistry;
+use blufio_config::model::SkillConfig;
 use std::sync::Arc;
+use tracing::warn;
+
+/// Creates a registry with the built-in tools configured by `config`,
+/// including the bash dry-run mode (`skill.bash_dry_run`).
+///
+/// `serve`, `shell` and `mcp-server` all build their registry through this,
+/// so every entry point honours the same settings.
+pub fn builtin_registry(config: &SkillConfig) -> ToolRegistry {
+    let mut registry = ToolRegistry::new();
+    registry.set_dry_run(config.bash_dry_run);
+    if config.bash_dry_run {
+        warn!("bash tool is in dry-run mode; commands will not be executed");
+    }
+    register_builtins_from_config(&mut registry, config);
+    registry
+}
 
 /// Registers all built-in tools into the given registry.
 ///
 /// Built-in tools are marked with [`ToolRegistry::register_builtin`] so they
-/// always win on collision with external MCP tools.
+/// always win on collision with external MCP tools. The registry's dry-run
+/// policy ([`ToolRegistry::set_dry_run`]) must be set before calling this.
 pub fn register_builtins(registry: &mut ToolRegistry) {
+    register_builtins_from_config(registry, &SkillConfig::default());
+}
+
+/// Registers all built-in tools, applying the `[skill.bash]` policy and the
+/// `[skill.http]` and `[skill.file]` limits from `config`. The grep tool is
+/// only registered when `[skill.grep] enabled` is set; it shares the file
+/// tool's allowed root.
+pub fn register_builtins_from_config(registry: &mut ToolRegistry, config: &SkillConfig) {
+    let bash = BashTool::new()
+        .with_dry_run(registry.is_dry_run())
+        .with_policy(BashPolicy::from(&config.bash));
+    let http = HttpTool::new().with_max_response_bytes(config.http.max_response_bytes);
+    let mut file = FileTool::new().with_max_bytes(config.file.max_bytes);
+    if let Some(root) = &config.file.allowed_root {
+        file = file.with_allowed_root(root);
+    }
     registry
-        .register_builtin(Arc::new(BashTool))
+        .register_builtin(Arc::new(bash))
         .expect("register built-in: bash");
     registry
-        .register_builtin(Arc::new(HttpTool::new()))
+        .register_builtin(Arc::new(http))
         .expect("register built-in: http");
     registry
-        .register_builtin(Arc::new(FileTool))
+        .register_builtin(Arc::new(file))
         .expect("register built-in: file");
+
+    if config.grep.enabled {
+        let mut grep = GrepTool::new().with_max_matches(config.grep.max_matches);
+        if let Some(root) = &config.file.allowed_root {
+            grep = grep.with_allowed_root(root);
+        }
+        registry
+            .register_builtin(Arc::new(grep))
+            .expect("register built-in: grep");
+    }
 }
 
 #[cfg(test)]
@@ -44,5 +90,58 @@ mod tests {
         assert!(registry.get("bash").is_some());
         assert!(registry.get("http").is_some());
         assert!(registry.get("file").is_some());
+        assert!(registry.get("grep").is_none());
+    }
+
+    #[test]
+    fn grep_is_registered_when_enabled() {
+        let mut config = SkillConfig::default();
+        config.grep.enabled = true;
+        let mut registry = ToolRegistry::new();
+        register_builtins_from_config(&mut registry, &config);
+        assert_eq!(registry.len(), 4);
+        assert!(registry.get("grep").is_some());
+    }
+
+    #[tokio::test]
+    async fn builtin_registry_applies_configured_dry_run() {
+        let config = SkillConfig {
+            bash_dry_run: true,
+            ..Default::default()
+        };
+        let registry = builtin_registry(&config);
+        assert!(registry.is_dry_run());
+        let output = registry
+            .get("bash")
+            .unwrap()
+            .invoke(serde_json::json!({"command": "exit 1"}))
+            .await
+            .unwrap();
+        assert_eq!(output.content, "[dry-run] would execute: exit 1");
+
+        assert!(!builtin_registry(&SkillConfig::default()).is_dry_run());
+    }
+
+    #[tokio::test]
+    async fn dry_run_registry_does_not_execute_bash() {
+        let marker = std::env::temp_dir().join(format!("blufio-dry-run-{}", std::process::id()));
+        let _ = std::fs::remove_file(&marker);
+        let command = format!("touch {}", marker.display());
+
+        let mut registry = ToolRegistry::new();
+        registry.set_dry_run(true);
+        register_builtins(&mut registry);
+        let bash = registry.get("bash").unwrap();
+        let output = bash
+            .invoke(serde_json::json!({"command": command}))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            output.content,
+            format!("[dry-run] would execute: {command}")
+        );
+        assert!(!output.is_error);
+        assert!(!marker.exists());
     }
 }
@@ -0,0 +1,429 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Interactive capability grants for WASM skills.
+//!
+//! When a skill traps because it lacks a capability, the sandbox reports the
+//! specific [`CapabilityRequest`] (a domain, a read path or a write path).
+//! With prompts enabled (`[skill] capability_prompts = true`),
+//! [`CapabilityGrantFlow`] asks the user through a [`CapabilityApprover`]
+//! ("Skill X wants network access to api.foo.com — allow?"). On approval
+//! the capability is added to the stored manifest, the skill is reloaded
+//! and the invocation retried.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use blufio_core::types::{
+    AdapterType, FilesystemCapability, HealthStatus, NetworkCapability, SkillCapabilities,
+    SkillInvocation, SkillManifest, SkillResult,
+};
+use blufio_core::{BlufioError, PluginAdapter, SkillRuntimeAdapter};
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::WasmSkillRuntime;
+use crate::store::SkillStore;
+
+/// Most capabilities granted while serving one invocation.
+const MAX_GRANTS_PER_INVOCATION: usize = 4;
+
+/// A capability a skill tried to use without having it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CapabilityRequest {
+    /// Network access to a domain.
+    Network { domain: String },
+    /// Read access to a path.
+    FilesystemRead { path: String },
+    /// Write access to a path.
+    FilesystemWrite { path: String },
+}
+
+impl CapabilityRequest {
+    /// Human-readable description, e.g. "network access to api.foo.com".
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Network { domain } => format!("network access to {domain}"),
+            Self::FilesystemRead { path } => format!("read access to {path}"),
+            Self::FilesystemWrite { path } => format!("write access to {path}"),
+        }
+    }
+
+    /// Adds the requested capability to `capabilities`.
+    pub fn grant(&self, capabilities: &mut SkillCapabilities) {
+        match self {
+            Self::Network { domain } => {
+                let network = capabilities
+                    .network
+                    .get_or_insert_with(|| NetworkCapability { domains: vec![] });
+                if !network.domains.contains(domain) {
+                    network.domains.push(domain.clone());
+                }
+            }
+            Self::FilesystemRead { path } | Self::FilesystemWrite { path } => {
+                let fs = capabilities
+                    .filesystem
+                    .get_or_insert_with(|| FilesystemCapability {
+                        read: vec![],
+                        write: vec![],
+                    });
+                let paths = if matches!(self, Self::FilesystemRead { .. }) {
+                    &mut fs.read
+                } else {
+                    &mut fs.write
+                };
+                if !paths.contains(path) {
+                    paths.push(path.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Host function error raised when a skill lacks a capability.
+///
+/// Displays as "capability not permitted: ..." like the sandbox's other
+/// denials, and carries the request so a grant can be offered.
+#[derive(Debug)]
+pub struct CapabilityDenied {
+    /// What the skill asked for.
+    pub request: CapabilityRequest,
+    message: String,
+}
+
+impl CapabilityDenied {
+    /// Creates a denial for `request` with the detail shown to the LLM.
+    pub fn new(request: CapabilityRequest, message: impl Into<String>) -> Self {
+        Self {
+            request,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for CapabilityDenied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "capability not permitted: {}", self.message)
+    }
+}
+
+impl std::error::Error for CapabilityDenied {}
+
+/// Asks the user whether a skill may have a capability.
+///
+/// Implemented on top of a channel: send the prompt, wait for a yes/no.
+#[async_trait]
+pub trait CapabilityApprover: Send + Sync {
+    /// Returns true if the user grants `request` to `skill_name`.
+    async fn approve(
+        &self,
+        skill_name: &str,
+        request: &CapabilityRequest,
+        prompt: &str,
+    ) -> Result<bool, BlufioError>;
+}
+
+/// The question put to the user for a capability request.
+pub fn capability_prompt(skill_name: &str, request: &CapabilityRequest) -> String {
+    format!(
+        "Skill {skill_name} wants {} \u{2014} allow?",
+        request.describe()
+    )
+}
+
+/// Invokes skills, offering the user to grant capabilities a skill is denied.
+pub struct CapabilityGrantFlow {
+    runtime: Arc<RwLock<WasmSkillRuntime>>,
+    approver: Arc<dyn CapabilityApprover>,
+    store: Option<Arc<SkillStore>>,
+    enabled: bool,
+}
+
+impl CapabilityGrantFlow {
+    /// Creates a flow over `runtime`. Prompts are off until enabled.
+    pub fn new(
+        runtime: Arc<RwLock<WasmSkillRuntime>>,
+        approver: Arc<dyn CapabilityApprover>,
+    ) -> Self {
+        Self {
+            runtime,
+            approver,
+            store: None,
+            enabled: false,
+        }
+    }
+
+    /// Persists granted capabilities to `store`.
+    pub fn with_store(mut self, store: Arc<SkillStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Turns capability prompts on or off (`[skill] capability_prompts`).
+    pub fn with_prompts_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Invokes a skill. A capability denial is turned into a prompt when
+    /// enabled; if the user approves, the capability is granted, the skill
+    /// reloaded and the invocation retried. Otherwise the denial is returned.
+    pub async fn invoke(&self, invocation: SkillInvocation) -> Result<SkillResult, BlufioError> {
+        let skill_name = invocation.skill_name.clone();
+        let mut grants = 0;
+        loop {
+            let (result, denied) = self
+                .runtime
+                .read()
+                .await
+                .invoke_reporting_denial(invocation.clone())
+                .await?;
+            let Some(request) = denied else {
+                return Ok(result);
+            };
+            if !self.enabled || grants == MAX_GRANTS_PER_INVOCATION {
+                return Ok(result);
+            }
+
+            let prompt = capability_prompt(&skill_name, &request);
+            if !self
+                .approver
+                .approve(&skill_name, &request, &prompt)
+                .await?
+            {
+                info!(skill = %skill_name, request = %request.describe(), "capability declined");
+                return Ok(result);
+            }
+
+            self.grant(&skill_name, &request).await?;
+            grants += 1;
+        }
+    }
+
+    /// Records `request` in the stored manifest and reloads the skill.
+    async fn grant(
+        &self,
+        skill_name: &str,
+        request: &CapabilityRequest,
+    ) -> Result<(), BlufioError> {
+        let capabilities = self
+            .runtime
+            .write()
+            .await
+            .reload_with_grant(skill_name, request)?;
+
+        if let Some(store) = &self.store {
+            let json = serde_json::to_string(&capabilities)
+                .map_err(BlufioError::skill_execution_failed)?;
+            store.update_capabilities(skill_name, &json).await?;
+        }
+        info!(skill = %skill_name, request = %request.describe(), "capability granted");
+        Ok(())
+    }
+}
+
+/// Registered in place of the WASM runtime when capability prompts are on.
+#[async_trait]
+impl PluginAdapter for CapabilityGrantFlow {
+    fn name(&self) -> &str {
+        "wasm"
+    }
+
+    fn version(&self) -> semver::Version {
+        semver::Version::new(0, 1, 0)
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::SkillRuntime
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus, BlufioError> {
+        Ok(HealthStatus::Healthy)
+    }
+
+    async fn shutdown(&self) -> Result<(), BlufioError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SkillRuntimeAdapter for CapabilityGrantFlow {
+    async fn invoke(&self, invocation: SkillInvocation) -> Result<SkillResult, BlufioError> {
+        CapabilityGrantFlow::invoke(self, invocation).await
+    }
+
+    /// Skills loaded in the runtime. Empty for the moment a grant holds the
+    /// runtime to reload a skill.
+    fn list_skills(&self) -> Vec<SkillManifest> {
+        self.runtime
+            .try_read()
+            .map(|runtime| runtime.list_skills())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_manifest;
+    use std::sync::Mutex;
+
+    /// Approver with a fixed answer that records the prompts it saw.
+    struct MockApprover {
+        answer: bool,
+        prompts: Mutex<Vec<String>>,
+    }
+
+    impl MockApprover {
+        fn new(answer: bool) -> Arc<Self> {
+            Arc::new(Self {
+                answer,
+                prompts: Mutex::new(vec![]),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl CapabilityApprover for MockApprover {
+        async fn approve(
+            &self,
+            _skill_name: &str,
+            _request: &CapabilityRequest,
+            prompt: &str,
+        ) -> Result<bool, BlufioError> {
+            self.prompts.lock().unwrap().push(prompt.to_string());
+            Ok(self.answer)
+        }
+    }
+
+    fn manifest() -> SkillManifest {
+        SkillManifest {
+            name: "reader".to_string(),
+            description: "reads a file".to_string(),
+            ..test_manifest()
+        }
+    }
+
+    /// A skill that calls `read_file` on `path` and traps if denied.
+    fn reader_runtime(path: &str) -> Arc<RwLock<WasmSkillRuntime>> {
+        let wat = format!(
+            r#"(module
+                (import "blufio" "read_file" (func $read (param i32 i32 i32 i32) (result i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{path}")
+                (func (export "run")
+                    (drop (call $read (i32.const 0) (i32.const {len}) (i32.const 0) (i32.const 0)))))"#,
+            len = path.len()
+        );
+        let wasm = wat::parse_str(&wat).unwrap();
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        runtime.load_skill(manifest(), &wasm, None).unwrap();
+        Arc::new(RwLock::new(runtime))
+    }
+
+    fn invocation() -> SkillInvocation {
+        SkillInvocation {
+            skill_name: "reader".to_string(),
+            input: serde_json::json!({}),
+            session_id: None,
+        }
+    }
+
+    fn temp_file() -> (tempfile::TempDir, String) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.txt");
+        std::fs::write(&path, "contents").unwrap();
+        let path = path.to_str().unwrap().to_string();
+        (dir, path)
+    }
+
+    #[tokio::test]
+    async fn approval_grants_reloads_and_retries() {
+        let (_dir, path) = temp_file();
+        let runtime = reader_runtime(&path);
+        let approver = MockApprover::new(true);
+        let flow =
+            CapabilityGrantFlow::new(runtime.clone(), approver.clone()).with_prompts_enabled(true);
+
+        let result = flow.invoke(invocation()).await.unwrap();
+        assert!(!result.is_error, "got: {}", result.content);
+        assert_eq!(result.content, "contents");
+        assert_eq!(
+            *approver.prompts.lock().unwrap(),
+            vec![format!(
+                "Skill reader wants read access to {path} \u{2014} allow?"
+            )]
+        );
+
+        let skills = runtime.read().await.list_skills();
+        let fs = skills[0].capabilities.filesystem.as_ref().unwrap();
+        assert_eq!(fs.read, vec![path]);
+    }
+
+    #[tokio::test]
+    async fn declined_request_returns_denial() {
+        let (_dir, path) = temp_file();
+        let runtime = reader_runtime(&path);
+        let flow = CapabilityGrantFlow::new(runtime.clone(), MockApprover::new(false))
+            .with_prompts_enabled(true);
+
+        let result = flow.invoke(invocation()).await.unwrap();
+        assert!(result.is_error);
+        assert!(result.content.contains("capability not permitted"));
+        assert!(
+            runtime.read().await.list_skills()[0]
+                .capabilities
+                .filesystem
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn disabled_flow_never_prompts() {
+        let (_dir, path) = temp_file();
+        let approver = MockApprover::new(true);
+        let flow = CapabilityGrantFlow::new(reader_runtime(&path), approver.clone());
+
+        let result = flow.invoke(invocation()).await.unwrap();
+        assert!(result.is_error);
+        assert!(approver.prompts.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn flow_serves_as_the_wasm_runtime() {
+        let (_dir, path) = temp_file();
+        let approver = MockApprover::new(true);
+        let flow = CapabilityGrantFlow::new(reader_runtime(&path), approver.clone())
+            .with_prompts_enabled(true);
+        let mut runtimes = crate::SkillRuntimes::new();
+        runtimes.register("wasm", Arc::new(flow));
+        assert_eq!(runtimes.list_skills().len(), 1);
+
+        let result = runtimes.invoke(invocation()).await.unwrap();
+        assert_eq!(result.content, "contents");
+        assert_eq!(approver.prompts.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn grant_adds_capabilities_once() {
+        let mut caps = SkillCapabilities::default();
+        let request = CapabilityRequest::Network {
+            domain: "api.foo.com".to_string(),
+        };
+        request.grant(&mut caps);
+        request.grant(&mut caps);
+        CapabilityRequest::FilesystemWrite {
+            path: "/tmp/out".to_string(),
+        }
+        .grant(&mut caps);
+
+        assert_eq!(caps.network.unwrap().domains, vec!["api.foo.com"]);
+        let fs = caps.filesystem.unwrap();
+        assert!(fs.read.is_empty());
+        assert_eq!(fs.write, vec!["/tmp/out"]);
+        assert_eq!(
+            capability_prompt("weather", &request),
+            "Skill weather wants network access to api.foo.com \u{2014} allow?"
+        );
+    }
+}
@@ -0,0 +1,215 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! WebAssembly Component Model skills.
+//!
+//! Besides core modules with the hand-rolled `blufio` ABI, the sandbox runs
+//! components targeting the typed `skill` world in `wit/skill.wit`: the host
+//! provides the `blufio:skill/host` interface and the component exports
+//! `run: func(input: string) -> result<string, string>`. Strings cross the
+//! boundary through the canonical ABI, so skills no longer marshal memory.
+//!
+//! Capability gating is the same as for core modules: each host import checks
+//! the manifest and traps with a [`CapabilityDenied`] error when the skill has
+//! not declared the capability it needs.
+
+use anyhow::anyhow;
+use blufio_core::BlufioError;
+use blufio_core::types::SkillManifest;
+use tracing::{debug, info, warn};
+use wasmtime::Store;
+use wasmtime::component::{Component, Linker};
+
+use crate::capability::{CapabilityDenied, CapabilityRequest};
+use crate::sandbox::SkillState;
+
+/// Name of the host interface instance components import.
+pub const HOST_INTERFACE: &str = "blufio:skill/host@0.1.0";
+
+/// Name of the function components export.
+pub const RUN_EXPORT: &str = "run";
+
+/// Returns true if `bytes` is a WebAssembly component rather than a core
+/// module (the binary header's layer field is 1 for components).
+pub fn is_component(bytes: &[u8]) -> bool {
+    bytes.len() >= 8 && bytes[..4] == *b"\0asm" && bytes[6..8] == [1, 0]
+}
+
+/// Defines the `blufio:skill/host` interface, gated by `manifest`.
+///
+/// `http-get` goes through `http_client`, the sandbox's SSRF-safe client that
+/// also serves core modules' `http_request`.
+pub(crate) fn define_host_interface(
+    linker: &mut Linker<SkillState>,
+    manifest: &SkillManifest,
+    http_client: &reqwest::Client,
+) -> Result<(), BlufioError> {
+    let mut host = linker.instance(HOST_INTERFACE).map_err(linker_err)?;
+
+    host.func_wrap(
+        "log",
+        |mut store: wasmtime::StoreContextMut<'_, SkillState>, (level, message): (u32, String)| {
+            let level = match level {
+                0 => "TRACE",
+                1 => "DEBUG",
+                3 => "WARN",
+                4 => "ERROR",
+                _ => "INFO",
+            };
+            debug!(skill_log = %message, level, "skill log");
+            store.data_mut().output.push(format!("[{level}] {message}"));
+            Ok(())
+        },
+    )
+    .map_err(linker_err)?;
+
+    let allowed_domains: Option<Vec<String>> = manifest
+        .capabilities
+        .network
+        .as_ref()
+        .map(|n| n.domains.clone());
+    let http_client = http_client.clone();
+    host.func_wrap(
+        "http-get",
+        move |_store: wasmtime::StoreContextMut<'_, SkillState>,
+              (url,): (String,)|
+              -> wasmtime::Result<(Result<String, String>,)> {
+            let parsed =
+                reqwest::Url::parse(&url).map_err(|e| anyhow!("invalid URL '{url}': {e}"))?;
+            let domain = parsed
+                .host_str()
+                .ok_or_else(|| anyhow!("URL has no host: {url}"))?
+                .to_string();
+            let permitted = allowed_domains.as_ref().is_some_and(|domains| {
+                domains
+                    .iter()
+                    .any(|d| domain == *d || domain.ends_with(&format!(".{d}")))
+            });
+            if !permitted {
+                warn!(domain = %domain, "component attempted http-get without network capability");
+                let message = format!("domain '{domain}' not in allowed network domains");
+                return Err(denied(CapabilityRequest::Network { domain }, message));
+            }
+            if let Err(e) = blufio_security::ssrf::validate_url_host(&url) {
+                return Ok((Err(format!("SSRF blocked: {e}")),));
+            }
+
+            let handle = tokio::runtime::Handle::current();
+            let response = handle.block_on(async {
+                let resp = http_client.get(&url).send().await?;
+                resp.text().await
+            });
+            info!(url = %url, ok = response.is_ok(), "component http-get completed");
+            // Keep the source chain, which says why a host was refused.
+            Ok((response.map_err(|e| format!("{:#}", anyhow::Error::new(e))),))
+        },
+    )
+    .map_err(linker_err)?;
+
+    let read_paths = manifest
+        .capabilities
+        .filesystem
+        .as_ref()
+        .map(|f| f.read.clone())
+        .unwrap_or_default();
+    host.func_wrap(
+        "read-file",
+        move |_store: wasmtime::StoreContextMut<'_, SkillState>,
+              (path,): (String,)|
+              -> wasmtime::Result<(Result<String, String>,)> {
+            if !read_paths.iter().any(|allowed| path.starts_with(allowed)) {
+                let message = format!("path '{path}' not within allowed read paths {read_paths:?}");
+                return Err(denied(CapabilityRequest::FilesystemRead { path }, message));
+            }
+            Ok((std::fs::read_to_string(&path).map_err(|e| e.to_string()),))
+        },
+    )
+    .map_err(linker_err)?;
+
+    let write_paths = manifest
+        .capabilities
+        .filesystem
+        .as_ref()
+        .map(|f| f.write.clone())
+        .unwrap_or_default();
+    host.func_wrap(
+        "write-file",
+        move |_store: wasmtime::StoreContextMut<'_, SkillState>,
+              (path, contents): (String, String)|
+              -> wasmtime::Result<(Result<(), String>,)> {
+            if !write_paths.iter().any(|allowed| path.starts_with(allowed)) {
+                let message =
+                    format!("path '{path}' not within allowed write paths {write_paths:?}");
+                return Err(denied(CapabilityRequest::FilesystemWrite { path }, message));
+            }
+            Ok((std::fs::write(&path, contents).map_err(|e| e.to_string()),))
+        },
+    )
+    .map_err(linker_err)?;
+
+    let allowed_env = manifest.capabilities.env.clone();
+    host.func_wrap(
+        "get-env",
+        move |_store: wasmtime::StoreContextMut<'_, SkillState>,
+              (key,): (String,)|
+              -> wasmtime::Result<(Option<String>,)> {
+            if !allowed_env.contains(&key) {
+                warn!(key = %key, "component attempted get-env for non-permitted key");
+                return Ok((None,));
+            }
+            Ok((std::env::var(&key).ok(),))
+        },
+    )
+    .map_err(linker_err)?;
+
+    Ok(())
+}
+
+/// Instantiates `component` and calls its `run` export with the store's
+/// input JSON. The returned text becomes the skill result; an `Err` from the
+/// component is reported as a skill error.
+pub(crate) fn run_component(
+    store: &mut Store<SkillState>,
+    linker: &Linker<SkillState>,
+    component: &Component,
+) -> anyhow::Result<()> {
+    let instance = linker.instantiate(&mut *store, component)?;
+    let run = instance
+        .get_typed_func::<(String,), (Result<String, String>,)>(&mut *store, RUN_EXPORT)
+        .map_err(|e| {
+            anyhow!(
+                "component has no 'run: func(input: string) -> result<string, string>' export: {e}"
+            )
+        })?;
+    let input = store.data().input_json.clone();
+    let (output,) = run.call(&mut *store, (input,))?;
+    match output {
+        Ok(text) => {
+            store.data_mut().result_json = Some(text);
+            Ok(())
+        }
+        Err(message) => Err(anyhow!("skill returned an error: {message}")),
+    }
+}
+
+fn denied(request: CapabilityRequest, message: String) -> anyhow::Error {
+    anyhow::Error::new(CapabilityDenied::new(request, message))
+}
+
+fn linker_err(e: anyhow::Error) -> BlufioError {
+    BlufioError::skill_compilation_msg(&format!("failed to define component host function: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_components_by_header() {
+        let module = wat::parse_str("(module)").unwrap();
+        let component = wat::parse_str("(component)").unwrap();
+        assert!(!is_component(&module));
+        assert!(is_component(&component));
+        assert!(!is_component(b"\0as"));
+    }
+}
@@ -12,23 +12,36 @@
 //! - [`builtin::BashTool`] -- Execute shell commands
 //! - [`builtin::HttpTool`] -- Make HTTP requests
 //! - [`builtin::FileTool`] -- Read and write files
+//! - [`builtin::GrepTool`] -- Search files for a regex (opt-in)
 
 pub mod builtin;
+pub mod capability;
+pub mod component;
 pub mod manifest;
 pub mod provider;
+pub mod runtime;
 pub mod sandbox;
 pub mod scaffold;
 pub mod signing;
+pub mod skill_tool;
 pub mod store;
 pub mod tool;
+pub mod wasi;
 
-pub use manifest::{load_manifest, parse_manifest};
+#[cfg(test)]
+mod test_support;
+
+pub use capability::{CapabilityApprover, CapabilityGrantFlow, CapabilityRequest};
+pub use manifest::{ManifestValidation, load_manifest, parse_manifest, validate_manifest};
 pub use provider::SkillProvider;
-pub use sandbox::WasmSkillRuntime;
+pub use runtime::SkillRuntimes;
+pub use sandbox::{SkillCancelHandle, WasmSkillRuntime};
 pub use scaffold::scaffold_skill;
 pub use signing::{
-    PublisherKeypair, compute_content_hash, load_private_key_from_file, load_public_key_from_file,
-    save_keypair_to_file, signature_from_hex, signature_to_hex,
+    PublisherKeypair, VerificationStatus, compute_content_hash, load_private_key_from_file,
+    load_public_key_from_file, save_keypair_to_file, signature_from_hex, signature_to_hex,
+    verify_skill_signature, verifying_key_from_hex,
 };
+pub use skill_tool::{WasmSkillTool, load_installed_skills, register_skill_tools};
 pub use store::{SkillStore, VerificationInfo};
 pub use tool::{Tool, ToolOutput, ToolRegistry};
@@ -12,6 +12,7 @@ use std::path::Path;
 use blufio_core::BlufioError;
 use blufio_core::types::{
     FilesystemCapability, NetworkCapability, SkillCapabilities, SkillManifest, SkillResources,
+    SkillSignature,
 };
 use serde::Deserialize;
 
@@ -27,6 +28,12 @@ struct ManifestFile {
     resources: ResourcesSection,
     #[serde(default)]
     wasm: WasmSection,
+    #[serde(default)]
+    input: InputSection,
+    #[serde(default)]
+    output: OutputSection,
+    #[serde(default)]
+    signature: Option<SignatureSection>,
 }
 
 /// The [skill] section of the manifest.
@@ -37,6 +44,8 @@ struct SkillSection {
     description: String,
     #[serde(default)]
     author: Option<String>,
+    #[serde(default = "default_runtime")]
+    runtime: String,
 }
 
 /// The [capabilities] section of the manifest.
@@ -77,17 +86,43 @@ struct ResourcesSection {
     epoch_timeout_secs: Option<u64>,
 }
 
+/// The [input] section.
+#[derive(Debug, Default, Deserialize)]
+struct InputSection {
+    /// JSON Schema for the skill's input, written as a TOML table.
+    #[serde(default)]
+    schema: Option<serde_json::Value>,
+}
+
+/// The [output] section.
+#[derive(Debug, Default, Deserialize)]
+struct OutputSection {
+    /// JSON Schema for the skill's output, written as a TOML table.
+    #[serde(default)]
+    schema: Option<serde_json::Value>,
+}
+
+/// The [signature] section: an Ed25519 signature over the WASM bytes.
+#[derive(Debug, Deserialize)]
+struct SignatureSection {
+    publisher_id: String,
+    signature: String,
+}
+
 /// The [wasm] section.
 #[derive(Debug, Deserialize)]
 struct WasmSection {
     #[serde(default = "default_entry")]
     entry: String,
+    #[serde(default)]
+    wasi: bool,
 }
 
 impl Default for WasmSection {
     fn default() -> Self {
         Self {
             entry: default_entry(),
+            wasi: false,
         }
     }
 }
@@ -96,6 +131,10 @@ fn default_entry() -> String {
     "skill.wasm".to_string()
 }
 
+fn default_runtime() -> String {
+    "wasm".to_string()
+}
+
 // --- Public API ---
 
 /// Parses a skill manifest from a TOML string.
@@ -153,6 +192,14 @@ pub fn parse_manifest(toml_content: &str) -> Result<SkillManifest, BlufioError>
         capabilities,
         resources,
         wasm_entry: manifest_file.wasm.entry,
+        runtime: manifest_file.skill.runtime,
+        input_schema: manifest_file.input.schema,
+        output_schema: manifest_file.output.schema,
+        signature: manifest_file.signature.map(|s| SkillSignature {
+            publisher_id: s.publisher_id,
+            signature: s.signature,
+        }),
+        wasi: manifest_file.wasm.wasi,
     })
 }
 
@@ -162,6 +209,164 @@ pub fn load_manifest(path: &Path) -> Result<SkillManifest, BlufioError> {
     parse_manifest(&content)
 }
 
+/// Keys each manifest table accepts, by dotted table path. `input.schema` and
+/// `output.schema` hold free-form JSON Schema and are not checked.
+const KNOWN_KEYS: &[(&str, &[&str])] = &[
+    (
+        "",
+        &[
+            "skill",
+            "capabilities",
+            "resources",
+            "wasm",
+            "input",
+            "output",
+            "signature",
+        ],
+    ),
+    (
+        "skill",
+        &["name", "version", "description", "author", "runtime"],
+    ),
+    ("capabilities", &["network", "filesystem", "env"]),
+    ("capabilities.network", &["domains"]),
+    ("capabilities.filesystem", &["read", "write"]),
+    ("resources", &["fuel", "memory_mb", "epoch_timeout_secs"]),
+    ("wasm", &["entry", "wasi"]),
+    ("input", &["schema"]),
+    ("output", &["schema"]),
+    ("signature", &["publisher_id", "signature"]),
+];
+
+/// Result of [`validate_manifest`].
+#[derive(Debug)]
+pub struct ManifestValidation {
+    /// The parsed manifest, if it parsed at all.
+    pub manifest: Option<SkillManifest>,
+    /// Problems that make the manifest unusable.
+    pub errors: Vec<String>,
+    /// Suspicious but accepted content, such as unknown keys.
+    pub warnings: Vec<String>,
+}
+
+impl ManifestValidation {
+    /// Returns true when there are no errors.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Checks a skill manifest more strictly than [`parse_manifest`].
+///
+/// On top of parsing, requires a non-empty semver `version` and
+/// `description`, syntactically valid hostnames in
+/// `[capabilities.network] domains`, and absolute filesystem paths. Keys the
+/// manifest format does not know are reported as warnings, since serde
+/// otherwise ignores them and a typo silently drops a capability.
+pub fn validate_manifest(toml_content: &str) -> ManifestValidation {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    match toml::from_str::<toml::Table>(toml_content) {
+        Ok(table) => collect_unknown_keys(&table, "", &mut warnings),
+        Err(e) => {
+            errors.push(format!("invalid TOML: {e}"));
+            return ManifestValidation {
+                manifest: None,
+                errors,
+                warnings,
+            };
+        }
+    }
+
+    let manifest = match parse_manifest(toml_content) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            errors.push(e.to_string());
+            return ManifestValidation {
+                manifest: None,
+                errors,
+                warnings,
+            };
+        }
+    };
+
+    if semver::Version::parse(&manifest.version).is_err() {
+        errors.push(format!(
+            "skill.version '{}' is not a semantic version (e.g. 0.1.0)",
+            manifest.version
+        ));
+    }
+    if manifest.description.trim().is_empty() {
+        errors.push("skill.description must not be empty".to_string());
+    }
+    if let Some(ref network) = manifest.capabilities.network {
+        for domain in &network.domains {
+            if !is_valid_hostname(domain) {
+                errors.push(format!(
+                    "capabilities.network.domains: '{domain}' is not a valid hostname"
+                ));
+            }
+        }
+    }
+    if let Some(ref fs) = manifest.capabilities.filesystem {
+        for (field, paths) in [("read", &fs.read), ("write", &fs.write)] {
+            for path in paths {
+                if !Path::new(path).is_absolute() {
+                    errors.push(format!(
+                        "capabilities.filesystem.{field}: '{path}' must be an absolute path"
+                    ));
+                }
+            }
+        }
+    }
+
+    ManifestValidation {
+        manifest: Some(manifest),
+        errors,
+        warnings,
+    }
+}
+
+/// Appends a warning for every key in `table` (at dotted path `prefix`) that
+/// the manifest format does not define.
+fn collect_unknown_keys(table: &toml::Table, prefix: &str, warnings: &mut Vec<String>) {
+    let Some((_, known)) = KNOWN_KEYS.iter().find(|(path, _)| *path == prefix) else {
+        return;
+    };
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        if !known.contains(&key.as_str()) {
+            warnings.push(format!("unknown key '{path}' is ignored"));
+        } else if let toml::Value::Table(inner) = value {
+            collect_unknown_keys(inner, &path, warnings);
+        }
+    }
+}
+
+/// Whether `s` is an IP address or a DNS hostname: dot-separated labels of
+/// 1-63 letters, digits, and inner hyphens, 253 characters at most.
+fn is_valid_hostname(s: &str) -> bool {
+    if s.parse::<std::net::IpAddr>().is_ok() {
+        return true;
+    }
+    !s.is_empty()
+        && s.len() <= 253
+        && s.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,6 +397,7 @@ epoch_timeout_secs = 3
 
 [wasm]
 entry = "weather.wasm"
+wasi = true
 "#;
         let manifest = parse_manifest(toml).unwrap();
         assert_eq!(manifest.name, "weather-lookup");
@@ -199,6 +405,7 @@ entry = "weather.wasm"
         assert_eq!(manifest.description, "Looks up current weather for a city");
         assert_eq!(manifest.author.as_deref(), Some("Test Author"));
         assert_eq!(manifest.wasm_entry, "weather.wasm");
+        assert!(manifest.wasi);
 
         // Capabilities
         let network = manifest.capabilities.network.as_ref().unwrap();
@@ -231,6 +438,81 @@ description = "A minimal skill"
         assert!(manifest.capabilities.filesystem.is_none());
         assert!(manifest.capabilities.env.is_empty());
         assert_eq!(manifest.wasm_entry, "skill.wasm");
+        assert_eq!(manifest.runtime, "wasm");
+        assert!(manifest.input_schema.is_none());
+        assert!(manifest.output_schema.is_none());
+        assert!(manifest.signature.is_none());
+        assert!(!manifest.wasi);
+    }
+
+    #[test]
+    fn parse_manifest_signature() {
+        let toml = r#"
+[skill]
+name = "signed"
+version = "1.0.0"
+description = "A signed skill"
+
+[signature]
+publisher_id = "abcd"
+signature = "ef01"
+"#;
+        let manifest = parse_manifest(toml).unwrap();
+        let sig = manifest.signature.unwrap();
+        assert_eq!(sig.publisher_id, "abcd");
+        assert_eq!(sig.signature, "ef01");
+    }
+
+    #[test]
+    fn parse_manifest_output_schema() {
+        let toml = r#"
+[skill]
+name = "weather"
+version = "0.1.0"
+description = "Weather lookup"
+
+[output.schema]
+type = "object"
+required = ["temp_c"]
+
+[output.schema.properties.temp_c]
+type = "number"
+"#;
+        let manifest = parse_manifest(toml).unwrap();
+        assert_eq!(
+            manifest.output_schema,
+            Some(serde_json::json!({
+                "type": "object",
+                "required": ["temp_c"],
+                "properties": {"temp_c": {"type": "number"}}
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_manifest_input_schema() {
+        let toml = r#"
+[skill]
+name = "weather"
+version = "0.1.0"
+description = "Weather lookup"
+
+[input.schema]
+type = "object"
+required = ["city"]
+
+[input.schema.properties.city]
+type = "string"
+"#;
+        let manifest = parse_manifest(toml).unwrap();
+        assert_eq!(
+            manifest.input_schema,
+            Some(serde_json::json!({
+                "type": "object",
+                "required": ["city"],
+                "properties": {"city": {"type": "string"}}
+            }))
+        );
     }
 
     #[test]
@@ -343,4 +625,101 @@ description = "No permissions needed"
         assert!(manifest.capabilities.filesystem.is_none());
         assert!(manifest.capabilities.env.is_empty());
     }
+
+    #[test]
+    fn validate_manifest_accepts_valid_manifest() {
+        let toml = r#"
+[skill]
+name = "weather"
+version = "0.1.0"
+description = "Weather lookup"
+
+[capabilities]
+env = ["WEATHER_API_KEY"]
+
+[capabilities.network]
+domains = ["api.weather.com", "127.0.0.1"]
+
+[capabilities.filesystem]
+read = ["/tmp/cache"]
+
+[output.schema]
+type = "object"
+"#;
+        let report = validate_manifest(toml);
+        assert!(report.is_valid(), "{:?}", report.errors);
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+        assert_eq!(report.manifest.unwrap().name, "weather");
+    }
+
+    #[test]
+    fn validate_manifest_reports_missing_required_field() {
+        let report = validate_manifest("[skill]\nname = \"x\"\nversion = \"0.1.0\"\n");
+        assert!(!report.is_valid());
+        assert!(report.manifest.is_none());
+        assert!(
+            report.errors[0].contains("description"),
+            "{:?}",
+            report.errors
+        );
+    }
+
+    #[test]
+    fn validate_manifest_rejects_bad_domains_and_relative_paths() {
+        let toml = r#"
+[skill]
+name = "bad"
+version = "latest"
+description = "Bad capabilities"
+
+[capabilities.network]
+domains = ["https://api.example.com", "-bad.example.com", "ok.example.com"]
+
+[capabilities.filesystem]
+read = ["data/cache"]
+write = ["/tmp/out"]
+"#;
+        let report = validate_manifest(toml);
+        assert_eq!(report.errors.len(), 4, "{:?}", report.errors);
+        assert!(report.errors[0].contains("not a semantic version"));
+        assert!(report.errors[1].contains("https://api.example.com"));
+        assert!(report.errors[2].contains("-bad.example.com"));
+        assert!(report.errors[3].contains("filesystem.read: 'data/cache'"));
+    }
+
+    #[test]
+    fn validate_manifest_warns_on_unknown_keys() {
+        let toml = r#"
+[skill]
+name = "typo"
+version = "0.1.0"
+description = "Typo in capabilities"
+licence = "MIT"
+
+[capabilities.netwrok]
+domains = ["api.example.com"]
+
+[resources]
+memory = 8
+"#;
+        let report = validate_manifest(toml);
+        assert!(report.is_valid(), "{:?}", report.errors);
+        let mut warnings = report.warnings;
+        warnings.sort();
+        assert_eq!(
+            warnings,
+            vec![
+                "unknown key 'capabilities.netwrok' is ignored",
+                "unknown key 'resources.memory' is ignored",
+                "unknown key 'skill.licence' is ignored",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_manifest_rejects_invalid_toml() {
+        let report = validate_manifest("[skill\nname = ");
+        assert!(!report.is_valid());
+        assert!(report.errors[0].starts_with("invalid TOML"));
+    }
 }
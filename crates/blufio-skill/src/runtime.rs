@@ -0,0 +1,202 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Registry of skill runtimes keyed by skill type.
+//!
+//! Each skill manifest names the runtime that executes it (`runtime = "wasm"`
+//! by default). [`SkillRuntimes`] holds one [`SkillRuntimeAdapter`] per skill
+//! type and routes invocations to the runtime that has the skill loaded,
+//! found through an index of skill names built when the runtime is
+//! registered.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use blufio_core::types::{SkillInvocation, SkillManifest, SkillResult};
+use blufio_core::{BlufioError, SkillRuntimeAdapter};
+
+/// Skill runtimes available to the agent, keyed by skill type.
+#[derive(Default)]
+pub struct SkillRuntimes {
+    runtimes: HashMap<String, Arc<dyn SkillRuntimeAdapter + Send + Sync>>,
+    /// Runtime of each loaded skill, keyed by skill name.
+    skills: HashMap<String, Arc<dyn SkillRuntimeAdapter + Send + Sync>>,
+}
+
+impl SkillRuntimes {
+    /// Creates an empty set of runtimes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `runtime` for skills of type `skill_type`, replacing any
+    /// runtime previously registered for it.
+    ///
+    /// The skills `runtime` has loaded are indexed for [`invoke`](Self::invoke),
+    /// so register a runtime after loading its skills.
+    pub fn register(
+        &mut self,
+        skill_type: impl Into<String>,
+        runtime: Arc<dyn SkillRuntimeAdapter + Send + Sync>,
+    ) {
+        if let Some(previous) = self.runtimes.insert(skill_type.into(), runtime.clone()) {
+            self.skills.retain(|_, r| !Arc::ptr_eq(r, &previous));
+        }
+        for manifest in runtime.list_skills() {
+            self.skills.insert(manifest.name, runtime.clone());
+        }
+    }
+
+    /// Returns the runtime registered for `skill_type`.
+    pub fn get(&self, skill_type: &str) -> Option<Arc<dyn SkillRuntimeAdapter + Send + Sync>> {
+        self.runtimes.get(skill_type).cloned()
+    }
+
+    /// Returns the runtime that executes skills described by `manifest`.
+    pub fn runtime_for(
+        &self,
+        manifest: &SkillManifest,
+    ) -> Option<Arc<dyn SkillRuntimeAdapter + Send + Sync>> {
+        self.get(&manifest.runtime)
+    }
+
+    /// Lists the skills loaded in every registered runtime.
+    pub fn list_skills(&self) -> Vec<SkillManifest> {
+        self.runtimes
+            .values()
+            .flat_map(|runtime| runtime.list_skills())
+            .collect()
+    }
+
+    /// Invokes a skill through the runtime of its type.
+    pub async fn invoke(&self, invocation: SkillInvocation) -> Result<SkillResult, BlufioError> {
+        let runtime = self
+            .skills
+            .get(&invocation.skill_name)
+            .cloned()
+            .ok_or_else(|| {
+                BlufioError::skill_execution_msg(&format!(
+                    "skill not found in any runtime: {}",
+                    invocation.skill_name
+                ))
+            })?;
+        runtime.invoke(invocation).await
+    }
+
+    /// Returns the number of registered runtimes.
+    pub fn len(&self) -> usize {
+        self.runtimes.len()
+    }
+
+    /// Returns true if no runtime is registered.
+    pub fn is_empty(&self) -> bool {
+        self.runtimes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use blufio_core::PluginAdapter;
+    use blufio_core::types::{AdapterType, HealthStatus};
+
+    use crate::WasmSkillRuntime;
+    use crate::test_support::test_manifest;
+
+    /// Native-process runtime stand-in that echoes its input.
+    struct EchoRuntime;
+
+    #[async_trait]
+    impl PluginAdapter for EchoRuntime {
+        fn name(&self) -> &str {
+            "echo"
+        }
+        fn version(&self) -> semver::Version {
+            semver::Version::new(0, 1, 0)
+        }
+        fn adapter_type(&self) -> AdapterType {
+            AdapterType::SkillRuntime
+        }
+        async fn health_check(&self) -> Result<HealthStatus, BlufioError> {
+            Ok(HealthStatus::Healthy)
+        }
+        async fn shutdown(&self) -> Result<(), BlufioError> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl SkillRuntimeAdapter for EchoRuntime {
+        async fn invoke(&self, invocation: SkillInvocation) -> Result<SkillResult, BlufioError> {
+            Ok(SkillResult {
+                content: invocation.input.to_string(),
+                is_error: false,
+            })
+        }
+        fn list_skills(&self) -> Vec<SkillManifest> {
+            vec![manifest("echo-skill", "native")]
+        }
+    }
+
+    fn manifest(name: &str, runtime: &str) -> SkillManifest {
+        SkillManifest {
+            name: name.to_string(),
+            runtime: runtime.to_string(),
+            ..test_manifest()
+        }
+    }
+
+    fn invocation(skill_name: &str) -> SkillInvocation {
+        SkillInvocation {
+            skill_name: skill_name.to_string(),
+            input: serde_json::json!({"x": 1}),
+            session_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn invocations_route_by_skill_type() {
+        let mut wasm = WasmSkillRuntime::new().unwrap();
+        let module =
+            wat::parse_str(r#"(module (func (export "run")) (memory (export "memory") 1))"#)
+                .unwrap();
+        wasm.load_skill(manifest("wasm-skill", "wasm"), &module, None)
+            .unwrap();
+
+        let mut runtimes = SkillRuntimes::new();
+        runtimes.register("wasm", Arc::new(wasm));
+        runtimes.register("native", Arc::new(EchoRuntime));
+        assert_eq!(runtimes.len(), 2);
+        assert_eq!(runtimes.list_skills().len(), 2);
+        assert_eq!(
+            runtimes
+                .runtime_for(&manifest("any", "native"))
+                .unwrap()
+                .name(),
+            "echo"
+        );
+
+        let echoed = runtimes.invoke(invocation("echo-skill")).await.unwrap();
+        assert_eq!(echoed.content, r#"{"x":1}"#);
+
+        let ran = runtimes.invoke(invocation("wasm-skill")).await.unwrap();
+        assert!(!ran.is_error, "got: {}", ran.content);
+    }
+
+    #[tokio::test]
+    async fn unknown_skill_is_an_error() {
+        let mut runtimes = SkillRuntimes::new();
+        runtimes.register("native", Arc::new(EchoRuntime));
+        assert!(runtimes.invoke(invocation("missing")).await.is_err());
+        assert!(runtimes.runtime_for(&manifest("x", "wasm")).is_none());
+    }
+
+    #[tokio::test]
+    async fn replacing_a_runtime_unroutes_its_skills() {
+        let mut runtimes = SkillRuntimes::new();
+        runtimes.register("native", Arc::new(EchoRuntime));
+        runtimes.register("native", Arc::new(WasmSkillRuntime::new().unwrap()));
+        assert!(runtimes.invoke(invocation("echo-skill")).await.is_err());
+    }
+}
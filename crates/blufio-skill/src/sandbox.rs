@@ -18,29 +18,135 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use anyhow::anyhow;
-use blufio_core::BlufioError;
-use blufio_core::types::{SkillInvocation, SkillManifest, SkillResult};
+use async_trait::async_trait;
+use blufio_core::types::{
+    AdapterType, HealthStatus, SkillCapabilities, SkillInvocation, SkillManifest, SkillResult,
+};
+use blufio_core::{BlufioError, PluginAdapter, SkillRuntimeAdapter};
+use blufio_security::SsrfSafeResolver;
 use ed25519_dalek::VerifyingKey;
 use tracing::{debug, info, warn};
-use wasmtime::{Caller, Config, Engine, Linker, Memory, Module, Store};
-
-use crate::signing::{PublisherKeypair, compute_content_hash, signature_from_hex};
+use wasmtime::component::Component;
+use wasmtime::{Caller, Config, Engine, Linker, Memory, Module, Store, UpdateDeadline};
+
+use crate::capability::{CapabilityDenied, CapabilityRequest};
+use crate::signing::{
+    PublisherKeypair, VerificationStatus, compute_content_hash, signature_from_hex,
+    verify_skill_signature,
+};
 use crate::store::VerificationInfo;
+use crate::{component, wasi};
 
 /// State stored in each wasmtime Store for a single skill invocation.
-struct SkillState {
+pub(crate) struct SkillState {
     /// The skill's manifest (for capability checks in host function impls).
     /// Accessed via caller.data() in host function closures during invocation.
     #[allow(dead_code)]
-    manifest: SkillManifest,
+    pub(crate) manifest: SkillManifest,
     /// Accumulated log output from the skill.
-    output: Vec<String>,
+    pub(crate) output: Vec<String>,
     /// Input JSON passed to the skill (read by the skill via host function).
-    input_json: String,
+    pub(crate) input_json: String,
     /// Result JSON written by the skill.
-    result_json: Option<String>,
+    pub(crate) result_json: Option<String>,
+    /// Memory and table caps enforced through [`Store::limiter`].
+    pub(crate) limiter: SkillLimiter,
+    /// WASI preview1 context, linked only for skills with `wasi = true`.
+    pub(crate) wasi: wasmtime_wasi::p1::WasiP1Ctx,
+}
+
+/// Most table elements a skill may grow a table to.
+const MAX_TABLE_ELEMENTS: usize = 10_000;
+
+/// Caps linear memory and table growth for one invocation.
+///
+/// Growth past a cap traps with a "memory limit exceeded" error rather than
+/// making `memory.grow` return -1, so a runaway skill halts instead of
+/// continuing with a failed allocation.
+pub(crate) struct SkillLimiter {
+    max_memory_bytes: usize,
+    max_table_elements: usize,
+}
+
+impl SkillLimiter {
+    /// Limits linear memory to `memory_mb` megabytes.
+    pub(crate) fn new(memory_mb: u32) -> Self {
+        Self {
+            max_memory_bytes: (memory_mb as usize).saturating_mul(1024 * 1024),
+            max_table_elements: MAX_TABLE_ELEMENTS,
+        }
+    }
+}
+
+impl wasmtime::ResourceLimiter for SkillLimiter {
+    fn memory_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        if desired > self.max_memory_bytes {
+            return Err(anyhow!(
+                "memory limit exceeded: growing from {current} to {desired} bytes, limit is {} bytes",
+                self.max_memory_bytes
+            ));
+        }
+        Ok(true)
+    }
+
+    fn table_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        if desired > self.max_table_elements {
+            return Err(anyhow!(
+                "memory limit exceeded: growing table from {current} to {desired} elements, limit is {}",
+                self.max_table_elements
+            ));
+        }
+        Ok(true)
+    }
+}
+
+/// Trap raised in a skill cancelled through a [`SkillCancelHandle`].
+#[derive(Debug)]
+struct SkillCancelled;
+
+impl std::fmt::Display for SkillCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("skill cancelled")
+    }
+}
+
+impl std::error::Error for SkillCancelled {}
+
+/// Cancels in-flight and later invocations of a [`WasmSkillRuntime`], e.g.
+/// on agent shutdown.
+///
+/// Cancelling bumps the engine epoch so running skills reach their epoch
+/// deadline immediately and trap instead of using their full timeout.
+#[derive(Clone)]
+pub struct SkillCancelHandle {
+    engine: Engine,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl SkillCancelHandle {
+    /// Cancels all running skills and refuses new invocations.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.engine.increment_epoch();
+    }
+
+    /// Returns true once [`cancel`](Self::cancel) has been called.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
 }
 
 /// WASM skill runtime with per-invocation sandboxing.
@@ -52,12 +158,21 @@ pub struct WasmSkillRuntime {
     engine: Engine,
     manifests: HashMap<String, SkillManifest>,
     modules: HashMap<String, Module>,
+    /// Compiled components, for skills built against the `skill` WIT world.
+    components: HashMap<String, Component>,
     /// Raw WASM bytes stored for runtime hash verification (avoids TOCTOU).
     wasm_bytes: HashMap<String, Vec<u8>>,
     /// Verification metadata loaded at skill load time, checked at invoke time.
     verification: HashMap<String, VerificationInfo>,
     /// Optional EventBus for publishing skill lifecycle events.
     event_bus: Option<Arc<blufio_bus::EventBus>>,
+    /// Set in strict mode (`skill.require_verified`): only skills signed by
+    /// one of these publishers load.
+    trusted_publishers: Option<Vec<String>>,
+    /// Set by [`SkillCancelHandle::cancel`].
+    cancelled: Arc<AtomicBool>,
+    /// Client for `http_request`, shared by all invocations.
+    http_client: reqwest::Client,
 }
 
 impl WasmSkillRuntime {
@@ -78,33 +193,98 @@ impl WasmSkillRuntime {
             engine,
             manifests: HashMap::new(),
             modules: HashMap::new(),
+            components: HashMap::new(),
             wasm_bytes: HashMap::new(),
             verification: HashMap::new(),
             event_bus: None,
+            trusted_publishers: None,
+            cancelled: Arc::new(AtomicBool::new(false)),
+            http_client: skill_http_client(Vec::new())?,
         })
     }
 
+    /// Lets `http_request` reach the given private IPs, e.g. a local mock
+    /// server.
+    #[cfg(test)]
+    pub(crate) fn set_allowed_private_ips(&mut self, ips: Vec<String>) {
+        self.http_client = skill_http_client(ips).unwrap();
+    }
+
     /// Sets the EventBus for publishing skill lifecycle events.
     pub fn set_event_bus(&mut self, bus: Arc<blufio_bus::EventBus>) {
         self.event_bus = Some(bus);
     }
 
+    /// Returns a handle that cancels this runtime's skill invocations.
+    pub fn cancel_handle(&self) -> SkillCancelHandle {
+        SkillCancelHandle {
+            engine: self.engine.clone(),
+            cancelled: self.cancelled.clone(),
+        }
+    }
+
+    /// Refuses to load skills that are not signed by one of
+    /// `trusted_publishers` (strict mode).
+    ///
+    /// The signature is checked against this list at load time, so a
+    /// publisher removed from the trust store since install is refused.
+    pub fn require_trusted_publishers(&mut self, trusted_publishers: Vec<String>) {
+        self.trusted_publishers = Some(trusted_publishers);
+    }
+
     /// Loads a skill from its manifest and WASM binary bytes.
     ///
-    /// The WASM module is compiled once and cached. Subsequent invocations
-    /// reuse the compiled module with fresh Store instances.
+    /// The bytes may be a core module using the `blufio` ABI or a component
+    /// targeting the `skill` WIT world (see [`crate::component`]). Either is
+    /// compiled once and cached; invocations get fresh Store instances.
+    ///
+    /// In strict mode (see
+    /// [`require_trusted_publishers`](Self::require_trusted_publishers))
+    /// unsigned and untrusted skills are rejected with `BlufioError::Security`,
+    /// whatever status was recorded at install.
     pub fn load_skill(
         &mut self,
         manifest: SkillManifest,
         wasm_bytes: &[u8],
         verification_info: Option<VerificationInfo>,
     ) -> Result<(), BlufioError> {
-        let module = Module::new(&self.engine, wasm_bytes).map_err(|e| {
-            BlufioError::skill_compilation_msg(&format!(
-                "failed to compile WASM module for skill '{}': {e}",
-                manifest.name
-            ))
-        })?;
+        if let Some(trusted_publishers) = &self.trusted_publishers {
+            let status = match &verification_info {
+                Some(info) => verify_skill_signature(
+                    wasm_bytes,
+                    info.signature.as_deref(),
+                    info.publisher_id.as_deref(),
+                    trusted_publishers,
+                )?,
+                None => VerificationStatus::Unsigned,
+            };
+            if status != VerificationStatus::Verified {
+                return Err(BlufioError::Security(format!(
+                    "skill '{}' is {status} -- refusing to load because skill.require_verified is set",
+                    manifest.name
+                )));
+            }
+        }
+
+        if component::is_component(wasm_bytes) {
+            let component = Component::new(&self.engine, wasm_bytes).map_err(|e| {
+                BlufioError::skill_compilation_msg(&format!(
+                    "failed to compile WASM component for skill '{}': {e}",
+                    manifest.name
+                ))
+            })?;
+            self.modules.remove(&manifest.name);
+            self.components.insert(manifest.name.clone(), component);
+        } else {
+            let module = Module::new(&self.engine, wasm_bytes).map_err(|e| {
+                BlufioError::skill_compilation_msg(&format!(
+                    "failed to compile WASM module for skill '{}': {e}",
+                    manifest.name
+                ))
+            })?;
+            self.components.remove(&manifest.name);
+            self.modules.insert(manifest.name.clone(), module);
+        }
 
         info!(skill = %manifest.name, version = %manifest.version, "loaded WASM skill");
 
@@ -117,7 +297,6 @@ impl WasmSkillRuntime {
             self.verification.insert(manifest.name.clone(), info);
         }
 
-        self.modules.insert(manifest.name.clone(), module);
         self.manifests.insert(manifest.name.clone(), manifest);
         Ok(())
     }
@@ -188,6 +367,17 @@ impl WasmSkillRuntime {
     }
 
     pub async fn invoke(&self, invocation: SkillInvocation) -> Result<SkillResult, BlufioError> {
+        self.invoke_reporting_denial(invocation)
+            .await
+            .map(|(result, _)| result)
+    }
+
+    /// Invokes a loaded skill like [`invoke`](Self::invoke), also returning
+    /// the capability the skill was denied when it trapped on one.
+    pub async fn invoke_reporting_denial(
+        &self,
+        invocation: SkillInvocation,
+    ) -> Result<(SkillResult, Option<CapabilityRequest>), BlufioError> {
         // Pre-execution cryptographic verification.
         self.verify_before_execution(&invocation.skill_name)?;
 
@@ -211,37 +401,76 @@ impl WasmSkillRuntime {
             ))
         })?;
 
-        let module = self.modules.get(&invocation.skill_name).ok_or_else(|| {
-            BlufioError::skill_execution_msg(&format!(
+        let module = self.modules.get(&invocation.skill_name).cloned();
+        let component = self.components.get(&invocation.skill_name).cloned();
+        if module.is_none() && component.is_none() {
+            return Err(BlufioError::skill_execution_msg(&format!(
                 "module for skill '{}' not found",
                 invocation.skill_name
-            ))
-        })?;
+            )));
+        }
 
         let input_json = serde_json::to_string(&invocation.input)
             .map_err(BlufioError::skill_execution_failed)?;
 
         // Create fresh Store with skill state.
+        let wasi_stdout = wasmtime_wasi::p2::pipe::MemoryOutputPipe::new(wasi::MAX_STDOUT_BYTES);
+        let wasi_ctx = wasi::build_ctx(manifest, &input_json, &wasi_stdout)?;
         let state = SkillState {
             manifest: manifest.clone(),
             output: Vec::new(),
             input_json,
             result_json: None,
+            limiter: SkillLimiter::new(manifest.resources.memory_mb),
+            wasi: wasi_ctx,
         };
         let mut store = Store::new(&self.engine, state);
 
+        // Cap linear memory and table growth.
+        store.limiter(|state| &mut state.limiter);
+
         // Set fuel limit.
         store
             .set_fuel(manifest.resources.fuel)
             .map_err(|e| BlufioError::skill_execution_msg(&format!("failed to set fuel: {e}")))?;
 
-        // Configure epoch deadline for wall-clock timeout.
-        store.epoch_deadline_trap();
-        store.set_epoch_deadline(manifest.resources.epoch_timeout_secs);
+        // Configure epoch deadline for wall-clock timeout. The deadline is
+        // checked on every epoch tick so a cancellation traps right away; an
+        // already-cancelled runtime traps on entry.
+        let cancelled = self.cancelled.clone();
+        let mut ticks_left = manifest.resources.epoch_timeout_secs;
+        store.set_epoch_deadline(if cancelled.load(Ordering::SeqCst) {
+            0
+        } else {
+            1
+        });
+        store.epoch_deadline_callback(move |_| {
+            if cancelled.load(Ordering::SeqCst) {
+                return Err(anyhow::Error::new(SkillCancelled));
+            }
+            ticks_left = ticks_left.saturating_sub(1);
+            if ticks_left == 0 {
+                return Err(wasmtime::Trap::Interrupt.into());
+            }
+            Ok(UpdateDeadline::Continue(1))
+        });
 
-        // Create linker with host functions.
-        let mut linker = Linker::new(&self.engine);
-        define_host_functions(&mut linker, manifest)?;
+        // Create linker with host functions (core ABI or WIT host interface).
+        let linker = match &component {
+            Some(_) => {
+                let mut linker = wasmtime::component::Linker::new(&self.engine);
+                component::define_host_interface(&mut linker, manifest, &self.http_client)?;
+                SkillLinker::Component(linker)
+            }
+            None => {
+                let mut linker = Linker::new(&self.engine);
+                define_host_functions(&mut linker, manifest, &self.http_client)?;
+                if manifest.wasi {
+                    wasi::add_to_linker(&mut linker)?;
+                }
+                SkillLinker::Module(linker)
+            }
+        };
 
         // Spawn epoch ticker (increments engine epoch every 1 second).
         let engine_clone = self.engine.clone();
@@ -253,17 +482,41 @@ impl WasmSkillRuntime {
             }
         });
 
-        // Clone module for the blocking task (Module is cheaply cloneable).
-        let module = module.clone();
-
         // Run WASM execution on a blocking thread so the epoch ticker can
         // advance on the tokio runtime while the WASM is executing.
+        let uses_wasi = manifest.wasi;
         let wasm_result = tokio::task::spawn_blocking(move || {
-            let instance = linker.instantiate(&mut store, &module)?;
-            let run_func = instance
-                .get_typed_func::<(), ()>(&mut store, "run")
-                .map_err(|e| anyhow::anyhow!("skill has no 'run' export: {e}"))?;
-            run_func.call(&mut store, ())?;
+            match (linker, module, component) {
+                (SkillLinker::Component(linker), _, Some(component)) => {
+                    component::run_component(&mut store, &linker, &component)?;
+                }
+                (SkillLinker::Module(linker), Some(module), _) => {
+                    let instance = linker.instantiate(&mut store, &module)?;
+                    // WASI commands export `_start` rather than `run`.
+                    let entry = if uses_wasi && instance.get_export(&mut store, "run").is_none() {
+                        "_start"
+                    } else {
+                        "run"
+                    };
+                    let run_func = instance
+                        .get_typed_func::<(), ()>(&mut store, entry)
+                        .map_err(|e| anyhow::anyhow!("skill has no '{entry}' export: {e}"))?;
+                    match run_func.call(&mut store, ()) {
+                        Ok(()) => {}
+                        // `proc_exit(0)` ends a WASI command successfully.
+                        Err(e) if wasi::exit_code(&e) == Some(0) => {}
+                        Err(e) => return Err(e),
+                    }
+                    if uses_wasi && store.data().result_json.is_none() {
+                        let stdout = wasi_stdout.contents();
+                        if !stdout.is_empty() {
+                            store.data_mut().result_json =
+                                Some(String::from_utf8_lossy(&stdout).into_owned());
+                        }
+                    }
+                }
+                _ => unreachable!("linker kind matches the loaded skill kind"),
+            }
             Ok::<Store<SkillState>, anyhow::Error>(store)
         })
         .await
@@ -277,27 +530,43 @@ impl WasmSkillRuntime {
         let skill_name = &invocation.skill_name;
         let fuel = manifest.resources.fuel;
         let timeout = manifest.resources.epoch_timeout_secs;
+        let memory_mb = manifest.resources.memory_mb;
 
+        let mut denial = None;
         let result = match wasm_result {
             Ok(store) => {
                 let state = store.data();
-                let content = if let Some(ref result_json) = state.result_json {
-                    result_json.clone()
-                } else if !state.output.is_empty() {
-                    state.output.join("\n")
-                } else {
-                    "Skill completed successfully (no output)".to_string()
-                };
-
-                Ok(SkillResult {
-                    content,
-                    is_error: false,
-                })
+                match &manifest.output_schema {
+                    Some(schema) => Ok(check_structured_output(
+                        skill_name,
+                        schema,
+                        state.result_json.as_deref(),
+                    )),
+                    None => {
+                        let content = if let Some(ref result_json) = state.result_json {
+                            result_json.clone()
+                        } else if !state.output.is_empty() {
+                            state.output.join("\n")
+                        } else {
+                            "Skill completed successfully (no output)".to_string()
+                        };
+
+                        Ok(SkillResult {
+                            content,
+                            is_error: false,
+                        })
+                    }
+                }
             }
             Err(e) => {
+                denial = e
+                    .downcast_ref::<CapabilityDenied>()
+                    .map(|d| d.request.clone());
                 // Use {e:#} to get the full error chain including nested causes.
                 let error_msg = format!("{e:#}");
-                let content = if error_msg.contains("all fuel consumed") {
+                let content = if e.downcast_ref::<SkillCancelled>().is_some() {
+                    format!("Skill '{skill_name}' was cancelled before completing: {error_msg}")
+                } else if error_msg.contains("all fuel consumed") {
                     format!(
                         "Skill '{skill_name}' exceeded fuel limit ({fuel} fuel units): {error_msg}"
                     )
@@ -305,6 +574,10 @@ impl WasmSkillRuntime {
                     format!(
                         "Skill '{skill_name}' exceeded wall-clock timeout ({timeout}s): {error_msg}"
                     )
+                } else if error_msg.contains("memory limit exceeded") {
+                    format!(
+                        "Skill '{skill_name}' exceeded memory limit ({memory_mb} MB): {error_msg}"
+                    )
                 } else if error_msg.contains("capability not permitted") {
                     format!("Skill '{skill_name}' capability denied: {error_msg}")
                 } else {
@@ -331,7 +604,32 @@ impl WasmSkillRuntime {
             .await;
         }
 
-        result
+        result.map(|result| (result, denial))
+    }
+
+    /// Adds `request` to a loaded skill's capabilities and reloads the skill
+    /// from its stored bytes, keeping its verification info. Returns the
+    /// updated capabilities.
+    pub fn reload_with_grant(
+        &mut self,
+        skill_name: &str,
+        request: &CapabilityRequest,
+    ) -> Result<SkillCapabilities, BlufioError> {
+        let (Some(manifest), Some(bytes)) = (
+            self.manifests.get(skill_name),
+            self.wasm_bytes.get(skill_name),
+        ) else {
+            return Err(BlufioError::skill_execution_msg(&format!(
+                "skill '{skill_name}' not loaded"
+            )));
+        };
+        let mut manifest = manifest.clone();
+        let bytes = bytes.clone();
+        request.grant(&mut manifest.capabilities);
+        let capabilities = manifest.capabilities.clone();
+        let verification = self.verification.remove(skill_name);
+        self.load_skill(manifest, &bytes, verification)?;
+        Ok(capabilities)
     }
 
     /// Returns clones of all loaded skill manifests.
@@ -347,8 +645,112 @@ impl WasmSkillRuntime {
 
     /// Returns true if a skill with the given name is loaded.
     pub fn has_skill(&self, name: &str) -> bool {
-        self.modules.contains_key(name)
+        self.modules.contains_key(name) || self.components.contains_key(name)
+    }
+}
+
+/// Validates a skill's output against its declared output schema.
+///
+/// Conforming output is returned as compact JSON; missing, unparseable or
+/// non-conforming output becomes an error result naming the violations.
+fn check_structured_output(
+    skill_name: &str,
+    schema: &serde_json::Value,
+    output: Option<&str>,
+) -> SkillResult {
+    let error = |content: String| SkillResult {
+        content,
+        is_error: true,
+    };
+    let Some(output) = output else {
+        return error(format!(
+            "Skill '{skill_name}' declares an output schema but set no output"
+        ));
+    };
+    let value: serde_json::Value = match serde_json::from_str(output) {
+        Ok(value) => value,
+        Err(e) => {
+            return error(format!(
+                "Skill '{skill_name}' output is not valid JSON: {e}"
+            ));
+        }
+    };
+    let violations: Vec<String> = match crate::tool::schema_violations(schema, &value) {
+        Ok(violations) => violations
+            .into_iter()
+            .map(|v| format!("{} at '{}'", v.message, v.path))
+            .collect(),
+        Err(e) => {
+            return error(format!(
+                "Skill '{skill_name}' output schema is invalid: {e}"
+            ));
+        }
+    };
+    if !violations.is_empty() {
+        return error(format!(
+            "Skill '{skill_name}' output does not match its output schema: {}",
+            violations.join("; ")
+        ));
+    }
+    SkillResult {
+        content: value.to_string(),
+        is_error: false,
+    }
+}
+
+/// Host bindings for one invocation, by skill kind.
+enum SkillLinker {
+    Module(Linker<SkillState>),
+    Component(wasmtime::component::Linker<SkillState>),
+}
+
+#[async_trait]
+impl PluginAdapter for WasmSkillRuntime {
+    fn name(&self) -> &str {
+        "wasm"
+    }
+
+    fn version(&self) -> semver::Version {
+        semver::Version::new(0, 1, 0)
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::SkillRuntime
     }
+
+    async fn health_check(&self) -> Result<HealthStatus, BlufioError> {
+        Ok(HealthStatus::Healthy)
+    }
+
+    async fn shutdown(&self) -> Result<(), BlufioError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SkillRuntimeAdapter for WasmSkillRuntime {
+    async fn invoke(&self, invocation: SkillInvocation) -> Result<SkillResult, BlufioError> {
+        WasmSkillRuntime::invoke(self, invocation).await
+    }
+
+    fn list_skills(&self) -> Vec<SkillManifest> {
+        WasmSkillRuntime::list_skills(self)
+    }
+}
+
+/// Builds the client for skill `http_request` calls.
+///
+/// Hostnames are resolved through [`SsrfSafeResolver`], so a name that points
+/// at a private address is refused like a literal private IP, and redirects
+/// are not followed, so an allowed host cannot bounce a request elsewhere.
+fn skill_http_client(allowed_private_ips: Vec<String>) -> Result<reqwest::Client, BlufioError> {
+    reqwest::Client::builder()
+        .dns_resolver(Arc::new(SsrfSafeResolver::new(allowed_private_ips)))
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .map_err(|e| {
+            BlufioError::skill_execution_msg(&format!("failed to build skill HTTP client: {e}"))
+        })
 }
 
 /// Defines capability-gated host functions in the linker.
@@ -360,6 +762,7 @@ impl WasmSkillRuntime {
 fn define_host_functions(
     linker: &mut Linker<SkillState>,
     manifest: &SkillManifest,
+    http_client: &reqwest::Client,
 ) -> Result<(), BlufioError> {
     // --- log: always available ---
     linker
@@ -416,6 +819,49 @@ fn define_host_functions(
         )
         .map_err(linker_err)?;
 
+    // --- get_input_field: always available ---
+    // Serializes one top-level field of the input JSON into out_ptr. Returns
+    // -1 when the key is missing, otherwise the serialized length; the value
+    // is only written when it fits in out_len, so a skill can retry with a
+    // larger buffer. Traps if the key or output buffer lies outside memory.
+    linker
+        .func_wrap(
+            "blufio",
+            "get_input_field",
+            |mut caller: Caller<'_, SkillState>,
+             key_ptr: i32,
+             key_len: i32,
+             out_ptr: i32,
+             out_len: i32|
+             -> Result<i32, wasmtime::Error> {
+                let memory = match caller.get_export("memory") {
+                    Some(wasmtime::Extern::Memory(mem)) => mem,
+                    _ => return Err(anyhow!("WASM module has no exported memory")),
+                };
+                let key = read_bytes_from_memory(&memory, &caller, key_ptr, key_len)
+                    .ok_or_else(|| anyhow!("input field key lies outside WASM memory"))?;
+                let key = String::from_utf8(key)
+                    .map_err(|_| anyhow!("input field key is not valid UTF-8"))?;
+                let out = usize::try_from(out_ptr)
+                    .ok()
+                    .zip(usize::try_from(out_len).ok())
+                    .filter(|&(ptr, len)| {
+                        ptr.checked_add(len)
+                            .is_some_and(|end| end <= memory.data_size(&caller))
+                    })
+                    .ok_or_else(|| anyhow!("input field output buffer lies outside WASM memory"))?;
+
+                let Some(value) = input_field(&caller.data().input_json, &key) else {
+                    return Ok(-1);
+                };
+                if value.len() <= out.1 {
+                    write_bytes_to_memory(&memory, &mut caller, out_ptr, value.as_bytes());
+                }
+                Ok(value.len() as i32)
+            },
+        )
+        .map_err(linker_err)?;
+
     // --- set_output: always available ---
     // Skill writes its result JSON to host.
     linker
@@ -437,7 +883,9 @@ fn define_host_functions(
     // --- http_request: capability-gated ---
     // Traps if network capability is not declared. When permitted, makes a real
     // HTTP request using reqwest (via tokio runtime handle) with domain validation
-    // and SSRF prevention. Stores response body in result_json, returns status code.
+    // and SSRF prevention (see `skill_http_client`). `method` selects the verb
+    // (see `http_method`) and a non-empty body is read from WASM memory, up to
+    // MAX_HTTP_BODY_BYTES. Stores response body in result_json, returns status code.
     let has_network = manifest.capabilities.network.is_some();
     let allowed_domains: Vec<String> = manifest
         .capabilities
@@ -445,6 +893,7 @@ fn define_host_functions(
         .as_ref()
         .map(|n| n.domains.clone())
         .unwrap_or_default();
+    let http_client = http_client.clone();
     linker
         .func_wrap(
             "blufio",
@@ -452,15 +901,21 @@ fn define_host_functions(
             move |mut caller: Caller<'_, SkillState>,
                   url_ptr: i32,
                   url_len: i32,
-                  _method: i32,
-                  _body_ptr: i32,
-                  _body_len: i32|
+                  method: i32,
+                  body_ptr: i32,
+                  body_len: i32|
                   -> Result<i32, wasmtime::Error> {
                 if !has_network {
                     warn!("skill attempted http_request without network capability");
-                    return Err(anyhow!(
-                        "capability not permitted: skill lacks network permission"
-                    ));
+                    let message = "skill lacks network permission";
+                    return Err(
+                        match requested_string(&mut caller, url_ptr, url_len)
+                            .and_then(|url| host_of(&url))
+                        {
+                            Some(domain) => denied(CapabilityRequest::Network { domain }, message),
+                            None => anyhow!("capability not permitted: {message}"),
+                        },
+                    );
                 }
 
                 let memory = match caller.get_export("memory") {
@@ -482,9 +937,11 @@ fn define_host_functions(
                         .iter()
                         .any(|d| domain == d || domain.ends_with(&format!(".{d}")))
                     {
-                        return Err(anyhow!(
-                            "capability not permitted: domain '{domain}' not in allowed list {:?}",
-                            allowed_domains
+                        return Err(denied(
+                            CapabilityRequest::Network {
+                                domain: domain.to_string(),
+                            },
+                            format!("domain '{domain}' not in allowed list {allowed_domains:?}"),
                         ));
                     }
                 } else {
@@ -496,12 +953,29 @@ fn define_host_functions(
                     return Err(anyhow!("SSRF blocked: {e}"));
                 }
 
+                let method = http_method(method)?;
+                let body = if body_len == 0 {
+                    None
+                } else if body_len < 0 || body_len as usize > MAX_HTTP_BODY_BYTES {
+                    return Err(anyhow!(
+                        "http_request body of {body_len} bytes exceeds the {MAX_HTTP_BODY_BYTES} byte limit"
+                    ));
+                } else {
+                    match read_bytes_from_memory(&memory, &caller, body_ptr, body_len) {
+                        Some(b) => Some(b),
+                        None => return Err(anyhow!("failed to read request body from WASM memory")),
+                    }
+                };
+
                 // Make the HTTP request using the tokio runtime handle.
                 // We are inside spawn_blocking, so Handle::current() is available.
                 let handle = tokio::runtime::Handle::current();
                 let response = handle.block_on(async {
-                    let client = reqwest::Client::new();
-                    client.get(&url).send().await
+                    let mut request = http_client.request(method, &url);
+                    if let Some(body) = body {
+                        request = request.body(body);
+                    }
+                    request.send().await
                 });
 
                 match response {
@@ -516,7 +990,8 @@ fn define_host_functions(
                     }
                     Err(e) => {
                         warn!(url = %url, error = %e, "WASM http_request failed");
-                        Err(anyhow!("HTTP request failed: {e}"))
+                        // Keep the source chain, which says why a host was refused.
+                        Err(anyhow::Error::new(e).context("HTTP request failed"))
                     }
                 }
             },
@@ -550,9 +1025,11 @@ fn define_host_functions(
                   -> Result<i32, wasmtime::Error> {
                 if !has_fs_read {
                     warn!("skill attempted read_file without filesystem read capability");
-                    return Err(anyhow!(
-                        "capability not permitted: skill lacks filesystem read permission"
-                    ));
+                    let message = "skill lacks filesystem read permission";
+                    return Err(match requested_string(&mut caller, path_ptr, path_len) {
+                        Some(path) => denied(CapabilityRequest::FilesystemRead { path }, message),
+                        None => anyhow!("capability not permitted: {message}"),
+                    });
                 }
 
                 let memory = match caller.get_export("memory") {
@@ -568,11 +1045,9 @@ fn define_host_functions(
                 // Validate that the path starts with one of the manifest's read paths.
                 let path_allowed = read_paths.iter().any(|allowed| path.starts_with(allowed));
                 if !path_allowed {
-                    return Err(anyhow!(
-                        "capability not permitted: path '{}' not within allowed read paths {:?}",
-                        path,
-                        read_paths
-                    ));
+                    let message =
+                        format!("path '{path}' not within allowed read paths {read_paths:?}");
+                    return Err(denied(CapabilityRequest::FilesystemRead { path }, message));
                 }
 
                 // Read the file.
@@ -616,9 +1091,11 @@ fn define_host_functions(
                   -> Result<i32, wasmtime::Error> {
                 if !has_fs_write {
                     warn!("skill attempted write_file without filesystem write capability");
-                    return Err(anyhow!(
-                        "capability not permitted: skill lacks filesystem write permission"
-                    ));
+                    let message = "skill lacks filesystem write permission";
+                    return Err(match requested_string(&mut caller, path_ptr, path_len) {
+                        Some(path) => denied(CapabilityRequest::FilesystemWrite { path }, message),
+                        None => anyhow!("capability not permitted: {message}"),
+                    });
                 }
 
                 let memory = match caller.get_export("memory") {
@@ -639,11 +1116,9 @@ fn define_host_functions(
                 // Validate that the path starts with one of the manifest's write paths.
                 let path_allowed = write_paths.iter().any(|allowed| path.starts_with(allowed));
                 if !path_allowed {
-                    return Err(anyhow!(
-                        "capability not permitted: path '{}' not within allowed write paths {:?}",
-                        path,
-                        write_paths
-                    ));
+                    let message =
+                        format!("path '{path}' not within allowed write paths {write_paths:?}");
+                    return Err(denied(CapabilityRequest::FilesystemWrite { path }, message));
                 }
 
                 // Write the file.
@@ -702,6 +1177,29 @@ fn define_host_functions(
     Ok(())
 }
 
+/// Helper: a capability-denial trap carrying the request.
+fn denied(request: CapabilityRequest, message: impl Into<String>) -> anyhow::Error {
+    anyhow::Error::new(CapabilityDenied::new(request, message))
+}
+
+/// Helper: best-effort read of the URL or path a denied call asked for.
+fn requested_string(caller: &mut Caller<'_, SkillState>, ptr: i32, len: i32) -> Option<String> {
+    match caller.get_export("memory") {
+        Some(wasmtime::Extern::Memory(memory)) => {
+            read_string_from_memory(&memory, caller, ptr, len)
+        }
+        _ => None,
+    }
+}
+
+/// Helper: the host of a URL, if it parses and has one.
+fn host_of(url: &str) -> Option<String> {
+    reqwest::Url::parse(url)
+        .ok()?
+        .host_str()
+        .map(str::to_string)
+}
+
 /// Helper: read a UTF-8 string from WASM memory.
 fn read_string_from_memory(
     memory: &Memory,
@@ -709,13 +1207,45 @@ fn read_string_from_memory(
     ptr: i32,
     len: i32,
 ) -> Option<String> {
-    let ptr = ptr as usize;
-    let len = len as usize;
-    let data = memory.data(caller);
-    if ptr + len > data.len() {
-        return None;
+    String::from_utf8(read_bytes_from_memory(memory, caller, ptr, len)?).ok()
+}
+
+/// Serializes the top-level field `key` of the JSON object `input_json`.
+fn input_field(input_json: &str, key: &str) -> Option<String> {
+    let input: serde_json::Value = serde_json::from_str(input_json).ok()?;
+    input.get(key).map(|value| value.to_string())
+}
+
+/// Helper: read raw bytes from WASM memory.
+fn read_bytes_from_memory(
+    memory: &Memory,
+    caller: &Caller<'_, SkillState>,
+    ptr: i32,
+    len: i32,
+) -> Option<Vec<u8>> {
+    let ptr = usize::try_from(ptr).ok()?;
+    let len = usize::try_from(len).ok()?;
+    memory
+        .data(caller)
+        .get(ptr..ptr.checked_add(len)?)
+        .map(<[u8]>::to_vec)
+}
+
+/// Largest request body a skill may send through `http_request`.
+const MAX_HTTP_BODY_BYTES: usize = 1024 * 1024;
+
+/// Maps the `http_request` method argument to an HTTP verb:
+/// 0 GET, 1 POST, 2 PUT, 3 DELETE.
+fn http_method(method: i32) -> Result<reqwest::Method, wasmtime::Error> {
+    match method {
+        0 => Ok(reqwest::Method::GET),
+        1 => Ok(reqwest::Method::POST),
+        2 => Ok(reqwest::Method::PUT),
+        3 => Ok(reqwest::Method::DELETE),
+        other => Err(anyhow!(
+            "unsupported http_request method {other} (0 GET, 1 POST, 2 PUT, 3 DELETE)"
+        )),
     }
-    String::from_utf8(data[ptr..ptr + len].to_vec()).ok()
 }
 
 /// Helper: write bytes into WASM memory.
@@ -740,7 +1270,8 @@ fn linker_err(e: anyhow::Error) -> BlufioError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use blufio_core::types::{NetworkCapability, SkillResources};
+    use crate::test_support::test_manifest;
+    use blufio_core::types::NetworkCapability;
 
     #[test]
     fn sandbox_runtime_creates_successfully() {
@@ -760,6 +1291,8 @@ mod tests {
                 output: Vec::new(),
                 input_json: "{}".to_string(),
                 result_json: None,
+                limiter: SkillLimiter::new(16),
+                wasi: wasmtime_wasi::WasiCtxBuilder::new().build_p1(),
             },
         );
         // set_fuel should succeed because consume_fuel is enabled.
@@ -822,12 +1355,7 @@ mod tests {
         let manifest = test_manifest();
         runtime.load_skill(manifest, &wasm, None).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({"query": "test"}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await.unwrap();
+        let result = invoke_test_skill_with(&runtime, serde_json::json!({"query": "test"})).await;
         assert!(!result.is_error);
     }
 
@@ -852,12 +1380,7 @@ mod tests {
 
         runtime.load_skill(manifest, &wasm, None).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await.unwrap();
+        let result = invoke_test_skill(&runtime).await;
         assert!(result.is_error);
         assert!(
             result.content.contains("exceeded fuel limit"),
@@ -866,6 +1389,52 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn sandbox_memory_growth_past_limit_returns_error() {
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+
+        // Skill that grows its memory by 32 pages (2 MB) past a 1 MB cap.
+        let wat = r#"(module
+            (func (export "run")
+                (drop (memory.grow (i32.const 32)))
+            )
+            (memory (export "memory") 1)
+        )"#;
+        let wasm = wat::parse_str(wat).unwrap();
+
+        let mut manifest = test_manifest();
+        manifest.resources.memory_mb = 1;
+        runtime.load_skill(manifest, &wasm, None).unwrap();
+
+        let result = invoke_test_skill(&runtime).await;
+        assert!(result.is_error);
+        assert!(
+            result.content.contains("exceeded memory limit (1 MB)"),
+            "Expected memory limit error, got: {}",
+            result.content
+        );
+    }
+
+    #[tokio::test]
+    async fn sandbox_memory_growth_within_limit_succeeds() {
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+
+        let wat = r#"(module
+            (func (export "run")
+                (drop (memory.grow (i32.const 8)))
+            )
+            (memory (export "memory") 1)
+        )"#;
+        let wasm = wat::parse_str(wat).unwrap();
+
+        let mut manifest = test_manifest();
+        manifest.resources.memory_mb = 1;
+        runtime.load_skill(manifest, &wasm, None).unwrap();
+
+        let result = invoke_test_skill(&runtime).await;
+        assert!(!result.is_error, "got: {}", result.content);
+    }
+
     #[tokio::test]
     async fn sandbox_skill_with_log_output() {
         let mut runtime = WasmSkillRuntime::new().unwrap();
@@ -890,12 +1459,7 @@ mod tests {
         let manifest = test_manifest();
         runtime.load_skill(manifest, &wasm, None).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await.unwrap();
+        let result = invoke_test_skill(&runtime).await;
         assert!(!result.is_error);
         assert!(
             result.content.contains("hello"),
@@ -928,12 +1492,7 @@ mod tests {
         runtime.load_skill(manifest, &wasm, None).unwrap();
 
         let start = std::time::Instant::now();
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await.unwrap();
+        let result = invoke_test_skill(&runtime).await;
         let elapsed = start.elapsed();
 
         assert!(result.is_error);
@@ -984,12 +1543,7 @@ mod tests {
         let manifest = test_manifest();
         runtime.load_skill(manifest, &wasm, None).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await.unwrap();
+        let result = invoke_test_skill(&runtime).await;
         assert!(
             result.is_error,
             "Expected error result, got: {}",
@@ -1031,12 +1585,7 @@ mod tests {
         let manifest = test_manifest();
         runtime.load_skill(manifest, &wasm, None).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await.unwrap();
+        let result = invoke_test_skill(&runtime).await;
         assert!(
             result.is_error,
             "Expected error result, got: {}",
@@ -1080,12 +1629,7 @@ mod tests {
         let manifest = test_manifest();
         runtime.load_skill(manifest, &wasm, None).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await.unwrap();
+        let result = invoke_test_skill(&runtime).await;
         assert!(
             result.is_error,
             "Expected error result, got: {}",
@@ -1143,12 +1687,7 @@ mod tests {
 
         runtime.load_skill(manifest, &wasm, None).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await.unwrap();
+        let result = invoke_test_skill(&runtime).await;
         assert!(!result.is_error, "Unexpected error: {}", result.content);
         assert_eq!(result.content, "hello from file");
     }
@@ -1206,12 +1745,7 @@ mod tests {
 
         runtime.load_skill(manifest, &wasm, None).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await.unwrap();
+        let result = invoke_test_skill(&runtime).await;
         assert!(!result.is_error, "Unexpected error: {}", result.content);
 
         // Verify the file was written.
@@ -1262,12 +1796,7 @@ mod tests {
 
         runtime.load_skill(manifest, &wasm, None).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await.unwrap();
+        let result = invoke_test_skill(&runtime).await;
         assert!(result.is_error, "Expected error, got: {}", result.content);
         assert!(
             result.content.contains("capability not permitted"),
@@ -1315,12 +1844,7 @@ mod tests {
 
         runtime.load_skill(manifest, &wasm, None).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await.unwrap();
+        let result = invoke_test_skill(&runtime).await;
         assert!(
             result.is_error,
             "Expected error result, got: {}",
@@ -1334,23 +1858,659 @@ mod tests {
         );
     }
 
-    /// Helper: create a test manifest with no capabilities.
-    fn test_manifest() -> SkillManifest {
-        SkillManifest {
-            name: "test-skill".to_string(),
-            version: "0.1.0".to_string(),
-            description: "A test skill".to_string(),
-            author: None,
-            capabilities: Default::default(),
-            resources: SkillResources {
-                fuel: 1_000_000_000,
-                memory_mb: 16,
-                epoch_timeout_secs: 5,
-            },
-            wasm_entry: "skill.wasm".to_string(),
+    #[tokio::test]
+    async fn sandbox_get_input_field_extracts_value() {
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+
+        // Reads "query" into offset 64 and outputs it; then records the
+        // results for a missing key and a too-small buffer in a log line.
+        let wat = r#"(module
+            (import "blufio" "get_input_field" (func $field (param i32 i32 i32 i32) (result i32)))
+            (import "blufio" "set_output" (func $set_output (param i32 i32)))
+            (func (export "run")
+                (local $len i32)
+                (local.set $len (call $field (i32.const 0) (i32.const 5) (i32.const 64) (i32.const 64)))
+                (call $set_output (i32.const 64) (local.get $len))
+                (if (i32.ne (call $field (i32.const 8) (i32.const 7) (i32.const 64) (i32.const 64)) (i32.const -1))
+                    (then unreachable))
+                (if (i32.ne (call $field (i32.const 0) (i32.const 5) (i32.const 128) (i32.const 2)) (local.get $len))
+                    (then unreachable))
+                (if (i32.ne (i32.load8_u (i32.const 128)) (i32.const 0))
+                    (then unreachable))
+            )
+            (memory (export "memory") 1)
+            (data (i32.const 0) "query")
+            (data (i32.const 8) "missing")
+        )"#;
+        let wasm = wat::parse_str(wat).unwrap();
+        runtime.load_skill(test_manifest(), &wasm, None).unwrap();
+
+        let result = invoke_test_skill_with(
+            &runtime,
+            serde_json::json!({"query": "weather in Oslo", "limit": 3}),
+        )
+        .await;
+        assert!(!result.is_error, "got: {}", result.content);
+        assert_eq!(result.content, r#""weather in Oslo""#);
+    }
+
+    #[tokio::test]
+    async fn sandbox_get_input_field_out_of_bounds_traps() {
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        let wat = r#"(module
+            (import "blufio" "get_input_field" (func $field (param i32 i32 i32 i32) (result i32)))
+            (func (export "run")
+                (drop (call $field (i32.const 0) (i32.const 5) (i32.const 65530) (i32.const 64)))
+            )
+            (memory (export "memory") 1)
+            (data (i32.const 0) "query")
+        )"#;
+        let wasm = wat::parse_str(wat).unwrap();
+        runtime.load_skill(test_manifest(), &wasm, None).unwrap();
+
+        let result = invoke_test_skill_with(&runtime, serde_json::json!({"query": "x"})).await;
+        assert!(result.is_error);
+        assert!(
+            result.content.contains("outside WASM memory"),
+            "got: {}",
+            result.content
+        );
+    }
+
+    #[tokio::test]
+    async fn sandbox_get_input_field_negative_key_length_traps() {
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        let wat = r#"(module
+            (import "blufio" "get_input_field" (func $field (param i32 i32 i32 i32) (result i32)))
+            (func (export "run")
+                (drop (call $field (i32.const 0) (i32.const -1) (i32.const 64) (i32.const 64)))
+            )
+            (memory (export "memory") 1)
+        )"#;
+        let wasm = wat::parse_str(wat).unwrap();
+        runtime.load_skill(test_manifest(), &wasm, None).unwrap();
+
+        let result = invoke_test_skill_with(&runtime, serde_json::json!({"query": "x"})).await;
+        assert!(result.is_error);
+        assert!(
+            result.content.contains("key lies outside WASM memory"),
+            "got: {}",
+            result.content
+        );
+    }
+
+    #[tokio::test]
+    async fn sandbox_cancel_handle_traps_running_skill_promptly() {
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        let wat = r#"(module
+            (func (export "run")
+                (loop $forever
+                    (br $forever)
+                )
+            )
+            (memory (export "memory") 1)
+        )"#;
+        let wasm = wat::parse_str(wat).unwrap();
+
+        let mut manifest = test_manifest();
+        manifest.resources.fuel = u64::MAX;
+        manifest.resources.epoch_timeout_secs = 60;
+        runtime.load_skill(manifest, &wasm, None).unwrap();
+
+        let handle = runtime.cancel_handle();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            handle.cancel();
+        });
+
+        let start = std::time::Instant::now();
+        let result = invoke_test_skill(&runtime).await;
+
+        assert!(result.is_error);
+        assert!(
+            result.content.contains("was cancelled"),
+            "Expected cancellation, got: {}",
+            result.content
+        );
+        assert!(!result.content.contains("wall-clock timeout"));
+        assert!(
+            start.elapsed().as_secs() < 5,
+            "cancellation should trap well before the 60s timeout, took {:?}",
+            start.elapsed()
+        );
+
+        // Later invocations are refused straight away.
+        assert!(runtime.cancel_handle().is_cancelled());
+        let result = invoke_test_skill(&runtime).await;
+        assert!(
+            result.content.contains("was cancelled"),
+            "got: {}",
+            result.content
+        );
+    }
+
+    /// A WASI command that prints the file `path`, opened relative to the
+    /// first preopened directory, to stdout. Traps if any WASI call fails.
+    fn wasi_cat_wat(path: &str) -> Vec<u8> {
+        wat::parse_str(format!(
+            r#"(module
+            (import "wasi_snapshot_preview1" "path_open"
+                (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "fd_read"
+                (func $fd_read (param i32 i32 i32 i32) (result i32)))
+            (import "wasi_snapshot_preview1" "fd_write"
+                (func $fd_write (param i32 i32 i32 i32) (result i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 512) "{path}")
+            (func (export "_start")
+                ;; path_open(fd 3, no flags, path, rights FD_READ) -> fd at 16
+                (if (call $path_open (i32.const 3) (i32.const 0)
+                        (i32.const 512) (i32.const {path_len}) (i32.const 0)
+                        (i64.const 2) (i64.const 0) (i32.const 0) (i32.const 16))
+                    (then unreachable))
+                ;; iovec at 32 -> 128-byte buffer at 256
+                (i32.store (i32.const 32) (i32.const 256))
+                (i32.store (i32.const 36) (i32.const 128))
+                (if (call $fd_read (i32.load (i32.const 16)) (i32.const 32) (i32.const 1) (i32.const 40))
+                    (then unreachable))
+                ;; write the bytes read to stdout
+                (i32.store (i32.const 36) (i32.load (i32.const 40)))
+                (if (call $fd_write (i32.const 1) (i32.const 32) (i32.const 1) (i32.const 44))
+                    (then unreachable))
+            )
+        )"#,
+            path_len = path.len(),
+        ))
+        .unwrap()
+    }
+
+    fn wasi_manifest(read_dir: &std::path::Path) -> SkillManifest {
+        let mut manifest = test_manifest();
+        manifest.wasi = true;
+        manifest.capabilities.filesystem = Some(blufio_core::types::FilesystemCapability {
+            read: vec![read_dir.to_string_lossy().into_owned()],
+            write: vec![],
+        });
+        manifest
+    }
+
+    /// Invocation of the `test-skill` loaded from [`test_manifest`].
+    fn test_invocation(input: serde_json::Value) -> SkillInvocation {
+        SkillInvocation {
+            skill_name: "test-skill".to_string(),
+            input,
+            session_id: None,
+        }
+    }
+
+    async fn invoke_test_skill(runtime: &WasmSkillRuntime) -> SkillResult {
+        invoke_test_skill_with(runtime, serde_json::json!({})).await
+    }
+
+    async fn invoke_test_skill_with(
+        runtime: &WasmSkillRuntime,
+        input: serde_json::Value,
+    ) -> SkillResult {
+        runtime.invoke(test_invocation(input)).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn sandbox_wasi_skill_reads_allowed_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("hello.txt"), "hello from wasi").unwrap();
+
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        runtime
+            .load_skill(wasi_manifest(dir.path()), &wasi_cat_wat("hello.txt"), None)
+            .unwrap();
+
+        let result = invoke_test_skill(&runtime).await;
+        assert!(!result.is_error, "got: {}", result.content);
+        assert_eq!(result.content, "hello from wasi");
+    }
+
+    #[tokio::test]
+    async fn sandbox_wasi_skill_cannot_escape_preopen() {
+        let root = tempfile::tempdir().unwrap();
+        let allowed = root.path().join("allowed");
+        std::fs::create_dir(&allowed).unwrap();
+        std::fs::write(root.path().join("secret.txt"), "secret").unwrap();
+
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        runtime
+            .load_skill(
+                wasi_manifest(&allowed),
+                &wasi_cat_wat("../secret.txt"),
+                None,
+            )
+            .unwrap();
+
+        let result = invoke_test_skill(&runtime).await;
+        assert!(result.is_error);
+        assert_ne!(result.content, "secret");
+    }
+
+    #[tokio::test]
+    async fn sandbox_wasi_write_only_preopen_refuses_reads() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("hello.txt"), "hello from wasi").unwrap();
+        let mut manifest = wasi_manifest(dir.path());
+        manifest.capabilities.filesystem = Some(blufio_core::types::FilesystemCapability {
+            read: vec![],
+            write: vec![dir.path().to_string_lossy().into_owned()],
+        });
+
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        runtime
+            .load_skill(manifest, &wasi_cat_wat("hello.txt"), None)
+            .unwrap();
+
+        // path_open with FD_READ rights fails, so the skill traps.
+        let result = invoke_test_skill(&runtime).await;
+        assert!(result.is_error);
+        assert_ne!(result.content, "hello from wasi");
+    }
+
+    #[tokio::test]
+    async fn sandbox_wasi_imports_need_manifest_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manifest = wasi_manifest(dir.path());
+        manifest.wasi = false;
+
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        runtime
+            .load_skill(manifest, &wasi_cat_wat("hello.txt"), None)
+            .unwrap();
+
+        let result = invoke_test_skill(&runtime).await;
+        assert!(result.is_error);
+        assert!(
+            result.content.contains("wasi_snapshot_preview1"),
+            "got: {}",
+            result.content
+        );
+    }
+
+    /// Builds a skill whose `run` calls `http_request` on `url` with `method`,
+    /// sending `body_len` bytes of `body` (placed at offset 1024).
+    fn http_request_wat(url: &str, method: i32, body: &str, body_len: usize) -> Vec<u8> {
+        wat::parse_str(format!(
+            r#"(module
+            (import "blufio" "http_request" (func $http_request (param i32 i32 i32 i32 i32) (result i32)))
+            (func (export "run")
+                (drop (call $http_request
+                    (i32.const 0) (i32.const {url_len})
+                    (i32.const {method})
+                    (i32.const 1024) (i32.const {body_len})))
+            )
+            (memory (export "memory") 32)
+            (data (i32.const 0) "{url}")
+            (data (i32.const 1024) "{body}")
+        )"#,
+            url_len = url.len(),
+        ))
+        .unwrap()
+    }
+
+    fn localhost_manifest() -> SkillManifest {
+        let mut manifest = test_manifest();
+        manifest.capabilities.network = Some(NetworkCapability {
+            domains: vec!["localhost".to_string()],
+        });
+        manifest
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sandbox_http_request_posts_body() {
+        use wiremock::matchers::{body_string, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/submit"))
+            .and(body_string(r#"{"city":"Oslo"}"#))
+            .respond_with(ResponseTemplate::new(201).set_body_string("created"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url = format!("http://localhost:{}/submit", server.address().port());
+        let body = r#"{\22city\22:\22Oslo\22}"#;
+        let wasm = http_request_wat(&url, 1, body, r#"{"city":"Oslo"}"#.len());
+
+        // The mock listens on loopback, which only an explicit allowlist reaches.
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        runtime.set_allowed_private_ips(vec!["127.0.0.1".to_string()]);
+        runtime
+            .load_skill(localhost_manifest(), &wasm, None)
+            .unwrap();
+        let result = invoke_test_skill(&runtime).await;
+        assert!(!result.is_error, "got: {}", result.content);
+        assert_eq!(result.content, "created");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sandbox_http_request_put_and_delete_select_verb() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        for verb in ["PUT", "DELETE"] {
+            Mock::given(method(verb))
+                .and(path("/item"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(verb))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let url = format!("http://localhost:{}/item", server.address().port());
+        for (code, verb) in [(2, "PUT"), (3, "DELETE")] {
+            let mut runtime = WasmSkillRuntime::new().unwrap();
+            runtime.set_allowed_private_ips(vec!["127.0.0.1".to_string()]);
+            let wasm = http_request_wat(&url, code, "", 0);
+            runtime
+                .load_skill(localhost_manifest(), &wasm, None)
+                .unwrap();
+            let result = invoke_test_skill(&runtime).await;
+            assert_eq!(result.content, verb);
         }
     }
 
+    #[tokio::test]
+    async fn sandbox_http_request_oversized_body_traps() {
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        let wasm = http_request_wat(
+            "http://api.example.com/upload",
+            1,
+            "",
+            MAX_HTTP_BODY_BYTES + 1,
+        );
+        let mut manifest = test_manifest();
+        manifest.capabilities.network = Some(NetworkCapability {
+            domains: vec!["api.example.com".to_string()],
+        });
+        runtime.load_skill(manifest, &wasm, None).unwrap();
+
+        let result = invoke_test_skill(&runtime).await;
+        assert!(result.is_error);
+        assert!(
+            result.content.contains("exceeds the 1048576 byte limit"),
+            "got: {}",
+            result.content
+        );
+    }
+
+    #[tokio::test]
+    async fn sandbox_http_request_post_still_blocks_private_ip() {
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        let wasm = http_request_wat("http://10.0.0.1/admin", 1, "x", 1);
+        let mut manifest = test_manifest();
+        manifest.capabilities.network = Some(NetworkCapability {
+            domains: vec!["10.0.0.1".to_string()],
+        });
+        runtime.load_skill(manifest, &wasm, None).unwrap();
+
+        let result = invoke_test_skill(&runtime).await;
+        assert!(result.is_error);
+        assert!(
+            result.content.contains("SSRF blocked"),
+            "got: {}",
+            result.content
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sandbox_http_request_blocks_hostname_resolving_to_loopback() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let url = format!("http://localhost:{}/admin", server.address().port());
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        runtime
+            .load_skill(
+                localhost_manifest(),
+                &http_request_wat(&url, 1, "x", 1),
+                None,
+            )
+            .unwrap();
+
+        let result = invoke_test_skill(&runtime).await;
+        assert!(result.is_error);
+        assert!(
+            result.content.contains("SSRF blocked"),
+            "got: {}",
+            result.content
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sandbox_http_request_does_not_follow_redirects() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/start"))
+            .respond_with(
+                ResponseTemplate::new(307)
+                    .insert_header("location", "/internal")
+                    .set_body_string("redirected"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(path("/internal"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("internal"))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let url = format!("http://localhost:{}/start", server.address().port());
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        runtime.set_allowed_private_ips(vec!["127.0.0.1".to_string()]);
+        runtime
+            .load_skill(
+                localhost_manifest(),
+                &http_request_wat(&url, 1, "x", 1),
+                None,
+            )
+            .unwrap();
+
+        let result = invoke_test_skill(&runtime).await;
+        assert!(!result.is_error, "got: {}", result.content);
+        assert_eq!(result.content, "redirected");
+    }
+
+    #[tokio::test]
+    async fn sandbox_http_request_unknown_method_traps() {
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        let wasm = http_request_wat("http://api.example.com/x", 9, "", 0);
+        let mut manifest = test_manifest();
+        manifest.capabilities.network = Some(NetworkCapability {
+            domains: vec!["api.example.com".to_string()],
+        });
+        runtime.load_skill(manifest, &wasm, None).unwrap();
+
+        let result = invoke_test_skill(&runtime).await;
+        assert!(result.is_error);
+        assert!(
+            result.content.contains("unsupported http_request method 9"),
+            "got: {}",
+            result.content
+        );
+    }
+
+    /// Runs a skill that calls `set_output` with `output`, under a manifest
+    /// declaring an output schema that requires an integer `temp_c`.
+    async fn run_with_output_schema(output: &str) -> SkillResult {
+        let wat = format!(
+            r#"(module
+                (import "blufio" "set_output" (func $set_output (param i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{}")
+                (func (export "run") (call $set_output (i32.const 0) (i32.const {}))))"#,
+            output.replace('"', "\\\""),
+            output.len()
+        );
+        let mut manifest = test_manifest();
+        manifest.output_schema = Some(serde_json::json!({
+            "type": "object",
+            "required": ["temp_c"],
+            "properties": {"temp_c": {"type": "integer"}}
+        }));
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        runtime
+            .load_skill(manifest, &wat::parse_str(&wat).unwrap(), None)
+            .unwrap();
+        runtime
+            .invoke(test_invocation(serde_json::json!({})))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn conforming_output_is_returned_as_json() {
+        let result = run_with_output_schema(r#"{ "temp_c": 21 }"#).await;
+        assert!(!result.is_error, "got: {}", result.content);
+        assert_eq!(result.content, r#"{"temp_c":21}"#);
+    }
+
+    #[tokio::test]
+    async fn non_conforming_output_is_an_error() {
+        let result = run_with_output_schema(r#"{"temp_c": "warm"}"#).await;
+        assert!(result.is_error);
+        assert!(
+            result
+                .content
+                .contains("output does not match its output schema"),
+            "got: {}",
+            result.content
+        );
+        assert!(
+            result.content.contains("/temp_c"),
+            "got: {}",
+            result.content
+        );
+
+        let result = run_with_output_schema("not json").await;
+        assert!(result.is_error);
+        assert!(result.content.contains("not valid JSON"));
+    }
+
+    #[tokio::test]
+    async fn wasm_runtime_is_a_skill_runtime_adapter() {
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        let wasm = wat::parse_str(r#"(module (func (export "run")) (memory (export "memory") 1))"#)
+            .unwrap();
+        runtime.load_skill(test_manifest(), &wasm, None).unwrap();
+
+        let adapter: Arc<dyn SkillRuntimeAdapter + Send + Sync> = Arc::new(runtime);
+        assert_eq!(adapter.name(), "wasm");
+        assert_eq!(adapter.adapter_type(), AdapterType::SkillRuntime);
+        assert_eq!(adapter.list_skills().len(), 1);
+
+        let result = adapter
+            .invoke(test_invocation(serde_json::json!({})))
+            .await
+            .unwrap();
+        assert!(!result.is_error, "got: {}", result.content);
+    }
+
+    /// Component exporting `run` that echoes its input, built from core
+    /// WAT with the canonical ABI done by hand (what a bindings generator
+    /// would emit for the `skill` world).
+    const ECHO_COMPONENT: &str = r#"
+        (component
+          (core module $m
+            (memory (export "memory") 1)
+            (global $bump (mut i32) (i32.const 1024))
+            (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+              (local $p i32)
+              (local.set $p (global.get $bump))
+              (global.set $bump (i32.add (global.get $bump) (local.get 3)))
+              (local.get $p))
+            ;; result<string, string>: discriminant at 0, (ptr, len) at 4 and 8.
+            (func (export "run") (param $ptr i32) (param $len i32) (result i32)
+              (i32.store8 (i32.const 0) (i32.const 0))
+              (i32.store (i32.const 4) (local.get $ptr))
+              (i32.store (i32.const 8) (local.get $len))
+              (i32.const 0)))
+          (core instance $i (instantiate $m))
+          (func (export "run") (param "input" string) (result (result string (error string)))
+            (canon lift (core func $i "run") (memory $i "memory") (realloc (func $i "realloc")))))
+    "#;
+
+    #[tokio::test]
+    async fn component_skill_returns_output() {
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        let wasm = wat::parse_str(ECHO_COMPONENT).unwrap();
+        assert!(component::is_component(&wasm));
+        runtime.load_skill(test_manifest(), &wasm, None).unwrap();
+        assert!(runtime.has_skill("test-skill"));
+
+        let result = runtime
+            .invoke(test_invocation(serde_json::json!({"city": "Oslo"})))
+            .await
+            .unwrap();
+        assert!(!result.is_error, "got: {}", result.content);
+        assert_eq!(result.content, r#"{"city":"Oslo"}"#);
+    }
+
+    #[tokio::test]
+    async fn component_import_without_capability_is_denied() {
+        // Reads the file named by its input through the WIT host interface.
+        let wat = r#"
+            (component
+              (import "blufio:skill/host@0.1.0" (instance $host
+                (export "read-file" (func (param "path" string) (result (result string (error string)))))))
+              (core module $mem
+                (memory (export "memory") 1)
+                (global $bump (mut i32) (i32.const 1024))
+                (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+                  (local $p i32)
+                  (local.set $p (global.get $bump))
+                  (global.set $bump (i32.add (global.get $bump) (local.get 3)))
+                  (local.get $p)))
+              (core instance $memi (instantiate $mem))
+              (core func $read (canon lower (func $host "read-file")
+                (memory $memi "memory") (realloc (func $memi "realloc"))))
+              (core module $m
+                (import "host" "read-file" (func $read (param i32 i32 i32)))
+                (import "mem" "memory" (memory 1))
+                (func (export "run") (param $ptr i32) (param $len i32) (result i32)
+                  (call $read (local.get $ptr) (local.get $len) (i32.const 16))
+                  (i32.const 16)))
+              (core instance $i (instantiate $m
+                (with "host" (instance (export "read-file" (func $read))))
+                (with "mem" (instance $memi))))
+              (func (export "run") (param "input" string) (result (result string (error string)))
+                (canon lift (core func $i "run") (memory $memi "memory") (realloc (func $memi "realloc")))))
+        "#;
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        runtime
+            .load_skill(test_manifest(), &wat::parse_str(wat).unwrap(), None)
+            .unwrap();
+
+        let (result, denial) = runtime
+            .invoke_reporting_denial(test_invocation(serde_json::json!("/etc/hostname")))
+            .await
+            .unwrap();
+        assert!(result.is_error);
+        assert!(result.content.contains("capability not permitted"));
+        assert!(matches!(
+            denial,
+            Some(CapabilityRequest::FilesystemRead { .. })
+        ));
+    }
+
     // ---- Pre-execution verification tests ----
 
     #[tokio::test]
@@ -1362,12 +2522,7 @@ mod tests {
             .unwrap();
         runtime.load_skill(manifest, &wasm, None).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await.unwrap();
+        let result = invoke_test_skill(&runtime).await;
         assert!(!result.is_error, "unsigned skill should execute normally");
     }
 
@@ -1384,15 +2539,11 @@ mod tests {
             content_hash: Some(hash),
             signature: None,
             publisher_id: None,
+            status: VerificationStatus::Unsigned,
         };
         runtime.load_skill(manifest, &wasm, Some(info)).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await.unwrap();
+        let result = invoke_test_skill(&runtime).await;
         assert!(
             !result.is_error,
             "hash-verified skill should execute normally"
@@ -1413,15 +2564,11 @@ mod tests {
             ),
             signature: None,
             publisher_id: None,
+            status: VerificationStatus::Unsigned,
         };
         runtime.load_skill(manifest, &wasm, Some(info)).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await;
+        let result = runtime.invoke(test_invocation(serde_json::json!({}))).await;
         assert!(result.is_err(), "hash mismatch should block execution");
         let err_msg = format!("{}", result.unwrap_err());
         assert!(
@@ -1447,15 +2594,11 @@ mod tests {
             content_hash: Some(hash),
             signature: Some(sig_hex),
             publisher_id: Some(keypair.public_hex()),
+            status: VerificationStatus::Verified,
         };
         runtime.load_skill(manifest, &wasm, Some(info)).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await.unwrap();
+        let result = invoke_test_skill(&runtime).await;
         assert!(!result.is_error, "validly signed skill should execute");
     }
 
@@ -1477,15 +2620,11 @@ mod tests {
             content_hash: Some(hash),
             signature: Some(sig_hex),
             publisher_id: Some(keypair.public_hex()),
+            status: VerificationStatus::Verified,
         };
         runtime.load_skill(manifest, &wasm, Some(info)).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await;
+        let result = runtime.invoke(test_invocation(serde_json::json!({}))).await;
         assert!(result.is_err(), "bad signature should block execution");
         let err_msg = format!("{}", result.unwrap_err());
         assert!(
@@ -1512,15 +2651,71 @@ mod tests {
             content_hash: Some(hash),
             signature: Some(sig_hex),
             publisher_id: Some(keypair2.public_hex()), // Wrong key!
+            status: VerificationStatus::Verified,
         };
         runtime.load_skill(manifest, &wasm, Some(info)).unwrap();
 
-        let invocation = SkillInvocation {
-            skill_name: "test-skill".to_string(),
-            input: serde_json::json!({}),
-            session_id: None,
-        };
-        let result = runtime.invoke(invocation).await;
+        let result = runtime.invoke(test_invocation(serde_json::json!({}))).await;
         assert!(result.is_err(), "wrong pubkey should block execution");
     }
+
+    #[test]
+    fn strict_mode_refuses_unverified_skills() {
+        let keypair = crate::signing::PublisherKeypair::generate();
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        runtime.require_trusted_publishers(vec![keypair.public_hex()]);
+        let wasm = wat::parse_str(r#"(module (func (export "run")) (memory (export "memory") 1))"#)
+            .unwrap();
+
+        let err = runtime
+            .load_skill(test_manifest(), &wasm, None)
+            .unwrap_err();
+        assert!(err.to_string().contains("is unsigned"), "got: {err}");
+
+        // Signed by a publisher that is not trusted, though recorded as
+        // verified at install.
+        let other = crate::signing::PublisherKeypair::generate();
+        let info = VerificationInfo {
+            content_hash: Some(crate::signing::compute_content_hash(&wasm)),
+            signature: Some(crate::signing::signature_to_hex(&other.sign(&wasm))),
+            publisher_id: Some(other.public_hex()),
+            status: VerificationStatus::Verified,
+        };
+        let err = runtime
+            .load_skill(test_manifest(), &wasm, Some(info))
+            .unwrap_err();
+        assert!(err.to_string().contains("is untrusted"), "got: {err}");
+        assert!(!runtime.has_skill("test-skill"));
+
+        let info = VerificationInfo {
+            content_hash: Some(crate::signing::compute_content_hash(&wasm)),
+            signature: Some(crate::signing::signature_to_hex(&keypair.sign(&wasm))),
+            publisher_id: Some(keypair.public_hex()),
+            status: VerificationStatus::Verified,
+        };
+        runtime
+            .load_skill(test_manifest(), &wasm, Some(info))
+            .unwrap();
+        assert!(runtime.has_skill("test-skill"));
+    }
+
+    #[test]
+    fn strict_mode_refuses_publisher_removed_from_trust_store() {
+        let keypair = crate::signing::PublisherKeypair::generate();
+        let wasm = wat::parse_str(r#"(module (func (export "run")) (memory (export "memory") 1))"#)
+            .unwrap();
+        let info = VerificationInfo {
+            content_hash: Some(crate::signing::compute_content_hash(&wasm)),
+            signature: Some(crate::signing::signature_to_hex(&keypair.sign(&wasm))),
+            publisher_id: Some(keypair.public_hex()),
+            status: VerificationStatus::Verified,
+        };
+
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        runtime.require_trusted_publishers(Vec::new());
+        let err = runtime
+            .load_skill(test_manifest(), &wasm, Some(info))
+            .unwrap_err();
+        assert!(err.to_string().contains("is untrusted"), "got: {err}");
+    }
 }
@@ -84,6 +84,8 @@ pub extern "C" fn run() {{
     //   - log(level, ptr, len)       -- emit log output
     //   - get_input_len() -> i32     -- get input JSON length
     //   - get_input(ptr)             -- read input JSON into memory
+    //   - get_input_field(key_ptr, key_len, out_ptr, out_len) -> i32
+    //                                -- read one input field as JSON (-1 if missing)
     //   - set_output(ptr, len)       -- write result JSON to host
 }}
 "#,
@@ -0,0 +1,323 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Exposes loaded WASM skills as [`Tool`]s.
+//!
+//! A [`WasmSkillTool`] forwards its input as a skill invocation to
+//! [`SkillRuntimes`], which routes it to the runtime of the skill's type.
+//! Its parameters schema is the manifest's `[input] schema`,
+//! or any JSON object when none is declared. When the skill's manifest
+//! declares an output schema, the sandbox has already validated the output,
+//! so the structured JSON is passed through unchanged and the schema is
+//! advertised in the tool definition.
+//!
+//! At startup, [`load_installed_skills`] loads every skill in the
+//! [`SkillStore`] into the WASM runtime, which is then registered in
+//! [`SkillRuntimes`], and [`register_skill_tools`] adds one tool per loaded
+//! skill to the [`ToolRegistry`].
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use blufio_core::BlufioError;
+use blufio_core::types::{SkillInvocation, SkillManifest};
+use tracing::{info, warn};
+
+use crate::runtime::SkillRuntimes;
+use crate::signing::VerificationStatus;
+use crate::store::{InstalledSkill, SkillStore, VerificationInfo};
+use crate::tool::{Tool, ToolOutput, ToolRegistry};
+use crate::{WasmSkillRuntime, parse_manifest};
+
+/// A loaded WASM skill callable as a tool.
+pub struct WasmSkillTool {
+    manifest: SkillManifest,
+    tool_name: String,
+    runtimes: Arc<SkillRuntimes>,
+}
+
+impl WasmSkillTool {
+    /// Creates a tool for the skill described by `manifest`, loaded in one
+    /// of `runtimes`.
+    pub fn new(manifest: SkillManifest, runtimes: Arc<SkillRuntimes>) -> Self {
+        // Tool names allow [a-zA-Z0-9_]; skill names may contain hyphens.
+        let tool_name = manifest.name.replace('-', "_");
+        Self {
+            manifest,
+            tool_name,
+            runtimes,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for WasmSkillTool {
+    fn name(&self) -> &str {
+        &self.tool_name
+    }
+
+    fn description(&self) -> &str {
+        &self.manifest.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.manifest
+            .input_schema
+            .clone()
+            .unwrap_or_else(|| serde_json::json!({"type": "object"}))
+    }
+
+    async fn invoke(&self, input: serde_json::Value) -> Result<ToolOutput, BlufioError> {
+        let result = self
+            .runtimes
+            .invoke(SkillInvocation {
+                skill_name: self.manifest.name.clone(),
+                input,
+                session_id: None,
+            })
+            .await?;
+        Ok(ToolOutput {
+            content: result.content,
+            is_error: result.is_error,
+        })
+    }
+
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        self.manifest.output_schema.clone()
+    }
+}
+
+/// Loads every installed WASM skill from `store` into `runtime`.
+///
+/// A skill whose manifest or WASM file can no longer be read, or that the
+/// runtime refuses (for example an unverified skill in strict mode), is
+/// skipped with a warning. Returns the number of skills loaded.
+pub async fn load_installed_skills(
+    store: &SkillStore,
+    runtime: &mut WasmSkillRuntime,
+) -> Result<usize, BlufioError> {
+    let mut loaded = 0;
+    for skill in store.list().await? {
+        match load_installed_skill(&skill, runtime) {
+            Ok(true) => loaded += 1,
+            Ok(false) => {}
+            Err(e) => warn!(skill = %skill.name, error = %e, "failed to load installed skill"),
+        }
+    }
+    Ok(loaded)
+}
+
+/// Loads one installed skill. Returns false for skills of another runtime.
+fn load_installed_skill(
+    skill: &InstalledSkill,
+    runtime: &mut WasmSkillRuntime,
+) -> Result<bool, BlufioError> {
+    let manifest = parse_manifest(&skill.manifest_toml)?;
+    if manifest.runtime != "wasm" {
+        return Ok(false);
+    }
+    let wasm_bytes =
+        std::fs::read(&skill.wasm_path).map_err(BlufioError::skill_execution_failed)?;
+    let verification = VerificationInfo {
+        content_hash: skill.content_hash.clone(),
+        signature: skill.signature.clone(),
+        publisher_id: skill.publisher_id.clone(),
+        status: VerificationStatus::from_stored(&skill.verification_status),
+    };
+    runtime.load_skill(manifest, &wasm_bytes, Some(verification))?;
+    Ok(true)
+}
+
+/// Registers a [`WasmSkillTool`] for each skill loaded in `runtimes`.
+///
+/// Skills whose tool name is invalid or already taken (built-in tools win)
+/// are skipped with a warning. Returns the number of tools registered.
+pub fn register_skill_tools(registry: &mut ToolRegistry, runtimes: &Arc<SkillRuntimes>) -> usize {
+    let mut manifests = runtimes.list_skills();
+    manifests.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut registered = 0;
+    for manifest in manifests {
+        let name = manifest.name.clone();
+        match registry.register(Arc::new(WasmSkillTool::new(manifest, runtimes.clone()))) {
+            Ok(()) => registered += 1,
+            Err(e) => warn!(skill = %name, error = %e, "skipping skill tool"),
+        }
+    }
+    if registered > 0 {
+        info!(count = registered, "registered WASM skill tools");
+    }
+    registered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_manifest;
+
+    /// Wraps `runtime` as the only runtime, for skills of type "wasm".
+    fn wasm_runtimes(runtime: WasmSkillRuntime) -> Arc<SkillRuntimes> {
+        let mut runtimes = SkillRuntimes::new();
+        runtimes.register("wasm", Arc::new(runtime));
+        Arc::new(runtimes)
+    }
+
+    fn manifest() -> SkillManifest {
+        SkillManifest {
+            name: "word-count".to_string(),
+            description: "Counts words".to_string(),
+            output_schema: Some(serde_json::json!({
+                "type": "object",
+                "required": ["words"],
+                "properties": {"words": {"type": "integer"}}
+            })),
+            ..test_manifest()
+        }
+    }
+
+    #[tokio::test]
+    async fn structured_output_passes_through_and_is_advertised() {
+        let output = r#"{ "words": 3 }"#;
+        let wat = format!(
+            r#"(module
+                (import "blufio" "set_output" (func $set_output (param i32 i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{}")
+                (func (export "run") (call $set_output (i32.const 0) (i32.const {}))))"#,
+            output.replace('"', "\\\""),
+            output.len()
+        );
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        runtime
+            .load_skill(manifest(), &wat::parse_str(&wat).unwrap(), None)
+            .unwrap();
+        let tool = WasmSkillTool::new(manifest(), wasm_runtimes(runtime));
+
+        let out = tool.invoke(serde_json::json!({})).await.unwrap();
+        assert!(!out.is_error, "got: {}", out.content);
+        assert_eq!(out.content, r#"{"words":3}"#);
+
+        let mut registry = ToolRegistry::new();
+        registry.register_builtin(Arc::new(tool)).unwrap();
+        let defs = registry.tool_definitions();
+        assert_eq!(defs[0].name, "word_count");
+        assert!(defs[0].description.contains(r#""required":["words"]"#));
+    }
+
+    #[tokio::test]
+    async fn input_schema_comes_from_manifest() {
+        let runtimes = wasm_runtimes(WasmSkillRuntime::new().unwrap());
+        let tool = WasmSkillTool::new(manifest(), runtimes.clone());
+        assert_eq!(
+            tool.parameters_schema(),
+            serde_json::json!({"type": "object"})
+        );
+
+        let schema = serde_json::json!({
+            "type": "object",
+            "required": ["text"],
+            "properties": {"text": {"type": "string"}}
+        });
+        let mut with_input = manifest();
+        with_input.input_schema = Some(schema.clone());
+        let tool = WasmSkillTool::new(with_input, runtimes);
+        assert_eq!(tool.parameters_schema(), schema);
+        assert!(crate::tool::validate_tool_input(&tool, &serde_json::json!({})).is_err());
+    }
+
+    #[tokio::test]
+    async fn installed_skill_is_invokable_through_registry() {
+        let dir = tempfile::tempdir().unwrap();
+        let wasm_path = dir.path().join("echo.wasm");
+        let wasm = wat::parse_str(
+            r#"(module
+                (import "blufio" "get_input_len" (func $get_input_len (result i32)))
+                (import "blufio" "get_input" (func $get_input (param i32)))
+                (import "blufio" "set_output" (func $set_output (param i32 i32)))
+                (memory (export "memory") 1)
+                (func (export "run")
+                    (call $get_input (i32.const 0))
+                    (call $set_output (i32.const 0) (call $get_input_len))))"#,
+        )
+        .unwrap();
+        std::fs::write(&wasm_path, &wasm).unwrap();
+        let manifest_toml = r#"
+[skill]
+name = "echo-input"
+version = "0.1.0"
+description = "Echoes its input"
+
+[input.schema]
+type = "object"
+required = ["text"]
+"#;
+
+        let store = SkillStore::new(crate::store::tests::setup_db().await);
+        store
+            .install(
+                "echo-input",
+                "0.1.0",
+                "Echoes its input",
+                None,
+                wasm_path.to_str().unwrap(),
+                manifest_toml,
+                "{}",
+                Some(&crate::compute_content_hash(&wasm)),
+                None,
+                None,
+                VerificationStatus::Unsigned,
+            )
+            .await
+            .unwrap();
+
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        assert_eq!(
+            load_installed_skills(&store, &mut runtime).await.unwrap(),
+            1
+        );
+        let mut registry = ToolRegistry::new();
+        assert_eq!(
+            register_skill_tools(&mut registry, &wasm_runtimes(runtime)),
+            1
+        );
+
+        let tool = registry.get("echo_input").unwrap();
+        assert_eq!(
+            tool.parameters_schema()["required"],
+            serde_json::json!(["text"])
+        );
+        let out = crate::tool::invoke_validated(tool.as_ref(), serde_json::json!({"text": "hi"}))
+            .await
+            .unwrap();
+        assert!(!out.is_error, "got: {}", out.content);
+        assert_eq!(out.content, r#"{"text":"hi"}"#);
+    }
+
+    #[tokio::test]
+    async fn unreadable_installed_skill_is_skipped() {
+        let store = SkillStore::new(crate::store::tests::setup_db().await);
+        store
+            .install(
+                "gone",
+                "0.1.0",
+                "Missing WASM file",
+                None,
+                "/nonexistent/gone.wasm",
+                "[skill]\nname = \"gone\"\nversion = \"0.1.0\"\ndescription = \"x\"\n",
+                "{}",
+                None,
+                None,
+                None,
+                VerificationStatus::Unsigned,
+            )
+            .await
+            .unwrap();
+
+        let mut runtime = WasmSkillRuntime::new().unwrap();
+        assert_eq!(
+            load_installed_skills(&store, &mut runtime).await.unwrap(),
+            0
+        );
+        assert!(!runtime.has_skill("gone"));
+    }
+}
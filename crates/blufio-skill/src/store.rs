@@ -13,6 +13,8 @@ use blufio_core::BlufioError;
 use chrono::Utc;
 use tokio_rusqlite::Connection;
 
+use crate::signing::VerificationStatus;
+
 /// Metadata for an installed skill as stored in SQLite.
 #[derive(Debug, Clone)]
 pub struct InstalledSkill {
@@ -37,6 +39,8 @@ pub struct VerificationInfo {
     pub content_hash: Option<String>,
     pub signature: Option<String>,
     pub publisher_id: Option<String>,
+    /// Trust status recorded at install time.
+    pub status: VerificationStatus,
 }
 
 /// SQLite-backed store for installed skill metadata.
@@ -56,8 +60,9 @@ impl SkillStore {
     /// Installs or updates a skill in the registry.
     ///
     /// Uses INSERT OR REPLACE so re-installing a skill updates its metadata.
-    /// Sets `verification_status` to "verified" when a signature is present,
-    /// "unverified" otherwise.
+    /// `verification_status` is the result of
+    /// [`verify_skill_signature`](crate::signing::verify_skill_signature)
+    /// against the configured trust store.
     #[allow(clippy::too_many_arguments)]
     pub async fn install(
         &self,
@@ -71,6 +76,7 @@ impl SkillStore {
         content_hash: Option<&str>,
         signature: Option<&str>,
         publisher_id: Option<&str>,
+        verification_status: VerificationStatus,
     ) -> Result<(), BlufioError> {
         let now = Utc::now().to_rfc3339();
         let name = name.to_string();
@@ -83,11 +89,7 @@ impl SkillStore {
         let content_hash = content_hash.map(|s| s.to_string());
         let signature = signature.map(|s| s.to_string());
         let publisher_id = publisher_id.map(|s| s.to_string());
-        let verification_status = if signature.is_some() {
-            "verified".to_string()
-        } else {
-            "unverified".to_string()
-        };
+        let verification_status = verification_status.as_str();
         let now_clone = now.clone();
 
         self.conn
@@ -139,6 +141,7 @@ impl SkillStore {
         content_hash: Option<&str>,
         signature: Option<&str>,
         publisher_id: Option<&str>,
+        verification_status: VerificationStatus,
     ) -> Result<(), BlufioError> {
         // Check that the skill exists.
         let existing = self.get(name).await?;
@@ -174,10 +177,43 @@ impl SkillStore {
             content_hash,
             signature,
             publisher_id,
+            verification_status,
         )
         .await
     }
 
+    /// Replaces the stored capabilities of an installed skill, e.g. after the
+    /// user grants one at runtime. Errors if the skill does not exist.
+    pub async fn update_capabilities(
+        &self,
+        name: &str,
+        capabilities_json: &str,
+    ) -> Result<(), BlufioError> {
+        let now = Utc::now().to_rfc3339();
+        let name_owned = name.to_string();
+        let capabilities_json = capabilities_json.to_string();
+        let updated = self
+            .conn
+            .call(move |conn| {
+                let n = conn.execute(
+                    "UPDATE installed_skills SET capabilities_json = ?1, updated_at = ?2 \
+                     WHERE name = ?3",
+                    rusqlite::params![capabilities_json, now, name_owned],
+                )?;
+                Ok(n)
+            })
+            .await
+            .map_err(|e: tokio_rusqlite::Error<rusqlite::Error>| {
+                BlufioError::skill_execution_failed(e)
+            })?;
+        if updated == 0 {
+            return Err(BlufioError::skill_execution_msg(&format!(
+                "skill '{name}' not installed"
+            )));
+        }
+        Ok(())
+    }
+
     /// Removes a skill from the registry by name.
     pub async fn remove(&self, name: &str) -> Result<(), BlufioError> {
         let name = name.to_string();
@@ -242,7 +278,7 @@ impl SkillStore {
         self.conn
             .call(move |conn| {
                 let mut stmt = conn.prepare(
-                    "SELECT content_hash, signature, publisher_id \
+                    "SELECT content_hash, signature, publisher_id, verification_status \
                      FROM installed_skills WHERE name = ?1",
                 )?;
                 let result = stmt
@@ -251,6 +287,7 @@ impl SkillStore {
                             content_hash: row.get(0)?,
                             signature: row.get(1)?,
                             publisher_id: row.get(2)?,
+                            status: VerificationStatus::from_stored(&row.get::<_, String>(3)?),
                         })
                     })
                     .optional()?;
@@ -464,12 +501,12 @@ impl SkillStore {
 use rusqlite::OptionalExtension;
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
     /// Create an in-memory SQLite database with the installed_skills table
     /// including the V8 signing columns.
-    async fn setup_db() -> Arc<Connection> {
+    pub(crate) async fn setup_db() -> Arc<Connection> {
         let conn = Connection::open_in_memory().await.unwrap();
         conn.call(|conn| {
             conn.execute_batch(
@@ -520,6 +557,7 @@ mod tests {
                 None,
                 None,
                 None,
+                VerificationStatus::Unsigned,
             )
             .await
             .unwrap();
@@ -530,7 +568,7 @@ mod tests {
         assert_eq!(skill.description, "Weather lookup");
         assert_eq!(skill.author.as_deref(), Some("Test Author"));
         assert_eq!(skill.wasm_path, "/skills/weather.wasm");
-        assert_eq!(skill.verification_status, "unverified");
+        assert_eq!(skill.verification_status, "unsigned");
         assert!(skill.content_hash.is_none());
         assert!(skill.signature.is_none());
         assert!(skill.publisher_id.is_none());
@@ -553,6 +591,7 @@ mod tests {
                 Some("abc123hash"),
                 Some("sig_hex_here"),
                 Some("publisher_hex"),
+                VerificationStatus::Verified,
             )
             .await
             .unwrap();
@@ -565,7 +604,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn store_install_unsigned_sets_unverified() {
+    async fn store_install_unsigned_sets_unsigned() {
         let conn = setup_db().await;
         let store = SkillStore::new(conn);
 
@@ -581,16 +620,50 @@ mod tests {
                 Some("hash"),
                 None,
                 None,
+                VerificationStatus::Unsigned,
             )
             .await
             .unwrap();
 
         let skill = store.get("unsigned").await.unwrap().unwrap();
-        assert_eq!(skill.verification_status, "unverified");
+        assert_eq!(skill.verification_status, "unsigned");
         assert_eq!(skill.content_hash.as_deref(), Some("hash"));
         assert!(skill.signature.is_none());
     }
 
+    #[tokio::test]
+    async fn store_install_untrusted_keeps_signature() {
+        let conn = setup_db().await;
+        let store = SkillStore::new(conn);
+
+        store
+            .install(
+                "untrusted",
+                "1.0.0",
+                "Unknown publisher",
+                None,
+                "/t.wasm",
+                "",
+                "{}",
+                Some("hash"),
+                Some("sig"),
+                Some("pub"),
+                VerificationStatus::Untrusted,
+            )
+            .await
+            .unwrap();
+
+        let skill = store.get("untrusted").await.unwrap().unwrap();
+        assert_eq!(skill.verification_status, "untrusted");
+        assert_eq!(skill.signature.as_deref(), Some("sig"));
+        let info = store
+            .get_verification_info("untrusted")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.status, VerificationStatus::Untrusted);
+    }
+
     #[tokio::test]
     async fn store_install_and_list() {
         let conn = setup_db().await;
@@ -608,6 +681,7 @@ mod tests {
                 None,
                 None,
                 None,
+                VerificationStatus::Unsigned,
             )
             .await
             .unwrap();
@@ -623,6 +697,7 @@ mod tests {
                 None,
                 None,
                 None,
+                VerificationStatus::Unsigned,
             )
             .await
             .unwrap();
@@ -650,6 +725,7 @@ mod tests {
                 None,
                 None,
                 None,
+                VerificationStatus::Unsigned,
             )
             .await
             .unwrap();
@@ -691,6 +767,7 @@ mod tests {
                 None,
                 None,
                 None,
+                VerificationStatus::Unsigned,
             )
             .await
             .unwrap();
@@ -706,6 +783,7 @@ mod tests {
                 None,
                 None,
                 None,
+                VerificationStatus::Unsigned,
             )
             .await
             .unwrap();
@@ -736,6 +814,7 @@ mod tests {
                 Some("hash1"),
                 None,
                 None,
+                VerificationStatus::Unsigned,
             )
             .await
             .unwrap();
@@ -752,6 +831,7 @@ mod tests {
                 Some("hash2"),
                 None,
                 None,
+                VerificationStatus::Unsigned,
             )
             .await
             .unwrap();
@@ -774,6 +854,25 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn store_update_capabilities() {
+        let conn = setup_db().await;
+        let store = SkillStore::new(conn);
+        store
+            .install(
+                "reader", "0.1.0", "Reader", None, "/r.wasm", "", "{}", None, None, None,
+            )
+            .await
+            .unwrap();
+
+        let caps = r#"{"filesystem":{"read":["/data"],"write":[]}}"#;
+        store.update_capabilities("reader", caps).await.unwrap();
+        let skill = store.get("reader").await.unwrap().unwrap();
+        assert_eq!(skill.capabilities_json, caps);
+
+        assert!(store.update_capabilities("missing", "{}").await.is_err());
+    }
+
     #[tokio::test]
     async fn store_update_publisher_continuity() {
         let conn = setup_db().await;
@@ -792,6 +891,7 @@ mod tests {
                 Some("h"),
                 Some("sig"),
                 Some("pub_a"),
+                VerificationStatus::Verified,
             )
             .await
             .unwrap();
@@ -809,6 +909,7 @@ mod tests {
                 Some("h2"),
                 Some("sig2"),
                 Some("pub_b"),
+                VerificationStatus::Verified,
             )
             .await;
         assert!(result.is_err());
@@ -826,6 +927,7 @@ mod tests {
                 Some("h2"),
                 Some("sig2"),
                 Some("pub_a"),
+                VerificationStatus::Verified,
             )
             .await
             .unwrap();
@@ -848,6 +950,7 @@ mod tests {
                 Some("hash1"),
                 Some("sig1"),
                 Some("pub1"),
+                VerificationStatus::Verified,
             )
             .await
             .unwrap();
@@ -856,6 +959,7 @@ mod tests {
         assert_eq!(info.content_hash.as_deref(), Some("hash1"));
         assert_eq!(info.signature.as_deref(), Some("sig1"));
         assert_eq!(info.publisher_id.as_deref(), Some("pub1"));
+        assert_eq!(info.status, VerificationStatus::Verified);
 
         assert!(
             store
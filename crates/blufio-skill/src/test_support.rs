@@ -0,0 +1,29 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Fixtures shared by the unit tests of this crate.
+
+use blufio_core::types::{SkillManifest, SkillResources};
+
+/// A WASM skill manifest named `test-skill` with no capabilities, for tests
+/// to adjust.
+pub(crate) fn test_manifest() -> SkillManifest {
+    SkillManifest {
+        name: "test-skill".to_string(),
+        version: "0.1.0".to_string(),
+        description: "A test skill".to_string(),
+        author: None,
+        capabilities: Default::default(),
+        resources: SkillResources {
+            fuel: 1_000_000_000,
+            memory_mb: 16,
+            epoch_timeout_secs: 5,
+        },
+        wasm_entry: "skill.wasm".to_string(),
+        runtime: "wasm".to_string(),
+        input_schema: None,
+        output_schema: None,
+        signature: None,
+        wasi: false,
+    }
+}
@@ -67,6 +67,99 @@ pub trait Tool: Send + Sync {
     fn is_open_world(&self) -> bool {
         true
     }
+
+    /// Indicates this tool may run concurrently with other tool calls of the
+    /// same turn. Default: true (tools that mutate shared state should opt out).
+    fn is_parallel_safe(&self) -> bool {
+        true
+    }
+
+    /// Whether inputs are checked against [`parameters_schema`](Self::parameters_schema)
+    /// before `invoke`. Default: true (tools with loose schemas can opt out).
+    fn validates_input(&self) -> bool {
+        true
+    }
+
+    /// JSON Schema the tool's output conforms to, if it returns structured
+    /// output. Advertised to the LLM alongside the description.
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        None
+    }
+}
+
+/// One way in which a value fails its JSON Schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// JSON pointer to the offending value (empty for the root).
+    pub path: String,
+    /// Human-readable description of the failure.
+    pub message: String,
+}
+
+/// Checks `value` against `schema`, returning every violation in order.
+///
+/// Fails with a description of the problem if `schema` does not compile.
+pub fn schema_violations(
+    schema: &serde_json::Value,
+    value: &serde_json::Value,
+) -> Result<Vec<SchemaViolation>, String> {
+    let validator = jsonschema::validator_for(schema).map_err(|e| e.to_string())?;
+    Ok(validator
+        .iter_errors(value)
+        .map(|e| SchemaViolation {
+            path: e.instance_path.to_string(),
+            message: e.to_string(),
+        })
+        .collect())
+}
+
+/// Checks `input` against the tool's parameter schema.
+///
+/// On failure, returns an error [`ToolOutput`] whose content is JSON listing
+/// each violation with its JSON-pointer path, so the LLM can correct the call:
+/// `{"error": "invalid_input", "tool": "...", "violations": [{"path": "/a", "message": "..."}]}`.
+/// Tools that opt out via [`Tool::validates_input`], and tools whose schema
+/// does not compile, are not checked.
+pub fn validate_tool_input(tool: &dyn Tool, input: &serde_json::Value) -> Result<(), ToolOutput> {
+    if !tool.validates_input() {
+        return Ok(());
+    }
+    let violations = match schema_violations(&tool.parameters_schema(), input) {
+        Ok(violations) => violations,
+        Err(e) => {
+            tracing::warn!(tool = tool.name(), error = %e, "tool schema does not compile; skipping input validation");
+            return Ok(());
+        }
+    };
+    if violations.is_empty() {
+        return Ok(());
+    }
+    let violations: Vec<serde_json::Value> = violations
+        .into_iter()
+        .map(|v| serde_json::json!({"path": v.path, "message": v.message}))
+        .collect();
+    Err(ToolOutput {
+        content: serde_json::json!({
+            "error": "invalid_input",
+            "tool": tool.name(),
+            "violations": violations,
+        })
+        .to_string(),
+        is_error: true,
+    })
+}
+
+/// Validates `input` (see [`validate_tool_input`]) and invokes the tool.
+///
+/// Invalid input is returned as an error output without calling `invoke`.
+pub async fn invoke_validated(
+    tool: &dyn Tool,
+    input: serde_json::Value,
+) -> Result<ToolOutput, BlufioError> {
+    if let Err(output) = validate_tool_input(tool, &input) {
+        return Ok(output);
+    }
+    tool.invoke(input).await
 }
 
 /// Regex for valid flat tool names: letter followed by letters/digits/underscores.
@@ -112,6 +205,7 @@ pub fn validate_namespaced_tool_name(name: &str) -> bool {
 pub struct ToolRegistry {
     tools: HashMap<String, Arc<dyn Tool>>,
     builtin_names: HashSet<String>,
+    dry_run: bool,
 }
 
 impl ToolRegistry {
@@ -120,9 +214,23 @@ impl ToolRegistry {
         Self {
             tools: HashMap::new(),
             builtin_names: HashSet::new(),
+            dry_run: false,
         }
     }
 
+    /// Enables or disables dry-run mode for tools registered afterwards.
+    ///
+    /// In dry-run mode, side-effecting built-ins such as `bash` report what
+    /// they would do instead of doing it.
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    /// Returns whether dry-run mode is enabled.
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
     /// Registers a tool with name validation.
     ///
     /// This is the backward-compatible entry point. It validates the tool
@@ -238,7 +346,13 @@ impl ToolRegistry {
             .iter()
             .map(|(registry_name, t)| blufio_core::types::ToolDefinition {
                 name: registry_name.clone(),
-                description: t.description().to_string(),
+                description: match t.output_schema() {
+                    Some(schema) => format!(
+                        "{}\n\nReturns JSON matching this schema: {schema}",
+                        t.description()
+                    ),
+                    None => t.description().to_string(),
+                },
                 input_schema: t.parameters_schema(),
             })
             .collect();
@@ -694,6 +808,43 @@ mod tests {
         assert_eq!(defs[1].name, "github__add");
     }
 
+    #[test]
+    fn tool_definitions_advertise_output_schema() {
+        struct StructuredTool;
+
+        #[async_trait]
+        impl Tool for StructuredTool {
+            fn name(&self) -> &str {
+                "structured"
+            }
+            fn description(&self) -> &str {
+                "Returns a count"
+            }
+            fn parameters_schema(&self) -> serde_json::Value {
+                serde_json::json!({"type": "object"})
+            }
+            async fn invoke(&self, _input: serde_json::Value) -> Result<ToolOutput, BlufioError> {
+                Ok(ToolOutput {
+                    content: r#"{"count":1}"#.to_string(),
+                    is_error: false,
+                })
+            }
+            fn output_schema(&self) -> Option<serde_json::Value> {
+                Some(serde_json::json!({"type": "object"}))
+            }
+        }
+
+        let mut registry = ToolRegistry::new();
+        registry.register_builtin(Arc::new(StructuredTool)).unwrap();
+        registry.register_builtin(Arc::new(EchoTool)).unwrap();
+        let defs = registry.tool_definitions();
+        assert_eq!(defs[0].description, "Echoes the input back");
+        assert_eq!(
+            defs[1].description,
+            "Returns a count\n\nReturns JSON matching this schema: {\"type\":\"object\"}"
+        );
+    }
+
     // ── Unregister tests ─────────────────────────────────────────────
 
     #[test]
@@ -739,4 +890,76 @@ mod tests {
         registry.register(Arc::new(EchoTool)).unwrap();
         assert!(registry.get("echo").is_some());
     }
+
+    /// Tool with a loose schema that opts out of validation.
+    struct UncheckedTool;
+
+    #[async_trait]
+    impl Tool for UncheckedTool {
+        fn name(&self) -> &str {
+            "unchecked"
+        }
+        fn description(&self) -> &str {
+            "Accepts anything"
+        }
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({"type": "object", "required": ["x"]})
+        }
+        async fn invoke(&self, _: serde_json::Value) -> Result<ToolOutput, BlufioError> {
+            Ok(ToolOutput {
+                content: "ran".to_string(),
+                is_error: false,
+            })
+        }
+        fn validates_input(&self) -> bool {
+            false
+        }
+    }
+
+    fn violations(output: &ToolOutput) -> Vec<serde_json::Value> {
+        let json: serde_json::Value = serde_json::from_str(&output.content).unwrap();
+        assert_eq!(json["error"], "invalid_input");
+        json["violations"].as_array().unwrap().clone()
+    }
+
+    #[tokio::test]
+    async fn missing_required_field_is_rejected_before_invoke() {
+        let output = invoke_validated(&EchoTool, serde_json::json!({}))
+            .await
+            .unwrap();
+        assert!(output.is_error);
+        let violations = violations(&output);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0]["path"], "");
+        assert!(
+            violations[0]["message"]
+                .as_str()
+                .unwrap()
+                .contains("message")
+        );
+    }
+
+    #[tokio::test]
+    async fn wrong_type_reports_field_path() {
+        let output = invoke_validated(&AddTool, serde_json::json!({"a": 1, "b": "two"}))
+            .await
+            .unwrap();
+        assert!(output.is_error);
+        let violations = violations(&output);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0]["path"], "/b");
+    }
+
+    #[tokio::test]
+    async fn valid_input_and_opted_out_tools_are_invoked() {
+        let output = invoke_validated(&AddTool, serde_json::json!({"a": 1, "b": 2}))
+            .await
+            .unwrap();
+        assert_eq!(output.content, "3");
+
+        let output = invoke_validated(&UncheckedTool, serde_json::json!({}))
+            .await
+            .unwrap();
+        assert_eq!(output.content, "ran");
+    }
 }
@@ -0,0 +1,195 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! WASI preview1 support for skills built for `wasm32-wasip1`.
+//!
+//! A core-module skill that sets `[wasm] wasi = true` in its manifest gets the
+//! standard `wasi_snapshot_preview1` imports alongside the `blufio` host ABI,
+//! so ordinary Rust or TinyGo programs run without hand-written bindings.
+//!
+//! The WASI context is virtualized per invocation:
+//! - stdin yields the input JSON and stdout becomes the skill output
+//! - only directories declared under `[capabilities.filesystem]` are
+//!   preopened, readable only if listed under `read` and writable only if
+//!   listed under `write`
+//! - only env vars declared under `[capabilities] env` are visible
+//! - there is no network access
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use blufio_core::BlufioError;
+use blufio_core::types::SkillManifest;
+use tracing::warn;
+use wasmtime::Linker;
+use wasmtime_wasi::p1::WasiP1Ctx;
+use wasmtime_wasi::p2::pipe::{MemoryInputPipe, MemoryOutputPipe};
+use wasmtime_wasi::{DirPerms, FilePerms, I32Exit, WasiCtxBuilder};
+
+use crate::sandbox::SkillState;
+
+/// Most bytes a WASI skill may write to stdout.
+pub(crate) const MAX_STDOUT_BYTES: usize = 1024 * 1024;
+
+/// Access granted to one preopened directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct Preopen {
+    pub(crate) read: bool,
+    pub(crate) write: bool,
+}
+
+/// Directories to preopen for `manifest`, keyed by path. A path listed under
+/// both `read` and `write` is preopened once with both.
+pub(crate) fn preopens(manifest: &SkillManifest) -> BTreeMap<String, Preopen> {
+    let mut dirs: BTreeMap<String, Preopen> = BTreeMap::new();
+    if let Some(ref fs) = manifest.capabilities.filesystem {
+        for path in &fs.read {
+            dirs.entry(path.clone()).or_default().read = true;
+        }
+        for path in &fs.write {
+            dirs.entry(path.clone()).or_default().write = true;
+        }
+    }
+    dirs
+}
+
+/// WASI permissions for a preopen with `access`.
+///
+/// Read and write are granted independently, so a write-only directory
+/// cannot be read back.
+fn perms(access: Preopen) -> (DirPerms, FilePerms) {
+    let mut dir_perms = DirPerms::empty();
+    let mut file_perms = FilePerms::empty();
+    if access.read {
+        dir_perms |= DirPerms::READ;
+        file_perms |= FilePerms::READ;
+    }
+    if access.write {
+        dir_perms |= DirPerms::MUTATE;
+        file_perms |= FilePerms::WRITE;
+    }
+    (dir_perms, file_perms)
+}
+
+/// Builds the WASI context for one invocation of `manifest`.
+///
+/// Skills without `wasi = true` get an empty context that is never linked.
+pub(crate) fn build_ctx(
+    manifest: &SkillManifest,
+    input_json: &str,
+    stdout: &MemoryOutputPipe,
+) -> Result<WasiP1Ctx, BlufioError> {
+    let mut builder = WasiCtxBuilder::new();
+    if !manifest.wasi {
+        return Ok(builder.build_p1());
+    }
+
+    builder
+        .stdin(MemoryInputPipe::new(input_json.to_string()))
+        .stdout(stdout.clone());
+
+    for key in &manifest.capabilities.env {
+        if let Ok(value) = std::env::var(key) {
+            builder.env(key, value);
+        }
+    }
+
+    for (path, access) in preopens(manifest) {
+        if !Path::new(&path).is_dir() {
+            warn!(skill = %manifest.name, path = %path, "declared filesystem path is not a directory, not preopened");
+            continue;
+        }
+        let (dir_perms, file_perms) = perms(access);
+        builder
+            .preopened_dir(&path, &path, dir_perms, file_perms)
+            .map_err(|e| {
+                BlufioError::skill_execution_msg(&format!(
+                    "skill '{}': failed to preopen '{path}': {e}",
+                    manifest.name
+                ))
+            })?;
+    }
+
+    Ok(builder.build_p1())
+}
+
+/// Links the `wasi_snapshot_preview1` imports into `linker`.
+pub(crate) fn add_to_linker(linker: &mut Linker<SkillState>) -> Result<(), BlufioError> {
+    wasmtime_wasi::p1::add_to_linker_sync(linker, |state: &mut SkillState| &mut state.wasi).map_err(
+        |e| BlufioError::skill_compilation_msg(&format!("failed to link WASI preview1: {e}")),
+    )
+}
+
+/// The exit code if `error` is a WASI `proc_exit`.
+pub(crate) fn exit_code(error: &anyhow::Error) -> Option<i32> {
+    error.downcast_ref::<I32Exit>().map(|exit| exit.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::test_manifest;
+    use blufio_core::types::FilesystemCapability;
+
+    #[test]
+    fn perms_grant_read_and_write_independently() {
+        let read_only = perms(Preopen {
+            read: true,
+            write: false,
+        });
+        assert_eq!(read_only, (DirPerms::READ, FilePerms::READ));
+
+        let write_only = perms(Preopen {
+            read: false,
+            write: true,
+        });
+        assert_eq!(write_only, (DirPerms::MUTATE, FilePerms::WRITE));
+
+        let both = perms(Preopen {
+            read: true,
+            write: true,
+        });
+        assert_eq!(both, (DirPerms::all(), FilePerms::all()));
+    }
+
+    #[test]
+    fn preopens_merge_read_and_write_paths() {
+        let mut manifest = SkillManifest {
+            wasi: true,
+            ..test_manifest()
+        };
+        assert!(preopens(&manifest).is_empty());
+
+        manifest.capabilities.filesystem = Some(FilesystemCapability {
+            read: vec!["/data".to_string(), "/shared".to_string()],
+            write: vec!["/shared".to_string(), "/out".to_string()],
+        });
+        let dirs = preopens(&manifest);
+        assert_eq!(
+            dirs.into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    "/data".to_string(),
+                    Preopen {
+                        read: true,
+                        write: false
+                    }
+                ),
+                (
+                    "/out".to_string(),
+                    Preopen {
+                        read: false,
+                        write: true
+                    }
+                ),
+                (
+                    "/shared".to_string(),
+                    Preopen {
+                        read: true,
+                        write: true
+                    }
+                ),
+            ]
+        );
+    }
+}
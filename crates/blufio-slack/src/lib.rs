@@ -592,6 +592,7 @@ mod tests {
             reply_to: None,
             parse_mode: None,
             metadata: Some(r#"{"chat_id":"C123456789"}"#.into()),
+            idempotency_key: None,
         };
         let id = extract_channel_id(&msg).unwrap();
         assert_eq!(id.to_string(), "C123456789");
@@ -606,6 +607,7 @@ mod tests {
             reply_to: None,
             parse_mode: None,
             metadata: None,
+            idempotency_key: None,
         };
         let id = extract_channel_id(&msg).unwrap();
         assert_eq!(id.to_string(), "C123456789");
@@ -620,6 +622,7 @@ mod tests {
             reply_to: None,
             parse_mode: None,
             metadata: None,
+            idempotency_key: None,
         };
         let id = extract_channel_id(&msg).unwrap();
         assert_eq!(id.to_string(), "D123456789");
@@ -634,6 +637,7 @@ mod tests {
             reply_to: None,
             parse_mode: None,
             metadata: None,
+            idempotency_key: None,
         };
         assert!(extract_channel_id(&msg).is_err());
     }
@@ -0,0 +1,12 @@
+-- V16: Durable tool-loop state for crash recovery.
+
+-- One row per session with a tool iteration in flight. Written before tools
+-- execute and cleared when the turn completes, so a row left behind after a
+-- restart marks a tool loop that was interrupted.
+CREATE TABLE IF NOT EXISTS tool_loop_state (
+    session_id TEXT PRIMARY KEY NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    iteration INTEGER NOT NULL,
+    pending_tool_uses TEXT NOT NULL, -- JSON array of {id, name, input}
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
@@ -0,0 +1,21 @@
+-- V18: Source references for merged memories.
+
+-- Each row records an additional origin of a memory whose near-duplicates
+-- were merged into it. The memory's own source and session_id stay on the
+-- memories row.
+CREATE TABLE IF NOT EXISTS memory_sources (
+    memory_id TEXT NOT NULL,
+    source TEXT NOT NULL,
+    session_id TEXT,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+-- One row per (memory, source, session). A plain UNIQUE would let rows with
+-- a NULL session_id repeat, since NULLs never compare equal.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_memory_sources_unique
+    ON memory_sources(memory_id, source, COALESCE(session_id, ''));
+
+-- Drop a memory's source references when the memory row is hard-deleted.
+CREATE TRIGGER IF NOT EXISTS memory_sources_ad AFTER DELETE ON memories BEGIN
+    DELETE FROM memory_sources WHERE memory_id = old.id;
+END;
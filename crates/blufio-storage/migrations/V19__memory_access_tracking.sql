@@ -0,0 +1,15 @@
+-- V19: Access tracking for memory decay and archival.
+
+-- Retrieval bumps these; never-retrieved memories keep last_accessed_at NULL.
+ALTER TABLE memories ADD COLUMN last_accessed_at TEXT;
+ALTER TABLE memories ADD COLUMN access_count INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_memories_last_accessed ON memories(last_accessed_at);
+
+-- Only content is indexed, so access bookkeeping must not rewrite FTS rows.
+DROP TRIGGER IF EXISTS memories_au;
+CREATE TRIGGER IF NOT EXISTS memories_au AFTER UPDATE OF content ON memories BEGIN
+    INSERT INTO memories_fts(memories_fts, rowid, content)
+        VALUES('delete', old.rowid, old.content);
+    INSERT INTO memories_fts(rowid, content) VALUES (new.rowid, new.content);
+END;
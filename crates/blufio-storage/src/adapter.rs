@@ -7,21 +7,49 @@ use async_trait::async_trait;
 use tokio::sync::OnceCell;
 use tracing::debug;
 
-use blufio_config::model::StorageConfig;
-use blufio_core::types::{Message, QueueEntry, Session};
+use blufio_config::model::{RetentionConfig, StorageConfig};
+use blufio_core::types::{
+    Message, PurgeReport, QueueEntry, QueueRecovery, Session, ToolLoopState, ToolUseData,
+};
 use blufio_core::{AdapterType, BlufioError, HealthStatus, PluginAdapter, StorageAdapter};
 
 use crate::database::Database;
 use crate::queries;
+use crate::read_pool::ReadPool;
+
+/// On-disk size of the database around a [`SqliteStorage::maintain`] run.
+///
+/// Sizes include the `-wal` file, so reclaimed WAL space is counted too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaintenanceReport {
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+impl MaintenanceReport {
+    /// Bytes freed by the run (0 if the database grew meanwhile).
+    pub fn bytes_reclaimed(&self) -> u64 {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+}
+
+/// Size of the database file plus its WAL; missing files count as 0.
+fn on_disk_size(path: &str) -> u64 {
+    let size = |p: &str| std::fs::metadata(p).map(|m| m.len()).unwrap_or(0);
+    size(path) + size(&format!("{path}-wal"))
+}
 
 /// SQLite-backed storage adapter.
 ///
 /// Wraps a [`Database`] handle and delegates all query operations to the
 /// typed query modules. The database is lazily initialized on the first
-/// call to [`StorageAdapter::initialize`].
+/// call to [`StorageAdapter::initialize`]. Session and message reads go
+/// through a [`ReadPool`] when `read_pool_size` is non-zero.
 pub struct SqliteStorage {
     config: StorageConfig,
+    retention: Option<RetentionConfig>,
     db: OnceCell<Database>,
+    readers: OnceCell<ReadPool>,
 }
 
 impl SqliteStorage {
@@ -31,7 +59,62 @@ impl SqliteStorage {
     pub fn new(config: StorageConfig) -> Self {
         Self {
             config,
+            retention: None,
             db: OnceCell::new(),
+            readers: OnceCell::new(),
+        }
+    }
+
+    /// Set the retention policy applied by
+    /// [`purge_expired`](StorageAdapter::purge_expired).
+    ///
+    /// Without one, `purge_expired` leaves every row in place.
+    pub fn with_retention(mut self, retention: RetentionConfig) -> Self {
+        self.retention = Some(retention);
+        self
+    }
+
+    /// Checkpoint the WAL into the main database file and truncate it.
+    ///
+    /// Used by the periodic checkpoint task in `blufio serve` to keep the WAL
+    /// from growing between SQLite's own auto-checkpoints.
+    pub async fn checkpoint(&self) -> Result<(), BlufioError> {
+        self.db()?.checkpoint().await?;
+        debug!("periodic WAL checkpoint complete");
+        Ok(())
+    }
+
+    /// Checkpoint the WAL, VACUUM, then checkpoint again.
+    ///
+    /// Reclaims space left by deletes and retention purges. VACUUM rewrites
+    /// the whole file and blocks other writers while it runs, so this is
+    /// meant for `blufio maintain` or a rare scheduled task.
+    pub async fn maintain(&self) -> Result<MaintenanceReport, BlufioError> {
+        let db = self.db()?;
+        let bytes_before = on_disk_size(&self.config.database_path);
+        db.checkpoint().await?;
+        db.vacuum().await?;
+        db.checkpoint().await?;
+        let report = MaintenanceReport {
+            bytes_before,
+            bytes_after: on_disk_size(&self.config.database_path),
+        };
+        debug!(
+            bytes_before = report.bytes_before,
+            bytes_after = report.bytes_after,
+            "database maintenance complete"
+        );
+        Ok(report)
+    }
+
+    /// Handle for reads that need not see in-flight writes.
+    ///
+    /// A read-only handle from the pool when one is configured, otherwise the
+    /// writer. Committed writes are always visible.
+    pub fn reader(&self) -> Result<&Database, BlufioError> {
+        match self.readers.get() {
+            Some(pool) => Ok(pool.get()),
+            None => self.db(),
         }
     }
 
@@ -93,12 +176,21 @@ impl StorageAdapter for SqliteStorage {
     async fn initialize(&self) -> Result<(), BlufioError> {
         let path = self.config.database_path.clone();
         let db = Database::open(&path).await?;
+        if self.config.wal_mode {
+            db.set_wal_autocheckpoint(self.config.wal_autocheckpoint_pages)
+                .await?;
+        }
         self.db.set(db).map_err(|_| {
             BlufioError::storage_connection_failed(std::io::Error::new(
                 std::io::ErrorKind::AlreadyExists,
                 "storage already initialized",
             ))
         })?;
+        // Readers only help under WAL; in rollback mode they block the writer.
+        if self.config.wal_mode && self.config.read_pool_size > 0 {
+            let pool = ReadPool::open(&path, self.config.read_pool_size).await?;
+            let _ = self.readers.set(pool);
+        }
         debug!(path = %self.config.database_path, "SQLite storage initialized");
         Ok(())
     }
@@ -124,29 +216,58 @@ impl StorageAdapter for SqliteStorage {
     }
 
     async fn get_session(&self, id: &str) -> Result<Option<Session>, BlufioError> {
-        queries::sessions::get_session(self.db()?, id).await
+        queries::sessions::get_session(self.reader()?, id).await
     }
 
     async fn list_sessions(&self, state: Option<&str>) -> Result<Vec<Session>, BlufioError> {
-        queries::sessions::list_sessions(self.db()?, state).await
+        queries::sessions::list_sessions(self.reader()?, state).await
+    }
+
+    async fn create_session_with_messages(
+        &self,
+        session: &Session,
+        messages: &[Message],
+    ) -> Result<(), BlufioError> {
+        queries::sessions::create_session_with_messages(self.db()?, session, messages).await
     }
 
     async fn update_session_state(&self, id: &str, state: &str) -> Result<(), BlufioError> {
         queries::sessions::update_session_state(self.db()?, id, state).await
     }
 
+    async fn update_session_metadata(
+        &self,
+        id: &str,
+        metadata: Option<&str>,
+    ) -> Result<(), BlufioError> {
+        queries::sessions::update_session_metadata(self.db()?, id, metadata).await
+    }
+
     // --- Message operations ---
 
     async fn insert_message(&self, message: &Message) -> Result<(), BlufioError> {
         queries::messages::insert_message(self.db()?, message).await
     }
 
+    async fn insert_messages(&self, messages: &[Message]) -> Result<(), BlufioError> {
+        queries::messages::insert_messages(self.db()?, messages).await
+    }
+
     async fn get_messages(
         &self,
         session_id: &str,
         limit: Option<i64>,
     ) -> Result<Vec<Message>, BlufioError> {
-        queries::messages::get_messages_for_session(self.db()?, session_id, limit).await
+        queries::messages::get_messages_for_session(self.reader()?, session_id, limit).await
+    }
+
+    async fn get_messages_page(
+        &self,
+        session_id: &str,
+        after_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Message>, BlufioError> {
+        queries::messages::get_messages_page(self.reader()?, session_id, after_id, limit).await
     }
 
     async fn delete_messages_by_ids(
@@ -157,6 +278,23 @@ impl StorageAdapter for SqliteStorage {
         queries::messages::delete_messages_by_ids(self.db()?, session_id, message_ids).await
     }
 
+    async fn search_messages(
+        &self,
+        session_id: Option<&str>,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<Message>, BlufioError> {
+        queries::messages::search_messages(self.reader()?, session_id, query, limit).await
+    }
+
+    async fn purge_expired(&self, now: &str) -> Result<PurgeReport, BlufioError> {
+        let db = self.db()?;
+        match &self.retention {
+            Some(policy) => queries::retention::purge_expired(db, policy, now).await,
+            None => Ok(PurgeReport::default()),
+        }
+    }
+
     // --- Queue operations ---
 
     async fn enqueue(&self, queue_name: &str, payload: &str) -> Result<i64, BlufioError> {
@@ -175,6 +313,35 @@ impl StorageAdapter for SqliteStorage {
         queries::queue::fail(self.db()?, id).await
     }
 
+    async fn recover_in_flight_queue(&self) -> Result<QueueRecovery, BlufioError> {
+        queries::queue::recover_in_flight(self.db()?).await
+    }
+
+    // --- Tool-loop state operations ---
+
+    async fn save_tool_loop_state(
+        &self,
+        session_id: &str,
+        iteration: u32,
+        pending_tool_uses: &[ToolUseData],
+    ) -> Result<(), BlufioError> {
+        queries::tool_loop::save_tool_loop_state(
+            self.db()?,
+            session_id,
+            iteration,
+            pending_tool_uses,
+        )
+        .await
+    }
+
+    async fn clear_tool_loop_state(&self, session_id: &str) -> Result<(), BlufioError> {
+        queries::tool_loop::clear_tool_loop_state(self.db()?, session_id).await
+    }
+
+    async fn list_tool_loop_states(&self) -> Result<Vec<ToolLoopState>, BlufioError> {
+        queries::tool_loop::list_tool_loop_states(self.db()?).await
+    }
+
     // --- Classification operations ---
 
     async fn get_entity_classification(
@@ -245,6 +412,7 @@ mod tests {
         StorageConfig {
             database_path: path.to_string(),
             wal_mode: true,
+            ..Default::default()
         }
     }
 
@@ -280,6 +448,124 @@ mod tests {
         assert!(result.is_err(), "second initialize should fail");
     }
 
+    #[tokio::test]
+    async fn initialize_applies_configured_wal_autocheckpoint() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("autockpt.db");
+        let storage = SqliteStorage::new(StorageConfig {
+            wal_autocheckpoint_pages: 250,
+            ..make_config(db_path.to_str().unwrap())
+        });
+        storage.initialize().await.unwrap();
+
+        let pages: i64 = storage
+            .db()
+            .unwrap()
+            .connection()
+            .call(|conn| conn.query_row("PRAGMA wal_autocheckpoint;", [], |row| row.get(0)))
+            .await
+            .unwrap();
+        assert_eq!(pages, 250);
+    }
+
+    #[tokio::test]
+    async fn checkpoint_truncates_wal_after_writes() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("ckpt.db");
+        let wal_path = dir.path().join("ckpt.db-wal");
+        let storage = SqliteStorage::new(StorageConfig {
+            wal_autocheckpoint_pages: 0,
+            ..make_config(db_path.to_str().unwrap())
+        });
+        storage.initialize().await.unwrap();
+
+        for i in 0..50 {
+            storage
+                .enqueue(
+                    "ckpt",
+                    &format!("{{\"payload\":\"{}\"}}", "x".repeat(1024 + i)),
+                )
+                .await
+                .unwrap();
+        }
+        let before = std::fs::metadata(&wal_path).unwrap().len();
+        assert!(before > 0, "writes should accumulate in the WAL");
+
+        storage.checkpoint().await.unwrap();
+        let after = std::fs::metadata(&wal_path).map(|m| m.len()).unwrap_or(0);
+        assert!(
+            after < before,
+            "checkpoint should shrink the WAL ({before} -> {after})"
+        );
+    }
+
+    #[tokio::test]
+    async fn maintain_reports_space_reclaimed_after_deletes() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("maintain.db");
+        let storage = SqliteStorage::new(make_config(db_path.to_str().unwrap()));
+        storage.initialize().await.unwrap();
+
+        for _ in 0..200 {
+            storage.enqueue("bulk", &"x".repeat(4096)).await.unwrap();
+        }
+        storage
+            .db()
+            .unwrap()
+            .connection()
+            .call(|conn| conn.execute("DELETE FROM queue", []))
+            .await
+            .unwrap();
+
+        let report = storage.maintain().await.unwrap();
+        assert!(report.bytes_after < report.bytes_before, "{report:?}");
+        assert_eq!(
+            report.bytes_reclaimed(),
+            report.bytes_before - report.bytes_after
+        );
+        assert_eq!(
+            report.bytes_after,
+            std::fs::metadata(&db_path).unwrap().len()
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_use_read_only_pool_and_see_committed_writes() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("readers.db");
+        let storage = SqliteStorage::new(make_config(db_path.to_str().unwrap()));
+        storage.initialize().await.unwrap();
+        assert!(storage.reader().unwrap().is_read_only());
+
+        storage
+            .create_session(&Session {
+                id: "s1".to_string(),
+                channel: "cli".to_string(),
+                user_id: None,
+                state: "active".to_string(),
+                metadata: None,
+                created_at: "2026-01-01T00:00:00Z".to_string(),
+                updated_at: "2026-01-01T00:00:00Z".to_string(),
+                classification: Default::default(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(storage.list_sessions(None).await.unwrap().len(), 1);
+        assert!(storage.get_session("s1").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn zero_read_pool_reads_through_writer() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("no-readers.db");
+        let storage = SqliteStorage::new(StorageConfig {
+            read_pool_size: 0,
+            ..make_config(db_path.to_str().unwrap())
+        });
+        storage.initialize().await.unwrap();
+        assert!(!storage.reader().unwrap().is_read_only());
+    }
+
     #[tokio::test]
     async fn health_check_returns_healthy_when_initialized() {
         let dir = tempdir().unwrap();
@@ -87,6 +87,21 @@ fn ensure_parent_dirs(path: &str) -> Result<(), BlufioError> {
     Ok(())
 }
 
+/// WAL auto-checkpoint threshold applied by [`open_connection`], once set.
+static WAL_AUTOCHECKPOINT_PAGES: std::sync::Mutex<Option<u32>> = std::sync::Mutex::new(None);
+
+/// Set the WAL page count at which every connection later opened by
+/// [`open_connection`] checkpoints automatically (0 disables it).
+///
+/// `wal_autocheckpoint` is per connection, so setting it on the main handle
+/// alone would leave the memory, vault, cron and other connections to the same
+/// file checkpointing at SQLite's default. Call this before opening any.
+pub fn set_wal_autocheckpoint_pages(pages: u32) {
+    *WAL_AUTOCHECKPOINT_PAGES
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(pages);
+}
+
 /// Open an async tokio-rusqlite connection with optional SQLCipher encryption.
 ///
 /// When `BLUFIO_DB_KEY` is set, `PRAGMA key` is applied as the **first** statement
@@ -94,6 +109,9 @@ fn ensure_parent_dirs(path: &str) -> Result<(), BlufioError> {
 /// plain-text mode -- but if the file already exists and is encrypted, a hard
 /// error is returned.
 ///
+/// The threshold from [`set_wal_autocheckpoint_pages`], if set, is applied
+/// after the key.
+///
 /// All production code should use this function instead of calling
 /// `tokio_rusqlite::Connection::open()` directly.
 pub async fn open_connection(path: &str) -> Result<tokio_rusqlite::Connection, BlufioError> {
@@ -140,6 +158,15 @@ pub async fn open_connection(path: &str) -> Result<tokio_rusqlite::Connection, B
         }
     }
 
+    let pages = *WAL_AUTOCHECKPOINT_PAGES
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let Some(pages) = pages {
+        conn.call(move |conn| conn.execute_batch(&format!("PRAGMA wal_autocheckpoint = {pages};")))
+            .await
+            .map_err(map_tokio_rusqlite_err)?;
+    }
+
     Ok(conn)
 }
 
@@ -178,6 +205,67 @@ pub fn open_connection_sync(
     Ok(conn)
 }
 
+/// Open a read-only async connection for reporting and other read paths.
+///
+/// The file is opened with `SQLITE_OPEN_READ_ONLY` and `PRAGMA query_only = ON`,
+/// so the connection never takes the write lock. In WAL mode it reads the last
+/// committed snapshot while the writer keeps going. The database must already
+/// exist -- read-only connections never create files or run migrations.
+pub async fn open_read_only_connection(
+    path: &str,
+) -> Result<tokio_rusqlite::Connection, BlufioError> {
+    let key = std::env::var("BLUFIO_DB_KEY").ok();
+    let file_path = std::path::Path::new(path);
+
+    // Pre-flight: detect encrypted file without a key.
+    if key.is_none() && file_path.exists() {
+        let is_plain = is_plaintext_sqlite(file_path).unwrap_or(true);
+        if !is_plain {
+            return Err(BlufioError::storage_connection_failed(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                "Database is encrypted but BLUFIO_DB_KEY is not set",
+            )));
+        }
+    }
+
+    let flags = rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY
+        | rusqlite::OpenFlags::SQLITE_OPEN_URI
+        | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX;
+    let conn = tokio_rusqlite::Connection::open_with_flags(path, flags)
+        .await
+        .map_err(BlufioError::storage_connection_failed)?;
+
+    if let Some(key) = key {
+        conn.call(move |conn| {
+            apply_encryption_key(conn, &key)?;
+            Ok(())
+        })
+        .await
+        .map_err(map_tokio_rusqlite_err)?;
+    }
+
+    conn.call(|conn| {
+        // Doubles as the key check: a wrong key fails on the first real query.
+        conn.query_row("SELECT count(*) FROM sqlite_master;", [], |_| Ok(()))?;
+        conn.execute_batch(
+            "PRAGMA query_only = ON;
+             PRAGMA busy_timeout = 5000;
+             PRAGMA cache_size = -16000;
+             PRAGMA temp_store = MEMORY;",
+        )?;
+        Ok(())
+    })
+    .await
+    .map_err(|_| {
+        BlufioError::storage_connection_failed(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "Cannot open database read-only: file is missing, encrypted, or not a database.",
+        ))
+    })?;
+
+    Ok(conn)
+}
+
 // ---------------------------------------------------------------------------
 // Database struct
 // ---------------------------------------------------------------------------
@@ -187,8 +275,12 @@ pub fn open_connection_sync(
 /// `Database` enforces the single-writer pattern: all reads and writes go
 /// through the single background thread managed by `tokio_rusqlite::Connection`.
 /// This eliminates SQLITE_BUSY errors under concurrent access.
+///
+/// Read-only handles from [`open_read_only`](Self::open_read_only) are the
+/// exception: they run alongside the writer and only ever read.
 pub struct Database {
     conn: tokio_rusqlite::Connection,
+    read_only: bool,
 }
 
 impl Database {
@@ -239,7 +331,30 @@ impl Database {
         .await
         .map_err(map_tokio_rusqlite_err)?;
 
-        Ok(Self { conn })
+        Ok(Self {
+            conn,
+            read_only: false,
+        })
+    }
+
+    /// Open a read-only handle on an existing database.
+    ///
+    /// The same query modules work against it, but any write fails with
+    /// `SQLITE_READONLY`. Used by [`ReadPool`](crate::read_pool::ReadPool)
+    /// so reads do not queue behind the writer thread.
+    pub async fn open_read_only(path: &str) -> Result<Self, BlufioError> {
+        crate::register_sqlite_vec();
+        let conn = open_read_only_connection(path).await?;
+        debug!(path = %path, "opened read-only database handle");
+        Ok(Self {
+            conn,
+            read_only: true,
+        })
+    }
+
+    /// Whether this handle was opened with [`open_read_only`](Self::open_read_only).
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
     }
 
     /// Returns a reference to the underlying tokio-rusqlite connection.
@@ -249,20 +364,74 @@ impl Database {
         &self.conn
     }
 
-    /// Checkpoint WAL and close the database.
+    /// Set the WAL page count at which SQLite checkpoints automatically.
     ///
-    /// After this call, the database file is self-contained (no `-wal` file)
-    /// and safe for `cp` backup (PERS-04).
-    pub async fn close(self) -> Result<(), BlufioError> {
-        // Checkpoint WAL to merge it into the main database file.
+    /// `wal_autocheckpoint` is a per-connection setting, so it must be applied
+    /// on this handle's connection. A value of 0 disables auto-checkpointing.
+    pub async fn set_wal_autocheckpoint(&self, pages: u32) -> Result<(), BlufioError> {
+        self.conn
+            .call(move |conn| {
+                conn.execute_batch(&format!("PRAGMA wal_autocheckpoint = {pages};"))?;
+                debug!(pages, "applied wal_autocheckpoint");
+                Ok(())
+            })
+            .await
+            .map_err(map_tokio_rusqlite_err)
+    }
+
+    /// Merge the WAL into the main database file and truncate it to zero bytes.
+    pub async fn checkpoint(&self) -> Result<(), BlufioError> {
         self.conn
             .call(|conn| {
                 conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")?;
-                debug!("WAL checkpoint complete");
                 Ok(())
             })
             .await
-            .map_err(map_tokio_rusqlite_err)?;
+            .map_err(map_tokio_rusqlite_err)
+    }
+
+    /// Rebuild the database file so pages freed by deletes are released.
+    ///
+    /// Runs on the writer thread like every other statement, so it waits for
+    /// in-flight writes instead of racing them. In WAL mode the rebuilt pages
+    /// land in the WAL; follow with [`checkpoint`](Self::checkpoint) to
+    /// shrink the main file.
+    ///
+    /// VACUUM may renumber the implicit rowids of `messages` and `memories`,
+    /// which their external-content FTS indexes point at, so both indexes are
+    /// rebuilt afterwards.
+    pub async fn vacuum(&self) -> Result<(), BlufioError> {
+        self.conn
+            .call(|conn| {
+                conn.execute_batch(
+                    "VACUUM;
+                     INSERT INTO messages_fts(messages_fts) VALUES('rebuild');
+                     INSERT INTO memories_fts(memories_fts) VALUES('rebuild');",
+                )?;
+                debug!("VACUUM complete");
+                Ok(())
+            })
+            .await
+            .map_err(map_tokio_rusqlite_err)
+    }
+
+    /// Checkpoint WAL and close the database.
+    ///
+    /// After this call, the database file is self-contained (no `-wal` file)
+    /// and safe for `cp` backup (PERS-04).
+    pub async fn close(self) -> Result<(), BlufioError> {
+        // Checkpoint WAL to merge it into the main database file. Read-only
+        // handles leave that to the writer.
+        if !self.read_only {
+            self.conn
+                .call(|conn| {
+                    conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")?;
+                    debug!("WAL checkpoint complete");
+                    Ok(())
+                })
+                .await
+                .map_err(map_tokio_rusqlite_err)?;
+        }
 
         // Close the connection.
         self.conn
@@ -376,6 +545,90 @@ mod tests {
         db.close().await.unwrap();
     }
 
+    #[tokio::test]
+    async fn vacuum_shrinks_file_after_deletes() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("vacuum_test.db");
+        let db = Database::open(db_path.to_str().unwrap()).await.unwrap();
+
+        db.connection()
+            .call(|conn| -> Result<(), rusqlite::Error> {
+                let payload = "x".repeat(4096);
+                for i in 0..200 {
+                    conn.execute(
+                        "INSERT INTO queue (queue_name, payload) VALUES (?1, ?2)",
+                        rusqlite::params![format!("q{i}"), payload],
+                    )?;
+                }
+                Ok(())
+            })
+            .await
+            .unwrap();
+        db.checkpoint().await.unwrap();
+        let full = std::fs::metadata(&db_path).unwrap().len();
+
+        db.connection()
+            .call(|conn| conn.execute("DELETE FROM queue", []))
+            .await
+            .unwrap();
+        db.checkpoint().await.unwrap();
+        // Deleted pages go on the freelist; the file keeps its size.
+        assert_eq!(std::fs::metadata(&db_path).unwrap().len(), full);
+
+        db.vacuum().await.unwrap();
+        db.checkpoint().await.unwrap();
+        let vacuumed = std::fs::metadata(&db_path).unwrap().len();
+        assert!(
+            vacuumed < full,
+            "vacuum should shrink the file ({full} -> {vacuumed})"
+        );
+
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn fts_search_finds_messages_after_delete_and_vacuum() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("fts_vacuum_test.db");
+        let db = Database::open(db_path.to_str().unwrap()).await.unwrap();
+
+        db.connection()
+            .call(|conn| -> Result<(), rusqlite::Error> {
+                conn.execute(
+                    "INSERT INTO sessions (id, channel) VALUES ('s1', 'test')",
+                    [],
+                )?;
+                for i in 0..50 {
+                    conn.execute(
+                        "INSERT INTO messages (id, session_id, role, content)
+                         VALUES (?1, 's1', 'user', ?2)",
+                        rusqlite::params![format!("m{i}"), format!("word{i} shared")],
+                    )?;
+                }
+                conn.execute("DELETE FROM messages WHERE id NOT IN ('m42', 'm49')", [])?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+        db.vacuum().await.unwrap();
+
+        let found: Vec<String> = db
+            .connection()
+            .call(|conn| -> Result<Vec<String>, rusqlite::Error> {
+                let mut stmt = conn.prepare(
+                    "SELECT m.id FROM messages_fts f JOIN messages m ON m.rowid = f.rowid
+                     WHERE messages_fts MATCH ?1 ORDER BY m.id",
+                )?;
+                let rows = stmt.query_map(["shared"], |row| row.get(0))?;
+                rows.collect()
+            })
+            .await
+            .unwrap();
+        assert_eq!(found, vec!["m42", "m49"]);
+
+        db.close().await.unwrap();
+    }
+
     #[tokio::test]
     async fn close_checkpoints_wal() {
         let dir = tempdir().unwrap();
@@ -445,6 +698,24 @@ mod tests {
         assert_eq!(result, 42);
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn open_connection_applies_configured_wal_autocheckpoint() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("autockpt.db");
+
+        set_wal_autocheckpoint_pages(321);
+        let conn = open_connection(db_path.to_str().unwrap()).await;
+        *WAL_AUTOCHECKPOINT_PAGES.lock().unwrap() = None;
+
+        let pages: i64 = conn
+            .unwrap()
+            .call(|conn| conn.query_row("PRAGMA wal_autocheckpoint;", [], |row| row.get(0)))
+            .await
+            .unwrap();
+        assert_eq!(pages, 321);
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_open_connection_with_key() {
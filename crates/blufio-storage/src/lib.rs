@@ -16,12 +16,18 @@ pub mod database;
 pub mod migrations;
 pub mod models;
 pub mod queries;
+pub mod read_pool;
 pub mod writer;
 
-pub use adapter::SqliteStorage;
-pub use database::{Database, is_plaintext_sqlite, open_connection, open_connection_sync};
+pub use adapter::{MaintenanceReport, SqliteStorage};
+pub use database::{
+    Database, is_plaintext_sqlite, open_connection, open_connection_sync,
+    open_read_only_connection, set_wal_autocheckpoint_pages,
+};
 pub use models::*;
 pub use queries::classification::BulkClassificationResult;
+pub use queries::queue::QueueRecovery;
+pub use read_pool::ReadPool;
 
 /// Register the sqlite-vec extension globally via `sqlite3_auto_extension`.
 ///
@@ -7,4 +7,4 @@
 //! adapter trait boundaries. This module re-exports them for convenience
 //! within the storage crate.
 
-pub use blufio_core::types::{Message, QueueEntry, Session};
+pub use blufio_core::types::{Message, QueueEntry, Session, ToolLoopState};
@@ -15,20 +15,55 @@ pub async fn insert_message(db: &Database, msg: &Message) -> Result<(), BlufioEr
     let msg = msg.clone();
     db.connection()
         .call(move |conn| {
-            conn.execute(
-                "INSERT INTO messages (id, session_id, role, content, token_count, metadata, created_at, classification)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-                params![
-                    msg.id,
-                    msg.session_id,
-                    msg.role,
-                    msg.content,
-                    msg.token_count,
-                    msg.metadata,
-                    msg.created_at,
-                    msg.classification.as_str(),
-                ],
-            )?;
+            let tx = conn.transaction()?;
+            insert_message_tx(&tx, &msg)?;
+            tx.commit()?;
+            Ok(())
+        })
+        .await
+        .map_err(crate::database::map_tr_err)
+}
+
+/// Insert one message inside an open transaction.
+///
+/// Shared by the message and session writers so every path uses the same
+/// statement; the caller commits.
+pub(crate) fn insert_message_tx(
+    tx: &rusqlite::Transaction<'_>,
+    msg: &Message,
+) -> rusqlite::Result<()> {
+    tx.prepare_cached(
+        "INSERT INTO messages (id, session_id, role, content, token_count, metadata, created_at, classification)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+    )?
+    .execute(params![
+        msg.id,
+        msg.session_id,
+        msg.role,
+        msg.content,
+        msg.token_count,
+        msg.metadata,
+        msg.created_at,
+        msg.classification.as_str(),
+    ])?;
+    Ok(())
+}
+
+/// Insert several messages in one transaction.
+///
+/// Either every message is written or, if any insert fails, none are.
+pub async fn insert_messages(db: &Database, msgs: &[Message]) -> Result<(), BlufioError> {
+    if msgs.is_empty() {
+        return Ok(());
+    }
+    let msgs = msgs.to_vec();
+    db.connection()
+        .call(move |conn| {
+            let tx = conn.transaction()?;
+            for msg in &msgs {
+                insert_message_tx(&tx, msg)?;
+            }
+            tx.commit()?;
             Ok(())
         })
         .await
@@ -79,6 +114,37 @@ pub async fn get_messages_for_session(
         .map_err(crate::database::map_tr_err)
 }
 
+/// Get up to `limit` messages of a session following `after_id`.
+///
+/// Keyset pagination on `(created_at, rowid)`, so each page is an index
+/// range scan however deep into the session it starts. An `after_id` that
+/// is not in the session yields no rows.
+pub async fn get_messages_page(
+    db: &Database,
+    session_id: &str,
+    after_id: Option<&str>,
+    limit: usize,
+) -> Result<Vec<Message>, BlufioError> {
+    let session_id = session_id.to_string();
+    let after_id = after_id.map(str::to_string);
+    db.connection()
+        .call(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, session_id, role, content, token_count, metadata, created_at, classification
+                 FROM messages WHERE session_id = ?1 AND classification != 'restricted' AND deleted_at IS NULL
+                   AND (?2 IS NULL OR (created_at, rowid) >
+                        (SELECT created_at, rowid FROM messages WHERE id = ?2 AND session_id = ?1))
+                 ORDER BY created_at ASC, rowid ASC LIMIT ?3",
+            )?;
+            let rows = stmt.query_map(params![session_id, after_id, limit as i64], |row| {
+                Ok(row_to_message(row))
+            })?;
+            rows.collect::<Result<Vec<_>, _>>()
+        })
+        .await
+        .map_err(crate::database::map_tr_err)
+}
+
 /// Delete specific messages by their IDs within a session.
 ///
 /// Returns the number of rows deleted.
@@ -119,6 +185,36 @@ pub async fn delete_messages_by_ids(
         .map_err(crate::database::map_tr_err)
 }
 
+/// Search message content via FTS5, best BM25 matches first.
+///
+/// `query` is an FTS5 match expression. Restricted and soft-deleted messages
+/// are excluded. Searches all sessions when `session_id` is `None`.
+pub async fn search_messages(
+    db: &Database,
+    session_id: Option<&str>,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<Message>, BlufioError> {
+    let session_id = session_id.map(str::to_string);
+    let query = query.to_string();
+    db.connection()
+        .call(move |conn| {
+            let mut stmt = conn.prepare(
+                "SELECT m.id, m.session_id, m.role, m.content, m.token_count, m.metadata, m.created_at, m.classification
+                 FROM messages_fts JOIN messages m ON m.rowid = messages_fts.rowid
+                 WHERE messages_fts MATCH ?1 AND (?2 IS NULL OR m.session_id = ?2)
+                   AND m.classification != 'restricted' AND m.deleted_at IS NULL
+                 ORDER BY bm25(messages_fts) LIMIT ?3",
+            )?;
+            let rows = stmt.query_map(params![query, session_id, limit as i64], |row| {
+                Ok(row_to_message(row))
+            })?;
+            rows.collect::<Result<Vec<_>, _>>()
+        })
+        .await
+        .map_err(crate::database::map_tr_err)
+}
+
 /// Convert a rusqlite Row to a Message struct.
 ///
 /// Column order: id(0), session_id(1), role(2), content(3), token_count(4),
@@ -138,7 +234,7 @@ fn row_to_message(row: &rusqlite::Row) -> Message {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use crate::models::Session;
     use crate::queries::sessions::create_session;
@@ -164,7 +260,7 @@ mod tests {
         (db, dir)
     }
 
-    fn make_msg(id: &str, role: &str, content: &str, timestamp: &str) -> Message {
+    pub(crate) fn make_msg(id: &str, role: &str, content: &str, timestamp: &str) -> Message {
         Message {
             id: id.to_string(),
             session_id: "sess-1".to_string(),
@@ -231,4 +327,220 @@ mod tests {
         assert!(messages.is_empty());
         db.close().await.unwrap();
     }
+
+    #[tokio::test]
+    async fn insert_messages_writes_whole_batch() {
+        let (db, _dir) = setup_db_with_session().await;
+        let batch = [
+            make_msg("m1", "user", "result one", "2026-01-01T00:00:01.000Z"),
+            make_msg("m2", "user", "result two", "2026-01-01T00:00:02.000Z"),
+        ];
+        insert_messages(&db, &batch).await.unwrap();
+        insert_messages(&db, &[]).await.unwrap();
+
+        let messages = get_messages_for_session(&db, "sess-1", None).await.unwrap();
+        let ids: Vec<&str> = messages.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["m1", "m2"]);
+
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn failed_batch_rolls_back_entirely() {
+        let (db, _dir) = setup_db_with_session().await;
+        insert_message(
+            &db,
+            &make_msg("m0", "user", "existing", "2026-01-01T00:00:00.000Z"),
+        )
+        .await
+        .unwrap();
+
+        // The third row reuses an existing ID, so the whole batch must fail.
+        let batch = [
+            make_msg("m1", "user", "result one", "2026-01-01T00:00:01.000Z"),
+            make_msg("m2", "user", "result two", "2026-01-01T00:00:02.000Z"),
+            make_msg("m0", "user", "duplicate", "2026-01-01T00:00:03.000Z"),
+        ];
+        assert!(insert_messages(&db, &batch).await.is_err());
+
+        let messages = get_messages_for_session(&db, "sess-1", None).await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].content, "existing");
+
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn get_messages_page_walks_session_in_order() {
+        let (db, _dir) = setup_db_with_session().await;
+        // m2 and m3 share a timestamp; insertion order breaks the tie.
+        for (id, ts) in [
+            ("m0", "2026-01-01T00:00:00.000Z"),
+            ("m1", "2026-01-01T00:00:01.000Z"),
+            ("m2", "2026-01-01T00:00:02.000Z"),
+            ("m3", "2026-01-01T00:00:02.000Z"),
+            ("m4", "2026-01-01T00:00:03.000Z"),
+        ] {
+            insert_message(&db, &make_msg(id, "user", id, ts))
+                .await
+                .unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let page = get_messages_page(&db, "sess-1", after.as_deref(), 2)
+                .await
+                .unwrap();
+            if page.is_empty() {
+                break;
+            }
+            after = page.last().map(|m| m.id.clone());
+            seen.extend(page.into_iter().map(|m| m.id));
+        }
+        assert_eq!(seen, ["m0", "m1", "m2", "m3", "m4"]);
+
+        let unknown = get_messages_page(&db, "sess-1", Some("nope"), 10)
+            .await
+            .unwrap();
+        assert!(unknown.is_empty());
+
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn search_messages_matches_content() {
+        let (db, _dir) = setup_db_with_session().await;
+        insert_message(
+            &db,
+            &make_msg(
+                "m1",
+                "user",
+                "plan the garden layout",
+                "2026-01-01T00:00:01.000Z",
+            ),
+        )
+        .await
+        .unwrap();
+        insert_message(
+            &db,
+            &make_msg(
+                "m2",
+                "assistant",
+                "tomatoes need sun",
+                "2026-01-01T00:00:02.000Z",
+            ),
+        )
+        .await
+        .unwrap();
+        insert_message(
+            &db,
+            &make_msg(
+                "m3",
+                "user",
+                "garden garden garden",
+                "2026-01-01T00:00:03.000Z",
+            ),
+        )
+        .await
+        .unwrap();
+
+        let results = search_messages(&db, None, "garden", 10).await.unwrap();
+        let ids: Vec<&str> = results.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["m3", "m1"], "more frequent term ranks first");
+
+        let results = search_messages(&db, None, "garden", 1).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(
+            search_messages(&db, None, "cucumber", 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn search_messages_filters_by_session() {
+        let (db, _dir) = setup_db_with_session().await;
+        let other = Session {
+            id: "sess-2".to_string(),
+            channel: "cli".to_string(),
+            user_id: None,
+            state: "active".to_string(),
+            metadata: None,
+            created_at: "2026-01-01T00:00:00.000Z".to_string(),
+            updated_at: "2026-01-01T00:00:00.000Z".to_string(),
+            classification: DataClassification::default(),
+        };
+        create_session(&db, &other).await.unwrap();
+        insert_message(
+            &db,
+            &make_msg("m1", "user", "budget review", "2026-01-01T00:00:01.000Z"),
+        )
+        .await
+        .unwrap();
+        let mut m2 = make_msg("m2", "user", "budget forecast", "2026-01-01T00:00:02.000Z");
+        m2.session_id = "sess-2".to_string();
+        insert_message(&db, &m2).await.unwrap();
+
+        assert_eq!(
+            search_messages(&db, None, "budget", 10)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        let scoped = search_messages(&db, Some("sess-2"), "budget", 10)
+            .await
+            .unwrap();
+        assert_eq!(scoped.len(), 1);
+        assert_eq!(scoped[0].id, "m2");
+
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn search_index_tracks_deletes_and_excludes_restricted() {
+        let (db, _dir) = setup_db_with_session().await;
+        insert_message(
+            &db,
+            &make_msg(
+                "m1",
+                "user",
+                "secret launch date",
+                "2026-01-01T00:00:01.000Z",
+            ),
+        )
+        .await
+        .unwrap();
+        let mut m2 = make_msg("m2", "user", "launch checklist", "2026-01-01T00:00:02.000Z");
+        m2.classification = DataClassification::Restricted;
+        insert_message(&db, &m2).await.unwrap();
+        insert_message(
+            &db,
+            &make_msg("m3", "user", "launch party", "2026-01-01T00:00:03.000Z"),
+        )
+        .await
+        .unwrap();
+
+        let ids: Vec<String> = search_messages(&db, None, "launch", 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        assert!(!ids.contains(&"m2".to_string()), "restricted excluded");
+        assert_eq!(ids.len(), 2);
+
+        delete_messages_by_ids(&db, "sess-1", &["m1".to_string()])
+            .await
+            .unwrap();
+        let results = search_messages(&db, None, "launch", 10).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "m3");
+
+        db.close().await.unwrap();
+    }
 }
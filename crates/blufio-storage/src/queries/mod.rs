@@ -7,4 +7,6 @@ pub mod archives;
 pub mod classification;
 pub mod messages;
 pub mod queue;
+pub mod retention;
 pub mod sessions;
+pub mod tool_loop;
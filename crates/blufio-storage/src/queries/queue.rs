@@ -4,6 +4,7 @@
 //! Queue operations for crash-safe message processing.
 
 use blufio_core::BlufioError;
+pub use blufio_core::types::QueueRecovery;
 use rusqlite::params;
 
 use crate::database::Database;
@@ -144,6 +145,39 @@ pub async fn fail(db: &Database, id: i64) -> Result<(), BlufioError> {
         .map_err(crate::database::map_tr_err)
 }
 
+/// Reset entries left in "processing" by a crash, in every queue.
+///
+/// The interrupted run counts as a failed attempt, so an entry that keeps
+/// crashing the process ends up "failed" after `max_attempts` like any other
+/// failure. Running it again finds nothing to recover.
+pub async fn recover_in_flight(db: &Database) -> Result<QueueRecovery, BlufioError> {
+    db.connection()
+        .call(|conn| {
+            let tx = conn.transaction()?;
+            let dead_lettered = tx.execute(
+                "UPDATE queue SET status = 'failed', attempts = attempts + 1,
+                 locked_until = NULL,
+                 updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+                 WHERE status = 'processing' AND attempts + 1 >= max_attempts",
+                [],
+            )?;
+            let requeued = tx.execute(
+                "UPDATE queue SET status = 'pending', attempts = attempts + 1,
+                 locked_until = NULL,
+                 updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+                 WHERE status = 'processing'",
+                [],
+            )?;
+            tx.commit()?;
+            Ok(QueueRecovery {
+                requeued,
+                dead_lettered,
+            })
+        })
+        .await
+        .map_err(crate::database::map_tr_err)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -259,6 +293,79 @@ mod tests {
         db.close().await.unwrap();
     }
 
+    async fn status_and_attempts(db: &Database, id: i64) -> (String, i32) {
+        db.connection()
+            .call(move |conn| -> Result<(String, i32), rusqlite::Error> {
+                conn.query_row(
+                    "SELECT status, attempts FROM queue WHERE id = ?1",
+                    params![id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn recover_in_flight_requeues_interrupted_entries() {
+        let (db, _dir) = setup_db().await;
+
+        let in_flight = enqueue(&db, "inbound", "a").await.unwrap();
+        let waiting = enqueue(&db, "inbound", "b").await.unwrap();
+        dequeue(&db, "inbound").await.unwrap().unwrap();
+
+        let recovery = recover_in_flight(&db).await.unwrap();
+        assert_eq!(
+            recovery,
+            QueueRecovery {
+                requeued: 1,
+                dead_lettered: 0
+            }
+        );
+        assert_eq!(
+            status_and_attempts(&db, in_flight).await,
+            ("pending".to_string(), 1)
+        );
+        assert_eq!(
+            status_and_attempts(&db, waiting).await,
+            ("pending".to_string(), 0)
+        );
+
+        // The recovered entry is dequeued again, ahead of newer ones.
+        let entry = dequeue(&db, "inbound").await.unwrap().unwrap();
+        assert_eq!(entry.id, in_flight);
+
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn recover_in_flight_dead_letters_on_last_attempt() {
+        let (db, _dir) = setup_db().await;
+
+        let id = enqueue(&db, "inbound", "poison").await.unwrap();
+        // Two failed attempts, then a crash during the third (max_attempts = 3).
+        for _ in 0..2 {
+            dequeue(&db, "inbound").await.unwrap().unwrap();
+            fail(&db, id).await.unwrap();
+        }
+        dequeue(&db, "inbound").await.unwrap().unwrap();
+
+        let recovery = recover_in_flight(&db).await.unwrap();
+        assert_eq!(recovery.dead_lettered, 1);
+        assert_eq!(recovery.requeued, 0);
+        assert_eq!(
+            status_and_attempts(&db, id).await,
+            ("failed".to_string(), 3)
+        );
+
+        // Idempotent: nothing left to recover.
+        assert_eq!(
+            recover_in_flight(&db).await.unwrap(),
+            QueueRecovery::default()
+        );
+        db.close().await.unwrap();
+    }
+
     #[tokio::test]
     async fn dequeue_empty_queue_returns_none() {
         let (db, _dir) = setup_db().await;
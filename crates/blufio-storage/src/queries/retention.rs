@@ -0,0 +1,273 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Retention purge for messages and sessions.
+//!
+//! Two phases in one transaction, matching the cron retention engine:
+//! rows past their retention period get `deleted_at`, and rows soft-deleted
+//! longer ago than the grace period are removed. `deleted_at` uses SQLite's
+//! `datetime()` format, as the cron engine writes it.
+
+use blufio_config::model::{RetentionConfig, RetentionPeriods};
+use blufio_core::BlufioError;
+use blufio_core::types::PurgeReport;
+use rusqlite::params;
+
+use crate::database::Database;
+
+/// Tables purged here; memories and cost records are left to the cron engine.
+const TABLES: &[&str] = &["messages", "sessions"];
+
+fn retention_days(periods: &RetentionPeriods, table: &str) -> Option<u64> {
+    match table {
+        "messages" => periods.messages,
+        "sessions" => periods.sessions,
+        _ => None,
+    }
+}
+
+/// Soft-delete expired messages and sessions, then remove those past grace.
+///
+/// `now` is an ISO 8601 timestamp; ages are measured from it rather than the
+/// database clock so callers (and tests) control the cutoff.
+pub async fn purge_expired(
+    db: &Database,
+    policy: &RetentionConfig,
+    now: &str,
+) -> Result<PurgeReport, BlufioError> {
+    let policy = policy.clone();
+    let now = now.to_string();
+    db.connection()
+        .call(move |conn| {
+            let tx = conn.transaction()?;
+            let mut soft = [0u64; 2];
+            let mut purged = [0u64; 2];
+
+            for (i, table) in TABLES.iter().enumerate() {
+                if let Some(days) = retention_days(&policy.periods, table) {
+                    soft[i] += tx.execute(
+                        &format!(
+                            "UPDATE {table} SET deleted_at = datetime(?1)
+                             WHERE deleted_at IS NULL AND classification != 'restricted'
+                               AND created_at < strftime('%Y-%m-%dT%H:%M:%fZ', ?1, ?2)"
+                        ),
+                        params![now, format!("-{days} days")],
+                    )? as u64;
+                }
+                if let Some(days) = retention_days(&policy.restricted, table) {
+                    soft[i] += tx.execute(
+                        &format!(
+                            "UPDATE {table} SET deleted_at = datetime(?1)
+                             WHERE deleted_at IS NULL AND classification = 'restricted'
+                               AND created_at < strftime('%Y-%m-%dT%H:%M:%fZ', ?1, ?2)"
+                        ),
+                        params![now, format!("-{days} days")],
+                    )? as u64;
+                }
+            }
+
+            // Messages first so their count is not hidden by the session cascade.
+            for (i, table) in TABLES.iter().enumerate() {
+                purged[i] = tx.execute(
+                    &format!(
+                        "DELETE FROM {table}
+                         WHERE deleted_at IS NOT NULL AND deleted_at < datetime(?1, ?2)"
+                    ),
+                    params![now, format!("-{} days", policy.grace_period_days)],
+                )? as u64;
+            }
+
+            tx.commit()?;
+            Ok(PurgeReport {
+                messages_soft_deleted: soft[0],
+                sessions_soft_deleted: soft[1],
+                messages_purged: purged[0],
+                sessions_purged: purged[1],
+            })
+        })
+        .await
+        .map_err(crate::database::map_tr_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Message, Session};
+    use crate::queries::messages::{get_messages_for_session, insert_message};
+    use crate::queries::sessions::{create_session, get_session};
+    use blufio_core::classification::DataClassification;
+    use tempfile::tempdir;
+
+    const NOW: &str = "2026-03-01T00:00:00.000Z";
+
+    async fn setup_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(db_path.to_str().unwrap()).await.unwrap();
+        (db, dir)
+    }
+
+    fn make_session(id: &str, created_at: &str) -> Session {
+        Session {
+            id: id.to_string(),
+            channel: "cli".to_string(),
+            user_id: None,
+            state: "active".to_string(),
+            metadata: None,
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            classification: DataClassification::default(),
+        }
+    }
+
+    fn make_msg(id: &str, session_id: &str, created_at: &str) -> Message {
+        Message {
+            id: id.to_string(),
+            session_id: session_id.to_string(),
+            role: "user".to_string(),
+            content: format!("message {id}"),
+            token_count: None,
+            metadata: None,
+            created_at: created_at.to_string(),
+            classification: DataClassification::default(),
+        }
+    }
+
+    fn policy(messages: Option<u64>, sessions: Option<u64>) -> RetentionConfig {
+        RetentionConfig {
+            enabled: true,
+            grace_period_days: 7,
+            periods: RetentionPeriods {
+                messages,
+                sessions,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    async fn deleted_at(db: &Database, table: &'static str, id: &str) -> Option<String> {
+        let id = id.to_string();
+        db.connection()
+            .call(move |conn| {
+                conn.query_row(
+                    &format!("SELECT deleted_at FROM {table} WHERE id = ?1"),
+                    params![id],
+                    |row| row.get(0),
+                )
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn expired_messages_are_soft_deleted_and_fresh_ones_remain() {
+        let (db, _dir) = setup_db().await;
+        create_session(&db, &make_session("s1", "2026-02-27T00:00:00.000Z"))
+            .await
+            .unwrap();
+        insert_message(&db, &make_msg("old", "s1", "2026-01-01T00:00:00.000Z"))
+            .await
+            .unwrap();
+        insert_message(&db, &make_msg("fresh", "s1", "2026-02-28T00:00:00.000Z"))
+            .await
+            .unwrap();
+
+        let report = purge_expired(&db, &policy(Some(30), None), NOW)
+            .await
+            .unwrap();
+        assert_eq!(report.messages_soft_deleted, 1);
+        assert_eq!(report.messages_purged, 0);
+
+        assert!(deleted_at(&db, "messages", "old").await.is_some());
+        let visible = get_messages_for_session(&db, "s1", None).await.unwrap();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].id, "fresh");
+
+        // A second run within the grace period changes nothing.
+        let report = purge_expired(&db, &policy(Some(30), None), NOW)
+            .await
+            .unwrap();
+        assert!(report.is_empty());
+
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn soft_deleted_rows_are_purged_after_grace_period() {
+        let (db, _dir) = setup_db().await;
+        create_session(&db, &make_session("old", "2025-12-01T00:00:00.000Z"))
+            .await
+            .unwrap();
+        create_session(&db, &make_session("fresh", "2026-02-27T00:00:00.000Z"))
+            .await
+            .unwrap();
+        insert_message(&db, &make_msg("m-old", "old", "2025-12-01T00:00:01.000Z"))
+            .await
+            .unwrap();
+        insert_message(
+            &db,
+            &make_msg("m-fresh", "fresh", "2026-02-27T00:00:01.000Z"),
+        )
+        .await
+        .unwrap();
+
+        let policy = policy(Some(30), Some(30));
+        let report = purge_expired(&db, &policy, NOW).await.unwrap();
+        assert_eq!(report.messages_soft_deleted, 1);
+        assert_eq!(report.sessions_soft_deleted, 1);
+
+        // Eight days later the grace period has passed.
+        let report = purge_expired(&db, &policy, "2026-03-09T00:00:00.000Z")
+            .await
+            .unwrap();
+        assert_eq!(report.messages_purged, 1);
+        assert_eq!(report.sessions_purged, 1);
+
+        assert!(get_session(&db, "old").await.unwrap().is_none());
+        assert!(get_session(&db, "fresh").await.unwrap().is_some());
+        let fresh = get_messages_for_session(&db, "fresh", None).await.unwrap();
+        assert_eq!(fresh.len(), 1);
+
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn restricted_rows_use_their_own_period() {
+        let (db, _dir) = setup_db().await;
+        create_session(&db, &make_session("s1", "2026-02-27T00:00:00.000Z"))
+            .await
+            .unwrap();
+        let mut secret = make_msg("secret", "s1", "2026-02-20T00:00:00.000Z");
+        secret.classification = DataClassification::Restricted;
+        insert_message(&db, &secret).await.unwrap();
+        insert_message(&db, &make_msg("plain", "s1", "2026-02-20T00:00:00.000Z"))
+            .await
+            .unwrap();
+
+        let mut policy = policy(Some(30), None);
+        policy.restricted.messages = Some(5);
+        let report = purge_expired(&db, &policy, NOW).await.unwrap();
+        assert_eq!(report.messages_soft_deleted, 1);
+        assert!(deleted_at(&db, "messages", "secret").await.is_some());
+        assert!(deleted_at(&db, "messages", "plain").await.is_none());
+
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn no_periods_means_nothing_expires() {
+        let (db, _dir) = setup_db().await;
+        create_session(&db, &make_session("s1", "2020-01-01T00:00:00.000Z"))
+            .await
+            .unwrap();
+        insert_message(&db, &make_msg("m1", "s1", "2020-01-01T00:00:00.000Z"))
+            .await
+            .unwrap();
+
+        let report = purge_expired(&db, &policy(None, None), NOW).await.unwrap();
+        assert!(report.is_empty());
+
+        db.close().await.unwrap();
+    }
+}
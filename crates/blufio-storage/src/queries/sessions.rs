@@ -8,33 +8,67 @@ use blufio_core::classification::DataClassification;
 use rusqlite::params;
 
 use crate::database::Database;
-use crate::models::Session;
+use crate::models::{Message, Session};
+use crate::queries::messages::insert_message_tx;
 
 /// Create a new session.
 pub async fn create_session(db: &Database, session: &Session) -> Result<(), BlufioError> {
     let session = session.clone();
     db.connection()
         .call(move |conn| {
-            conn.execute(
-                "INSERT INTO sessions (id, channel, user_id, state, metadata, created_at, updated_at, classification)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-                params![
-                    session.id,
-                    session.channel,
-                    session.user_id,
-                    session.state,
-                    session.metadata,
-                    session.created_at,
-                    session.updated_at,
-                    session.classification.as_str(),
-                ],
-            )?;
+            let tx = conn.transaction()?;
+            insert_session_tx(&tx, &session)?;
+            tx.commit()?;
+            Ok(())
+        })
+        .await
+        .map_err(crate::database::map_tr_err)
+}
+
+/// Create a session and insert its initial messages in one transaction.
+///
+/// Either the session and every message are written or, if any insert
+/// fails, nothing is.
+pub async fn create_session_with_messages(
+    db: &Database,
+    session: &Session,
+    msgs: &[Message],
+) -> Result<(), BlufioError> {
+    let session = session.clone();
+    let msgs = msgs.to_vec();
+    db.connection()
+        .call(move |conn| {
+            let tx = conn.transaction()?;
+            insert_session_tx(&tx, &session)?;
+            for msg in &msgs {
+                insert_message_tx(&tx, msg)?;
+            }
+            tx.commit()?;
             Ok(())
         })
         .await
         .map_err(crate::database::map_tr_err)
 }
 
+/// Insert one session row inside an open transaction; the caller commits.
+fn insert_session_tx(tx: &rusqlite::Transaction<'_>, session: &Session) -> rusqlite::Result<()> {
+    tx.execute(
+        "INSERT INTO sessions (id, channel, user_id, state, metadata, created_at, updated_at, classification)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            session.id,
+            session.channel,
+            session.user_id,
+            session.state,
+            session.metadata,
+            session.created_at,
+            session.updated_at,
+            session.classification.as_str(),
+        ],
+    )?;
+    Ok(())
+}
+
 /// Get a session by ID.
 pub async fn get_session(db: &Database, id: &str) -> Result<Option<Session>, BlufioError> {
     let id = id.to_string();
@@ -115,6 +149,27 @@ pub async fn update_session_state(db: &Database, id: &str, state: &str) -> Resul
         .map_err(crate::database::map_tr_err)
 }
 
+/// Replace a session's metadata JSON.
+pub async fn update_session_metadata(
+    db: &Database,
+    id: &str,
+    metadata: Option<&str>,
+) -> Result<(), BlufioError> {
+    let id = id.to_string();
+    let metadata = metadata.map(str::to_string);
+    db.connection()
+        .call(move |conn| {
+            conn.execute(
+                "UPDATE sessions SET metadata = ?1, updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+                 WHERE id = ?2",
+                params![metadata, id],
+            )?;
+            Ok(())
+        })
+        .await
+        .map_err(crate::database::map_tr_err)
+}
+
 /// Convert a rusqlite Row to a Session struct.
 ///
 /// Column order: id(0), channel(1), user_id(2), state(3), metadata(4),
@@ -136,6 +191,7 @@ fn row_to_session(row: &rusqlite::Row) -> Session {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::queries::messages::tests::make_msg;
     use tempfile::tempdir;
 
     async fn setup_db() -> (Database, tempfile::TempDir) {
@@ -219,4 +275,61 @@ mod tests {
         assert_eq!(retrieved.state, "paused");
         db.close().await.unwrap();
     }
+
+    #[tokio::test]
+    async fn update_session_metadata_works() {
+        let (db, _dir) = setup_db().await;
+        create_session(&db, &make_session("s-meta")).await.unwrap();
+
+        update_session_metadata(&db, "s-meta", Some(r#"{"persona":"concise"}"#))
+            .await
+            .unwrap();
+        let retrieved = get_session(&db, "s-meta").await.unwrap().unwrap();
+        assert_eq!(
+            retrieved.metadata.as_deref(),
+            Some(r#"{"persona":"concise"}"#)
+        );
+
+        update_session_metadata(&db, "s-meta", None).await.unwrap();
+        let retrieved = get_session(&db, "s-meta").await.unwrap().unwrap();
+        assert_eq!(retrieved.metadata, None);
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn create_session_with_messages_is_all_or_nothing() {
+        let (db, _dir) = setup_db().await;
+        let ts = "2026-01-01T00:00:01.000Z";
+        create_session_with_messages(
+            &db,
+            &make_session("sess-1"),
+            &[
+                make_msg("m1", "user", "hello", ts),
+                make_msg("m2", "assistant", "hi", ts),
+            ],
+        )
+        .await
+        .unwrap();
+        assert!(get_session(&db, "sess-1").await.unwrap().is_some());
+        let messages = crate::queries::messages::get_messages_for_session(&db, "sess-1", None)
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 2);
+
+        // The second message reuses an existing ID, so the session must not
+        // be created either.
+        let in_bad = |id| Message {
+            session_id: "s-bad".to_string(),
+            ..make_msg(id, "user", "again", ts)
+        };
+        let result = create_session_with_messages(
+            &db,
+            &make_session("s-bad"),
+            &[in_bad("m3"), in_bad("m1")],
+        )
+        .await;
+        assert!(result.is_err());
+        assert!(get_session(&db, "s-bad").await.unwrap().is_none());
+        db.close().await.unwrap();
+    }
 }
@@ -0,0 +1,182 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Tool-loop state operations for crash recovery of in-flight tool calls.
+
+use blufio_core::BlufioError;
+use blufio_core::types::ToolUseData;
+use rusqlite::params;
+
+use crate::database::Database;
+use crate::models::ToolLoopState;
+
+/// Record the tool iteration about to execute for a session.
+///
+/// Replaces any earlier record for the same session.
+pub async fn save_tool_loop_state(
+    db: &Database,
+    session_id: &str,
+    iteration: u32,
+    pending_tool_uses: &[ToolUseData],
+) -> Result<(), BlufioError> {
+    let session_id = session_id.to_string();
+    let pending =
+        serde_json::to_string(pending_tool_uses).map_err(BlufioError::storage_corruption)?;
+    db.connection()
+        .call(move |conn| {
+            conn.execute(
+                "INSERT INTO tool_loop_state (session_id, iteration, pending_tool_uses)
+                 VALUES (?1, ?2, ?3)
+                 ON CONFLICT(session_id) DO UPDATE SET
+                    iteration = excluded.iteration,
+                    pending_tool_uses = excluded.pending_tool_uses,
+                    updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')",
+                params![session_id, iteration, pending],
+            )?;
+            Ok(())
+        })
+        .await
+        .map_err(crate::database::map_tr_err)
+}
+
+/// Remove the tool-loop record for a session, if any.
+pub async fn clear_tool_loop_state(db: &Database, session_id: &str) -> Result<(), BlufioError> {
+    let session_id = session_id.to_string();
+    db.connection()
+        .call(move |conn| {
+            conn.execute(
+                "DELETE FROM tool_loop_state WHERE session_id = ?1",
+                params![session_id],
+            )?;
+            Ok(())
+        })
+        .await
+        .map_err(crate::database::map_tr_err)
+}
+
+/// List every tool-loop record, oldest first.
+///
+/// At startup these are the loops interrupted by the previous shutdown.
+pub async fn list_tool_loop_states(db: &Database) -> Result<Vec<ToolLoopState>, BlufioError> {
+    let rows = db
+        .connection()
+        .call(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT session_id, iteration, pending_tool_uses, created_at, updated_at
+                 FROM tool_loop_state ORDER BY created_at ASC",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, u32>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            })?;
+            rows.collect::<Result<Vec<_>, _>>()
+        })
+        .await
+        .map_err(crate::database::map_tr_err)?;
+
+    rows.into_iter()
+        .map(|(session_id, iteration, pending, created_at, updated_at)| {
+            let pending_tool_uses =
+                serde_json::from_str(&pending).map_err(BlufioError::storage_corruption)?;
+            Ok(ToolLoopState {
+                session_id,
+                iteration,
+                pending_tool_uses,
+                created_at,
+                updated_at,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Session;
+    use crate::queries::sessions::create_session;
+    use tempfile::tempdir;
+
+    async fn setup_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(db_path.to_str().unwrap()).await.unwrap();
+        (db, dir)
+    }
+
+    async fn make_session(db: &Database, id: &str) {
+        let session = Session {
+            id: id.to_string(),
+            channel: "cli".to_string(),
+            user_id: None,
+            state: "active".to_string(),
+            metadata: None,
+            created_at: "2026-01-01T00:00:00.000Z".to_string(),
+            updated_at: "2026-01-01T00:00:00.000Z".to_string(),
+            classification: Default::default(),
+        };
+        create_session(db, &session).await.unwrap();
+    }
+
+    fn tool_use(id: &str) -> ToolUseData {
+        ToolUseData {
+            id: id.to_string(),
+            name: "bash".to_string(),
+            input: serde_json::json!({"command": "ls"}),
+        }
+    }
+
+    #[tokio::test]
+    async fn save_list_and_clear() {
+        let (db, _dir) = setup_db().await;
+        make_session(&db, "sess-1").await;
+
+        save_tool_loop_state(&db, "sess-1", 0, &[tool_use("tu-1"), tool_use("tu-2")])
+            .await
+            .unwrap();
+
+        let states = list_tool_loop_states(&db).await.unwrap();
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].session_id, "sess-1");
+        assert_eq!(states[0].iteration, 0);
+        let ids: Vec<_> = states[0].pending_tool_uses.iter().map(|t| &t.id).collect();
+        assert_eq!(ids, ["tu-1", "tu-2"]);
+        assert_eq!(states[0].pending_tool_uses[0].input["command"], "ls");
+
+        clear_tool_loop_state(&db, "sess-1").await.unwrap();
+        assert!(list_tool_loop_states(&db).await.unwrap().is_empty());
+
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn save_replaces_previous_iteration() {
+        let (db, _dir) = setup_db().await;
+        make_session(&db, "sess-1").await;
+
+        save_tool_loop_state(&db, "sess-1", 0, &[tool_use("tu-1")])
+            .await
+            .unwrap();
+        save_tool_loop_state(&db, "sess-1", 1, &[tool_use("tu-2")])
+            .await
+            .unwrap();
+
+        let states = list_tool_loop_states(&db).await.unwrap();
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].iteration, 1);
+        assert_eq!(states[0].pending_tool_uses[0].id, "tu-2");
+
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn clear_without_record_is_noop() {
+        let (db, _dir) = setup_db().await;
+        clear_tool_loop_state(&db, "missing").await.unwrap();
+        db.close().await.unwrap();
+    }
+}
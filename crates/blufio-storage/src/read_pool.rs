@@ -0,0 +1,145 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Pool of read-only database handles.
+//!
+//! Every [`Database`] runs its statements on one background thread, so a slow
+//! report would otherwise queue behind (and in front of) agent-loop writes.
+//! A `ReadPool` holds a few read-only handles, each with its own thread, and
+//! hands them out round-robin. With WAL, readers see the last committed
+//! snapshot and never block the writer.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use blufio_core::BlufioError;
+
+use crate::database::Database;
+
+/// Round-robin pool of read-only [`Database`] handles.
+pub struct ReadPool {
+    readers: Vec<Database>,
+    next: AtomicUsize,
+}
+
+impl ReadPool {
+    /// Open `size` read-only handles on an existing database (at least one).
+    pub async fn open(path: &str, size: usize) -> Result<Self, BlufioError> {
+        let mut readers = Vec::with_capacity(size.max(1));
+        for _ in 0..size.max(1) {
+            readers.push(Database::open_read_only(path).await?);
+        }
+        Ok(Self {
+            readers,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Next reader in round-robin order.
+    pub fn get(&self) -> &Database {
+        let i = self.next.fetch_add(1, Ordering::Relaxed) % self.readers.len();
+        &self.readers[i]
+    }
+
+    /// Number of handles in the pool.
+    pub fn len(&self) -> usize {
+        self.readers.len()
+    }
+
+    /// Always false: a pool holds at least one handle.
+    pub fn is_empty(&self) -> bool {
+        self.readers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn read_runs_while_write_transaction_is_open() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("pool.db");
+        let path = path.to_str().unwrap();
+        let writer = Database::open(path).await.unwrap();
+        writer
+            .connection()
+            .call(|conn| {
+                conn.execute(
+                    "INSERT INTO sessions (id, channel) VALUES ('committed', 'cli')",
+                    [],
+                )
+            })
+            .await
+            .unwrap();
+
+        let pool = ReadPool::open(path, 2).await.unwrap();
+        assert_eq!(pool.len(), 2);
+
+        // Hold a write transaction open on the writer's thread until told to
+        // finish.
+        let (opened_tx, opened_rx) = tokio::sync::oneshot::channel();
+        let (finish_tx, finish_rx) = std::sync::mpsc::channel::<()>();
+        let write = tokio::spawn(async move {
+            writer
+                .connection()
+                .call(move |conn| {
+                    let tx = conn.transaction()?;
+                    tx.execute(
+                        "INSERT INTO sessions (id, channel) VALUES ('pending', 'cli')",
+                        [],
+                    )?;
+                    opened_tx.send(()).unwrap();
+                    finish_rx.recv().unwrap();
+                    tx.commit()
+                })
+                .await
+                .unwrap();
+            writer
+        });
+        opened_rx.await.unwrap();
+
+        // The reader is not blocked and sees only committed rows.
+        let ids: Vec<String> = tokio::time::timeout(
+            std::time::Duration::from_secs(2),
+            pool.get().connection().call(|conn| {
+                let mut stmt = conn.prepare("SELECT id FROM sessions ORDER BY id")?;
+                let rows = stmt.query_map([], |row| row.get(0))?;
+                rows.collect::<Result<Vec<String>, _>>()
+            }),
+        )
+        .await
+        .expect("read should not wait for the open write transaction")
+        .unwrap();
+        assert_eq!(ids, ["committed"]);
+
+        finish_tx.send(()).unwrap();
+        let writer = write.await.unwrap();
+        writer.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn readers_reject_writes() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("ro.db");
+        let path = path.to_str().unwrap();
+        Database::open(path).await.unwrap().close().await.unwrap();
+
+        let pool = ReadPool::open(path, 1).await.unwrap();
+        assert!(pool.get().is_read_only());
+        let result = pool
+            .get()
+            .connection()
+            .call(|conn| conn.execute("INSERT INTO sessions (id, channel) VALUES ('x', 'cli')", []))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn open_fails_for_missing_database() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("missing.db");
+        assert!(ReadPool::open(path.to_str().unwrap(), 1).await.is_err());
+        assert!(!path.exists(), "read-only open must not create the file");
+    }
+}
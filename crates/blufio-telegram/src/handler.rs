@@ -6,15 +6,56 @@
 //! Determines whether an incoming Telegram message should be processed
 //! based on authorization rules and chat type, then extracts the content
 //! into a channel-agnostic [`InboundMessage`].
+//!
+//! DMs are always handled. Group chats are opt-in via
+//! `telegram.allow_groups`, and by default only messages that @-mention or
+//! reply to the bot get a response.
 
-use blufio_core::error::BlufioError;
+use blufio_config::model::TelegramConfig;
+use blufio_core::error::{BlufioError, ChannelErrorKind};
+use blufio_core::traits::TranscriptionAdapter;
 use blufio_core::types::{InboundMessage, MessageContent};
 use teloxide::prelude::*;
-use teloxide::types::ChatKind;
+use teloxide::types::{ChatKind, MessageEntityKind, UserId};
 use tracing::debug;
 
 use crate::media;
 
+/// Reply to a voice message when no speech-to-text backend is configured.
+pub const VOICE_UNAVAILABLE_NOTE: &str =
+    "Sorry, I can't listen to voice messages here yet. Please send your message as text.";
+
+/// Reply to a voice message whose transcription failed.
+pub const VOICE_FAILED_NOTE: &str =
+    "Sorry, I couldn't make out that voice message. Please try again or send it as text.";
+
+/// Reply to a photo with no resolution under `telegram.max_image_bytes`.
+pub const IMAGE_TOO_LARGE_NOTE: &str =
+    "Sorry, that image is too large for me to look at. Please send a smaller one.";
+
+/// Returns the note to send instead of processing a voice message.
+///
+/// Without a transcriber the agent would get only a placeholder, so voice
+/// messages are ignored and the user is told why.
+pub fn voice_note(msg: &Message, can_transcribe: bool) -> Option<&'static str> {
+    (msg.voice().is_some() && !can_transcribe).then_some(VOICE_UNAVAILABLE_NOTE)
+}
+
+/// Returns the note explaining why a message's content couldn't be used.
+pub fn extraction_failure_note(msg: &Message, error: &BlufioError) -> Option<&'static str> {
+    if msg.voice().is_some() {
+        return Some(VOICE_FAILED_NOTE);
+    }
+    let too_large = matches!(
+        error,
+        BlufioError::Channel {
+            kind: ChannelErrorKind::MessageTooLarge,
+            ..
+        }
+    );
+    (msg.photo().is_some() && too_large).then_some(IMAGE_TOO_LARGE_NOTE)
+}
+
 /// Checks whether the message sender is authorized.
 ///
 /// Authorization passes if the sender's user ID (as string) or username
@@ -58,13 +99,93 @@ pub fn is_dm(msg: &Message) -> bool {
     matches!(msg.chat.kind, ChatKind::Private(_))
 }
 
+/// Checks whether the message is from a group or supergroup chat.
+pub fn is_group(msg: &Message) -> bool {
+    msg.chat.is_group() || msg.chat.is_supergroup()
+}
+
+/// The bot's own Telegram identity, used to recognise mentions and replies.
+#[derive(Debug, Clone)]
+pub struct BotIdentity {
+    /// The bot's user ID.
+    pub id: UserId,
+    /// The bot's username, without the `@` prefix.
+    pub username: Option<String>,
+}
+
+/// Checks whether the message text or caption @-mentions the bot.
+pub fn is_mentioned(msg: &Message, bot: &BotIdentity) -> bool {
+    let entities = msg
+        .parse_entities()
+        .or_else(|| msg.parse_caption_entities())
+        .unwrap_or_default();
+
+    entities.iter().any(|entity| match entity.kind() {
+        MessageEntityKind::Mention => {
+            let name = entity.text().strip_prefix('@').unwrap_or(entity.text());
+            bot.username
+                .as_deref()
+                .is_some_and(|username| username.eq_ignore_ascii_case(name))
+        }
+        MessageEntityKind::TextMention { user } => user.id == bot.id,
+        _ => false,
+    })
+}
+
+/// Checks whether the message is a reply to one of the bot's own messages.
+pub fn is_reply_to_bot(msg: &Message, bot: &BotIdentity) -> bool {
+    msg.reply_to_message()
+        .and_then(|reply| reply.from.as_ref())
+        .is_some_and(|user| user.id == bot.id)
+}
+
+/// Returns true if the bot should respond to this message.
+///
+/// DMs always get a response. Group and supergroup messages only when
+/// `allow_groups` is set and, with `respond_on_mention_only`, the message
+/// mentions or replies to the bot. Channel posts are never handled.
+pub fn should_respond(msg: &Message, config: &TelegramConfig, bot: Option<&BotIdentity>) -> bool {
+    if is_dm(msg) {
+        return true;
+    }
+    if !is_group(msg) || !config.allow_groups {
+        return false;
+    }
+    if !config.respond_on_mention_only {
+        return true;
+    }
+    bot.is_some_and(|bot| is_mentioned(msg, bot) || is_reply_to_bot(msg, bot))
+}
+
+/// Strips the bot @mention from message text.
+///
+/// Telegram usernames are case-insensitive, so the mention is matched
+/// ignoring ASCII case.
+pub fn strip_mention(text: &str, username: &str) -> String {
+    let mention = format!("@{}", username.to_ascii_lowercase());
+    // ASCII lowercasing keeps byte offsets, so matches index `text` too.
+    let lower = text.to_ascii_lowercase();
+    let mut stripped = String::with_capacity(text.len());
+    let mut rest = 0;
+    for (start, _) in lower.match_indices(&mention) {
+        stripped.push_str(&text[rest..start]);
+        rest = start + mention.len();
+    }
+    stripped.push_str(&text[rest..]);
+    stripped.trim().to_string()
+}
+
 /// Extracts content from a Telegram message.
 ///
-/// Handles text, photo, document, and voice message types.
+/// Handles text, photo, document, and voice message types. Photos are
+/// limited to `max_image_bytes`. Voice messages become their transcript when
+/// a `transcriber` is given, and raw audio otherwise.
 /// Returns `None` for unsupported message types (stickers, locations, etc.).
 pub async fn extract_content(
     bot: &Bot,
     msg: &Message,
+    transcriber: Option<&(dyn TranscriptionAdapter + Send + Sync)>,
+    max_image_bytes: usize,
 ) -> Result<Option<MessageContent>, BlufioError> {
     // Text message
     if let Some(text) = msg.text() {
@@ -74,7 +195,7 @@ pub async fn extract_content(
     // Photo message
     if let Some(photos) = msg.photo() {
         let caption = msg.caption();
-        let content = media::extract_photo_content(bot, photos, caption).await?;
+        let content = media::extract_photo_content(bot, photos, caption, max_image_bytes).await?;
         return Ok(Some(content));
     }
 
@@ -86,7 +207,10 @@ pub async fn extract_content(
 
     // Voice message
     if let Some(voice) = msg.voice() {
-        let content = media::extract_voice_content(bot, voice).await?;
+        let content = match transcriber {
+            Some(transcriber) => media::extract_voice_transcript(bot, voice, transcriber).await?,
+            None => media::extract_voice_content(bot, voice).await?,
+        };
         return Ok(Some(content));
     }
 
@@ -96,19 +220,42 @@ pub async fn extract_content(
 }
 
 /// Converts a Telegram message and extracted content into an [`InboundMessage`].
-pub fn to_inbound_message(msg: &Message, content: MessageContent) -> InboundMessage {
-    let sender_id = msg
+///
+/// In groups the sender ID is `<chat_id>:<user_id>`, so each member of a
+/// shared group gets their own session. Whether the message mentions or
+/// replies to `bot` is recorded in the metadata.
+pub fn to_inbound_message(
+    msg: &Message,
+    content: MessageContent,
+    bot: Option<&BotIdentity>,
+) -> InboundMessage {
+    let user_id = msg
         .from
         .as_ref()
         .map(|u| u.id.0.to_string())
         .unwrap_or_else(|| "unknown".to_string());
+    let chat_id = msg.chat.id.0.to_string();
+    let group = is_group(msg);
+
+    let sender_id = if group {
+        format!("{chat_id}:{user_id}")
+    } else {
+        user_id.clone()
+    };
 
     let timestamp = chrono::DateTime::to_rfc3339(&msg.date);
 
     // Store chat_id in metadata for routing responses back
     let metadata = Some(
         serde_json::json!({
-            "chat_id": msg.chat.id.0.to_string(),
+            "chat_id": chat_id,
+            "user_id": user_id,
+            "is_group": group,
+            "mentioned": bot.is_some_and(|b| is_mentioned(msg, b)),
+            "reply_to_bot": bot.is_some_and(|b| is_reply_to_bot(msg, b)),
+            // Kept when a voice note arrives as its transcript, so the
+            // reply can still be spoken back.
+            "voice": msg.voice().is_some(),
         })
         .to_string(),
     );
@@ -162,7 +309,13 @@ mod tests {
 
     /// Build a mock group chat message.
     fn make_group_message(user_id: u64, text: &str) -> Message {
-        let json = serde_json::json!({
+        serde_json::from_value(group_message_json(user_id, text))
+            .expect("failed to deserialize mock group message")
+    }
+
+    /// JSON for a supergroup message, for tests that add entities or replies.
+    fn group_message_json(user_id: u64, text: &str) -> serde_json::Value {
+        serde_json::json!({
             "message_id": 1,
             "date": 1700000000i64,
             "chat": {
@@ -176,9 +329,53 @@ mod tests {
                 "first_name": "Test",
             },
             "text": text,
-        });
+        })
+    }
+
+    const BOT_ID: u64 = 999;
 
-        serde_json::from_value(json).expect("failed to deserialize mock group message")
+    fn bot_identity() -> BotIdentity {
+        BotIdentity {
+            id: UserId(BOT_ID),
+            username: Some("blufio_bot".to_string()),
+        }
+    }
+
+    fn group_config(respond_on_mention_only: bool) -> TelegramConfig {
+        TelegramConfig {
+            allow_groups: true,
+            respond_on_mention_only,
+            ..Default::default()
+        }
+    }
+
+    /// Build a mock group message whose text starts with an @mention entity.
+    fn make_group_mention(user_id: u64, mention: &str, rest: &str) -> Message {
+        let text = format!("{mention} {rest}");
+        let mut json = group_message_json(user_id, &text);
+        json["entities"] = serde_json::json!([{
+            "type": "mention",
+            "offset": 0,
+            "length": mention.encode_utf16().count(),
+        }]);
+        serde_json::from_value(json).expect("failed to deserialize mock mention message")
+    }
+
+    /// Build a mock group message replying to a message from `replied_user_id`.
+    fn make_group_reply(user_id: u64, replied_user_id: u64, text: &str) -> Message {
+        let mut json = group_message_json(user_id, text);
+        json["reply_to_message"] = serde_json::json!({
+            "message_id": 0,
+            "date": 1699999999i64,
+            "chat": json["chat"].clone(),
+            "from": {
+                "id": replied_user_id,
+                "is_bot": replied_user_id == BOT_ID,
+                "first_name": "Replied",
+            },
+            "text": "earlier",
+        });
+        serde_json::from_value(json).expect("failed to deserialize mock reply message")
     }
 
     /// Build a mock message without a sender.
@@ -255,7 +452,7 @@ mod tests {
     fn to_inbound_message_maps_fields() {
         let msg = make_private_message(12345, Some("testuser"), "hello");
         let content = MessageContent::Text("hello".into());
-        let inbound = to_inbound_message(&msg, content);
+        let inbound = to_inbound_message(&msg, content, None);
 
         assert_eq!(inbound.id, "1");
         assert_eq!(inbound.channel, "telegram");
@@ -267,16 +464,280 @@ mod tests {
         let meta: serde_json::Value =
             serde_json::from_str(inbound.metadata.as_ref().unwrap()).unwrap();
         assert_eq!(meta["chat_id"], "12345");
+        assert_eq!(meta["is_group"], false);
+        assert_eq!(meta["mentioned"], false);
+    }
+
+    #[test]
+    fn dm_always_gets_response() {
+        let msg = make_private_message(12345, None, "hello");
+        let bot = bot_identity();
+        assert!(should_respond(&msg, &TelegramConfig::default(), None));
+        assert!(should_respond(&msg, &group_config(true), Some(&bot)));
+    }
+
+    #[test]
+    fn group_ignored_unless_allowed() {
+        let msg = make_group_mention(12345, "@blufio_bot", "hello");
+        let bot = bot_identity();
+        assert!(!should_respond(
+            &msg,
+            &TelegramConfig::default(),
+            Some(&bot)
+        ));
+    }
+
+    #[test]
+    fn group_with_mention_gets_response() {
+        let msg = make_group_mention(12345, "@Blufio_Bot", "hello");
+        let bot = bot_identity();
+        assert!(is_mentioned(&msg, &bot));
+        assert!(should_respond(&msg, &group_config(true), Some(&bot)));
+    }
+
+    #[test]
+    fn group_reply_to_bot_gets_response() {
+        let msg = make_group_reply(12345, BOT_ID, "and then?");
+        let bot = bot_identity();
+        assert!(is_reply_to_bot(&msg, &bot));
+        assert!(should_respond(&msg, &group_config(true), Some(&bot)));
+    }
+
+    #[test]
+    fn group_without_mention_is_ignored() {
+        let bot = bot_identity();
+        let plain = make_group_message(12345, "hello everyone");
+        let other_mention = make_group_mention(12345, "@someone_else", "hello");
+        let reply_to_human = make_group_reply(12345, 777, "agreed");
+
+        for msg in [&plain, &other_mention, &reply_to_human] {
+            assert!(!should_respond(msg, &group_config(true), Some(&bot)));
+        }
+        // Without mention gating every group message is handled.
+        assert!(should_respond(&plain, &group_config(false), Some(&bot)));
+    }
+
+    #[test]
+    fn group_inbound_keys_session_by_chat_and_user() {
+        let bot = bot_identity();
+        let msg = make_group_mention(12345, "@blufio_bot", "hello");
+        let inbound = to_inbound_message(&msg, MessageContent::Text("hello".into()), Some(&bot));
+
+        assert_eq!(inbound.sender_id, "-100123:12345");
+        let meta: serde_json::Value =
+            serde_json::from_str(inbound.metadata.as_ref().unwrap()).unwrap();
+        assert_eq!(meta["chat_id"], "-100123");
+        assert_eq!(meta["user_id"], "12345");
+        assert_eq!(meta["is_group"], true);
+        assert_eq!(meta["mentioned"], true);
+        assert_eq!(meta["reply_to_bot"], false);
+    }
+
+    #[test]
+    fn group_inbound_records_reply_without_mention() {
+        let bot = bot_identity();
+        let msg = make_group_reply(12345, BOT_ID, "and then?");
+        let inbound =
+            to_inbound_message(&msg, MessageContent::Text("and then?".into()), Some(&bot));
+
+        let meta: serde_json::Value =
+            serde_json::from_str(inbound.metadata.as_ref().unwrap()).unwrap();
+        assert_eq!(meta["mentioned"], false);
+        assert_eq!(meta["reply_to_bot"], true);
+    }
+
+    #[test]
+    fn strip_mention_removes_bot_username() {
+        assert_eq!(
+            strip_mention("@blufio_bot what time is it?", "blufio_bot"),
+            "what time is it?"
+        );
+        assert_eq!(
+            strip_mention("no mention here", "blufio_bot"),
+            "no mention here"
+        );
+    }
+
+    #[test]
+    fn strip_mention_ignores_case() {
+        assert_eq!(
+            strip_mention("@Blufio_Bot what time is it?", "blufio_bot"),
+            "what time is it?"
+        );
+        assert_eq!(
+            strip_mention("hey @blufio_bot, ¿qué hora es?", "Blufio_Bot"),
+            "hey , ¿qué hora es?"
+        );
     }
 
     #[tokio::test]
     async fn extract_text_content() {
         let msg = make_private_message(12345, None, "hello world");
         let bot = Bot::new("test:token");
-        let content = extract_content(&bot, &msg).await.unwrap();
+        let content = extract_content(&bot, &msg, None, usize::MAX).await.unwrap();
         match content {
             Some(MessageContent::Text(t)) => assert_eq!(t, "hello world"),
             other => panic!("expected Some(Text), got {other:?}"),
         }
     }
+
+    fn make_voice_message(user_id: u64) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "message_id": 7,
+            "date": 1700000000i64,
+            "chat": {"id": user_id as i64, "type": "private", "first_name": "Test"},
+            "from": {"id": user_id, "is_bot": false, "first_name": "Test"},
+            "voice": {
+                "file_id": "voice-1",
+                "file_unique_id": "voice-u1",
+                "duration": 3,
+                "mime_type": "audio/ogg",
+                "file_size": 4
+            }
+        }))
+        .expect("failed to deserialize mock voice message")
+    }
+
+    #[tokio::test]
+    async fn voice_message_is_downloaded_and_transcribed() {
+        use wiremock::matchers::{method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex("(?i)/getfile$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ok": true,
+                "result": {
+                    "file_id": "voice-1",
+                    "file_unique_id": "voice-u1",
+                    "file_size": 4,
+                    "file_path": "voice/file_1.oga"
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex("/file/bot.+/voice/file_1.oga$"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![1u8, 2, 3, 4]))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let bot = Bot::new("test:token").set_api_url(reqwest::Url::parse(&server.uri()).unwrap());
+        let transcriber = blufio_test_utils::MockTranscriber::new(Ok("what's the weather"));
+        let msg = make_voice_message(12345);
+
+        let content = extract_content(&bot, &msg, Some(&transcriber), usize::MAX)
+            .await
+            .unwrap();
+        match content {
+            Some(MessageContent::Text(t)) => assert_eq!(t, "what's the weather"),
+            other => panic!("expected Some(Text), got {other:?}"),
+        }
+        let requests = transcriber.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].audio_data, vec![1, 2, 3, 4]);
+
+        let inbound = to_inbound_message(&msg, MessageContent::Text("x".into()), None);
+        let meta: serde_json::Value =
+            serde_json::from_str(inbound.metadata.as_ref().unwrap()).unwrap();
+        assert_eq!(meta["voice"], true);
+    }
+
+    fn make_photo_message(user_id: u64, caption: &str) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "message_id": 8,
+            "date": 1700000000i64,
+            "chat": {"id": user_id as i64, "type": "private", "first_name": "Test"},
+            "from": {"id": user_id, "is_bot": false, "first_name": "Test"},
+            "caption": caption,
+            "photo": [
+                {
+                    "file_id": "photo-s", "file_unique_id": "ps",
+                    "width": 90, "height": 90, "file_size": 4
+                },
+                {
+                    "file_id": "photo-l", "file_unique_id": "pl",
+                    "width": 1280, "height": 1280, "file_size": 900000
+                }
+            ]
+        }))
+        .expect("failed to deserialize mock photo message")
+    }
+
+    #[tokio::test]
+    async fn photo_becomes_multimodal_inbound_message() {
+        use wiremock::matchers::{body_partial_json, method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        // The full-size variant is over the limit, so the small one is used.
+        Mock::given(method("POST"))
+            .and(path_regex("(?i)/getfile$"))
+            .and(body_partial_json(serde_json::json!({"file_id": "photo-s"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ok": true,
+                "result": {
+                    "file_id": "photo-s",
+                    "file_unique_id": "ps",
+                    "file_size": 4,
+                    "file_path": "photos/file_2.jpg"
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex("/file/bot.+/photos/file_2.jpg$"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0xFFu8, 0xD8, 0xFF, 0xE0]))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let bot = Bot::new("test:token").set_api_url(reqwest::Url::parse(&server.uri()).unwrap());
+        let msg = make_photo_message(12345, "what is this?");
+
+        let content = extract_content(&bot, &msg, None, 100_000)
+            .await
+            .unwrap()
+            .unwrap();
+        let inbound = to_inbound_message(&msg, content, None);
+        match inbound.content {
+            MessageContent::Image {
+                data,
+                mime_type,
+                caption,
+            } => {
+                assert_eq!(data, vec![0xFF, 0xD8, 0xFF, 0xE0]);
+                assert_eq!(mime_type, "image/jpeg");
+                assert_eq!(caption.as_deref(), Some("what is this?"));
+            }
+            other => panic!("expected Image, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn photo_over_limit_is_rejected_with_note() {
+        let bot = Bot::new("test:token");
+        let msg = make_photo_message(12345, "too big");
+
+        // No variant fits, so nothing is downloaded.
+        let err = extract_content(&bot, &msg, None, 2).await.unwrap_err();
+        assert_eq!(
+            extraction_failure_note(&msg, &err),
+            Some(IMAGE_TOO_LARGE_NOTE)
+        );
+    }
+
+    #[test]
+    fn voice_without_transcriber_gets_note() {
+        let voice = make_voice_message(12345);
+        assert_eq!(voice_note(&voice, false), Some(VOICE_UNAVAILABLE_NOTE));
+        assert_eq!(voice_note(&voice, true), None);
+
+        let text = make_private_message(12345, None, "hello");
+        assert_eq!(voice_note(&text, false), None);
+    }
 }
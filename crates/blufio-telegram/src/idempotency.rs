@@ -0,0 +1,110 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Idempotent delivery tracking for outbound Telegram messages.
+//!
+//! A send can succeed on Telegram's side but fail locally (e.g. the response
+//! is lost), so a retry would post the message twice. [`RecentSends`]
+//! remembers the `MessageId` delivered for each idempotency key for a
+//! configurable window, letting `send` short-circuit repeated deliveries.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use blufio_core::types::MessageId;
+
+/// Maximum number of keys remembered at once; the oldest is evicted first.
+const MAX_TRACKED_KEYS: usize = 256;
+
+/// Bounded, time-windowed record of recently delivered idempotency keys.
+#[derive(Debug)]
+pub struct RecentSends {
+    window: Duration,
+    entries: VecDeque<(String, MessageId, Instant)>,
+}
+
+impl RecentSends {
+    /// Create a tracker that remembers keys for `window`.
+    ///
+    /// A zero window disables tracking.
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            entries: VecDeque::new(),
+        }
+    }
+
+    /// Returns the `MessageId` recorded for `key` if it was delivered within the window.
+    pub fn get(&mut self, key: &str) -> Option<MessageId> {
+        self.evict_expired(Instant::now());
+        self.entries
+            .iter()
+            .find(|(k, _, _)| k == key)
+            .map(|(_, id, _)| id.clone())
+    }
+
+    /// Record that `key` was delivered as `id`.
+    pub fn record(&mut self, key: &str, id: MessageId) {
+        if self.window.is_zero() {
+            return;
+        }
+        let now = Instant::now();
+        self.evict_expired(now);
+        self.entries.retain(|(k, _, _)| k != key);
+        if self.entries.len() >= MAX_TRACKED_KEYS {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((key.to_string(), id, now));
+    }
+
+    fn evict_expired(&mut self, now: Instant) {
+        while let Some((_, _, sent_at)) = self.entries.front() {
+            if now.duration_since(*sent_at) < self.window {
+                break;
+            }
+            self.entries.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remembers_key_within_window() {
+        let mut recent = RecentSends::new(Duration::from_secs(60));
+        assert!(recent.get("k1").is_none());
+        recent.record("k1", MessageId("42".into()));
+        assert_eq!(recent.get("k1").unwrap().0, "42");
+        assert!(recent.get("k2").is_none());
+    }
+
+    #[test]
+    fn forgets_key_after_window() {
+        let mut recent = RecentSends::new(Duration::from_millis(10));
+        recent.record("k1", MessageId("42".into()));
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(recent.get("k1").is_none());
+    }
+
+    #[test]
+    fn zero_window_disables_tracking() {
+        let mut recent = RecentSends::new(Duration::ZERO);
+        recent.record("k1", MessageId("42".into()));
+        assert!(recent.get("k1").is_none());
+    }
+
+    #[test]
+    fn evicts_oldest_when_full() {
+        let mut recent = RecentSends::new(Duration::from_secs(60));
+        for i in 0..=MAX_TRACKED_KEYS {
+            recent.record(&format!("k{i}"), MessageId(i.to_string()));
+        }
+        assert!(recent.get("k0").is_none());
+        assert_eq!(
+            recent.get(&format!("k{MAX_TRACKED_KEYS}")).unwrap().0,
+            MAX_TRACKED_KEYS.to_string()
+        );
+    }
+}
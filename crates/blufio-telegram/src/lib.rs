@@ -9,38 +9,70 @@
 //! and MarkdownV2 formatting.
 
 pub mod handler;
+pub mod idempotency;
 pub mod markdown;
 pub mod media;
 pub mod streaming;
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use blufio_config::model::TelegramConfig;
 use blufio_core::error::{BlufioError, ChannelErrorKind, ErrorContext};
-use blufio_core::format::{FormatPipeline, split_at_paragraphs};
-use blufio_core::traits::{ChannelAdapter, PluginAdapter};
+use blufio_core::format::FormatPipeline;
+use blufio_core::traits::{ChannelAdapter, PluginAdapter, TranscriptionAdapter};
 use blufio_core::types::{
-    AdapterType, ChannelCapabilities, FormattingSupport, HealthStatus, InboundMessage, MessageId,
-    OutboundMessage, RateLimit, StreamingType,
+    AdapterType, ChannelCapabilities, FormattingSupport, HealthStatus, InboundMessage,
+    MessageContent, MessageId, OutboundMessage, RateLimit, StreamingType,
 };
 use teloxide::prelude::*;
-use teloxide::types::{ChatAction, ChatId, ParseMode, Recipient};
+use teloxide::types::{ChatAction, ChatId, InputFile, ParseMode, Recipient};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
+use crate::idempotency::RecentSends;
+use crate::streaming::EditThrottle;
+
+/// Maximum length of a single Telegram text message.
+const TELEGRAM_MAX_MESSAGE_LENGTH: usize = 4096;
+
 /// Telegram channel adapter implementing [`ChannelAdapter`].
 ///
 /// Connects to Telegram via long polling, filters messages by authorization
-/// and chat type, and delivers responses with edit-in-place streaming.
+/// and chat type (DMs, plus groups when `allow_groups` is set), and delivers
+/// responses with edit-in-place streaming.
 pub struct TelegramChannel {
     bot: Bot,
     config: TelegramConfig,
     inbound_rx: tokio::sync::Mutex<mpsc::Receiver<InboundMessage>>,
     inbound_tx: mpsc::Sender<InboundMessage>,
     polling_handle: Option<tokio::task::JoinHandle<()>>,
+    /// Recently delivered idempotency keys, for deduplicating retried sends.
+    recent_sends: tokio::sync::Mutex<RecentSends>,
+    /// Replies being streamed into a live message, keyed by chat ID. Each
+    /// reply has its own lock so one chat's API calls never stall another's.
+    live_replies: Arc<tokio::sync::Mutex<HashMap<i64, SharedLiveReply>>>,
+    /// Speech-to-text backend for inbound voice messages.
+    transcriber: Option<Arc<dyn TranscriptionAdapter + Send + Sync>>,
+}
+
+/// A reply that is being shown while it streams in.
+///
+/// The first delta posts a message; later deltas are coalesced and edited
+/// into it at most once per throttle interval. The final `send` edits the
+/// complete reply into the same message instead of posting a new one, and
+/// ends the live reply so later deltas start a new message.
+struct LiveReply {
+    message_id: Option<teloxide::types::MessageId>,
+    text: String,
+    throttle: EditThrottle,
 }
 
+/// A [`LiveReply`] locked on its own, apart from the map of all chats.
+type SharedLiveReply = Arc<tokio::sync::Mutex<LiveReply>>;
+
 impl TelegramChannel {
     /// Creates a new Telegram channel adapter.
     ///
@@ -58,6 +90,7 @@ impl TelegramChannel {
 
         let bot = Bot::new(token);
         let (inbound_tx, inbound_rx) = mpsc::channel(100);
+        let recent_sends = RecentSends::new(Duration::from_secs(config.idempotency_window_secs));
 
         Ok(Self {
             bot,
@@ -65,6 +98,9 @@ impl TelegramChannel {
             inbound_rx: tokio::sync::Mutex::new(inbound_rx),
             inbound_tx,
             polling_handle: None,
+            recent_sends: tokio::sync::Mutex::new(recent_sends),
+            live_replies: Arc::new(tokio::sync::Mutex::new(HashMap::new())),
+            transcriber: None,
         })
     }
 
@@ -72,6 +108,75 @@ impl TelegramChannel {
     pub fn bot(&self) -> &Bot {
         &self.bot
     }
+
+    /// Sets the speech-to-text backend that turns voice messages into text.
+    ///
+    /// Without one, voice messages are answered with a note and not
+    /// forwarded to the agent.
+    pub fn set_transcriber(&mut self, transcriber: Arc<dyn TranscriptionAdapter + Send + Sync>) {
+        self.transcriber = Some(transcriber);
+    }
+
+    fn edit_throttle(&self) -> Duration {
+        Duration::from_millis(self.config.edit_throttle_ms)
+    }
+
+    /// The chat's live reply, if one is streaming.
+    async fn live_reply(&self, chat_id: ChatId) -> Option<SharedLiveReply> {
+        self.live_replies.lock().await.get(&chat_id.0).cloned()
+    }
+
+    /// Ends the chat's live reply and returns its streamed message, if any,
+    /// for the final reply to be edited into.
+    ///
+    /// Deltas sent after this, e.g. for a proactive message, start a new
+    /// live reply rather than extending the committed one.
+    async fn claim_live_reply(&self, chat_id: ChatId) -> Option<teloxide::types::MessageId> {
+        let reply = self.live_replies.lock().await.remove(&chat_id.0)?;
+        // Waits out a delta still being posted, so its message ID is seen.
+        let reply = reply.lock().await;
+        reply.message_id
+    }
+
+    /// Edits the final text of a streamed reply into its live message.
+    ///
+    /// Returns `false` when the message could not be edited, so the caller
+    /// can post the chunk as a new message instead.
+    async fn finalize_live_reply(
+        &self,
+        chat_id: ChatId,
+        message_id: teloxide::types::MessageId,
+        chunk: &str,
+        markdown: bool,
+    ) -> bool {
+        let result = if markdown {
+            match self
+                .bot
+                .edit_message_text(chat_id, message_id, chunk)
+                .parse_mode(ParseMode::MarkdownV2)
+                .await
+            {
+                Err(e) if e.to_string().contains("can't parse entities") => {
+                    warn!(error = %e, "MarkdownV2 final edit failed, retrying as plain text");
+                    metrics::counter!("blufio_format_fallback_total", "channel" => "telegram")
+                        .increment(1);
+                    self.bot.edit_message_text(chat_id, message_id, chunk).await
+                }
+                other => other,
+            }
+        } else {
+            self.bot.edit_message_text(chat_id, message_id, chunk).await
+        };
+
+        match result {
+            Ok(_) => true,
+            Err(e) if e.to_string().contains("message is not modified") => true,
+            Err(e) => {
+                warn!(error = %e, "failed to finalize streamed reply, sending a new message");
+                false
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -116,7 +221,7 @@ impl ChannelAdapter for TelegramChannel {
             supports_images: true,
             supports_documents: true,
             supports_voice: true,
-            max_message_length: Some(4096),
+            max_message_length: Some(TELEGRAM_MAX_MESSAGE_LENGTH),
             supports_embeds: false,
             supports_reactions: false,
             supports_threads: false,
@@ -137,33 +242,97 @@ impl ChannelAdapter for TelegramChannel {
             return Ok(()); // Already connected
         }
 
+        // Mention and reply detection in groups needs the bot's own identity.
+        let bot_identity = if self.config.allow_groups {
+            let me = self.bot.get_me().await.map_err(|e| BlufioError::Channel {
+                kind: ChannelErrorKind::ConnectionLost,
+                context: ErrorContext {
+                    channel_name: Some("telegram".to_string()),
+                    ..Default::default()
+                },
+                source: Some(Box::new(e)),
+            })?;
+            Some(Arc::new(handler::BotIdentity {
+                id: me.user.id,
+                username: me.user.username.clone(),
+            }))
+        } else {
+            None
+        };
+
         let bot = self.bot.clone();
         let tx = self.inbound_tx.clone();
-        let allowed_users: Arc<Vec<String>> = Arc::new(self.config.allowed_users.clone());
+        let config = Arc::new(self.config.clone());
+        let live_replies = self.live_replies.clone();
+        let transcriber = self.transcriber.clone();
 
-        info!("starting Telegram long polling");
+        info!(
+            allow_groups = config.allow_groups,
+            "starting Telegram long polling"
+        );
 
         let handle = tokio::spawn(async move {
             let handler = Update::filter_message().endpoint(move |bot: Bot, msg: Message| {
                 let tx = tx.clone();
-                let allowed = allowed_users.clone();
+                let config = config.clone();
+                let bot_identity = bot_identity.clone();
+                let live_replies = live_replies.clone();
+                let transcriber = transcriber.clone();
                 async move {
-                    // Filter: DMs only
-                    if !handler::is_dm(&msg) {
-                        debug!(chat_id = msg.chat.id.0, "ignoring non-DM message");
+                    // Filter: DMs, plus groups when enabled (mention-gated by default)
+                    if !handler::should_respond(&msg, &config, bot_identity.as_deref()) {
+                        debug!(
+                            chat_id = msg.chat.id.0,
+                            "ignoring message not addressed to bot"
+                        );
                         return respond(());
                     }
 
                     // Filter: authorized users only
-                    if !handler::is_authorized(&msg, &allowed) {
+                    if !handler::is_authorized(&msg, &config.allowed_users) {
                         debug!(chat_id = msg.chat.id.0, "ignoring unauthorized user");
                         return respond(());
                     }
 
+                    // Voice needs speech-to-text before the agent can use it.
+                    if let Some(note) = handler::voice_note(&msg, transcriber.is_some()) {
+                        debug!(
+                            chat_id = msg.chat.id.0,
+                            "ignoring voice message without transcriber"
+                        );
+                        if let Err(e) = bot.send_message(msg.chat.id, note).await {
+                            warn!(error = %e, "failed to send voice message note");
+                        }
+                        return respond(());
+                    }
+
                     // Extract content
-                    match handler::extract_content(&bot, &msg).await {
+                    match handler::extract_content(
+                        &bot,
+                        &msg,
+                        transcriber.as_deref(),
+                        config.max_image_bytes,
+                    )
+                    .await
+                    {
                         Ok(Some(content)) => {
-                            let inbound = handler::to_inbound_message(&msg, content);
+                            // Drop the @mention so the model sees only the question.
+                            let content = match (content, bot_identity.as_deref()) {
+                                (
+                                    MessageContent::Text(text),
+                                    Some(handler::BotIdentity {
+                                        username: Some(username),
+                                        ..
+                                    }),
+                                ) if handler::is_group(&msg) => {
+                                    MessageContent::Text(handler::strip_mention(&text, username))
+                                }
+                                (content, _) => content,
+                            };
+                            let inbound =
+                                handler::to_inbound_message(&msg, content, bot_identity.as_deref());
+                            // A new turn streams into a new message.
+                            live_replies.lock().await.remove(&msg.chat.id.0);
                             if tx.send(inbound).await.is_err() {
                                 warn!("inbound channel closed, dropping message");
                             }
@@ -173,6 +342,11 @@ impl ChannelAdapter for TelegramChannel {
                         }
                         Err(e) => {
                             error!(error = %e, "failed to extract message content");
+                            if let Some(note) = handler::extraction_failure_note(&msg, &e)
+                                && let Err(e) = bot.send_message(msg.chat.id, note).await
+                            {
+                                warn!(error = %e, "failed to send content note");
+                            }
                         }
                     }
 
@@ -192,17 +366,43 @@ impl ChannelAdapter for TelegramChannel {
     }
 
     async fn send(&self, msg: OutboundMessage) -> Result<MessageId, BlufioError> {
+        // A retried delivery that already reached Telegram is not re-sent.
+        if let Some(key) = msg.idempotency_key.as_deref()
+            && let Some(id) = self.recent_sends.lock().await.get(key)
+        {
+            debug!(idempotency_key = key, "skipping duplicate send");
+            return Ok(id);
+        }
+
         let chat_id = extract_chat_id(&msg)?;
         let caps = self.capabilities();
 
         // Pipeline: detect_and_format -> adapter_escape -> split -> send each chunk
         let formatted = FormatPipeline::detect_and_format(&msg.content, &caps);
         let escaped = markdown::format_for_telegram(&formatted);
-        let chunks = split_at_paragraphs(&escaped, caps.max_message_length);
-
-        let mut first_id = None;
+        let chunks = markdown::split_for_telegram(
+            &escaped,
+            caps.max_message_length
+                .unwrap_or(TELEGRAM_MAX_MESSAGE_LENGTH),
+        );
+
+        let mut last_id = None;
+        let markdown = msg.parse_mode.as_deref() == Some("MarkdownV2") || msg.parse_mode.is_none();
+
+        // A reply already shown while streaming gets its final text edited
+        // into the live message rather than posted again.
+        let mut skip = 0;
+        if let Some(live_id) = self.claim_live_reply(chat_id).await
+            && let Some(first) = chunks.first()
+            && self
+                .finalize_live_reply(chat_id, live_id, first, markdown)
+                .await
+        {
+            last_id = Some(MessageId(live_id.0.to_string()));
+            skip = 1;
+        }
 
-        for chunk in &chunks {
+        for chunk in chunks.iter().skip(skip) {
             if msg.parse_mode.as_deref() == Some("MarkdownV2") || msg.parse_mode.is_none() {
                 // Try MarkdownV2 first, fall back to plain text on parse error
                 match self
@@ -212,9 +412,7 @@ impl ChannelAdapter for TelegramChannel {
                     .await
                 {
                     Ok(sent) => {
-                        if first_id.is_none() {
-                            first_id = Some(MessageId(sent.id.0.to_string()));
-                        }
+                        last_id = Some(MessageId(sent.id.0.to_string()));
                     }
                     Err(e) => {
                         let err_str = e.to_string();
@@ -228,9 +426,7 @@ impl ChannelAdapter for TelegramChannel {
                                 .map_err(|e| {
                                 BlufioError::channel_delivery_failed("telegram", e)
                             })?;
-                            if first_id.is_none() {
-                                first_id = Some(MessageId(sent.id.0.to_string()));
-                            }
+                            last_id = Some(MessageId(sent.id.0.to_string()));
                         } else {
                             return Err(BlufioError::channel_delivery_failed("telegram", e));
                         }
@@ -242,13 +438,74 @@ impl ChannelAdapter for TelegramChannel {
                     .send_message(Recipient::Id(chat_id), chunk)
                     .await
                     .map_err(|e| BlufioError::channel_delivery_failed("telegram", e))?;
-                if first_id.is_none() {
-                    first_id = Some(MessageId(sent.id.0.to_string()));
-                }
+                last_id = Some(MessageId(sent.id.0.to_string()));
             }
         }
 
-        Ok(first_id.unwrap_or_else(|| MessageId(String::new())))
+        let id = last_id.unwrap_or_else(|| MessageId(String::new()));
+        if let Some(key) = msg.idempotency_key.as_deref() {
+            self.recent_sends.lock().await.record(key, id.clone());
+        }
+        Ok(id)
+    }
+
+    async fn send_partial(&self, msg: OutboundMessage) -> Result<(), BlufioError> {
+        if msg.content.is_empty() {
+            return Ok(());
+        }
+        let chat_id = extract_chat_id(&msg)?;
+        let now = Instant::now();
+
+        let reply = self
+            .live_replies
+            .lock()
+            .await
+            .entry(chat_id.0)
+            .or_insert_with(|| {
+                Arc::new(tokio::sync::Mutex::new(LiveReply {
+                    message_id: None,
+                    text: String::new(),
+                    throttle: EditThrottle::new(self.edit_throttle()),
+                }))
+            })
+            .clone();
+        // The chat's reply stays locked across the API call so its deltas
+        // stay in order; other chats are not held up.
+        let mut reply = reply.lock().await;
+        let reply = &mut *reply;
+        reply.text.push_str(&msg.content);
+
+        // Past one message's worth, only the final send can deliver it.
+        if reply.text.len() > TELEGRAM_MAX_MESSAGE_LENGTH || !reply.throttle.on_update(now) {
+            return Ok(());
+        }
+
+        // Partial text is often unbalanced markdown, so it goes out plain.
+        match reply.message_id {
+            None => match self
+                .bot
+                .send_message(Recipient::Id(chat_id), &reply.text)
+                .await
+            {
+                Ok(sent) => {
+                    reply.message_id = Some(sent.id);
+                    reply.throttle.mark_sent(now);
+                }
+                Err(e) => debug!(error = %e, "failed to send streamed reply"),
+            },
+            Some(message_id) => match self
+                .bot
+                .edit_message_text(chat_id, message_id, &reply.text)
+                .await
+            {
+                Ok(_) => reply.throttle.mark_sent(now),
+                Err(e) if e.to_string().contains("message is not modified") => {
+                    reply.throttle.mark_sent(now);
+                }
+                Err(e) => debug!(error = %e, "failed to edit streamed reply"),
+            },
+        }
+        Ok(())
     }
 
     async fn receive(&self) -> Result<InboundMessage, BlufioError> {
@@ -289,6 +546,16 @@ impl ChannelAdapter for TelegramChannel {
                 source: None,
             })?;
 
+        // Edits of a streamed reply carry all its text so far, so deltas
+        // after this point extend it and the throttle restarts from now.
+        if let Some(reply) = self.live_reply(chat_id).await {
+            let mut reply = reply.lock().await;
+            if reply.message_id == Some(msg_id) {
+                reply.text = text.to_string();
+                reply.throttle.mark_sent(Instant::now());
+            }
+        }
+
         let caps = self.capabilities();
         let formatted = FormatPipeline::detect_and_format(text, &caps);
         let escaped = markdown::format_for_telegram(&formatted);
@@ -329,6 +596,25 @@ impl ChannelAdapter for TelegramChannel {
         }
     }
 
+    async fn send_voice(
+        &self,
+        msg: OutboundMessage,
+        audio: Vec<u8>,
+        content_type: &str,
+    ) -> Result<MessageId, BlufioError> {
+        let chat_id = extract_chat_id(&msg)?;
+        let file = InputFile::memory(audio);
+        // Telegram plays only OGG/Opus as a voice note; other formats go out
+        // as an audio file.
+        let sent = if content_type == "audio/ogg" {
+            self.bot.send_voice(Recipient::Id(chat_id), file).await
+        } else {
+            self.bot.send_audio(Recipient::Id(chat_id), file).await
+        }
+        .map_err(|e| BlufioError::channel_delivery_failed("telegram", e))?;
+        Ok(MessageId(sent.id.0.to_string()))
+    }
+
     async fn send_typing(&self, chat_id: &str) -> Result<(), BlufioError> {
         let chat_id = chat_id
             .parse::<i64>()
@@ -352,22 +638,28 @@ impl ChannelAdapter for TelegramChannel {
 }
 
 /// Extracts the chat ID from an outbound message's metadata.
+///
+/// `chat_id` may be a JSON string (`"12345"`) or an integer (`12345`),
+/// depending on how the upstream serialized it.
 fn extract_chat_id(msg: &OutboundMessage) -> Result<ChatId, BlufioError> {
     // Try to get chat_id from metadata
     if let Some(ref metadata) = msg.metadata
         && let Ok(meta) = serde_json::from_str::<serde_json::Value>(metadata)
-        && let Some(chat_id_str) = meta.get("chat_id").and_then(|v| v.as_str())
+        && let Some(chat_id) = meta.get("chat_id").filter(|v| !v.is_null())
     {
-        let id = chat_id_str
-            .parse::<i64>()
-            .map_err(|_e| BlufioError::Channel {
-                kind: ChannelErrorKind::DeliveryFailed,
-                context: ErrorContext {
-                    channel_name: Some("telegram".to_string()),
-                    ..Default::default()
-                },
-                source: None,
-            })?;
+        let id = match chat_id {
+            serde_json::Value::String(s) => s.parse::<i64>().ok(),
+            serde_json::Value::Number(n) => n.as_i64(),
+            _ => None,
+        }
+        .ok_or_else(|| BlufioError::Channel {
+            kind: ChannelErrorKind::DeliveryFailed,
+            context: ErrorContext {
+                channel_name: Some("telegram".to_string()),
+                ..Default::default()
+            },
+            source: None,
+        })?;
         return Ok(ChatId(id));
     }
 
@@ -394,6 +686,7 @@ mod tests {
         let config = TelegramConfig {
             bot_token: None,
             allowed_users: vec![],
+            ..Default::default()
         };
         assert!(TelegramChannel::new(config).is_err());
     }
@@ -403,6 +696,7 @@ mod tests {
         let config = TelegramConfig {
             bot_token: Some(String::new()),
             allowed_users: vec![],
+            ..Default::default()
         };
         assert!(TelegramChannel::new(config).is_err());
     }
@@ -412,6 +706,7 @@ mod tests {
         let config = TelegramConfig {
             bot_token: Some("123456:ABC-DEF1234ghIkl-zyx57W2v1u123ew11".into()),
             allowed_users: vec!["user1".into()],
+            ..Default::default()
         };
         assert!(TelegramChannel::new(config).is_ok());
     }
@@ -421,6 +716,7 @@ mod tests {
         let config = TelegramConfig {
             bot_token: Some("test:token".into()),
             allowed_users: vec![],
+            ..Default::default()
         };
         let channel = TelegramChannel::new(config).unwrap();
         let caps = channel.capabilities();
@@ -441,6 +737,7 @@ mod tests {
             reply_to: None,
             parse_mode: None,
             metadata: Some(r#"{"chat_id":"12345"}"#.into()),
+            idempotency_key: None,
         };
         let id = extract_chat_id(&msg).unwrap();
         assert_eq!(id.0, 12345);
@@ -455,6 +752,7 @@ mod tests {
             reply_to: None,
             parse_mode: None,
             metadata: None,
+            idempotency_key: None,
         };
         let id = extract_chat_id(&msg).unwrap();
         assert_eq!(id.0, 12345);
@@ -469,15 +767,223 @@ mod tests {
             reply_to: None,
             parse_mode: None,
             metadata: None,
+            idempotency_key: None,
         };
         assert!(extract_chat_id(&msg).is_err());
     }
 
+    fn msg_with_metadata(metadata: &str) -> OutboundMessage {
+        OutboundMessage {
+            session_id: None,
+            channel: "telegram".into(),
+            content: "hello".into(),
+            reply_to: None,
+            parse_mode: None,
+            metadata: Some(metadata.into()),
+            idempotency_key: None,
+        }
+    }
+
+    #[test]
+    fn extract_chat_id_from_numeric_metadata() {
+        let id = extract_chat_id(&msg_with_metadata(r#"{"chat_id":12345}"#)).unwrap();
+        assert_eq!(id.0, 12345);
+        // Group chats have negative IDs.
+        let id = extract_chat_id(&msg_with_metadata(r#"{"chat_id":-1001234567890}"#)).unwrap();
+        assert_eq!(id.0, -1_001_234_567_890);
+    }
+
+    #[test]
+    fn extract_chat_id_rejects_invalid_metadata_values() {
+        for meta in [
+            r#"{"chat_id":"abc"}"#,
+            r#"{"chat_id":12.5}"#,
+            r#"{"chat_id":true}"#,
+            r#"{"chat_id":[1]}"#,
+        ] {
+            assert!(extract_chat_id(&msg_with_metadata(meta)).is_err(), "{meta}");
+        }
+    }
+
+    #[test]
+    fn extract_chat_id_missing_in_metadata_falls_back_to_channel() {
+        let mut msg = msg_with_metadata(r#"{"other":"value"}"#);
+        assert!(extract_chat_id(&msg).is_err());
+        msg.channel = "777".into();
+        assert_eq!(extract_chat_id(&msg).unwrap().0, 777);
+    }
+
+    #[tokio::test]
+    async fn repeated_idempotency_key_sends_once() {
+        use wiremock::matchers::{method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path_regex("(?i)/sendmessage$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ok": true,
+                "result": {
+                    "message_id": 42,
+                    "date": 1_700_000_000,
+                    "chat": {"id": 12345, "type": "private", "first_name": "Test"},
+                    "text": "hello"
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = TelegramConfig {
+            bot_token: Some("test:token".into()),
+            ..Default::default()
+        };
+        let mut channel = TelegramChannel::new(config).unwrap();
+        channel.bot =
+            Bot::new("test:token").set_api_url(reqwest::Url::parse(&server.uri()).unwrap());
+
+        let msg = OutboundMessage {
+            session_id: None,
+            channel: "telegram".into(),
+            content: "hello".into(),
+            reply_to: None,
+            parse_mode: None,
+            metadata: Some(r#"{"chat_id":"12345"}"#.into()),
+            idempotency_key: Some("reply-1".into()),
+        };
+        let first = channel.send(msg.clone()).await.unwrap();
+        let second = channel.send(msg).await.unwrap();
+
+        assert_eq!(first.0, "42");
+        assert_eq!(second, first);
+        // `expect(1)` is verified when the mock server drops.
+    }
+
+    #[tokio::test]
+    async fn streamed_deltas_are_throttled_then_finalized() {
+        use wiremock::matchers::{method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let message = serde_json::json!({
+            "ok": true,
+            "result": {
+                "message_id": 42,
+                "date": 1_700_000_000,
+                "chat": {"id": 12345, "type": "private", "first_name": "Test"},
+                "text": "hello"
+            }
+        });
+        let server = MockServer::start().await;
+        // The first delta posts the live message...
+        Mock::given(method("POST"))
+            .and(path_regex("(?i)/sendmessage$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(message.clone()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // ...and the rest, arriving within one throttle interval, are
+        // coalesced into the single final edit.
+        Mock::given(method("POST"))
+            .and(path_regex("(?i)/editmessagetext$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(message))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = TelegramConfig {
+            bot_token: Some("test:token".into()),
+            edit_throttle_ms: 60_000,
+            ..Default::default()
+        };
+        let mut channel = TelegramChannel::new(config).unwrap();
+        channel.bot =
+            Bot::new("test:token").set_api_url(reqwest::Url::parse(&server.uri()).unwrap());
+
+        let mut msg = OutboundMessage {
+            session_id: None,
+            channel: "telegram".into(),
+            content: String::new(),
+            reply_to: None,
+            parse_mode: None,
+            metadata: Some(r#"{"chat_id":"12345"}"#.into()),
+            idempotency_key: None,
+        };
+        let mut full = String::new();
+        for i in 0..50 {
+            msg.content = format!("tok{i} ");
+            full.push_str(&msg.content);
+            channel.send_partial(msg.clone()).await.unwrap();
+        }
+
+        msg.content = full;
+        let id = channel.send(msg).await.unwrap();
+        assert_eq!(id.0, "42");
+        // `expect(1)` on both endpoints is verified when the mock server drops.
+    }
+
+    #[tokio::test]
+    async fn deltas_after_a_committed_reply_start_a_new_message() {
+        use wiremock::matchers::{method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let message = serde_json::json!({
+            "ok": true,
+            "result": {
+                "message_id": 42,
+                "date": 1_700_000_000,
+                "chat": {"id": 12345, "type": "private", "first_name": "Test"},
+                "text": "hello"
+            }
+        });
+        let server = MockServer::start().await;
+        // One live message per reply...
+        Mock::given(method("POST"))
+            .and(path_regex("(?i)/sendmessage$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(message.clone()))
+            .expect(2)
+            .mount(&server)
+            .await;
+        // ...and only the first reply has been finalized.
+        Mock::given(method("POST"))
+            .and(path_regex("(?i)/editmessagetext$"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(message))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = TelegramConfig {
+            bot_token: Some("test:token".into()),
+            edit_throttle_ms: 60_000,
+            ..Default::default()
+        };
+        let mut channel = TelegramChannel::new(config).unwrap();
+        channel.bot =
+            Bot::new("test:token").set_api_url(reqwest::Url::parse(&server.uri()).unwrap());
+
+        let msg = |content: &str| OutboundMessage {
+            session_id: None,
+            channel: "telegram".into(),
+            content: content.into(),
+            reply_to: None,
+            parse_mode: None,
+            metadata: Some(r#"{"chat_id":"12345"}"#.into()),
+            idempotency_key: None,
+        };
+        channel.send_partial(msg("first")).await.unwrap();
+        channel.send(msg("first reply")).await.unwrap();
+
+        // A proactive message streams into a message of its own.
+        channel.send_partial(msg("second")).await.unwrap();
+        let reply = channel.live_reply(ChatId(12345)).await.unwrap();
+        assert_eq!(reply.lock().await.text, "second");
+    }
+
     #[test]
     fn plugin_adapter_metadata() {
         let config = TelegramConfig {
             bot_token: Some("test:token".into()),
             allowed_users: vec![],
+            ..Default::default()
         };
         let channel = TelegramChannel::new(config).unwrap();
         assert_eq!(channel.name(), "telegram");
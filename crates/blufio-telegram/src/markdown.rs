@@ -6,6 +6,9 @@
 //! Telegram's MarkdownV2 parse mode requires escaping 18 special characters
 //! outside of code blocks. Characters inside inline code (`` ` ``) or fenced
 //! code blocks (`` ``` ``) must NOT be escaped.
+//!
+//! [`split_for_telegram`] splits escaped text into messages that fit
+//! Telegram's length limit without breaking escapes or entities.
 
 /// Characters that must be escaped in MarkdownV2 outside code blocks.
 const SPECIAL_CHARS: &[char] = &[
@@ -94,6 +97,205 @@ pub fn format_for_telegram(text: &str) -> String {
     escape_markdown_v2(text)
 }
 
+/// Closing fence appended to a chunk that ends inside a fenced code block.
+const FENCE_CLOSE: &str = "```";
+
+/// A position in MarkdownV2 text where a message may be split.
+#[derive(Debug, Clone, Copy)]
+struct CutPoint {
+    /// Byte offset of the cut.
+    pos: usize,
+    /// Preference for cutting here: 3 = paragraph break, 2 = line break,
+    /// 1 = after a space, 0 = anywhere else, `None` = inside an inline entity.
+    priority: Option<u8>,
+    /// Byte range of the opening fence line if the cut falls inside a fenced
+    /// code block; the fence is closed before the cut and reopened after it.
+    fence: Option<(usize, usize)>,
+}
+
+/// Scans MarkdownV2 text and returns every char boundary that does not split
+/// an escape sequence, annotated with how good a cut it is.
+fn cut_points(text: &str) -> Vec<CutPoint> {
+    let bytes = text.as_bytes();
+    let mut points = Vec::with_capacity(text.len() + 1);
+    let mut fence: Option<(usize, usize)> = None;
+    let mut inline_code = false;
+    let mut open: Vec<&'static str> = Vec::new();
+    let mut escaped = false;
+
+    let priority_at = |pos: usize| -> u8 {
+        if text[..pos].ends_with("\n\n") {
+            3
+        } else if text[..pos].ends_with('\n') {
+            2
+        } else if text[..pos].ends_with(' ') {
+            1
+        } else {
+            0
+        }
+    };
+
+    let mut iter = text.char_indices().peekable();
+    while let Some((i, c)) = iter.next() {
+        if escaped {
+            // The escaped character is literal and can't be cut from its backslash.
+            escaped = false;
+            continue;
+        }
+
+        let in_entity = fence.is_none() && (inline_code || !open.is_empty());
+        points.push(CutPoint {
+            pos: i,
+            priority: (!in_entity).then(|| priority_at(i)),
+            fence,
+        });
+
+        if fence.is_some() {
+            // Inside a fenced block only the closing fence is significant.
+            if text[i..].starts_with("```") {
+                fence = None;
+                iter.next();
+                iter.next();
+            }
+            continue;
+        }
+
+        match c {
+            '\\' => escaped = true,
+            '`' if text[i..].starts_with("```") && !inline_code => {
+                let line_end = text[i..].find('\n').map_or(text.len(), |n| i + n + 1);
+                fence = Some((i, line_end));
+                // Skip the rest of the opening line (language tag).
+                while iter.peek().is_some_and(|&(j, _)| j < line_end) {
+                    iter.next();
+                }
+            }
+            '`' => inline_code = !inline_code,
+            _ if inline_code => {}
+            '*' => toggle_marker(&mut open, "*"),
+            '_' if bytes.get(i + 1) == Some(&b'_') => {
+                toggle_marker(&mut open, "__");
+                iter.next();
+            }
+            '_' => toggle_marker(&mut open, "_"),
+            '~' => toggle_marker(&mut open, "~"),
+            '|' if bytes.get(i + 1) == Some(&b'|') => {
+                toggle_marker(&mut open, "||");
+                iter.next();
+            }
+            _ => {}
+        }
+    }
+
+    points.push(CutPoint {
+        pos: text.len(),
+        priority: Some(3),
+        fence,
+    });
+    points
+}
+
+/// Opens `marker` or closes its most recent occurrence.
+fn toggle_marker(open: &mut Vec<&'static str>, marker: &'static str) {
+    match open.iter().rposition(|m| *m == marker) {
+        Some(idx) => {
+            open.remove(idx);
+        }
+        None => open.push(marker),
+    }
+}
+
+/// Splits escaped MarkdownV2 text into messages of at most `max_len` bytes.
+///
+/// Cuts prefer paragraph breaks, then line breaks, then spaces, and never
+/// fall inside an escape sequence or an open inline entity (bold, italic,
+/// inline code, ...) unless a single entity is itself too long. A fenced code
+/// block that spans a cut is closed with `` ``` `` at the end of one part and
+/// reopened (with its language tag) at the start of the next.
+pub fn split_for_telegram(text: &str, max_len: usize) -> Vec<String> {
+    if text.len() <= max_len {
+        return vec![text.to_string()];
+    }
+
+    let points = cut_points(text);
+    let mut chunks = Vec::new();
+    let mut start_idx = 0;
+
+    while points[start_idx].pos < text.len() {
+        let start = points[start_idx];
+        let prefix = start.fence.map_or("", |(a, b)| &text[a..b]);
+        let fits = |p: &CutPoint| {
+            let suffix = if p.fence.is_some() {
+                FENCE_CLOSE.len() + usize::from(!text[..p.pos].ends_with('\n'))
+            } else {
+                0
+            };
+            prefix.len() + (p.pos - start.pos) + suffix <= max_len
+        };
+
+        let candidates = &points[start_idx + 1..];
+        let end_idx = match candidates.iter().take_while(|p| fits(p)).count() {
+            // Not even one character fits alongside the reopened fence.
+            0 => start_idx + 1,
+            n => {
+                let window = &candidates[..n];
+                // Best boundary in the back half of the window, so a break
+                // near the start doesn't produce a tiny message.
+                let best = |min_len: usize| {
+                    window
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, p)| p.priority.is_some() && p.pos - start.pos >= min_len)
+                        .max_by_key(|(i, p)| (p.priority, *i))
+                        .map(|(i, _)| i)
+                };
+                // Entities too long for one message are cut wherever they must be.
+                start_idx + 1 + best(max_len / 2).or_else(|| best(0)).unwrap_or(n - 1)
+            }
+        };
+        let end = points[end_idx];
+
+        let mut chunk = String::with_capacity(max_len);
+        chunk.push_str(prefix);
+        chunk.push_str(&text[start.pos..end.pos]);
+        if end.fence.is_some() && end.pos < text.len() {
+            if !chunk.ends_with('\n') {
+                chunk.push('\n');
+            }
+            chunk.push_str(FENCE_CLOSE);
+        } else {
+            trim_cut_end(&mut chunk);
+        }
+        if !chunk.is_empty() {
+            chunks.push(chunk);
+        }
+
+        // Drop the break we cut at from the start of the next part.
+        start_idx = end_idx;
+        while end.fence.is_none()
+            && points[start_idx].pos < text.len()
+            && text[points[start_idx].pos..].starts_with([' ', '\n'])
+        {
+            start_idx += 1;
+        }
+    }
+
+    chunks
+}
+
+/// Strips trailing spaces and newlines left by a cut, keeping any that are
+/// escaped so no backslash is left dangling.
+fn trim_cut_end(chunk: &mut String) {
+    while chunk.ends_with([' ', '\n']) {
+        let body = &chunk[..chunk.len() - 1];
+        let backslashes = body.len() - body.trim_end_matches('\\').len();
+        if backslashes % 2 == 1 {
+            break;
+        }
+        chunk.pop();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,4 +425,97 @@ mod tests {
         let expected = "map\\{key\\}";
         assert_eq!(escape_markdown_v2(input), expected);
     }
+
+    #[test]
+    fn split_short_text_is_single_chunk() {
+        assert_eq!(split_for_telegram("Hello\\!", 4096), vec!["Hello\\!"]);
+    }
+
+    #[test]
+    fn split_plain_text_at_paragraphs() {
+        let para = "word ".repeat(30).trim_end().to_string();
+        let text = [para.as_str(); 6].join("\n\n");
+        let chunks = split_for_telegram(&text, 400);
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.len() <= 400, "chunk of {} bytes", chunk.len());
+            assert!(chunk.starts_with("word") && chunk.ends_with("word"));
+        }
+        assert_eq!(chunks.join("\n\n"), text);
+    }
+
+    #[test]
+    fn split_long_line_at_spaces() {
+        let text = "abcdefghi ".repeat(100);
+        let chunks = split_for_telegram(text.trim_end(), 95);
+        for chunk in &chunks {
+            assert!(chunk.len() <= 95);
+            assert!(!chunk.starts_with(' '));
+            assert!(chunk.split_whitespace().all(|w| w == "abcdefghi"));
+        }
+    }
+
+    #[test]
+    fn split_never_breaks_escape_sequence() {
+        let text = "\\.".repeat(100);
+        for max_len in [7, 8, 9, 10] {
+            let chunks = split_for_telegram(&text, max_len);
+            for chunk in &chunks {
+                assert!(chunk.len() <= max_len);
+                assert!(chunk.starts_with('\\') && !chunk.ends_with('\\'));
+            }
+            assert_eq!(chunks.concat(), text);
+        }
+    }
+
+    #[test]
+    fn split_giant_code_block_reopens_fence() {
+        let body: String = (0..200).map(|i| format!("let x{i} = {i};\n")).collect();
+        let text = format!("Intro\\.\n\n```rust\n{body}```\n\nOutro\\.");
+        let chunks = split_for_telegram(&text, 500);
+
+        assert!(chunks.len() > 2);
+        for chunk in &chunks {
+            assert!(chunk.len() <= 500, "chunk of {} bytes", chunk.len());
+            assert_eq!(chunk.matches("```").count() % 2, 0, "unbalanced: {chunk}");
+        }
+        // Every part of the code block reopens with the language tag.
+        for chunk in &chunks[1..chunks.len() - 1] {
+            assert!(chunk.starts_with("```rust\n"), "{chunk}");
+            assert!(chunk.ends_with("\n```"), "{chunk}");
+        }
+        // No code line is lost or split across messages.
+        let code_lines: Vec<&str> = chunks
+            .iter()
+            .flat_map(|c| c.lines())
+            .filter(|l| l.starts_with("let "))
+            .collect();
+        assert_eq!(code_lines.len(), 200);
+        assert!(chunks.last().unwrap().ends_with("Outro\\."));
+    }
+
+    #[test]
+    fn split_keeps_nested_emphasis_together() {
+        // The space right before the limit is inside *bold _italic_*, so the
+        // cut moves back to the space before the entity opens.
+        let lead = "a".repeat(30);
+        let text = format!("{lead} *bold _nested italic_ text* tail");
+        let chunks = split_for_telegram(&text, 45);
+
+        assert_eq!(chunks[0], lead);
+        assert!(chunks[1].starts_with("*bold _nested italic_ text*"));
+        for chunk in &chunks {
+            assert_eq!(chunk.matches('*').count() % 2, 0, "unbalanced: {chunk}");
+            assert_eq!(chunk.matches('_').count() % 2, 0, "unbalanced: {chunk}");
+        }
+    }
+
+    #[test]
+    fn split_ignores_escaped_markers() {
+        // Escaped asterisks don't open an entity, so the space is a valid cut.
+        let text = format!("{} \\*not bold {}", "a".repeat(20), "b".repeat(20));
+        let chunks = split_for_telegram(&text, 35);
+        assert_eq!(chunks[0], format!("{} \\*not bold", "a".repeat(20)));
+    }
 }
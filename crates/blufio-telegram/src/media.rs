@@ -4,10 +4,12 @@
 //! Media content extraction for Telegram messages.
 //!
 //! Downloads files from Telegram servers and converts them to
-//! [`MessageContent`] variants for the channel adapter.
+//! [`MessageContent`] variants for the channel adapter. Voice notes can be
+//! transcribed to text on the way in through a [`TranscriptionAdapter`].
 
 use blufio_core::error::{BlufioError, ChannelErrorKind, ErrorContext};
-use blufio_core::types::MessageContent;
+use blufio_core::traits::TranscriptionAdapter;
+use blufio_core::types::{MessageContent, TranscriptionRequest};
 use teloxide::net::Download;
 use teloxide::prelude::*;
 use teloxide::types::{Document, FileMeta, PhotoSize, Voice};
@@ -38,32 +40,80 @@ pub async fn download_file(bot: &Bot, file_meta: &FileMeta) -> Result<Vec<u8>, B
 
 /// Extracts image content from a Telegram photo message.
 ///
-/// Downloads the largest available photo variant (last in the array).
-/// Returns [`MessageContent::Image`] with JPEG mime type.
+/// Downloads the largest photo variant of at most `max_bytes`, so oversized
+/// photos are passed on at a lower resolution. Returns
+/// [`MessageContent::Image`], which the agent turns into an image block plus
+/// the caption as text. Fails with [`ChannelErrorKind::MessageTooLarge`]
+/// when no variant fits.
 pub async fn extract_photo_content(
     bot: &Bot,
     photos: &[PhotoSize],
     caption: Option<&str>,
+    max_bytes: usize,
 ) -> Result<MessageContent, BlufioError> {
-    // Telegram provides multiple sizes; the last one is the largest.
-    let largest = photos.last().ok_or_else(|| BlufioError::Channel {
-        kind: ChannelErrorKind::DeliveryFailed,
-        context: ErrorContext {
-            channel_name: Some("telegram".to_string()),
-            ..Default::default()
-        },
-        source: None,
-    })?;
+    if photos.is_empty() {
+        return Err(media_error(ChannelErrorKind::DeliveryFailed));
+    }
+    let photo = select_photo(photos, max_bytes)
+        .ok_or_else(|| media_error(ChannelErrorKind::MessageTooLarge))?;
 
-    let data = download_file(bot, &largest.file).await?;
+    let data = download_file(bot, &photo.file).await?;
+    // The reported size can be missing or stale; check what arrived.
+    if data.len() > max_bytes {
+        return Err(media_error(ChannelErrorKind::MessageTooLarge));
+    }
+    debug!(
+        width = photo.width,
+        height = photo.height,
+        size = data.len(),
+        "extracted Telegram photo"
+    );
 
     Ok(MessageContent::Image {
+        mime_type: image_mime_type(&data).to_string(),
         data,
-        mime_type: "image/jpeg".to_string(),
         caption: caption.map(|s| s.to_string()),
     })
 }
 
+/// Picks the largest photo variant no bigger than `max_bytes`.
+///
+/// Telegram lists variants from smallest to largest. Variants of unknown
+/// size are tried and checked after download.
+fn select_photo(photos: &[PhotoSize], max_bytes: usize) -> Option<&PhotoSize> {
+    photos
+        .iter()
+        .rev()
+        .find(|p| p.file.size == 0 || p.file.size as usize <= max_bytes)
+}
+
+/// MIME type of downloaded image bytes.
+///
+/// Telegram re-encodes photos as JPEG, but the signature is checked so a
+/// provider never gets a mislabelled image.
+fn image_mime_type(data: &[u8]) -> &'static str {
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "image/png"
+    } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP") {
+        "image/webp"
+    } else if data.starts_with(b"GIF8") {
+        "image/gif"
+    } else {
+        "image/jpeg"
+    }
+}
+
+fn media_error(kind: ChannelErrorKind) -> BlufioError {
+    BlufioError::Channel {
+        kind,
+        context: ErrorContext {
+            channel_name: Some("telegram".to_string()),
+            ..Default::default()
+        },
+        source: None,
+    }
+}
+
 /// Extracts document content from a Telegram document message.
 ///
 /// Downloads the document file and determines the filename and MIME type
@@ -109,3 +159,114 @@ pub async fn extract_voice_content(
         duration_secs,
     })
 }
+
+/// Transcribes a Telegram voice message into text content.
+///
+/// Downloads the voice file and passes it to `transcriber`. Returns
+/// [`MessageContent::Text`] with the transcript; an empty transcript is an
+/// error, since the agent would have nothing to answer.
+pub async fn extract_voice_transcript(
+    bot: &Bot,
+    voice: &Voice,
+    transcriber: &(dyn TranscriptionAdapter + Send + Sync),
+) -> Result<MessageContent, BlufioError> {
+    let data = download_file(bot, &voice.file).await?;
+    transcribe_voice(
+        transcriber,
+        data,
+        voice.mime_type.as_ref().map(|m| m.as_ref()),
+    )
+    .await
+}
+
+/// Runs downloaded voice audio through `transcriber`.
+///
+/// Telegram records voice notes as OGG/Opus, which is assumed when the
+/// message carries no MIME type.
+async fn transcribe_voice(
+    transcriber: &(dyn TranscriptionAdapter + Send + Sync),
+    audio_data: Vec<u8>,
+    mime_type: Option<&str>,
+) -> Result<MessageContent, BlufioError> {
+    let request = TranscriptionRequest {
+        audio_data,
+        content_type: mime_type.unwrap_or("audio/ogg").to_string(),
+        language: None,
+    };
+    let response = transcriber.transcribe(request).await?;
+    let text = response.text.trim();
+    if text.is_empty() {
+        return Err(BlufioError::Internal(
+            "voice message transcription was empty".to_string(),
+        ));
+    }
+
+    debug!(chars = text.len(), "transcribed Telegram voice message");
+    Ok(MessageContent::Text(text.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blufio_test_utils::MockTranscriber;
+
+    fn photo(id: &str, width: u32, size: u32) -> PhotoSize {
+        serde_json::from_value(serde_json::json!({
+            "file_id": id,
+            "file_unique_id": format!("{id}-u"),
+            "width": width,
+            "height": width,
+            "file_size": size
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn select_photo_prefers_largest_variant_that_fits() {
+        let photos = [
+            photo("small", 90, 1_000),
+            photo("medium", 320, 20_000),
+            photo("large", 1280, 200_000),
+        ];
+        assert_eq!(select_photo(&photos, 500_000).unwrap().file.id.0, "large");
+        assert_eq!(select_photo(&photos, 50_000).unwrap().file.id.0, "medium");
+        assert!(select_photo(&photos, 500).is_none());
+    }
+
+    #[test]
+    fn image_mime_type_from_signature() {
+        assert_eq!(image_mime_type(&[0xFF, 0xD8, 0xFF, 0xE0]), "image/jpeg");
+        assert_eq!(image_mime_type(b"\x89PNG\r\n\x1a\n...."), "image/png");
+        assert_eq!(image_mime_type(b"RIFF\0\0\0\0WEBPVP8 "), "image/webp");
+    }
+
+    #[tokio::test]
+    async fn transcript_becomes_text_content() {
+        let transcriber = MockTranscriber::new(Ok(" remind me at five \n"));
+        let content = transcribe_voice(&transcriber, vec![1, 2, 3], None)
+            .await
+            .unwrap();
+
+        assert!(matches!(&content, MessageContent::Text(t) if t == "remind me at five"));
+        let requests = transcriber.requests();
+        assert_eq!(requests[0].audio_data, vec![1, 2, 3]);
+        assert_eq!(requests[0].content_type, "audio/ogg");
+    }
+
+    #[tokio::test]
+    async fn voice_mime_type_is_forwarded() {
+        let transcriber = MockTranscriber::new(Ok("hi"));
+        transcribe_voice(&transcriber, vec![0], Some("audio/mpeg"))
+            .await
+            .unwrap();
+        assert_eq!(transcriber.requests()[0].content_type, "audio/mpeg");
+    }
+
+    #[tokio::test]
+    async fn empty_or_failed_transcription_is_an_error() {
+        for result in [Ok("  "), Err("backend down")] {
+            let transcriber = MockTranscriber::new(result);
+            assert!(transcribe_voice(&transcriber, vec![0], None).await.is_err());
+        }
+    }
+}
@@ -10,7 +10,7 @@
 //! Uses the shared [`StreamingEditorOps`] trait and [`StreamingBuffer`] from
 //! `blufio-core` for cross-adapter consistency.
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use blufio_core::error::{BlufioError, ChannelErrorKind, ErrorContext};
@@ -32,6 +32,51 @@ const SPLIT_THRESHOLD: usize = 3800;
 /// Default throttle interval between message edits.
 const DEFAULT_THROTTLE: Duration = Duration::from_millis(1500);
 
+/// Limits edit-in-place updates to one per interval.
+///
+/// Deltas that arrive before the interval has passed are left pending and
+/// coalesced into the next edit; the caller flushes whatever is pending with
+/// a final edit when the stream ends.
+#[derive(Debug, Clone)]
+pub struct EditThrottle {
+    interval: Duration,
+    last_edit: Option<Instant>,
+    pending: bool,
+}
+
+impl EditThrottle {
+    /// Creates a throttle allowing one edit per `interval`.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_edit: None,
+            pending: false,
+        }
+    }
+
+    /// Records new content at `now` and returns whether to edit right away.
+    ///
+    /// When it returns `false` the content stays pending.
+    pub fn on_update(&mut self, now: Instant) -> bool {
+        let ready = self
+            .last_edit
+            .is_none_or(|last| now.saturating_duration_since(last) >= self.interval);
+        self.pending = !ready;
+        ready
+    }
+
+    /// Records that an edit carrying all content so far went out at `now`.
+    pub fn mark_sent(&mut self, now: Instant) {
+        self.last_edit = Some(now);
+        self.pending = false;
+    }
+
+    /// Whether content has arrived since the last edit.
+    pub fn has_pending(&self) -> bool {
+        self.pending
+    }
+}
+
 /// Telegram-specific streaming operations.
 ///
 /// Wraps a teloxide `Bot` and `ChatId` to provide MarkdownV2 send/edit
@@ -194,6 +239,46 @@ pub fn start_typing_indicator(
 mod tests {
     use super::*;
 
+    #[test]
+    fn edit_throttle_bounds_rapid_updates() {
+        let interval = Duration::from_millis(1500);
+        let mut throttle = EditThrottle::new(interval);
+        let start = Instant::now();
+
+        // 1000 deltas spread over 6 seconds, one every 6ms.
+        let mut edits = 0;
+        for i in 0..1000u64 {
+            let now = start + Duration::from_millis(i * 6);
+            if throttle.on_update(now) {
+                throttle.mark_sent(now);
+                edits += 1;
+            }
+        }
+        if throttle.has_pending() {
+            edits += 1; // final flush
+        }
+
+        // One edit per started interval, plus the final flush.
+        let bound = 6000 / 1500 + 1 + 1;
+        assert!(edits <= bound, "{edits} edits exceeds bound {bound}");
+        assert!(edits >= 4);
+    }
+
+    #[test]
+    fn edit_throttle_first_update_is_immediate_and_coalesces() {
+        let mut throttle = EditThrottle::new(Duration::from_secs(1));
+        let t0 = Instant::now();
+        assert!(throttle.on_update(t0));
+        throttle.mark_sent(t0);
+
+        assert!(!throttle.on_update(t0 + Duration::from_millis(100)));
+        assert!(!throttle.on_update(t0 + Duration::from_millis(900)));
+        assert!(throttle.has_pending());
+
+        assert!(throttle.on_update(t0 + Duration::from_millis(1000)));
+        assert!(!throttle.has_pending());
+    }
+
     #[test]
     fn split_at_paragraph_boundary_double_newline() {
         let text = "First paragraph.\n\nSecond paragraph that is longer.";
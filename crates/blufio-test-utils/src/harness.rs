@@ -10,38 +10,49 @@
 use std::pin::Pin;
 use std::sync::Arc;
 
+use blufio_agent::AgentLoop;
 use blufio_config::model::{
     AgentConfig, BlufioConfig, ContextConfig, CostConfig, RoutingConfig, StorageConfig,
 };
 use blufio_context::ContextEngine;
 use blufio_core::token_counter::{TokenizerCache, TokenizerMode};
 use blufio_core::types::{
-    InboundMessage, MessageContent, ProviderStreamChunk, StreamEventType, TokenUsage,
+    InboundMessage, MessageContent, OutboundMessage, ProviderStreamChunk, StreamEventType,
+    TokenUsage,
 };
 use blufio_core::{BlufioError, ProviderAdapter, StorageAdapter};
-use blufio_cost::{BudgetTracker, CostLedger};
+use blufio_cost::{BudgetTracker, CostLedger, CostRecord, FeatureType};
 use blufio_router::ModelRouter;
-use blufio_skill::ToolRegistry;
+use blufio_skill::{Tool, ToolRegistry};
 use blufio_storage::SqliteStorage;
 use futures::{Stream, StreamExt};
 use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
 
 use crate::mock_channel::MockChannel;
-use crate::mock_provider::MockProvider;
+use crate::mock_provider::{MockFailure, MockProvider, MockTurn};
 
 /// Builder for creating test environments with configurable options.
 pub struct TestHarnessBuilder {
     responses: Vec<String>,
+    script: Vec<MockTurn>,
     daily_budget_usd: Option<f64>,
     system_prompt: Option<String>,
+    failure: Option<MockFailure>,
+    tools: Vec<Arc<dyn Tool>>,
+    echo: bool,
 }
 
 impl TestHarnessBuilder {
     fn new() -> Self {
         Self {
             responses: Vec::new(),
+            script: Vec::new(),
             daily_budget_usd: None,
             system_prompt: None,
+            failure: None,
+            tools: Vec::new(),
+            echo: false,
         }
     }
 
@@ -51,6 +62,34 @@ impl TestHarnessBuilder {
         self
     }
 
+    /// Script the mock provider turn by turn (see [`MockProvider::with_script`]).
+    ///
+    /// Takes precedence over [`with_mock_responses`](Self::with_mock_responses).
+    pub fn with_mock_script(mut self, script: Vec<MockTurn>) -> Self {
+        self.script = script;
+        self
+    }
+
+    /// Make the mock provider fail in the given way instead of responding.
+    ///
+    /// Takes precedence over [`with_mock_responses`](Self::with_mock_responses).
+    pub fn with_mock_failure(mut self, failure: MockFailure) -> Self {
+        self.failure = Some(failure);
+        self
+    }
+
+    /// Use an echoing mock provider (see [`MockProvider::echo`]).
+    pub fn with_echo_provider(mut self) -> Self {
+        self.echo = true;
+        self
+    }
+
+    /// Register a tool (e.g. a [`MockTool`](crate::MockTool)) in the harness tool registry.
+    pub fn with_tool(mut self, tool: Arc<dyn Tool>) -> Self {
+        self.tools.push(tool);
+        self
+    }
+
     /// Set a daily budget cap for the test environment.
     pub fn with_budget(mut self, daily_usd: f64) -> Self {
         self.daily_budget_usd = Some(daily_usd);
@@ -74,6 +113,7 @@ impl TestHarnessBuilder {
         let storage_config = StorageConfig {
             database_path: db_path_str.clone(),
             wal_mode: true,
+            ..Default::default()
         };
         let storage = SqliteStorage::new(storage_config);
         storage.initialize().await?;
@@ -87,6 +127,7 @@ impl TestHarnessBuilder {
             daily_budget_usd: self.daily_budget_usd,
             monthly_budget_usd: None,
             track_tokens: true,
+            ..Default::default()
         };
         let budget_tracker = Arc::new(tokio::sync::Mutex::new(BudgetTracker::new(&cost_config)));
 
@@ -109,11 +150,21 @@ impl TestHarnessBuilder {
         };
         let router = Arc::new(ModelRouter::new(routing_config.clone()));
 
-        // Create empty tool registry
-        let tool_registry = Arc::new(RwLock::new(ToolRegistry::new()));
+        // Create tool registry with any configured tools
+        let mut registry = ToolRegistry::new();
+        for tool in self.tools {
+            registry.register(tool)?;
+        }
+        let tool_registry = Arc::new(RwLock::new(registry));
 
         // Create mock provider
-        let mock_provider = Arc::new(if self.responses.is_empty() {
+        let mock_provider = Arc::new(if let Some(failure) = self.failure {
+            MockProvider::with_failure(failure)
+        } else if self.echo {
+            MockProvider::echo()
+        } else if !self.script.is_empty() {
+            MockProvider::with_script(self.script)
+        } else if self.responses.is_empty() {
             MockProvider::new()
         } else {
             MockProvider::with_responses(self.responses)
@@ -165,7 +216,7 @@ pub struct TestHarness {
     pub context_engine: Arc<ContextEngine>,
     /// Model router (routing disabled by default).
     pub router: Arc<ModelRouter>,
-    /// Tool registry (empty by default).
+    /// Tool registry (empty unless tools were added via the builder).
     pub tool_registry: Arc<RwLock<ToolRegistry>>,
     /// Blufio configuration.
     pub config: BlufioConfig,
@@ -230,8 +281,14 @@ impl TestHarness {
             fallback_chain: Vec::new(),
             event_bus: None,
             injection_pipeline: None,
+            provider_limiter: None,
             boundary_manager: None,
             channel_interactive: true,
+            tool_timeout: None,
+            max_parallel_tools: 1,
+            pii_redactor: None,
+            personas: Default::default(),
+            persona: None,
         });
 
         // Create inbound message
@@ -257,10 +314,121 @@ impl TestHarness {
         Ok(response_text)
     }
 
+    /// Run one inbound message through a full [`AgentLoop`], including the tool loop.
+    ///
+    /// Unlike [`send_message`](Self::send_message), tool calls emitted by the mock
+    /// provider are executed against the harness tool registry and their results
+    /// fed back until the provider stops or the iteration cap is reached. A fresh
+    /// `AgentLoop` (and therefore a fresh session) is built per call. Returns the
+    /// messages sent to the mock channel during this turn.
+    pub async fn run_turn(&self, text: &str) -> Result<Vec<OutboundMessage>, BlufioError> {
+        let mut agent_loop = AgentLoop::new(
+            Box::new((*self.mock_channel).clone()),
+            self.mock_provider.clone() as Arc<dyn ProviderAdapter + Send + Sync>,
+            self.storage.clone(),
+            self.context_engine.clone(),
+            self.cost_ledger.clone(),
+            self.budget_tracker.clone(),
+            None,
+            None,
+            self.router.clone(),
+            None,
+            self.tool_registry.clone(),
+            self.config.clone(),
+        )
+        .await?;
+
+        let already_sent = self.mock_channel.sent_messages().await.len();
+        let inbound = InboundMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            session_id: None,
+            channel: "mock".to_string(),
+            sender_id: "test-user".to_string(),
+            content: MessageContent::Text(text.to_string()),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            metadata: None,
+        };
+        agent_loop.handle_inbound(inbound).await?;
+
+        Ok(self
+            .mock_channel
+            .sent_messages()
+            .await
+            .into_iter()
+            .skip(already_sent)
+            .collect())
+    }
+
+    /// Spawn a long-running [`AgentLoop::run`] over a clone of the mock channel.
+    ///
+    /// Messages injected into [`mock_channel`](Self::mock_channel) are processed
+    /// until the returned token is cancelled; the handle yields `run`'s result.
+    pub async fn spawn_agent_loop(
+        &self,
+    ) -> Result<
+        (
+            tokio::task::JoinHandle<Result<(), BlufioError>>,
+            CancellationToken,
+        ),
+        BlufioError,
+    > {
+        let mut agent_loop = AgentLoop::new(
+            Box::new((*self.mock_channel).clone()),
+            self.mock_provider.clone() as Arc<dyn ProviderAdapter + Send + Sync>,
+            self.storage.clone(),
+            self.context_engine.clone(),
+            self.cost_ledger.clone(),
+            self.budget_tracker.clone(),
+            None,
+            None,
+            self.router.clone(),
+            None,
+            self.tool_registry.clone(),
+            self.config.clone(),
+        )
+        .await?;
+
+        let cancel = CancellationToken::new();
+        let token = cancel.clone();
+        let handle = tokio::spawn(async move { agent_loop.run(token).await });
+        Ok((handle, cancel))
+    }
+
     /// Add a response to the mock provider's queue.
     pub async fn add_provider_response(&self, text: String) {
         self.mock_provider.add_response(text).await;
     }
+
+    /// All cost records written to the ledger by this harness, oldest first.
+    pub async fn cost_records(&self) -> Result<Vec<CostRecord>, BlufioError> {
+        self.cost_ledger.records(None).await
+    }
+
+    /// Number of cost records of the given feature type.
+    pub async fn cost_record_count(&self, feature: FeatureType) -> Result<usize, BlufioError> {
+        Ok(self
+            .cost_records()
+            .await?
+            .iter()
+            .filter(|r| r.feature_type == feature)
+            .count())
+    }
+
+    /// Token totals summed across all cost records.
+    pub async fn cost_token_totals(&self) -> Result<TokenUsage, BlufioError> {
+        let totals = self
+            .cost_records()
+            .await?
+            .iter()
+            .fold(TokenUsage::default(), |mut acc, r| {
+                acc.input_tokens += r.input_tokens;
+                acc.output_tokens += r.output_tokens;
+                acc.cache_read_tokens += r.cache_read_tokens;
+                acc.cache_creation_tokens += r.cache_creation_tokens;
+                acc
+            });
+        Ok(totals)
+    }
 }
 
 /// Consume a provider stream, collecting text and usage.
@@ -305,6 +473,7 @@ async fn consume_stream(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use blufio_core::types::ContentBlock;
 
     #[tokio::test]
     async fn builder_creates_working_environment() {
@@ -366,6 +535,76 @@ mod tests {
         assert_eq!(messages[1].content, "stored response");
     }
 
+    #[tokio::test]
+    async fn mid_stream_failure_persists_partial_response() {
+        let harness = TestHarness::builder()
+            .with_mock_failure(MockFailure::ErrorAfterChunks(vec![
+                "half an ".to_string(),
+                "answer".to_string(),
+            ]))
+            .build()
+            .await
+            .unwrap();
+
+        let resp = harness.send_message("hello").await.unwrap();
+        assert_eq!(resp, "half an answer");
+
+        let sessions = harness.storage.list_sessions(None).await.unwrap();
+        let messages = harness
+            .storage
+            .get_messages(&sessions[0].id, None)
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1].content, "half an answer");
+    }
+
+    #[tokio::test]
+    async fn agent_loop_forwards_text_deltas_before_final_reply() {
+        let harness = TestHarness::builder()
+            .with_mock_failure(MockFailure::ErrorAfterChunks(vec![
+                "half an ".to_string(),
+                "answer".to_string(),
+            ]))
+            .build()
+            .await
+            .unwrap();
+
+        let sent = harness.run_turn("hello").await.unwrap();
+
+        let deltas: Vec<String> = harness
+            .mock_channel
+            .partial_messages()
+            .await
+            .into_iter()
+            .map(|m| m.content)
+            .collect();
+        assert_eq!(deltas, ["half an ", "answer"]);
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].content, "half an answer");
+    }
+
+    #[tokio::test]
+    async fn always_error_failure_surfaces_provider_error() {
+        let harness = TestHarness::builder()
+            .with_mock_failure(MockFailure::AlwaysError(
+                blufio_core::error::ProviderErrorKind::RateLimited,
+            ))
+            .build()
+            .await
+            .unwrap();
+
+        let err = harness.send_message("hello").await.unwrap_err();
+        assert!(matches!(
+            err,
+            BlufioError::Provider {
+                kind: blufio_core::error::ProviderErrorKind::RateLimited,
+                ..
+            }
+        ));
+        assert!(harness.mock_provider.call_count() >= 1);
+    }
+
     #[tokio::test]
     async fn with_budget_configures_tracker() {
         let harness = TestHarness::builder()
@@ -397,6 +636,141 @@ mod tests {
         assert!(daily_cost > 0.0, "expected non-zero cost, got {daily_cost}");
     }
 
+    #[tokio::test]
+    async fn single_turn_records_exactly_one_message_cost() {
+        let harness = TestHarness::builder()
+            .with_mock_responses(vec!["counted once".to_string()])
+            .build()
+            .await
+            .unwrap();
+
+        harness.send_message("count me").await.unwrap();
+
+        let records = harness.cost_records().await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            harness
+                .cost_record_count(FeatureType::Message)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            harness
+                .cost_record_count(FeatureType::Compaction)
+                .await
+                .unwrap(),
+            0
+        );
+
+        // MockProvider reports 10 input / 20 output tokens per turn.
+        let totals = harness.cost_token_totals().await.unwrap();
+        assert_eq!(totals.input_tokens, 10);
+        assert_eq!(totals.output_tokens, 20);
+    }
+
+    #[tokio::test]
+    async fn tool_loop_executes_tool_and_feeds_result_back() {
+        let tool = Arc::new(crate::MockTool::new("mock_tool"));
+        tool.add_output("tool says hi").await;
+        let harness = TestHarness::builder()
+            .with_tool(tool.clone())
+            .build()
+            .await
+            .unwrap();
+        harness
+            .mock_provider
+            .add_tool_use("toolu_1", "mock_tool", serde_json::json!({"q": "hi"}))
+            .await;
+        harness
+            .add_provider_response("final answer".to_string())
+            .await;
+
+        let sent = harness.run_turn("use the tool").await.unwrap();
+
+        assert_eq!(
+            tool.invocations().await,
+            vec![serde_json::json!({"q": "hi"})]
+        );
+        assert_eq!(harness.mock_provider.call_count(), 2);
+        assert_eq!(sent.last().unwrap().content, "final answer");
+
+        // The tool result was persisted and fed back to the provider.
+        let sessions = harness.storage.list_sessions(None).await.unwrap();
+        let messages = harness
+            .storage
+            .get_messages(&sessions[0].id, None)
+            .await
+            .unwrap();
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.content.contains("tool_result") && m.content.contains("tool says hi"))
+        );
+    }
+
+    #[tokio::test]
+    async fn scripted_bash_tool_use_then_final_answer() {
+        let bash = Arc::new(crate::MockTool::new("bash"));
+        bash.add_output("Cargo.toml\nsrc").await;
+        let harness = TestHarness::builder()
+            .with_tool(bash.clone())
+            .with_mock_script(vec![
+                MockTurn::ToolUse {
+                    id: "toolu_bash".to_string(),
+                    name: "bash".to_string(),
+                    input: serde_json::json!({"command": "ls"}),
+                },
+                MockTurn::Text("The project has Cargo.toml and src.".to_string()),
+            ])
+            .build()
+            .await
+            .unwrap();
+
+        let sent = harness.run_turn("what files are here?").await.unwrap();
+
+        assert_eq!(
+            bash.invocations().await,
+            vec![serde_json::json!({"command": "ls"})]
+        );
+        assert_eq!(harness.mock_provider.call_count(), 2);
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].content, "The project has Cargo.toml and src.");
+
+        // The follow-up request carried the bash result back to the provider.
+        let requests = harness.mock_provider.requests();
+        let last = requests[1].messages.last().unwrap();
+        assert_eq!(last.role, "user");
+        assert!(matches!(
+            &last.content[0],
+            ContentBlock::ToolResult { tool_use_id, content, .. }
+                if tool_use_id == "toolu_bash" && content == "Cargo.toml\nsrc"
+        ));
+    }
+
+    #[tokio::test]
+    async fn tool_loop_stops_at_iteration_cap() {
+        use blufio_agent::session::MAX_TOOL_ITERATIONS;
+
+        let tool = Arc::new(crate::MockTool::new("mock_tool"));
+        let harness = TestHarness::builder()
+            .with_tool(tool.clone())
+            .build()
+            .await
+            .unwrap();
+        for i in 0..MAX_TOOL_ITERATIONS + 5 {
+            harness
+                .mock_provider
+                .add_tool_use(&format!("toolu_{i}"), "mock_tool", serde_json::json!({}))
+                .await;
+        }
+
+        harness.run_turn("loop forever").await.unwrap();
+
+        assert_eq!(tool.call_count().await, MAX_TOOL_ITERATIONS);
+        assert_eq!(harness.mock_provider.call_count(), MAX_TOOL_ITERATIONS + 1);
+    }
+
     #[tokio::test]
     async fn temp_db_is_unique_per_harness() {
         let h1 = TestHarness::builder().build().await.unwrap();
@@ -9,13 +9,18 @@
 //!
 //! # Components
 //!
-//! - [`MockProvider`] - Mock LLM provider with pre-configured responses
+//! - [`MockProvider`] - Mock LLM provider with pre-configured responses or injected failures
 //! - [`MockChannel`] - Mock messaging channel with message injection and capture
+//! - [`MockTool`] - Mock tool with scriptable outputs, errors, and delays
 
 pub mod harness;
 pub mod mock_channel;
 pub mod mock_provider;
+pub mod mock_tool;
+pub mod mock_transcriber;
 
 pub use harness::TestHarness;
 pub use mock_channel::MockChannel;
-pub use mock_provider::MockProvider;
+pub use mock_provider::{MockFailure, MockProvider, MockTurn};
+pub use mock_tool::MockTool;
+pub use mock_transcriber::MockTranscriber;
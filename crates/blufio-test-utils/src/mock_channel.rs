@@ -8,6 +8,7 @@
 
 use std::collections::VecDeque;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use async_trait::async_trait;
 use tokio::sync::{Mutex, Notify};
@@ -25,10 +26,29 @@ use blufio_core::types::{
 /// Provides two queues:
 /// - **inbound**: Messages injected via `inject_message()` are returned by `receive()`
 /// - **sent**: Messages passed to `send()` are captured and retrievable via `sent_messages()`
+///
+/// Streaming deltas passed to `send_partial()` are captured separately and
+/// retrievable via `partial_messages()`, chat IDs passed to `send_typing()`
+/// via `typing_events()`, and audio passed to `send_voice()` via
+/// `voice_messages()`.
+///
+/// All capabilities are off by default; `with_edit_and_typing()` turns on
+/// message editing and typing indicators, and `with_voice()` voice messages.
+/// `with_send_failures()` makes the next sends fail, for retry tests.
+///
+/// Clones share the same queues, so a test can hand one clone to an
+/// `AgentLoop` and keep another for injection and assertions.
+#[derive(Clone)]
 pub struct MockChannel {
     inbound: Arc<Mutex<VecDeque<InboundMessage>>>,
     sent: Arc<Mutex<Vec<OutboundMessage>>>,
+    partial: Arc<Mutex<Vec<OutboundMessage>>>,
+    typing: Arc<Mutex<Vec<String>>>,
+    voice: Arc<Mutex<Vec<(OutboundMessage, Vec<u8>)>>>,
     notify: Arc<Notify>,
+    send_failures: Arc<AtomicUsize>,
+    edit_and_typing: bool,
+    voice_enabled: bool,
 }
 
 impl MockChannel {
@@ -37,10 +57,36 @@ impl MockChannel {
         Self {
             inbound: Arc::new(Mutex::new(VecDeque::new())),
             sent: Arc::new(Mutex::new(Vec::new())),
+            partial: Arc::new(Mutex::new(Vec::new())),
+            typing: Arc::new(Mutex::new(Vec::new())),
+            voice: Arc::new(Mutex::new(Vec::new())),
             notify: Arc::new(Notify::new()),
+            send_failures: Arc::new(AtomicUsize::new(0)),
+            edit_and_typing: false,
+            voice_enabled: false,
         }
     }
 
+    /// Report `supports_edit` and `supports_typing` in capabilities.
+    pub fn with_edit_and_typing(mut self) -> Self {
+        self.edit_and_typing = true;
+        self
+    }
+
+    /// Report `supports_voice` in capabilities and accept `send_voice()`.
+    pub fn with_voice(mut self) -> Self {
+        self.voice_enabled = true;
+        self
+    }
+
+    /// Make the next `count` calls to `send()` fail with a delivery error.
+    ///
+    /// Pass `usize::MAX` for a channel that never accepts a message.
+    pub fn with_send_failures(self, count: usize) -> Self {
+        self.send_failures.store(count, Ordering::SeqCst);
+        self
+    }
+
     /// Inject an inbound message into the receive queue.
     ///
     /// The next call to `receive()` will return this message.
@@ -54,11 +100,40 @@ impl MockChannel {
         self.sent.lock().await.clone()
     }
 
+    /// Get all streaming deltas that were sent through `send_partial()`.
+    pub async fn partial_messages(&self) -> Vec<OutboundMessage> {
+        self.partial.lock().await.clone()
+    }
+
+    /// Get the chat IDs of every typing indicator sent through `send_typing()`.
+    pub async fn typing_events(&self) -> Vec<String> {
+        self.typing.lock().await.clone()
+    }
+
+    /// Get every voice message sent through `send_voice()` with its audio.
+    pub async fn voice_messages(&self) -> Vec<(OutboundMessage, Vec<u8>)> {
+        self.voice.lock().await.clone()
+    }
+
     /// Get the count of sent messages.
     pub async fn sent_count(&self) -> usize {
         self.sent.lock().await.len()
     }
 
+    /// Wait until at least `count` messages have been sent, polling every 10ms.
+    ///
+    /// Returns `false` if `timeout` elapses first.
+    pub async fn wait_for_sent(&self, count: usize, timeout: std::time::Duration) -> bool {
+        let deadline = tokio::time::Instant::now() + timeout;
+        while self.sent_count().await < count {
+            if tokio::time::Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        true
+    }
+
     /// Clear all sent messages.
     pub async fn clear_sent(&self) {
         self.sent.lock().await.clear();
@@ -98,11 +173,11 @@ impl PluginAdapter for MockChannel {
 impl ChannelAdapter for MockChannel {
     fn capabilities(&self) -> ChannelCapabilities {
         ChannelCapabilities {
-            supports_edit: false,
-            supports_typing: false,
+            supports_edit: self.edit_and_typing,
+            supports_typing: self.edit_and_typing,
             supports_images: false,
             supports_documents: false,
-            supports_voice: false,
+            supports_voice: self.voice_enabled,
             max_message_length: None,
             supports_embeds: false,
             supports_reactions: false,
@@ -120,11 +195,46 @@ impl ChannelAdapter for MockChannel {
     }
 
     async fn send(&self, msg: OutboundMessage) -> Result<MessageId, BlufioError> {
+        if self
+            .send_failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+        {
+            return Err(BlufioError::channel_delivery_failed(
+                "mock",
+                std::io::Error::other("simulated send failure"),
+            ));
+        }
         let id = format!("mock-msg-{}", uuid::Uuid::new_v4());
         self.sent.lock().await.push(msg);
         Ok(MessageId(id))
     }
 
+    async fn send_partial(&self, msg: OutboundMessage) -> Result<(), BlufioError> {
+        self.partial.lock().await.push(msg);
+        Ok(())
+    }
+
+    async fn send_typing(&self, chat_id: &str) -> Result<(), BlufioError> {
+        self.typing.lock().await.push(chat_id.to_string());
+        Ok(())
+    }
+
+    async fn send_voice(
+        &self,
+        msg: OutboundMessage,
+        audio: Vec<u8>,
+        _content_type: &str,
+    ) -> Result<MessageId, BlufioError> {
+        if !self.voice_enabled {
+            return Err(BlufioError::Internal(
+                "mock channel has voice disabled".to_string(),
+            ));
+        }
+        self.voice.lock().await.push((msg, audio));
+        Ok(MessageId(format!("mock-voice-{}", uuid::Uuid::new_v4())))
+    }
+
     async fn receive(&self) -> Result<InboundMessage, BlufioError> {
         loop {
             // Try to pop from queue
@@ -181,6 +291,7 @@ mod tests {
             reply_to: None,
             parse_mode: None,
             metadata: None,
+            idempotency_key: None,
         };
 
         let msg_id = channel.send(msg).await.unwrap();
@@ -204,6 +315,17 @@ mod tests {
         assert!(caps.max_message_length.is_none());
     }
 
+    #[tokio::test]
+    async fn edit_and_typing_capabilities_and_capture() {
+        let channel = MockChannel::new().with_edit_and_typing();
+        let caps = channel.capabilities();
+        assert!(caps.supports_edit);
+        assert!(caps.supports_typing);
+
+        channel.send_typing("chat-1").await.unwrap();
+        assert_eq!(channel.typing_events().await, vec!["chat-1".to_string()]);
+    }
+
     #[tokio::test]
     async fn connect_succeeds() {
         let mut channel = MockChannel::new();
@@ -263,6 +385,7 @@ mod tests {
             reply_to: None,
             parse_mode: None,
             metadata: None,
+            idempotency_key: None,
         };
 
         channel.send(msg.clone()).await.unwrap();
@@ -4,30 +4,75 @@
 //! Mock LLM provider adapter for deterministic testing.
 //!
 //! `MockProvider` implements `ProviderAdapter` with pre-configured responses,
-//! enabling fast, CI-runnable tests without external API calls.
+//! enabling fast, CI-runnable tests without external API calls. Failure
+//! injection constructors exercise the agent's provider error paths.
 
 use std::collections::VecDeque;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
 use async_trait::async_trait;
 use futures::stream;
 use tokio::sync::Mutex;
 
 use blufio_core::BlufioError;
+use blufio_core::error::{ErrorContext, ProviderErrorKind};
 use blufio_core::traits::adapter::PluginAdapter;
 use blufio_core::traits::provider::ProviderAdapter;
 use blufio_core::types::{
-    AdapterType, HealthStatus, ProviderRequest, ProviderResponse, ProviderStreamChunk,
-    StreamEventType, TokenUsage,
+    AdapterType, ContentBlock, HealthStatus, ProviderRequest, ProviderResponse,
+    ProviderStreamChunk, StreamEventType, TokenUsage, ToolUseData,
 };
 
+/// Failure injected by a [`MockProvider`] instead of a normal response.
+#[derive(Debug, Clone)]
+pub enum MockFailure {
+    /// Every call fails immediately with a provider error of this kind.
+    AlwaysError(ProviderErrorKind),
+    /// The stream emits these text deltas, then an error item (no `MessageStop`).
+    /// Non-streaming calls fail with a server error.
+    ErrorAfterChunks(Vec<String>),
+    /// Every call waits this long, then fails with a provider timeout.
+    Timeout(Duration),
+}
+
+/// One scripted provider turn, consumed per `complete`/`stream` call.
+#[derive(Debug, Clone)]
+pub enum MockTurn {
+    /// A plain text answer (`stop_reason = "end_turn"`).
+    Text(String),
+    /// A single tool call (`stop_reason = "tool_use"`) with no text.
+    ToolUse {
+        /// Tool use block ID.
+        id: String,
+        /// Name of the tool to invoke.
+        name: String,
+        /// JSON input for the tool.
+        input: serde_json::Value,
+    },
+    /// A text answer cut off by the token limit (`stop_reason = "max_tokens"`).
+    Truncated(String),
+    /// A response with no content that stopped for `stop_reason`
+    /// (e.g. `"max_tokens"`).
+    Empty {
+        /// Stop reason reported by the provider.
+        stop_reason: String,
+    },
+}
+
 /// A mock LLM provider that returns pre-configured responses.
 ///
 /// Responses are popped from a FIFO queue. When the queue is empty,
-/// a default "mock response" text is returned.
+/// a default "mock response" text is returned. When a [`MockFailure`]
+/// is configured, every call fails in that way instead.
 pub struct MockProvider {
-    responses: Arc<Mutex<VecDeque<String>>>,
+    responses: Arc<Mutex<VecDeque<MockTurn>>>,
+    failure: Option<MockFailure>,
+    echo: bool,
+    calls: AtomicUsize,
+    requests: std::sync::Mutex<Vec<ProviderRequest>>,
 }
 
 impl MockProvider {
@@ -35,28 +80,157 @@ impl MockProvider {
     pub fn new() -> Self {
         Self {
             responses: Arc::new(Mutex::new(VecDeque::new())),
+            failure: None,
+            echo: false,
+            calls: AtomicUsize::new(0),
+            requests: std::sync::Mutex::new(Vec::new()),
         }
     }
 
     /// Create a mock provider pre-loaded with the given responses.
     pub fn with_responses(responses: Vec<String>) -> Self {
         Self {
-            responses: Arc::new(Mutex::new(VecDeque::from(responses))),
+            responses: Arc::new(Mutex::new(
+                responses.into_iter().map(MockTurn::Text).collect(),
+            )),
+            failure: None,
+            echo: false,
+            calls: AtomicUsize::new(0),
+            requests: std::sync::Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Create a mock provider that replays `script`, one turn per call.
+    ///
+    /// Each `complete`/`stream` call consumes the next turn, so a
+    /// [`MockTurn::ToolUse`] followed by a [`MockTurn::Text`] drives one tool
+    /// iteration and then the final answer. Once the script is exhausted the
+    /// default "mock response" text is returned.
+    pub fn with_script(script: Vec<MockTurn>) -> Self {
+        Self {
+            responses: Arc::new(Mutex::new(script.into())),
+            ..Self::new()
+        }
+    }
+
+    /// Create a mock provider that fails in the given way on every call.
+    pub fn with_failure(failure: MockFailure) -> Self {
+        Self {
+            failure: Some(failure),
+            ..Self::new()
         }
     }
 
+    /// Create a mock provider that answers `"echo: <last user text>"`.
+    ///
+    /// Replies depend only on the request, so concurrent tests can check that
+    /// each session got the answer to its own message.
+    pub fn echo() -> Self {
+        Self {
+            echo: true,
+            ..Self::new()
+        }
+    }
+
+    /// Create a mock provider whose calls always fail with a 5xx-style server error.
+    pub fn always_error() -> Self {
+        Self::with_failure(MockFailure::AlwaysError(ProviderErrorKind::ServerError))
+    }
+
+    /// Create a mock provider whose stream emits `chunks` and then errors mid-stream.
+    pub fn error_after_chunks(chunks: Vec<String>) -> Self {
+        Self::with_failure(MockFailure::ErrorAfterChunks(chunks))
+    }
+
+    /// Create a mock provider whose calls hang for `delay` and then time out.
+    pub fn with_timeout(delay: Duration) -> Self {
+        Self::with_failure(MockFailure::Timeout(delay))
+    }
+
+    /// Number of `complete`/`stream` calls made so far (including failed ones).
+    pub fn call_count(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    /// Every request received so far, in call order (including failed ones).
+    pub fn requests(&self) -> Vec<ProviderRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Count the call and keep a copy of its request.
+    fn record_call(&self, request: &ProviderRequest) {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        self.requests.lock().unwrap().push(request.clone());
+    }
+
     /// Add a response to the end of the queue.
     pub async fn add_response(&self, text: String) {
-        self.responses.lock().await.push_back(text);
+        self.responses.lock().await.push_back(MockTurn::Text(text));
+    }
+
+    /// Add a tool-call turn to the queue.
+    pub async fn add_tool_use(&self, id: &str, name: &str, input: serde_json::Value) {
+        self.responses.lock().await.push_back(MockTurn::ToolUse {
+            id: id.to_string(),
+            name: name.to_string(),
+            input,
+        });
     }
 
-    /// Pop the next response, or return the default.
-    async fn next_response(&self) -> String {
+    /// Next turn for `request`: an echo in echo mode, else the queue head
+    /// or the default text response.
+    async fn next_turn(&self, request: &ProviderRequest) -> MockTurn {
+        if self.echo {
+            return MockTurn::Text(format!("echo: {}", last_user_text(request)));
+        }
         self.responses
             .lock()
             .await
             .pop_front()
-            .unwrap_or_else(|| "mock response".to_string())
+            .unwrap_or_else(|| MockTurn::Text("mock response".to_string()))
+    }
+}
+
+/// Concatenated text blocks of the last user message in the request.
+fn last_user_text(request: &ProviderRequest) -> String {
+    request
+        .messages
+        .iter()
+        .rev()
+        .find(|m| m.role == "user")
+        .map(|m| {
+            m.content
+                .iter()
+                .filter_map(|block| match block {
+                    ContentBlock::Text { text } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect::<String>()
+        })
+        .unwrap_or_default()
+}
+
+/// Build the provider error for an injected failure of the given kind.
+fn injected_error(kind: ProviderErrorKind) -> BlufioError {
+    BlufioError::Provider {
+        kind,
+        context: ErrorContext {
+            provider_name: Some("mock-provider".to_string()),
+            ..Default::default()
+        },
+        source: None,
+    }
+}
+
+/// Build a text delta chunk.
+fn text_chunk(text: String) -> ProviderStreamChunk {
+    ProviderStreamChunk {
+        event_type: StreamEventType::ContentBlockDelta,
+        text: Some(text),
+        usage: None,
+        error: None,
+        tool_use: None,
+        stop_reason: None,
     }
 }
 
@@ -92,12 +266,30 @@ impl PluginAdapter for MockProvider {
 #[async_trait]
 impl ProviderAdapter for MockProvider {
     async fn complete(&self, request: ProviderRequest) -> Result<ProviderResponse, BlufioError> {
-        let text = self.next_response().await;
+        self.record_call(&request);
+        match &self.failure {
+            Some(MockFailure::AlwaysError(kind)) => return Err(injected_error(*kind)),
+            Some(MockFailure::ErrorAfterChunks(_)) => {
+                return Err(injected_error(ProviderErrorKind::ServerError));
+            }
+            Some(MockFailure::Timeout(delay)) => {
+                tokio::time::sleep(*delay).await;
+                return Err(BlufioError::provider_timeout("mock-provider"));
+            }
+            None => {}
+        }
+
+        let (text, stop_reason) = match self.next_turn(&request).await {
+            MockTurn::Text(text) => (text, "end_turn".to_string()),
+            MockTurn::Truncated(text) => (text, "max_tokens".to_string()),
+            MockTurn::ToolUse { .. } => (String::new(), "tool_use".to_string()),
+            MockTurn::Empty { stop_reason } => (String::new(), stop_reason),
+        };
         Ok(ProviderResponse {
             id: format!("mock-resp-{}", uuid::Uuid::new_v4()),
             content: text,
             model: request.model,
-            stop_reason: Some("end_turn".to_string()),
+            stop_reason: Some(stop_reason),
             usage: TokenUsage {
                 input_tokens: 10,
                 output_tokens: 20,
@@ -114,29 +306,61 @@ impl ProviderAdapter for MockProvider {
         Pin<Box<dyn futures_core::Stream<Item = Result<ProviderStreamChunk, BlufioError>> + Send>>,
         BlufioError,
     > {
-        let text = self.next_response().await;
+        self.record_call(&request);
+        match &self.failure {
+            Some(MockFailure::AlwaysError(kind)) => return Err(injected_error(*kind)),
+            Some(MockFailure::ErrorAfterChunks(chunks)) => {
+                let mut items = vec![Ok(ProviderStreamChunk {
+                    event_type: StreamEventType::MessageStart,
+                    text: None,
+                    usage: None,
+                    error: None,
+                    tool_use: None,
+                    stop_reason: None,
+                })];
+                items.extend(chunks.iter().cloned().map(|c| Ok(text_chunk(c))));
+                items.push(Err(injected_error(ProviderErrorKind::ServerError)));
+                return Ok(Box::pin(stream::iter(items)));
+            }
+            Some(MockFailure::Timeout(delay)) => {
+                tokio::time::sleep(*delay).await;
+                return Err(BlufioError::provider_timeout("mock-provider"));
+            }
+            None => {}
+        }
+
         let model = request.model.clone();
 
         // Produce a realistic SSE event sequence:
-        // MessageStart -> ContentBlockDelta (text) -> MessageDelta (usage + stop) -> MessageStop
+        // MessageStart -> ContentBlockDelta (text) | ContentBlockStop (tool_use)
+        //   -> MessageDelta (usage + stop) -> MessageStop
+        let (content, stop_reason) = match self.next_turn(&request).await {
+            MockTurn::Text(text) => (Some(text_chunk(text)), "end_turn".to_string()),
+            MockTurn::Truncated(text) => (Some(text_chunk(text)), "max_tokens".to_string()),
+            MockTurn::ToolUse { id, name, input } => (
+                Some(ProviderStreamChunk {
+                    event_type: StreamEventType::ContentBlockStop,
+                    text: None,
+                    usage: None,
+                    error: None,
+                    tool_use: Some(ToolUseData { id, name, input }),
+                    stop_reason: None,
+                }),
+                "tool_use".to_string(),
+            ),
+            MockTurn::Empty { stop_reason } => (None, stop_reason),
+        };
         let chunks = vec![
-            Ok(ProviderStreamChunk {
+            Some(Ok(ProviderStreamChunk {
                 event_type: StreamEventType::MessageStart,
                 text: None,
                 usage: None,
                 error: None,
                 tool_use: None,
                 stop_reason: None,
-            }),
-            Ok(ProviderStreamChunk {
-                event_type: StreamEventType::ContentBlockDelta,
-                text: Some(text),
-                usage: None,
-                error: None,
-                tool_use: None,
-                stop_reason: None,
-            }),
-            Ok(ProviderStreamChunk {
+            })),
+            content.map(Ok),
+            Some(Ok(ProviderStreamChunk {
                 event_type: StreamEventType::MessageDelta,
                 text: None,
                 usage: Some(TokenUsage {
@@ -147,17 +371,18 @@ impl ProviderAdapter for MockProvider {
                 }),
                 error: None,
                 tool_use: None,
-                stop_reason: Some("end_turn".to_string()),
-            }),
-            Ok(ProviderStreamChunk {
+                stop_reason: Some(stop_reason),
+            })),
+            Some(Ok(ProviderStreamChunk {
                 event_type: StreamEventType::MessageStop,
                 text: None,
                 usage: None,
                 error: None,
                 tool_use: None,
                 stop_reason: None,
-            }),
+            })),
         ];
+        let chunks: Vec<_> = chunks.into_iter().flatten().collect();
 
         let _ = model; // Used in real provider for MessageStart metadata
         Ok(Box::pin(stream::iter(chunks)))
@@ -279,4 +504,134 @@ mod tests {
             "dynamic response"
         );
     }
+
+    fn stream_request() -> ProviderRequest {
+        ProviderRequest {
+            model: "test-model".to_string(),
+            system_prompt: None,
+            system_blocks: None,
+            messages: vec![],
+            max_tokens: 100,
+            stream: true,
+            tools: None,
+        }
+    }
+
+    /// Tool call and text of one streamed turn.
+    async fn stream_turn(provider: &MockProvider) -> (Option<ToolUseData>, String) {
+        let mut stream = provider.stream(stream_request()).await.unwrap();
+        let (mut tool_use, mut text) = (None, String::new());
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.unwrap();
+            tool_use = tool_use.or(chunk.tool_use);
+            text.push_str(chunk.text.as_deref().unwrap_or_default());
+        }
+        (tool_use, text)
+    }
+
+    #[tokio::test]
+    async fn script_replays_one_turn_per_call() {
+        let provider = MockProvider::with_script(vec![
+            MockTurn::ToolUse {
+                id: "toolu_1".to_string(),
+                name: "bash".to_string(),
+                input: serde_json::json!({"command": "ls"}),
+            },
+            MockTurn::Text("done".to_string()),
+        ]);
+
+        let (tool_use, text) = stream_turn(&provider).await;
+        let tool_use = tool_use.expect("first turn is a tool call");
+        assert_eq!(tool_use.id, "toolu_1");
+        assert_eq!(tool_use.name, "bash");
+        assert!(text.is_empty());
+
+        let (tool_use, text) = stream_turn(&provider).await;
+        assert!(tool_use.is_none());
+        assert_eq!(text, "done");
+
+        // Script exhausted: default response.
+        assert_eq!(stream_turn(&provider).await.1, "mock response");
+        assert_eq!(provider.call_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn always_error_fails_every_call() {
+        let provider = MockProvider::always_error();
+        assert!(provider.complete(stream_request()).await.is_err());
+        assert!(provider.stream(stream_request()).await.is_err());
+        assert_eq!(provider.call_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn error_after_chunks_yields_partial_text_then_error() {
+        let provider =
+            MockProvider::error_after_chunks(vec!["partial ".to_string(), "answer".to_string()]);
+        let mut stream = provider.stream(stream_request()).await.unwrap();
+
+        let mut text = String::new();
+        let mut saw_error = false;
+        while let Some(item) = stream.next().await {
+            match item {
+                Ok(chunk) => text.push_str(chunk.text.as_deref().unwrap_or_default()),
+                Err(e) => {
+                    assert!(matches!(
+                        e,
+                        BlufioError::Provider {
+                            kind: ProviderErrorKind::ServerError,
+                            ..
+                        }
+                    ));
+                    saw_error = true;
+                }
+            }
+        }
+        assert_eq!(text, "partial answer");
+        assert!(saw_error);
+    }
+
+    #[tokio::test]
+    async fn timeout_fails_after_delay() {
+        let provider = MockProvider::with_timeout(Duration::from_millis(20));
+        let started = std::time::Instant::now();
+        let err = provider.stream(stream_request()).await.err().unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(20));
+        assert!(matches!(
+            err,
+            BlufioError::Provider {
+                kind: ProviderErrorKind::Timeout,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn echo_replies_with_last_user_text() {
+        let provider = MockProvider::echo();
+        let mut request = stream_request();
+        request.stream = false;
+        request.messages = vec![
+            blufio_core::types::ProviderMessage {
+                role: "user".to_string(),
+                content: vec![ContentBlock::Text {
+                    text: "first".to_string(),
+                }],
+            },
+            blufio_core::types::ProviderMessage {
+                role: "assistant".to_string(),
+                content: vec![ContentBlock::Text {
+                    text: "reply".to_string(),
+                }],
+            },
+            blufio_core::types::ProviderMessage {
+                role: "user".to_string(),
+                content: vec![ContentBlock::Text {
+                    text: "second".to_string(),
+                }],
+            },
+        ];
+
+        let resp = provider.complete(request).await.unwrap();
+        assert_eq!(resp.content, "echo: second");
+    }
 }
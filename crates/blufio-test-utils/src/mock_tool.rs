@@ -0,0 +1,162 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Mock tool with scriptable outputs for testing the agent tool loop.
+//!
+//! `MockTool` implements `Tool`, returning queued outputs (including error
+//! results) with an optional per-call delay, and records every input it
+//! was invoked with for assertions.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use blufio_core::BlufioError;
+use blufio_skill::{Tool, ToolOutput};
+use tokio::sync::Mutex;
+
+/// A mock tool that returns pre-configured outputs.
+///
+/// Outputs are popped from a FIFO queue. When the queue is empty,
+/// a successful "mock tool output" result is returned.
+pub struct MockTool {
+    name: String,
+    outputs: Arc<Mutex<VecDeque<ToolOutput>>>,
+    invocations: Arc<Mutex<Vec<serde_json::Value>>>,
+    delay: Option<Duration>,
+    parallel_safe: bool,
+}
+
+impl MockTool {
+    /// Create a new mock tool with the given name and an empty output queue.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            outputs: Arc::new(Mutex::new(VecDeque::new())),
+            invocations: Arc::new(Mutex::new(Vec::new())),
+            delay: None,
+            parallel_safe: true,
+        }
+    }
+
+    /// Pre-load the output queue.
+    pub fn with_outputs(mut self, outputs: Vec<ToolOutput>) -> Self {
+        self.outputs = Arc::new(Mutex::new(VecDeque::from(outputs)));
+        self
+    }
+
+    /// Sleep for `delay` before returning each output.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Mark the tool as safe (or not) to run concurrently with other tools.
+    pub fn with_parallel_safe(mut self, parallel_safe: bool) -> Self {
+        self.parallel_safe = parallel_safe;
+        self
+    }
+
+    /// Queue a successful output.
+    pub async fn add_output(&self, content: &str) {
+        self.outputs.lock().await.push_back(ToolOutput {
+            content: content.to_string(),
+            is_error: false,
+        });
+    }
+
+    /// Queue an error result (`is_error = true`).
+    pub async fn add_error(&self, content: &str) {
+        self.outputs.lock().await.push_back(ToolOutput {
+            content: content.to_string(),
+            is_error: true,
+        });
+    }
+
+    /// Inputs the tool was invoked with, in call order.
+    pub async fn invocations(&self) -> Vec<serde_json::Value> {
+        self.invocations.lock().await.clone()
+    }
+
+    /// Number of times the tool was invoked.
+    pub async fn call_count(&self) -> usize {
+        self.invocations.lock().await.len()
+    }
+}
+
+#[async_trait]
+impl Tool for MockTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "Mock tool for tests"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {},
+        })
+    }
+
+    fn is_parallel_safe(&self) -> bool {
+        self.parallel_safe
+    }
+
+    async fn invoke(&self, input: serde_json::Value) -> Result<ToolOutput, BlufioError> {
+        self.invocations.lock().await.push(input);
+        if let Some(delay) = self.delay {
+            tokio::time::sleep(delay).await;
+        }
+        Ok(self
+            .outputs
+            .lock()
+            .await
+            .pop_front()
+            .unwrap_or_else(|| ToolOutput {
+                content: "mock tool output".to_string(),
+                is_error: false,
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn returns_queued_outputs_then_default() {
+        let tool = MockTool::new("mock_tool").with_outputs(vec![ToolOutput {
+            content: "first".to_string(),
+            is_error: false,
+        }]);
+        tool.add_error("boom").await;
+
+        let out = tool.invoke(serde_json::json!({"n": 1})).await.unwrap();
+        assert_eq!(out.content, "first");
+        assert!(!out.is_error);
+
+        let out = tool.invoke(serde_json::json!({"n": 2})).await.unwrap();
+        assert_eq!(out.content, "boom");
+        assert!(out.is_error);
+
+        let out = tool.invoke(serde_json::json!({"n": 3})).await.unwrap();
+        assert_eq!(out.content, "mock tool output");
+
+        let invocations = tool.invocations().await;
+        assert_eq!(invocations.len(), 3);
+        assert_eq!(invocations[1]["n"], 2);
+    }
+
+    #[tokio::test]
+    async fn delay_is_applied() {
+        let tool = MockTool::new("slow_tool").with_delay(Duration::from_millis(20));
+        let started = std::time::Instant::now();
+        tool.invoke(serde_json::json!({})).await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(20));
+        assert_eq!(tool.call_count().await, 1);
+    }
+}
@@ -0,0 +1,78 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Mock speech-to-text adapter for testing voice message handling.
+//!
+//! `MockTranscriber` implements `TranscriptionAdapter`, returning a fixed
+//! transcript (or error) and recording every request it received.
+
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use blufio_core::BlufioError;
+use blufio_core::traits::TranscriptionAdapter;
+use blufio_core::traits::adapter::PluginAdapter;
+use blufio_core::types::{AdapterType, HealthStatus, TranscriptionRequest, TranscriptionResponse};
+
+/// Transcriber returning a fixed result and recording the requests it got.
+pub struct MockTranscriber {
+    result: Result<String, String>,
+    requests: Mutex<Vec<TranscriptionRequest>>,
+}
+
+impl MockTranscriber {
+    /// Create a transcriber that answers every request with `result`; an
+    /// `Err` becomes an internal error carrying that message.
+    pub fn new(result: Result<&str, &str>) -> Self {
+        Self {
+            result: result.map(str::to_string).map_err(str::to_string),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Every request received so far, in call order.
+    pub fn requests(&self) -> Vec<TranscriptionRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl PluginAdapter for MockTranscriber {
+    fn name(&self) -> &str {
+        "mock-transcriber"
+    }
+
+    fn version(&self) -> semver::Version {
+        semver::Version::new(0, 1, 0)
+    }
+
+    fn adapter_type(&self) -> AdapterType {
+        AdapterType::Transcription
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus, BlufioError> {
+        Ok(HealthStatus::Healthy)
+    }
+
+    async fn shutdown(&self) -> Result<(), BlufioError> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TranscriptionAdapter for MockTranscriber {
+    async fn transcribe(
+        &self,
+        request: TranscriptionRequest,
+    ) -> Result<TranscriptionResponse, BlufioError> {
+        self.requests.lock().unwrap().push(request);
+        match &self.result {
+            Ok(text) => Ok(TranscriptionResponse {
+                text: text.clone(),
+                language: None,
+                duration_secs: None,
+            }),
+            Err(e) => Err(BlufioError::Internal(e.clone())),
+        }
+    }
+}
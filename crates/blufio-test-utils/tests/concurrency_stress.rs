@@ -0,0 +1,114 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Concurrency stress test for the agent loop.
+//!
+//! Many senders inject messages concurrently into a single running
+//! `AgentLoop`. Every reply must reach the right session (no cross-session
+//! leakage), each session must see its own messages in send order, and the
+//! loop must shut down cleanly afterwards.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use blufio_core::types::{InboundMessage, MessageContent};
+use blufio_test_utils::TestHarness;
+
+const SENDERS: usize = 8;
+const MESSAGES_PER_SENDER: usize = 5;
+
+fn inbound(sender: &str, text: &str) -> InboundMessage {
+    InboundMessage {
+        id: uuid::Uuid::new_v4().to_string(),
+        session_id: None,
+        channel: "mock".to_string(),
+        sender_id: sender.to_string(),
+        content: MessageContent::Text(text.to_string()),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        metadata: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_senders_get_isolated_ordered_sessions() {
+    let harness = TestHarness::builder()
+        .with_echo_provider()
+        .build()
+        .await
+        .unwrap();
+    let (handle, cancel) = harness.spawn_agent_loop().await.unwrap();
+
+    // Each sender injects its messages in order from its own task.
+    let mut injectors = Vec::new();
+    for s in 0..SENDERS {
+        let channel = harness.mock_channel.clone();
+        injectors.push(tokio::spawn(async move {
+            let sender = format!("sender-{s}");
+            for m in 0..MESSAGES_PER_SENDER {
+                channel
+                    .inject_message(inbound(&sender, &format!("{sender} msg-{m}")))
+                    .await;
+                tokio::task::yield_now().await;
+            }
+        }));
+    }
+    for injector in injectors {
+        injector.await.unwrap();
+    }
+
+    let total = SENDERS * MESSAGES_PER_SENDER;
+    assert!(
+        harness
+            .mock_channel
+            .wait_for_sent(total, Duration::from_secs(60))
+            .await,
+        "only {} of {total} replies were sent",
+        harness.mock_channel.sent_count().await
+    );
+
+    // One session per sender, keyed by user_id.
+    let sessions = harness.storage.list_sessions(None).await.unwrap();
+    assert_eq!(sessions.len(), SENDERS);
+    let sender_by_session: HashMap<String, String> = sessions
+        .iter()
+        .map(|s| (s.id.clone(), s.user_id.clone().unwrap()))
+        .collect();
+
+    // Every reply went to the session of the sender it answers.
+    let sent = harness.mock_channel.sent_messages().await;
+    assert_eq!(sent.len(), total);
+    for out in &sent {
+        let session_id = out.session_id.as_ref().expect("reply without session");
+        let sender = &sender_by_session[session_id];
+        assert!(
+            out.content.starts_with(&format!("echo: {sender} msg-")),
+            "reply {:?} leaked into session of {sender}",
+            out.content
+        );
+    }
+
+    // Each session holds exactly its own exchanges, in send order.
+    for session in &sessions {
+        let sender = session.user_id.as_deref().unwrap();
+        let messages = harness
+            .storage
+            .get_messages(&session.id, None)
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), MESSAGES_PER_SENDER * 2);
+        for (m, pair) in messages.chunks(2).enumerate() {
+            let expected = format!("{sender} msg-{m}");
+            assert_eq!(pair[0].role, "user");
+            assert_eq!(pair[0].content, expected);
+            assert_eq!(pair[1].role, "assistant");
+            assert_eq!(pair[1].content, format!("echo: {expected}"));
+        }
+    }
+
+    // Shut down last: `run` closes storage on exit.
+    cancel.cancel();
+    handle
+        .await
+        .expect("agent loop panicked")
+        .expect("agent loop returned an error");
+}
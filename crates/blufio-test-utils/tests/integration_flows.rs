@@ -735,6 +735,7 @@ async fn flow_skill_install_verify_execute_cost() {
             Some(&content_hash),
             Some(&signature_hex),
             Some(&publisher_hex),
+            blufio_skill::VerificationStatus::Verified,
         )
         .await
         .expect("skill install should succeed");
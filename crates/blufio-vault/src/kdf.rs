@@ -4,12 +4,134 @@
 //! Argon2id key derivation from a passphrase.
 //!
 //! Derives a 32-byte key using Argon2id (Algorithm::Argon2id, Version::V0x13)
-//! with parameters from VaultConfig (OWASP-recommended defaults).
+//! with parameters from VaultConfig (OWASP-recommended defaults), and can
+//! auto-tune those parameters to a target derivation time on this machine.
+//!
+//! Parameters only apply to newly wrapped master keys: an existing vault
+//! stores the parameters it was wrapped with and always unlocks with those.
+
+use std::time::{Duration, Instant};
 
+use blufio_config::model::VaultConfig;
 use blufio_core::BlufioError;
 use ring::rand::{SecureRandom, SystemRandom};
 use zeroize::Zeroizing;
 
+/// Argon2id cost parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Params {
+    /// Memory cost in KiB.
+    pub memory_cost: u32,
+    /// Iteration (time) cost.
+    pub iterations: u32,
+    /// Parallelism lanes.
+    pub parallelism: u32,
+}
+
+impl Argon2Params {
+    /// Lowest memory cost accepted by config validation (32 MiB).
+    pub const MIN_MEMORY_COST: u32 = 32768;
+    /// Lowest iteration count accepted by config validation.
+    pub const MIN_ITERATIONS: u32 = 2;
+    /// Upper bound for auto-tuned memory cost (1 GiB).
+    pub const MAX_MEMORY_COST: u32 = 1_048_576;
+    /// Upper bound for auto-tuned parallelism.
+    pub const MAX_PARALLELISM: u32 = 4;
+
+    /// Parameters currently configured in `[vault]`.
+    pub fn from_config(config: &VaultConfig) -> Self {
+        Self {
+            memory_cost: config.kdf_memory_cost,
+            iterations: config.kdf_iterations,
+            parallelism: config.kdf_parallelism,
+        }
+    }
+
+    /// Writes these parameters into a `[vault]` config.
+    pub fn apply_to(&self, config: &mut VaultConfig) {
+        config.kdf_memory_cost = self.memory_cost;
+        config.kdf_iterations = self.iterations;
+        config.kdf_parallelism = self.parallelism;
+    }
+
+    /// Derive a key with these parameters.
+    pub fn derive(
+        &self,
+        passphrase: &[u8],
+        salt: &[u8; 16],
+    ) -> Result<Zeroizing<[u8; 32]>, BlufioError> {
+        derive_key(
+            passphrase,
+            salt,
+            self.memory_cost,
+            self.iterations,
+            self.parallelism,
+        )
+    }
+
+    /// Time one derivation with these parameters on this machine.
+    pub fn measure(&self) -> Result<Duration, BlufioError> {
+        let salt = [0x5au8; 16];
+        let start = Instant::now();
+        self.derive(b"blufio-kdf-benchmark", &salt)?;
+        Ok(start.elapsed())
+    }
+
+    /// Find parameters whose derivation takes about `target_ms` here.
+    ///
+    /// Memory is raised first (doubling from the 32 MiB floor, up to 1 GiB)
+    /// since memory hardness is Argon2id's main defense, then iterations are
+    /// scaled to fill the remaining budget. Never returns parameters below
+    /// the validation floor, so on slow machines the result may exceed the
+    /// target. Returns the parameters with their measured derivation time.
+    pub fn auto_tune(target_ms: u64) -> Result<(Self, Duration), BlufioError> {
+        let parallelism = std::thread::available_parallelism()
+            .map(|n| n.get() as u32)
+            .unwrap_or(1)
+            .clamp(1, Self::MAX_PARALLELISM);
+        Self::tune(Duration::from_millis(target_ms), parallelism, Self::measure)
+    }
+
+    /// [`auto_tune`](Self::auto_tune) with the derivation timer supplied by
+    /// the caller, so the search can be exercised against a cost model.
+    fn tune(
+        target: Duration,
+        parallelism: u32,
+        mut measure: impl FnMut(&Self) -> Result<Duration, BlufioError>,
+    ) -> Result<(Self, Duration), BlufioError> {
+        let mut params = Self {
+            memory_cost: Self::MIN_MEMORY_COST,
+            iterations: Self::MIN_ITERATIONS,
+            parallelism,
+        };
+        let mut elapsed = measure(&params)?;
+
+        // Derivation time scales roughly linearly with memory.
+        while elapsed * 2 <= target && params.memory_cost < Self::MAX_MEMORY_COST {
+            params.memory_cost *= 2;
+            elapsed = measure(&params)?;
+        }
+
+        // ...and with iterations.
+        if elapsed < target {
+            let per_iteration = elapsed / params.iterations;
+            if !per_iteration.is_zero() {
+                let fit = (target.as_nanos() / per_iteration.as_nanos()) as u32;
+                params.iterations = fit.max(Self::MIN_ITERATIONS);
+                elapsed = measure(&params)?;
+            }
+        }
+
+        // Step back if the estimate overshot.
+        while elapsed > target && params.iterations > Self::MIN_ITERATIONS {
+            params.iterations -= 1;
+            elapsed = measure(&params)?;
+        }
+
+        Ok((params, elapsed))
+    }
+}
+
 /// Derive a 32-byte key from passphrase using Argon2id.
 ///
 /// The returned key is wrapped in [`Zeroizing`] for automatic memory zeroing
@@ -87,6 +209,58 @@ mod tests {
         assert_ne!(salt1, salt2);
     }
 
+    #[test]
+    fn params_roundtrip_through_config() {
+        let params = Argon2Params {
+            memory_cost: 65536,
+            iterations: 4,
+            parallelism: 2,
+        };
+        let mut config = VaultConfig::default();
+        params.apply_to(&mut config);
+        assert_eq!(Argon2Params::from_config(&config), params);
+    }
+
+    /// Cost model for `tune`: 10 ms per iteration per 32 MiB of memory.
+    fn linear_cost(params: &Argon2Params) -> Result<Duration, BlufioError> {
+        let units = params.memory_cost / Argon2Params::MIN_MEMORY_COST * params.iterations;
+        Ok(Duration::from_millis(10 * u64::from(units)))
+    }
+
+    #[test]
+    fn auto_tune_lands_within_tolerance_of_target() {
+        // Floor costs 20 ms; memory doubles to 512 MiB (320 ms), then one
+        // more iteration fills the 500 ms budget.
+        let (params, tuned) =
+            Argon2Params::tune(Duration::from_millis(500), 2, linear_cost).unwrap();
+        assert_eq!(
+            params,
+            Argon2Params {
+                memory_cost: 524_288,
+                iterations: 3,
+                parallelism: 2,
+            }
+        );
+        assert_eq!(tuned, Duration::from_millis(480));
+    }
+
+    #[test]
+    fn auto_tune_caps_memory_and_scales_iterations() {
+        let (params, tuned) = Argon2Params::tune(Duration::from_secs(2), 1, linear_cost).unwrap();
+        assert_eq!(params.memory_cost, Argon2Params::MAX_MEMORY_COST);
+        assert_eq!(params.iterations, 6);
+        assert_eq!(tuned, Duration::from_millis(1920));
+    }
+
+    #[test]
+    fn auto_tune_never_goes_below_floor() {
+        // An unreachable target still yields the validation floor.
+        let (params, tuned) = Argon2Params::tune(Duration::from_millis(1), 1, linear_cost).unwrap();
+        assert_eq!(params.memory_cost, Argon2Params::MIN_MEMORY_COST);
+        assert_eq!(params.iterations, Argon2Params::MIN_ITERATIONS);
+        assert_eq!(tuned, Duration::from_millis(20));
+    }
+
     #[test]
     fn derive_key_output_is_32_bytes() {
         let key = derive_key(b"test", &[0u8; 16], 32768, 2, 1).unwrap();
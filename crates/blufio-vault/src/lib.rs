@@ -13,8 +13,15 @@ pub mod crypto;
 pub mod kdf;
 pub mod migration;
 pub mod prompt;
+pub mod resolve;
+pub mod shamir;
 pub mod vault;
 
-pub use migration::{MigrationReport, migrate_plaintext_secrets, vault_startup_check};
+pub use kdf::Argon2Params;
+pub use migration::{
+    MigrationReport, migrate_plaintext_secrets, plaintext_secret_names, vault_startup_check,
+};
 pub use prompt::get_vault_passphrase;
+pub use resolve::{apply_vault_secrets, configured_secrets};
+pub use shamir::RecoveryShare;
 pub use vault::{Vault, mask_secret};
@@ -14,10 +14,12 @@ use tracing::{info, warn};
 
 use crate::vault::Vault;
 
-/// Report of what the migration did.
+/// Report of what the migration did, or would do in a dry run.
 #[derive(Debug, Default)]
 pub struct MigrationReport {
-    /// Names of secrets that were migrated to vault.
+    /// Whether this was a dry run (nothing was changed).
+    pub dry_run: bool,
+    /// Names of secrets that were (or, in a dry run, would be) migrated to vault.
     pub migrated: Vec<String>,
     /// Names of secrets already in vault (skipped).
     pub skipped: Vec<String>,
@@ -25,22 +27,17 @@ pub struct MigrationReport {
     pub warnings: Vec<String>,
 }
 
-/// Scan config for plaintext secrets and migrate them to the vault.
-///
-/// This is called on startup after the vault is unlocked. Known secret fields:
-/// - `telegram.bot_token`
-/// - `anthropic.api_key`
-///
-/// Returns a report of migrated and skipped secrets.
-pub async fn migrate_plaintext_secrets(
-    config: &BlufioConfig,
-    config_path: &Path,
-    vault: &Vault,
-) -> Result<MigrationReport, BlufioError> {
-    let mut report = MigrationReport::default();
+/// Names of known secret fields set in plaintext in `config`.
+pub fn plaintext_secret_names(config: &BlufioConfig) -> Vec<&'static str> {
+    collect_plaintext_secrets(config)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect()
+}
 
-    // Collect secrets to migrate.
-    let mut secrets_to_migrate: Vec<(&str, String)> = Vec::new();
+/// Known secret fields with a non-empty plaintext value in `config`.
+fn collect_plaintext_secrets(config: &BlufioConfig) -> Vec<(&'static str, String)> {
+    let mut secrets_to_migrate: Vec<(&'static str, String)> = Vec::new();
 
     if let Some(ref token) = config.telegram.bot_token
         && !token.is_empty()
@@ -54,6 +51,31 @@ pub async fn migrate_plaintext_secrets(
         secrets_to_migrate.push(("anthropic.api_key", key.clone()));
     }
 
+    secrets_to_migrate
+}
+
+/// Scan config for plaintext secrets and migrate them to the vault.
+///
+/// This is called on startup after the vault is unlocked. Known secret fields:
+/// - `telegram.bot_token`
+/// - `anthropic.api_key`
+///
+/// With `dry_run`, the vault and config file are left untouched and the
+/// report lists what a real run would migrate and skip.
+///
+/// Returns a report of migrated and skipped secrets.
+pub async fn migrate_plaintext_secrets(
+    config: &BlufioConfig,
+    config_path: &Path,
+    vault: &Vault,
+    dry_run: bool,
+) -> Result<MigrationReport, BlufioError> {
+    let mut report = MigrationReport {
+        dry_run,
+        ..Default::default()
+    };
+
+    let secrets_to_migrate = collect_plaintext_secrets(config);
     if secrets_to_migrate.is_empty() {
         info!("no plaintext secrets found in config -- nothing to migrate");
         return Ok(report);
@@ -69,6 +91,12 @@ pub async fn migrate_plaintext_secrets(
             continue;
         }
 
+        if dry_run {
+            report.migrated.push(name.to_string());
+            info!(name = %name, "dry run: would migrate plaintext secret to vault");
+            continue;
+        }
+
         // Store in vault.
         vault.store_secret(name, value).await?;
         report.migrated.push(name.to_string());
@@ -76,7 +104,8 @@ pub async fn migrate_plaintext_secrets(
     }
 
     // Rewrite config file to remove secrets.
-    if !report.migrated.is_empty()
+    if !dry_run
+        && !report.migrated.is_empty()
         && let Err(e) = rewrite_config_without_secrets(config_path, &report.migrated)
     {
         let warning = format!(
@@ -197,7 +226,7 @@ api_key = "sk-ant-test-key"
             ..Default::default()
         };
 
-        let report = migrate_plaintext_secrets(&config, &config_path, &vault)
+        let report = migrate_plaintext_secrets(&config, &config_path, &vault, false)
             .await
             .unwrap();
 
@@ -236,19 +265,87 @@ api_key = "sk-ant-test-key"
         };
 
         // First migration.
-        let report1 = migrate_plaintext_secrets(&config, &config_path, &vault)
+        let report1 = migrate_plaintext_secrets(&config, &config_path, &vault, false)
             .await
             .unwrap();
         assert_eq!(report1.migrated.len(), 1);
 
         // Second migration -- should skip.
-        let report2 = migrate_plaintext_secrets(&config, &config_path, &vault)
+        let report2 = migrate_plaintext_secrets(&config, &config_path, &vault, false)
             .await
             .unwrap();
         assert_eq!(report2.migrated.len(), 0);
         assert_eq!(report2.skipped.len(), 1);
     }
 
+    #[tokio::test]
+    async fn dry_run_reports_without_side_effects() {
+        let (conn, dir) = open_test_db().await;
+        let vault_config = test_config();
+        let passphrase = SecretString::from("test".to_string());
+        let vault = Vault::create(conn, &passphrase, &vault_config)
+            .await
+            .unwrap();
+        vault
+            .store_secret("telegram.bot_token", "already-here")
+            .await
+            .unwrap();
+
+        let config_path = dir.path().join("blufio.toml");
+        let original = "[telegram]\nbot_token = \"abc\"\n\n[anthropic]\napi_key = \"sk-ant-dry\"\n";
+        std::fs::write(&config_path, original).unwrap();
+
+        let config = BlufioConfig {
+            telegram: blufio_config::model::TelegramConfig {
+                bot_token: Some("abc".to_string()),
+                ..Default::default()
+            },
+            anthropic: blufio_config::model::AnthropicConfig {
+                api_key: Some("sk-ant-dry".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let report = migrate_plaintext_secrets(&config, &config_path, &vault, true)
+            .await
+            .unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.migrated, vec!["anthropic.api_key".to_string()]);
+        assert_eq!(report.skipped, vec!["telegram.bot_token".to_string()]);
+
+        // Vault and config file are unchanged.
+        assert!(
+            vault
+                .retrieve_secret("anthropic.api_key")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let names: Vec<String> = vault
+            .list_secrets()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, vec!["telegram.bot_token".to_string()]);
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), original);
+    }
+
+    #[test]
+    fn plaintext_secret_names_lists_set_fields() {
+        let config = BlufioConfig {
+            anthropic: blufio_config::model::AnthropicConfig {
+                api_key: Some("sk-ant-x".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(plaintext_secret_names(&config), vec!["anthropic.api_key"]);
+        assert!(plaintext_secret_names(&BlufioConfig::default()).is_empty());
+    }
+
     #[tokio::test]
     #[serial]
     async fn vault_startup_check_no_vault_returns_none() {
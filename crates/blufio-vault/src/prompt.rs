@@ -9,6 +9,10 @@ use secrecy::SecretString;
 /// The environment variable name for providing the vault passphrase.
 pub const VAULT_KEY_ENV_VAR: &str = "BLUFIO_VAULT_KEY";
 
+/// The environment variable name for providing the new passphrase when
+/// rotating the vault passphrase non-interactively.
+pub const NEW_VAULT_KEY_ENV_VAR: &str = "BLUFIO_VAULT_NEW_KEY";
+
 /// Get vault passphrase from environment variable or interactive TTY prompt.
 ///
 /// Priority:
@@ -80,6 +84,43 @@ pub fn get_vault_passphrase_with_confirm() -> Result<SecretString, BlufioError>
     ))
 }
 
+/// Get the replacement passphrase for a passphrase rotation.
+///
+/// Priority:
+/// 1. `BLUFIO_VAULT_NEW_KEY` environment variable
+/// 2. Interactive TTY prompt, entered twice for confirmation
+pub fn get_new_vault_passphrase() -> Result<SecretString, BlufioError> {
+    if let Ok(key) = std::env::var(NEW_VAULT_KEY_ENV_VAR)
+        && !key.is_empty()
+    {
+        return Ok(SecretString::from(key));
+    }
+
+    if std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+        eprint!("New vault passphrase: ");
+        let pass1 = rpassword::read_password()
+            .map_err(|e| BlufioError::Vault(format!("failed to read passphrase: {e}")))?;
+        eprint!("Confirm new vault passphrase: ");
+        let pass2 = rpassword::read_password()
+            .map_err(|e| BlufioError::Vault(format!("failed to read passphrase: {e}")))?;
+
+        if pass1 != pass2 {
+            return Err(BlufioError::Vault("passphrases do not match".to_string()));
+        }
+        if pass1.is_empty() {
+            return Err(BlufioError::Vault(
+                "empty passphrase not allowed".to_string(),
+            ));
+        }
+        return Ok(SecretString::from(pass1));
+    }
+
+    Err(BlufioError::Vault(
+        "No new passphrase provided. Set BLUFIO_VAULT_NEW_KEY environment variable or run interactively."
+            .to_string(),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,6 +147,16 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    #[serial]
+    fn get_new_passphrase_from_env_var() {
+        unsafe { std::env::set_var(NEW_VAULT_KEY_ENV_VAR, "rotated-passphrase") };
+        let result = get_new_vault_passphrase();
+        unsafe { std::env::remove_var(NEW_VAULT_KEY_ENV_VAR) };
+
+        assert!(result.is_ok());
+    }
+
     #[test]
     #[serial]
     fn empty_env_var_is_rejected() {
@@ -0,0 +1,206 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Runtime resolution of known config secrets from the vault.
+//!
+//! Secrets stored with `blufio config set-secret <name>` are applied to the
+//! loaded config before providers and channels are initialized. Resolution
+//! precedence for each secret is:
+//!
+//! 1. Explicit value in the config file (or its `BLUFIO_*` env override)
+//! 2. Vault entry with the same dotted name
+//! 3. The consumer's own environment fallback (e.g. `ANTHROPIC_API_KEY`)
+//! 4. Error raised by the consumer
+//!
+//! This module implements step 2; steps 3 and 4 stay with each consumer.
+
+use blufio_config::model::BlufioConfig;
+use blufio_core::BlufioError;
+use secrecy::ExposeSecret;
+use tracing::{debug, info};
+
+use crate::vault::Vault;
+
+/// Config secrets that are resolved from the vault, by dotted name.
+pub const KNOWN_SECRETS: &[&str] = &[
+    "anthropic.api_key",
+    "telegram.bot_token",
+    "discord.bot_token",
+    "slack.bot_token",
+    "slack.app_token",
+    "gateway.bearer_token",
+    "providers.openai.api_key",
+    "providers.openrouter.api_key",
+    "providers.gemini.api_key",
+];
+
+/// Returns the config field backing a known secret name.
+fn secret_slot<'a>(config: &'a mut BlufioConfig, name: &str) -> Option<&'a mut Option<String>> {
+    match name {
+        "anthropic.api_key" => Some(&mut config.anthropic.api_key),
+        "telegram.bot_token" => Some(&mut config.telegram.bot_token),
+        "discord.bot_token" => Some(&mut config.discord.bot_token),
+        "slack.bot_token" => Some(&mut config.slack.bot_token),
+        "slack.app_token" => Some(&mut config.slack.app_token),
+        "gateway.bearer_token" => Some(&mut config.gateway.bearer_token),
+        "providers.openai.api_key" => Some(&mut config.providers.openai.api_key),
+        "providers.openrouter.api_key" => Some(&mut config.providers.openrouter.api_key),
+        "providers.gemini.api_key" => Some(&mut config.providers.gemini.api_key),
+        _ => None,
+    }
+}
+
+/// Fill unset known secrets in `config` from the vault.
+///
+/// Values already present in the config are never overridden. Returns the
+/// names of the secrets that were resolved from the vault.
+pub async fn apply_vault_secrets(
+    config: &mut BlufioConfig,
+    vault: &Vault,
+) -> Result<Vec<&'static str>, BlufioError> {
+    let mut resolved = Vec::new();
+
+    for &name in KNOWN_SECRETS {
+        let Some(slot) = secret_slot(config, name) else {
+            continue;
+        };
+        if slot.as_deref().is_some_and(|v| !v.is_empty()) {
+            debug!(name, "secret set in config -- vault value not consulted");
+            continue;
+        }
+        if let Some(secret) = vault.retrieve_secret(name).await? {
+            *slot = Some(secret.expose_secret().to_string());
+            resolved.push(name);
+        }
+    }
+
+    if !resolved.is_empty() {
+        info!(count = resolved.len(), "config secrets resolved from vault");
+    }
+    Ok(resolved)
+}
+
+/// Returns the non-empty values of the known secrets set in `config`, for
+/// registration with log redaction.
+///
+/// Takes `&mut` only to share the field mapping of [`apply_vault_secrets`];
+/// the config is not modified.
+pub fn configured_secrets(config: &mut BlufioConfig) -> Vec<String> {
+    KNOWN_SECRETS
+        .iter()
+        .filter_map(|name| secret_slot(config, name)?.clone())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blufio_config::model::VaultConfig;
+    use secrecy::SecretString;
+    use tempfile::tempdir;
+
+    fn test_config() -> VaultConfig {
+        VaultConfig {
+            kdf_memory_cost: 32768,
+            kdf_iterations: 2,
+            kdf_parallelism: 1,
+        }
+    }
+
+    async fn test_vault() -> (Vault, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("resolve_test.db");
+        let db = blufio_storage::Database::open(db_path.to_str().unwrap())
+            .await
+            .unwrap();
+        let passphrase = SecretString::from("test".to_string());
+        let vault = Vault::create(db.connection().clone(), &passphrase, &test_config())
+            .await
+            .unwrap();
+        (vault, dir)
+    }
+
+    #[test]
+    fn every_known_secret_has_a_slot() {
+        let mut config = BlufioConfig::default();
+        for name in KNOWN_SECRETS {
+            assert!(secret_slot(&mut config, name).is_some(), "{name}");
+        }
+    }
+
+    #[test]
+    fn configured_secrets_covers_every_known_secret() {
+        let mut config = BlufioConfig::default();
+        config.slack.app_token = Some("xapp-1".to_string());
+        config.providers.gemini.api_key = Some("gemini-key".to_string());
+        config.discord.bot_token = Some(String::new());
+
+        assert_eq!(
+            configured_secrets(&mut config),
+            vec!["xapp-1".to_string(), "gemini-key".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn vault_value_fills_unset_config() {
+        let (vault, _dir) = test_vault().await;
+        vault
+            .store_secret("anthropic.api_key", "sk-ant-from-vault")
+            .await
+            .unwrap();
+
+        let mut config = BlufioConfig::default();
+        let resolved = apply_vault_secrets(&mut config, &vault).await.unwrap();
+
+        assert_eq!(resolved, vec!["anthropic.api_key"]);
+        assert_eq!(
+            config.anthropic.api_key.as_deref(),
+            Some("sk-ant-from-vault")
+        );
+    }
+
+    #[tokio::test]
+    async fn config_value_takes_precedence_over_vault() {
+        let (vault, _dir) = test_vault().await;
+        vault
+            .store_secret("telegram.bot_token", "from-vault")
+            .await
+            .unwrap();
+
+        let mut config = BlufioConfig::default();
+        config.telegram.bot_token = Some("from-config".to_string());
+        let resolved = apply_vault_secrets(&mut config, &vault).await.unwrap();
+
+        assert!(resolved.is_empty());
+        assert_eq!(config.telegram.bot_token.as_deref(), Some("from-config"));
+    }
+
+    #[tokio::test]
+    async fn empty_config_value_falls_through_to_vault() {
+        let (vault, _dir) = test_vault().await;
+        vault
+            .store_secret("gateway.bearer_token", "from-vault")
+            .await
+            .unwrap();
+
+        let mut config = BlufioConfig::default();
+        config.gateway.bearer_token = Some(String::new());
+        apply_vault_secrets(&mut config, &vault).await.unwrap();
+
+        assert_eq!(config.gateway.bearer_token.as_deref(), Some("from-vault"));
+    }
+
+    #[tokio::test]
+    async fn missing_vault_entry_leaves_config_unset() {
+        let (vault, _dir) = test_vault().await;
+        vault.store_secret("unrelated", "value").await.unwrap();
+
+        let mut config = BlufioConfig::default();
+        let resolved = apply_vault_secrets(&mut config, &vault).await.unwrap();
+
+        // Left unset so the consumer's env fallback still applies.
+        assert!(resolved.is_empty());
+        assert!(config.anthropic.api_key.is_none());
+    }
+}
@@ -0,0 +1,418 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Shamir secret sharing of the vault master key for passphrase recovery.
+//!
+//! The 32-byte master key is split byte-wise over GF(2^8) into N shares, any
+//! K of which reconstruct it; fewer than K reveal nothing about the key.
+//! Shares are encoded as dash-separated groups of Crockford base32 (no
+//! `I`, `L`, `O` or `U`), so they can be written down or read aloud:
+//!
+//! ```text
+//! 0G2F-R03M-...-7QKD
+//! ```
+//!
+//! Each encoded share carries its threshold, its index, a random set id
+//! shared by all shares from one export (so mixing exports is detected), and
+//! a checksum that catches transcription errors.
+
+use blufio_core::BlufioError;
+use ring::digest;
+use ring::rand::{SecureRandom, SystemRandom};
+use zeroize::Zeroizing;
+
+/// Share encoding version.
+const SHARE_VERSION: u8 = 1;
+
+/// Encoded share length: version, set id (2), threshold, index, value (32),
+/// checksum (3) -- 40 bytes, exactly 64 base32 characters.
+const SHARE_BYTES: usize = 40;
+
+/// Crockford base32 alphabet.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Characters per dash-separated group in an encoded share.
+const GROUP_LEN: usize = 4;
+
+/// One share of a split master key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RecoveryShare {
+    /// Random id common to all shares of one split.
+    pub set_id: u16,
+    /// Number of shares needed to reconstruct the key.
+    pub threshold: u8,
+    /// Evaluation point of this share (1..=255, never 0).
+    pub index: u8,
+    value: Zeroizing<[u8; 32]>,
+}
+
+impl std::fmt::Debug for RecoveryShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecoveryShare")
+            .field("set_id", &self.set_id)
+            .field("threshold", &self.threshold)
+            .field("index", &self.index)
+            .field("value", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl RecoveryShare {
+    /// Encode the share as grouped Crockford base32.
+    pub fn encode(&self) -> String {
+        let mut bytes = Zeroizing::new([0u8; SHARE_BYTES]);
+        bytes[0] = SHARE_VERSION;
+        bytes[1..3].copy_from_slice(&self.set_id.to_be_bytes());
+        bytes[3] = self.threshold;
+        bytes[4] = self.index;
+        bytes[5..37].copy_from_slice(&*self.value);
+        let check = checksum(&bytes[..37]);
+        bytes[37..].copy_from_slice(&check);
+
+        let chars = Zeroizing::new(base32_encode(&*bytes));
+        chars
+            .as_bytes()
+            .chunks(GROUP_LEN)
+            .map(|group| std::str::from_utf8(group).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    /// Decode a share, ignoring case, whitespace and dashes.
+    ///
+    /// Commonly confused characters are accepted (`I`/`L` as `1`, `O` as `0`).
+    pub fn decode(encoded: &str) -> Result<Self, BlufioError> {
+        let bytes = base32_decode(encoded)?;
+        if bytes.len() != SHARE_BYTES {
+            return Err(BlufioError::Vault(format!(
+                "invalid recovery share: expected {} characters, got {}",
+                SHARE_BYTES * 8 / 5,
+                encoded
+                    .chars()
+                    .filter(|c| !c.is_whitespace() && *c != '-')
+                    .count()
+            )));
+        }
+        if checksum(&bytes[..37]) != bytes[37..] {
+            return Err(BlufioError::Vault(
+                "invalid recovery share: checksum mismatch (check for typos)".to_string(),
+            ));
+        }
+        if bytes[0] != SHARE_VERSION {
+            return Err(BlufioError::Vault(format!(
+                "unsupported recovery share version {}",
+                bytes[0]
+            )));
+        }
+        if bytes[3] == 0 || bytes[4] == 0 {
+            return Err(BlufioError::Vault(
+                "invalid recovery share: zero threshold or index".to_string(),
+            ));
+        }
+
+        let mut value = Zeroizing::new([0u8; 32]);
+        value.copy_from_slice(&bytes[5..37]);
+        Ok(Self {
+            set_id: u16::from_be_bytes([bytes[1], bytes[2]]),
+            threshold: bytes[3],
+            index: bytes[4],
+            value,
+        })
+    }
+}
+
+/// Split a 32-byte secret into `shares` shares with reconstruction threshold
+/// `threshold`.
+///
+/// Requires `2 <= threshold <= shares <= 255`.
+pub fn split(
+    secret: &[u8; 32],
+    threshold: u8,
+    shares: u8,
+) -> Result<Vec<RecoveryShare>, BlufioError> {
+    if threshold < 2 || threshold > shares {
+        return Err(BlufioError::Vault(format!(
+            "invalid recovery split: need 2 <= threshold <= shares, got {threshold} of {shares}"
+        )));
+    }
+
+    let rng = SystemRandom::new();
+    let mut set_id = [0u8; 2];
+    rng.fill(&mut set_id)
+        .map_err(|_| BlufioError::Vault("failed to generate share set id".to_string()))?;
+    let set_id = u16::from_be_bytes(set_id);
+
+    // coefficients[b] = [secret[b], c1, .., c(k-1)] for each key byte b.
+    let k = usize::from(threshold);
+    let mut coefficients = Zeroizing::new(vec![0u8; 32 * k]);
+    rng.fill(&mut coefficients)
+        .map_err(|_| BlufioError::Vault("failed to generate share coefficients".to_string()))?;
+    for (b, byte) in secret.iter().enumerate() {
+        coefficients[b * k] = *byte;
+    }
+
+    Ok((1..=shares)
+        .map(|x| {
+            let mut value = Zeroizing::new([0u8; 32]);
+            for (b, out) in value.iter_mut().enumerate() {
+                *out = eval_poly(&coefficients[b * k..(b + 1) * k], x);
+            }
+            RecoveryShare {
+                set_id,
+                threshold,
+                index: x,
+                value,
+            }
+        })
+        .collect())
+}
+
+/// Reconstruct the secret from at least `threshold` shares of one split.
+///
+/// With fewer than `threshold` shares this is an error; note that
+/// interpolating too few shares would silently yield an unrelated key.
+pub fn combine(shares: &[RecoveryShare]) -> Result<Zeroizing<[u8; 32]>, BlufioError> {
+    let first = shares
+        .first()
+        .ok_or_else(|| BlufioError::Vault("no recovery shares provided".to_string()))?;
+    if shares
+        .iter()
+        .any(|s| s.set_id != first.set_id || s.threshold != first.threshold)
+    {
+        return Err(BlufioError::Vault(
+            "recovery shares come from different exports".to_string(),
+        ));
+    }
+
+    let mut distinct: Vec<&RecoveryShare> = Vec::new();
+    for share in shares {
+        if !distinct.iter().any(|s| s.index == share.index) {
+            distinct.push(share);
+        }
+    }
+    if distinct.len() < usize::from(first.threshold) {
+        return Err(BlufioError::Vault(format!(
+            "need {} distinct recovery shares, got {}",
+            first.threshold,
+            distinct.len()
+        )));
+    }
+    let used = &distinct[..usize::from(first.threshold)];
+
+    // Lagrange interpolation at x = 0; subtraction in GF(2^8) is XOR.
+    let mut secret = Zeroizing::new([0u8; 32]);
+    for (i, share_i) in used.iter().enumerate() {
+        let mut basis = 1u8;
+        for (j, share_j) in used.iter().enumerate() {
+            if i != j {
+                basis = gf_mul(
+                    basis,
+                    gf_mul(share_j.index, gf_inv(share_j.index ^ share_i.index)),
+                );
+            }
+        }
+        for (out, y) in secret.iter_mut().zip(share_i.value.iter()) {
+            *out ^= gf_mul(*y, basis);
+        }
+    }
+    Ok(secret)
+}
+
+/// Evaluate a polynomial (constant term first) at `x` using Horner's rule.
+fn eval_poly(coefficients: &[u8], x: u8) -> u8 {
+    coefficients
+        .iter()
+        .rev()
+        .fold(0u8, |acc, c| gf_mul(acc, x) ^ c)
+}
+
+/// Multiply in GF(2^8) modulo the AES polynomial x^8 + x^4 + x^3 + x + 1.
+///
+/// Branch-free on the operand values to avoid timing leaks.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        product ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+/// Multiplicative inverse in GF(2^8) (a^254); `a` must be non-zero.
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exp = 254u8;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+/// First three bytes of SHA-256 over the share header and value.
+fn checksum(bytes: &[u8]) -> [u8; 3] {
+    let hash = digest::digest(&digest::SHA256, bytes);
+    let mut out = [0u8; 3];
+    out.copy_from_slice(&hash.as_ref()[..3]);
+    out
+}
+
+/// Crockford base32 encode (no padding).
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 8 / 5 + 1);
+    let mut buffer = 0u16;
+    let mut bits = 0;
+    for byte in bytes {
+        buffer = (buffer << 8) | u16::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(char::from(ALPHABET[usize::from((buffer >> bits) & 0x1f)]));
+        }
+    }
+    if bits > 0 {
+        out.push(char::from(
+            ALPHABET[usize::from((buffer << (5 - bits)) & 0x1f)],
+        ));
+    }
+    out
+}
+
+/// Crockford base32 decode, skipping dashes and whitespace.
+fn base32_decode(encoded: &str) -> Result<Zeroizing<Vec<u8>>, BlufioError> {
+    let mut out = Zeroizing::new(Vec::with_capacity(SHARE_BYTES));
+    let mut buffer = 0u16;
+    let mut bits = 0;
+    for c in encoded.chars() {
+        if c == '-' || c.is_whitespace() {
+            continue;
+        }
+        let c = match c.to_ascii_uppercase() {
+            'I' | 'L' => '1',
+            'O' => '0',
+            other => other,
+        };
+        let value = ALPHABET
+            .iter()
+            .position(|a| char::from(*a) == c)
+            .ok_or_else(|| {
+                BlufioError::Vault(format!("invalid character '{c}' in recovery share"))
+            })?;
+        buffer = (buffer << 5) | value as u16;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secret() -> [u8; 32] {
+        let mut s = [0u8; 32];
+        for (i, b) in s.iter_mut().enumerate() {
+            *b = (i as u8).wrapping_mul(37).wrapping_add(11);
+        }
+        s
+    }
+
+    #[test]
+    fn gf_inverse_roundtrip() {
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_inv(a)), 1, "a = {a}");
+        }
+    }
+
+    #[test]
+    fn any_k_of_n_shares_reconstruct() {
+        let shares = split(&secret(), 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        // Every 3-element subset, as a bitmask over the 5 shares.
+        for mask in (0u32..32).filter(|m| m.count_ones() == 3) {
+            let subset: Vec<RecoveryShare> = shares
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| mask & (1 << i) != 0)
+                .map(|(_, s)| s.clone())
+                .collect();
+            assert_eq!(*combine(&subset).unwrap(), secret());
+        }
+    }
+
+    #[test]
+    fn fewer_than_k_shares_do_not_reconstruct() {
+        let shares = split(&secret(), 3, 5).unwrap();
+
+        let err = combine(&shares[..2]).unwrap_err();
+        assert!(err.to_string().contains("need 3"), "{err}");
+
+        // Even forcing interpolation with K-1 shares yields a different key.
+        let forged: Vec<RecoveryShare> = shares[..2]
+            .iter()
+            .map(|s| RecoveryShare {
+                threshold: 2,
+                ..s.clone()
+            })
+            .collect();
+        assert_ne!(*combine(&forged).unwrap(), secret());
+    }
+
+    #[test]
+    fn duplicate_shares_do_not_count_twice() {
+        let shares = split(&secret(), 2, 3).unwrap();
+        let err = combine(&[shares[0].clone(), shares[0].clone()]).unwrap_err();
+        assert!(err.to_string().contains("distinct"), "{err}");
+    }
+
+    #[test]
+    fn shares_from_different_splits_are_rejected() {
+        let a = split(&secret(), 2, 3).unwrap();
+        let mut b = split(&secret(), 2, 3).unwrap();
+        b[1].set_id = a[0].set_id.wrapping_add(1);
+        assert!(combine(&[a[0].clone(), b[1].clone()]).is_err());
+    }
+
+    #[test]
+    fn invalid_threshold_is_rejected() {
+        assert!(split(&secret(), 1, 3).is_err());
+        assert!(split(&secret(), 4, 3).is_err());
+    }
+
+    #[test]
+    fn encode_decode_roundtrip() {
+        for share in split(&secret(), 2, 3).unwrap() {
+            let encoded = share.encode();
+            assert_eq!(encoded.len(), 64 + 15);
+            assert!(encoded.split('-').all(|g| g.len() == GROUP_LEN));
+            assert_eq!(RecoveryShare::decode(&encoded).unwrap(), share);
+
+            // Case-insensitive and tolerant of missing dashes.
+            let sloppy = encoded.replace('-', " ").to_lowercase();
+            assert_eq!(RecoveryShare::decode(&sloppy).unwrap(), share);
+        }
+    }
+
+    #[test]
+    fn decode_detects_typos() {
+        let encoded = split(&secret(), 2, 3).unwrap()[0].encode();
+        let mut chars: Vec<char> = encoded.chars().collect();
+        // Inside the second group, clear of the dashes.
+        let pos = GROUP_LEN + 3;
+        chars[pos] = if chars[pos] == 'A' { 'B' } else { 'A' };
+        let typo: String = chars.into_iter().collect();
+
+        let err = RecoveryShare::decode(&typo).unwrap_err();
+        assert!(err.to_string().contains("checksum"), "{err}");
+    }
+}
@@ -9,6 +9,8 @@
 //!   passphrase via Argon2id (stored in vault_meta as wrapped_master_key).
 //! - Changing the passphrase only re-wraps the master key; individual secrets
 //!   are never re-encrypted.
+//! - Optionally, the master key is split into Shamir recovery shares that
+//!   unlock the vault without the passphrase (see [`crate::shamir`]).
 
 use blufio_config::model::VaultConfig;
 use blufio_core::BlufioError;
@@ -19,6 +21,11 @@ use zeroize::Zeroizing;
 
 use crate::crypto;
 use crate::kdf;
+use crate::shamir::{self, RecoveryShare};
+
+/// Known plaintext sealed under the master key when recovery shares are
+/// exported, so a reconstructed key can be verified before use.
+const RECOVERY_CHECK_PLAINTEXT: &[u8] = b"blufio-vault-recovery-check";
 
 /// The unlocked vault, holding the master key in memory.
 ///
@@ -124,94 +131,103 @@ impl Vault {
         passphrase: &SecretString,
         _config: &VaultConfig,
     ) -> Result<Self, BlufioError> {
-        // Read vault_meta entries.
-        let meta = conn
-            .call(|conn| -> Result<VaultMeta, rusqlite::Error> {
-                let wrapped_master_key: Vec<u8> = conn.query_row(
-                    "SELECT value FROM vault_meta WHERE key = 'wrapped_master_key'",
-                    [],
-                    |row| row.get(0),
-                )?;
-                let nonce: Vec<u8> = conn.query_row(
-                    "SELECT value FROM vault_meta WHERE key = 'master_key_nonce'",
-                    [],
-                    |row| row.get(0),
-                )?;
-                let salt: Vec<u8> = conn.query_row(
-                    "SELECT value FROM vault_meta WHERE key = 'kdf_salt'",
-                    [],
-                    |row| row.get(0),
-                )?;
-                let kdf_params_bytes: Vec<u8> = conn.query_row(
-                    "SELECT value FROM vault_meta WHERE key = 'kdf_params'",
-                    [],
-                    |row| row.get(0),
-                )?;
-                Ok(VaultMeta {
-                    wrapped_master_key,
-                    nonce,
-                    salt,
-                    kdf_params_bytes,
-                })
-            })
-            .await
-            .map_err(map_tr_err)?;
+        let meta = read_meta(&conn).await?;
+        let master_key = unwrap_master_key(&meta, passphrase)?;
 
-        // Parse KDF params.
-        let kdf_params: serde_json::Value = serde_json::from_slice(&meta.kdf_params_bytes)
-            .map_err(|e| BlufioError::Vault(format!("corrupted KDF params: {e}")))?;
-
-        let memory_cost = kdf_params["memory_cost"]
-            .as_u64()
-            .ok_or_else(|| BlufioError::Vault("missing memory_cost in KDF params".to_string()))?
-            as u32;
-        let iterations = kdf_params["iterations"]
-            .as_u64()
-            .ok_or_else(|| BlufioError::Vault("missing iterations in KDF params".to_string()))?
-            as u32;
-        let parallelism = kdf_params["parallelism"]
-            .as_u64()
-            .ok_or_else(|| BlufioError::Vault("missing parallelism in KDF params".to_string()))?
-            as u32;
-
-        // Extract salt and nonce.
-        let salt: [u8; 16] = meta
-            .salt
-            .try_into()
-            .map_err(|_| BlufioError::Vault("corrupted salt (expected 16 bytes)".to_string()))?;
-        let nonce: [u8; 12] = meta
-            .nonce
-            .try_into()
-            .map_err(|_| BlufioError::Vault("corrupted nonce (expected 12 bytes)".to_string()))?;
-
-        // Derive wrapping key.
-        let wrapping_key = kdf::derive_key(
-            passphrase.expose_secret().as_bytes(),
-            &salt,
-            memory_cost,
-            iterations,
-            parallelism,
-        )?;
+        debug!("vault unlocked");
+        Ok(Self { master_key, conn })
+    }
 
-        // Unwrap master key.
-        let master_key_bytes = crypto::open(&wrapping_key, &nonce, &meta.wrapped_master_key)
-            .map_err(|_| {
-                BlufioError::Vault(
-                    "invalid passphrase or corrupted vault -- decryption failed".to_string(),
-                )
-            })?;
+    /// Unlock the vault with Shamir recovery shares instead of the passphrase.
+    ///
+    /// At least the export's threshold of shares is required. The
+    /// reconstructed key is checked against the value recorded at export
+    /// time, so too few, forged or mixed-up shares fail rather than yielding
+    /// a wrong key.
+    pub async fn unlock_with_shares(
+        conn: tokio_rusqlite::Connection,
+        shares: &[RecoveryShare],
+    ) -> Result<Self, BlufioError> {
+        let candidate = shamir::combine(shares)?;
 
-        let master_key: [u8; 32] = master_key_bytes.try_into().map_err(|_| {
-            BlufioError::Vault("corrupted master key (expected 32 bytes)".to_string())
+        let check = conn
+            .call(
+                |conn| -> Result<Option<(Vec<u8>, Vec<u8>)>, rusqlite::Error> {
+                    let read = |key: &str| -> Result<Option<Vec<u8>>, rusqlite::Error> {
+                        match conn.query_row(
+                            "SELECT value FROM vault_meta WHERE key = ?1",
+                            params![key],
+                            |row| row.get(0),
+                        ) {
+                            Ok(value) => Ok(Some(value)),
+                            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                            Err(e) => Err(e),
+                        }
+                    };
+                    Ok(read("recovery_check")?.zip(read("recovery_check_nonce")?))
+                },
+            )
+            .await
+            .map_err(map_tr_err)?;
+        let (ciphertext, nonce) = check.ok_or_else(|| {
+            BlufioError::Vault("no recovery shares have been exported for this vault".to_string())
+        })?;
+        let nonce: [u8; 12] = nonce.try_into().map_err(|_| {
+            BlufioError::Vault("corrupted recovery check nonce (expected 12 bytes)".to_string())
         })?;
 
-        debug!("vault unlocked");
+        match crypto::open(&candidate, &nonce, &ciphertext) {
+            Ok(plaintext) if plaintext == RECOVERY_CHECK_PLAINTEXT => {}
+            _ => {
+                return Err(BlufioError::Vault(
+                    "recovery shares do not reconstruct this vault's master key".to_string(),
+                ));
+            }
+        }
+
+        info!("vault unlocked with recovery shares");
         Ok(Self {
-            master_key: Zeroizing::new(master_key),
+            master_key: candidate,
             conn,
         })
     }
 
+    /// Split the master key into `shares` recovery shares, any `threshold` of
+    /// which unlock the vault via [`Vault::unlock_with_shares`].
+    ///
+    /// Shares stay valid across passphrase changes (the master key does not
+    /// change). A new export replaces the stored check value, so shares from
+    /// an earlier export keep working only because they encode the same key.
+    pub async fn export_recovery_shares(
+        &self,
+        threshold: u8,
+        shares: u8,
+    ) -> Result<Vec<RecoveryShare>, BlufioError> {
+        let split = shamir::split(&self.master_key, threshold, shares)?;
+
+        let (ciphertext, nonce) = crypto::seal(&self.master_key, RECOVERY_CHECK_PLAINTEXT)?;
+        let nonce_vec = nonce.to_vec();
+        self.conn
+            .call(move |conn| -> Result<(), rusqlite::Error> {
+                let tx = conn.transaction()?;
+                tx.execute(
+                    "INSERT OR REPLACE INTO vault_meta (key, value) VALUES ('recovery_check', ?1)",
+                    params![ciphertext],
+                )?;
+                tx.execute(
+                    "INSERT OR REPLACE INTO vault_meta (key, value) VALUES ('recovery_check_nonce', ?1)",
+                    params![nonce_vec],
+                )?;
+                tx.commit()?;
+                Ok(())
+            })
+            .await
+            .map_err(map_tr_err)?;
+
+        info!(threshold, shares, "vault recovery shares exported");
+        Ok(split)
+    }
+
     /// Store a secret in the vault, encrypted with the master key.
     pub async fn store_secret(&self, name: &str, plaintext: &str) -> Result<(), BlufioError> {
         let (ciphertext, nonce) = crypto::seal(&self.master_key, plaintext.as_bytes())?;
@@ -304,20 +320,72 @@ impl Vault {
     }
 
     /// Delete a secret from the vault.
-    pub async fn delete_secret(&self, name: &str) -> Result<(), BlufioError> {
+    ///
+    /// Idempotent: deleting a missing secret is not an error. Returns whether
+    /// a secret was actually removed.
+    pub async fn delete_secret(&self, name: &str) -> Result<bool, BlufioError> {
         let name_owned = name.to_string();
-        self.conn
-            .call(move |conn| -> Result<(), rusqlite::Error> {
+        let deleted = self
+            .conn
+            .call(move |conn| -> Result<usize, rusqlite::Error> {
                 conn.execute(
                     "DELETE FROM vault_entries WHERE name = ?1",
                     params![name_owned],
+                )
+            })
+            .await
+            .map_err(map_tr_err)?;
+        debug!(name = %name, deleted = deleted > 0, "secret deleted from vault");
+        Ok(deleted > 0)
+    }
+
+    /// Rename a secret, keeping its encrypted value.
+    ///
+    /// Fails if `from` does not exist or `to` already exists.
+    pub async fn rename_secret(&self, from: &str, to: &str) -> Result<(), BlufioError> {
+        let from_owned = from.to_string();
+        let to_owned = to.to_string();
+        let outcome = self
+            .conn
+            .call(move |conn| -> Result<RenameOutcome, rusqlite::Error> {
+                let tx = conn.transaction()?;
+                let exists = |name: &str| -> Result<bool, rusqlite::Error> {
+                    tx.query_row(
+                        "SELECT COUNT(*) FROM vault_entries WHERE name = ?1",
+                        params![name],
+                        |row| row.get::<_, i64>(0),
+                    )
+                    .map(|count| count > 0)
+                };
+                if !exists(&from_owned)? {
+                    return Ok(RenameOutcome::SourceMissing);
+                }
+                if exists(&to_owned)? {
+                    return Ok(RenameOutcome::TargetExists);
+                }
+                tx.execute(
+                    "UPDATE vault_entries SET name = ?2, \
+                     updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now') WHERE name = ?1",
+                    params![from_owned, to_owned],
                 )?;
-                Ok(())
+                tx.commit()?;
+                Ok(RenameOutcome::Renamed)
             })
             .await
             .map_err(map_tr_err)?;
-        debug!(name = %name, "secret deleted from vault");
-        Ok(())
+
+        match outcome {
+            RenameOutcome::Renamed => {
+                debug!(from = %from, to = %to, "secret renamed in vault");
+                Ok(())
+            }
+            RenameOutcome::SourceMissing => Err(BlufioError::Vault(format!(
+                "secret '{from}' not found in vault"
+            ))),
+            RenameOutcome::TargetExists => Err(BlufioError::Vault(format!(
+                "secret '{to}' already exists in vault -- delete it first"
+            ))),
+        }
     }
 
     /// Change the vault passphrase by re-wrapping the master key.
@@ -328,6 +396,59 @@ impl Vault {
         new_passphrase: &SecretString,
         config: &VaultConfig,
     ) -> Result<(), BlufioError> {
+        self.rewrap_master_key(new_passphrase, config, None).await?;
+
+        info!("vault passphrase changed successfully");
+        Ok(())
+    }
+
+    /// Rotate the vault passphrase, verifying the current one first.
+    ///
+    /// The old passphrase must unwrap the stored master key to the key this
+    /// vault holds. The four `vault_meta` rows are then replaced in a single
+    /// transaction, guarded by the wrapped key read during verification, so a
+    /// crash leaves either the old or the new passphrase valid -- never
+    /// neither -- and a concurrent rotation aborts this one.
+    pub async fn rotate_passphrase(
+        &self,
+        old_passphrase: &SecretString,
+        new_passphrase: &SecretString,
+        config: &VaultConfig,
+    ) -> Result<(), BlufioError> {
+        let meta = read_meta(&self.conn).await?;
+        let current_key = unwrap_master_key(&meta, old_passphrase).map_err(|_| {
+            BlufioError::Vault("current passphrase is incorrect -- rotation aborted".to_string())
+        })?;
+        if *current_key != *self.master_key {
+            return Err(BlufioError::Vault(
+                "vault master key changed since unlock -- rotation aborted".to_string(),
+            ));
+        }
+
+        let swapped = self
+            .rewrap_master_key(new_passphrase, config, Some(meta.wrapped_master_key))
+            .await?;
+        if !swapped {
+            return Err(BlufioError::Vault(
+                "vault passphrase was changed concurrently -- rotation aborted".to_string(),
+            ));
+        }
+
+        info!("vault passphrase rotated successfully");
+        Ok(())
+    }
+
+    /// Wrap the master key under a new passphrase and replace the four
+    /// `vault_meta` rows in one transaction.
+    ///
+    /// With `expected_wrapped_key`, the stored wrapper is only replaced if it
+    /// still matches (compare-and-swap); returns `false` when it did not.
+    async fn rewrap_master_key(
+        &self,
+        new_passphrase: &SecretString,
+        config: &VaultConfig,
+        expected_wrapped_key: Option<Vec<u8>>,
+    ) -> Result<bool, BlufioError> {
         // Generate new salt and derive new wrapping key.
         let new_salt = kdf::generate_salt()?;
         let new_wrapping_key = kdf::derive_key(
@@ -352,12 +473,22 @@ impl Vault {
         let new_nonce_vec = new_nonce.to_vec();
 
         self.conn
-            .call(move |conn| -> Result<(), rusqlite::Error> {
+            .call(move |conn| -> Result<bool, rusqlite::Error> {
                 let tx = conn.transaction()?;
-                tx.execute(
-                    "UPDATE vault_meta SET value = ?1 WHERE key = 'wrapped_master_key'",
-                    params![new_wrapped_key],
-                )?;
+                let updated = match expected_wrapped_key {
+                    Some(expected) => tx.execute(
+                        "UPDATE vault_meta SET value = ?1 WHERE key = 'wrapped_master_key' AND value = ?2",
+                        params![new_wrapped_key, expected],
+                    )?,
+                    None => tx.execute(
+                        "UPDATE vault_meta SET value = ?1 WHERE key = 'wrapped_master_key'",
+                        params![new_wrapped_key],
+                    )?,
+                };
+                if updated != 1 {
+                    // Dropping the transaction rolls it back.
+                    return Ok(false);
+                }
                 tx.execute(
                     "UPDATE vault_meta SET value = ?1 WHERE key = 'master_key_nonce'",
                     params![new_nonce_vec],
@@ -371,13 +502,10 @@ impl Vault {
                     params![kdf_params_bytes],
                 )?;
                 tx.commit()?;
-                Ok(())
+                Ok(true)
             })
             .await
-            .map_err(map_tr_err)?;
-
-        info!("vault passphrase changed successfully");
-        Ok(())
+            .map_err(map_tr_err)
     }
 
     /// Returns a reference to the underlying database connection.
@@ -386,6 +514,13 @@ impl Vault {
     }
 }
 
+/// Result of the rename transaction.
+enum RenameOutcome {
+    Renamed,
+    SourceMissing,
+    TargetExists,
+}
+
 /// Internal struct for reading vault_meta entries.
 struct VaultMeta {
     wrapped_master_key: Vec<u8>,
@@ -394,6 +529,97 @@ struct VaultMeta {
     kdf_params_bytes: Vec<u8>,
 }
 
+/// Read the wrapped master key and its KDF inputs from vault_meta.
+async fn read_meta(conn: &tokio_rusqlite::Connection) -> Result<VaultMeta, BlufioError> {
+    conn.call(|conn| -> Result<VaultMeta, rusqlite::Error> {
+        let wrapped_master_key: Vec<u8> = conn.query_row(
+            "SELECT value FROM vault_meta WHERE key = 'wrapped_master_key'",
+            [],
+            |row| row.get(0),
+        )?;
+        let nonce: Vec<u8> = conn.query_row(
+            "SELECT value FROM vault_meta WHERE key = 'master_key_nonce'",
+            [],
+            |row| row.get(0),
+        )?;
+        let salt: Vec<u8> = conn.query_row(
+            "SELECT value FROM vault_meta WHERE key = 'kdf_salt'",
+            [],
+            |row| row.get(0),
+        )?;
+        let kdf_params_bytes: Vec<u8> = conn.query_row(
+            "SELECT value FROM vault_meta WHERE key = 'kdf_params'",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(VaultMeta {
+            wrapped_master_key,
+            nonce,
+            salt,
+            kdf_params_bytes,
+        })
+    })
+    .await
+    .map_err(map_tr_err)
+}
+
+/// Derive the wrapping key from the passphrase and decrypt the master key.
+fn unwrap_master_key(
+    meta: &VaultMeta,
+    passphrase: &SecretString,
+) -> Result<Zeroizing<[u8; 32]>, BlufioError> {
+    // Parse KDF params.
+    let kdf_params: serde_json::Value = serde_json::from_slice(&meta.kdf_params_bytes)
+        .map_err(|e| BlufioError::Vault(format!("corrupted KDF params: {e}")))?;
+
+    let memory_cost = kdf_params["memory_cost"]
+        .as_u64()
+        .ok_or_else(|| BlufioError::Vault("missing memory_cost in KDF params".to_string()))?
+        as u32;
+    let iterations = kdf_params["iterations"]
+        .as_u64()
+        .ok_or_else(|| BlufioError::Vault("missing iterations in KDF params".to_string()))?
+        as u32;
+    let parallelism = kdf_params["parallelism"]
+        .as_u64()
+        .ok_or_else(|| BlufioError::Vault("missing parallelism in KDF params".to_string()))?
+        as u32;
+
+    // Extract salt and nonce.
+    let salt: [u8; 16] = meta
+        .salt
+        .as_slice()
+        .try_into()
+        .map_err(|_| BlufioError::Vault("corrupted salt (expected 16 bytes)".to_string()))?;
+    let nonce: [u8; 12] = meta
+        .nonce
+        .as_slice()
+        .try_into()
+        .map_err(|_| BlufioError::Vault("corrupted nonce (expected 12 bytes)".to_string()))?;
+
+    // Derive wrapping key.
+    let wrapping_key = kdf::derive_key(
+        passphrase.expose_secret().as_bytes(),
+        &salt,
+        memory_cost,
+        iterations,
+        parallelism,
+    )?;
+
+    // Unwrap master key.
+    let master_key_bytes =
+        crypto::open(&wrapping_key, &nonce, &meta.wrapped_master_key).map_err(|_| {
+            BlufioError::Vault(
+                "invalid passphrase or corrupted vault -- decryption failed".to_string(),
+            )
+        })?;
+
+    let master_key: [u8; 32] = master_key_bytes
+        .try_into()
+        .map_err(|_| BlufioError::Vault("corrupted master key (expected 32 bytes)".to_string()))?;
+    Ok(Zeroizing::new(master_key))
+}
+
 /// Mask a secret value for display: "sk-ant-api03-abc...xyz" format.
 ///
 /// Shows prefix (up to 4 chars) and suffix (up to 4 chars) with "..." in between.
@@ -550,6 +776,79 @@ mod tests {
         assert!(vault.retrieve_secret("to-delete").await.unwrap().is_none());
     }
 
+    #[tokio::test]
+    async fn delete_then_list_omits_secret() {
+        let (conn, _dir) = open_test_db().await;
+        let config = test_config();
+        let passphrase = SecretString::from("test-pass".to_string());
+
+        let vault = Vault::create(conn, &passphrase, &config).await.unwrap();
+        vault.store_secret("keep", "keep-value-123").await.unwrap();
+        vault
+            .store_secret("leaked", "leaked-value-456")
+            .await
+            .unwrap();
+
+        assert!(vault.delete_secret("leaked").await.unwrap());
+
+        let names: Vec<String> = vault
+            .list_secrets()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, vec!["keep".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn delete_missing_secret_is_not_an_error() {
+        let (conn, _dir) = open_test_db().await;
+        let config = test_config();
+        let passphrase = SecretString::from("test-pass".to_string());
+
+        let vault = Vault::create(conn, &passphrase, &config).await.unwrap();
+        assert!(!vault.delete_secret("never-stored").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn rename_secret_moves_value() {
+        let (conn, _dir) = open_test_db().await;
+        let config = test_config();
+        let passphrase = SecretString::from("test-pass".to_string());
+
+        let vault = Vault::create(conn, &passphrase, &config).await.unwrap();
+        vault.store_secret("old.name", "value-123").await.unwrap();
+
+        vault.rename_secret("old.name", "new.name").await.unwrap();
+        assert!(vault.retrieve_secret("old.name").await.unwrap().is_none());
+        let secret = vault.retrieve_secret("new.name").await.unwrap().unwrap();
+        assert_eq!(secret.expose_secret(), "value-123");
+    }
+
+    #[tokio::test]
+    async fn rename_secret_fails_on_collision() {
+        let (conn, _dir) = open_test_db().await;
+        let config = test_config();
+        let passphrase = SecretString::from("test-pass".to_string());
+
+        let vault = Vault::create(conn, &passphrase, &config).await.unwrap();
+        vault.store_secret("a", "value-a").await.unwrap();
+        vault.store_secret("b", "value-b").await.unwrap();
+
+        let err = vault.rename_secret("a", "b").await.unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err}");
+
+        // Both secrets are untouched.
+        let a = vault.retrieve_secret("a").await.unwrap().unwrap();
+        let b = vault.retrieve_secret("b").await.unwrap().unwrap();
+        assert_eq!(a.expose_secret(), "value-a");
+        assert_eq!(b.expose_secret(), "value-b");
+
+        let err = vault.rename_secret("missing", "c").await.unwrap_err();
+        assert!(err.to_string().contains("not found"), "{err}");
+    }
+
     #[tokio::test]
     async fn change_passphrase_preserves_secrets() {
         let (conn, _dir) = open_test_db().await;
@@ -579,6 +878,210 @@ mod tests {
         assert_eq!(secret.expose_secret(), "secret-value-123");
     }
 
+    #[tokio::test]
+    async fn rotate_passphrase_round_trip() {
+        let (conn, _dir) = open_test_db().await;
+        let config = test_config();
+        let old_pass = SecretString::from("old-passphrase".to_string());
+        let new_pass = SecretString::from("new-passphrase".to_string());
+
+        let vault = Vault::create(conn.clone(), &old_pass, &config)
+            .await
+            .unwrap();
+        vault
+            .store_secret("api-key", "sk-rotate-123")
+            .await
+            .unwrap();
+        let ciphertext_before: Vec<u8> = conn
+            .call(|c| {
+                c.query_row(
+                    "SELECT ciphertext FROM vault_entries WHERE name = 'api-key'",
+                    [],
+                    |row| row.get(0),
+                )
+            })
+            .await
+            .unwrap();
+
+        vault
+            .rotate_passphrase(&old_pass, &new_pass, &config)
+            .await
+            .unwrap();
+        drop(vault);
+
+        assert!(
+            Vault::unlock(conn.clone(), &old_pass, &config)
+                .await
+                .is_err()
+        );
+        let vault2 = Vault::unlock(conn.clone(), &new_pass, &config)
+            .await
+            .unwrap();
+        let secret = vault2.retrieve_secret("api-key").await.unwrap().unwrap();
+        assert_eq!(secret.expose_secret(), "sk-rotate-123");
+
+        // Secrets are not re-encrypted, only the master key wrapper changes.
+        let ciphertext_after: Vec<u8> = conn
+            .call(|c| {
+                c.query_row(
+                    "SELECT ciphertext FROM vault_entries WHERE name = 'api-key'",
+                    [],
+                    |row| row.get(0),
+                )
+            })
+            .await
+            .unwrap();
+        assert_eq!(ciphertext_before, ciphertext_after);
+
+        // And back again.
+        vault2
+            .rotate_passphrase(&new_pass, &old_pass, &config)
+            .await
+            .unwrap();
+        assert!(Vault::unlock(conn, &old_pass, &config).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn rotate_passphrase_rejects_wrong_old_passphrase() {
+        let (conn, _dir) = open_test_db().await;
+        let config = test_config();
+        let pass = SecretString::from("correct".to_string());
+        let wrong = SecretString::from("wrong".to_string());
+        let new_pass = SecretString::from("new".to_string());
+
+        let vault = Vault::create(conn.clone(), &pass, &config).await.unwrap();
+        let err = vault
+            .rotate_passphrase(&wrong, &new_pass, &config)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("incorrect"), "{err}");
+
+        // Nothing changed: the original passphrase still unlocks.
+        assert!(Vault::unlock(conn.clone(), &pass, &config).await.is_ok());
+        assert!(Vault::unlock(conn, &new_pass, &config).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn rotate_passphrase_aborts_after_concurrent_change() {
+        let (conn, _dir) = open_test_db().await;
+        let config = test_config();
+        let pass = SecretString::from("original".to_string());
+        let other = SecretString::from("other".to_string());
+
+        let stale = Vault::create(conn.clone(), &pass, &config).await.unwrap();
+        let fresh = Vault::unlock(conn.clone(), &pass, &config).await.unwrap();
+        fresh
+            .rotate_passphrase(&pass, &other, &config)
+            .await
+            .unwrap();
+
+        // The stale handle's old passphrase no longer unwraps the stored key.
+        assert!(
+            stale
+                .rotate_passphrase(&pass, &pass, &config)
+                .await
+                .is_err()
+        );
+        assert!(Vault::unlock(conn, &other, &config).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn recovery_shares_unlock_vault() {
+        let (conn, _dir) = open_test_db().await;
+        let config = test_config();
+        let passphrase = SecretString::from("forgotten".to_string());
+
+        let vault = Vault::create(conn.clone(), &passphrase, &config)
+            .await
+            .unwrap();
+        vault.store_secret("api-key", "sk-recover-1").await.unwrap();
+        let shares = vault.export_recovery_shares(3, 5).await.unwrap();
+        drop(vault);
+
+        // Any 3 of 5 shares, round-tripped through their printed form.
+        let picked: Vec<RecoveryShare> = [&shares[4], &shares[0], &shares[2]]
+            .iter()
+            .map(|s| RecoveryShare::decode(&s.encode()).unwrap())
+            .collect();
+        let recovered = Vault::unlock_with_shares(conn.clone(), &picked)
+            .await
+            .unwrap();
+        let secret = recovered.retrieve_secret("api-key").await.unwrap().unwrap();
+        assert_eq!(secret.expose_secret(), "sk-recover-1");
+
+        // Recovery flow: set a new passphrase from the recovered vault.
+        let new_pass = SecretString::from("remembered".to_string());
+        recovered
+            .change_passphrase(&new_pass, &config)
+            .await
+            .unwrap();
+        assert!(Vault::unlock(conn, &new_pass, &config).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn too_few_recovery_shares_fail() {
+        let (conn, _dir) = open_test_db().await;
+        let config = test_config();
+        let passphrase = SecretString::from("pass".to_string());
+
+        let vault = Vault::create(conn.clone(), &passphrase, &config)
+            .await
+            .unwrap();
+        let shares = vault.export_recovery_shares(3, 5).await.unwrap();
+
+        assert!(
+            Vault::unlock_with_shares(conn.clone(), &shares[..2])
+                .await
+                .is_err()
+        );
+
+        // Forging a lower threshold interpolates a wrong key, which the
+        // recovery check rejects.
+        let forged: Vec<RecoveryShare> = shares[..2]
+            .iter()
+            .map(|s| {
+                let mut s = s.clone();
+                s.threshold = 2;
+                s
+            })
+            .collect();
+        let err = Vault::unlock_with_shares(conn, &forged).await.unwrap_err();
+        assert!(err.to_string().contains("do not reconstruct"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn unlock_with_shares_requires_export() {
+        let (conn, _dir) = open_test_db().await;
+        let config = test_config();
+        let passphrase = SecretString::from("pass".to_string());
+
+        let vault = Vault::create(conn.clone(), &passphrase, &config)
+            .await
+            .unwrap();
+        let shares = shamir::split(&vault.master_key, 2, 2).unwrap();
+
+        let err = Vault::unlock_with_shares(conn, &shares).await.unwrap_err();
+        assert!(err.to_string().contains("no recovery shares"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn unlock_uses_stored_kdf_params_not_config() {
+        let (conn, _dir) = open_test_db().await;
+        let passphrase = SecretString::from("tuned".to_string());
+
+        let _vault = Vault::create(conn.clone(), &passphrase, &test_config())
+            .await
+            .unwrap();
+
+        // Re-tuned config after creation must not affect unlocking.
+        let retuned = VaultConfig {
+            kdf_memory_cost: 65536,
+            kdf_iterations: 3,
+            kdf_parallelism: 2,
+        };
+        assert!(Vault::unlock(conn, &passphrase, &retuned).await.is_ok());
+    }
+
     #[tokio::test]
     async fn wrong_passphrase_fails_with_clear_error() {
         let (conn, _dir) = open_test_db().await;
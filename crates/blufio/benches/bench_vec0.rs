@@ -53,7 +53,9 @@ fn setup_bench_db(count: usize) -> (rusqlite::Connection, Vec<f32>) {
             classification TEXT NOT NULL DEFAULT 'internal',
             created_at TEXT NOT NULL DEFAULT '',
             updated_at TEXT NOT NULL DEFAULT '',
-            deleted_at TEXT
+            deleted_at TEXT,
+            last_accessed_at TEXT,
+            access_count INTEGER NOT NULL DEFAULT 0
         );",
     )
     .unwrap();
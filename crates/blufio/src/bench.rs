@@ -533,7 +533,7 @@ fn format_duration(d: Duration) -> String {
 }
 
 /// Format bytes for display.
-fn format_bytes(bytes: u64) -> String {
+pub(crate) fn format_bytes(bytes: u64) -> String {
     if bytes < 1024 {
         format!("{bytes} B")
     } else if bytes < 1024 * 1024 {
@@ -76,6 +76,341 @@ pub(crate) async fn cmd_list_secrets(
     Ok(())
 }
 
+/// Handle `blufio config delete-secret <key>`.
+///
+/// Deleting a secret that does not exist is not an error.
+pub(crate) async fn cmd_delete_secret(
+    config: &blufio_config::model::BlufioConfig,
+    key: &str,
+) -> Result<(), blufio_core::BlufioError> {
+    let db = open_db(config).await?;
+    let conn = db.connection().clone();
+
+    if !blufio_vault::Vault::exists(&conn).await? {
+        println!("No vault found. Nothing to delete.");
+        db.close().await?;
+        return Ok(());
+    }
+
+    let passphrase = blufio_vault::get_vault_passphrase()?;
+    let vault = blufio_vault::Vault::unlock(conn, &passphrase, &config.vault).await?;
+
+    if vault.delete_secret(key).await? {
+        eprintln!("Secret '{key}' deleted from vault.");
+    } else {
+        eprintln!("Secret '{key}' not found in vault; nothing deleted.");
+    }
+
+    db.close().await?;
+    Ok(())
+}
+
+/// Handle `blufio config rename-secret <from> <to>`.
+///
+/// Fails if `<to>` already exists.
+pub(crate) async fn cmd_rename_secret(
+    config: &blufio_config::model::BlufioConfig,
+    from: &str,
+    to: &str,
+) -> Result<(), blufio_core::BlufioError> {
+    let db = open_db(config).await?;
+    let conn = db.connection().clone();
+
+    if !blufio_vault::Vault::exists(&conn).await? {
+        db.close().await?;
+        return Err(blufio_core::BlufioError::Vault(
+            "no vault found -- use 'blufio config set-secret' to create one".to_string(),
+        ));
+    }
+
+    let passphrase = blufio_vault::get_vault_passphrase()?;
+    let vault = blufio_vault::Vault::unlock(conn, &passphrase, &config.vault).await?;
+
+    vault.rename_secret(from, to).await?;
+    eprintln!("Secret '{from}' renamed to '{to}'.");
+
+    db.close().await?;
+    Ok(())
+}
+
+/// Handle `blufio config migrate-secrets [--dry-run]`.
+///
+/// Moves plaintext secrets from `file` into the vault and removes them from
+/// the file. With `dry_run`, only reports what would be migrated or skipped;
+/// no vault is created and nothing is written.
+pub(crate) async fn cmd_migrate_secrets(
+    config: &blufio_config::model::BlufioConfig,
+    file: &std::path::Path,
+    dry_run: bool,
+) -> Result<(), blufio_core::BlufioError> {
+    // Detect secrets from the file itself, not env overrides or other layers.
+    let content = std::fs::read_to_string(file).map_err(|e| {
+        blufio_core::BlufioError::Config(format!("failed to read {}: {e}", file.display()))
+    })?;
+    let file_config = blufio_config::load_config_from_str(&content).map_err(|e| {
+        blufio_core::BlufioError::Config(format!("failed to parse {}: {e}", file.display()))
+    })?;
+
+    let db = open_db(config).await?;
+    let conn = db.connection().clone();
+
+    let vault = if blufio_vault::Vault::exists(&conn).await? {
+        let passphrase = blufio_vault::get_vault_passphrase()?;
+        blufio_vault::Vault::unlock(conn, &passphrase, &config.vault).await?
+    } else if dry_run {
+        let names = blufio_vault::plaintext_secret_names(&file_config);
+        println!("Dry run: no vault exists yet; a real run would create one.");
+        print_migration_plan(
+            &names.iter().map(|n| n.to_string()).collect::<Vec<_>>(),
+            &[],
+            true,
+        );
+        db.close().await?;
+        return Ok(());
+    } else {
+        eprintln!("No vault found. Creating a new vault.");
+        let passphrase = blufio_vault::prompt::get_vault_passphrase_with_confirm()?;
+        blufio_vault::Vault::create(conn, &passphrase, &config.vault).await?
+    };
+
+    let report =
+        blufio_vault::migrate_plaintext_secrets(&file_config, file, &vault, dry_run).await?;
+    print_migration_plan(&report.migrated, &report.skipped, report.dry_run);
+    for warning in &report.warnings {
+        eprintln!("warning: {warning}");
+    }
+
+    db.close().await?;
+    Ok(())
+}
+
+/// Print migrated (or planned) and skipped secret names.
+fn print_migration_plan(migrated: &[String], skipped: &[String], dry_run: bool) {
+    if migrated.is_empty() && skipped.is_empty() {
+        println!("No plaintext secrets found.");
+        return;
+    }
+    let verb = if dry_run { "would migrate" } else { "migrated" };
+    for name in migrated {
+        println!("{verb}: {name}");
+    }
+    for name in skipped {
+        println!("already in vault: {name}");
+    }
+}
+
+/// Handle `blufio config tune-vault`.
+///
+/// Benchmarks Argon2id on this machine and prints `[vault]` parameters that
+/// take about `target_ms` per derivation. With `write`, the parameters are
+/// merged into that TOML file (created if missing). New parameters apply the
+/// next time the master key is wrapped (vault creation or passphrase change);
+/// existing vaults keep unlocking with their stored parameters.
+pub(crate) fn cmd_tune_vault(
+    target_ms: u64,
+    write: Option<&std::path::Path>,
+) -> Result<(), blufio_core::BlufioError> {
+    eprintln!("Benchmarking Argon2id (target {target_ms} ms)...");
+    let (params, elapsed) = blufio_vault::Argon2Params::auto_tune(target_ms)?;
+    eprintln!("Derivation takes {} ms with:", elapsed.as_millis());
+
+    let snippet = format!(
+        "[vault]\nkdf_memory_cost = {}\nkdf_iterations = {}\nkdf_parallelism = {}\n",
+        params.memory_cost, params.iterations, params.parallelism
+    );
+    print!("{snippet}");
+
+    if let Some(path) = write {
+        write_vault_params(path, &params)?;
+        eprintln!(
+            "Wrote [vault] parameters to {}. They apply on the next `blufio config rotate-passphrase`.",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Merge tuned KDF parameters into the `[vault]` table of a TOML file.
+///
+/// Like the plaintext-secret migration, the file is rewritten from its parsed
+/// form, so comments are not preserved.
+pub(crate) fn write_vault_params(
+    path: &std::path::Path,
+    params: &blufio_vault::Argon2Params,
+) -> Result<(), blufio_core::BlufioError> {
+    let err = |msg: String| {
+        blufio_core::BlufioError::Config(format!("failed to update {}: {msg}", path.display()))
+    };
+
+    let mut doc: toml::Value = if path.exists() {
+        std::fs::read_to_string(path)
+            .map_err(|e| err(e.to_string()))?
+            .parse()
+            .map_err(|e: toml::de::Error| err(e.to_string()))?
+    } else {
+        toml::Value::Table(toml::map::Map::new())
+    };
+
+    let vault = doc
+        .as_table_mut()
+        .ok_or_else(|| err("config root is not a table".to_string()))?
+        .entry("vault")
+        .or_insert_with(|| toml::Value::Table(toml::map::Map::new()))
+        .as_table_mut()
+        .ok_or_else(|| err("[vault] is not a table".to_string()))?;
+    for (key, value) in [
+        ("kdf_memory_cost", params.memory_cost),
+        ("kdf_iterations", params.iterations),
+        ("kdf_parallelism", params.parallelism),
+    ] {
+        vault.insert(key.to_string(), toml::Value::Integer(i64::from(value)));
+    }
+
+    let content = toml::to_string_pretty(&doc).map_err(|e| err(e.to_string()))?;
+    std::fs::write(path, content).map_err(|e| err(e.to_string()))
+}
+
+/// Handle `blufio config rotate-passphrase`.
+///
+/// Verifies the current passphrase (`BLUFIO_VAULT_KEY` or prompt), then
+/// re-wraps the master key under the new one (`BLUFIO_VAULT_NEW_KEY` or
+/// prompt). Stored secrets are not re-encrypted.
+pub(crate) async fn cmd_rotate_passphrase(
+    config: &blufio_config::model::BlufioConfig,
+) -> Result<(), blufio_core::BlufioError> {
+    let db = open_db(config).await?;
+    let conn = db.connection().clone();
+
+    if !blufio_vault::Vault::exists(&conn).await? {
+        println!("No vault found. Use 'blufio config set-secret' to create one.");
+        db.close().await?;
+        return Ok(());
+    }
+
+    let old_passphrase = blufio_vault::get_vault_passphrase()?;
+    let vault = blufio_vault::Vault::unlock(conn, &old_passphrase, &config.vault).await?;
+    let new_passphrase = blufio_vault::prompt::get_new_vault_passphrase()?;
+
+    vault
+        .rotate_passphrase(&old_passphrase, &new_passphrase, &config.vault)
+        .await?;
+    eprintln!("Vault passphrase rotated. Update BLUFIO_VAULT_KEY wherever it is set.");
+
+    db.close().await?;
+    Ok(())
+}
+
+/// Handle `blufio config export-recovery`.
+///
+/// Splits the vault master key into `shares` Shamir shares, any `threshold`
+/// of which can later reset a lost passphrase via `blufio config
+/// recover-vault`. Shares are printed to stdout, one per line.
+pub(crate) async fn cmd_export_recovery(
+    config: &blufio_config::model::BlufioConfig,
+    threshold: u8,
+    shares: u8,
+) -> Result<(), blufio_core::BlufioError> {
+    let db = open_db(config).await?;
+    let conn = db.connection().clone();
+
+    if !blufio_vault::Vault::exists(&conn).await? {
+        db.close().await?;
+        return Err(blufio_core::BlufioError::Vault(
+            "no vault found -- use 'blufio config set-secret' to create one".to_string(),
+        ));
+    }
+
+    let passphrase = blufio_vault::get_vault_passphrase()?;
+    let vault = blufio_vault::Vault::unlock(conn, &passphrase, &config.vault).await?;
+    let split = vault.export_recovery_shares(threshold, shares).await?;
+
+    eprintln!(
+        "Vault recovery shares: any {threshold} of these {shares} unlock the vault without the passphrase."
+    );
+    eprintln!(
+        "Store each share in a separate safe place. Anyone holding {threshold} can read every secret."
+    );
+    for share in &split {
+        println!("{}", share.encode());
+    }
+
+    db.close().await?;
+    Ok(())
+}
+
+/// Handle `blufio config recover-vault`.
+///
+/// Reads recovery shares (one per line, from the TTY or piped stdin) until
+/// the threshold is reached, unlocks the vault with them, and sets a new
+/// passphrase (`BLUFIO_VAULT_NEW_KEY` or prompt).
+pub(crate) async fn cmd_recover_vault(
+    config: &blufio_config::model::BlufioConfig,
+) -> Result<(), blufio_core::BlufioError> {
+    let db = open_db(config).await?;
+    let conn = db.connection().clone();
+
+    if !blufio_vault::Vault::exists(&conn).await? {
+        db.close().await?;
+        return Err(blufio_core::BlufioError::Vault(
+            "no vault found".to_string(),
+        ));
+    }
+
+    let shares = read_recovery_shares()?;
+    let vault = blufio_vault::Vault::unlock_with_shares(conn, &shares).await?;
+    let new_passphrase = blufio_vault::prompt::get_new_vault_passphrase()?;
+    vault
+        .change_passphrase(&new_passphrase, &config.vault)
+        .await?;
+    eprintln!("Vault recovered and passphrase reset. Update BLUFIO_VAULT_KEY wherever it is set.");
+
+    db.close().await?;
+    Ok(())
+}
+
+/// Read recovery shares line by line until the threshold named by the first
+/// share is reached or input ends.
+///
+/// Shares are read with echo off on a TTY, and each line is zeroized once
+/// decoded.
+fn read_recovery_shares() -> Result<Vec<blufio_vault::RecoveryShare>, blufio_core::BlufioError> {
+    let interactive = std::io::IsTerminal::is_terminal(&std::io::stdin());
+    let mut shares: Vec<blufio_vault::RecoveryShare> = Vec::new();
+    let mut stdin = std::io::stdin().lock();
+    let read_err = |e: std::io::Error| {
+        blufio_core::BlufioError::Vault(format!("failed to read recovery share: {e}"))
+    };
+
+    loop {
+        if let Some(first) = shares.first()
+            && shares.len() >= usize::from(first.threshold)
+        {
+            break;
+        }
+        let line = if interactive {
+            eprint!("Recovery share {}: ", shares.len() + 1);
+            let line = zeroize::Zeroizing::new(rpassword::read_password().map_err(read_err)?);
+            // An empty entry ends input, as EOF does for piped shares.
+            if line.trim().is_empty() {
+                break;
+            }
+            line
+        } else {
+            let mut line = zeroize::Zeroizing::new(String::new());
+            if std::io::BufRead::read_line(&mut stdin, &mut line).map_err(read_err)? == 0 {
+                break;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            line
+        };
+        shares.push(blufio_vault::RecoveryShare::decode(&line)?);
+    }
+
+    Ok(shares)
+}
+
 /// Read a secret value from interactive TTY (hidden input) or piped stdin.
 pub(crate) fn read_secret_value(key: &str) -> Result<String, blufio_core::BlufioError> {
     if std::io::IsTerminal::is_terminal(&std::io::stdin()) {
@@ -230,6 +565,11 @@ log_level = "warn"
 [telegram]
 # bot_token = "<your-telegram-bot-token>"
 # allowed_users = []
+# idempotency_window_secs = 300
+# allow_groups = false
+# respond_on_mention_only = true
+# edit_throttle_ms = 1500  # min gap between streaming edits
+# max_image_bytes = 5242880  # largest photo passed to the model
 
 [discord]
 # bot_token = "<your-discord-bot-token>"
@@ -254,10 +594,42 @@ log_level = "warn"
 # password = "<your-matrix-password>"
 # rooms = ["#your-room:matrix.org"]
 
+[transcription]
+# command = "whisper-cli"
+# args = ["-m", "/opt/whisper/ggml-base.bin", "-l", "{language}", "-nt", "-np", "-f", "{input}"]
+# language = "en"
+
+[tts]
+# command = "piper"
+# args = ["--model", "/opt/piper/{voice}.onnx", "--output_file", "{output}"]
+# voice = "en_US-amy-medium"
+# output_format = "wav"
+
+[moderation]
+# blocked_terms = []
+# refusal_message = "Sorry, I can't help with that."
+
+[pii_redaction]
+# user_messages = true
+# assistant_messages = true
+# tool_results = true
+# memory_extraction = true
+# keep_encrypted_original = false
+# provider_requests = false
+
+[outbound]
+# enabled = false
+# base_backoff_ms = 1000
+# max_backoff_ms = 60000
+# poll_interval_ms = 1000
+
 [anthropic]
 # api_key = "<your-anthropic-api-key>"
 default_model = "claude-sonnet-4-20250514"
 max_tokens = 4096
+# max_retries = 3
+# retry_base_delay_ms = 1000
+# retry_max_delay_ms = 30000
 
 [providers]
 default = "anthropic"
@@ -269,6 +641,15 @@ default = "anthropic"
 [storage]
 database_path = "/var/lib/blufio/blufio.db"
 wal_mode = true
+# wal_autocheckpoint_pages = 1000
+# wal_checkpoint_interval_secs = 300
+# vacuum_interval_secs = 604800  # weekly checkpoint + VACUUM
+# read_pool_size = 2
+# backend = "redis"  # share sessions between replicas
+
+# [storage.redis]
+# url = "redis://127.0.0.1:6379"
+# pool_size = 4
 
 [cost]
 # daily_limit_usd = 100.0
@@ -286,6 +667,21 @@ host = "0.0.0.0"
 port = 3000
 # bearer_token = "<generate-a-strong-token>"
 # default_rate_limit = 60
+# metrics_require_auth = true
+# metrics_allowlist = ["10.0.0.0/8"]  # Prometheus scrapers
+# identity_requests_per_minute = 120  # per API key / master token
+# identity_burst = 20
+# max_body_bytes = 2097152  # 2 MiB; larger requests get 413
+# inbound_send_timeout_ms = 5000  # queue-full wait before 503
+# shutdown_drain_secs = 10  # in-flight grace period on shutdown
+# ws_ping_interval_secs = 30  # 0 disables WebSocket pings
+# ws_pong_timeout_secs = 10
+# ws_idle_timeout_secs = 900  # 0 disables the idle timeout
+
+# [gateway.cors]
+# allowed_origins = ["https://app.example.com"]  # empty = deny all
+# allowed_methods = ["GET", "POST", "DELETE"]
+# allowed_headers = ["authorization", "content-type", "accept", "x-signature", "x-timestamp"]
 
 [prometheus]
 enabled = true
@@ -326,6 +722,10 @@ default_memory_mb = 16
 default_epoch_timeout_secs = 5
 max_skills_in_prompt = 3
 
+[skill.bash]
+# allowed_prefixes = ["ls", "cat", "git status"]
+denied_substrings = ["rm -rf", "curl | sh", "wget | sh"]
+
 [gateway]
 enabled = false
 "#
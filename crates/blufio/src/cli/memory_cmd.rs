@@ -3,14 +3,119 @@
 
 //! Memory management CLI handlers for `blufio memory` subcommands.
 
+use std::sync::Arc;
+
+use blufio_core::BlufioError;
+use blufio_memory::{HybridRetriever, Memory, MemoryStatus, MemoryStore};
+use clap::ValueEnum;
+
 use crate::MemoryCommand;
 
+/// Longest content preview shown per memory in text output.
+const PREVIEW_CHARS: usize = 80;
+
+/// Memory status accepted by `blufio memory list --status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum StatusFilter {
+    Active,
+    Superseded,
+    Forgotten,
+    Archived,
+}
+
+impl From<StatusFilter> for MemoryStatus {
+    fn from(filter: StatusFilter) -> Self {
+        match filter {
+            StatusFilter::Active => MemoryStatus::Active,
+            StatusFilter::Superseded => MemoryStatus::Superseded,
+            StatusFilter::Forgotten => MemoryStatus::Forgotten,
+            StatusFilter::Archived => MemoryStatus::Archived,
+        }
+    }
+}
+
 /// Handle `blufio memory <command>` subcommands.
 pub(crate) async fn handle_memory_command(
     config: &blufio_config::model::BlufioConfig,
     command: MemoryCommand,
 ) -> Result<(), blufio_core::BlufioError> {
     match command {
+        MemoryCommand::List {
+            status,
+            limit,
+            json,
+        } => {
+            let store = open_store(config).await?;
+            let memories = store.list(status.map(MemoryStatus::from), limit).await?;
+
+            if json {
+                let now = chrono::Utc::now();
+                let entries: Vec<serde_json::Value> = memories
+                    .iter()
+                    .map(|m| {
+                        serde_json::json!({
+                            "id": m.id,
+                            "status": m.status.as_str(),
+                            "source": m.source.as_str(),
+                            "age": format_age(&m.created_at, now),
+                            "created_at": m.created_at,
+                            "content": m.content,
+                        })
+                    })
+                    .collect();
+                println!("{}", serde_json::Value::Array(entries));
+            } else if memories.is_empty() {
+                println!("No memories found.");
+            } else {
+                let now = chrono::Utc::now();
+                for memory in &memories {
+                    println!("{}", format_memory_line(memory, now));
+                }
+            }
+        }
+        MemoryCommand::Search { query, json } => {
+            let store = Arc::new(open_store(config).await?);
+            let (embedder, _) = crate::serve::storage::init_embedder(config).await?;
+            let embedder = blufio_memory::embedder::verify_dimensions(embedder, &store).await?;
+            // Searching from the CLI is inspection, not use: leave decay alone.
+            let retriever =
+                HybridRetriever::new(store, embedder, config.memory.clone()).read_only();
+            let results = retriever.retrieve(&query).await?;
+
+            if json {
+                let entries: Vec<serde_json::Value> = results
+                    .iter()
+                    .map(|r| {
+                        serde_json::json!({
+                            "id": r.memory.id,
+                            "score": r.score,
+                            "source": r.memory.source.as_str(),
+                            "content": r.memory.content,
+                        })
+                    })
+                    .collect();
+                println!("{}", serde_json::Value::Array(entries));
+            } else if results.is_empty() {
+                println!("No matching memories.");
+            } else {
+                for r in &results {
+                    println!(
+                        "{:.4}  {}  {}",
+                        r.score,
+                        r.memory.id,
+                        preview(&r.memory.content)
+                    );
+                }
+            }
+        }
+        MemoryCommand::Forget { id } => {
+            let store = open_store(config).await?;
+            if forget_memory(&store, &id).await? {
+                println!("Forgot memory {id}");
+            } else {
+                println!("Memory {id} was already forgotten");
+            }
+        }
         MemoryCommand::Validate { dry_run, json } => {
             let conn = blufio_storage::open_connection(&config.storage.database_path).await?;
             let store = blufio_memory::MemoryStore::new(conn);
@@ -76,3 +181,173 @@ pub(crate) async fn handle_memory_command(
     }
     Ok(())
 }
+
+/// Open the memory store on the configured database, mirroring writes to
+/// vec0 when it is enabled so KNN search stays consistent.
+async fn open_store(
+    config: &blufio_config::model::BlufioConfig,
+) -> Result<MemoryStore, BlufioError> {
+    if config.memory.vec0_enabled {
+        blufio_memory::vec0::ensure_sqlite_vec_registered();
+    }
+    let conn = blufio_storage::open_connection(&config.storage.database_path).await?;
+    Ok(MemoryStore::with_vec0(
+        conn,
+        None,
+        config.memory.vec0_enabled,
+    ))
+}
+
+/// Mark a memory as forgotten.
+///
+/// Returns `false` if it already was, and an error if no memory has `id`.
+async fn forget_memory(store: &MemoryStore, id: &str) -> Result<bool, BlufioError> {
+    let memory = store
+        .get_by_id(id)
+        .await?
+        .ok_or_else(|| BlufioError::Internal(format!("memory '{id}' not found")))?;
+    if memory.status == MemoryStatus::Forgotten {
+        return Ok(false);
+    }
+    store.soft_delete(id).await?;
+    Ok(true)
+}
+
+/// One line of `blufio memory list` text output: id, status, age, content.
+fn format_memory_line(memory: &Memory, now: chrono::DateTime<chrono::Utc>) -> String {
+    format!(
+        "{}  {:<10}  {:>4}  {}",
+        memory.id,
+        memory.status.as_str(),
+        format_age(&memory.created_at, now),
+        preview(&memory.content)
+    )
+}
+
+/// Compact age of an ISO 8601 timestamp, e.g. `5m`, `3h`, `12d`.
+fn format_age(created_at: &str, now: chrono::DateTime<chrono::Utc>) -> String {
+    let Ok(created) = chrono::DateTime::parse_from_rfc3339(created_at) else {
+        return "?".to_string();
+    };
+    let age = now - created.with_timezone(&chrono::Utc);
+    if age.num_days() > 0 {
+        format!("{}d", age.num_days())
+    } else if age.num_hours() > 0 {
+        format!("{}h", age.num_hours())
+    } else {
+        format!("{}m", age.num_minutes().max(0))
+    }
+}
+
+/// Single-line content preview, truncated to [`PREVIEW_CHARS`].
+fn preview(content: &str) -> String {
+    let line = content.replace('\n', " ");
+    if line.chars().count() > PREVIEW_CHARS {
+        let truncated: String = line.chars().take(PREVIEW_CHARS - 3).collect();
+        format!("{truncated}...")
+    } else {
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blufio_core::classification::DataClassification;
+    use blufio_memory::MemorySource;
+
+    async fn temp_store() -> (MemoryStore, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("memory.db");
+        let db = blufio_storage::Database::open(path.to_str().unwrap())
+            .await
+            .unwrap();
+        (MemoryStore::new(db.connection().clone()), dir)
+    }
+
+    fn memory(id: &str, content: &str, created_at: &str) -> Memory {
+        Memory {
+            id: id.to_string(),
+            content: content.to_string(),
+            embedding: vec![0.1; 384],
+            source: MemorySource::Explicit,
+            confidence: 0.9,
+            status: MemoryStatus::Active,
+            superseded_by: None,
+            session_id: Some("cli-test".to_string()),
+            classification: DataClassification::default(),
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn list_shows_status_and_age() {
+        let (store, _dir) = temp_store().await;
+        store
+            .save(&memory(
+                "mem-1",
+                "Dog is named Max",
+                "2026-03-01T00:00:00.000Z",
+            ))
+            .await
+            .unwrap();
+        store
+            .save(&memory(
+                "mem-2",
+                "Lives in Lisbon",
+                "2026-03-10T00:00:00.000Z",
+            ))
+            .await
+            .unwrap();
+
+        let now = chrono::DateTime::parse_from_rfc3339("2026-03-11T06:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let lines: Vec<String> = store
+            .list(None, 10)
+            .await
+            .unwrap()
+            .iter()
+            .map(|m| format_memory_line(m, now))
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "mem-2  active        1d  Lives in Lisbon");
+        assert_eq!(lines[1], "mem-1  active       10d  Dog is named Max");
+    }
+
+    #[tokio::test]
+    async fn forget_marks_memory_forgotten() {
+        let (store, _dir) = temp_store().await;
+        store
+            .save(&memory("mem-1", "Wrong fact", "2026-03-01T00:00:00.000Z"))
+            .await
+            .unwrap();
+
+        assert!(forget_memory(&store, "mem-1").await.unwrap());
+        assert!(!forget_memory(&store, "mem-1").await.unwrap());
+
+        let forgotten = store.list(Some(MemoryStatus::Forgotten), 10).await.unwrap();
+        assert_eq!(forgotten.len(), 1);
+        assert_eq!(forgotten[0].id, "mem-1");
+        assert!(
+            store
+                .list(Some(MemoryStatus::Active), 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let err = forget_memory(&store, "missing").await.unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
+
+    #[test]
+    fn preview_truncates_long_content() {
+        let long = "x".repeat(200);
+        let p = preview(&long);
+        assert_eq!(p.chars().count(), PREVIEW_CHARS);
+        assert!(p.ends_with("..."));
+        assert_eq!(preview("line one\nline two"), "line one line two");
+    }
+}
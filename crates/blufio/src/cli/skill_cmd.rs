@@ -56,48 +56,19 @@ pub(crate) async fn handle_skill_command(
             // Compute content hash.
             let content_hash = blufio_skill::compute_content_hash(&wasm_bytes);
 
-            // Check for adjacent .sig file.
-            let sig_path = format!("{}.sig", wasm_path);
-            let (signature, publisher_id) = if std::path::Path::new(&sig_path).exists() {
-                let sig_content = std::fs::read_to_string(&sig_path)
-                    .map_err(blufio_core::BlufioError::skill_execution_failed)?;
-                let (pub_id, sig_hash, sig_hex) = parse_sig_file(&sig_content)?;
-
-                // Verify the hash in .sig matches our computed hash.
-                if sig_hash != content_hash {
-                    return Err(blufio_core::BlufioError::Security(format!(
-                        "signature file hash mismatch: .sig says {} but WASM hashes to {}",
-                        &sig_hash[..12],
-                        &content_hash[..12]
-                    )));
-                }
-
-                // Verify the signature against the WASM bytes.
-                let sig = blufio_skill::signature_from_hex(&sig_hex)?;
-                let pubkey_bytes = hex::decode(&pub_id).map_err(|e| {
-                    blufio_core::BlufioError::Security(format!("invalid publisher_id hex: {e}"))
-                })?;
-                let pubkey_array: [u8; 32] = pubkey_bytes.try_into().map_err(|_| {
-                    blufio_core::BlufioError::Security(
-                        "publisher_id must be exactly 32 bytes".to_string(),
-                    )
-                })?;
-                let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(&pubkey_array)
-                    .map_err(|e| {
-                        blufio_core::BlufioError::Security(format!("invalid publisher key: {e}"))
-                    })?;
-                blufio_skill::PublisherKeypair::verify_signature(
-                    &verifying_key,
-                    &wasm_bytes,
-                    &sig,
-                )?;
-
-                eprintln!("  Signature verified (publisher: {}...)", &pub_id[..12]);
-
-                (Some(sig_hex), Some(pub_id))
-            } else {
-                (None, None)
-            };
+            let (signature, publisher_id, status) = resolve_skill_signature(
+                &manifest,
+                &wasm_path,
+                &wasm_bytes,
+                &content_hash,
+                &config.skill.trusted_publishers,
+            )?;
+            if let Some(ref pub_id) = publisher_id {
+                eprintln!(
+                    "  Signature valid (publisher: {}...) [{status}]",
+                    &pub_id[..12.min(pub_id.len())]
+                );
+            }
 
             // Serialize capabilities to JSON for storage.
             let capabilities_json =
@@ -124,14 +95,10 @@ pub(crate) async fn handle_skill_command(
                     Some(&content_hash),
                     signature.as_deref(),
                     publisher_id.as_deref(),
+                    status,
                 )
                 .await?;
 
-            let status = if signature.is_some() {
-                "verified"
-            } else {
-                "unverified"
-            };
             eprintln!(
                 "Skill '{}' v{} installed successfully. [{}]",
                 manifest.name, manifest.version, status
@@ -174,41 +141,13 @@ pub(crate) async fn handle_skill_command(
 
             let content_hash = blufio_skill::compute_content_hash(&wasm_bytes);
 
-            // Check for adjacent .sig file.
-            let sig_path = format!("{}.sig", wasm_path);
-            let (signature, publisher_id) = if std::path::Path::new(&sig_path).exists() {
-                let sig_content = std::fs::read_to_string(&sig_path)
-                    .map_err(blufio_core::BlufioError::skill_execution_failed)?;
-                let (pub_id, sig_hash, sig_hex) = parse_sig_file(&sig_content)?;
-                if sig_hash != content_hash {
-                    return Err(blufio_core::BlufioError::Security(format!(
-                        "signature file hash mismatch: .sig says {} but WASM hashes to {}",
-                        &sig_hash[..12],
-                        &content_hash[..12]
-                    )));
-                }
-                let sig = blufio_skill::signature_from_hex(&sig_hex)?;
-                let pubkey_bytes = hex::decode(&pub_id).map_err(|e| {
-                    blufio_core::BlufioError::Security(format!("invalid publisher_id hex: {e}"))
-                })?;
-                let pubkey_array: [u8; 32] = pubkey_bytes.try_into().map_err(|_| {
-                    blufio_core::BlufioError::Security(
-                        "publisher_id must be exactly 32 bytes".to_string(),
-                    )
-                })?;
-                let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(&pubkey_array)
-                    .map_err(|e| {
-                        blufio_core::BlufioError::Security(format!("invalid publisher key: {e}"))
-                    })?;
-                blufio_skill::PublisherKeypair::verify_signature(
-                    &verifying_key,
-                    &wasm_bytes,
-                    &sig,
-                )?;
-                (Some(sig_hex), Some(pub_id))
-            } else {
-                (None, None)
-            };
+            let (signature, publisher_id, status) = resolve_skill_signature(
+                &manifest,
+                &wasm_path,
+                &wasm_bytes,
+                &content_hash,
+                &config.skill.trusted_publishers,
+            )?;
 
             let capabilities_json =
                 serde_json::to_string(&manifest.capabilities).unwrap_or_else(|_| "{}".to_string());
@@ -232,10 +171,14 @@ pub(crate) async fn handle_skill_command(
                     Some(&content_hash),
                     signature.as_deref(),
                     publisher_id.as_deref(),
+                    status,
                 )
                 .await?;
 
-            eprintln!("Skill '{}' updated to v{}.", name, manifest.version);
+            eprintln!(
+                "Skill '{}' updated to v{}. [{}]",
+                name, manifest.version, status
+            );
             Ok(())
         }
         SkillCommands::Sign {
@@ -327,18 +270,7 @@ pub(crate) async fn handle_skill_command(
                         name
                     ))
                 })?;
-                let pubkey_bytes = hex::decode(pub_id).map_err(|e| {
-                    blufio_core::BlufioError::Security(format!("invalid publisher_id hex: {e}"))
-                })?;
-                let pubkey_array: [u8; 32] = pubkey_bytes.try_into().map_err(|_| {
-                    blufio_core::BlufioError::Security(
-                        "publisher_id must be exactly 32 bytes".to_string(),
-                    )
-                })?;
-                let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(&pubkey_array)
-                    .map_err(|e| {
-                        blufio_core::BlufioError::Security(format!("invalid publisher key: {e}"))
-                    })?;
+                let verifying_key = blufio_skill::verifying_key_from_hex(pub_id)?;
                 blufio_skill::PublisherKeypair::verify_signature(
                     &verifying_key,
                     &wasm_bytes,
@@ -353,6 +285,32 @@ pub(crate) async fn handle_skill_command(
             eprintln!("Skill '{}' verification complete.", name);
             Ok(())
         }
+        SkillCommands::Validate { manifest_path } => {
+            let content = std::fs::read_to_string(&manifest_path)
+                .map_err(blufio_core::BlufioError::skill_execution_failed)?;
+            let report = blufio_skill::validate_manifest(&content);
+
+            for warning in &report.warnings {
+                eprintln!("  warning: {warning}");
+            }
+            for error in &report.errors {
+                eprintln!("  error:   {error}");
+            }
+            match report.manifest {
+                Some(ref manifest) if report.is_valid() => {
+                    eprintln!(
+                        "Manifest '{}' is valid (skill '{}' v{}).",
+                        manifest_path, manifest.name, manifest.version
+                    );
+                    Ok(())
+                }
+                _ => Err(blufio_core::BlufioError::skill_execution_msg(&format!(
+                    "manifest '{}' has {} error(s)",
+                    manifest_path,
+                    report.errors.len()
+                ))),
+            }
+        }
         SkillCommands::Info { name } => {
             let conn = blufio_storage::open_connection(&config.storage.database_path).await?;
             let store = blufio_skill::SkillStore::new(std::sync::Arc::new(conn));
@@ -393,6 +351,57 @@ pub(crate) async fn handle_skill_command(
     }
 }
 
+/// Find and verify a skill's signature for install or update.
+///
+/// The manifest's `[signature]` section takes precedence over an adjacent
+/// `<wasm>.sig` file. Returns the signature, publisher ID, and the trust
+/// status against `skill.trusted_publishers`; an invalid signature is an error.
+fn resolve_skill_signature(
+    manifest: &blufio_core::types::SkillManifest,
+    wasm_path: &str,
+    wasm_bytes: &[u8],
+    content_hash: &str,
+    trusted_publishers: &[String],
+) -> Result<
+    (
+        Option<String>,
+        Option<String>,
+        blufio_skill::VerificationStatus,
+    ),
+    blufio_core::BlufioError,
+> {
+    let (signature, publisher_id) = if let Some(ref sig) = manifest.signature {
+        (Some(sig.signature.clone()), Some(sig.publisher_id.clone()))
+    } else {
+        let sig_path = format!("{}.sig", wasm_path);
+        if std::path::Path::new(&sig_path).exists() {
+            let sig_content = std::fs::read_to_string(&sig_path)
+                .map_err(blufio_core::BlufioError::skill_execution_failed)?;
+            let (pub_id, sig_hash, sig_hex) = parse_sig_file(&sig_content)?;
+
+            // Verify the hash in .sig matches our computed hash.
+            if sig_hash != content_hash {
+                return Err(blufio_core::BlufioError::Security(format!(
+                    "signature file hash mismatch: .sig says {} but WASM hashes to {}",
+                    &sig_hash[..12.min(sig_hash.len())],
+                    &content_hash[..12]
+                )));
+            }
+            (Some(sig_hex), Some(pub_id))
+        } else {
+            (None, None)
+        }
+    };
+
+    let status = blufio_skill::verify_skill_signature(
+        wasm_bytes,
+        signature.as_deref(),
+        publisher_id.as_deref(),
+        trusted_publishers,
+    )?;
+    Ok((signature, publisher_id, status))
+}
+
 /// Parse a .sig file content into (publisher_id, content_hash, signature).
 pub(crate) fn parse_sig_file(
     content: &str,
@@ -0,0 +1,114 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! `blufio cost` CLI subcommands for spend reporting.
+
+use blufio_config::model::BlufioConfig;
+use blufio_core::error::BlufioError;
+use blufio_cost::{CostLedger, CostReport};
+use chrono::{Datelike, NaiveDate, Utc};
+use clap::Subcommand;
+
+/// Cost reporting subcommands.
+#[derive(Subcommand, Debug)]
+pub enum CostCommand {
+    /// Summarize spend by model and feature over a date range.
+    Report {
+        /// First day to include (YYYY-MM-DD). Defaults to the first of the
+        /// `--to` month.
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// Last day to include (YYYY-MM-DD). Defaults to today (UTC).
+        #[arg(long)]
+        to: Option<NaiveDate>,
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Run the `blufio cost` subcommand.
+pub async fn run_cost(config: &BlufioConfig, command: CostCommand) -> Result<(), BlufioError> {
+    match command {
+        CostCommand::Report { from, to, json } => {
+            let to = to.unwrap_or_else(|| Utc::now().date_naive());
+            let from = from.unwrap_or_else(|| to.with_day(1).unwrap_or(to));
+            if from > to {
+                return Err(BlufioError::Config(format!(
+                    "--from ({from}) is after --to ({to})"
+                )));
+            }
+
+            let (from, to) = (from.to_string(), to.to_string());
+            // Nothing has run yet, so nothing has been spent. Read-only
+            // connections never create the database.
+            let report = if std::path::Path::new(&config.storage.database_path).exists() {
+                let ledger = CostLedger::open_read_only(&config.storage.database_path).await?;
+                ledger.report(&from, &to).await?
+            } else {
+                CostReport {
+                    from,
+                    to,
+                    lines: Vec::new(),
+                    total_cost_usd: 0.0,
+                }
+            };
+
+            if json {
+                let out = serde_json::to_string_pretty(&report)
+                    .map_err(|e| BlufioError::Internal(format!("failed to serialize: {e}")))?;
+                println!("{out}");
+            } else {
+                print_report(&report);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Print a cost report as a plain-text table.
+fn print_report(report: &CostReport) {
+    println!("Cost report {} to {}", report.from, report.to);
+    if report.lines.is_empty() {
+        println!("  No recorded spend.");
+        return;
+    }
+    println!(
+        "  {:<32} {:<11} {:>6} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "MODEL", "FEATURE", "CALLS", "INPUT", "OUTPUT", "CACHE RD", "CACHE WR", "USD"
+    );
+    for line in &report.lines {
+        println!(
+            "  {:<32} {:<11} {:>6} {:>10} {:>10} {:>10} {:>10} {:>10.4}",
+            line.model,
+            line.feature_type.to_string(),
+            line.calls,
+            line.input_tokens,
+            line.output_tokens,
+            line.cache_read_tokens,
+            line.cache_creation_tokens,
+            line.cost_usd
+        );
+    }
+    println!("  Total: ${:.4}", report.total_cost_usd);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn report_without_a_database_shows_no_spend() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = BlufioConfig::default();
+        config.storage.database_path = dir.path().join("missing.db").to_string_lossy().to_string();
+
+        let command = CostCommand::Report {
+            from: None,
+            to: None,
+            json: true,
+        };
+        run_cost(&config, command).await.unwrap();
+        assert!(!dir.path().join("missing.db").exists());
+    }
+}
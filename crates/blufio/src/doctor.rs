@@ -36,11 +36,23 @@ pub struct CheckResult {
     pub duration: Duration,
 }
 
+/// WAL files larger than this multiple of the main database are flagged.
+const WAL_SIZE_RATIO_WARN: f64 = 1.0;
+
+/// WAL files smaller than this are never flagged, regardless of ratio.
+const WAL_MIN_WARN_BYTES: u64 = 4 * 1024 * 1024;
+
 /// Run the `blufio doctor` command.
 ///
 /// Runs quick diagnostic checks. With `--deep`, runs additional intensive checks.
+/// With `--checkpoint` (deep only), truncates an oversized WAL file.
 /// With `--plain`, disables colored output.
-pub async fn run_doctor(config: &BlufioConfig, deep: bool, plain: bool) -> Result<(), BlufioError> {
+pub async fn run_doctor(
+    config: &BlufioConfig,
+    deep: bool,
+    checkpoint: bool,
+    plain: bool,
+) -> Result<(), BlufioError> {
     let use_color = !plain && std::io::stdout().is_terminal();
     let mut results = Vec::new();
 
@@ -86,7 +98,8 @@ pub async fn run_doctor(config: &BlufioConfig, deep: bool, plain: bool) -> Resul
     // Deep checks (only with --deep)
     if deep {
         results.push(check_db_integrity(&config.storage.database_path).await);
-        results.push(check_disk_space(&config.storage.database_path).await);
+        results.push(check_disk_space(config).await);
+        results.push(check_wal(&config.storage.database_path, checkpoint).await);
         results.push(check_memory_baseline().await);
     }
 
@@ -800,49 +813,202 @@ async fn check_db_integrity(db_path: &str) -> CheckResult {
     }
 }
 
-/// Deep check: available disk space.
-async fn check_disk_space(db_path: &str) -> CheckResult {
+/// Deep check: available disk space and database growth.
+///
+/// Reports free space on the filesystems holding the database (which also
+/// holds the vault) and the embedding models, and the current database size
+/// including its WAL. Warns when free space drops below
+/// `daemon.disk_min_free_mb` or the database exceeds `daemon.db_size_warn_mb`.
+async fn check_disk_space(config: &BlufioConfig) -> CheckResult {
     let start = Instant::now();
-    let path = std::path::Path::new(db_path);
-    let check_path = if path.exists() {
-        path.to_path_buf()
+    let db_path = std::path::Path::new(&config.storage.database_path);
+    let data_dir = db_path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(std::path::Path::new("."));
+    let min_free_bytes = config.daemon.disk_min_free_mb.saturating_mul(1024 * 1024);
+    let db_warn_bytes = config.daemon.db_size_warn_mb.saturating_mul(1024 * 1024);
+
+    let mut details = Vec::new();
+    let mut warnings = Vec::new();
+
+    let targets = [
+        ("data", data_dir.to_path_buf()),
+        ("models", data_dir.join("models")),
+    ];
+    for (label, target) in &targets {
+        let Some(probe) = target.ancestors().find(|p| p.exists()) else {
+            warnings.push(format!("{label} path not accessible"));
+            continue;
+        };
+        match available_disk_bytes(probe) {
+            Some(free) if free < min_free_bytes => warnings.push(format!(
+                "{label} disk low: {} free (< {} MB)",
+                format_mb(free),
+                config.daemon.disk_min_free_mb
+            )),
+            Some(free) => details.push(format!("{label}: {} free", format_mb(free))),
+            None => details.push(format!("{label}: free space unknown")),
+        }
+    }
+
+    if db_path.exists() {
+        let wal_path = format!("{}-wal", config.storage.database_path);
+        let db_size = std::fs::metadata(db_path).map(|m| m.len()).unwrap_or(0)
+            + std::fs::metadata(&wal_path).map(|m| m.len()).unwrap_or(0);
+        if db_size > db_warn_bytes {
+            warnings.push(format!(
+                "DB size {} exceeds {} MB",
+                format_mb(db_size),
+                config.daemon.db_size_warn_mb
+            ));
+        } else {
+            details.push(format!("DB size: {}", format_mb(db_size)));
+        }
     } else {
-        path.parent()
-            .unwrap_or(std::path::Path::new("."))
-            .to_path_buf()
+        details.push("DB not created yet".to_string());
+    }
+
+    if warnings.is_empty() {
+        CheckResult {
+            name: "Disk space".to_string(),
+            status: CheckStatus::Pass,
+            message: details.join(", "),
+            duration: start.elapsed(),
+        }
+    } else {
+        CheckResult {
+            name: "Disk space".to_string(),
+            status: CheckStatus::Warn,
+            message: warnings.join("; "),
+            duration: start.elapsed(),
+        }
+    }
+}
+
+/// Formats a byte count as megabytes with one decimal place.
+fn format_mb(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+/// Returns the bytes available to unprivileged users on the filesystem containing `path`.
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)]
+fn available_disk_bytes(path: &std::path::Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `c_path` is NUL-terminated and `stat` is a valid out-pointer.
+    let ret = unsafe { libc::statvfs(c_path.as_ptr(), stat.as_mut_ptr()) };
+    if ret != 0 {
+        return None;
+    }
+    // SAFETY: statvfs returned 0, so `stat` is initialized.
+    let stat = unsafe { stat.assume_init() };
+    Some((stat.f_bavail as u64).saturating_mul(stat.f_frsize as u64))
+}
+
+/// Free-space probing is only implemented on unix platforms.
+#[cfg(not(unix))]
+fn available_disk_bytes(_path: &std::path::Path) -> Option<u64> {
+    None
+}
+
+/// Deep check: WAL file size relative to the main database.
+///
+/// A `-wal` file that keeps growing means checkpoints are not completing,
+/// which inflates read cost and disk usage. With `checkpoint` set, an
+/// oversized WAL is merged and truncated via `wal_checkpoint(TRUNCATE)`.
+async fn check_wal(db_path: &str, checkpoint: bool) -> CheckResult {
+    let start = Instant::now();
+    let path = std::path::Path::new(db_path);
+
+    if !path.exists() {
+        return CheckResult {
+            name: "WAL".to_string(),
+            status: CheckStatus::Warn,
+            message: "database not found (skipped)".to_string(),
+            duration: start.elapsed(),
+        };
+    }
+
+    let wal_path = format!("{db_path}-wal");
+    let db_size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let wal_size = std::fs::metadata(&wal_path).map(|m| m.len()).unwrap_or(0);
+
+    if !wal_is_oversized(db_size, wal_size) {
+        return CheckResult {
+            name: "WAL".to_string(),
+            status: CheckStatus::Pass,
+            message: format!("{} (DB {})", format_mb(wal_size), format_mb(db_size)),
+            duration: start.elapsed(),
+        };
+    }
+
+    if !checkpoint {
+        return CheckResult {
+            name: "WAL".to_string(),
+            status: CheckStatus::Warn,
+            message: format!(
+                "{} exceeds DB size {}; run `blufio doctor --deep --checkpoint`",
+                format_mb(wal_size),
+                format_mb(db_size)
+            ),
+            duration: start.elapsed(),
+        };
+    }
+
+    let result = match blufio_storage::open_connection(db_path).await {
+        Ok(conn) => conn
+            .call(|conn| {
+                conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |row| {
+                    row.get::<_, i64>(0)
+                })
+            })
+            .await
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
     };
 
-    match std::fs::metadata(&check_path) {
-        Ok(_) => {
-            // On most platforms we can't easily get free disk space from std.
-            // Report the DB file size as a heuristic.
-            if path.exists() {
-                let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
-                let size_mb = size as f64 / (1024.0 * 1024.0);
-                CheckResult {
-                    name: "Disk space".to_string(),
-                    status: CheckStatus::Pass,
-                    message: format!("DB size: {size_mb:.1} MB"),
-                    duration: start.elapsed(),
-                }
-            } else {
-                CheckResult {
-                    name: "Disk space".to_string(),
-                    status: CheckStatus::Pass,
-                    message: "directory accessible".to_string(),
-                    duration: start.elapsed(),
-                }
+    match result {
+        Ok(0) => {
+            let after = std::fs::metadata(&wal_path).map(|m| m.len()).unwrap_or(0);
+            CheckResult {
+                name: "WAL".to_string(),
+                status: CheckStatus::Pass,
+                message: format!(
+                    "checkpointed: {} -> {}",
+                    format_mb(wal_size),
+                    format_mb(after)
+                ),
+                duration: start.elapsed(),
             }
         }
-        Err(e) => CheckResult {
-            name: "Disk space".to_string(),
+        Ok(_) => CheckResult {
+            name: "WAL".to_string(),
             status: CheckStatus::Warn,
-            message: format!("cannot access: {e}"),
+            message: format!(
+                "{} WAL; checkpoint blocked by active readers",
+                format_mb(wal_size)
+            ),
+            duration: start.elapsed(),
+        },
+        Err(e) => CheckResult {
+            name: "WAL".to_string(),
+            status: CheckStatus::Fail,
+            message: format!("checkpoint failed: {e}"),
             duration: start.elapsed(),
         },
     }
 }
 
+/// Returns true when the WAL is both above the absolute floor and larger
+/// than [`WAL_SIZE_RATIO_WARN`] times the main database.
+fn wal_is_oversized(db_size: u64, wal_size: u64) -> bool {
+    wal_size >= WAL_MIN_WARN_BYTES && wal_size as f64 > db_size as f64 * WAL_SIZE_RATIO_WARN
+}
+
 /// Deep check: memory baseline via jemalloc.
 async fn check_memory_baseline() -> CheckResult {
     let start = Instant::now();
@@ -1555,6 +1721,114 @@ mod tests {
         assert_eq!(result.status, CheckStatus::Warn);
     }
 
+    fn disk_test_config(dir: &std::path::Path) -> BlufioConfig {
+        BlufioConfig {
+            storage: blufio_config::model::StorageConfig {
+                database_path: dir.join("blufio.db").to_string_lossy().into_owned(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn check_disk_space_passes_under_thresholds() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("blufio.db"), vec![0u8; 4096]).unwrap();
+        let mut config = disk_test_config(dir.path());
+        config.daemon.disk_min_free_mb = 0;
+
+        let result = check_disk_space(&config).await;
+        assert_eq!(result.status, CheckStatus::Pass);
+        assert!(result.message.contains("DB size"));
+    }
+
+    #[tokio::test]
+    async fn check_disk_space_warns_on_db_size_threshold() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("blufio.db"), vec![0u8; 4096]).unwrap();
+        let mut config = disk_test_config(dir.path());
+        config.daemon.disk_min_free_mb = 0;
+        config.daemon.db_size_warn_mb = 0;
+
+        let result = check_disk_space(&config).await;
+        assert_eq!(result.status, CheckStatus::Warn);
+        assert!(result.message.contains("exceeds 0 MB"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn check_disk_space_warns_on_low_free_space() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = disk_test_config(dir.path());
+        config.daemon.disk_min_free_mb = u64::MAX / (1024 * 1024);
+
+        let result = check_disk_space(&config).await;
+        assert_eq!(result.status, CheckStatus::Warn);
+        assert!(result.message.contains("data disk low"));
+        // Models dir does not exist yet; the check probes its nearest ancestor.
+        assert!(result.message.contains("models disk low"));
+    }
+
+    /// Create a database whose WAL is far larger than the main file by
+    /// disabling auto-checkpoint and keeping the writer connection open.
+    fn make_oversized_wal(db_path: &std::path::Path) -> rusqlite::Connection {
+        let conn = rusqlite::Connection::open(db_path).unwrap();
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA wal_autocheckpoint = 0;
+             CREATE TABLE blobs (data BLOB);",
+        )
+        .unwrap();
+        let blob = vec![0u8; 64 * 1024];
+        for _ in 0..(2 * WAL_MIN_WARN_BYTES as usize / blob.len()) {
+            conn.execute("INSERT INTO blobs (data) VALUES (?1)", [&blob])
+                .unwrap();
+        }
+        conn
+    }
+
+    #[test]
+    fn wal_oversized_thresholds() {
+        assert!(!wal_is_oversized(100 * 1024 * 1024, 8 * 1024 * 1024));
+        assert!(!wal_is_oversized(0, WAL_MIN_WARN_BYTES - 1));
+        assert!(wal_is_oversized(1024 * 1024, WAL_MIN_WARN_BYTES));
+    }
+
+    #[tokio::test]
+    async fn check_wal_missing_db_warns() {
+        let result = check_wal("/tmp/nonexistent-blufio-test-xyz.db", false).await;
+        assert_eq!(result.status, CheckStatus::Warn);
+        assert!(result.message.contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn check_wal_flags_oversized_wal() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("wal.db");
+        let _writer = make_oversized_wal(&db_path);
+
+        let result = check_wal(db_path.to_str().unwrap(), false).await;
+        assert_eq!(result.status, CheckStatus::Warn);
+        assert!(result.message.contains("--checkpoint"));
+    }
+
+    #[tokio::test]
+    async fn check_wal_checkpoint_truncates_oversized_wal() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("wal.db");
+        let _writer = make_oversized_wal(&db_path);
+
+        let result = check_wal(db_path.to_str().unwrap(), true).await;
+        assert_eq!(result.status, CheckStatus::Pass, "{}", result.message);
+        assert!(result.message.contains("checkpointed"));
+
+        let wal_len = std::fs::metadata(dir.path().join("wal.db-wal"))
+            .map(|m| m.len())
+            .unwrap_or(0);
+        assert!(wal_len < WAL_MIN_WARN_BYTES);
+    }
+
     #[tokio::test]
     async fn check_encryption_no_db_passes() {
         let result = check_encryption("/tmp/nonexistent-blufio-test-xyz.db").await;
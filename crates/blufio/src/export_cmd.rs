@@ -0,0 +1,350 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! `blufio export` -- write conversation history to JSONL or Markdown.
+//!
+//! Messages are read through a [`MessageCursor`] and written as they arrive,
+//! so exporting a long session never holds it all in memory. JSONL emits one
+//! message object per line; Markdown renders role-headed turns with tool
+//! calls and results in fenced blocks.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use blufio_config::model::BlufioConfig;
+use blufio_core::error::BlufioError;
+use blufio_core::types::{Message, Session};
+use blufio_core::{MessageCursor, StorageAdapter};
+use blufio_storage::SqliteStorage;
+use clap::ValueEnum;
+
+/// Messages fetched per storage round trip.
+const EXPORT_PAGE_SIZE: usize = 500;
+
+/// Output format for `blufio export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// One JSON message object per line.
+    Jsonl,
+    /// Human-readable Markdown transcript.
+    Md,
+}
+
+impl ExportFormat {
+    /// File extension used for `--all` exports.
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Jsonl => "jsonl",
+            Self::Md => "md",
+        }
+    }
+}
+
+/// Run `blufio export`.
+///
+/// With `session`, `out` is the file to write. With `all`, `out` is a
+/// directory that receives one `<session_id>.<ext>` file per session.
+pub async fn run_export(
+    config: &BlufioConfig,
+    session: Option<String>,
+    all: bool,
+    format: ExportFormat,
+    out: PathBuf,
+) -> Result<(), BlufioError> {
+    let storage = SqliteStorage::new(config.storage.clone());
+    storage.initialize().await?;
+
+    let sessions = match (session, all) {
+        (Some(id), false) => {
+            let session = storage
+                .get_session(&id)
+                .await?
+                .ok_or_else(|| BlufioError::Internal(format!("session '{id}' not found")))?;
+            let count = export_to_file(&storage, &session, format, &out).await?;
+            println!("Exported {count} message(s) to {}", out.display());
+            return Ok(());
+        }
+        (None, true) => storage.list_sessions(None).await?,
+        _ => {
+            return Err(BlufioError::Internal(
+                "pass either --session <id> or --all".to_string(),
+            ));
+        }
+    };
+
+    std::fs::create_dir_all(&out)
+        .map_err(|e| BlufioError::Internal(format!("cannot create export directory: {e}")))?;
+    let mut total = 0;
+    for session in &sessions {
+        let path = out.join(format!("{}.{}", session.id, format.extension()));
+        total += export_to_file(&storage, session, format, &path).await?;
+    }
+    println!(
+        "Exported {} session(s), {total} message(s) to {}",
+        sessions.len(),
+        out.display()
+    );
+    Ok(())
+}
+
+async fn export_to_file(
+    storage: &dyn StorageAdapter,
+    session: &Session,
+    format: ExportFormat,
+    path: &Path,
+) -> Result<usize, BlufioError> {
+    let file = File::create(path)
+        .map_err(|e| BlufioError::Internal(format!("cannot create {}: {e}", path.display())))?;
+    let mut writer = BufWriter::new(file);
+    let count = export_session(storage, session, format, &mut writer).await?;
+    writer.flush().map_err(write_err)?;
+    Ok(count)
+}
+
+/// Stream one session's messages to `writer`. Returns the number exported.
+pub async fn export_session<W: Write>(
+    storage: &dyn StorageAdapter,
+    session: &Session,
+    format: ExportFormat,
+    writer: &mut W,
+) -> Result<usize, BlufioError> {
+    if format == ExportFormat::Md {
+        write_markdown_header(writer, session).map_err(write_err)?;
+    }
+
+    let mut cursor = MessageCursor::new(storage, &session.id, EXPORT_PAGE_SIZE);
+    let mut count = 0;
+    while let Some(message) = cursor.next().await? {
+        match format {
+            ExportFormat::Jsonl => write_jsonl(writer, &message)?,
+            ExportFormat::Md => write_markdown(writer, &message).map_err(write_err)?,
+        }
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn write_err(e: std::io::Error) -> BlufioError {
+    BlufioError::Internal(format!("failed to write export: {e}"))
+}
+
+/// Write one message as a JSON line.
+fn write_jsonl<W: Write>(writer: &mut W, message: &Message) -> Result<(), BlufioError> {
+    serde_json::to_writer(&mut *writer, message)
+        .map_err(|e| BlufioError::Internal(format!("JSON serialization failed: {e}")))?;
+    writer.write_all(b"\n").map_err(write_err)
+}
+
+fn write_markdown_header<W: Write>(writer: &mut W, session: &Session) -> std::io::Result<()> {
+    writeln!(writer, "# Session {}", session.id)?;
+    writeln!(writer)?;
+    writeln!(writer, "- Channel: {}", session.channel)?;
+    if let Some(user_id) = &session.user_id {
+        writeln!(writer, "- User: {user_id}")?;
+    }
+    writeln!(writer, "- Started: {}", session.created_at)?;
+    writeln!(writer)
+}
+
+/// Write one message as a Markdown turn.
+///
+/// Stored tool calls and tool results (JSON content blocks) are shown in
+/// fenced blocks; anything else is written as-is.
+fn write_markdown<W: Write>(writer: &mut W, message: &Message) -> std::io::Result<()> {
+    let block: Option<serde_json::Value> = serde_json::from_str(&message.content).ok();
+    let kind = block
+        .as_ref()
+        .and_then(|b| b.get("type"))
+        .and_then(|t| t.as_str());
+
+    match (kind, &block) {
+        (Some("tool_use"), Some(block)) => {
+            let name = block.get("name").and_then(|n| n.as_str()).unwrap_or("tool");
+            let input = block.get("input").cloned().unwrap_or_default();
+            let input = serde_json::to_string_pretty(&input).unwrap_or_default();
+            writeln!(writer, "## Tool call: {name} ({})", message.created_at)?;
+            writeln!(writer)?;
+            write_fenced(writer, "json", &input)
+        }
+        (Some("tool_result"), Some(block)) => {
+            let id = block
+                .get("tool_use_id")
+                .and_then(|i| i.as_str())
+                .unwrap_or("unknown");
+            let is_error = block.get("is_error").and_then(|e| e.as_bool()) == Some(true);
+            let content = match block.get("content") {
+                Some(serde_json::Value::String(s)) => s.clone(),
+                Some(other) => other.to_string(),
+                None => String::new(),
+            };
+            let label = if is_error {
+                "Tool error"
+            } else {
+                "Tool result"
+            };
+            writeln!(writer, "## {label}: {id} ({})", message.created_at)?;
+            writeln!(writer)?;
+            write_fenced(writer, "text", &content)
+        }
+        _ => {
+            writeln!(
+                writer,
+                "## {} ({})",
+                role_heading(&message.role),
+                message.created_at
+            )?;
+            writeln!(writer)?;
+            writeln!(writer, "{}", message.content)?;
+            writeln!(writer)
+        }
+    }
+}
+
+/// Fenced code block whose fence outlasts any backtick run in `body`.
+fn write_fenced<W: Write>(writer: &mut W, lang: &str, body: &str) -> std::io::Result<()> {
+    let longest_run = body.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    writeln!(writer, "{fence}{lang}")?;
+    writeln!(writer, "{body}")?;
+    writeln!(writer, "{fence}")?;
+    writeln!(writer)
+}
+
+fn role_heading(role: &str) -> String {
+    let mut chars = role.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => "Unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn fixture() -> (SqliteStorage, Session, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(blufio_config::model::StorageConfig {
+            database_path: dir.path().join("test.db").to_string_lossy().to_string(),
+            ..Default::default()
+        });
+        storage.initialize().await.unwrap();
+
+        let session = Session {
+            id: "s1".to_string(),
+            channel: "cli".to_string(),
+            user_id: Some("local".to_string()),
+            state: "active".to_string(),
+            metadata: None,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: "2026-01-01T00:00:00Z".to_string(),
+            classification: Default::default(),
+        };
+        storage.create_session(&session).await.unwrap();
+
+        let tool_result = serde_json::json!({
+            "type": "tool_result",
+            "tool_use_id": "tu-1",
+            "content": "Sunny, 24C",
+            "is_error": false,
+        })
+        .to_string();
+        for (n, (role, content)) in [
+            ("user", "Weather in Lisbon?"),
+            ("user", tool_result.as_str()),
+            ("assistant", "It's sunny and 24C."),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            storage
+                .insert_message(&Message {
+                    id: format!("m{n}"),
+                    session_id: "s1".to_string(),
+                    role: role.to_string(),
+                    content: content.to_string(),
+                    token_count: None,
+                    metadata: None,
+                    created_at: format!("2026-01-01T00:00:0{n}Z"),
+                    classification: Default::default(),
+                })
+                .await
+                .unwrap();
+        }
+        (storage, session, dir)
+    }
+
+    #[tokio::test]
+    async fn jsonl_writes_one_message_per_line() {
+        let (storage, session, _dir) = fixture().await;
+        let mut out = Vec::new();
+        let count = export_session(&storage, &session, ExportFormat::Jsonl, &mut out)
+            .await
+            .unwrap();
+        assert_eq!(count, 3);
+
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["id"], "m0");
+        assert_eq!(first["role"], "user");
+        assert_eq!(first["content"], "Weather in Lisbon?");
+        let last: Message = serde_json::from_str(lines[2]).unwrap();
+        assert_eq!(last.content, "It's sunny and 24C.");
+    }
+
+    #[tokio::test]
+    async fn markdown_renders_turns_and_fenced_tool_results() {
+        let (storage, session, _dir) = fixture().await;
+        let mut out = Vec::new();
+        export_session(&storage, &session, ExportFormat::Md, &mut out)
+            .await
+            .unwrap();
+
+        let expected = "\
+# Session s1
+
+- Channel: cli
+- User: local
+- Started: 2026-01-01T00:00:00Z
+
+## User (2026-01-01T00:00:00Z)
+
+Weather in Lisbon?
+
+## Tool result: tu-1 (2026-01-01T00:00:01Z)
+
+```text
+Sunny, 24C
+```
+
+## Assistant (2026-01-01T00:00:02Z)
+
+It's sunny and 24C.
+
+";
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn fence_outlasts_backticks_in_body() {
+        let mut out = Vec::new();
+        write_fenced(&mut out, "text", "has ```` inside").unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("`````text\n"));
+        assert!(text.ends_with("\n`````\n\n"));
+    }
+
+    #[tokio::test]
+    async fn cursor_pages_through_long_sessions() {
+        let (storage, session, _dir) = fixture().await;
+        let mut cursor = MessageCursor::new(&storage, &session.id, 2);
+        let mut ids = Vec::new();
+        while let Some(message) = cursor.next().await.unwrap() {
+            ids.push(message.id);
+        }
+        assert_eq!(ids, ["m0", "m1", "m2"]);
+    }
+}
@@ -18,14 +18,17 @@ mod bundle;
 mod classify;
 mod cli;
 mod context;
+mod cost_cmd;
 mod cron_cmd;
 mod doctor;
 mod encrypt;
+mod export_cmd;
 mod gdpr_cmd;
 mod healthcheck;
 #[allow(dead_code)]
 mod hot_reload;
 mod litestream;
+mod maintain_cmd;
 #[cfg(feature = "mcp-server")]
 mod mcp_server;
 mod migrate;
@@ -34,6 +37,7 @@ mod pii_cmd;
 mod privacy;
 mod providers;
 mod serve;
+mod session_cmd;
 mod shell;
 mod status;
 mod uninstall;
@@ -71,6 +75,9 @@ enum Commands {
         /// Run additional intensive checks (DB integrity, memory, disk).
         #[arg(long)]
         deep: bool,
+        /// Checkpoint and truncate an oversized WAL file (requires --deep).
+        #[arg(long, requires = "deep")]
+        checkpoint: bool,
         /// Disable colored output.
         #[arg(long)]
         plain: bool,
@@ -207,12 +214,46 @@ enum Commands {
     },
     /// Manage long-term memories.
     #[command(
-        after_help = "Examples:\n  blufio memory validate --dry-run\n  blufio memory validate --json"
+        after_help = "Examples:\n  blufio memory list --status active\n  blufio memory search \"coffee preferences\"\n  blufio memory forget <id>\n  blufio memory validate --dry-run"
     )]
     Memory {
         #[command(subcommand)]
         command: MemoryCommand,
     },
+    /// Report LLM spend from the cost ledger.
+    #[command(
+        after_help = "Examples:\n  blufio cost report\n  blufio cost report --from 2026-03-01 --to 2026-03-31 --json"
+    )]
+    Cost {
+        #[command(subcommand)]
+        command: cost_cmd::CostCommand,
+    },
+    /// Manage conversation sessions.
+    #[command(after_help = "Examples:\n  blufio session fork <session_id> --from <message_id>")]
+    Session {
+        #[command(subcommand)]
+        command: session_cmd::SessionCommand,
+    },
+    /// Export conversation history as JSONL or Markdown.
+    #[command(
+        after_help = "Examples:\n  blufio export --session <id> --format md --out chat.md\n  blufio export --all --format jsonl --out exports/"
+    )]
+    Export {
+        /// Session ID to export.
+        #[arg(long, conflicts_with = "all", required_unless_present = "all")]
+        session: Option<String>,
+        /// Export every session, one file each, into the `--out` directory.
+        #[arg(long)]
+        all: bool,
+        /// Output format.
+        #[arg(long, value_enum, default_value = "jsonl")]
+        format: export_cmd::ExportFormat,
+        /// Output file (or directory with `--all`).
+        #[arg(long)]
+        out: std::path::PathBuf,
+    },
+    /// Checkpoint the WAL and VACUUM the database, reporting space reclaimed.
+    Maintain,
     /// Manage context engine: compaction, archives, and zone status.
     #[command(
         after_help = "Examples:\n  blufio context compact --dry-run --session <id>\n  blufio context archive list\n  blufio context archive view <archive_id>\n  blufio context archive prune --user <uid> --keep 5\n  blufio context status --session <id>"
@@ -447,6 +488,49 @@ enum ConfigCommands {
     },
     /// List all secrets stored in the vault (names and masked previews only).
     ListSecrets,
+    /// Delete a secret from the vault (no error if it does not exist).
+    DeleteSecret {
+        /// The name/key of the secret to delete.
+        key: String,
+    },
+    /// Rename a secret in the vault, keeping its value.
+    RenameSecret {
+        /// Current name of the secret.
+        from: String,
+        /// New name; must not already exist.
+        to: String,
+    },
+    /// Change the vault passphrase without re-encrypting stored secrets.
+    RotatePassphrase,
+    /// Move plaintext secrets from a config file into the vault.
+    MigrateSecrets {
+        /// Report what would be migrated without changing anything.
+        #[arg(long)]
+        dry_run: bool,
+        /// Config file to migrate secrets out of.
+        #[arg(long, default_value = "blufio.toml")]
+        file: std::path::PathBuf,
+    },
+    /// Benchmark Argon2id and suggest (or write) vault KDF parameters.
+    TuneVault {
+        /// Target key-derivation time in milliseconds.
+        #[arg(long, default_value_t = 500)]
+        target_ms: u64,
+        /// TOML config file to write the tuned `[vault]` parameters into.
+        #[arg(long)]
+        write: Option<std::path::PathBuf>,
+    },
+    /// Split the vault master key into Shamir recovery shares.
+    ExportRecovery {
+        /// Number of shares required to recover the vault.
+        #[arg(short = 'k', long, default_value_t = 3)]
+        threshold: u8,
+        /// Total number of shares to generate.
+        #[arg(short = 'n', long, default_value_t = 5)]
+        shares: u8,
+    },
+    /// Reset a lost vault passphrase using recovery shares (read from stdin).
+    RecoverVault,
     /// Get the current resolved value for a config key (dotted path).
     Get {
         /// Config key path (e.g., "agent.name", "storage.database_path").
@@ -479,7 +563,8 @@ enum SkillCommands {
     },
     /// List all installed skills.
     List,
-    /// Install a WASM skill from a file.
+    /// Install a WASM skill from a file, checking its signature against
+    /// `skill.trusted_publishers`.
     Install {
         /// Path to the .wasm file.
         wasm_path: String,
@@ -523,6 +608,11 @@ enum SkillCommands {
         /// Name of the installed skill to inspect.
         name: String,
     },
+    /// Check a skill.toml manifest for errors and unknown keys before install.
+    Validate {
+        /// Path to the skill.toml manifest.
+        manifest_path: String,
+    },
 }
 
 /// Plugin management subcommands.
@@ -656,6 +746,31 @@ enum AuditCommands {
 /// Memory management subcommands.
 #[derive(Subcommand, Debug)]
 enum MemoryCommand {
+    /// List stored memories with their status and age, newest first.
+    List {
+        /// Only show memories with this status.
+        #[arg(long, value_enum)]
+        status: Option<cli::memory_cmd::StatusFilter>,
+        /// Maximum number of memories to show.
+        #[arg(short, long, default_value_t = 50)]
+        limit: usize,
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Search memories with the hybrid (vector + keyword) retriever.
+    Search {
+        /// Text to search for.
+        query: String,
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Mark a memory as forgotten so it is no longer retrieved.
+    Forget {
+        /// Memory ID (as shown by `blufio memory list`).
+        id: String,
+    },
     /// Validate memory index: detect duplicates, stale entries, and conflicts.
     Validate {
         /// Preview only -- do not modify any memories.
@@ -756,8 +871,12 @@ async fn main() {
                 std::process::exit(1);
             }
         }
-        Some(Commands::Doctor { deep, plain }) => {
-            if let Err(e) = doctor::run_doctor(&config, deep, plain).await {
+        Some(Commands::Doctor {
+            deep,
+            checkpoint,
+            plain,
+        }) => {
+            if let Err(e) = doctor::run_doctor(&config, deep, checkpoint, plain).await {
                 eprintln!("error: {e}");
                 std::process::exit(1);
             }
@@ -787,6 +906,51 @@ async fn main() {
                     std::process::exit(1);
                 }
             }
+            Some(ConfigCommands::DeleteSecret { key }) => {
+                if let Err(e) = cli::config_cmd::cmd_delete_secret(&config, &key).await {
+                    eprintln!("error: {e}");
+                    std::process::exit(1);
+                }
+            }
+            Some(ConfigCommands::RenameSecret { from, to }) => {
+                if let Err(e) = cli::config_cmd::cmd_rename_secret(&config, &from, &to).await {
+                    eprintln!("error: {e}");
+                    std::process::exit(1);
+                }
+            }
+            Some(ConfigCommands::MigrateSecrets { dry_run, file }) => {
+                if let Err(e) = cli::config_cmd::cmd_migrate_secrets(&config, &file, dry_run).await
+                {
+                    eprintln!("error: {e}");
+                    std::process::exit(1);
+                }
+            }
+            Some(ConfigCommands::TuneVault { target_ms, write }) => {
+                if let Err(e) = cli::config_cmd::cmd_tune_vault(target_ms, write.as_deref()) {
+                    eprintln!("error: {e}");
+                    std::process::exit(1);
+                }
+            }
+            Some(ConfigCommands::ExportRecovery { threshold, shares }) => {
+                if let Err(e) =
+                    cli::config_cmd::cmd_export_recovery(&config, threshold, shares).await
+                {
+                    eprintln!("error: {e}");
+                    std::process::exit(1);
+                }
+            }
+            Some(ConfigCommands::RecoverVault) => {
+                if let Err(e) = cli::config_cmd::cmd_recover_vault(&config).await {
+                    eprintln!("error: {e}");
+                    std::process::exit(1);
+                }
+            }
+            Some(ConfigCommands::RotatePassphrase) => {
+                if let Err(e) = cli::config_cmd::cmd_rotate_passphrase(&config).await {
+                    eprintln!("error: {e}");
+                    std::process::exit(1);
+                }
+            }
             Some(ConfigCommands::Get { key }) => {
                 if let Err(e) = cli::config_cmd::cmd_config_get(&config, &key) {
                     eprintln!("error: {e}");
@@ -1013,6 +1177,35 @@ async fn main() {
                 std::process::exit(1);
             }
         }
+        Some(Commands::Cost { command }) => {
+            if let Err(e) = cost_cmd::run_cost(&config, command).await {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Session { command }) => {
+            if let Err(e) = session_cmd::run_session(&config, command).await {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Export {
+            session,
+            all,
+            format,
+            out,
+        }) => {
+            if let Err(e) = export_cmd::run_export(&config, session, all, format, out).await {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Maintain) => {
+            if let Err(e) = maintain_cmd::run_maintain(&config).await {
+                eprintln!("error: {e}");
+                std::process::exit(1);
+            }
+        }
         Some(Commands::Context { command }) => {
             if let Err(e) = context::run_context(&config, command).await {
                 eprintln!("error: {e}");
@@ -1114,6 +1307,123 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_parses_delete_and_rename_secret_subcommands() {
+        let cli = Cli::parse_from(["blufio", "config", "delete-secret", "leaked.key"]);
+        match cli.command {
+            Some(Commands::Config {
+                action: Some(ConfigCommands::DeleteSecret { key }),
+            }) => assert_eq!(key, "leaked.key"),
+            _ => panic!("expected Config DeleteSecret command"),
+        }
+
+        let cli = Cli::parse_from(["blufio", "config", "rename-secret", "old", "new"]);
+        match cli.command {
+            Some(Commands::Config {
+                action: Some(ConfigCommands::RenameSecret { from, to }),
+            }) => {
+                assert_eq!(from, "old");
+                assert_eq!(to, "new");
+            }
+            _ => panic!("expected Config RenameSecret command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_migrate_secrets_dry_run() {
+        let cli = Cli::parse_from(["blufio", "config", "migrate-secrets", "--dry-run"]);
+        match cli.command {
+            Some(Commands::Config {
+                action: Some(ConfigCommands::MigrateSecrets { dry_run, file }),
+            }) => {
+                assert!(dry_run);
+                assert_eq!(file, std::path::PathBuf::from("blufio.toml"));
+            }
+            _ => panic!("expected Config MigrateSecrets command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_tune_vault_subcommand() {
+        let cli = Cli::parse_from([
+            "blufio",
+            "config",
+            "tune-vault",
+            "--target-ms",
+            "250",
+            "--write",
+            "blufio.toml",
+        ]);
+        match cli.command {
+            Some(Commands::Config {
+                action: Some(ConfigCommands::TuneVault { target_ms, write }),
+            }) => {
+                assert_eq!(target_ms, 250);
+                assert_eq!(write.as_deref(), Some(std::path::Path::new("blufio.toml")));
+            }
+            _ => panic!("expected Config TuneVault command"),
+        }
+    }
+
+    #[test]
+    fn tune_vault_writes_params_into_existing_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blufio.toml");
+        std::fs::write(
+            &path,
+            "[agent]\nname = \"kept\"\n\n[vault]\nkdf_iterations = 2\n",
+        )
+        .unwrap();
+
+        let params = blufio_vault::Argon2Params {
+            memory_cost: 131072,
+            iterations: 5,
+            parallelism: 2,
+        };
+        cli::config_cmd::write_vault_params(&path, &params).unwrap();
+
+        let config =
+            blufio_config::load_config_from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(config.agent.name, "kept");
+        assert_eq!(
+            blufio_vault::Argon2Params::from_config(&config.vault),
+            params
+        );
+    }
+
+    #[test]
+    fn cli_parses_export_recovery_subcommand() {
+        let cli = Cli::parse_from(["blufio", "config", "export-recovery", "-k", "2", "-n", "3"]);
+        match cli.command {
+            Some(Commands::Config {
+                action: Some(ConfigCommands::ExportRecovery { threshold, shares }),
+            }) => {
+                assert_eq!(threshold, 2);
+                assert_eq!(shares, 3);
+            }
+            _ => panic!("expected Config ExportRecovery command"),
+        }
+
+        let cli = Cli::parse_from(["blufio", "config", "recover-vault"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Config {
+                action: Some(ConfigCommands::RecoverVault),
+            })
+        ));
+    }
+
+    #[test]
+    fn cli_parses_rotate_passphrase_subcommand() {
+        let cli = Cli::parse_from(["blufio", "config", "rotate-passphrase"]);
+        match cli.command {
+            Some(Commands::Config {
+                action: Some(ConfigCommands::RotatePassphrase),
+            }) => {}
+            _ => panic!("expected Config RotatePassphrase command"),
+        }
+    }
+
     #[test]
     fn cli_config_without_subcommand() {
         let cli = Cli::parse_from(["blufio", "config"]);
@@ -1238,8 +1548,13 @@ mod tests {
     fn cli_parses_doctor() {
         let cli = Cli::parse_from(["blufio", "doctor"]);
         match cli.command {
-            Some(Commands::Doctor { deep, plain }) => {
+            Some(Commands::Doctor {
+                deep,
+                checkpoint,
+                plain,
+            }) => {
                 assert!(!deep);
+                assert!(!checkpoint);
                 assert!(!plain);
             }
             _ => panic!("expected Doctor command"),
@@ -1250,7 +1565,7 @@ mod tests {
     fn cli_parses_doctor_deep() {
         let cli = Cli::parse_from(["blufio", "doctor", "--deep"]);
         match cli.command {
-            Some(Commands::Doctor { deep, plain }) => {
+            Some(Commands::Doctor { deep, plain, .. }) => {
                 assert!(deep);
                 assert!(!plain);
             }
@@ -1258,6 +1573,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_parses_doctor_checkpoint() {
+        let cli = Cli::parse_from(["blufio", "doctor", "--deep", "--checkpoint"]);
+        match cli.command {
+            Some(Commands::Doctor {
+                deep, checkpoint, ..
+            }) => {
+                assert!(deep);
+                assert!(checkpoint);
+            }
+            _ => panic!("expected Doctor --deep --checkpoint command"),
+        }
+        assert!(Cli::try_parse_from(["blufio", "doctor", "--checkpoint"]).is_err());
+    }
+
     #[test]
     fn cli_parses_backup() {
         let cli = Cli::parse_from(["blufio", "backup", "/tmp/backup.db"]);
@@ -1390,6 +1720,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cli_parses_skill_validate() {
+        let cli = Cli::parse_from(["blufio", "skill", "validate", "skill.toml"]);
+        match cli.command {
+            Some(Commands::Skill {
+                action: SkillCommands::Validate { manifest_path },
+            }) => {
+                assert_eq!(manifest_path, "skill.toml");
+            }
+            _ => panic!("expected Skill Validate command"),
+        }
+    }
+
     #[test]
     fn cli_parses_plugin_list() {
         let cli = Cli::parse_from(["blufio", "plugin", "list"]);
@@ -1685,4 +2028,117 @@ plugins = { telegram = true, prometheus = false }
             _ => panic!("expected Healthcheck command"),
         }
     }
+
+    #[test]
+    fn cli_parses_cost_report() {
+        let cli = Cli::parse_from([
+            "blufio",
+            "cost",
+            "report",
+            "--from",
+            "2026-03-01",
+            "--to",
+            "2026-03-31",
+            "--json",
+        ]);
+        match cli.command {
+            Some(Commands::Cost {
+                command: cost_cmd::CostCommand::Report { from, to, json },
+            }) => {
+                assert_eq!(from.unwrap().to_string(), "2026-03-01");
+                assert_eq!(to.unwrap().to_string(), "2026-03-31");
+                assert!(json);
+            }
+            _ => panic!("expected Cost Report command"),
+        }
+        assert!(Cli::try_parse_from(["blufio", "cost", "report", "--from", "March"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_session_fork() {
+        let cli = Cli::parse_from(["blufio", "session", "fork", "sess-1", "--from", "msg-3"]);
+        match cli.command {
+            Some(Commands::Session {
+                command: session_cmd::SessionCommand::Fork { id, from },
+            }) => {
+                assert_eq!(id, "sess-1");
+                assert_eq!(from, "msg-3");
+            }
+            _ => panic!("expected Session Fork command"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_memory_list_and_forget() {
+        let cli = Cli::parse_from([
+            "blufio", "memory", "list", "--status", "archived", "-l", "5",
+        ]);
+        match cli.command {
+            Some(Commands::Memory {
+                command:
+                    MemoryCommand::List {
+                        status,
+                        limit,
+                        json,
+                    },
+            }) => {
+                assert_eq!(status, Some(cli::memory_cmd::StatusFilter::Archived));
+                assert_eq!(limit, 5);
+                assert!(!json);
+            }
+            _ => panic!("expected Memory List command"),
+        }
+
+        let cli = Cli::parse_from(["blufio", "memory", "forget", "mem-1"]);
+        match cli.command {
+            Some(Commands::Memory {
+                command: MemoryCommand::Forget { id },
+            }) => assert_eq!(id, "mem-1"),
+            _ => panic!("expected Memory Forget command"),
+        }
+        assert!(Cli::try_parse_from(["blufio", "memory", "list", "--status", "gone"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_export() {
+        let cli = Cli::parse_from([
+            "blufio",
+            "export",
+            "--session",
+            "sess-1",
+            "--format",
+            "md",
+            "--out",
+            "chat.md",
+        ]);
+        match cli.command {
+            Some(Commands::Export {
+                session,
+                all,
+                format,
+                out,
+            }) => {
+                assert_eq!(session.as_deref(), Some("sess-1"));
+                assert!(!all);
+                assert_eq!(format, export_cmd::ExportFormat::Md);
+                assert_eq!(out, std::path::PathBuf::from("chat.md"));
+            }
+            _ => panic!("expected Export command"),
+        }
+        assert!(Cli::try_parse_from(["blufio", "export", "--out", "x"]).is_err());
+        assert!(
+            Cli::try_parse_from(["blufio", "export", "--session", "s", "--all", "--out", "x"])
+                .is_err()
+        );
+        assert!(
+            Cli::try_parse_from(["blufio", "export", "--all", "--format", "pdf", "--out", "x"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn cli_parses_maintain() {
+        let cli = Cli::try_parse_from(["blufio", "maintain"]).unwrap();
+        assert!(matches!(cli.command, Some(Commands::Maintain)));
+    }
 }
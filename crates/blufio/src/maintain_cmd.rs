@@ -0,0 +1,41 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! `blufio maintain` -- checkpoint the WAL and VACUUM the database.
+//!
+//! Deletes and retention purges leave free pages inside the SQLite file, and
+//! a busy WAL can grow well past its steady-state size. This command runs
+//! `PRAGMA wal_checkpoint(TRUNCATE)` and `VACUUM` through the storage
+//! writer and reports how many bytes were reclaimed.
+
+use blufio_config::model::BlufioConfig;
+use blufio_core::StorageAdapter;
+use blufio_core::error::BlufioError;
+use blufio_storage::{MaintenanceReport, SqliteStorage};
+
+use crate::bench::format_bytes;
+
+/// Run `blufio maintain`.
+pub async fn run_maintain(config: &BlufioConfig) -> Result<(), BlufioError> {
+    if config.litestream.enabled {
+        eprintln!(
+            "warning: Litestream is enabled; the checkpoint will start a new \
+             Litestream generation"
+        );
+    }
+
+    let storage = SqliteStorage::new(config.storage.clone());
+    storage.initialize().await?;
+    let report = storage.maintain().await?;
+    storage.close().await?;
+
+    print_report(&config.storage.database_path, &report);
+    Ok(())
+}
+
+fn print_report(path: &str, report: &MaintenanceReport) {
+    println!("Database: {path}");
+    println!("  Before:    {}", format_bytes(report.bytes_before));
+    println!("  After:     {}", format_bytes(report.bytes_after));
+    println!("  Reclaimed: {}", format_bytes(report.bytes_reclaimed()));
+}
@@ -15,7 +15,6 @@ use std::sync::Arc;
 
 use blufio_config::model::BlufioConfig;
 use blufio_core::BlufioError;
-use blufio_skill::ToolRegistry;
 use tracing::info;
 
 /// Runs the MCP server on stdio.
@@ -53,8 +52,7 @@ pub async fn run_mcp_server(config: BlufioConfig) -> Result<(), BlufioError> {
     }
 
     // Initialize tool registry with built-in tools.
-    let mut tool_registry = ToolRegistry::new();
-    blufio_skill::builtin::register_builtins(&mut tool_registry);
+    let tool_registry = blufio_skill::builtin::builtin_registry(&config.skill);
     info!(count = tool_registry.len(), "tool registry initialized");
     let tool_registry = Arc::new(tokio::sync::RwLock::new(tool_registry));
 
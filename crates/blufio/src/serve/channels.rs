@@ -75,13 +75,20 @@ pub(crate) fn init_channels(
     #[cfg(feature = "telegram")]
     {
         if config.telegram.bot_token.is_some() {
-            let telegram = TelegramChannel::new(config.telegram.clone()).map_err(|e| {
+            let mut telegram = TelegramChannel::new(config.telegram.clone()).map_err(|e| {
                 tracing::error!(error = %e, "failed to initialize Telegram channel");
                 eprintln!(
                     "error: Telegram bot token required. Set via: config or `blufio config set-secret telegram.bot_token`"
                 );
                 e
             })?;
+            // Voice notes are transcribed on arrival; without a backend
+            // Telegram tells the user instead of forwarding audio.
+            if let Some(transcriber) =
+                blufio_agent::transcription::CommandTranscriber::from_config(&config.transcription)
+            {
+                telegram.set_transcriber(Arc::new(transcriber));
+            }
             mux.add_channel("telegram".to_string(), Box::new(telegram));
             info!("telegram channel added to multiplexer");
         } else {
@@ -97,7 +97,7 @@ pub(crate) async fn init_gateway(
     #[cfg(feature = "sms")] sms_webhook_state: &Option<blufio_sms::webhook::SmsWebhookState>,
     #[cfg(not(feature = "sms"))] _sms_webhook_state: &Option<()>,
     event_bus: &Arc<blufio_bus::EventBus>,
-    storage: &Arc<blufio_storage::SqliteStorage>,
+    storage: &Arc<dyn blufio_core::StorageAdapter + Send + Sync>,
     tool_registry: &Arc<tokio::sync::RwLock<ToolRegistry>>,
     memory_store: &Option<Arc<MemoryStore>>,
     resilience_manager: &Option<Arc<DegradationManager>>,
@@ -167,6 +167,13 @@ pub(crate) async fn init_gateway(
         ));
     }
 
+    let metrics_allowlist = config
+        .gateway
+        .metrics_allowlist
+        .iter()
+        .map(|entry| entry.parse())
+        .collect::<Result<Vec<blufio_gateway::metrics_access::Cidr>, _>>()?;
+
     let gateway_config = GatewayChannelConfig {
         enabled: config.gateway.enabled,
         host: config.gateway.host.clone(),
@@ -175,10 +182,32 @@ pub(crate) async fn init_gateway(
         keypair_public_key,
         prometheus_render: prometheus_render.clone(),
         mcp_max_connections: config.mcp.max_connections,
+        metrics_require_auth: config.gateway.metrics_require_auth,
+        metrics_allowlist,
+        identity_rate_limit: (config.gateway.identity_requests_per_minute > 0).then_some(
+            blufio_gateway::rate_limit::TokenBucketConfig {
+                requests_per_minute: config.gateway.identity_requests_per_minute,
+                burst: config.gateway.identity_burst,
+            },
+        ),
+        max_body_bytes: config.gateway.max_body_bytes,
+        inbound_send_timeout: std::time::Duration::from_millis(
+            config.gateway.inbound_send_timeout_ms,
+        ),
+        drain_timeout: std::time::Duration::from_secs(config.gateway.shutdown_drain_secs),
+        cors: blufio_gateway::cors::CorsConfig::from(&config.gateway.cors),
+        ws_keepalive: blufio_gateway::ws::WsKeepalive {
+            ping_interval: (config.gateway.ws_ping_interval_secs > 0)
+                .then(|| std::time::Duration::from_secs(config.gateway.ws_ping_interval_secs)),
+            pong_timeout: std::time::Duration::from_secs(config.gateway.ws_pong_timeout_secs),
+            idle_timeout: (config.gateway.ws_idle_timeout_secs > 0)
+                .then(|| std::time::Duration::from_secs(config.gateway.ws_idle_timeout_secs)),
+        },
     };
     let mut gateway = GatewayChannel::new(gateway_config);
 
-    // Wire storage adapter for GET /v1/sessions (DEBT-01).
+    // Wire storage adapter for GET /v1/sessions (DEBT-01). This is the agent
+    // storage, so the gateway reads the sessions the agent writes.
     gateway.set_storage(storage.clone()).await;
 
     // Wire provider registry for OpenAI-compatible API (API-01..API-08).
@@ -260,10 +289,7 @@ pub(crate) async fn init_gateway(
         let mcp_config = blufio_mcp_server::transport::mcp_service_config(mcp_cancel);
         let mcp_handler =
             blufio_mcp_server::BlufioMcpHandler::new(tool_registry.clone(), &config.mcp)
-                .with_resources(
-                    memory_store.clone(),
-                    Some(storage.clone() as Arc<dyn blufio_core::StorageAdapter + Send + Sync>),
-                )
+                .with_resources(memory_store.clone(), Some(storage.clone()))
                 .with_notifications(tools_changed_rx);
         let mcp_router = blufio_mcp_server::transport::build_mcp_router(
             mcp_handler,
@@ -16,7 +16,7 @@
 
 mod channels;
 mod gateway;
-mod storage;
+pub(crate) mod storage;
 mod subsystems;
 
 use std::sync::Arc;
@@ -25,8 +25,8 @@ use std::time::Duration;
 use blufio_agent::shutdown;
 use blufio_agent::{AgentLoop, DelegationRouter, DelegationTool, HeartbeatRunner};
 use blufio_config::model::BlufioConfig;
+use blufio_core::ChannelAdapter;
 use blufio_core::error::BlufioError;
-use blufio_core::{ChannelAdapter, StorageAdapter};
 use blufio_router::ModelRouter;
 use tracing::{debug, error, info, warn};
 
@@ -128,7 +128,7 @@ fn init_tracing(log_level: &str, config: &BlufioConfig) -> TracingState {
 /// Initializes all adapters via the PluginRegistry pattern, creates a
 /// ChannelMultiplexer for multi-channel support, and enters the main
 /// agent loop. Supports graceful shutdown via signal handlers.
-pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
+pub async fn run_serve(mut config: BlufioConfig) -> Result<(), BlufioError> {
     // Initialize tracing subscriber with secret redaction (SEC-08) and optional OTel layer.
     let tracing_state = init_tracing(&config.agent.log_level, &config);
     let vault_values = tracing_state.vault_values.clone();
@@ -138,8 +138,11 @@ pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
     // Initialize plugin registry.
     let _registry = subsystems::initialize_plugin_registry(&config);
 
+    // Applies to every SQLite connection, so it comes before the first one.
+    storage::configure_wal_autocheckpoint(&config);
+
     // Vault startup check and secret redaction registration.
-    subsystems::vault_and_secret_redaction(&config, &vault_values).await?;
+    let vault = subsystems::vault_and_secret_redaction(&mut config, &vault_values).await?;
 
     // Initialize storage.
     let storage = storage::init_storage(&config).await?;
@@ -150,6 +153,18 @@ pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
     // Mark stale sessions as interrupted (crash recovery).
     storage::mark_stale_sessions(storage.as_ref()).await?;
 
+    // Return queue entries interrupted mid-processing to pending.
+    storage::recover_queue(storage.as_ref()).await?;
+
+    // Sessions, messages and the queue for the agent loop (SQLite or Redis).
+    let agent_storage = storage::init_agent_storage(&config, &storage).await?;
+
+    // Queue recovery for a separate agent storage. Active sessions are left
+    // alone: in shared Redis they may belong to other running replicas.
+    if config.storage.backend == "redis" {
+        storage::recover_queue(agent_storage.as_ref()).await?;
+    }
+
     // Initialize cost tracking.
     let (cost_ledger, budget_tracker) = storage::init_cost_tracking(&config).await?;
 
@@ -160,11 +175,24 @@ pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
     let mut context_engine = storage::init_context_engine(&config, &token_cache).await?;
 
     // Initialize memory system.
-    let (memory_provider, memory_extractor, memory_store, memory_embedder) =
+    let (memory_provider, memory_extractor, memory_store, memory_embedder, onnx_embedder) =
         storage::init_memory_system(&config, &mut context_engine).await;
 
-    // Initialize tool registry.
-    let tool_registry = subsystems::init_tool_registry().await;
+    // Capability prompts for WASM skills go to paired operator devices; the
+    // node system attaches its approval router once it starts.
+    #[cfg(feature = "node")]
+    let capability_approver = Arc::new(subsystems::NodeCapabilityApprover::default());
+    #[cfg(feature = "node")]
+    let skill_approver = config
+        .node
+        .enabled
+        .then(|| capability_approver.clone() as Arc<dyn blufio_skill::CapabilityApprover>);
+    #[cfg(not(feature = "node"))]
+    let skill_approver = None;
+
+    // Initialize tool registry (built-in tools and installed WASM skills).
+    let (tool_registry, skill_cancel_handle) =
+        subsystems::init_tool_registry(&config, skill_approver).await;
 
     // Create global event bus.
     let event_bus = subsystems::create_event_bus();
@@ -274,6 +302,11 @@ pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
     // Initialize Anthropic provider.
     let provider = gateway::init_provider(&config).await?;
 
+    let provider = subsystems::init_provider_interceptors(&config, provider);
+
+    // Content moderation, applied by the agent loop to user turns only.
+    let moderator = subsystems::init_moderator(&config);
+
     // Initialize Prometheus metrics.
     let prometheus_render = gateway::init_prometheus(&config);
 
@@ -329,7 +362,7 @@ pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
         &channel_result.imessage_webhook_state,
         &channel_result.sms_webhook_state,
         &event_bus,
-        &storage,
+        &agent_storage,
         &tool_registry,
         &memory_store,
         &resilience.manager,
@@ -437,6 +470,7 @@ pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
                                 serde_json::json!({"is_degradation_notification": true})
                                     .to_string(),
                             ),
+                            idempotency_key: None,
                         };
                         if let Err(e) = adapter.send(outbound).await {
                             tracing::warn!(
@@ -481,6 +515,7 @@ pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
                         reply_to: None,
                         parse_mode: None,
                         metadata: Some(serde_json::json!({"is_bridged": true}).to_string()),
+                        idempotency_key: None,
                     };
                     if let Err(e) = adapter.send(outbound).await {
                         warn!(
@@ -506,8 +541,20 @@ pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
         None
     };
 
-    // Initialize model router.
-    let router = Arc::new(ModelRouter::new(config.routing.clone()));
+    // Initialize model router, blending exemplar embeddings into
+    // classification when the local memory embedder is loaded. Embedding is
+    // synchronous ONNX inference, so the exemplars are embedded on the
+    // blocking pool; sessions route on it too.
+    let mut router = ModelRouter::new(config.routing.clone());
+    if let Some(embedder) = onnx_embedder.clone() {
+        router = tokio::task::spawn_blocking(move || {
+            router.with_embedder(Arc::new(move |text: &str| embedder.embed_text(text).ok()))
+        })
+        .await
+        .map_err(|e| BlufioError::Internal(format!("router spawn_blocking join: {e}")))?;
+        info!("query classification blends exemplar embeddings");
+    }
+    let router = Arc::new(router);
     if config.routing.enabled {
         if let Some(ref forced) = config.routing.force_model {
             info!(
@@ -531,15 +578,20 @@ pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
 
     // Wire multi-agent delegation (if enabled and agents configured).
     if config.delegation.enabled && !config.agents.is_empty() {
-        let delegation_router = Arc::new(DelegationRouter::new(
+        // Specialists get the same per-tool deadline as the primary agent.
+        let tool_timeout = (config.skill.tool_timeout_secs > 0)
+            .then(|| std::time::Duration::from_secs(config.skill.tool_timeout_secs));
+        let delegation_router = DelegationRouter::new(
             &config.agents,
             provider.clone(),
-            storage.clone() as Arc<dyn StorageAdapter + Send + Sync>,
+            agent_storage.clone(),
             cost_ledger.clone(),
             budget_tracker.clone(),
             router.clone(),
             config.delegation.timeout_secs,
-        ));
+        )
+        .with_tool_timeout(tool_timeout);
+        let delegation_router = Arc::new(delegation_router);
         let delegation_tool = DelegationTool::new(delegation_router);
         {
             let mut registry = tool_registry.write().await;
@@ -561,7 +613,7 @@ pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
         let runner = Arc::new(HeartbeatRunner::new(
             config.heartbeat.clone(),
             provider.clone(),
-            storage.clone(),
+            agent_storage.clone(),
             cost_ledger.clone(),
         ));
         info!(
@@ -596,6 +648,7 @@ pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
             config.node.approval.clone(),
         ));
         conn_manager.set_approval_router(approval_router.clone());
+        capability_approver.attach(approval_router.clone());
 
         {
             let approval_bus = event_bus.clone();
@@ -658,6 +711,42 @@ pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
         );
     }
 
+    // Spawn periodic WAL checkpoint task (Litestream manages checkpoints itself).
+    if config.storage.wal_mode
+        && config.storage.wal_checkpoint_interval_secs > 0
+        && !config.litestream.enabled
+    {
+        let interval = Duration::from_secs(config.storage.wal_checkpoint_interval_secs);
+        tokio::spawn(storage::wal_checkpoint_loop(
+            storage.clone(),
+            interval,
+            cancel.clone(),
+        ));
+        info!(
+            interval_secs = config.storage.wal_checkpoint_interval_secs,
+            "WAL checkpoint task started"
+        );
+    }
+
+    // Spawn scheduled checkpoint + VACUUM (skipped under Litestream, which
+    // owns checkpoints).
+    if config.storage.vacuum_interval_secs > 0 {
+        if config.litestream.enabled {
+            warn!("storage.vacuum_interval_secs is ignored while Litestream is enabled");
+        } else {
+            let interval = Duration::from_secs(config.storage.vacuum_interval_secs);
+            tokio::spawn(storage::vacuum_loop(
+                storage.clone(),
+                interval,
+                cancel.clone(),
+            ));
+            info!(
+                interval_secs = config.storage.vacuum_interval_secs,
+                "database maintenance task started"
+            );
+        }
+    }
+
     // Spawn sd_notify watchdog ping task.
     #[cfg(unix)]
     {
@@ -730,11 +819,21 @@ pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
     // Initialize injection defense pipeline (INJC-06).
     let injection_pipeline = subsystems::init_injection_pipeline(&config, &event_bus);
 
+    // PII redaction before persistence and memory extraction.
+    let pii_redactor = subsystems::init_pii_redactor(&config, vault);
+
+    // Speech-to-text for inbound voice messages (disabled without a command).
+    let transcriber =
+        blufio_agent::transcription::CommandTranscriber::from_config(&config.transcription);
+
+    // Text-to-speech for spoken replies (disabled without a command).
+    let synthesizer = subsystems::init_synthesizer(&config);
+
     // Create and run agent loop with channel multiplexer.
     let mut agent_loop = AgentLoop::new(
         Box::new(channel_result.mux),
         provider,
-        storage,
+        agent_storage,
         context_engine,
         cost_ledger,
         budget_tracker,
@@ -750,6 +849,11 @@ pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
     // Wire EventBus into AgentLoop.
     agent_loop.set_event_bus(event_bus.clone());
 
+    // Cancel in-flight WASM skills on shutdown.
+    if let Some(handle) = skill_cancel_handle {
+        agent_loop.add_skill_cancel_handle(handle);
+    }
+
     // Wire resilience subsystem into AgentLoop.
     if let Some(ref registry) = resilience.registry {
         agent_loop.set_circuit_breaker_registry(registry.clone());
@@ -774,6 +878,21 @@ pub async fn run_serve(config: BlufioConfig) -> Result<(), BlufioError> {
         agent_loop.set_injection_pipeline(pipeline.clone());
     }
 
+    // Wire voice transcription.
+    if let Some(transcriber) = transcriber {
+        info!("voice transcription enabled");
+        agent_loop.set_transcriber(Arc::new(transcriber));
+    }
+    if let Some(synthesizer) = synthesizer {
+        agent_loop.set_synthesizer(synthesizer);
+    }
+    if let Some(redactor) = pii_redactor {
+        agent_loop.set_pii_redactor(redactor);
+    }
+    if let Some(moderator) = moderator {
+        agent_loop.set_moderator(moderator);
+    }
+
     // Log integration status summary.
     {
         let security_status = "OK (TLS 1.2+ / SSRF protection)";
@@ -4,28 +4,54 @@
 //! Storage, cost ledger, tokenizer, context engine, and memory initialization
 //! for the `blufio serve` command.
 
+#[cfg(feature = "onnx")]
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use blufio_config::model::BlufioConfig;
 use blufio_context::ContextEngine;
-use blufio_core::StorageAdapter;
 use blufio_core::error::BlufioError;
 use blufio_core::token_counter::{TokenizerCache, TokenizerMode};
+use blufio_core::{EmbeddingAdapter, StorageAdapter};
 use blufio_cost::{BudgetTracker, CostLedger};
 use blufio_memory::{
-    HybridRetriever, MemoryExtractor, MemoryProvider, MemoryStore, ModelManager, OnnxEmbedder,
+    HybridRetriever, MemoryExtractor, MemoryProvider, MemoryStore, OnnxEmbedder, RemoteEmbedder,
 };
 use tracing::{debug, info, warn};
 
 #[cfg(feature = "sqlite")]
 use blufio_storage::SqliteStorage;
 
+/// Set the WAL auto-checkpoint threshold for every SQLite connection opened
+/// from here on, forced to 0 when Litestream controls checkpoints.
+pub(crate) fn configure_wal_autocheckpoint(config: &BlufioConfig) {
+    if !config.storage.wal_mode {
+        return;
+    }
+    let pages = if config.litestream.enabled {
+        0
+    } else {
+        config.storage.wal_autocheckpoint_pages
+    };
+    blufio_storage::set_wal_autocheckpoint_pages(pages);
+}
+
 /// Initialize SQLite storage (migrations included).
+///
+/// When Litestream is enabled, SQLite auto-checkpointing is forced off so
+/// Litestream stays in control of WAL checkpoints. The `[retention]` policy
+/// is attached when enabled, for the agent loop's periodic purge.
 pub(crate) async fn init_storage(config: &BlufioConfig) -> Result<Arc<SqliteStorage>, BlufioError> {
     #[cfg(feature = "sqlite")]
     {
-        let storage = SqliteStorage::new(config.storage.clone());
+        let mut storage_config = config.storage.clone();
+        if config.litestream.enabled {
+            storage_config.wal_autocheckpoint_pages = 0;
+        }
+        let mut storage = SqliteStorage::new(storage_config);
+        if config.retention.enabled {
+            storage = storage.with_retention(config.retention.clone());
+        }
         storage.initialize().await?;
         Ok(Arc::new(storage))
     }
@@ -34,6 +60,100 @@ pub(crate) async fn init_storage(config: &BlufioConfig) -> Result<Arc<SqliteStor
     compile_error!("blufio requires the 'sqlite' feature for storage");
 }
 
+/// Select the storage used by the agent loop, delegation and heartbeats.
+///
+/// With `storage.backend = "redis"`, sessions, messages and the queue live in
+/// Redis so several replicas can share them; cost, memory and the gateway
+/// keep using SQLite. Otherwise the SQLite storage is returned.
+pub(crate) async fn init_agent_storage(
+    config: &BlufioConfig,
+    sqlite: &Arc<SqliteStorage>,
+) -> Result<Arc<dyn StorageAdapter + Send + Sync>, BlufioError> {
+    if config.storage.backend != "redis" {
+        return Ok(sqlite.clone());
+    }
+
+    #[cfg(feature = "redis")]
+    {
+        let storage = blufio_redis_storage::RedisStorage::new(config.storage.redis.clone());
+        storage.initialize().await?;
+        info!(
+            key_prefix = config.storage.redis.key_prefix.as_str(),
+            pool_size = config.storage.redis.pool_size,
+            "Redis storage backend active"
+        );
+        Ok(Arc::new(storage))
+    }
+
+    #[cfg(not(feature = "redis"))]
+    Err(BlufioError::Config(
+        "storage.backend = \"redis\" requires blufio built with the 'redis' feature".to_string(),
+    ))
+}
+
+/// Periodically checkpoint and truncate the WAL until cancelled.
+///
+/// Complements SQLite's page-count auto-checkpoint for bursty workloads where
+/// the WAL can grow large between quiet periods. Failures are logged and the
+/// loop keeps running.
+#[cfg(feature = "sqlite")]
+pub(crate) async fn wal_checkpoint_loop(
+    storage: Arc<SqliteStorage>,
+    interval: std::time::Duration,
+    cancel: tokio_util::sync::CancellationToken,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                if let Err(e) = storage.checkpoint().await {
+                    warn!(error = %e, "periodic WAL checkpoint failed");
+                }
+            }
+            _ = cancel.cancelled() => {
+                debug!("WAL checkpoint task shutting down");
+                break;
+            }
+        }
+    }
+}
+
+/// Periodically checkpoint and VACUUM the database until cancelled.
+///
+/// The scheduled form of `blufio maintain`. The first run waits a full
+/// interval so startup is never slowed by a VACUUM.
+#[cfg(feature = "sqlite")]
+pub(crate) async fn vacuum_loop(
+    storage: Arc<SqliteStorage>,
+    interval: std::time::Duration,
+    cancel: tokio_util::sync::CancellationToken,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                match storage.maintain().await {
+                    Ok(report) => info!(
+                        bytes_before = report.bytes_before,
+                        bytes_after = report.bytes_after,
+                        bytes_reclaimed = report.bytes_reclaimed(),
+                        "scheduled database maintenance complete"
+                    ),
+                    Err(e) => warn!(error = %e, "scheduled database maintenance failed"),
+                }
+            }
+            _ = cancel.cancelled() => {
+                debug!("database maintenance task shutting down");
+                break;
+            }
+        }
+    }
+}
+
 /// Apply Litestream WAL pragma and warn about SQLCipher incompatibility.
 pub(crate) async fn apply_litestream_pragma(config: &BlufioConfig) -> Result<(), BlufioError> {
     if config.litestream.enabled {
@@ -79,6 +199,19 @@ pub(crate) async fn mark_stale_sessions(storage: &dyn StorageAdapter) -> Result<
     Ok(())
 }
 
+/// Return queue entries interrupted mid-processing to pending (crash recovery).
+pub(crate) async fn recover_queue(storage: &dyn StorageAdapter) -> Result<(), BlufioError> {
+    let recovery = storage.recover_in_flight_queue().await?;
+    if recovery.requeued > 0 || recovery.dead_lettered > 0 {
+        info!(
+            requeued = recovery.requeued,
+            dead_lettered = recovery.dead_lettered,
+            "recovered in-flight queue entries"
+        );
+    }
+    Ok(())
+}
+
 /// Initialize cost ledger and budget tracker.
 pub(crate) async fn init_cost_tracking(
     config: &BlufioConfig,
@@ -128,11 +261,97 @@ pub(crate) async fn init_context_engine(
     Ok(context_engine)
 }
 
-/// Initialize the memory system: downloads model, creates embedder, store,
-/// retriever, provider, and extractor. Registers the provider with ContextEngine.
+/// Creates the memory embedder: the remote API when configured, otherwise
+/// the local ONNX model (downloaded on first run).
 ///
-/// Returns (MemoryProvider, MemoryExtractor, MemoryStore, OnnxEmbedder) on success.
-#[allow(dead_code)]
+/// Also returns the ONNX embedder itself when used, for callers that need
+/// synchronous embedding. Without the `onnx` feature only the remote API is
+/// available.
+pub(crate) async fn init_embedder(
+    config: &BlufioConfig,
+) -> Result<
+    (
+        Arc<dyn EmbeddingAdapter + Send + Sync>,
+        Option<Arc<OnnxEmbedder>>,
+    ),
+    BlufioError,
+> {
+    if let Some(remote) = RemoteEmbedder::from_config(&config.memory.remote_embedding)? {
+        info!(endpoint = remote.endpoint(), "using remote embedding API");
+        return Ok((Arc::new(remote), None));
+    }
+
+    #[cfg(feature = "onnx")]
+    {
+        // Determine data directory (parent of the database path).
+        let db_path = PathBuf::from(&config.storage.database_path);
+        let data_dir = db_path
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        // Download model on first run (never in offline mode).
+        let mut model_manager = blufio_memory::ModelManager::new(data_dir)
+            .with_offline(config.offline)
+            .with_progress(model_download_progress());
+        if let Some(dir) = &config.memory.model_dir {
+            model_manager = model_manager.with_model_dir(PathBuf::from(dir));
+        }
+        info!("ensuring embedding model is available...");
+        let model_path = model_manager.ensure_model().await?;
+        info!(path = %model_path.display(), "embedding model ready");
+
+        // Create ONNX embedder.
+        let onnx = Arc::new(OnnxEmbedder::new(&model_path)?);
+        Ok((onnx.clone(), Some(onnx)))
+    }
+
+    #[cfg(not(feature = "onnx"))]
+    Err(BlufioError::Config(
+        "local embeddings need the onnx feature -- configure [memory.remote_embedding] instead"
+            .to_string(),
+    ))
+}
+
+/// Progress bars on stderr for the first-run model download, one per file.
+/// Hidden when stderr is not a terminal.
+#[cfg(feature = "onnx")]
+fn model_download_progress() -> blufio_memory::ProgressCallback {
+    let style = indicatif::ProgressStyle::with_template(
+        "  {prefix:<16} [{bar:30.cyan/dim}] {bytes}/{total_bytes}",
+    )
+    .unwrap_or_else(|_| indicatif::ProgressStyle::default_bar());
+    let current = std::sync::Mutex::new(None::<(String, indicatif::ProgressBar)>);
+    Arc::new(move |progress: &blufio_memory::DownloadProgress| {
+        let Ok(mut current) = current.lock() else {
+            return;
+        };
+        if current
+            .as_ref()
+            .is_none_or(|(name, _)| *name != progress.filename)
+        {
+            if let Some((_, bar)) = current.take() {
+                bar.finish();
+            }
+            let bar = indicatif::ProgressBar::new(progress.total.unwrap_or(0))
+                .with_style(style.clone())
+                .with_prefix(progress.filename.clone());
+            *current = Some((progress.filename.clone(), bar));
+        }
+        if let Some((_, bar)) = current.as_ref() {
+            bar.set_position(progress.downloaded);
+            if progress.total == Some(progress.downloaded) {
+                bar.finish();
+            }
+        }
+    })
+}
+
+/// Initialize the memory system: creates embedder, store, retriever,
+/// provider, and extractor. Registers the provider with ContextEngine.
+///
+/// Returns (MemoryProvider, MemoryExtractor, MemoryStore, embedder, ONNX
+/// embedder when local) on success.
 pub(crate) async fn initialize_memory(
     config: &BlufioConfig,
     context_engine: &mut ContextEngine,
@@ -141,25 +360,12 @@ pub(crate) async fn initialize_memory(
         MemoryProvider,
         Arc<MemoryExtractor>,
         Arc<MemoryStore>,
-        Arc<OnnxEmbedder>,
+        Arc<dyn EmbeddingAdapter + Send + Sync>,
+        Option<Arc<OnnxEmbedder>>,
     ),
     BlufioError,
 > {
-    // Determine data directory (parent of the database path).
-    let db_path = PathBuf::from(&config.storage.database_path);
-    let data_dir = db_path
-        .parent()
-        .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| PathBuf::from("."));
-
-    // Download model on first run.
-    let model_manager = ModelManager::new(data_dir);
-    info!("ensuring embedding model is available...");
-    let model_path = model_manager.ensure_model().await?;
-    info!(path = %model_path.display(), "embedding model ready");
-
-    // Create ONNX embedder.
-    let embedder = Arc::new(OnnxEmbedder::new(&model_path)?);
+    let (embedder, onnx_embedder) = init_embedder(config).await?;
 
     // Register sqlite-vec extension before opening the connection (must be
     // called before any connections so sqlite3_auto_extension takes effect).
@@ -188,6 +394,9 @@ pub(crate) async fn initialize_memory(
         }
     }
 
+    // Refuse an embedder whose vectors don't match the stored ones.
+    let embedder = blufio_memory::embedder::verify_dimensions(embedder, &memory_store).await?;
+
     // Create hybrid retriever.
     let retriever = Arc::new(HybridRetriever::new(
         memory_store.clone(),
@@ -196,18 +405,28 @@ pub(crate) async fn initialize_memory(
     ));
 
     // Create memory provider and register with context engine.
-    let memory_provider = MemoryProvider::new(retriever);
+    let memory_provider =
+        MemoryProvider::new(retriever).with_placement(&config.memory.injection_placement);
     context_engine.add_conditional_provider(Box::new(memory_provider.clone()));
 
     // Create memory extractor.
-    let extractor = Arc::new(MemoryExtractor::new(
-        memory_store.clone(),
-        embedder.clone(),
-        config.memory.extraction_model.clone(),
-    ));
+    let extractor = Arc::new(
+        MemoryExtractor::new(
+            memory_store.clone(),
+            embedder.clone(),
+            config.memory.extraction_model.clone(),
+        )
+        .with_dedup(config.memory.dedup_threshold, &config.memory.dedup_strategy),
+    );
 
     info!("memory system initialized");
-    Ok((memory_provider, extractor, memory_store, embedder))
+    Ok((
+        memory_provider,
+        extractor,
+        memory_store,
+        embedder,
+        onnx_embedder,
+    ))
 }
 
 /// Initialize the memory system, returning tuple of optional components.
@@ -218,32 +437,145 @@ pub(crate) async fn init_memory_system(
     Option<MemoryProvider>,
     Option<Arc<MemoryExtractor>>,
     Option<Arc<MemoryStore>>,
+    Option<Arc<dyn EmbeddingAdapter + Send + Sync>>,
     Option<Arc<OnnxEmbedder>>,
 ) {
-    #[cfg(feature = "onnx")]
-    let result = if config.memory.enabled {
-        match initialize_memory(config, context_engine).await {
-            Ok((mp, me, ms, emb)) => (Some(mp), Some(me), Some(ms), Some(emb)),
-            Err(e) => {
-                warn!(error = %e, "memory system initialization failed, continuing without memory");
-                (None, None, None, None)
-            }
-        }
-    } else {
+    if !config.memory.enabled {
         info!("memory system disabled by configuration");
-        (None, None, None, None)
-    };
+        return (None, None, None, None, None);
+    }
 
-    #[cfg(not(feature = "onnx"))]
-    let result: (
-        Option<MemoryProvider>,
-        Option<Arc<MemoryExtractor>>,
-        Option<Arc<MemoryStore>>,
-        Option<Arc<OnnxEmbedder>>,
-    ) = {
-        info!("memory system disabled (onnx feature not enabled)");
-        (None, None, None, None)
-    };
+    match initialize_memory(config, context_engine).await {
+        Ok((mp, me, ms, emb, onnx)) => (Some(mp), Some(me), Some(ms), Some(emb), onnx),
+        Err(e) => {
+            warn!(error = %e, "memory system initialization failed, continuing without memory");
+            (None, None, None, None, None)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn wal_checkpoint_loop_truncates_wal() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("serve-ckpt.db");
+        let wal_path = dir.path().join("serve-ckpt.db-wal");
+        let storage = SqliteStorage::new(blufio_config::model::StorageConfig {
+            database_path: db_path.to_string_lossy().into_owned(),
+            wal_autocheckpoint_pages: 0,
+            ..Default::default()
+        });
+        storage.initialize().await.unwrap();
+        let storage = Arc::new(storage);
+
+        for i in 0..50 {
+            storage
+                .enqueue("ckpt", &"x".repeat(1024 + i))
+                .await
+                .unwrap();
+        }
+        let before = std::fs::metadata(&wal_path).unwrap().len();
+        assert!(before > 0);
 
-    result
+        let cancel = tokio_util::sync::CancellationToken::new();
+        let task = tokio::spawn(wal_checkpoint_loop(
+            storage.clone(),
+            std::time::Duration::from_millis(20),
+            cancel.clone(),
+        ));
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        cancel.cancel();
+        task.await.unwrap();
+
+        let after = std::fs::metadata(&wal_path).map(|m| m.len()).unwrap_or(0);
+        assert!(after < before, "periodic checkpoint should shrink the WAL");
+    }
+
+    #[tokio::test]
+    async fn vacuum_loop_shrinks_database_after_deletes() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("serve-vacuum.db");
+        let storage = SqliteStorage::new(blufio_config::model::StorageConfig {
+            database_path: db_path.to_string_lossy().into_owned(),
+            ..Default::default()
+        });
+        storage.initialize().await.unwrap();
+        let storage = Arc::new(storage);
+
+        storage
+            .create_session(&blufio_core::types::Session {
+                id: "s1".to_string(),
+                channel: "cli".to_string(),
+                user_id: None,
+                state: "active".to_string(),
+                metadata: None,
+                created_at: "2026-01-01T00:00:00Z".to_string(),
+                updated_at: "2026-01-01T00:00:00Z".to_string(),
+                classification: Default::default(),
+            })
+            .await
+            .unwrap();
+        let messages: Vec<_> = (0..200)
+            .map(|i| blufio_core::types::Message {
+                id: format!("m{i}"),
+                session_id: "s1".to_string(),
+                role: "user".to_string(),
+                content: "x".repeat(4096),
+                token_count: None,
+                metadata: None,
+                created_at: "2026-01-01T00:00:01Z".to_string(),
+                classification: Default::default(),
+            })
+            .collect();
+        storage.insert_messages(&messages).await.unwrap();
+        storage.checkpoint().await.unwrap();
+        let before = std::fs::metadata(&db_path).unwrap().len();
+        let ids: Vec<String> = messages.into_iter().map(|m| m.id).collect();
+        storage.delete_messages_by_ids("s1", &ids).await.unwrap();
+
+        let cancel = tokio_util::sync::CancellationToken::new();
+        let task = tokio::spawn(vacuum_loop(
+            storage.clone(),
+            std::time::Duration::from_millis(20),
+            cancel.clone(),
+        ));
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        cancel.cancel();
+        task.await.unwrap();
+
+        let after = std::fs::metadata(&db_path).unwrap().len();
+        assert!(after < before, "scheduled VACUUM should shrink the file");
+    }
+
+    #[tokio::test]
+    async fn startup_recovers_in_flight_queue_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = blufio_config::model::StorageConfig {
+            database_path: dir
+                .path()
+                .join("serve-queue.db")
+                .to_string_lossy()
+                .into_owned(),
+            ..Default::default()
+        };
+
+        // A previous run dequeued an entry and died before acking it.
+        let crashed = SqliteStorage::new(config.clone());
+        crashed.initialize().await.unwrap();
+        let id = crashed.enqueue("inbound", "hello").await.unwrap();
+        assert_eq!(crashed.dequeue("inbound").await.unwrap().unwrap().id, id);
+        assert!(crashed.dequeue("inbound").await.unwrap().is_none());
+        drop(crashed);
+
+        let storage = SqliteStorage::new(config);
+        storage.initialize().await.unwrap();
+        recover_queue(&storage).await.unwrap();
+
+        let entry = storage.dequeue("inbound").await.unwrap().unwrap();
+        assert_eq!(entry.id, id);
+        assert_eq!(entry.attempts, 1);
+    }
 }
@@ -13,9 +13,10 @@ use std::time::Duration;
 
 use blufio_config::model::BlufioConfig;
 use blufio_core::error::BlufioError;
+use blufio_core::{EmbeddingAdapter, ModerationAdapter, ProviderAdapter, TtsAdapter};
 use blufio_cron::CronScheduler;
 use blufio_hooks::HookManager;
-use blufio_memory::{MemoryStore, OnnxEmbedder};
+use blufio_memory::MemoryStore;
 use blufio_plugin::{PluginRegistry, PluginStatus, builtin_catalog};
 use blufio_resilience::{
     CircuitBreakerConfig, CircuitBreakerRegistry, DegradationManager, EscalationConfig,
@@ -46,23 +47,28 @@ pub(crate) fn initialize_plugin_registry(config: &BlufioConfig) -> PluginRegistr
     registry
 }
 
-/// Perform vault startup check and register config secrets for log redaction.
+/// Perform vault startup check, resolve unset config secrets from the vault,
+/// and register config secrets for log redaction.
+///
+/// Returns the unlocked vault, if one exists.
 pub(crate) async fn vault_and_secret_redaction(
-    config: &BlufioConfig,
+    config: &mut BlufioConfig,
     vault_values: &std::sync::Arc<std::sync::RwLock<Vec<String>>>,
-) -> Result<(), BlufioError> {
+) -> Result<Option<blufio_vault::Vault>, BlufioError> {
     // SEC-03: Vault startup check -- unlock vault if it exists so secrets
     // are available for provider initialization. Silent no-op when no vault.
-    {
+    let vault = {
         let vault_conn = blufio_storage::open_connection(&config.storage.database_path).await?;
         match blufio_vault::vault_startup_check(vault_conn, &config.vault).await {
-            Ok(Some(_vault)) => {
+            Ok(Some(vault)) => {
                 info!("vault unlocked -- secrets available");
                 #[cfg(unix)]
                 blufio_agent::sdnotify::notify_status("Initializing: vault unlocked");
+                Some(vault)
             }
             Ok(None) => {
                 debug!("no vault found -- skipping vault startup check");
+                None
             }
             Err(e) => {
                 error!(error = %e, "vault startup check failed");
@@ -73,26 +79,20 @@ pub(crate) async fn vault_and_secret_redaction(
                 return Err(e);
             }
         }
+    };
+
+    // Fill secrets stored via `blufio config set-secret` that the config
+    // leaves unset (precedence: config -> vault -> env).
+    if let Some(ref vault) = vault {
+        blufio_vault::apply_vault_secrets(config, vault).await?;
     }
 
     // Register known config secrets for log redaction (SEC-08).
     {
-        if let Some(ref key) = config.anthropic.api_key {
-            blufio_security::RedactingWriter::<std::io::Stderr>::add_vault_value(
-                vault_values,
-                key.clone(),
-            );
-        }
-        if let Some(ref token) = config.telegram.bot_token {
+        for secret in blufio_vault::configured_secrets(config) {
             blufio_security::RedactingWriter::<std::io::Stderr>::add_vault_value(
                 vault_values,
-                token.clone(),
-            );
-        }
-        if let Some(ref token) = config.gateway.bearer_token {
-            blufio_security::RedactingWriter::<std::io::Stderr>::add_vault_value(
-                vault_values,
-                token.clone(),
+                secret,
             );
         }
         let secret_count = vault_values.read().map(|v| v.len()).unwrap_or(0);
@@ -101,7 +101,74 @@ pub(crate) async fn vault_and_secret_redaction(
         }
     }
 
-    Ok(())
+    Ok(vault)
+}
+
+/// Build the moderation backend, or `None` when no terms are blocked.
+pub(crate) fn init_moderator(
+    config: &BlufioConfig,
+) -> Option<Arc<dyn ModerationAdapter + Send + Sync>> {
+    let moderator = blufio_agent::moderation::KeywordModerator::from_config(&config.moderation)?;
+    info!(
+        terms = config.moderation.blocked_terms.len(),
+        "keyword moderation enabled"
+    );
+    Some(Arc::new(moderator))
+}
+
+/// Build the speech synthesizer for spoken replies, or `None` without a
+/// `[tts]` command.
+pub(crate) fn init_synthesizer(config: &BlufioConfig) -> Option<Arc<dyn TtsAdapter + Send + Sync>> {
+    let synthesizer = blufio_agent::synthesis::CommandSynthesizer::from_config(&config.tts)?;
+    info!("spoken voice replies enabled");
+    Some(Arc::new(synthesizer))
+}
+
+/// Wrap the LLM provider in the interceptor chain for the configured
+/// cross-cutting behavior: PII redaction of outgoing requests. Returns the
+/// provider unwrapped when no interceptor applies.
+///
+/// Moderation is not installed here: the agent loop moderates user input and
+/// streamed replies itself, and the remaining `complete` calls on this shared
+/// provider are internal (compaction, memory extraction, heartbeat and
+/// delegation), which must not fail on a moderation verdict.
+pub(crate) fn init_provider_interceptors(
+    config: &BlufioConfig,
+    provider: Arc<dyn ProviderAdapter + Send + Sync>,
+) -> Arc<dyn ProviderAdapter + Send + Sync> {
+    use blufio_agent::interceptor::{InterceptedProvider, RedactionInterceptor};
+
+    let mut intercepted = InterceptedProvider::new(provider.clone());
+    if config.pii_redaction.provider_requests {
+        intercepted = intercepted.with_interceptor(Arc::new(RedactionInterceptor));
+    }
+    if intercepted.is_empty() {
+        return provider;
+    }
+    info!(count = intercepted.len(), "provider interceptors installed");
+    Arc::new(intercepted)
+}
+
+/// Build the PII redactor for stored messages, or `None` when disabled.
+pub(crate) fn init_pii_redactor(
+    config: &BlufioConfig,
+    vault: Option<blufio_vault::Vault>,
+) -> Option<Arc<blufio_agent::redaction::PiiRedactor>> {
+    let redactor = blufio_agent::redaction::PiiRedactor::from_config(&config.pii_redaction)?;
+    let redactor = match vault {
+        Some(vault) if config.pii_redaction.keep_encrypted_original => {
+            redactor.with_vault(Arc::new(vault))
+        }
+        None if config.pii_redaction.keep_encrypted_original => {
+            warn!(
+                "pii_redaction.keep_encrypted_original needs a vault; originals will be discarded"
+            );
+            redactor
+        }
+        _ => redactor,
+    };
+    info!("PII redaction enabled for stored messages");
+    Some(Arc::new(redactor))
 }
 
 /// Create the global event bus.
@@ -378,15 +445,125 @@ pub(crate) async fn init_resilience(
     }
 }
 
-/// Initialize tool registry with built-in tools.
-pub(crate) async fn init_tool_registry() -> Arc<tokio::sync::RwLock<ToolRegistry>> {
-    let mut tool_registry = ToolRegistry::new();
-    blufio_skill::builtin::register_builtins(&mut tool_registry);
+/// Initialize tool registry with built-in tools and installed WASM skills.
+///
+/// Also returns a handle that cancels in-flight skill invocations, when
+/// skills are enabled.
+pub(crate) async fn init_tool_registry(
+    config: &BlufioConfig,
+    capability_approver: Option<Arc<dyn blufio_skill::CapabilityApprover>>,
+) -> (
+    Arc<tokio::sync::RwLock<ToolRegistry>>,
+    Option<blufio_skill::SkillCancelHandle>,
+) {
+    let mut tool_registry = blufio_skill::builtin::builtin_registry(&config.skill);
     info!(
         "tool registry initialized with {} built-in tools",
         tool_registry.len()
     );
-    Arc::new(tokio::sync::RwLock::new(tool_registry))
+    let cancel_handle = if config.skill.enabled {
+        register_installed_skills(config, &mut tool_registry, capability_approver).await
+    } else {
+        None
+    };
+    (
+        Arc::new(tokio::sync::RwLock::new(tool_registry)),
+        cancel_handle,
+    )
+}
+
+/// Load installed WASM skills into a sandbox runtime and register one tool
+/// per skill. Failures are logged; the agent starts without skill tools.
+async fn register_installed_skills(
+    config: &BlufioConfig,
+    tool_registry: &mut ToolRegistry,
+    capability_approver: Option<Arc<dyn blufio_skill::CapabilityApprover>>,
+) -> Option<blufio_skill::SkillCancelHandle> {
+    let conn = match blufio_storage::open_connection(&config.storage.database_path).await {
+        Ok(conn) => conn,
+        Err(e) => {
+            warn!(error = %e, "failed to open skill store, skipping installed skills");
+            return None;
+        }
+    };
+    let store = blufio_skill::SkillStore::new(Arc::new(conn));
+    let mut runtime = match blufio_skill::WasmSkillRuntime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            warn!(error = %e, "failed to create WASM skill runtime, skipping installed skills");
+            return None;
+        }
+    };
+    if config.skill.require_verified {
+        runtime.require_trusted_publishers(config.skill.trusted_publishers.clone());
+    }
+    match blufio_skill::load_installed_skills(&store, &mut runtime).await {
+        Ok(count) => info!(count, "installed skills loaded"),
+        Err(e) => {
+            warn!(error = %e, "failed to list installed skills");
+            return None;
+        }
+    }
+    let cancel_handle = runtime.cancel_handle();
+    let mut runtimes = blufio_skill::SkillRuntimes::new();
+    match capability_approver {
+        Some(approver) if config.skill.capability_prompts => {
+            let flow = blufio_skill::CapabilityGrantFlow::new(
+                Arc::new(tokio::sync::RwLock::new(runtime)),
+                approver,
+            )
+            .with_store(Arc::new(store))
+            .with_prompts_enabled(true);
+            info!("skill capability prompts enabled");
+            runtimes.register("wasm", Arc::new(flow));
+        }
+        None if config.skill.capability_prompts => {
+            warn!("skill.capability_prompts needs the node system; prompts disabled");
+            runtimes.register("wasm", Arc::new(runtime));
+        }
+        _ => runtimes.register("wasm", Arc::new(runtime)),
+    }
+    blufio_skill::register_skill_tools(tool_registry, &Arc::new(runtimes));
+    Some(cancel_handle)
+}
+
+/// Asks paired operator devices (the node system) whether a WASM skill may
+/// have a capability it was denied. Declines until the node system attaches
+/// its approval router.
+#[cfg(feature = "node")]
+#[derive(Default)]
+pub(crate) struct NodeCapabilityApprover {
+    router: std::sync::OnceLock<Arc<blufio_node::ApprovalRouter>>,
+}
+
+#[cfg(feature = "node")]
+impl NodeCapabilityApprover {
+    /// Sends prompts through `router` from now on.
+    pub(crate) fn attach(&self, router: Arc<blufio_node::ApprovalRouter>) {
+        let _ = self.router.set(router);
+    }
+}
+
+#[cfg(feature = "node")]
+#[async_trait::async_trait]
+impl blufio_skill::CapabilityApprover for NodeCapabilityApprover {
+    async fn approve(
+        &self,
+        skill_name: &str,
+        _request: &blufio_skill::CapabilityRequest,
+        prompt: &str,
+    ) -> Result<bool, BlufioError> {
+        let Some(router) = self.router.get() else {
+            warn!(skill = %skill_name, "node system not running, declining capability request");
+            return Ok(false);
+        };
+        let outcome = router
+            .request_approval("skill.capability", prompt)
+            .await
+            .map_err(BlufioError::skill_execution_failed)?;
+        // A dropped sender means the request was never resolved.
+        Ok(outcome.await.is_ok_and(|outcome| outcome.approved))
+    }
 }
 
 /// Redact MCP server auth tokens and prepare injection classifier for MCP.
@@ -437,7 +614,7 @@ pub(crate) async fn register_context_providers(
 pub(crate) async fn spawn_memory_tasks(
     config: &BlufioConfig,
     memory_store: &Option<Arc<MemoryStore>>,
-    memory_embedder: &Option<Arc<OnnxEmbedder>>,
+    memory_embedder: &Option<Arc<dyn EmbeddingAdapter + Send + Sync>>,
     event_bus: &Arc<blufio_bus::EventBus>,
     cancel: &tokio_util::sync::CancellationToken,
 ) {
@@ -468,7 +645,7 @@ pub(crate) async fn spawn_memory_tasks(
             match blufio_memory::watcher::initial_scan(
                 &config.memory.file_watcher,
                 store,
-                embedder_arc,
+                embedder_arc.as_ref(),
             )
             .await
             {
@@ -766,3 +943,17 @@ fn read_rss_bytes() -> Option<u64> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn synthesizer_follows_tts_config() {
+        let mut config = BlufioConfig::default();
+        assert!(init_synthesizer(&config).is_none());
+
+        config.tts.command = Some("piper".to_string());
+        assert!(init_synthesizer(&config).is_some());
+    }
+}
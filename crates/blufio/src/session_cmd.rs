@@ -0,0 +1,219 @@
+// SPDX-FileCopyrightText: 2026 Blufio Contributors
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! `blufio session` CLI subcommands.
+//!
+//! `fork` branches a conversation: it copies a session's messages up to and
+//! including a chosen message into a new session, so an alternative can be
+//! explored there while the original thread stays untouched.
+
+use blufio_config::model::BlufioConfig;
+use blufio_core::StorageAdapter;
+use blufio_core::error::BlufioError;
+use blufio_core::types::{Message, Session};
+use blufio_storage::SqliteStorage;
+use clap::Subcommand;
+
+/// Session management subcommands.
+#[derive(Subcommand, Debug)]
+pub enum SessionCommand {
+    /// Copy a session's history up to a message into a new session.
+    Fork {
+        /// Session ID to fork.
+        id: String,
+        /// Last message to copy into the new session.
+        #[arg(long = "from")]
+        from: String,
+    },
+}
+
+/// Run the `blufio session` subcommand.
+pub async fn run_session(
+    config: &BlufioConfig,
+    command: SessionCommand,
+) -> Result<(), BlufioError> {
+    match command {
+        SessionCommand::Fork { id, from } => {
+            let storage = SqliteStorage::new(config.storage.clone());
+            storage.initialize().await?;
+
+            let (fork, copied) = fork_session(&storage, &id, &from).await?;
+            println!("Forked session {id} at message {from} into {}", fork.id);
+            println!("  Messages copied: {copied}");
+            Ok(())
+        }
+    }
+}
+
+/// Creates a new session holding copies of `session_id`'s messages up to and
+/// including `from_message_id`.
+///
+/// The fork keeps the source's channel and user, and records its origin in
+/// the session metadata. The session and its messages are written in one
+/// transaction, so a failure leaves no partial fork. Returns the new session
+/// and the number of messages copied.
+pub async fn fork_session(
+    storage: &dyn StorageAdapter,
+    session_id: &str,
+    from_message_id: &str,
+) -> Result<(Session, usize), BlufioError> {
+    let source = storage
+        .get_session(session_id)
+        .await?
+        .ok_or_else(|| BlufioError::Internal(format!("session '{session_id}' not found")))?;
+
+    let messages = storage.get_messages(session_id, None).await?;
+    let Some(end) = messages.iter().position(|m| m.id == from_message_id) else {
+        return Err(BlufioError::Internal(format!(
+            "message '{from_message_id}' does not belong to session '{session_id}'"
+        )));
+    };
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let fork = Session {
+        id: uuid::Uuid::new_v4().to_string(),
+        channel: source.channel,
+        user_id: source.user_id,
+        state: "active".to_string(),
+        metadata: Some(
+            serde_json::json!({
+                "forked_from": session_id,
+                "fork_point": from_message_id,
+            })
+            .to_string(),
+        ),
+        created_at: now.clone(),
+        updated_at: now,
+        classification: source.classification,
+    };
+
+    // Original timestamps keep the copied history in order.
+    let copies: Vec<Message> = messages[..=end]
+        .iter()
+        .map(|message| Message {
+            id: uuid::Uuid::new_v4().to_string(),
+            session_id: fork.id.clone(),
+            ..message.clone()
+        })
+        .collect();
+    storage.create_session_with_messages(&fork, &copies).await?;
+
+    Ok((fork, copies.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn storage() -> (SqliteStorage, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = SqliteStorage::new(blufio_config::model::StorageConfig {
+            database_path: dir.path().join("test.db").to_string_lossy().to_string(),
+            ..Default::default()
+        });
+        storage.initialize().await.unwrap();
+        (storage, dir)
+    }
+
+    fn session(id: &str) -> Session {
+        Session {
+            id: id.to_string(),
+            channel: "cli".to_string(),
+            user_id: Some("local".to_string()),
+            state: "active".to_string(),
+            metadata: None,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: "2026-01-01T00:00:00Z".to_string(),
+            classification: Default::default(),
+        }
+    }
+
+    fn message(id: &str, session_id: &str, n: u32, content: &str) -> Message {
+        Message {
+            id: id.to_string(),
+            session_id: session_id.to_string(),
+            role: if n % 2 == 0 { "user" } else { "assistant" }.to_string(),
+            content: content.to_string(),
+            token_count: None,
+            metadata: None,
+            created_at: format!("2026-01-01T00:00:{n:02}Z"),
+            classification: Default::default(),
+        }
+    }
+
+    async fn contents(storage: &SqliteStorage, session_id: &str) -> Vec<String> {
+        storage
+            .get_messages(session_id, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.content)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn fork_copies_prefix_and_sessions_diverge() {
+        let (storage, _dir) = storage().await;
+        storage.create_session(&session("main")).await.unwrap();
+        for (n, content) in ["plan a trip", "Paris?", "somewhere warmer", "Lisbon?"]
+            .iter()
+            .enumerate()
+        {
+            let n = n as u32;
+            storage
+                .insert_message(&message(&format!("m{n}"), "main", n, content))
+                .await
+                .unwrap();
+        }
+
+        let (fork, copied) = fork_session(&storage, "main", "m1").await.unwrap();
+        assert_eq!(copied, 2);
+        assert_ne!(fork.id, "main");
+        assert!(fork.metadata.unwrap().contains("\"forked_from\":\"main\""));
+        assert_eq!(
+            contents(&storage, &fork.id).await,
+            ["plan a trip", "Paris?"]
+        );
+
+        storage
+            .insert_message(&message("f2", &fork.id, 10, "what about Rome?"))
+            .await
+            .unwrap();
+        storage
+            .insert_message(&message("m4", "main", 10, "book Lisbon"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            contents(&storage, &fork.id).await,
+            ["plan a trip", "Paris?", "what about Rome?"]
+        );
+        assert_eq!(
+            contents(&storage, "main").await,
+            [
+                "plan a trip",
+                "Paris?",
+                "somewhere warmer",
+                "Lisbon?",
+                "book Lisbon"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn fork_rejects_message_from_another_session() {
+        let (storage, _dir) = storage().await;
+        storage.create_session(&session("a")).await.unwrap();
+        storage.create_session(&session("b")).await.unwrap();
+        storage
+            .insert_message(&message("b0", "b", 0, "hello"))
+            .await
+            .unwrap();
+
+        let err = fork_session(&storage, "a", "b0").await.unwrap_err();
+        assert!(err.to_string().contains("does not belong"));
+        assert_eq!(storage.list_sessions(None).await.unwrap().len(), 2);
+
+        assert!(fork_session(&storage, "missing", "b0").await.is_err());
+    }
+}
@@ -7,7 +7,6 @@
 //! and readline history. Uses the three-zone context engine and records
 //! costs for every LLM call. Creates a new session per invocation.
 
-use std::path::PathBuf;
 use std::sync::Arc;
 
 use blufio_anthropic::AnthropicProvider;
@@ -22,9 +21,7 @@ use blufio_core::types::{
 use blufio_core::{ProviderAdapter, StorageAdapter};
 use blufio_cost::ledger::{CostRecord, FeatureType};
 use blufio_cost::{BudgetTracker, CostLedger, pricing};
-use blufio_memory::{
-    HybridRetriever, MemoryExtractor, MemoryProvider, MemoryStore, ModelManager, OnnxEmbedder,
-};
+use blufio_memory::{HybridRetriever, MemoryExtractor, MemoryProvider, MemoryStore};
 use blufio_router::ModelRouter;
 use blufio_skill::{SkillProvider, ToolRegistry};
 use blufio_storage::SqliteStorage;
@@ -42,12 +39,23 @@ const MAX_TOOL_ITERATIONS: usize = 10;
 /// Creates a CLI session, prompts for user input, and streams LLM responses
 /// directly to stdout. Uses context engine for prompt assembly and records
 /// costs for every call.
-pub async fn run_shell(config: BlufioConfig) -> Result<(), BlufioError> {
+pub async fn run_shell(mut config: BlufioConfig) -> Result<(), BlufioError> {
     // Initialize storage.
     let storage = SqliteStorage::new(config.storage.clone());
     storage.initialize().await?;
     let storage: Arc<dyn StorageAdapter + Send + Sync> = Arc::new(storage);
 
+    // Resolve secrets stored via `blufio config set-secret` that the config
+    // leaves unset (precedence: config -> vault -> env).
+    let vault_conn = blufio_storage::open_connection(&config.storage.database_path).await?;
+    match blufio_vault::vault_startup_check(vault_conn, &config.vault).await {
+        Ok(Some(vault)) => {
+            blufio_vault::apply_vault_secrets(&mut config, &vault).await?;
+        }
+        Ok(None) => debug!("no vault found -- secrets resolved from config and env only"),
+        Err(e) => warn!(error = %e, "vault could not be unlocked -- vault secrets unavailable"),
+    }
+
     // Initialize Anthropic provider.
     let provider: Arc<dyn ProviderAdapter + Send + Sync> =
         Arc::new(AnthropicProvider::new(&config).await.inspect_err(|_| {
@@ -69,7 +77,6 @@ pub async fn run_shell(config: BlufioConfig) -> Result<(), BlufioError> {
         ContextEngine::new(&config.agent, &config.context, token_cache).await?;
 
     // Initialize memory system (if enabled).
-    #[cfg(feature = "onnx")]
     let memory_provider: Option<MemoryProvider> = if config.memory.enabled {
         match initialize_memory(&config, &mut context_engine).await {
             Ok((mp, _extractor)) => {
@@ -87,15 +94,8 @@ pub async fn run_shell(config: BlufioConfig) -> Result<(), BlufioError> {
         None
     };
 
-    #[cfg(not(feature = "onnx"))]
-    let memory_provider: Option<MemoryProvider> = {
-        info!("memory system disabled (onnx feature not enabled)");
-        None
-    };
-
     // Initialize tool registry with built-in tools.
-    let mut tool_registry = ToolRegistry::new();
-    blufio_skill::builtin::register_builtins(&mut tool_registry);
+    let tool_registry = blufio_skill::builtin::builtin_registry(&config.skill);
     info!(
         "tool registry initialized with {} built-in tools",
         tool_registry.len()
@@ -266,7 +266,7 @@ async fn handle_shell_message(
         let decision = router.route(input, &recent_refs, budget_util);
 
         if decision.downgraded {
-            let short = ModelRouter::short_model_name(&decision.actual_model);
+            let short = router.short_model_name(&decision.actual_model);
             eprintln!(
                 "{}",
                 format!("(Using {short} -- budget downgrade)").dimmed()
@@ -501,7 +501,9 @@ async fn handle_shell_message(
             for tu in &tool_uses {
                 eprintln!("{}", format!("[tool: {}] executing...", tu.name).dimmed());
                 let output = if let Some(tool) = registry.get(&tu.name) {
-                    match tool.invoke(tu.input.clone()).await {
+                    match blufio_skill::tool::invoke_validated(tool.as_ref(), tu.input.clone())
+                        .await
+                    {
                         Ok(output) => output,
                         Err(e) => blufio_skill::ToolOutput {
                             content: format!("Tool error: {e}"),
@@ -576,30 +578,16 @@ async fn handle_shell_message(
     Ok(())
 }
 
-/// Initializes the memory system: downloads model, creates embedder, store,
-/// retriever, provider, and extractor. Registers the provider with ContextEngine.
+/// Initializes the memory system: creates embedder, store, retriever,
+/// provider, and extractor. Registers the provider with ContextEngine.
 ///
 /// Returns (MemoryProvider, MemoryExtractor) on success.
-#[allow(dead_code)]
 async fn initialize_memory(
     config: &BlufioConfig,
     context_engine: &mut ContextEngine,
 ) -> Result<(MemoryProvider, Arc<MemoryExtractor>), BlufioError> {
-    // Determine data directory (parent of the database path).
-    let db_path = PathBuf::from(&config.storage.database_path);
-    let data_dir = db_path
-        .parent()
-        .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| PathBuf::from("."));
-
-    // Download model on first run.
-    let model_manager = ModelManager::new(data_dir);
-    info!("ensuring embedding model is available...");
-    let model_path = model_manager.ensure_model().await?;
-    info!(path = %model_path.display(), "embedding model ready");
-
-    // Create ONNX embedder.
-    let embedder = Arc::new(OnnxEmbedder::new(&model_path)?);
+    // Remote embedding API when configured, otherwise the local ONNX model.
+    let (embedder, _onnx) = crate::serve::storage::init_embedder(config).await?;
 
     // Register sqlite-vec extension before opening the connection (must be
     // called before any connections so sqlite3_auto_extension takes effect).
@@ -628,6 +616,9 @@ async fn initialize_memory(
         }
     }
 
+    // Refuse an embedder whose vectors don't match the stored ones.
+    let embedder = blufio_memory::embedder::verify_dimensions(embedder, &memory_store).await?;
+
     // Create hybrid retriever.
     let retriever = Arc::new(HybridRetriever::new(
         memory_store.clone(),
@@ -636,15 +627,19 @@ async fn initialize_memory(
     ));
 
     // Create memory provider and register with context engine.
-    let memory_provider = MemoryProvider::new(retriever);
+    let memory_provider =
+        MemoryProvider::new(retriever).with_placement(&config.memory.injection_placement);
     context_engine.add_conditional_provider(Box::new(memory_provider.clone()));
 
     // Create memory extractor.
-    let extractor = Arc::new(MemoryExtractor::new(
-        memory_store,
-        embedder,
-        config.memory.extraction_model.clone(),
-    ));
+    let extractor = Arc::new(
+        MemoryExtractor::new(
+            memory_store,
+            embedder,
+            config.memory.extraction_model.clone(),
+        )
+        .with_dedup(config.memory.dedup_threshold, &config.memory.dedup_strategy),
+    );
 
     info!("memory system initialized");
     Ok((memory_provider, extractor))
@@ -217,6 +217,7 @@ async fn test_delegation_router_delegates_to_specialist() {
     let storage_config = StorageConfig {
         database_path: db_path_str.clone(),
         wal_mode: true,
+        ..Default::default()
     };
     let storage = SqliteStorage::new(storage_config);
     storage.initialize().await.unwrap();
@@ -234,6 +235,7 @@ async fn test_delegation_router_delegates_to_specialist() {
         daily_budget_usd: None,
         monthly_budget_usd: None,
         track_tokens: true,
+        ..Default::default()
     };
     let budget_tracker = Arc::new(tokio::sync::Mutex::new(BudgetTracker::new(&cost_config)));
 
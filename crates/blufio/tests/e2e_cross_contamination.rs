@@ -47,6 +47,9 @@ fn build_test_router() -> Router {
         event_bus: None,
         degradation_manager: None,
         circuit_breaker_registry: None,
+        identity_limiter: None,
+        inbound_send_timeout: std::time::Duration::from_secs(5),
+        ws_keepalive: blufio_gateway::ws::WsKeepalive::default(),
     };
 
     // Build routes matching the gateway server setup (without auth middleware for testing).
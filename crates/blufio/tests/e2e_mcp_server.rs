@@ -26,7 +26,7 @@ fn create_test_handler() -> BlufioMcpHandler {
 /// Creates a test handler with resources (storage) configured.
 fn create_test_handler_with_resources() -> BlufioMcpHandler {
     use async_trait::async_trait;
-    use blufio_core::types::{Message, QueueEntry, Session};
+    use blufio_core::types::{Message, QueueEntry, Session, ToolLoopState, ToolUseData};
     use blufio_core::{BlufioError, StorageAdapter};
 
     /// Minimal mock storage adapter for resource tests.
@@ -73,6 +73,13 @@ fn create_test_handler_with_resources() -> BlufioMcpHandler {
         async fn update_session_state(&self, _id: &str, _state: &str) -> Result<(), BlufioError> {
             Ok(())
         }
+        async fn update_session_metadata(
+            &self,
+            _id: &str,
+            _metadata: Option<&str>,
+        ) -> Result<(), BlufioError> {
+            Ok(())
+        }
         async fn insert_message(&self, _message: &Message) -> Result<(), BlufioError> {
             Ok(())
         }
@@ -102,6 +109,20 @@ fn create_test_handler_with_resources() -> BlufioMcpHandler {
         async fn fail(&self, _id: i64) -> Result<(), BlufioError> {
             Ok(())
         }
+        async fn save_tool_loop_state(
+            &self,
+            _session_id: &str,
+            _iteration: u32,
+            _pending_tool_uses: &[ToolUseData],
+        ) -> Result<(), BlufioError> {
+            Ok(())
+        }
+        async fn clear_tool_loop_state(&self, _session_id: &str) -> Result<(), BlufioError> {
+            Ok(())
+        }
+        async fn list_tool_loop_states(&self) -> Result<Vec<ToolLoopState>, BlufioError> {
+            Ok(vec![])
+        }
         async fn get_entity_classification(
             &self,
             _entity_type: &str,
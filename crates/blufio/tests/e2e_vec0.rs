@@ -51,7 +51,9 @@ async fn setup_test_db() -> Connection {
                 classification TEXT NOT NULL DEFAULT 'internal',
                 created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
                 updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
-                deleted_at TEXT
+                deleted_at TEXT,
+                last_accessed_at TEXT,
+                access_count INTEGER NOT NULL DEFAULT 0
             );
 
             CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
@@ -123,7 +125,9 @@ async fn setup_test_db_no_vec0() -> Connection {
                 classification TEXT NOT NULL DEFAULT 'internal',
                 created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
                 updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
-                deleted_at TEXT
+                deleted_at TEXT,
+                last_accessed_at TEXT,
+                access_count INTEGER NOT NULL DEFAULT 0
             );
 
             CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(